@@ -0,0 +1,243 @@
+use atrium_api::app::bsky::feed::post::RecordData as PostRecord;
+use atrium_api::com::atproto::{repo, server};
+use atrium_api::record::KnownRecord;
+use atrium_api::types::BlobRef;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::data::{AppViewSearchResponse, BskyNotificationsResponse};
+use crate::error::{get_json, send, FetchError};
+use crate::settings::Settings;
+
+const SERVICE: &str = "Bluesky";
+/// Image uploads can be slow on a poor connection
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Has the PDS forward app.bsky.* reads to the Bluesky AppView
+const APPVIEW_PROXY: &str = "did:web:api.bsky.app#bsky_appview";
+
+/// Largest image the PDS accepts as a blob
+pub const MAX_BLOB_BYTES: usize = 1_000_000;
+
+thread_local! {
+    /// App settings, registered at startup so posting reads the current credentials
+    static SETTINGS: RefCell<Option<Rc<RefCell<Settings>>>> = const { RefCell::new(None) };
+    /// Signed-in session, created on first use
+    static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+}
+
+/// Make the app settings available for signing in
+pub fn register_settings(settings: Rc<RefCell<Settings>>) {
+    SETTINGS.with(|current| *current.borrow_mut() = Some(settings));
+}
+
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub did: String,
+    pub handle: String,
+    access_jwt: String,
+    pds_url: String,
+}
+
+struct Credentials {
+    identifier: String,
+    app_password: String,
+    pds_url: String,
+}
+
+fn credentials() -> Option<Credentials> {
+    SETTINGS.with(|current| {
+        let current = current.borrow();
+        let settings = current.as_ref()?.borrow();
+        if settings.bluesky_identifier.is_empty() || settings.bluesky_app_password.is_empty() {
+            return None;
+        }
+        Some(Credentials {
+            identifier: settings.bluesky_identifier.clone(),
+            app_password: settings.bluesky_app_password.clone(),
+            pds_url: settings.bluesky_pds_url.trim_end_matches('/').to_string(),
+        })
+    })
+}
+
+/// Whether a handle and app password are set in preferences
+pub fn is_configured() -> bool {
+    credentials().is_some()
+}
+
+/// Forget the current session, e.g. after the credentials change
+pub fn sign_out() {
+    SESSION.with(|session| session.borrow_mut().take());
+}
+
+async fn create_session(credentials: &Credentials) -> Result<Session, FetchError> {
+    let url = format!("{}/xrpc/com.atproto.server.createSession", credentials.pds_url);
+    let body = serde_json::json!({
+        "identifier": credentials.identifier,
+        "password": credentials.app_password,
+    });
+
+    let output: server::create_session::OutputData = get_json(SERVICE, crate::http::client().post(&url).timeout(TIMEOUT).json(&body)).await?;
+    Ok(Session {
+        did: output.did.to_string(),
+        handle: output.handle.to_string(),
+        access_jwt: output.access_jwt,
+        pds_url: credentials.pds_url.clone(),
+    })
+}
+
+/// The signed-in session, signing in with the app password if needed
+pub async fn session() -> Result<Session, FetchError> {
+    if let Some(session) = SESSION.with(|session| session.borrow().clone()) {
+        return Ok(session);
+    }
+
+    let credentials = credentials().ok_or(FetchError::Auth { service: SERVICE })?;
+    let session = create_session(&credentials).await?;
+    SESSION.with(|current| *current.borrow_mut() = Some(session.clone()));
+    Ok(session)
+}
+
+/// Make an authenticated request, signing in again once if the access token
+/// has expired (the PDS answers that with 400 ExpiredToken or 401)
+async fn send_with_session(
+    request: impl Fn(&reqwest::Client, &Session) -> reqwest::RequestBuilder,
+) -> Result<reqwest::Response, FetchError> {
+    let client = crate::http::client();
+    let session = session().await?;
+
+    match send(SERVICE, request(&client, &session).timeout(TIMEOUT).bearer_auth(&session.access_jwt)).await {
+        Err(FetchError::Auth { .. }) | Err(FetchError::Upstream { status: 400, .. }) => {
+            sign_out();
+            let session = self::session().await?;
+            send(SERVICE, request(&client, &session).timeout(TIMEOUT).bearer_auth(&session.access_jwt)).await
+        }
+        result => result,
+    }
+}
+
+/// Make an authenticated request and decode its JSON response
+async fn with_session<T: serde::de::DeserializeOwned>(
+    request: impl Fn(&reqwest::Client, &Session) -> reqwest::RequestBuilder,
+) -> Result<T, FetchError> {
+    send_with_session(request)
+        .await?
+        .json::<T>()
+        .await
+        .map_err(|e| FetchError::from_reqwest(SERVICE, e))
+}
+
+/// Upload an image for use in a post, returning the blob reference to embed
+pub async fn upload_blob(bytes: Vec<u8>, mime_type: &str) -> Result<BlobRef, FetchError> {
+    let output: repo::upload_blob::OutputData = with_session(|client, session| {
+        client
+            .post(format!("{}/xrpc/com.atproto.repo.uploadBlob", session.pds_url))
+            .header(reqwest::header::CONTENT_TYPE, mime_type)
+            .body(bytes.clone())
+    })
+    .await?;
+    Ok(output.blob)
+}
+
+/// Write a record to the signed-in account's repo, returning its at:// URI
+async fn create_record(collection: &str, record: impl serde::Serialize) -> Result<String, FetchError> {
+    let output: repo::create_record::OutputData = with_session(|client, session| {
+        client
+            .post(format!("{}/xrpc/com.atproto.repo.createRecord", session.pds_url))
+            .json(&serde_json::json!({
+                "repo": session.did,
+                "collection": collection,
+                "record": record,
+            }))
+    })
+    .await?;
+    Ok(output.uri)
+}
+
+/// Publish a post to the signed-in account, returning its at:// URI
+pub async fn create_post(post: PostRecord) -> Result<String, FetchError> {
+    create_record("app.bsky.feed.post", KnownRecord::from(post)).await
+}
+
+/// Ways to react to someone else's post
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interaction {
+    Like,
+    Repost,
+}
+
+impl Interaction {
+    fn collection(self) -> &'static str {
+        match self {
+            Interaction::Like => "app.bsky.feed.like",
+            Interaction::Repost => "app.bsky.feed.repost",
+        }
+    }
+}
+
+/// Like or repost a post, returning the new record's URI so it can be undone
+pub async fn interact(interaction: Interaction, uri: &str, cid: &str) -> Result<String, FetchError> {
+    let record = serde_json::json!({
+        "$type": interaction.collection(),
+        "subject": { "uri": uri, "cid": cid },
+        "createdAt": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+    });
+    create_record(interaction.collection(), record).await
+}
+
+/// Delete a record the account created, e.g. to undo a like
+pub async fn delete_record(record_uri: &str) -> Result<(), FetchError> {
+    // at://did/collection/rkey
+    let mut parts = record_uri.trim_start_matches("at://").splitn(3, '/');
+    let (Some(_), Some(collection), Some(rkey)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(FetchError::InvalidRequest { service: SERVICE, message: format!("Not a record URI: {}", record_uri) });
+    };
+
+    send_with_session(|client, session| {
+        client
+            .post(format!("{}/xrpc/com.atproto.repo.deleteRecord", session.pds_url))
+            .json(&serde_json::json!({
+                "repo": session.did,
+                "collection": collection,
+                "rkey": rkey,
+            }))
+    })
+    .await?;
+    Ok(())
+}
+
+/// The signed-in account's most recent notifications, newest first
+pub async fn list_notifications(limit: u8) -> Result<BskyNotificationsResponse, FetchError> {
+    with_session(|client, session| {
+        client
+            .get(format!("{}/xrpc/app.bsky.notification.listNotifications", session.pds_url))
+            .query(&[("limit", limit.to_string())])
+            .header("atproto-proxy", APPVIEW_PROXY)
+    })
+    .await
+}
+
+/// Mark notifications up to now as read
+pub async fn update_seen() -> Result<(), FetchError> {
+    // Answers with an empty body, so there's nothing to decode
+    send_with_session(|client, session| {
+        client
+            .post(format!("{}/xrpc/app.bsky.notification.updateSeen", session.pds_url))
+            .header("atproto-proxy", APPVIEW_PROXY)
+            .json(&serde_json::json!({ "seenAt": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true) }))
+    })
+    .await?;
+    Ok(())
+}
+
+/// Search posts through the signed-in account, which the AppView answers
+/// more reliably than anonymous searches
+pub async fn search_posts(query: &str, limit: u8) -> Result<AppViewSearchResponse, FetchError> {
+    with_session(|client, session| {
+        client
+            .get(format!("{}/xrpc/app.bsky.feed.searchPosts", session.pds_url))
+            .query(&[("q", query), ("sort", "latest"), ("limit", &limit.to_string())])
+            .header("atproto-proxy", APPVIEW_PROXY)
+    })
+    .await
+}
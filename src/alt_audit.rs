@@ -0,0 +1,168 @@
+use gtk::prelude::*;
+use gtk::{glib, Label, Orientation};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use crate::data::FirehosePost;
+use crate::split_rules::image_alt_texts;
+
+/// Minutes of image counts kept for the audit chart
+const WINDOW_MINUTES: usize = 60;
+/// How often the chart and its caption catch up with the counts
+const REFRESH_SECONDS: u32 = 5;
+
+const CHART_HEIGHT: i32 = 40;
+
+/// Images seen in one minute of the stream, by whether they had alt text
+#[derive(Debug, Clone, Copy, Default)]
+struct Minute {
+    /// Minutes since the Unix epoch
+    at: i64,
+    described: u32,
+    missing: u32,
+}
+
+thread_local! {
+    /// Oldest first, at most `WINDOW_MINUTES` long; minutes without images are left out
+    static MINUTES: RefCell<VecDeque<Minute>> = const { RefCell::new(VecDeque::new()) };
+}
+
+fn current_minute() -> i64 {
+    chrono::Utc::now().timestamp() / 60
+}
+
+/// Count a post's images toward the audit, whatever the splits are filtering
+pub fn record(post: &FirehosePost) {
+    let Some(alt_texts) = image_alt_texts(post) else {
+        return;
+    };
+    let described = alt_texts.iter().filter(|alt| !alt.trim().is_empty()).count() as u32;
+    let missing = alt_texts.len() as u32 - described;
+
+    let now = current_minute();
+    MINUTES.with(|minutes| {
+        let mut minutes = minutes.borrow_mut();
+        if minutes.back().is_none_or(|minute| minute.at != now) {
+            minutes.push_back(Minute { at: now, ..Minute::default() });
+        }
+        if let Some(minute) = minutes.back_mut() {
+            minute.described += described;
+            minute.missing += missing;
+        }
+        while minutes.front().is_some_and(|minute| now - minute.at >= WINDOW_MINUTES as i64) {
+            minutes.pop_front();
+        }
+    });
+}
+
+/// Counts for each of the last `WINDOW_MINUTES` minutes, oldest first, with
+/// zeroes for minutes without images
+fn window() -> Vec<Minute> {
+    let now = current_minute();
+    MINUTES.with(|minutes| {
+        let minutes = minutes.borrow();
+        (0..WINDOW_MINUTES as i64)
+            .rev()
+            .map(|ago| {
+                let at = now - ago;
+                minutes
+                    .iter()
+                    .find(|minute| minute.at == at)
+                    .copied()
+                    .unwrap_or(Minute { at, ..Minute::default() })
+            })
+            .collect()
+    })
+}
+
+/// e.g. "Past hour: 1204 images, 38% with alt text"
+fn describe(window: &[Minute]) -> String {
+    let described: u32 = window.iter().map(|minute| minute.described).sum();
+    let missing: u32 = window.iter().map(|minute| minute.missing).sum();
+    let total = described + missing;
+    if total == 0 {
+        return "No images seen yet".to_string();
+    }
+    format!(
+        "Past hour: {} images, {:.0}% with alt text",
+        total,
+        described as f64 / total as f64 * 100.0
+    )
+}
+
+/// Stacked bar per minute: described images at the bottom, the rest above
+fn draw(cr: &gtk::cairo::Context, width: f64, height: f64, window: &[Minute]) {
+    let max = window
+        .iter()
+        .map(|minute| minute.described + minute.missing)
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+    let bar_width = width / window.len().max(1) as f64;
+
+    for (i, minute) in window.iter().enumerate() {
+        let x = i as f64 * bar_width;
+        let described_height = minute.described as f64 / max * height;
+        let missing_height = minute.missing as f64 / max * height;
+
+        cr.set_source_rgba(0.20, 0.82, 0.48, 0.9);
+        cr.rectangle(x, height - described_height, (bar_width - 1.0).max(1.0), described_height);
+        let _ = cr.fill();
+
+        cr.set_source_rgba(0.88, 0.11, 0.14, 0.7);
+        cr.rectangle(
+            x,
+            height - described_height - missing_height,
+            (bar_width - 1.0).max(1.0),
+            missing_height,
+        );
+        let _ = cr.fill();
+    }
+}
+
+/// Caption and per-minute chart of how many images in the stream have alt
+/// text, shown in splits that filter by it
+pub fn create_audit_chart() -> gtk::Box {
+    let container = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+    container.add_css_class("alt-audit");
+
+    let caption = Label::builder()
+        .xalign(0.0)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .tooltip_text("Images across the whole stream, green with alt text and red without")
+        .build();
+    caption.add_css_class("caption");
+    caption.add_css_class("dim-label");
+    container.append(&caption);
+
+    let drawing_area = gtk::DrawingArea::builder()
+        .content_height(CHART_HEIGHT)
+        .hexpand(true)
+        .build();
+    drawing_area.set_draw_func(|_, cr, width, height| {
+        draw(cr, width as f64, height as f64, &window());
+    });
+    container.append(&drawing_area);
+
+    caption.set_label(&describe(&window()));
+    let caption_for_tick = caption.downgrade();
+    let area_for_tick = drawing_area.downgrade();
+    glib::timeout_add_seconds_local(REFRESH_SECONDS, move || {
+        let (Some(caption), Some(drawing_area)) = (caption_for_tick.upgrade(), area_for_tick.upgrade()) else {
+            return glib::ControlFlow::Break;
+        };
+        // Closed splits stop refreshing; hidden ones skip the work
+        if caption.is_mapped() {
+            caption.set_label(&describe(&window()));
+            drawing_area.queue_draw();
+        }
+        glib::ControlFlow::Continue
+    });
+
+    container
+}
@@ -0,0 +1,88 @@
+use gtk::glib;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::error::{get_json, FetchError};
+use crate::settings::Settings;
+
+const SERVICE: &str = "Captioning server";
+/// Vision models are slow on CPU, so allow far longer than a page fetch
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Asked of the model for every image; short enough to fit Bluesky's alt text habits
+const PROMPT: &str = "Write alt text for this image for someone who can't see it. \
+    Describe what matters in one or two plain sentences, including any visible text. \
+    Reply with the alt text only.";
+
+thread_local! {
+    /// App settings, registered at startup so the composer reads the current captioning setup
+    static SETTINGS: RefCell<Option<Rc<RefCell<Settings>>>> = const { RefCell::new(None) };
+}
+
+/// Make the app settings available to the composer
+pub fn register_settings(settings: Rc<RefCell<Settings>>) {
+    SETTINGS.with(|current| *current.borrow_mut() = Some(settings));
+}
+
+fn with_settings<T>(read: impl FnOnce(&Settings) -> T) -> Option<T> {
+    SETTINGS.with(|current| current.borrow().as_ref().map(|settings| read(&settings.borrow())))
+}
+
+/// Whether posting is held back until every attached image has alt text
+pub fn is_required() -> bool {
+    with_settings(|settings| settings.require_alt_text).unwrap_or(false)
+}
+
+/// Captioning server and model, when one is set up in preferences
+fn captioner() -> Option<(String, String)> {
+    with_settings(|settings| {
+        let server_url = settings.caption_server_url.trim().trim_end_matches('/').to_string();
+        let model = settings.caption_model.trim().to_string();
+        (!server_url.is_empty() && !model.is_empty()).then_some((server_url, model))
+    })
+    .flatten()
+}
+
+/// Whether alt text can be suggested for attached images
+pub fn can_suggest() -> bool {
+    captioner().is_some()
+}
+
+#[derive(Serialize)]
+struct GenerateRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+    images: Vec<String>,
+    stream: bool,
+}
+
+#[derive(Deserialize)]
+struct GenerateResponse {
+    response: String,
+}
+
+/// Ask the captioning model to describe an image. The server speaks Ollama's
+/// generate API, so it can be a local Ollama or a remote server behind the same API
+pub async fn suggest(image: &[u8]) -> Result<String, FetchError> {
+    let Some((server_url, model)) = captioner() else {
+        return Err(FetchError::Empty { service: SERVICE });
+    };
+
+    let request = crate::http::client()
+        .post(format!("{}/api/generate", server_url))
+        .timeout(TIMEOUT)
+        .json(&GenerateRequest {
+            model: &model,
+            prompt: PROMPT,
+            images: vec![glib::base64_encode(image).to_string()],
+            stream: false,
+        });
+    let data: GenerateResponse = get_json(SERVICE, request).await?;
+
+    let caption = data.response.trim().trim_matches('"').to_string();
+    if caption.is_empty() {
+        return Err(FetchError::Empty { service: SERVICE });
+    }
+    Ok(caption)
+}
@@ -0,0 +1,138 @@
+use gtk::glib;
+use std::cell::RefCell;
+use std::time::Duration;
+
+use crate::account;
+use crate::data::{
+    AppViewAuthorFeed, AppViewPost, AppViewPostsResponse, AppViewProfile, AppViewSearchResponse, APPVIEW_API_URL,
+};
+use crate::error::{get_json, FetchError};
+
+const SERVICE: &str = "Bluesky";
+
+/// getPosts accepts at most this many URIs per request
+const MAX_URIS_PER_REQUEST: usize = 25;
+/// How long lookups are collected before they're sent together
+const BATCH_DELAY: Duration = Duration::from_millis(400);
+
+type PendingLookup = (String, flume::Sender<Result<AppViewPost, FetchError>>);
+
+thread_local! {
+    /// Lookups waiting for the next batch
+    static PENDING: RefCell<Vec<PendingLookup>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Fetch hydrated posts by at:// URI from the public Bluesky AppView
+pub async fn fetch_posts(uris: &[String]) -> Result<Vec<AppViewPost>, FetchError> {
+    let client = crate::http::client();
+
+    let query: Vec<(&str, &str)> = uris.iter().map(|uri| ("uris", uri.as_str())).collect();
+    let url = format!("{}/app.bsky.feed.getPosts", APPVIEW_API_URL);
+
+    let data: AppViewPostsResponse = get_json(SERVICE, client.get(&url).query(&query)).await?;
+    Ok(data.posts)
+}
+
+/// Fetch a single post by at:// URI
+pub async fn fetch_post(uri: &str) -> Result<AppViewPost, FetchError> {
+    fetch_posts(&[uri.to_string()])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(FetchError::Empty { service: SERVICE })
+}
+
+/// Latest posts tagged with a hashtag, hydrated with their authors and counts.
+/// Searches through the signed-in account when there is one
+pub async fn search_hashtag(tag: &str, limit: u8) -> Result<Vec<AppViewPost>, FetchError> {
+    let query = format!("#{}", tag);
+    if account::is_configured() {
+        return Ok(account::search_posts(&query, limit).await?.posts);
+    }
+
+    let client = crate::http::client();
+    let url = format!("{}/app.bsky.feed.searchPosts", APPVIEW_API_URL);
+    let request = client
+        .get(&url)
+        .query(&[("q", query.as_str()), ("sort", "latest"), ("limit", &limit.to_string())]);
+    let data: AppViewSearchResponse = get_json(SERVICE, request).await?;
+    Ok(data.posts)
+}
+
+/// An account's profile: names, bio and follower counts
+pub async fn fetch_profile(did: &str) -> Result<AppViewProfile, FetchError> {
+    let client = crate::http::client();
+    let url = format!("{}/app.bsky.actor.getProfile", APPVIEW_API_URL);
+    get_json(SERVICE, client.get(&url).query(&[("actor", did)])).await
+}
+
+/// An account's latest posts and reposts, leaving out its replies
+pub async fn fetch_author_feed(did: &str, limit: u8) -> Result<Vec<AppViewPost>, FetchError> {
+    let client = crate::http::client();
+    let url = format!("{}/app.bsky.feed.getAuthorFeed", APPVIEW_API_URL);
+    let request = client.get(&url).query(&[
+        ("actor", did),
+        ("filter", "posts_no_replies"),
+        ("limit", &limit.to_string()),
+    ]);
+    let data: AppViewAuthorFeed = get_json(SERVICE, request).await?;
+    Ok(data.feed.into_iter().map(|item| item.post).collect())
+}
+
+/// Fetch a single post, sharing a request with other lookups made around the
+/// same time; used for quoted posts, which can arrive many per second
+pub async fn fetch_post_batched(uri: &str) -> Result<AppViewPost, FetchError> {
+    let (tx, rx) = flume::bounded(1);
+    let first = PENDING.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        pending.push((uri.to_string(), tx));
+        pending.len() == 1
+    });
+    if first {
+        glib::timeout_add_local_once(BATCH_DELAY, || {
+            glib::spawn_future_local(send_pending());
+        });
+    }
+
+    rx.recv_async()
+        .await
+        .unwrap_or(Err(FetchError::Empty { service: SERVICE }))
+}
+
+async fn send_pending() {
+    let lookups = PENDING.with(|pending| std::mem::take(&mut *pending.borrow_mut()));
+
+    for batch in lookups.chunks(MAX_URIS_PER_REQUEST) {
+        let mut uris: Vec<String> = batch.iter().map(|(uri, _)| uri.clone()).collect();
+        uris.sort();
+        uris.dedup();
+
+        match fetch_posts(&uris).await {
+            Ok(posts) => {
+                // Posts that are deleted or hidden from us are simply missing
+                for (uri, tx) in batch {
+                    let post = posts.iter().find(|post| &post.uri == uri).cloned();
+                    let _ = tx.send(post.ok_or(FetchError::Empty { service: SERVICE }));
+                }
+            }
+            Err(e) => {
+                for (_, tx) in batch {
+                    let _ = tx.send(Err(e.clone()));
+                }
+            }
+        }
+    }
+}
+
+/// Convert an at://did/app.bsky.feed.post/rkey URI into a bsky.app web link
+pub fn post_web_url(uri: &str) -> Option<String> {
+    let path = uri.strip_prefix("at://")?;
+    let mut parts = path.split('/');
+    let did = parts.next()?;
+    let collection = parts.next()?;
+    let rkey = parts.next()?;
+    if collection != "app.bsky.feed.post" {
+        return None;
+    }
+    Some(format!("https://bsky.app/profile/{}/post/{}", did, rkey))
+}
@@ -0,0 +1,135 @@
+use gtk::prelude::*;
+use gtk::{gdk, glib, ListBox};
+
+use crate::coordinates::get_country_coordinates;
+use crate::data::GdeltArticle;
+
+/// Zoom the map flies to when showing an article's country
+const COUNTRY_ZOOM: f64 = 4.0;
+const FLY_DURATION_MS: u32 = 800;
+
+/// The article shown in a results row; rows carry the lead article's URL as
+/// their name, and rows that aren't articles match nothing
+fn article_for(row: &gtk::ListBoxRow) -> Option<GdeltArticle> {
+    let url = row.widget_name();
+    crate::global_affairs::current_articles()
+        .into_iter()
+        .find(|article| article.url == url.as_str())
+}
+
+/// Rows of the list that are articles and currently shown, in order
+fn article_rows(list: &ListBox) -> Vec<gtk::ListBoxRow> {
+    (0..)
+        .map_while(|index| list.row_at_index(index))
+        .filter(|row| row.is_visible() && row.is_child_visible() && article_for(row).is_some())
+        .collect()
+}
+
+/// Select the article `delta` rows away from the selected one, or the first
+fn step(list: &ListBox, delta: i32) {
+    let rows = article_rows(list);
+    if rows.is_empty() {
+        return;
+    }
+
+    let selected = list.selected_row();
+    let next = match selected.and_then(|selected| rows.iter().position(|row| *row == selected)) {
+        Some(index) => (index as i32 + delta).clamp(0, rows.len() as i32 - 1) as usize,
+        None => 0,
+    };
+    list.select_row(Some(&rows[next]));
+    // Focus scrolls the row into view
+    rows[next].grab_focus();
+}
+
+/// First descendant of `widget` with a CSS class, depth first
+fn find_descendant(widget: &gtk::Widget, css_class: &str) -> Option<gtk::Widget> {
+    let mut child = widget.first_child();
+    while let Some(current) = child {
+        if current.has_css_class(css_class) {
+            return Some(current);
+        }
+        if let Some(found) = find_descendant(&current, css_class) {
+            return Some(found);
+        }
+        child = current.next_sibling();
+    }
+    None
+}
+
+fn open_selected(list: &ListBox) {
+    let Some(row) = list.selected_row() else {
+        return;
+    };
+    let Some(article) = article_for(&row) else {
+        return;
+    };
+
+    crate::read_articles::mark_read(&article.url);
+    if let Some(card) = find_descendant(row.upcast_ref(), "news-article-card") {
+        card.add_css_class("article-read");
+    }
+    crate::links::open(&article.url);
+}
+
+/// Flip the card's own star, so the button and the store stay in step
+fn toggle_bookmark(list: &ListBox) {
+    let Some(row) = list.selected_row() else {
+        return;
+    };
+    let Some(star) = find_descendant(row.upcast_ref(), "bookmark-star").and_downcast::<gtk::ToggleButton>() else {
+        return;
+    };
+    star.set_active(!star.is_active());
+    crate::toasts::show(if star.is_active() { "Bookmarked" } else { "Bookmark removed" });
+}
+
+/// Fly the map to the selected article's source country and open its marker
+fn show_country(list: &ListBox, map_view: Option<&libshumate::Map>) {
+    let Some(article) = list.selected_row().as_ref().and_then(article_for) else {
+        return;
+    };
+    let country = article.sourcecountry;
+    let Some((lat, lon)) = get_country_coordinates(&country) else {
+        crate::toasts::show("No map location for this article's country");
+        return;
+    };
+
+    if let Some(map_view) = map_view {
+        map_view.go_to_full_with_duration(lat, lon, COUNTRY_ZOOM, FLY_DURATION_MS);
+    }
+    glib::timeout_add_local_once(
+        std::time::Duration::from_millis(FLY_DURATION_MS as u64 + 100),
+        move || {
+            if !crate::global_affairs::open_country_marker(&country) {
+                tracing::info!("No news marker for {}", country);
+            }
+        },
+    );
+}
+
+/// Make the results list navigable from the keyboard: j/k or the arrow keys
+/// move between articles, Enter opens one, b bookmarks it and m shows its
+/// country on the map
+pub fn attach(list: &ListBox, map_view: Option<libshumate::Map>) {
+    list.set_selection_mode(gtk::SelectionMode::Single);
+    list.add_css_class("article-nav");
+
+    let keys = gtk::EventControllerKey::new();
+    let list_for_keys = list.clone();
+    keys.connect_key_pressed(move |_, key, _, modifiers| {
+        if modifiers.intersects(gdk::ModifierType::CONTROL_MASK | gdk::ModifierType::ALT_MASK | gdk::ModifierType::SUPER_MASK) {
+            return glib::Propagation::Proceed;
+        }
+        match key {
+            gdk::Key::j | gdk::Key::Down => step(&list_for_keys, 1),
+            gdk::Key::k | gdk::Key::Up => step(&list_for_keys, -1),
+            gdk::Key::Return | gdk::Key::KP_Enter => open_selected(&list_for_keys),
+            gdk::Key::b => toggle_bookmark(&list_for_keys),
+            gdk::Key::m => show_country(&list_for_keys, map_view.as_ref()),
+            _ => return glib::Propagation::Proceed,
+        }
+        glib::Propagation::Stop
+    });
+    list.add_controller(keys);
+}
@@ -0,0 +1,389 @@
+use gtk::prelude::*;
+use gtk::{Label, ListBox, Orientation, ScrolledWindow, SearchEntry};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+use crate::json_store::JsonStore;
+
+const FILE: JsonStore = JsonStore::new("bookmarks.json", "bookmarks");
+
+/// What a bookmark points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BookmarkKind {
+    Article,
+    Post,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub kind: BookmarkKind,
+    /// Article URL or bsky.app post link; identifies the bookmark
+    pub url: String,
+    /// Article title, or the post text
+    pub title: String,
+    /// Domain for articles, DID for posts
+    pub source: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// RFC 3339 timestamp
+    pub saved_at: String,
+}
+
+impl Bookmark {
+    pub fn new(kind: BookmarkKind, url: &str, title: &str, source: &str) -> Self {
+        Self {
+            kind,
+            url: crate::links::canonicalize(url),
+            title: title.to_string(),
+            source: source.to_string(),
+            tags: Vec::new(),
+            saved_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Case-insensitive match against title, source and tags; `#tag` terms only match tags
+    fn matches(&self, query: &str) -> bool {
+        query.split_whitespace().all(|term| {
+            let term = term.to_lowercase();
+            if let Some(tag) = term.strip_prefix('#') {
+                self.tags.iter().any(|t| t.to_lowercase() == tag)
+            } else {
+                self.title.to_lowercase().contains(&term)
+                    || self.source.to_lowercase().contains(&term)
+                    || self.tags.iter().any(|t| t.to_lowercase().contains(&term))
+            }
+        })
+    }
+}
+
+thread_local! {
+    /// Bookmarks, newest first; loaded from disk on first use
+    static STORE: RefCell<Option<Vec<Bookmark>>> = const { RefCell::new(None) };
+    /// Called whenever bookmarks change so open views can refresh
+    static LISTENERS: RefCell<Vec<Box<dyn Fn()>>> = const { RefCell::new(Vec::new()) };
+}
+
+fn load() -> Vec<Bookmark> {
+    FILE.load()
+}
+
+fn save(bookmarks: &[Bookmark]) {
+    FILE.save(bookmarks);
+}
+
+/// Run `f` on the bookmark list, then persist and notify listeners
+fn modify(f: impl FnOnce(&mut Vec<Bookmark>)) {
+    STORE.with(|store| {
+        let mut store = store.borrow_mut();
+        let bookmarks = store.get_or_insert_with(load);
+        f(bookmarks);
+        save(bookmarks);
+    });
+
+    LISTENERS.with(|listeners| {
+        for listener in listeners.borrow().iter() {
+            listener();
+        }
+    });
+}
+
+/// All bookmarks, newest first
+pub fn all() -> Vec<Bookmark> {
+    STORE.with(|store| store.borrow_mut().get_or_insert_with(load).clone())
+}
+
+pub fn is_bookmarked(url: &str) -> bool {
+    STORE.with(|store| {
+        store
+            .borrow_mut()
+            .get_or_insert_with(load)
+            .iter()
+            .any(|bookmark| bookmark.url == url)
+    })
+}
+
+pub fn add(bookmark: Bookmark) {
+    modify(|bookmarks| {
+        if !bookmarks.iter().any(|b| b.url == bookmark.url) {
+            bookmarks.insert(0, bookmark);
+        }
+    });
+}
+
+pub fn remove(url: &str) {
+    modify(|bookmarks| bookmarks.retain(|bookmark| bookmark.url != url));
+}
+
+fn set_tags(url: &str, tags: Vec<String>) {
+    modify(|bookmarks| {
+        if let Some(bookmark) = bookmarks.iter_mut().find(|b| b.url == url) {
+            bookmark.tags = tags;
+        }
+    });
+}
+
+fn connect_changed(listener: impl Fn() + 'static) {
+    LISTENERS.with(|listeners| listeners.borrow_mut().push(Box::new(listener)));
+}
+
+/// Star toggle that adds or removes the bookmark, kept in sync with the store
+pub fn create_star_button(bookmark: Bookmark) -> gtk::ToggleButton {
+    let url = bookmark.url.clone();
+    let saved = is_bookmarked(&url);
+    let button = gtk::ToggleButton::builder()
+        .icon_name(if saved { "starred-symbolic" } else { "non-starred-symbolic" })
+        .tooltip_text(if saved { "Remove bookmark" } else { "Bookmark" })
+        .active(saved)
+        .valign(gtk::Align::Center)
+        .build();
+    button.add_css_class("flat");
+    button.add_css_class("bookmark-star");
+
+    button.connect_toggled(move |button| {
+        let active = button.is_active();
+        button.set_icon_name(if active { "starred-symbolic" } else { "non-starred-symbolic" });
+        button.set_tooltip_text(Some(if active { "Remove bookmark" } else { "Bookmark" }));
+
+        // Skip when the toggle just mirrors the store (e.g. removed from the bookmarks page)
+        if active != is_bookmarked(&bookmark.url) {
+            if active {
+                add(bookmark.clone());
+            } else {
+                remove(&bookmark.url);
+            }
+        }
+    });
+
+    // Pick up changes made elsewhere (e.g. removal from the bookmarks page) when shown again
+    button.connect_map(move |button| {
+        let saved = is_bookmarked(&url);
+        if button.is_active() != saved {
+            button.set_active(saved);
+        }
+    });
+
+    button
+}
+
+fn parse_tags(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(|tag| tag.trim().trim_start_matches('#').to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+fn create_bookmark_row(bookmark: &Bookmark) -> gtk::Box {
+    let row = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .margin_top(4)
+        .margin_bottom(4)
+        .margin_start(6)
+        .margin_end(6)
+        .build();
+    row.add_css_class("news-article-card");
+
+    let content_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .margin_top(8)
+        .margin_bottom(8)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    // Title opens the bookmarked item
+    let title_label = Label::builder()
+        .label(&bookmark.title)
+        .wrap(true)
+        .wrap_mode(gtk::pango::WrapMode::WordChar)
+        .xalign(0.0)
+        .lines(3)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build();
+    title_label.add_css_class("article-title");
+    title_label.add_css_class("activatable");
+
+    let gesture = gtk::GestureClick::new();
+    let url = bookmark.url.clone();
+    gesture.connect_released(move |_, _, _, _| {
+        crate::links::open(&url);
+    });
+    title_label.add_controller(gesture);
+    content_box.append(&title_label);
+
+    // Kind, source and date badges with a remove button
+    let badges_box = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(4)
+        .build();
+
+    let kind_badge = Label::builder()
+        .label(match bookmark.kind {
+            BookmarkKind::Article => "Article",
+            BookmarkKind::Post => "Post",
+        })
+        .build();
+    kind_badge.add_css_class("badge");
+    kind_badge.add_css_class("badge-country");
+    badges_box.append(&kind_badge);
+
+    if !bookmark.source.is_empty() {
+        let source_badge = Label::builder()
+            .label(&bookmark.source)
+            .ellipsize(gtk::pango::EllipsizeMode::Middle)
+            .max_width_chars(28)
+            .build();
+        source_badge.add_css_class("badge");
+        source_badge.add_css_class("badge-time");
+        badges_box.append(&source_badge);
+    }
+
+    if let Ok(saved_at) = chrono::DateTime::parse_from_rfc3339(&bookmark.saved_at) {
+        let date_badge = Label::builder()
+            .label(saved_at.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string())
+            .hexpand(true)
+            .xalign(0.0)
+            .build();
+        date_badge.add_css_class("caption");
+        date_badge.add_css_class("dim-label");
+        badges_box.append(&date_badge);
+    }
+
+    let remove_button = gtk::Button::builder()
+        .icon_name("user-trash-symbolic")
+        .tooltip_text("Remove bookmark")
+        .halign(gtk::Align::End)
+        .hexpand(true)
+        .build();
+    remove_button.add_css_class("flat");
+    let url = bookmark.url.clone();
+    remove_button.connect_clicked(move |_| remove(&url));
+    badges_box.append(&remove_button);
+
+    content_box.append(&badges_box);
+
+    // Comma-separated tags, saved on Enter
+    let tags_entry = gtk::Entry::builder()
+        .placeholder_text("Tags, comma separated")
+        .text(bookmark.tags.join(", "))
+        .build();
+    tags_entry.add_css_class("caption");
+    let url = bookmark.url.clone();
+    tags_entry.connect_activate(move |entry| {
+        set_tags(&url, parse_tags(&entry.text()));
+    });
+    content_box.append(&tags_entry);
+
+    row.append(&content_box);
+    row
+}
+
+/// Rebuild the list from the store, applying the search query
+fn populate(list: &ListBox, query: &str) {
+    while let Some(child) = list.first_child() {
+        list.remove(&child);
+    }
+
+    let bookmarks: Vec<Bookmark> = all()
+        .into_iter()
+        .filter(|bookmark| bookmark.matches(query))
+        .collect();
+
+    if bookmarks.is_empty() {
+        let empty_label = Label::builder()
+            .label(if query.trim().is_empty() {
+                "No bookmarks yet — star an article or post to save it here"
+            } else {
+                "No bookmarks match your search"
+            })
+            .margin_top(24)
+            .margin_bottom(24)
+            .build();
+        empty_label.add_css_class("dim-label");
+        list.append(&empty_label);
+        return;
+    }
+
+    for bookmark in &bookmarks {
+        list.append(&create_bookmark_row(bookmark));
+    }
+}
+
+/// The Bookmarks page: search, per-item tags, and Markdown/HTML export
+pub fn create_bookmarks_view() -> gtk::Box {
+    let container = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(8)
+        .build();
+
+    // Toolbar with search and export
+    let toolbar = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .margin_top(8)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+
+    let search_entry = SearchEntry::builder()
+        .placeholder_text("Search bookmarks, or #tag...")
+        .hexpand(true)
+        .build();
+    toolbar.append(&search_entry);
+
+    let export_button = gtk::Button::builder()
+        .icon_name("document-save-as-symbolic")
+        .tooltip_text("Export bookmarks")
+        .build();
+    toolbar.append(&export_button);
+
+    container.append(&toolbar);
+
+    let list = ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .build();
+
+    let scrolled = ScrolledWindow::builder()
+        .vexpand(true)
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .build();
+    scrolled.set_child(Some(&list));
+    container.append(&scrolled);
+
+    populate(&list, "");
+
+    let list_for_search = list.clone();
+    search_entry.connect_search_changed(move |entry| {
+        populate(&list_for_search, &entry.text());
+    });
+
+    let list_for_changes = list.clone();
+    let search_for_changes = search_entry.clone();
+    connect_changed(move || {
+        populate(&list_for_changes, &search_for_changes.text());
+    });
+
+    // Export the bookmarks matching the current search
+    let search_for_export = search_entry.clone();
+    export_button.connect_clicked(move |button| {
+        let Some(window) = button.root().and_downcast::<gtk::Window>() else {
+            return;
+        };
+
+        let query = search_for_export.text().to_string();
+        let bookmarks: Vec<Bookmark> = all().into_iter().filter(|b| b.matches(&query)).collect();
+        let suggested_name = format!("grapevine-bookmarks-{}.md", chrono::Local::now().format("%Y-%m-%d"));
+
+        crate::export::save_document(&window, &suggested_name, move |is_html| {
+            if is_html {
+                crate::export::bookmarks_to_html("Grapevine Bookmarks", &bookmarks)
+            } else {
+                crate::export::bookmarks_to_markdown("Grapevine Bookmarks", &bookmarks)
+            }
+        });
+    });
+
+    container
+}
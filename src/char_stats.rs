@@ -0,0 +1,268 @@
+use gtk::prelude::*;
+use gtk::{glib, Label, Orientation};
+use libadwaita::prelude::*;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Bars shown in each chart
+const TOP_COUNT: usize = 10;
+/// Distinct emoji counted; rarer ones seen after this are left out so odd
+/// joiner sequences can't grow the map without bound
+const MAX_EMOJI: usize = 5_000;
+const REFRESH_SECONDS: u32 = 2;
+
+/// Emoji by how many times they were used, counted on the Jetstream thread
+static EMOJI: Mutex<BTreeMap<String, u64>> = Mutex::new(BTreeMap::new());
+/// Scripts by how many posts used them
+static SCRIPTS: Mutex<BTreeMap<&'static str, u64>> = Mutex::new(BTreeMap::new());
+static POSTS_COUNTED: AtomicU64 = AtomicU64::new(0);
+
+/// Characters that start an emoji: pictographs, dingbats, symbols and flags
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F1E6..=0x1F1FF // regional indicators, paired into flags
+            | 0x1F300..=0x1F5FF
+            | 0x1F600..=0x1F64F
+            | 0x1F680..=0x1F6FF
+            | 0x1F900..=0x1F9FF
+            | 0x1FA70..=0x1FAFF
+            | 0x2600..=0x26FF
+            | 0x2700..=0x27BF
+            | 0x2B50
+    )
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+/// Variation selectors and skin tones that belong to the emoji before them
+fn is_emoji_modifier(c: char) -> bool {
+    c == '\u{FE0F}' || ('\u{1F3FB}'..='\u{1F3FF}').contains(&c)
+}
+
+/// The emoji in some text, with flags, skin tones and joined sequences such
+/// as families kept whole
+fn emoji_in(text: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if !is_emoji(c) {
+            continue;
+        }
+        let mut emoji = String::from(c);
+
+        if is_regional_indicator(c) {
+            if let Some(second) = chars.next_if(|next| is_regional_indicator(*next)) {
+                emoji.push(second);
+            }
+            found.push(emoji);
+            continue;
+        }
+
+        loop {
+            if let Some(modifier) = chars.next_if(|next| is_emoji_modifier(*next)) {
+                emoji.push(modifier);
+            } else if chars.next_if_eq(&'\u{200D}').is_some() {
+                emoji.push('\u{200D}');
+                match chars.next_if(|next| is_emoji(*next)) {
+                    Some(joined) => emoji.push(joined),
+                    None => break,
+                }
+            } else {
+                break;
+            }
+        }
+        // Variation selectors alone don't make a different emoji
+        found.push(emoji.trim_end_matches('\u{FE0F}').to_string());
+    }
+    found
+}
+
+/// The writing system a letter belongs to, for the scripts chart
+fn script_of(c: char) -> Option<&'static str> {
+    if !c.is_alphabetic() {
+        return None;
+    }
+    let script = match c as u32 {
+        0x0000..=0x024F | 0x1E00..=0x1EFF => "Latin",
+        0x0370..=0x03FF | 0x1F00..=0x1FFF => "Greek",
+        0x0400..=0x052F => "Cyrillic",
+        0x0530..=0x058F => "Armenian",
+        0x0590..=0x05FF => "Hebrew",
+        0x0600..=0x06FF | 0x0750..=0x077F | 0xFB50..=0xFEFF => "Arabic",
+        0x0900..=0x097F => "Devanagari",
+        0x0980..=0x09FF => "Bengali",
+        0x0B80..=0x0BFF => "Tamil",
+        0x0E00..=0x0E7F => "Thai",
+        0x10A0..=0x10FF => "Georgian",
+        0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => "Hangul",
+        0x3040..=0x30FF => "Kana",
+        0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0x20000..=0x2A6DF => "Han",
+        _ => "Other",
+    };
+    Some(script)
+}
+
+/// Count a post's emoji and scripts; runs on the Jetstream thread so the
+/// UI only reads the totals
+pub fn record(text: &str) {
+    POSTS_COUNTED.fetch_add(1, Ordering::Relaxed);
+
+    let emoji = emoji_in(text);
+    if !emoji.is_empty() {
+        if let Ok(mut counts) = EMOJI.lock() {
+            for emoji in emoji {
+                if let Some(count) = counts.get_mut(&emoji) {
+                    *count += 1;
+                } else if counts.len() < MAX_EMOJI {
+                    counts.insert(emoji, 1);
+                }
+            }
+        }
+    }
+
+    // Each script once per post, so long posts don't outweigh the rest
+    let mut scripts: Vec<&'static str> = text.chars().filter_map(script_of).collect();
+    scripts.sort_unstable();
+    scripts.dedup();
+    if !scripts.is_empty() {
+        if let Ok(mut counts) = SCRIPTS.lock() {
+            for script in scripts {
+                *counts.entry(script).or_insert(0) += 1;
+            }
+        }
+    }
+}
+
+/// The most counted entries, most first
+fn top<K: Clone + Ord>(counts: &Mutex<BTreeMap<K, u64>>) -> Vec<(K, u64)> {
+    let Ok(counts) = counts.lock() else {
+        return Vec::new();
+    };
+    let mut entries: Vec<(K, u64)> = counts.iter().map(|(key, count)| (key.clone(), *count)).collect();
+    entries.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    entries.truncate(TOP_COUNT);
+    entries
+}
+
+/// A chart of up to `TOP_COUNT` labelled bars, filled in by `update_chart`
+struct BarChart {
+    rows: Vec<(gtk::Box, Label, gtk::LevelBar, Label)>,
+    empty_label: Label,
+}
+
+fn create_bar_chart(container: &gtk::Box, label_css: &str, empty_text: &str) -> BarChart {
+    let empty_label = Label::builder()
+        .label(empty_text)
+        .xalign(0.0)
+        .build();
+    empty_label.add_css_class("dim-label");
+    container.append(&empty_label);
+
+    let rows = (0..TOP_COUNT)
+        .map(|_| {
+            let row = gtk::Box::builder()
+                .orientation(Orientation::Horizontal)
+                .spacing(8)
+                .visible(false)
+                .build();
+            let name_label = Label::builder()
+                .width_chars(9)
+                .xalign(0.0)
+                .build();
+            name_label.add_css_class(label_css);
+            let bar = gtk::LevelBar::builder()
+                .min_value(0.0)
+                .max_value(1.0)
+                .hexpand(true)
+                .valign(gtk::Align::Center)
+                .build();
+            let count_label = Label::builder()
+                .width_chars(7)
+                .xalign(1.0)
+                .build();
+            count_label.add_css_class("monospace");
+            count_label.add_css_class("caption");
+            row.append(&name_label);
+            row.append(&bar);
+            row.append(&count_label);
+            container.append(&row);
+            (row, name_label, bar, count_label)
+        })
+        .collect();
+
+    BarChart { rows, empty_label }
+}
+
+/// Bars are relative to the most used entry
+fn update_chart(chart: &BarChart, entries: &[(String, u64)]) {
+    let max = entries.first().map(|(_, count)| *count).unwrap_or(0).max(1) as f64;
+    chart.empty_label.set_visible(entries.is_empty());
+    for (index, (row, name_label, bar, count_label)) in chart.rows.iter().enumerate() {
+        match entries.get(index) {
+            Some((name, count)) => {
+                name_label.set_label(name);
+                bar.set_value(*count as f64 / max);
+                count_label.set_label(&count.to_string());
+                row.set_visible(true);
+            }
+            None => row.set_visible(false),
+        }
+    }
+}
+
+/// Live charts of the most used emoji and scripts in the stream this session
+pub fn create_char_stats_group() -> libadwaita::PreferencesGroup {
+    let group = libadwaita::PreferencesGroup::builder()
+        .title("Emoji and Scripts")
+        .build();
+
+    let content = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .build();
+    content.add_css_class("char-stats");
+
+    let heading = |text: &str| {
+        let label = Label::builder()
+            .label(text)
+            .xalign(0.0)
+            .margin_top(6)
+            .build();
+        label.add_css_class("heading");
+        content.append(&label);
+    };
+    heading("Top Emoji");
+    let emoji_chart = create_bar_chart(&content, "char-stats-emoji", "No emoji seen yet");
+    heading("Scripts");
+    let script_chart = create_bar_chart(&content, "caption", "No posts seen yet");
+    group.add(&content);
+
+    let refresh = move |group: &libadwaita::PreferencesGroup| {
+        let posts = POSTS_COUNTED.load(Ordering::Relaxed);
+        group.set_description(Some(&format!("Across {} posts this session; scripts count each post once", posts)));
+        update_chart(&emoji_chart, &top(&EMOJI));
+        let scripts: Vec<(String, u64)> = top(&SCRIPTS)
+            .into_iter()
+            .map(|(script, count)| (script.to_string(), count))
+            .collect();
+        update_chart(&script_chart, &scripts);
+    };
+    refresh(&group);
+
+    let group_for_tick = group.downgrade();
+    glib::timeout_add_seconds_local(REFRESH_SECONDS, move || {
+        let Some(group) = group_for_tick.upgrade() else {
+            return glib::ControlFlow::Break;
+        };
+        if group.is_mapped() {
+            refresh(&group);
+        }
+        glib::ControlFlow::Continue
+    });
+
+    group
+}
@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+
+use crate::data::GdeltArticle;
+
+/// Share of significant title words two articles need in common to be the same story
+const CLUSTER_SIMILARITY: f64 = 0.4;
+
+/// Articles about the same story, represented by its first article
+pub struct StoryCluster {
+    pub lead: GdeltArticle,
+    /// Articles in the story, including the lead
+    pub size: usize,
+    /// Distinct outlets covering the story, the lead's first
+    pub domains: Vec<String>,
+    /// The story's other articles, in the order they were given
+    pub others: Vec<GdeltArticle>,
+}
+
+/// Lowercased title words long enough to carry meaning
+fn title_words(title: &str) -> HashSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 3)
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+fn similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Most words a story's search is narrowed to
+const MAX_STORY_TERMS: usize = 4;
+
+/// Search terms for a story: the lead title's words that most of its articles
+/// share, in title order, falling back to the lead's longest words
+pub fn story_terms(story: &StoryCluster) -> String {
+    let others: Vec<HashSet<String>> = story.others.iter().map(|article| title_words(&article.title)).collect();
+    let mut seen = HashSet::new();
+    let lead_words: Vec<String> = story
+        .lead
+        .title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 3)
+        .map(|word| word.to_lowercase())
+        .filter(|word| seen.insert(word.clone()))
+        .collect();
+
+    let mut terms: Vec<&String> = lead_words
+        .iter()
+        .filter(|word| others.iter().filter(|words| words.contains(*word)).count() * 2 >= others.len())
+        .collect();
+    if terms.len() < 2 {
+        terms = lead_words.iter().collect();
+        terms.sort_by_key(|word| std::cmp::Reverse(word.chars().count()));
+    }
+    terms.truncate(MAX_STORY_TERMS);
+    terms.into_iter().map(String::as_str).collect::<Vec<_>>().join(" ")
+}
+
+/// Group articles whose titles share most of their words. Stories keep the
+/// order of their lead articles, so sort the input by relevance or recency first.
+pub fn cluster_stories(articles: Vec<GdeltArticle>) -> Vec<StoryCluster> {
+    let mut clusters: Vec<(HashSet<String>, StoryCluster)> = Vec::new();
+
+    for article in articles {
+        let words = title_words(&article.title);
+
+        match clusters
+            .iter_mut()
+            .find(|(lead_words, _)| similarity(lead_words, &words) >= CLUSTER_SIMILARITY)
+        {
+            Some((_, cluster)) => {
+                cluster.size += 1;
+                if !article.domain.is_empty() && !cluster.domains.contains(&article.domain) {
+                    cluster.domains.push(article.domain.clone());
+                }
+                cluster.others.push(article);
+            }
+            None => {
+                let domains = if article.domain.is_empty() { Vec::new() } else { vec![article.domain.clone()] };
+                clusters.push((words, StoryCluster { lead: article, size: 1, domains, others: Vec::new() }));
+            }
+        }
+    }
+
+    clusters.into_iter().map(|(_, cluster)| cluster).collect()
+}
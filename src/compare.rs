@@ -0,0 +1,342 @@
+use gtk::prelude::*;
+use gtk::{glib, Label, Orientation};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::coordinates::{get_country_currency, get_country_timezone};
+use crate::data::GdeltArticle;
+use crate::global_affairs;
+
+/// Headlines shown per country
+const TOP_HEADLINES: usize = 3;
+
+thread_local! {
+    /// Countries picked for comparison, in the order they were picked
+    static SELECTION: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    /// Map overlay bar offering to open the comparison
+    static COMPARE_BAR: RefCell<Option<(gtk::Box, gtk::Button)>> = const { RefCell::new(None) };
+}
+
+pub fn is_selected(country: &str) -> bool {
+    SELECTION.with(|selection| selection.borrow().iter().any(|c| c == country))
+}
+
+/// Add a country to the comparison or take it out
+pub fn set_selected(country: &str, selected: bool) {
+    let changed = SELECTION.with(|selection| {
+        let mut selection = selection.borrow_mut();
+        let position = selection.iter().position(|c| c == country);
+        match (position, selected) {
+            (None, true) => selection.push(country.to_string()),
+            (Some(index), false) => {
+                selection.remove(index);
+            }
+            _ => return false,
+        }
+        true
+    });
+    if changed {
+        selection_changed();
+    }
+}
+
+fn selection() -> Vec<String> {
+    SELECTION.with(|selection| selection.borrow().clone())
+}
+
+fn selection_changed() {
+    let selection = selection();
+    global_affairs::set_compare_marks(&selection);
+
+    COMPARE_BAR.with(|bar| {
+        if let Some((ref bar, ref button)) = *bar.borrow() {
+            bar.set_visible(selection.len() >= 2);
+            button.set_label(&format!("Compare {} Countries", selection.len()));
+        }
+    });
+}
+
+/// Bar at the bottom of the map that opens the comparison once two or more
+/// countries are picked
+pub fn create_compare_bar(use_12_hour: Rc<RefCell<bool>>) -> gtk::Box {
+    let bar = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .halign(gtk::Align::Center)
+        .valign(gtk::Align::End)
+        .margin_bottom(12)
+        .visible(false)
+        .build();
+    bar.add_css_class("compare-bar");
+
+    let compare_button = gtk::Button::builder()
+        .label("Compare Countries")
+        .css_classes(vec!["pill".to_string(), "suggested-action".to_string()])
+        .build();
+    compare_button.connect_clicked(move |button| {
+        let window = button.root().and_downcast::<gtk::Window>();
+        present_comparison(window.as_ref(), &selection(), use_12_hour.clone());
+    });
+    bar.append(&compare_button);
+
+    let clear_button = gtk::Button::builder()
+        .icon_name("edit-clear-symbolic")
+        .tooltip_text("Clear comparison")
+        .css_classes(vec!["circular".to_string(), "osd".to_string()])
+        .build();
+    clear_button.connect_clicked(|_| {
+        SELECTION.with(|selection| selection.borrow_mut().clear());
+        selection_changed();
+    });
+    bar.append(&clear_button);
+
+    COMPARE_BAR.with(|current| *current.borrow_mut() = Some((bar.clone(), compare_button)));
+    bar
+}
+
+/// Offset from UTC of a timezone right now, e.g. "UTC+5:30"
+fn format_utc_offset(tz: chrono_tz::Tz) -> String {
+    use chrono::Offset;
+    let seconds = chrono::Utc::now().with_timezone(&tz).offset().fix().local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let (hours, minutes) = (seconds.abs() / 3600, seconds.abs() % 3600 / 60);
+    if minutes == 0 {
+        format!("UTC{}{}", sign, hours)
+    } else {
+        format!("UTC{}{}:{:02}", sign, hours, minutes)
+    }
+}
+
+fn create_section_heading(text: &str) -> Label {
+    let label = Label::builder()
+        .label(text)
+        .xalign(0.0)
+        .margin_top(6)
+        .build();
+    label.add_css_class("heading");
+    label
+}
+
+/// Local time that ticks every second until the comparison closes
+pub fn create_clock(country: &str, use_12_hour: Rc<RefCell<bool>>) -> Option<gtk::Box> {
+    let tz: chrono_tz::Tz = get_country_timezone(country)?.parse().ok()?;
+
+    let clock = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    let time_label = Label::builder()
+        .xalign(0.0)
+        .hexpand(true)
+        .build();
+    time_label.add_css_class("title-4");
+    time_label.add_css_class("monospace");
+    clock.append(&time_label);
+
+    let offset_label = Label::builder()
+        .label(format_utc_offset(tz))
+        .build();
+    offset_label.add_css_class("dim-label");
+    offset_label.add_css_class("caption");
+    clock.append(&offset_label);
+
+    let update_time = move |label: &Label| {
+        let now = chrono::Utc::now().with_timezone(&tz);
+        let format = if *use_12_hour.borrow() { "%I:%M:%S %p" } else { "%H:%M:%S" };
+        label.set_label(&now.format(format).to_string());
+    };
+    update_time(&time_label);
+
+    let label = time_label.downgrade();
+    glib::timeout_add_seconds_local(1, move || match label.upgrade() {
+        Some(label) => {
+            update_time(&label);
+            glib::ControlFlow::Continue
+        }
+        None => glib::ControlFlow::Break,
+    });
+
+    Some(clock)
+}
+
+/// Exchange rate to USD with its 14-day change and trend, loaded in the background
+pub fn create_currency_section(currency_code: &str) -> gtk::Box {
+    let section = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .build();
+    section.add_css_class("popover-currency-section");
+
+    let pair = if currency_code == "USD" { "EUR to USD".to_string() } else { format!("{} to USD", currency_code) };
+    let status_label = Label::builder()
+        .label(format!("{} · loading…", pair))
+        .xalign(0.0)
+        .build();
+    status_label.add_css_class("caption");
+    section.append(&status_label);
+
+    glib::spawn_future_local(load_currency_rates(
+        section.clone(),
+        status_label,
+        pair,
+        currency_code.to_string(),
+    ));
+
+    section
+}
+
+/// Fill in the rate, its 14-day change and trend under the status label
+async fn load_currency_rates(section: gtk::Box, status_label: Label, pair: String, currency_code: String) {
+    let info = match global_affairs::fetch_currency_info(&currency_code).await {
+        Ok(info) => info,
+        Err(e) => {
+            status_label.set_label(&format!("{} · unavailable", pair));
+            crate::toasts::show_error(&e, move || {
+                status_label.set_label(&format!("{} · loading…", pair));
+                glib::spawn_future_local(load_currency_rates(
+                    section.clone(),
+                    status_label.clone(),
+                    pair.clone(),
+                    currency_code.clone(),
+                ));
+            });
+            return;
+        }
+    };
+    status_label.set_label(&pair);
+
+    let rate_box = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .build();
+    let rate_label = Label::builder()
+        .label(global_affairs::format_rate(info.rate))
+        .xalign(0.0)
+        .build();
+    rate_label.add_css_class("title-3");
+    rate_label.add_css_class("currency-rate");
+    rate_box.append(&rate_label);
+
+    if let Some(change) = info.change_7d {
+        let badge = Label::builder()
+            .label(format!("14d: {}{:.2}%", if change > 0.0 { "+" } else { "" }, change))
+            .valign(gtk::Align::Center)
+            .build();
+        badge.add_css_class("badge");
+        badge.add_css_class(if change > 0.0 {
+            "badge-positive"
+        } else if change < 0.0 {
+            "badge-negative"
+        } else {
+            "badge-neutral"
+        });
+        rate_box.append(&badge);
+    }
+    section.append(&rate_box);
+
+    if !info.trend_data.is_empty() {
+        section.append(&global_affairs::create_sparkline(&info.trend_data));
+    }
+}
+
+fn create_country_column(country: &str, articles: &[GdeltArticle], total: usize, use_12_hour: Rc<RefCell<bool>>) -> gtk::Box {
+    let column = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .width_request(300)
+        .build();
+    column.add_css_class("card");
+    column.add_css_class("compare-column");
+
+    let name_label = Label::builder()
+        .label(country)
+        .xalign(0.0)
+        .build();
+    name_label.add_css_class("title-3");
+    column.append(&name_label);
+
+    if let Some(clock) = create_clock(country, use_12_hour) {
+        column.append(&clock);
+    }
+
+    let share = if total > 0 { articles.len() as f64 / total as f64 * 100.0 } else { 0.0 };
+    let count_label = Label::builder()
+        .label(format!("{} articles · {:.0}% of results", articles.len(), share))
+        .xalign(0.0)
+        .build();
+    count_label.add_css_class("dim-label");
+    column.append(&count_label);
+
+    // Bar sized by share of the results, for a quick visual comparison
+    let share_bar = gtk::LevelBar::builder()
+        .min_value(0.0)
+        .max_value(100.0)
+        .value(share)
+        .build();
+    column.append(&share_bar);
+
+    column.append(&create_section_heading("Top Headlines"));
+    let mut latest = articles.to_vec();
+    latest.sort_by(|a, b| b.seendate.cmp(&a.seendate));
+    if latest.is_empty() {
+        let empty_label = Label::builder()
+            .label("No articles in the current results")
+            .xalign(0.0)
+            .build();
+        empty_label.add_css_class("dim-label");
+        empty_label.add_css_class("caption");
+        column.append(&empty_label);
+    }
+    for article in latest.iter().take(TOP_HEADLINES) {
+        column.append(&global_affairs::create_popover_article_row(article));
+    }
+
+    if let Some(currency_code) = get_country_currency(country) {
+        column.append(&create_section_heading("Currency"));
+        column.append(&create_currency_section(currency_code));
+    }
+
+    column
+}
+
+/// Window with a column per country comparing coverage, headlines, currency and local time
+fn present_comparison(parent: Option<&gtk::Window>, countries: &[String], use_12_hour: Rc<RefCell<bool>>) {
+    let articles = global_affairs::current_articles();
+
+    let columns = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(12)
+        .margin_top(12)
+        .margin_bottom(12)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+    for country in countries {
+        let country_articles: Vec<GdeltArticle> = articles
+            .iter()
+            .filter(|article| article.sourcecountry == *country)
+            .cloned()
+            .collect();
+        columns.append(&create_country_column(country, &country_articles, articles.len(), use_12_hour.clone()));
+    }
+
+    let scrolled = gtk::ScrolledWindow::builder()
+        .child(&columns)
+        .vscrollbar_policy(gtk::PolicyType::Automatic)
+        .hscrollbar_policy(gtk::PolicyType::Automatic)
+        .vexpand(true)
+        .build();
+
+    let toolbar_view = libadwaita::ToolbarView::new();
+    toolbar_view.add_top_bar(&libadwaita::HeaderBar::new());
+    toolbar_view.set_content(Some(&scrolled));
+
+    let window = libadwaita::Window::builder()
+        .title("Compare Countries")
+        .default_width((countries.len() as i32 * 324 + 24).min(1300))
+        .default_height(640)
+        .content(&toolbar_view)
+        .build();
+    window.set_transient_for(parent);
+    window.present();
+}
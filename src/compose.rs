@@ -0,0 +1,1041 @@
+use atrium_api::app::bsky::embed::{external, images as embed_images};
+use atrium_api::app::bsky::feed::post::{RecordData as PostRecord, RecordEmbedRefs, ReplyRefData};
+use atrium_api::app::bsky::richtext::facet;
+use atrium_api::com::atproto::repo::strong_ref;
+use atrium_api::types::string::Datetime;
+use atrium_api::types::Union;
+use gtk::prelude::*;
+use gtk::{gio, glib, Label, Orientation};
+use libadwaita::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::account;
+use crate::alt_text;
+use crate::drafts::{self, Draft};
+use crate::error::FetchError;
+use crate::images;
+use crate::opengraph::{self, PageMetadata};
+use crate::toasts;
+
+/// Bluesky's limit, counted in graphemes rather than bytes or code points
+const MAX_GRAPHEMES: usize = 300;
+/// Pause in typing before a pasted link is looked up for its card
+const CARD_DELAY: Duration = Duration::from_millis(700);
+/// Most images a Bluesky post can carry
+const MAX_IMAGES: usize = 4;
+
+/// Link card attached to the post being written
+#[derive(Debug, Clone)]
+struct LinkCard {
+    url: String,
+    metadata: PageMetadata,
+}
+
+/// Image attached to the post being written, ready to upload
+#[derive(Debug, Clone)]
+struct ImageAttachment {
+    bytes: glib::Bytes,
+    mime_type: &'static str,
+    alt: String,
+}
+
+/// Image attached in the composer; its alt text is read from the entry when posting
+struct AttachedImage {
+    bytes: glib::Bytes,
+    mime_type: &'static str,
+    row: gtk::Box,
+    alt_entry: gtk::Entry,
+}
+
+impl AttachedImage {
+    fn attachment(&self) -> ImageAttachment {
+        ImageAttachment {
+            bytes: self.bytes.clone(),
+            mime_type: self.mime_type,
+            alt: self.alt_entry.text().trim().to_string(),
+        }
+    }
+}
+
+/// The post being replied to, and the first post of its thread
+#[derive(Debug, Clone)]
+pub struct ReplyTarget {
+    parent: strong_ref::Main,
+    root: strong_ref::Main,
+    /// Author of the parent, shown above the text
+    handle: String,
+}
+
+impl ReplyTarget {
+    /// Reply target for a post given its URI, CID and raw record; None if the
+    /// CID doesn't parse
+    pub fn from_post(uri: &str, cid: &str, record: &serde_json::Value, handle: &str) -> Option<Self> {
+        let parent: strong_ref::Main = strong_ref::MainData {
+            cid: cid.parse().ok()?,
+            uri: uri.to_string(),
+        }
+        .into();
+
+        // Replies to a reply keep the thread's root; a top-level post is its own root
+        let root = record
+            .get("reply")
+            .and_then(|reply| reply.get("root"))
+            .and_then(|root| serde_json::from_value::<strong_ref::MainData>(root.clone()).ok())
+            .map(strong_ref::Main::from)
+            .unwrap_or_else(|| parent.clone());
+
+        Some(Self { parent, root, handle: handle.to_string() })
+    }
+}
+
+/// Number of user-perceived characters, so a flag emoji or an accented letter
+/// typed as two code points counts once, as the Bluesky apps count them
+fn grapheme_count(buffer: &gtk::TextBuffer) -> usize {
+    let mut iter = buffer.start_iter();
+    let mut count = 0;
+    while iter.forward_cursor_position() {
+        count += 1;
+    }
+    count
+}
+
+/// Byte ranges of the http(s) links in a post, for link facets
+fn find_links(text: &str) -> Vec<(usize, usize)> {
+    let mut links = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(found) = text[search_from..].find("http") {
+        let start = search_from + found;
+        let rest = &text[start..];
+        let word_len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let url = rest[..word_len].trim_end_matches(|c: char| ".,;:!?)\"'".contains(c));
+
+        let at_word_start = text[..start].chars().next_back().is_none_or(|c| c.is_whitespace() || c == '(');
+        let has_scheme = url.starts_with("https://") || url.starts_with("http://");
+        if at_word_start && has_scheme && !url.ends_with("://") {
+            links.push((start, start + url.len()));
+        }
+        search_from = start + word_len.max(1);
+    }
+
+    links
+}
+
+fn link_facets(text: &str) -> Option<Vec<facet::Main>> {
+    let facets: Vec<facet::Main> = find_links(text)
+        .into_iter()
+        .map(|(start, end)| {
+            facet::MainData {
+                features: vec![Union::Refs(facet::MainFeaturesItem::Link(Box::new(
+                    facet::LinkData { uri: text[start..end].to_string() }.into(),
+                )))],
+                index: facet::ByteSliceData { byte_start: start, byte_end: end }.into(),
+            }
+            .into()
+        })
+        .collect();
+    (!facets.is_empty()).then_some(facets)
+}
+
+/// Guess an image's type from its URL or file name; the PDS checks the bytes anyway
+fn image_mime_type(url: &str) -> &'static str {
+    let path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+    if path.ends_with(".png") {
+        "image/png"
+    } else if path.ends_with(".webp") {
+        "image/webp"
+    } else if path.ends_with(".gif") {
+        "image/gif"
+    } else {
+        "image/jpeg"
+    }
+}
+
+/// The page's preview image, re-encoded smaller if it's over the blob limit
+async fn thumbnail_for_upload(image_url: &str) -> Result<(Vec<u8>, &'static str), String> {
+    let bytes = images::fetch_image_bytes(image_url).await.map_err(|e| e.to_string())?;
+    if bytes.len() <= account::MAX_BLOB_BYTES {
+        return Ok((bytes, image_mime_type(image_url)));
+    }
+    images::shrink_to_jpeg(bytes, account::MAX_BLOB_BYTES)
+        .await
+        .map(|bytes| (bytes, "image/jpeg"))
+        .ok_or_else(|| "the image is too large to upload".to_string())
+}
+
+/// Card embed for a link, with the page's preview image uploaded as its thumbnail
+/// when it can be fetched
+async fn external_embed(card: &LinkCard) -> Union<RecordEmbedRefs> {
+    let mut thumb = None;
+    if !card.metadata.image.is_empty() {
+        match thumbnail_for_upload(&card.metadata.image).await {
+            Ok((bytes, mime_type)) => match account::upload_blob(bytes, mime_type).await {
+                Ok(blob) => thumb = Some(blob),
+                Err(e) => tracing::warn!("Link card posted without its image: {}", e),
+            },
+            Err(e) => tracing::warn!("Link card posted without its image: {}", e),
+        }
+    }
+
+    Union::Refs(RecordEmbedRefs::AppBskyEmbedExternalMain(Box::new(
+        external::MainData {
+            external: external::ExternalData {
+                description: card.metadata.description.clone(),
+                thumb,
+                title: card.metadata.title.clone(),
+                uri: card.url.clone(),
+            }
+            .into(),
+        }
+        .into(),
+    )))
+}
+
+/// Images embed, uploading each image; fails rather than posting with one missing
+async fn images_embed(images: Vec<ImageAttachment>) -> Result<Union<RecordEmbedRefs>, FetchError> {
+    let mut embedded = Vec::with_capacity(images.len());
+    for image in images {
+        let blob = account::upload_blob(image.bytes.to_vec(), image.mime_type).await?;
+        embedded.push(
+            embed_images::ImageData {
+                alt: image.alt,
+                aspect_ratio: None,
+                image: blob,
+            }
+            .into(),
+        );
+    }
+
+    Ok(Union::Refs(RecordEmbedRefs::AppBskyEmbedImagesMain(Box::new(
+        embed_images::MainData { images: embedded }.into(),
+    ))))
+}
+
+/// A post carries one embed, so attached images take the place of the link card
+async fn publish(
+    text: String,
+    card: Option<LinkCard>,
+    images: Vec<ImageAttachment>,
+    reply: Option<ReplyTarget>,
+) -> Result<String, FetchError> {
+    let embed = if !images.is_empty() {
+        Some(images_embed(images).await?)
+    } else {
+        match card {
+            Some(ref card) => Some(external_embed(card).await),
+            None => None,
+        }
+    };
+
+    let post = PostRecord {
+        created_at: Datetime::now(),
+        embed,
+        entities: None,
+        facets: link_facets(&text),
+        labels: None,
+        langs: None,
+        reply: reply.map(|target| ReplyRefData { parent: target.parent, root: target.root }.into()),
+        tags: None,
+        text,
+    };
+    account::create_post(post).await
+}
+
+/// Publish a saved draft, looking up the card for its link again
+pub async fn publish_with_link(text: String, card_url: Option<String>) -> Result<String, FetchError> {
+    let card = match card_url {
+        Some(url) => match opengraph::fetch_page_metadata(&url).await {
+            Ok(metadata) => Some(LinkCard { url, metadata }),
+            Err(e) => {
+                tracing::warn!("Scheduled post sent without its link card for {}: {}", url, e);
+                None
+            }
+        },
+        None => None,
+    };
+    publish(text, card, Vec::new(), None).await
+}
+
+/// Header button listing saved and scheduled drafts; picking one hands it to `on_open`
+fn create_drafts_button(on_open: impl Fn(Draft) + 'static) -> gtk::MenuButton {
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .build();
+    list.add_css_class("boxed-list");
+
+    let scrolled = gtk::ScrolledWindow::builder()
+        .child(&list)
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .propagate_natural_height(true)
+        .max_content_height(360)
+        .width_request(320)
+        .build();
+
+    let popover = gtk::Popover::builder()
+        .child(&scrolled)
+        .build();
+
+    let button = gtk::MenuButton::builder()
+        .icon_name("document-open-recent-symbolic")
+        .tooltip_text("Drafts")
+        .popover(&popover)
+        .build();
+
+    let on_open = Rc::new(on_open);
+    let populate = {
+        let list = list.clone();
+        let popover = popover.clone();
+        move || {
+            while let Some(child) = list.first_child() {
+                list.remove(&child);
+            }
+
+            let all = drafts::all();
+            if all.is_empty() {
+                let empty_label = Label::builder()
+                    .label("No drafts")
+                    .margin_top(12)
+                    .margin_bottom(12)
+                    .build();
+                empty_label.add_css_class("dim-label");
+                list.append(&empty_label);
+            }
+
+            for draft in all {
+                let subtitle = match (draft.scheduled_time(), &draft.last_error) {
+                    (Some(time), _) => format!("Scheduled for {}", time.format("%a %d %b, %H:%M")),
+                    (None, Some(error)) => format!("Not sent: {}", error),
+                    (None, None) => "Draft".to_string(),
+                };
+                let row = libadwaita::ActionRow::builder()
+                    .title(glib::markup_escape_text(draft.text.lines().next().unwrap_or_default()))
+                    .subtitle(glib::markup_escape_text(&subtitle))
+                    .title_lines(1)
+                    .activatable(true)
+                    .build();
+
+                let delete_button = gtk::Button::builder()
+                    .icon_name("user-trash-symbolic")
+                    .tooltip_text("Delete draft")
+                    .valign(gtk::Align::Center)
+                    .css_classes(vec!["flat".to_string()])
+                    .build();
+                let id = draft.id.clone();
+                delete_button.connect_clicked(move |_| drafts::remove(&id));
+                row.add_suffix(&delete_button);
+
+                let on_open = on_open.clone();
+                let popover = popover.clone();
+                row.connect_activated(move |_| {
+                    popover.popdown();
+                    on_open(draft.clone());
+                });
+                list.append(&row);
+            }
+        }
+    };
+
+    // Rebuilt on open, and while open if a draft is deleted or sent
+    let populate = Rc::new(populate);
+    let populate_on_show = populate.clone();
+    popover.connect_show(move |_| populate_on_show());
+    let popover_for_changes = popover.clone();
+    drafts::connect_changed(move || {
+        if popover_for_changes.is_visible() {
+            populate();
+        }
+    });
+
+    button
+}
+
+/// Footer button whose popover picks a date and time to post at
+fn create_schedule_button(on_schedule: impl Fn(chrono::DateTime<chrono::Local>) + 'static) -> gtk::MenuButton {
+    use chrono::{Local, TimeZone, Timelike};
+
+    let popover_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(8)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(6)
+        .margin_end(6)
+        .build();
+
+    let calendar = gtk::Calendar::new();
+    popover_box.append(&calendar);
+
+    // Defaults to the next full hour
+    let next_hour = Local::now() + chrono::Duration::hours(1);
+    let hour_spin = gtk::SpinButton::with_range(0.0, 23.0, 1.0);
+    hour_spin.set_value(next_hour.hour() as f64);
+    hour_spin.set_orientation(Orientation::Vertical);
+    let minute_spin = gtk::SpinButton::with_range(0.0, 55.0, 5.0);
+    minute_spin.set_orientation(Orientation::Vertical);
+
+    let time_box = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(4)
+        .halign(gtk::Align::Center)
+        .build();
+    time_box.append(&hour_spin);
+    time_box.append(&Label::new(Some(":")));
+    time_box.append(&minute_spin);
+    popover_box.append(&time_box);
+
+    let error_label = Label::builder()
+        .visible(false)
+        .build();
+    error_label.add_css_class("caption");
+    error_label.add_css_class("error");
+    popover_box.append(&error_label);
+
+    let schedule_button = gtk::Button::builder()
+        .label("Schedule Post")
+        .css_classes(vec!["suggested-action".to_string()])
+        .build();
+    popover_box.append(&schedule_button);
+
+    let popover = gtk::Popover::builder()
+        .child(&popover_box)
+        .build();
+
+    let button = gtk::MenuButton::builder()
+        .icon_name("alarm-symbolic")
+        .tooltip_text("Schedule for later")
+        .popover(&popover)
+        .build();
+
+    schedule_button.connect_clicked(move |_| {
+        let date = calendar.date();
+        let time = Local
+            .with_ymd_and_hms(
+                date.year(),
+                date.month() as u32,
+                date.day_of_month() as u32,
+                hour_spin.value() as u32,
+                minute_spin.value() as u32,
+                0,
+            )
+            .earliest();
+
+        match time {
+            Some(time) if time > Local::now() => {
+                error_label.set_visible(false);
+                popover.popdown();
+                on_schedule(time);
+            }
+            _ => {
+                error_label.set_label("Pick a time in the future");
+                error_label.set_visible(true);
+            }
+        }
+    });
+
+    button
+}
+
+/// Preview of the link card, with a button to drop it
+fn create_card_preview(card: &LinkCard, on_remove: impl Fn() + 'static) -> gtk::Box {
+    let preview = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    preview.add_css_class("popover-currency-section");
+
+    if !card.metadata.image.is_empty() {
+        let thumbnail = gtk::Picture::builder()
+            .content_fit(gtk::ContentFit::Cover)
+            .width_request(64)
+            .height_request(64)
+            .visible(false)
+            .build();
+        thumbnail.add_css_class("compose-card-thumbnail");
+        preview.append(&thumbnail);
+
+        let image_url = card.metadata.image.clone();
+        glib::spawn_future_local(async move {
+            match images::load_texture(&image_url, 128, 128).await {
+                Ok(texture) => {
+                    thumbnail.set_paintable(Some(&texture));
+                    thumbnail.set_visible(true);
+                }
+                Err(e) => tracing::warn!("No preview image for link card: {}", e),
+            }
+        });
+    }
+
+    let text_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .hexpand(true)
+        .build();
+
+    let title = Label::builder()
+        .label(if card.metadata.title.is_empty() { card.url.as_str() } else { card.metadata.title.as_str() })
+        .xalign(0.0)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build();
+    title.add_css_class("caption-heading");
+    text_box.append(&title);
+
+    if !card.metadata.description.is_empty() {
+        let description = Label::builder()
+            .label(card.metadata.description.as_str())
+            .xalign(0.0)
+            .ellipsize(gtk::pango::EllipsizeMode::End)
+            .lines(2)
+            .wrap(true)
+            .build();
+        description.add_css_class("caption");
+        description.add_css_class("dim-label");
+        text_box.append(&description);
+    }
+    preview.append(&text_box);
+
+    let remove_button = gtk::Button::builder()
+        .icon_name("window-close-symbolic")
+        .tooltip_text("Post without a link card")
+        .valign(gtk::Align::Start)
+        .css_classes(vec!["flat".to_string(), "circular".to_string()])
+        .build();
+    remove_button.connect_clicked(move |_| on_remove());
+    preview.append(&remove_button);
+
+    preview
+}
+
+/// Read an image picked for attaching, re-encoded smaller if it's over the blob limit
+async fn load_attachment(path: &std::path::Path) -> Result<(Vec<u8>, &'static str), String> {
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    if bytes.len() <= account::MAX_BLOB_BYTES {
+        return Ok((bytes, image_mime_type(&path.to_string_lossy())));
+    }
+    images::shrink_to_jpeg(bytes, account::MAX_BLOB_BYTES)
+        .await
+        .map(|bytes| (bytes, "image/jpeg"))
+        .ok_or_else(|| "the image is too large to upload".to_string())
+}
+
+/// Row for an attached image: its thumbnail, an entry for its alt text, a button to
+/// suggest one when a captioning server is set up, and a button to drop the image
+fn create_attachment_row(bytes: &glib::Bytes, on_remove: impl Fn(&gtk::Box) + 'static) -> (gtk::Box, gtk::Entry) {
+    let row = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    row.add_css_class("popover-currency-section");
+
+    let thumbnail = gtk::Picture::builder()
+        .content_fit(gtk::ContentFit::Cover)
+        .width_request(48)
+        .height_request(48)
+        .build();
+    thumbnail.add_css_class("compose-card-thumbnail");
+    row.append(&thumbnail);
+
+    let bytes_for_preview = bytes.to_vec();
+    glib::spawn_future_local(async move {
+        if let Some(texture) = images::decode_texture(bytes_for_preview, 96, 96).await {
+            thumbnail.set_paintable(Some(&texture));
+        }
+    });
+
+    let alt_entry = gtk::Entry::builder()
+        .placeholder_text(if alt_text::is_required() { "Describe this image (required)" } else { "Describe this image" })
+        .hexpand(true)
+        .valign(gtk::Align::Center)
+        .build();
+    alt_entry.update_property(&[gtk::accessible::Property::Label("Alt text")]);
+    row.append(&alt_entry);
+
+    if alt_text::can_suggest() {
+        let suggest_button = gtk::Button::builder()
+            .icon_name("document-edit-symbolic")
+            .tooltip_text("Suggest alt text")
+            .valign(gtk::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+
+        let bytes = bytes.clone();
+        let entry = alt_entry.clone();
+        suggest_button.connect_clicked(move |button| {
+            button.set_sensitive(false);
+            entry.set_progress_pulse_step(0.2);
+            let pulse = glib::timeout_add_local(Duration::from_millis(150), {
+                let entry = entry.clone();
+                move || {
+                    entry.progress_pulse();
+                    glib::ControlFlow::Continue
+                }
+            });
+
+            let button = button.clone();
+            let entry = entry.clone();
+            let bytes = bytes.clone();
+            glib::spawn_future_local(async move {
+                let result = alt_text::suggest(&bytes).await;
+                pulse.remove();
+                entry.set_progress_fraction(0.0);
+                button.set_sensitive(true);
+                match result {
+                    // A suggestion to check and edit, not to post unread
+                    Ok(caption) => {
+                        entry.set_text(&caption);
+                        entry.grab_focus();
+                    }
+                    Err(e) => {
+                        tracing::warn!("No alt text suggestion: {}", e);
+                        toasts::show("Couldn't suggest alt text");
+                    }
+                }
+            });
+        });
+        row.append(&suggest_button);
+    }
+
+    let remove_button = gtk::Button::builder()
+        .icon_name("window-close-symbolic")
+        .tooltip_text("Remove image")
+        .valign(gtk::Align::Center)
+        .css_classes(vec!["flat".to_string(), "circular".to_string()])
+        .build();
+    let row_weak = row.downgrade();
+    remove_button.connect_clicked(move |_| {
+        if let Some(row) = row_weak.upgrade() {
+            on_remove(&row);
+        }
+    });
+    row.append(&remove_button);
+
+    (row, alt_entry)
+}
+
+/// Window for writing and publishing a post to the signed-in Bluesky account,
+/// optionally as a reply
+pub fn present_compose_dialog(parent: &impl IsA<gtk::Window>, reply: Option<ReplyTarget>) {
+    if !account::is_configured() {
+        let dialog = libadwaita::MessageDialog::builder()
+            .transient_for(parent)
+            .modal(true)
+            .heading("Sign In to Post")
+            .body("Add your Bluesky handle and an app password in Preferences to publish posts.")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("preferences", "Open Preferences")]);
+        dialog.set_response_appearance("preferences", libadwaita::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("preferences"));
+        dialog.set_close_response("cancel");
+        dialog.connect_response(Some("preferences"), |dialog, _| {
+            let _ = dialog.activate_action("app.preferences", None);
+        });
+        dialog.present();
+        return;
+    }
+
+    let heading = if reply.is_some() { "Reply" } else { "New Post" };
+    let window = libadwaita::Window::builder()
+        .title(heading)
+        .transient_for(parent)
+        .modal(true)
+        .default_width(480)
+        .default_height(360)
+        .build();
+
+    let title = libadwaita::WindowTitle::new(heading, "");
+    let header_bar = libadwaita::HeaderBar::builder()
+        .title_widget(&title)
+        .show_end_title_buttons(false)
+        .build();
+
+    let cancel_button = gtk::Button::with_label("Cancel");
+    let post_button = gtk::Button::builder()
+        .label("Post")
+        .sensitive(false)
+        .css_classes(vec!["suggested-action".to_string()])
+        .build();
+    header_bar.pack_start(&cancel_button);
+    header_bar.pack_end(&post_button);
+
+    let content = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(8)
+        .margin_top(12)
+        .margin_bottom(12)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+
+    if let Some(ref target) = reply {
+        let replying_label = Label::builder()
+            .label(format!("Replying to @{}", target.handle))
+            .xalign(0.0)
+            .build();
+        replying_label.add_css_class("caption");
+        replying_label.add_css_class("dim-label");
+        content.append(&replying_label);
+    }
+
+    let text_view = gtk::TextView::builder()
+        .wrap_mode(gtk::WrapMode::WordChar)
+        .accepts_tab(false)
+        .top_margin(6)
+        .bottom_margin(6)
+        .left_margin(6)
+        .right_margin(6)
+        .build();
+    let buffer = text_view.buffer();
+
+    let scrolled = gtk::ScrolledWindow::builder()
+        .child(&text_view)
+        .vexpand(true)
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .build();
+    scrolled.add_css_class("card");
+    content.append(&scrolled);
+
+    let card_slot = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .build();
+    content.append(&card_slot);
+
+    let attachment_list = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .build();
+    content.append(&attachment_list);
+
+    let footer = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .build();
+    let status_label = Label::builder()
+        .xalign(0.0)
+        .hexpand(true)
+        .wrap(true)
+        .build();
+    status_label.add_css_class("caption");
+    let counter_label = Label::builder()
+        .label(MAX_GRAPHEMES.to_string())
+        .tooltip_text("Characters left")
+        .build();
+    counter_label.add_css_class("caption");
+    counter_label.add_css_class("monospace");
+    footer.append(&status_label);
+    footer.append(&counter_label);
+    content.append(&footer);
+
+    let toolbar_view = libadwaita::ToolbarView::new();
+    toolbar_view.add_top_bar(&header_bar);
+    toolbar_view.set_content(Some(&content));
+    window.set_content(Some(&toolbar_view));
+
+    // Show who the post will go out as, which also checks the credentials early
+    let title_for_session = title.clone();
+    let status_for_session = status_label.clone();
+    glib::spawn_future_local(async move {
+        match account::session().await {
+            Ok(session) => title_for_session.set_subtitle(&format!("@{}", session.handle)),
+            Err(e) => status_for_session.set_label(&e.to_string()),
+        }
+    });
+
+    let card: Rc<RefCell<Option<LinkCard>>> = Rc::new(RefCell::new(None));
+    // Link whose card was removed, so it isn't fetched again while it stays in the text
+    let dismissed_url: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+    // Bumped on every edit so only the last lookup after a pause runs
+    let generation = Rc::new(RefCell::new(0u64));
+
+    let show_card: Rc<dyn Fn(Option<LinkCard>)> = {
+        let card = card.clone();
+        let card_slot = card_slot.clone();
+        let dismissed_url = dismissed_url.clone();
+        Rc::new(move |new_card: Option<LinkCard>| {
+            while let Some(child) = card_slot.first_child() {
+                card_slot.remove(&child);
+            }
+            if let Some(ref new_card) = new_card {
+                let card = card.clone();
+                let slot = card_slot.clone();
+                let dismissed_url = dismissed_url.clone();
+                let url = new_card.url.clone();
+                card_slot.append(&create_card_preview(new_card, move || {
+                    *dismissed_url.borrow_mut() = Some(url.clone());
+                    card.borrow_mut().take();
+                    while let Some(child) = slot.first_child() {
+                        slot.remove(&child);
+                    }
+                }));
+            }
+            *card.borrow_mut() = new_card;
+        })
+    };
+
+    let attachments: Rc<RefCell<Vec<AttachedImage>>> = Rc::new(RefCell::new(Vec::new()));
+
+    // Posting needs text or an image, fits the limit, and has alt text when that's required
+    let update_post_button: Rc<dyn Fn()> = {
+        let buffer = buffer.clone();
+        let attachments = attachments.clone();
+        let post_button = post_button.clone();
+        Rc::new(move || {
+            let attachments = attachments.borrow();
+            let has_text = !buffer.text(&buffer.start_iter(), &buffer.end_iter(), false).trim().is_empty();
+            let fits = grapheme_count(&buffer) <= MAX_GRAPHEMES;
+            let missing_alt = alt_text::is_required()
+                && attachments.iter().any(|image| image.alt_entry.text().trim().is_empty());
+            post_button.set_sensitive((has_text || !attachments.is_empty()) && fits && !missing_alt);
+            post_button.set_tooltip_text(missing_alt.then_some("Describe every image to post"));
+        })
+    };
+
+    let update_post_button_for_changes = update_post_button.clone();
+    let card_for_changes = card.clone();
+    buffer.connect_changed(move |buffer| {
+        let count = grapheme_count(buffer);
+        let remaining = MAX_GRAPHEMES as i64 - count as i64;
+        counter_label.set_label(&remaining.to_string());
+        if remaining < 0 {
+            counter_label.add_css_class("error");
+        } else {
+            counter_label.remove_css_class("error");
+        }
+
+        update_post_button_for_changes();
+
+        let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false).to_string();
+
+        // The card follows the first link in the text
+        let first_link = find_links(&text).first().map(|&(start, end)| text[start..end].to_string());
+        let current_url = card_for_changes.borrow().as_ref().map(|card| card.url.clone());
+        if first_link == current_url {
+            return;
+        }
+
+        *generation.borrow_mut() += 1;
+        let Some(url) = first_link else {
+            show_card(None);
+            return;
+        };
+        if dismissed_url.borrow().as_deref() == Some(url.as_str()) {
+            return;
+        }
+
+        let expected = *generation.borrow();
+        let generation = generation.clone();
+        let show_card = show_card.clone();
+        let dismissed_url = dismissed_url.clone();
+        glib::timeout_add_local_once(CARD_DELAY, move || {
+            if *generation.borrow() != expected {
+                return;
+            }
+            glib::spawn_future_local(async move {
+                let metadata = match opengraph::fetch_page_metadata(&url).await {
+                    Ok(metadata) => metadata,
+                    Err(e) => {
+                        // Post the bare link; don't look it up again on every keystroke
+                        tracing::warn!("No link card for {}: {}", url, e);
+                        *dismissed_url.borrow_mut() = Some(url);
+                        return;
+                    }
+                };
+                // Typing may have moved on while the page loaded
+                if *generation.borrow() == expected {
+                    show_card(Some(LinkCard { url, metadata }));
+                }
+            });
+        });
+    });
+
+    // Draft being edited, replaced when saved again and removed once posted
+    let draft_id: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+    let current_draft = {
+        let buffer = buffer.clone();
+        let card = card.clone();
+        let draft_id = draft_id.clone();
+        move || {
+            let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false).trim().to_string();
+            let card_url = card.borrow().as_ref().map(|card| card.url.clone());
+            let mut draft = Draft::new(&text, card_url);
+            if let Some(ref id) = *draft_id.borrow() {
+                draft.id = id.clone();
+            }
+            draft
+        }
+    };
+
+    // Replies aren't kept as drafts or scheduled, since they answer a post now
+    if reply.is_none() {
+        let buffer_for_open = buffer.clone();
+        let draft_id_for_open = draft_id.clone();
+        let title_for_open = title.clone();
+        let drafts_button = create_drafts_button(move |draft| {
+            *draft_id_for_open.borrow_mut() = Some(draft.id.clone());
+            buffer_for_open.set_text(&draft.text);
+            title_for_open.set_title(if draft.scheduled_at.is_some() { "Scheduled Post" } else { "Draft" });
+        });
+        header_bar.pack_start(&drafts_button);
+
+        let current_draft = current_draft.clone();
+        let window_for_schedule = window.clone();
+        let schedule_button = create_schedule_button(move |time| {
+            let draft = Draft {
+                scheduled_at: Some(time.to_rfc3339()),
+                ..current_draft()
+            };
+            if draft.text.is_empty() {
+                return;
+            }
+            drafts::save_draft(draft);
+            toasts::show(&format!("Post scheduled for {}", time.format("%a %d %b, %H:%M")));
+            window_for_schedule.close();
+        });
+        footer.insert_child_after(&schedule_button, Some(&status_label));
+    }
+
+    let attach_button = gtk::Button::builder()
+        .icon_name("image-x-generic-symbolic")
+        .tooltip_text("Attach images")
+        .build();
+    footer.insert_child_after(&attach_button, Some(&status_label));
+
+    let attachment_list_for_post = attachment_list.clone();
+    let attach_button_for_post = attach_button.clone();
+    let attachments_for_attach = attachments.clone();
+    let update_post_button_for_attach = update_post_button.clone();
+    let card_slot_for_attach = card_slot.clone();
+    attach_button.connect_clicked(move |button| {
+        let filters = gio::ListStore::new::<gtk::FileFilter>();
+        let image_filter = gtk::FileFilter::new();
+        image_filter.set_name(Some("Images"));
+        image_filter.add_mime_type("image/*");
+        filters.append(&image_filter);
+
+        let dialog = gtk::FileDialog::builder()
+            .title("Attach Images")
+            .filters(&filters)
+            .modal(true)
+            .build();
+
+        let parent = button.root().and_downcast::<gtk::Window>();
+        let attachments = attachments_for_attach.clone();
+        let update_post_button = update_post_button_for_attach.clone();
+        let attachment_list = attachment_list.clone();
+        let card_slot = card_slot_for_attach.clone();
+        let attach_button = button.clone();
+        dialog.open_multiple(parent.as_ref(), gio::Cancellable::NONE, move |result| {
+            let Ok(files) = result else {
+                return; // Dismissed by the user
+            };
+            let paths: Vec<std::path::PathBuf> = files
+                .iter::<gio::File>()
+                .filter_map(|file| file.ok()?.path())
+                .collect();
+
+            glib::spawn_future_local(async move {
+                for path in paths {
+                    if attachments.borrow().len() >= MAX_IMAGES {
+                        toasts::show(&format!("Posts can have up to {} images", MAX_IMAGES));
+                        break;
+                    }
+                    let (bytes, mime_type) = match load_attachment(&path).await {
+                        Ok(loaded) => loaded,
+                        Err(e) => {
+                            tracing::warn!("Failed to attach {}: {}", path.display(), e);
+                            toasts::show("Couldn't attach that image");
+                            continue;
+                        }
+                    };
+                    let bytes = glib::Bytes::from_owned(bytes);
+
+                    let (row, alt_entry) = create_attachment_row(&bytes, {
+                        let attachments = attachments.clone();
+                        let attachment_list = attachment_list.clone();
+                        let update_post_button = update_post_button.clone();
+                        let card_slot = card_slot.clone();
+                        let attach_button = attach_button.clone();
+                        move |row: &gtk::Box| {
+                            attachment_list.remove(row);
+                            attachments.borrow_mut().retain(|image| &image.row != row);
+                            let remaining = attachments.borrow().len();
+                            card_slot.set_visible(remaining == 0);
+                            attach_button.set_sensitive(remaining < MAX_IMAGES);
+                            update_post_button();
+                        }
+                    });
+                    let update_post_button_for_alt = update_post_button.clone();
+                    alt_entry.connect_changed(move |_| update_post_button_for_alt());
+
+                    attachment_list.append(&row);
+                    attachments.borrow_mut().push(AttachedImage { bytes, mime_type, row, alt_entry });
+                }
+
+                let count = attachments.borrow().len();
+                // Images replace the link card, since a post has a single embed
+                card_slot.set_visible(count == 0);
+                attach_button.set_sensitive(count < MAX_IMAGES);
+                update_post_button();
+            });
+        });
+    });
+
+    let window_for_cancel = window.clone();
+    let is_reply = reply.is_some();
+    cancel_button.connect_clicked(move |_| {
+        // Keep unfinished posts rather than throwing them away
+        let draft = current_draft();
+        if !is_reply && !draft.text.is_empty() {
+            drafts::save_draft(draft);
+            toasts::show("Saved to drafts");
+        }
+        window_for_cancel.close();
+    });
+
+    let window_for_post = window.clone();
+    let text_view_for_post = text_view.clone();
+    post_button.connect_clicked(move |button| {
+        let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false).trim().to_string();
+        let images: Vec<ImageAttachment> = attachments.borrow().iter().map(AttachedImage::attachment).collect();
+        let card = if images.is_empty() { card.borrow().clone() } else { None };
+        let reply = reply.clone();
+        let draft_id = draft_id.clone();
+
+        button.set_sensitive(false);
+        text_view_for_post.set_editable(false);
+        attachment_list_for_post.set_sensitive(false);
+        attach_button_for_post.set_sensitive(false);
+        status_label.remove_css_class("error");
+        status_label.set_label("Posting…");
+
+        let button = button.clone();
+        let text_view = text_view_for_post.clone();
+        let status_label = status_label.clone();
+        let window = window_for_post.clone();
+        let attachment_list = attachment_list_for_post.clone();
+        let attach_button = attach_button_for_post.clone();
+        let image_count = images.len();
+        glib::spawn_future_local(async move {
+            match publish(text, card, images, reply).await {
+                Ok(uri) => {
+                    tracing::info!("Posted {}", uri);
+                    if let Some(id) = draft_id.borrow_mut().take() {
+                        drafts::remove(&id);
+                    }
+                    window.close();
+                }
+                Err(e) => {
+                    status_label.add_css_class("error");
+                    status_label.set_label(&e.to_string());
+                    button.set_sensitive(true);
+                    text_view.set_editable(true);
+                    attachment_list.set_sensitive(true);
+                    attach_button.set_sensitive(image_count < MAX_IMAGES);
+                }
+            }
+        });
+    });
+
+    window.present();
+    text_view.grab_focus();
+}
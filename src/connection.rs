@@ -0,0 +1,475 @@
+use gtk::glib;
+use gtk::{Label, Orientation};
+use libadwaita::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::settings::config_dir;
+
+/// Reconnect attempts in a row before giving up until the user retries
+const MAX_RETRIES: u32 = 8;
+const BASE_DELAY: Duration = Duration::from_secs(1);
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// How often a paused stream checks whether focus mode has been turned off
+const FOCUS_POLL: Duration = Duration::from_millis(500);
+
+/// Lag behind live after which a connected stream is reported as catching up
+const LAG_WARNING: Duration = Duration::from_secs(30);
+
+/// Gaps shorter than this since the last run aren't worth offering a catch-up for
+const MIN_CATCH_UP: Duration = Duration::from_secs(60);
+/// Jetstream only replays about a day of events
+const MAX_CATCH_UP: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Microsecond timestamp of the newest Jetstream event; 0 before the first one
+static CURSOR_US: AtomicU64 = AtomicU64::new(0);
+/// Cursor the user asked to catch up from, applied on the next connection; 0 when none
+static CATCH_UP_US: AtomicU64 = AtomicU64::new(0);
+/// Where the current backfill started, for its progress; 0 when streaming live
+static BACKFILL_FROM_US: AtomicU64 = AtomicU64::new(0);
+
+/// Reconnect every MAX_DELAY after retries run out instead of waiting for the user
+static KEEP_RETRYING: AtomicBool = AtomicBool::new(false);
+
+/// Where the Jetstream connection is, as reported by the streaming thread
+#[derive(Debug, Clone)]
+pub enum ConnectionState {
+    /// First connection attempt, nothing received yet
+    Connecting,
+    /// Events are arriving
+    Connected,
+    /// The stream dropped; the next attempt starts at `retry_at`
+    Reconnecting { attempt: u32, retry_at: Instant },
+    /// Retries are exhausted; waiting for the user to retry
+    Failed { reason: String },
+    /// Disconnected on purpose while focus mode is on
+    Paused,
+}
+
+/// Keep reconnecting without a Retry click, for displays nobody is watching
+pub fn set_keep_retrying(keep_retrying: bool) {
+    KEEP_RETRYING.store(keep_retrying, Ordering::Relaxed);
+}
+
+/// Remember the time of the newest event, used to resume and to measure lag
+pub fn record_event(time_us: u64) {
+    CURSOR_US.fetch_max(time_us, Ordering::Relaxed);
+}
+
+/// Cursor to resume from after a disconnect, None before any event arrived
+pub fn cursor() -> Option<chrono::DateTime<chrono::Utc>> {
+    match CURSOR_US.load(Ordering::Relaxed) {
+        0 => None,
+        cursor => chrono::DateTime::from_timestamp_micros(cursor as i64),
+    }
+}
+
+/// How far the newest event is behind the wall clock
+pub fn lag() -> Option<Duration> {
+    cursor().map(|cursor| (chrono::Utc::now() - cursor).to_std().unwrap_or_default())
+}
+
+/// Whether the user asked to catch up, so the current session should end
+pub fn catch_up_pending() -> bool {
+    CATCH_UP_US.load(Ordering::Relaxed) != 0
+}
+
+/// Move the cursor back to a requested catch-up point; call before connecting
+pub fn apply_catch_up() {
+    let from = CATCH_UP_US.swap(0, Ordering::Relaxed);
+    if from != 0 {
+        CURSOR_US.store(from, Ordering::Relaxed);
+        BACKFILL_FROM_US.store(from, Ordering::Relaxed);
+    }
+}
+
+/// Fraction of the backfill done, None when streaming live
+fn backfill_progress() -> Option<f64> {
+    let from = BACKFILL_FROM_US.load(Ordering::Relaxed);
+    if from == 0 {
+        return None;
+    }
+
+    // Backfill ends once the stream is close to live again
+    if lag().is_some_and(|lag| lag <= LAG_WARNING) {
+        BACKFILL_FROM_US.store(0, Ordering::Relaxed);
+        return None;
+    }
+
+    let now = chrono::Utc::now().timestamp_micros() as f64;
+    let cursor = CURSOR_US.load(Ordering::Relaxed) as f64;
+    let from = from as f64;
+    Some(((cursor - from) / (now - from).max(1.0)).clamp(0.0, 1.0))
+}
+
+/// Cursor persisted to ~/.config/grapevine/jetstream.toml between runs
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SavedCursor {
+    /// Microsecond timestamp of the last event seen
+    time_us: u64,
+}
+
+fn cursor_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("jetstream.toml"))
+}
+
+/// Cursor saved by the previous run, if any
+fn load_saved_cursor() -> Option<chrono::DateTime<chrono::Utc>> {
+    let path = cursor_path()?;
+    let text = std::fs::read_to_string(&path).ok()?;
+
+    match toml::from_str::<SavedCursor>(&text) {
+        Ok(saved) if saved.time_us > 0 => chrono::DateTime::from_timestamp_micros(saved.time_us as i64),
+        Ok(_) => None,
+        Err(e) => {
+            tracing::warn!("Failed to parse Jetstream cursor at {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Persist the newest cursor so the next run can catch up from it
+pub fn save_cursor() {
+    let time_us = CURSOR_US.load(Ordering::Relaxed);
+    if time_us == 0 {
+        // Nothing received this run; keep the previous run's cursor
+        return;
+    }
+
+    let Some(path) = cursor_path() else {
+        return;
+    };
+
+    if let Some(parent) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            tracing::warn!("Failed to create config directory {}: {}", parent.display(), e);
+            return;
+        }
+    }
+
+    match toml::to_string_pretty(&SavedCursor { time_us }) {
+        Ok(text) => {
+            if let Err(e) = std::fs::write(&path, text) {
+                tracing::warn!("Failed to save Jetstream cursor to {}: {}", path.display(), e);
+            }
+        }
+        Err(e) => tracing::warn!("Failed to serialize Jetstream cursor: {}", e),
+    }
+}
+
+/// Delay before reconnect attempt `attempt` (1-based), doubling up to MAX_DELAY
+fn backoff(attempt: u32) -> Duration {
+    BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(MAX_DELAY)
+}
+
+/// Streaming thread's end: reports state changes and waits for retries
+pub struct ConnectionReporter {
+    state_sender: flume::Sender<ConnectionState>,
+    retry_receiver: flume::Receiver<()>,
+}
+
+/// UI's end: receives state changes and asks for retries
+pub struct ConnectionMonitor {
+    state_receiver: flume::Receiver<ConnectionState>,
+    retry_sender: flume::Sender<()>,
+}
+
+pub fn channel() -> (ConnectionReporter, ConnectionMonitor) {
+    let (state_sender, state_receiver) = flume::unbounded();
+    let (retry_sender, retry_receiver) = flume::unbounded();
+    (
+        ConnectionReporter { state_sender, retry_receiver },
+        ConnectionMonitor { state_receiver, retry_sender },
+    )
+}
+
+/// How a streaming session ended
+pub enum SessionEnd {
+    /// The stream stopped after delivering `received` posts
+    Disconnected { received: u64 },
+    /// The connection could not be set up at all
+    Error(String),
+    /// The user asked to catch up; reconnect from the new cursor right away
+    Restart,
+    /// Focus mode was turned on; stay disconnected until it's off
+    Paused,
+    /// The UI dropped its receiver; stop streaming for good
+    Closed,
+}
+
+impl ConnectionReporter {
+    pub fn report(&self, state: ConnectionState) {
+        let _ = self.state_sender.send(state);
+    }
+
+    /// Block until the retry time passes or the user asks to retry now.
+    /// `None` waits for the user, or MAX_DELAY while `set_keep_retrying` is on.
+    /// Returns false once the UI is gone.
+    fn wait_for_retry(&self, delay: Option<Duration>) -> bool {
+        // Ignore clicks made before this wait started
+        while self.retry_receiver.try_recv().is_ok() {}
+
+        match delay {
+            Some(delay) => !matches!(
+                self.retry_receiver.recv_timeout(delay),
+                Err(flume::RecvTimeoutError::Disconnected)
+            ),
+            None => loop {
+                match self.retry_receiver.recv_timeout(MAX_DELAY) {
+                    Ok(()) => break true,
+                    Err(flume::RecvTimeoutError::Disconnected) => break false,
+                    Err(flume::RecvTimeoutError::Timeout) if KEEP_RETRYING.load(Ordering::Relaxed) => break true,
+                    Err(flume::RecvTimeoutError::Timeout) => {}
+                }
+            },
+        }
+    }
+
+    /// Run `session` until it ends, reconnecting with exponential backoff and
+    /// giving up after MAX_RETRIES failures in a row until the user retries
+    pub fn run(&self, mut session: impl FnMut(&Self) -> SessionEnd) {
+        self.report(ConnectionState::Connecting);
+        let mut attempt = 0;
+
+        loop {
+            let reason = match session(self) {
+                SessionEnd::Closed => return,
+                SessionEnd::Restart => {
+                    attempt = 0;
+                    self.report(ConnectionState::Connecting);
+                    continue;
+                }
+                SessionEnd::Paused => {
+                    self.report(ConnectionState::Paused);
+                    while crate::focus::is_enabled() {
+                        if !self.wait_for_retry(Some(FOCUS_POLL)) {
+                            return;
+                        }
+                    }
+                    // Resume live; replaying the paused stretch would be the flood focus mode avoided
+                    CURSOR_US.store(0, Ordering::Relaxed);
+                    attempt = 0;
+                    self.report(ConnectionState::Connecting);
+                    continue;
+                }
+                SessionEnd::Disconnected { received } => {
+                    // A session that delivered posts resets the retry budget
+                    if received > 0 {
+                        attempt = 0;
+                    }
+                    "Connection to Jetstream closed".to_string()
+                }
+                SessionEnd::Error(e) => e,
+            };
+            tracing::warn!("Jetstream disconnected: {}", reason);
+
+            attempt += 1;
+            if attempt > MAX_RETRIES {
+                self.report(ConnectionState::Failed { reason });
+                if !self.wait_for_retry(None) {
+                    return;
+                }
+                attempt = 0;
+                self.report(ConnectionState::Connecting);
+                continue;
+            }
+
+            let delay = backoff(attempt);
+            self.report(ConnectionState::Reconnecting {
+                attempt,
+                retry_at: Instant::now() + delay,
+            });
+            if !self.wait_for_retry(Some(delay)) {
+                return;
+            }
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds < 60 {
+        format!("{} s", seconds)
+    } else if seconds < 3600 {
+        format!("{} min", seconds / 60)
+    } else {
+        format!("{} h {} min", seconds / 3600, seconds % 3600 / 60)
+    }
+}
+
+/// Banner text for a state, or None when there is nothing to report
+fn describe(state: &ConnectionState) -> Option<String> {
+    let last_post = || {
+        cursor().map(|cursor| {
+            format!(
+                " Last post at {} UTC ({} ago).",
+                cursor.format("%H:%M:%S"),
+                format_duration(lag().unwrap_or_default())
+            )
+        })
+        .unwrap_or_default()
+    };
+
+    match state {
+        ConnectionState::Connecting | ConnectionState::Paused => None,
+        ConnectionState::Connected => {
+            let lag = lag().filter(|lag| *lag > LAG_WARNING)?;
+            Some(match backfill_progress() {
+                Some(progress) => format!(
+                    "Catching up from where you left off: {:.0}% done, {} behind live",
+                    progress * 100.0,
+                    format_duration(lag)
+                ),
+                None => format!("Catching up: the firehose is {} behind live", format_duration(lag)),
+            })
+        }
+        ConnectionState::Reconnecting { attempt, retry_at } => Some(format!(
+            "Firehose disconnected, reconnecting in {} (attempt {} of {}).{}",
+            format_duration(retry_at.saturating_duration_since(Instant::now())),
+            attempt,
+            MAX_RETRIES,
+            last_post()
+        )),
+        ConnectionState::Failed { reason } => Some(format!("Firehose stopped: {}.{}", reason, last_post())),
+    }
+}
+
+/// Banner for the top of the firehose view that appears while the stream is
+/// down or lagging, with a button to reconnect right away
+pub fn create_connection_banner(monitor: ConnectionMonitor) -> libadwaita::Banner {
+    let banner = libadwaita::Banner::builder()
+        .revealed(false)
+        .build();
+
+    let retry_sender = monitor.retry_sender.clone();
+    banner.connect_button_clicked(move |_| {
+        let _ = retry_sender.send(());
+    });
+
+    let state = Rc::new(RefCell::new(ConnectionState::Connecting));
+
+    let update = {
+        let banner = banner.clone();
+        let state = state.clone();
+        move || {
+            let state = state.borrow();
+            match describe(&state) {
+                Some(title) => {
+                    banner.set_title(&title);
+                    banner.set_revealed(true);
+                }
+                None => banner.set_revealed(false),
+            }
+            banner.set_button_label(match *state {
+                ConnectionState::Reconnecting { .. } => Some("Retry Now"),
+                ConnectionState::Failed { .. } => Some("Retry"),
+                _ => None,
+            });
+        }
+    };
+    let update = Rc::new(update);
+
+    let update_for_states = update.clone();
+    let retry_sender = monitor.retry_sender.clone();
+    glib::spawn_future_local(async move {
+        while let Ok(new_state) = monitor.state_receiver.recv_async().await {
+            if let ConnectionState::Failed { reason } = &new_state {
+                let retry_sender = retry_sender.clone();
+                crate::toasts::show_failure("Jetstream", &format!("Firehose stopped: {}", reason), move || {
+                    let _ = retry_sender.send(());
+                });
+            }
+            *state.borrow_mut() = new_state;
+            update_for_states();
+        }
+    });
+
+    // Refresh the countdown and lag
+    let banner_weak = banner.downgrade();
+    glib::timeout_add_seconds_local(1, move || {
+        if banner_weak.upgrade().is_none() {
+            return glib::ControlFlow::Break;
+        }
+        update();
+        glib::ControlFlow::Continue
+    });
+
+    banner
+}
+
+/// Bar offering to replay the posts missed since the last run, or None when the
+/// previous cursor is missing, recent, or too old for Jetstream to replay
+pub fn create_catch_up_bar(monitor: &ConnectionMonitor) -> Option<gtk::Revealer> {
+    let saved = load_saved_cursor()?;
+    let gap = (chrono::Utc::now() - saved).to_std().ok()?;
+    if !(MIN_CATCH_UP..=MAX_CATCH_UP).contains(&gap) {
+        return None;
+    }
+
+    let bar = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .build();
+    bar.add_css_class("catch-up-bar");
+
+    let label = Label::builder()
+        .label(format!(
+            "You missed {} of posts while Grapevine was closed. Streaming live.",
+            format_duration(gap)
+        ))
+        .xalign(0.0)
+        .hexpand(true)
+        .wrap(true)
+        .build();
+    bar.append(&label);
+
+    let live_button = gtk::Button::builder()
+        .label("Stay Live")
+        .build();
+    live_button.add_css_class("flat");
+    bar.append(&live_button);
+
+    let catch_up_button = gtk::Button::builder()
+        .label("Catch Up")
+        .tooltip_text(format!("Replay posts since {} UTC", saved.format("%H:%M:%S")))
+        .build();
+    catch_up_button.add_css_class("suggested-action");
+    bar.append(&catch_up_button);
+
+    let revealer = gtk::Revealer::builder()
+        .child(&bar)
+        .reveal_child(true)
+        .build();
+
+    let revealer_for_live = revealer.clone();
+    live_button.connect_clicked(move |_| {
+        revealer_for_live.set_reveal_child(false);
+    });
+
+    let revealer_for_catch_up = revealer.clone();
+    let retry_sender = monitor.retry_sender.clone();
+    catch_up_button.connect_clicked(move |_| {
+        CATCH_UP_US.store(saved.timestamp_micros() as u64, Ordering::Relaxed);
+        // Wake the streaming thread if it is waiting to reconnect
+        let _ = retry_sender.send(());
+        revealer_for_catch_up.set_reveal_child(false);
+    });
+
+    // Staying live is the default; stop asking after a couple of minutes
+    let revealer_weak = revealer.downgrade();
+    glib::timeout_add_seconds_local_once(120, move || {
+        if let Some(revealer) = revealer_weak.upgrade() {
+            revealer.set_reveal_child(false);
+        }
+    });
+
+    Some(revealer)
+}
@@ -1,300 +1,133 @@
 use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Every ISO 3166-1 country (plus Kosovo) with its centroid, main timezone and currency.
+/// Names follow what GDELT reports as the source country; aliases cover ISO and common spellings.
+const COUNTRIES_CSV: &str = include_str!("../resources/countries.csv");
+
+/// One row of the bundled country dataset
+#[derive(Debug, Clone)]
+pub struct CountryInfo {
+    /// ISO 3166-1 alpha-2 code
+    pub code: String,
+    pub name: String,
+    pub aliases: Vec<String>,
+    /// Approximate geographic center
+    pub latitude: f64,
+    pub longitude: f64,
+    /// IANA timezone of the capital (or most populous zone)
+    pub timezone: String,
+    /// ISO 4217 currency code; empty for uninhabited territories
+    pub currency: String,
+    /// Value for GDELT's sourcecountry: operator, the name lowercased without spaces
+    pub gdelt_name: String,
+}
+
+struct CountryRegistry {
+    countries: Vec<CountryInfo>,
+    /// Lowercased code, name and aliases to an index into `countries`
+    index: HashMap<String, usize>,
+}
+
+fn parse_countries(csv: &str) -> Vec<CountryInfo> {
+    let mut countries = Vec::new();
+
+    // The dataset has no quoted fields, so a plain split is enough
+    for (line_number, line) in csv.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').collect();
+        let [code, name, aliases, latitude, longitude, timezone, currency] = fields[..] else {
+            tracing::warn!("Skipping malformed country row {}: {}", line_number + 1, line);
+            continue;
+        };
+
+        let (Ok(latitude), Ok(longitude)) = (latitude.parse::<f64>(), longitude.parse::<f64>()) else {
+            tracing::warn!("Skipping country row {} with invalid coordinates", line_number + 1);
+            continue;
+        };
+
+        countries.push(CountryInfo {
+            code: code.to_string(),
+            name: name.to_string(),
+            aliases: aliases
+                .split(';')
+                .filter(|alias| !alias.is_empty())
+                .map(str::to_string)
+                .collect(),
+            latitude,
+            longitude,
+            timezone: timezone.to_string(),
+            currency: currency.to_string(),
+            gdelt_name: name
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect(),
+        });
+    }
+
+    countries
+}
+
+fn registry() -> &'static CountryRegistry {
+    static REGISTRY: OnceLock<CountryRegistry> = OnceLock::new();
+
+    REGISTRY.get_or_init(|| {
+        let countries = parse_countries(COUNTRIES_CSV);
+
+        let mut index = HashMap::new();
+        for (i, country) in countries.iter().enumerate() {
+            let keys = [&country.code, &country.name].into_iter().chain(country.aliases.iter());
+            for key in keys {
+                // Codes and names take precedence over aliases shared with another country
+                index.entry(key.to_lowercase()).or_insert(i);
+            }
+        }
+
+        CountryRegistry { countries, index }
+    })
+}
+
+/// All countries in the dataset, sorted by code
+pub fn all_countries() -> &'static [CountryInfo] {
+    &registry().countries
+}
+
+/// Look up a country by ISO code, name or alias, ignoring case
+pub fn lookup_country(country: &str) -> Option<&'static CountryInfo> {
+    let registry = registry();
+    registry
+        .index
+        .get(&country.trim().to_lowercase())
+        .map(|&i| &registry.countries[i])
+}
 
 /// Get the timezone for a country
 /// Returns the timezone string (e.g., "America/New_York") or None if not available
 pub fn get_country_timezone(country: &str) -> Option<&'static str> {
-    let timezones: HashMap<&str, &str> = [
-        // Major countries
-        ("United States", "America/New_York"),
-        ("United Kingdom", "Europe/London"),
-        ("Canada", "America/Toronto"),
-        ("Australia", "Australia/Sydney"),
-        ("Germany", "Europe/Berlin"),
-        ("France", "Europe/Paris"),
-        ("Italy", "Europe/Rome"),
-        ("Spain", "Europe/Madrid"),
-        ("Russia", "Europe/Moscow"),
-        ("China", "Asia/Shanghai"),
-        ("Japan", "Asia/Tokyo"),
-        ("India", "Asia/Kolkata"),
-        ("Brazil", "America/Sao_Paulo"),
-        ("Mexico", "America/Mexico_City"),
-        ("Argentina", "America/Argentina/Buenos_Aires"),
-        ("South Africa", "Africa/Johannesburg"),
-        ("Egypt", "Africa/Cairo"),
-        ("Nigeria", "Africa/Lagos"),
-        ("Kenya", "Africa/Nairobi"),
-        ("Saudi Arabia", "Asia/Riyadh"),
-        ("United Arab Emirates", "Asia/Dubai"),
-        ("Turkey", "Europe/Istanbul"),
-        ("Israel", "Asia/Jerusalem"),
-        ("Sweden", "Europe/Stockholm"),
-        ("Norway", "Europe/Oslo"),
-        ("Finland", "Europe/Helsinki"),
-        ("Denmark", "Europe/Copenhagen"),
-        ("Netherlands", "Europe/Amsterdam"),
-        ("Belgium", "Europe/Brussels"),
-        ("Switzerland", "Europe/Zurich"),
-        ("Austria", "Europe/Vienna"),
-        ("Poland", "Europe/Warsaw"),
-        ("Czech Republic", "Europe/Prague"),
-        ("Greece", "Europe/Athens"),
-        ("Portugal", "Europe/Lisbon"),
-        ("Ireland", "Europe/Dublin"),
-        ("New Zealand", "Pacific/Auckland"),
-        ("Singapore", "Asia/Singapore"),
-        ("Hong Kong", "Asia/Hong_Kong"),
-        ("South Korea", "Asia/Seoul"),
-        ("Thailand", "Asia/Bangkok"),
-        ("Malaysia", "Asia/Kuala_Lumpur"),
-        ("Indonesia", "Asia/Jakarta"),
-        ("Philippines", "Asia/Manila"),
-        ("Vietnam", "Asia/Ho_Chi_Minh"),
-        ("Ukraine", "Europe/Kiev"),
-        ("Romania", "Europe/Bucharest"),
-        ("Hungary", "Europe/Budapest"),
-        ("Chile", "America/Santiago"),
-        ("Colombia", "America/Bogota"),
-        ("Peru", "America/Lima"),
-        ("Venezuela", "America/Caracas"),
-        ("Pakistan", "Asia/Karachi"),
-        ("Bangladesh", "Asia/Dhaka"),
-        ("Ethiopia", "Africa/Addis_Ababa"),
-        ("Iran", "Asia/Tehran"),
-        ("Iraq", "Asia/Baghdad"),
-        ("Afghanistan", "Asia/Kabul"),
-        ("Qatar", "Asia/Qatar"),
-        ("Kuwait", "Asia/Kuwait"),
-        ("Oman", "Asia/Muscat"),
-        ("Lebanon", "Asia/Beirut"),
-        ("Jordan", "Asia/Amman"),
-        ("Syria", "Asia/Damascus"),
-        ("Yemen", "Asia/Aden"),
-        ("Taiwan", "Asia/Taipei"),
-    ].iter().cloned().collect();
-
-    timezones.get(country).copied()
+    lookup_country(country)
+        .map(|info| info.timezone.as_str())
+        .filter(|timezone| !timezone.is_empty())
 }
 
-/// Get the currency code for a country
-/// Returns the ISO 4217 currency code or None if not available
+/// Get the ISO 4217 currency code for a country, e.g. "EUR"
 pub fn get_country_currency(country: &str) -> Option<&'static str> {
-    let currencies: HashMap<&str, &str> = [
-        // Major countries
-        ("United States", "USD"),
-        ("United Kingdom", "GBP"),
-        ("Canada", "CAD"),
-        ("Australia", "AUD"),
-        ("Germany", "EUR"),
-        ("France", "EUR"),
-        ("Italy", "EUR"),
-        ("Spain", "EUR"),
-        ("Russia", "RUB"),
-        ("China", "CNY"),
-        ("Japan", "JPY"),
-        ("India", "INR"),
-        ("Brazil", "BRL"),
-        ("Mexico", "MXN"),
-        ("Argentina", "ARS"),
-        ("South Africa", "ZAR"),
-        ("Egypt", "EGP"),
-        ("Nigeria", "NGN"),
-        ("Kenya", "KES"),
-        ("Saudi Arabia", "SAR"),
-        ("United Arab Emirates", "AED"),
-        ("Turkey", "TRY"),
-        ("Israel", "ILS"),
-        ("Sweden", "SEK"),
-        ("Norway", "NOK"),
-        ("Finland", "EUR"),
-        ("Denmark", "DKK"),
-        ("Netherlands", "EUR"),
-        ("Belgium", "EUR"),
-        ("Switzerland", "CHF"),
-        ("Austria", "EUR"),
-        ("Poland", "PLN"),
-        ("Czech Republic", "CZK"),
-        ("Greece", "EUR"),
-        ("Portugal", "EUR"),
-        ("Ireland", "EUR"),
-        ("New Zealand", "NZD"),
-        ("Singapore", "SGD"),
-        ("Hong Kong", "HKD"),
-        ("South Korea", "KRW"),
-        ("Thailand", "THB"),
-        ("Malaysia", "MYR"),
-        ("Indonesia", "IDR"),
-        ("Philippines", "PHP"),
-        ("Vietnam", "VND"),
-        ("Ukraine", "UAH"),
-        ("Romania", "RON"),
-        ("Hungary", "HUF"),
-        ("Chile", "CLP"),
-        ("Colombia", "COP"),
-        ("Peru", "PEN"),
-        ("Venezuela", "VES"),
-        ("Pakistan", "PKR"),
-        ("Bangladesh", "BDT"),
-        ("Ethiopia", "ETB"),
-        ("Iran", "IRR"),
-        ("Iraq", "IQD"),
-        ("Afghanistan", "AFN"),
-        ("Qatar", "QAR"),
-        ("Kuwait", "KWD"),
-        ("Oman", "OMR"),
-        ("Lebanon", "LBP"),
-        ("Jordan", "JOD"),
-        ("Syria", "SYP"),
-        ("Yemen", "YER"),
-        ("Taiwan", "TWD"),
-    ].iter().cloned().collect();
-
-    currencies.get(country).copied()
+    lookup_country(country)
+        .map(|info| info.currency.as_str())
+        .filter(|currency| !currency.is_empty())
 }
 
 /// Get approximate coordinates for a country code or name
 /// Returns (latitude, longitude) or None if country is unknown
 pub fn get_country_coordinates(country: &str) -> Option<(f64, f64)> {
-    // Map of country codes and names to approximate center coordinates
-    let coords: HashMap<&str, (f64, f64)> = [
-        // Country codes
-        ("US", (37.0902, -95.7129)),
-        ("GB", (55.3781, -3.4360)),
-        ("CA", (56.1304, -106.3468)),
-        ("AU", (-25.2744, 133.7751)),
-        ("DE", (51.1657, 10.4515)),
-        ("FR", (46.2276, 2.2137)),
-        ("IT", (41.8719, 12.5674)),
-        ("ES", (40.4637, -3.7492)),
-        ("RU", (61.5240, 105.3188)),
-        ("CN", (35.8617, 104.1954)),
-        ("JP", (36.2048, 138.2529)),
-        ("IN", (20.5937, 78.9629)),
-        ("BR", (-14.2350, -51.9253)),
-        ("MX", (23.6345, -102.5528)),
-        ("AR", (-38.4161, -63.6167)),
-        ("ZA", (-30.5595, 22.9375)),
-        ("EG", (26.8206, 30.8025)),
-        ("NG", (9.0820, 8.6753)),
-        ("KE", (-0.0236, 37.9062)),
-        ("SA", (23.8859, 45.0792)),
-        ("AE", (23.4241, 53.8478)),
-        ("TR", (38.9637, 35.2433)),
-        ("IL", (31.0461, 34.8516)),
-        ("SE", (60.1282, 18.6435)),
-        ("NO", (60.4720, 8.4689)),
-        ("FI", (61.9241, 25.7482)),
-        ("DK", (56.2639, 9.5018)),
-        ("NL", (52.1326, 5.2913)),
-        ("BE", (50.5039, 4.4699)),
-        ("CH", (46.8182, 8.2275)),
-        ("AT", (47.5162, 14.5501)),
-        ("PL", (51.9194, 19.1451)),
-        ("CZ", (49.8175, 15.4730)),
-        ("GR", (39.0742, 21.8243)),
-        ("PT", (39.3999, -8.2245)),
-        ("IE", (53.4129, -8.2439)),
-        ("NZ", (-40.9006, 174.8860)),
-        ("SG", (1.3521, 103.8198)),
-        ("HK", (22.3193, 114.1694)),
-        ("KR", (35.9078, 127.7669)),
-        ("TH", (15.8700, 100.9925)),
-        ("MY", (4.2105, 101.9758)),
-        ("ID", (-0.7893, 113.9213)),
-        ("PH", (12.8797, 121.7740)),
-        ("VN", (14.0583, 108.2772)),
-        ("UA", (48.3794, 31.1656)),
-        ("RO", (45.9432, 24.9668)),
-        ("HU", (47.1625, 19.5033)),
-        ("CL", (-35.6751, -71.5430)),
-        ("CO", (4.5709, -74.2973)),
-        ("PE", (-9.1900, -75.0152)),
-        ("VE", (6.4238, -66.5897)),
-        ("PK", (30.3753, 69.3451)),
-        ("BD", (23.6850, 90.3563)),
-        ("NG", (9.0820, 8.6753)),
-        ("ET", (9.1450, 40.4897)),
-        ("KR", (35.9078, 127.7669)),
-        ("IR", (32.4279, 53.6880)),
-        ("IQ", (33.2232, 43.6793)),
-        ("AF", (33.9391, 67.7100)),
-        ("QA", (25.3548, 51.1839)),
-        ("KW", (29.3117, 47.4818)),
-        ("OM", (21.4735, 55.9754)),
-        ("LB", (33.8547, 35.8623)),
-        ("JO", (30.5852, 36.2384)),
-        ("SY", (34.8021, 38.9968)),
-        ("YE", (15.5527, 48.5164)),
-        ("TW", (23.6978, 120.9605)),
-
-        // Full country names (what GDELT returns)
-        ("United States", (37.0902, -95.7129)),
-        ("United Kingdom", (55.3781, -3.4360)),
-        ("Canada", (56.1304, -106.3468)),
-        ("Australia", (-25.2744, 133.7751)),
-        ("Germany", (51.1657, 10.4515)),
-        ("France", (46.2276, 2.2137)),
-        ("Italy", (41.8719, 12.5674)),
-        ("Spain", (40.4637, -3.7492)),
-        ("Russia", (61.5240, 105.3188)),
-        ("China", (35.8617, 104.1954)),
-        ("Japan", (36.2048, 138.2529)),
-        ("India", (20.5937, 78.9629)),
-        ("Brazil", (-14.2350, -51.9253)),
-        ("Mexico", (23.6345, -102.5528)),
-        ("Argentina", (-38.4161, -63.6167)),
-        ("South Africa", (-30.5595, 22.9375)),
-        ("Egypt", (26.8206, 30.8025)),
-        ("Nigeria", (9.0820, 8.6753)),
-        ("Kenya", (-0.0236, 37.9062)),
-        ("Saudi Arabia", (23.8859, 45.0792)),
-        ("United Arab Emirates", (23.4241, 53.8478)),
-        ("Turkey", (38.9637, 35.2433)),
-        ("Israel", (31.0461, 34.8516)),
-        ("Sweden", (60.1282, 18.6435)),
-        ("Norway", (60.4720, 8.4689)),
-        ("Finland", (61.9241, 25.7482)),
-        ("Denmark", (56.2639, 9.5018)),
-        ("Netherlands", (52.1326, 5.2913)),
-        ("Belgium", (50.5039, 4.4699)),
-        ("Switzerland", (46.8182, 8.2275)),
-        ("Austria", (47.5162, 14.5501)),
-        ("Poland", (51.9194, 19.1451)),
-        ("Czech Republic", (49.8175, 15.4730)),
-        ("Greece", (39.0742, 21.8243)),
-        ("Portugal", (39.3999, -8.2245)),
-        ("Ireland", (53.4129, -8.2439)),
-        ("New Zealand", (-40.9006, 174.8860)),
-        ("Singapore", (1.3521, 103.8198)),
-        ("Hong Kong", (22.3193, 114.1694)),
-        ("South Korea", (35.9078, 127.7669)),
-        ("Thailand", (15.8700, 100.9925)),
-        ("Malaysia", (4.2105, 101.9758)),
-        ("Indonesia", (-0.7893, 113.9213)),
-        ("Philippines", (12.8797, 121.7740)),
-        ("Vietnam", (14.0583, 108.2772)),
-        ("Ukraine", (48.3794, 31.1656)),
-        ("Romania", (45.9432, 24.9668)),
-        ("Hungary", (47.1625, 19.5033)),
-        ("Chile", (-35.6751, -71.5430)),
-        ("Colombia", (4.5709, -74.2973)),
-        ("Peru", (-9.1900, -75.0152)),
-        ("Venezuela", (6.4238, -66.5897)),
-        ("Pakistan", (30.3753, 69.3451)),
-        ("Bangladesh", (23.6850, 90.3563)),
-        ("Ethiopia", (9.1450, 40.4897)),
-        ("Iran", (32.4279, 53.6880)),
-        ("Iraq", (33.2232, 43.6793)),
-        ("Afghanistan", (33.9391, 67.7100)),
-        ("Qatar", (25.3548, 51.1839)),
-        ("Kuwait", (29.3117, 47.4818)),
-        ("Oman", (21.4735, 55.9754)),
-        ("Lebanon", (33.8547, 35.8623)),
-        ("Jordan", (30.5852, 36.2384)),
-        ("Syria", (34.8021, 38.9968)),
-        ("Yemen", (15.5527, 48.5164)),
-        ("Taiwan", (23.6978, 120.9605)),
-    ].iter().cloned().collect();
+    lookup_country(country).map(|info| (info.latitude, info.longitude))
+}
 
-    coords.get(country).copied()
+/// Look up a typed country code, name or alias
+/// Returns the dataset's name for the country along with its coordinates
+pub fn find_country_coordinates(query: &str) -> Option<(&'static str, (f64, f64))> {
+    lookup_country(query).map(|info| (info.name.as_str(), (info.latitude, info.longitude)))
 }
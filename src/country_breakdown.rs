@@ -0,0 +1,171 @@
+use gtk::prelude::*;
+use gtk::{glib, Label, Orientation};
+
+use crate::data::{GdeltArticle, GdeltTimelineResponse};
+use crate::error::FetchError;
+use crate::gdelt::{self, GdeltMode, GdeltQuery, Timespan};
+use crate::digest::top_counts;
+
+/// Bars shown per chart
+const MAX_BARS: usize = 6;
+
+/// Coverage volume per language for a country's outlets over the Global Affairs
+/// window, from GDELT's timelinelang mode. The article list is filtered to
+/// English, so the languages can't be read off the fetched result set.
+async fn fetch_language_volumes(query: &str, country: &str) -> Result<Vec<(String, f64)>, FetchError> {
+    let url = GdeltQuery::new(query)
+        .or_default_terms("world")
+        .country(country)
+        .mode(GdeltMode::TimelineLang)
+        .timespan(Timespan::hours(2))
+        .url()?;
+
+    let data: GdeltTimelineResponse = gdelt::fetch_json(&url).await?;
+
+    let mut volumes: Vec<(String, f64)> = data
+        .timeline
+        .into_iter()
+        .map(|series| {
+            let total = series.data.iter().map(|point| point.value).sum();
+            (series.series, total)
+        })
+        .filter(|(_, total)| *total > 0.0)
+        .collect();
+    volumes.sort_by(|a, b| b.1.total_cmp(&a.1));
+    Ok(volumes)
+}
+
+/// Horizontal bar chart: one row per (label, value), with the value's share of the total
+fn create_bar_chart(title: &str, values: &[(String, f64)]) -> gtk::Box {
+    let section = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .build();
+
+    let title_label = Label::builder()
+        .label(title)
+        .xalign(0.0)
+        .build();
+    title_label.add_css_class("heading");
+    section.append(&title_label);
+
+    let total: f64 = values.iter().map(|(_, value)| value).sum();
+    if total <= 0.0 {
+        let empty_label = Label::builder()
+            .label("No data for this window")
+            .xalign(0.0)
+            .build();
+        empty_label.add_css_class("dim-label");
+        empty_label.add_css_class("caption");
+        section.append(&empty_label);
+        return section;
+    }
+
+    let grid = gtk::Grid::builder()
+        .row_spacing(4)
+        .column_spacing(8)
+        .build();
+
+    for (row, (label, value)) in values.iter().take(MAX_BARS).enumerate() {
+        let share = value / total;
+
+        let name_label = Label::builder()
+            .label(label.as_str())
+            .xalign(0.0)
+            .width_chars(12)
+            .max_width_chars(16)
+            .ellipsize(gtk::pango::EllipsizeMode::End)
+            .tooltip_text(label.as_str())
+            .build();
+        name_label.add_css_class("caption");
+
+        let bar = gtk::ProgressBar::builder()
+            .fraction(share)
+            .hexpand(true)
+            .valign(gtk::Align::Center)
+            .build();
+
+        let share_label = Label::builder()
+            .label(format!("{:.0}%", share * 100.0))
+            .xalign(1.0)
+            .width_chars(4)
+            .build();
+        share_label.add_css_class("caption");
+        share_label.add_css_class("numeric");
+
+        grid.attach(&name_label, 0, row as i32, 1, 1);
+        grid.attach(&bar, 1, row as i32, 1, 1);
+        grid.attach(&share_label, 2, row as i32, 1, 1);
+    }
+
+    section.append(&grid);
+    section
+}
+
+/// Languages counted from the fetched articles, used when GDELT can't be reached
+fn article_languages(articles: &[GdeltArticle]) -> Vec<(String, f64)> {
+    top_counts(articles.iter().map(|article| article.language.as_str()))
+        .into_iter()
+        .map(|(language, count)| (language, count as f64))
+        .collect()
+}
+
+/// Body of the country popover's Sources tab: coverage by language and the
+/// outlets behind the fetched articles. Languages load when the tab is first shown.
+pub fn create_breakdown(country: &str, articles: &[GdeltArticle]) -> gtk::Box {
+    let container = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(12)
+        .margin_top(6)
+        .margin_start(6)
+        .margin_end(6)
+        .build();
+
+    let languages_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .build();
+    let loading_label = Label::builder()
+        .label("Loading languages…")
+        .xalign(0.0)
+        .build();
+    loading_label.add_css_class("dim-label");
+    loading_label.add_css_class("caption");
+    languages_box.append(&loading_label);
+    container.append(&languages_box);
+
+    let domains: Vec<(String, f64)> = top_counts(articles.iter().map(|article| article.domain.as_str()))
+        .into_iter()
+        .map(|(domain, count)| (domain, count as f64))
+        .collect();
+    container.append(&create_bar_chart("Top Outlets", &domains));
+
+    let country = country.to_string();
+    let fallback_languages = article_languages(articles);
+    let loaded = std::cell::Cell::new(false);
+    container.connect_map(move |_| {
+        if loaded.replace(true) {
+            return;
+        }
+
+        let languages_box = languages_box.clone();
+        let country = country.clone();
+        let fallback_languages = fallback_languages.clone();
+        glib::spawn_future_local(async move {
+            let query = crate::global_affairs::current_query();
+            let chart = match fetch_language_volumes(&query, &country).await {
+                Ok(volumes) => create_bar_chart("Coverage by Language", &volumes),
+                Err(e) => {
+                    tracing::warn!("No language volumes for {}: {}", country, e);
+                    create_bar_chart("Languages in Results", &fallback_languages)
+                }
+            };
+
+            while let Some(child) = languages_box.first_child() {
+                languages_box.remove(&child);
+            }
+            languages_box.append(&chart);
+        });
+    });
+
+    container
+}
@@ -0,0 +1,112 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::data::FirehosePost;
+
+/// Posts kept per country in the rolling index
+const MAX_POSTS_PER_COUNTRY: usize = 25;
+
+/// Posts older than this drop out of the index
+const MAX_POST_AGE: Duration = Duration::from_secs(30 * 60);
+
+/// Country names (as GDELT reports them) with the lowercase terms that count as a mention
+const COUNTRY_TERMS: &[(&str, &[&str])] = &[
+    ("Afghanistan", &["afghanistan", "afghan", "afghans", "kabul"]),
+    ("Argentina", &["argentina", "argentine", "argentinian", "buenos aires"]),
+    ("Australia", &["australia", "australian", "australians", "canberra"]),
+    ("Brazil", &["brazil", "brazilian", "brazilians", "brasil"]),
+    ("Canada", &["canada", "canadian", "canadians", "ottawa"]),
+    ("China", &["china", "chinese", "beijing"]),
+    ("Colombia", &["colombia", "colombian", "bogota"]),
+    ("Egypt", &["egypt", "egyptian", "egyptians", "cairo"]),
+    ("Ethiopia", &["ethiopia", "ethiopian", "addis ababa"]),
+    ("France", &["france", "french", "paris"]),
+    ("Germany", &["germany", "german", "germans", "berlin"]),
+    ("India", &["india", "indian", "indians", "new delhi"]),
+    ("Indonesia", &["indonesia", "indonesian", "jakarta"]),
+    ("Iran", &["iran", "iranian", "iranians", "tehran"]),
+    ("Iraq", &["iraq", "iraqi", "iraqis", "baghdad"]),
+    ("Israel", &["israel", "israeli", "israelis", "jerusalem", "tel aviv"]),
+    ("Italy", &["italy", "italian", "italians", "rome"]),
+    ("Japan", &["japan", "japanese", "tokyo"]),
+    ("Kenya", &["kenya", "kenyan", "kenyans", "nairobi"]),
+    ("Mexico", &["mexico", "mexican", "mexicans"]),
+    ("Netherlands", &["netherlands", "dutch", "amsterdam"]),
+    ("Nigeria", &["nigeria", "nigerian", "nigerians", "lagos", "abuja"]),
+    ("North Korea", &["north korea", "north korean", "pyongyang"]),
+    ("Pakistan", &["pakistan", "pakistani", "pakistanis", "islamabad"]),
+    ("Philippines", &["philippines", "filipino", "filipinos", "manila"]),
+    ("Poland", &["poland", "polish", "warsaw"]),
+    ("Russia", &["russia", "russian", "russians", "moscow", "kremlin"]),
+    ("Saudi Arabia", &["saudi arabia", "saudi", "saudis", "riyadh"]),
+    ("South Africa", &["south africa", "south african", "johannesburg", "pretoria"]),
+    ("South Korea", &["south korea", "south korean", "seoul"]),
+    ("Spain", &["spain", "spanish", "madrid"]),
+    ("Sudan", &["sudan", "sudanese", "khartoum"]),
+    ("Sweden", &["sweden", "swedish", "stockholm"]),
+    ("Syria", &["syria", "syrian", "syrians", "damascus"]),
+    ("Taiwan", &["taiwan", "taiwanese", "taipei"]),
+    ("Turkey", &["turkey", "turkish", "ankara", "istanbul", "türkiye"]),
+    ("Ukraine", &["ukraine", "ukrainian", "ukrainians", "kyiv", "kiev"]),
+    ("United Arab Emirates", &["united arab emirates", "uae", "emirati", "dubai", "abu dhabi"]),
+    ("United Kingdom", &["united kingdom", "uk", "britain", "british", "london"]),
+    ("United States", &["united states", "usa", "american", "americans", "washington"]),
+    ("Venezuela", &["venezuela", "venezuelan", "venezuelans", "caracas"]),
+    ("Yemen", &["yemen", "yemeni", "houthi", "houthis", "sanaa"]),
+];
+
+struct IndexedPost {
+    post: FirehosePost,
+    seen: Instant,
+}
+
+thread_local! {
+    /// Rolling per-country index of recent firehose posts, filled from the UI thread
+    static INDEX: RefCell<HashMap<&'static str, VecDeque<IndexedPost>>> = RefCell::new(HashMap::new());
+}
+
+/// Whether `term` occurs in `text` as a whole word (or phrase)
+pub fn contains_term(text: &str, term: &str) -> bool {
+    text.match_indices(term).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + term.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+/// Add a post to the index of every country it mentions
+pub fn record(post: &FirehosePost) {
+    let text = post.text.to_lowercase();
+    let now = Instant::now();
+
+    INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        for (country, terms) in COUNTRY_TERMS {
+            if !terms.iter().any(|term| contains_term(&text, term)) {
+                continue;
+            }
+
+            let posts = index.entry(country).or_default();
+            posts.push_front(IndexedPost { post: post.clone(), seen: now });
+            posts.truncate(MAX_POSTS_PER_COUNTRY);
+        }
+    });
+}
+
+/// Recent posts mentioning a country, newest first
+pub fn recent_posts(country: &str) -> Vec<FirehosePost> {
+    INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        let Some(posts) = index.get_mut(country) else {
+            return Vec::new();
+        };
+
+        // Expire old posts lazily on read
+        while posts.back().is_some_and(|indexed| indexed.seen.elapsed() > MAX_POST_AGE) {
+            posts.pop_back();
+        }
+
+        posts.iter().map(|indexed| indexed.post.clone()).collect()
+    })
+}
@@ -0,0 +1,240 @@
+use gtk::prelude::*;
+use gtk::{glib, Label, Orientation};
+use libadwaita::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use crate::compare;
+use crate::coordinates::get_country_currency;
+use crate::data::GdeltArticle;
+use crate::gdelt::{self, GdeltQuery, GdeltSort, Timespan};
+use crate::global_affairs;
+
+/// Most articles the country-scoped search asks GDELT for
+const MORE_RECORDS: u32 = 100;
+
+thread_local! {
+    /// Navigation view holding the Global Affairs results and any country pages pushed on top
+    static NAVIGATION: RefCell<Option<libadwaita::NavigationView>> = const { RefCell::new(None) };
+}
+
+/// Put the Global Affairs results at the root of a navigation view that
+/// country pages are pushed onto
+pub fn create_navigation(results: &impl IsA<gtk::Widget>) -> libadwaita::NavigationView {
+    let root_page = libadwaita::NavigationPage::builder()
+        .title("Global Affairs")
+        .tag("results")
+        .child(results)
+        .build();
+
+    let navigation = libadwaita::NavigationView::builder()
+        .vexpand(true)
+        .build();
+    navigation.add(&root_page);
+
+    NAVIGATION.with(|current| *current.borrow_mut() = Some(navigation.clone()));
+    navigation
+}
+
+/// Open a page for a country showing every article it has in the current results,
+/// its local time and currency, and more of its coverage from a country-scoped search
+pub fn show_country(country: &str, articles: &[GdeltArticle], use_12_hour: Rc<RefCell<bool>>) {
+    let Some(navigation) = NAVIGATION.with(|current| current.borrow().clone()) else {
+        return;
+    };
+
+    let tag = format!("country-{}", country);
+    if navigation.visible_page().and_then(|page| page.tag()).as_deref() == Some(tag.as_str()) {
+        return;
+    }
+    // Page tags are unique, so a country already further down is reopened from the root
+    if navigation.find_page(&tag).is_some() {
+        navigation.pop_to_tag("results");
+    }
+
+    let page = libadwaita::NavigationPage::builder()
+        .title(country)
+        .tag(tag.as_str())
+        .child(&create_country_content(country, articles, use_12_hour))
+        .build();
+    navigation.push(&page);
+}
+
+fn create_section_heading(text: &str) -> Label {
+    let label = Label::builder()
+        .label(text)
+        .xalign(0.0)
+        .margin_top(12)
+        .build();
+    label.add_css_class("heading");
+    label
+}
+
+fn create_country_content(country: &str, articles: &[GdeltArticle], use_12_hour: Rc<RefCell<bool>>) -> gtk::Widget {
+    let content = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .margin_top(18)
+        .margin_bottom(18)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+
+    let title_row = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(10)
+        .build();
+    let flag_picture = gtk::Picture::builder()
+        .content_fit(gtk::ContentFit::Contain)
+        .can_shrink(true)
+        .width_request(48)
+        .height_request(32)
+        .visible(false)
+        .build();
+    flag_picture.add_css_class("country-flag");
+    title_row.append(&flag_picture);
+
+    let name_label = Label::builder()
+        .label(country)
+        .xalign(0.0)
+        .hexpand(true)
+        .build();
+    name_label.add_css_class("title-1");
+    title_row.append(&name_label);
+    content.append(&title_row);
+
+    if let Some(clock) = compare::create_clock(country, use_12_hour) {
+        content.append(&clock);
+    }
+
+    let briefing_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .visible(false)
+        .build();
+    briefing_box.add_css_class("country-briefing");
+    content.append(&briefing_box);
+    glib::spawn_future_local(global_affairs::load_country_briefing(
+        briefing_box,
+        flag_picture,
+        country.to_string(),
+    ));
+
+    if let Some(currency_code) = get_country_currency(country) {
+        content.append(&create_section_heading("Currency"));
+        content.append(&compare::create_currency_section(currency_code));
+    }
+
+    let mut latest = articles.to_vec();
+    latest.sort_by(|a, b| b.seendate.cmp(&a.seendate));
+    content.append(&create_section_heading(&format!("In Current Results ({})", latest.len())));
+    let results_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .build();
+    for article in &latest {
+        results_box.append(&global_affairs::create_popover_article_row(article));
+    }
+    content.append(&results_box);
+
+    content.append(&create_section_heading(&format!("More from {}", country)));
+    let more_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .build();
+    content.append(&more_box);
+
+    let shown: HashSet<String> = latest.into_iter().map(|article| article.url).collect();
+    glib::spawn_future_local(load_more_coverage(
+        more_box,
+        country.to_string(),
+        global_affairs::current_query(),
+        Rc::new(shown),
+    ));
+
+    let clamp = libadwaita::Clamp::builder()
+        .maximum_size(760)
+        .child(&content)
+        .build();
+    let scrolled = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .vexpand(true)
+        .child(&clamp)
+        .build();
+
+    // The window's own buttons are already in the main header bar
+    let header_bar = libadwaita::HeaderBar::builder()
+        .show_start_title_buttons(false)
+        .show_end_title_buttons(false)
+        .build();
+    let toolbar_view = libadwaita::ToolbarView::new();
+    toolbar_view.add_top_bar(&header_bar);
+    toolbar_view.set_content(Some(&scrolled));
+    toolbar_view.upcast()
+}
+
+/// Fill the "More from" section with the country's outlets' coverage of the
+/// current search over the past day, leaving out articles already listed
+async fn load_more_coverage(more_box: gtk::Box, country: String, query: String, shown: Rc<HashSet<String>>) {
+    while let Some(child) = more_box.first_child() {
+        more_box.remove(&child);
+    }
+    let spinner = gtk::Spinner::builder()
+        .spinning(true)
+        .halign(gtk::Align::Center)
+        .margin_top(12)
+        .build();
+    more_box.append(&spinner);
+
+    let mut gdelt_query = GdeltQuery::new(&query)
+        .or_default_terms("world")
+        .country(&country)
+        .timespan(Timespan::days(1))
+        .max_records(MORE_RECORDS)
+        .sort(GdeltSort::DateDesc);
+    if !global_affairs::search_settings().0 {
+        gdelt_query = gdelt_query.language("english");
+    }
+    let result = match gdelt_query.url() {
+        Ok(url) => gdelt::fetch_articles(&url).await,
+        Err(e) => Err(e.into()),
+    };
+    more_box.remove(&spinner);
+
+    let articles = match result {
+        Ok(articles) => articles,
+        Err(e) => {
+            tracing::warn!("No country-scoped coverage for {}: {}", country, e);
+            let error_label = Label::builder()
+                .label("Couldn't load more coverage")
+                .xalign(0.0)
+                .build();
+            error_label.add_css_class("dim-label");
+            more_box.append(&error_label);
+            crate::toasts::show_error(&e, move || {
+                glib::spawn_future_local(load_more_coverage(
+                    more_box.clone(),
+                    country.clone(),
+                    query.clone(),
+                    shown.clone(),
+                ));
+            });
+            return;
+        }
+    };
+
+    let new_articles: Vec<&GdeltArticle> = articles.iter().filter(|article| !shown.contains(&article.url)).collect();
+    if new_articles.is_empty() {
+        let empty_label = Label::builder()
+            .label(format!("No other coverage from {} in the past day", country))
+            .xalign(0.0)
+            .build();
+        empty_label.add_css_class("dim-label");
+        more_box.append(&empty_label);
+        return;
+    }
+    for article in new_articles {
+        more_box.append(&global_affairs::create_popover_article_row(article));
+    }
+}
@@ -0,0 +1,155 @@
+use gtk::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::domain_filter::normalize_domain;
+use crate::json_store::JsonStore;
+
+/// What a list says about one outlet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rating {
+    pub score: f64,
+    #[serde(default)]
+    pub notes: String,
+}
+
+/// A third-party domain rating list, imported from CSV
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingList {
+    /// Taken from the file name; importing a file of the same name replaces the list
+    pub name: String,
+    pub ratings: HashMap<String, Rating>,
+}
+
+thread_local! {
+    /// Imported lists, in import order; loaded from disk on first use
+    static LISTS: RefCell<Option<Vec<RatingList>>> = const { RefCell::new(None) };
+}
+
+/// Imported lists can hold thousands of outlets, so they're stored compactly
+const FILE: JsonStore = JsonStore::new("source_ratings.json", "source ratings").compact();
+
+fn load() -> Vec<RatingList> {
+    FILE.load()
+}
+
+fn save(lists: &[RatingList]) {
+    FILE.save(lists);
+}
+
+fn with_lists<T>(f: impl FnOnce(&mut Vec<RatingList>) -> T) -> T {
+    LISTS.with(|lists| f(lists.borrow_mut().get_or_insert_with(load)))
+}
+
+/// Names and sizes of the imported lists
+pub fn lists() -> Vec<(String, usize)> {
+    with_lists(|lists| lists.iter().map(|list| (list.name.clone(), list.ratings.len())).collect())
+}
+
+pub fn remove_list(name: &str) {
+    with_lists(|lists| {
+        lists.retain(|list| list.name != name);
+        save(lists);
+    });
+}
+
+/// Split a CSV line into fields, honouring double quotes so notes can hold commas
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|field| field.trim().to_string()).collect()
+}
+
+/// Ratings from CSV rows of domain, score and optional notes. A header row, or
+/// any row whose score isn't a number, is skipped.
+fn parse_csv(text: &str) -> HashMap<String, Rating> {
+    text.lines()
+        .filter_map(|line| {
+            let fields = split_csv_line(line);
+            let domain = normalize_domain(fields.first()?);
+            let score = fields.get(1)?.parse::<f64>().ok().filter(|score| score.is_finite())?;
+            let notes = fields.get(2..).map(|rest| rest.join(", ")).unwrap_or_default();
+            (!domain.is_empty()).then_some((domain, Rating { score, notes }))
+        })
+        .collect()
+}
+
+/// Import a CSV rating list, replacing any list of the same name; returns its name
+/// and how many outlets it rates
+pub fn import_csv(path: &Path) -> Result<(String, usize), String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let ratings = parse_csv(&text);
+    if ratings.is_empty() {
+        return Err("no rows with a domain and a numeric score".to_string());
+    }
+
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Ratings".to_string());
+    let count = ratings.len();
+    with_lists(|lists| {
+        lists.retain(|list| list.name != name);
+        lists.push(RatingList { name: name.clone(), ratings });
+        save(lists);
+    });
+    Ok((name, count))
+}
+
+/// The first list's rating for a domain, matching parent domains too so a
+/// rating for bbc.co.uk covers news.bbc.co.uk
+pub fn rating(domain: &str) -> Option<(String, Rating)> {
+    let domain = normalize_domain(domain);
+    with_lists(|lists| {
+        let mut candidate = domain.as_str();
+        loop {
+            if let Some((list, rating)) = lists
+                .iter()
+                .find_map(|list| list.ratings.get(candidate).map(|rating| (list, rating)))
+            {
+                return Some((list.name.clone(), rating.clone()));
+            }
+            candidate = candidate.split_once('.')?.1;
+        }
+    })
+}
+
+/// Badge with the outlet's score, naming the list and its notes in the tooltip
+pub fn create_badge(domain: &str) -> Option<gtk::Label> {
+    let (list_name, rating) = rating(domain)?;
+    let score = if rating.score.fract() == 0.0 {
+        format!("{:.0}", rating.score)
+    } else {
+        format!("{:.2}", rating.score)
+    };
+
+    let tooltip = if rating.notes.is_empty() {
+        format!("{} rates {} at {}", list_name, domain, score)
+    } else {
+        format!("{} rates {} at {}\n{}", list_name, domain, score, rating.notes)
+    };
+    let badge = gtk::Label::builder()
+        .label(format!("{} {}", list_name, score))
+        .tooltip_text(tooltip)
+        .build();
+    badge.add_css_class("badge");
+    badge.add_css_class("badge-credibility");
+    Some(badge)
+}
@@ -0,0 +1,79 @@
+use crate::data::{CoinGeckoMarketChart, CurrencyInfo, COINGECKO_API_URL};
+use crate::error::{get_json, FetchError};
+
+const SERVICE: &str = "CoinGecko";
+
+/// Cryptocurrencies offered beside the fiat currencies: code and CoinGecko coin ID
+pub const COINS: &[(&str, &str)] = &[("BTC", "bitcoin"), ("ETH", "ethereum")];
+
+/// Days of daily prices fetched for the trend, as for fiat pairs
+const TREND_DAYS: u32 = 14;
+
+/// Words that make a search about crypto, so country popovers show coin prices too
+const CRYPTO_TERMS: &[&str] = &[
+    "bitcoin", "btc", "ethereum", "eth", "crypto", "cryptocurrency", "blockchain", "stablecoin", "coinbase", "binance",
+];
+
+fn coin_id(code: &str) -> Option<&'static str> {
+    COINS.iter().find(|(coin, _)| *coin == code).map(|(_, id)| *id)
+}
+
+/// Whether a currency code is one of the supported coins
+pub fn is_crypto(code: &str) -> bool {
+    coin_id(code).is_some()
+}
+
+/// Whether a search is about cryptocurrency
+pub fn is_crypto_query(query: &str) -> bool {
+    query
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| CRYPTO_TERMS.contains(&word))
+}
+
+/// Daily prices of a coin over the trend window, priced in `vs_currency`
+async fn fetch_prices(coin: &str, vs_currency: &str) -> Result<Vec<f64>, FetchError> {
+    let url = format!("{}/coins/{}/market_chart", COINGECKO_API_URL, coin);
+    let request = crate::http::client().get(&url).query(&[
+        ("vs_currency", vs_currency.to_lowercase()),
+        ("days", TREND_DAYS.to_string()),
+        ("interval", "daily".to_string()),
+    ]);
+    let chart: CoinGeckoMarketChart = get_json(SERVICE, request).await?;
+    let prices: Vec<f64> = chart.prices.into_iter().map(|(_, price)| price).filter(|price| *price > 0.0).collect();
+    if prices.is_empty() {
+        return Err(FetchError::Empty { service: SERVICE });
+    }
+    Ok(prices)
+}
+
+/// Rate and trend of a pair with a coin on either side, in the same shape as
+/// Frankfurter's, so popovers, comparisons and the Markets page show it alike.
+/// A fiat base is priced by inverting the coin's price in it.
+pub async fn request_pair(base_currency: &str, target_currency: &str) -> Result<CurrencyInfo, FetchError> {
+    let trend_data = match (coin_id(base_currency), coin_id(target_currency)) {
+        (Some(coin), _) => fetch_prices(coin, target_currency).await?,
+        (None, Some(coin)) => fetch_prices(coin, base_currency).await?.into_iter().map(|price| 1.0 / price).collect(),
+        (None, None) => {
+            return Err(FetchError::InvalidRequest {
+                service: SERVICE,
+                message: format!("Neither {} nor {} is a cryptocurrency", base_currency, target_currency),
+            })
+        }
+    };
+
+    // The last price is the current one; daily points before it give the changes
+    let rate = trend_data.last().copied().ok_or(FetchError::Empty { service: SERVICE })?;
+    let change_from = |earlier: f64| ((rate - earlier) / earlier) * 100.0;
+    let change_24h = (trend_data.len() >= 2).then(|| change_from(trend_data[trend_data.len() - 2]));
+    let change_7d = trend_data.first().map(|first| change_from(*first));
+
+    Ok(CurrencyInfo {
+        code: base_currency.to_string(),
+        target: target_currency.to_string(),
+        rate,
+        change_24h,
+        change_7d,
+        trend_data,
+    })
+}
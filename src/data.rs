@@ -1,24 +1,77 @@
 use serde::Deserialize;
 use std::collections::HashMap;
 
+use crate::settings::RecordType;
+
 pub const APP_ID: &str = "com.toasterrepair.Grapevine";
 pub const GDELT_API_URL: &str = "https://api.gdeltproject.org/api/v2/doc/doc";
+pub const GDELT_GEO_API_URL: &str = "https://api.gdeltproject.org/api/v2/geo/geo";
+pub const APPVIEW_API_URL: &str = "https://public.api.bsky.app/xrpc";
+pub const COINGECKO_API_URL: &str = "https://api.coingecko.com/api/v3";
+pub const WORLD_BANK_API_URL: &str = "https://api.worldbank.org/v2";
+pub const USGS_EARTHQUAKE_FEED_URL: &str = "https://earthquake.usgs.gov/earthquakes/feed/v1.0/summary/2.5_day.geojson";
+pub const NOMINATIM_SEARCH_URL: &str = "https://nominatim.openstreetmap.org/search";
+pub const GDACS_EVENTS_URL: &str = "https://www.gdacs.org/gdacsapi/api/events/geteventlist/EVENTS4APP";
+pub const GDELT_EVENTS_LAST_UPDATE_URL: &str = "http://data.gdeltproject.org/gdeltv2/lastupdate.txt";
+
+/// Anything the firehose passes from the Jetstream thread to the UI
+#[derive(Debug, Clone)]
+pub enum StreamRecord {
+    Post(Box<FirehosePost>),
+    Action(StreamAction),
+}
+
+/// A like, repost or follow, shown in splits following that record type
+#[derive(Debug, Clone)]
+pub struct StreamAction {
+    pub kind: RecordType,
+    pub did: String,
+    /// at:// URI of the liked or reposted post, or the followed account's DID
+    pub subject: String,
+    pub timestamp: String,
+}
 
 #[derive(Debug, Clone)]
 pub struct FirehosePost {
     pub timestamp: String,
     pub did: String,
     pub rkey: String,
+    pub cid: String, // Record CID, needed to like or repost; empty for demo posts
     pub text: String,
     pub embed: Option<PostEmbed>,
     pub facets: Option<Vec<PostFacet>>,
+    pub sentiment: f32, // Compound score in -1.0..=1.0
+    pub reply: Option<ReplyContext>,
+    pub langs: Vec<String>, // Primary language subtags declared by the author, lowercased
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplyContext {
+    pub parent_uri: String, // at:// URI of the post being replied to
+    pub root_uri: String,   // at:// URI of the thread's first post
 }
 
 #[derive(Debug, Clone)]
 pub enum PostEmbed {
-    Images { count: usize, alt_texts: Vec<String> },
+    Images { count: usize, alt_texts: Vec<String>, cids: Vec<String> }, // Blob CIDs, for CDN URLs
     External { uri: String, title: String, description: String },
-    Video,
+    Video { cid: String, alt: String }, // Blob CID of the uploaded mp4
+    Record { uri: String, media: Option<Box<PostEmbed>> }, // Quoted record's at:// URI, plus any media posted alongside it
+}
+
+/// Full-size CDN URL of an image blob in a Bluesky post
+pub fn bsky_image_url(did: &str, cid: &str) -> String {
+    format!("https://cdn.bsky.app/img/feed_fullsize/plain/{}/{}@jpeg", did, cid)
+}
+
+/// HLS playlist Bluesky's video service transcodes an uploaded video into
+pub fn bsky_video_playlist_url(did: &str, cid: &str) -> String {
+    format!("https://video.bsky.app/watch/{}/{}/playlist.m3u8", urlencoding::encode(did), cid)
+}
+
+/// Poster frame for a Bluesky video
+pub fn bsky_video_thumbnail_url(did: &str, cid: &str) -> String {
+    format!("https://video.bsky.app/watch/{}/{}/thumbnail.jpg", urlencoding::encode(did), cid)
 }
 
 #[derive(Debug, Clone)]
@@ -57,6 +110,126 @@ pub struct GdeltResponse {
     pub articles: Vec<GdeltArticle>,
 }
 
+/// Response of the GDELT DOC API in timeline modes (e.g. mode=timelinevol)
+#[derive(Debug, Deserialize)]
+pub struct GdeltTimelineResponse {
+    #[serde(default)]
+    pub timeline: Vec<GdeltTimelineSeries>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GdeltTimelineSeries {
+    /// Series name in breakdown modes, e.g. the language for mode=timelinelang
+    #[serde(default)]
+    pub series: String,
+    #[serde(default)]
+    pub data: Vec<GdeltTimelinePoint>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GdeltTimelinePoint {
+    pub date: String, // e.g. 20240101T120000Z
+    pub value: f64,
+}
+
+/// Places mentioned in coverage, from the GEO API (mode=PointData, format=GeoJSON)
+#[derive(Debug, Deserialize, Clone)]
+pub struct GdeltGeoResponse {
+    #[serde(default)]
+    pub features: Vec<GdeltGeoFeature>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GdeltGeoFeature {
+    pub geometry: GdeltGeoGeometry,
+    #[serde(default)]
+    pub properties: GdeltGeoProperties,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GdeltGeoGeometry {
+    pub coordinates: Vec<f64>, // [lon, lat]
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct GdeltGeoProperties {
+    #[serde(default)]
+    pub name: String,
+    /// Articles mentioning the place
+    #[serde(default)]
+    pub count: u32,
+}
+
+/// A geocoding result from Nominatim's search endpoint (format=jsonv2)
+#[derive(Debug, Deserialize, Clone)]
+pub struct NominatimPlace {
+    pub lat: String, // Nominatim returns coordinates as strings
+    pub lon: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub addresstype: String, // e.g. "country", "state", "city"
+}
+
+/// A page summary from Wikipedia's REST API (/page/summary/{title})
+#[derive(Debug, Deserialize, Clone)]
+pub struct WikipediaSummary {
+    #[serde(default)]
+    pub title: String,
+    /// "standard" for articles, "disambiguation" for pages listing several
+    #[serde(rename = "type", default)]
+    pub page_type: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub extract: String,
+    /// Wikidata item of the page, e.g. "Q142"
+    #[serde(default)]
+    pub wikibase_item: String,
+    #[serde(default)]
+    pub content_urls: Option<WikipediaContentUrls>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct WikipediaContentUrls {
+    pub desktop: WikipediaPageUrl,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct WikipediaPageUrl {
+    pub page: String,
+}
+
+/// Statements about a Wikidata item from wbgetclaims, keyed by property, e.g. "P1082"
+#[derive(Debug, Deserialize, Clone)]
+pub struct WikidataClaims {
+    #[serde(default)]
+    pub claims: HashMap<String, Vec<WikidataClaim>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct WikidataClaim {
+    pub mainsnak: WikidataSnak,
+    /// "preferred", "normal" or "deprecated"
+    #[serde(default)]
+    pub rank: String,
+    #[serde(default)]
+    pub qualifiers: HashMap<String, Vec<WikidataSnak>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct WikidataSnak {
+    pub datavalue: Option<WikidataValue>,
+}
+
+/// A statement's value, whose shape depends on the property's type
+#[derive(Debug, Deserialize, Clone)]
+pub struct WikidataValue {
+    pub value: serde_json::Value,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct FrankfurterRates {
     #[serde(flatten)]
@@ -78,11 +251,218 @@ pub struct FrankfurterHistoricalResponse {
     pub rates: HashMap<String, FrankfurterRates>,
 }
 
+/// A coin's price history from CoinGecko's market_chart endpoint
+#[derive(Debug, Deserialize, Clone)]
+pub struct CoinGeckoMarketChart {
+    /// [unix milliseconds, price] pairs, oldest first; the last is the current price
+    #[serde(default)]
+    pub prices: Vec<(f64, f64)>,
+}
+
+/// One year's value of a World Bank indicator for a country
+#[derive(Debug, Deserialize, Clone)]
+pub struct WorldBankObservation {
+    /// The year, e.g. "2023"
+    pub date: String,
+    pub value: Option<f64>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CurrencyInfo {
+    /// Currency priced, e.g. "EUR" in EUR/USD
     pub code: String,
-    pub rate_to_usd: f64,
+    /// Currency the rate is quoted in
+    pub target: String,
+    /// Units of `target` per unit of `code`
+    pub rate: f64,
     pub change_24h: Option<f64>,
     pub change_7d: Option<f64>,
     pub trend_data: Vec<f64>,
 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GeoJsonPoint {
+    #[serde(default)]
+    pub coordinates: Vec<f64>, // [longitude, latitude, (depth)]
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UsgsProperties {
+    pub mag: Option<f64>,
+    pub place: Option<String>,
+    #[serde(default)]
+    pub time: i64, // Milliseconds since epoch
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub tsunami: i32,
+    pub alert: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UsgsFeature {
+    pub properties: UsgsProperties,
+    pub geometry: Option<GeoJsonPoint>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UsgsFeed {
+    #[serde(default)]
+    pub features: Vec<UsgsFeature>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct GdacsUrls {
+    #[serde(default)]
+    pub report: String,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct GdacsSeverity {
+    #[serde(default)]
+    pub severitytext: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GdacsProperties {
+    #[serde(default)]
+    pub eventtype: String, // EQ, TC, FL, VO, DR, WF
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub alertlevel: String, // Green, Orange, Red
+    #[serde(default)]
+    pub country: String,
+    #[serde(default)]
+    pub fromdate: String,
+    #[serde(default)]
+    pub url: GdacsUrls,
+    #[serde(default)]
+    pub severitydata: GdacsSeverity,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GdacsFeature {
+    pub properties: GdacsProperties,
+    pub geometry: Option<GeoJsonPoint>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GdacsFeed {
+    #[serde(default)]
+    pub features: Vec<GdacsFeature>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AppViewAuthor {
+    #[serde(default)]
+    pub did: String,
+    pub handle: String,
+    #[serde(rename = "displayName", default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub avatar: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AppViewPost {
+    #[serde(default)]
+    pub uri: String,
+    #[serde(default)]
+    pub cid: String,
+    pub author: AppViewAuthor,
+    #[serde(default)]
+    pub record: serde_json::Value,
+    #[serde(default)]
+    pub like_count: u64,
+    #[serde(default)]
+    pub repost_count: u64,
+    #[serde(default)]
+    pub reply_count: u64,
+    #[serde(default)]
+    pub indexed_at: String,
+}
+
+impl AppViewPost {
+    /// Text of the underlying app.bsky.feed.post record
+    pub fn text(&self) -> &str {
+        self.record.get("text").and_then(|t| t.as_str()).unwrap_or("")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AppViewPostsResponse {
+    #[serde(default)]
+    pub posts: Vec<AppViewPost>,
+}
+
+/// Results of app.bsky.feed.searchPosts, newest first when sorted by latest
+#[derive(Debug, Deserialize)]
+pub struct AppViewSearchResponse {
+    #[serde(default)]
+    pub posts: Vec<AppViewPost>,
+}
+
+/// An account as returned by app.bsky.actor.getProfile
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AppViewProfile {
+    #[serde(default)]
+    pub did: String,
+    pub handle: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// The bio
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub avatar: Option<String>,
+    #[serde(default)]
+    pub followers_count: u64,
+    #[serde(default)]
+    pub follows_count: u64,
+    #[serde(default)]
+    pub posts_count: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AppViewFeedItem {
+    pub post: AppViewPost,
+}
+
+/// Posts and reposts from app.bsky.feed.getAuthorFeed, newest first
+#[derive(Debug, Deserialize)]
+pub struct AppViewAuthorFeed {
+    #[serde(default)]
+    pub feed: Vec<AppViewFeedItem>,
+}
+
+/// An entry from app.bsky.notification.listNotifications
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BskyNotification {
+    pub uri: String,
+    pub cid: String,
+    pub author: AppViewAuthor,
+    /// like, repost, follow, mention, reply, quote, ...
+    pub reason: String,
+    /// The post that was liked or reposted
+    #[serde(default)]
+    pub reason_subject: Option<String>,
+    #[serde(default)]
+    pub record: serde_json::Value,
+    #[serde(default)]
+    pub is_read: bool,
+    pub indexed_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BskyNotificationsResponse {
+    #[serde(default)]
+    pub notifications: Vec<BskyNotification>,
+}
@@ -0,0 +1,123 @@
+use gtk::prelude::*;
+use gtk::gio;
+use std::cell::RefCell;
+
+use crate::data::{FirehosePost, APP_ID};
+
+/// The interface is named after the app ID, like the bus name GApplication owns
+pub const INTERFACE_NAME: &str = APP_ID;
+
+/// Object the scripting interface is exported at, the app ID as a path:
+/// the same object GApplication exports its own interfaces on
+pub fn object_path() -> String {
+    format!("/{}", APP_ID.replace('.', "/"))
+}
+
+/// Introspection data; the interface name is filled in from `INTERFACE_NAME`
+const INTERFACE_XML: &str = r#"
+<node>
+  <interface name="@INTERFACE_NAME@">
+    <method name="SearchNews">
+      <arg type="s" name="query" direction="in"/>
+    </method>
+    <method name="AddFirehoseFilter">
+      <arg type="s" name="keyword" direction="in"/>
+    </method>
+    <method name="ShowPage">
+      <arg type="s" name="page" direction="in"/>
+    </method>
+    <method name="GetCurrentQuery">
+      <arg type="s" name="query" direction="out"/>
+    </method>
+    <signal name="KeywordMatch">
+      <arg type="s" name="keyword"/>
+      <arg type="s" name="author"/>
+      <arg type="s" name="uri"/>
+      <arg type="s" name="text"/>
+    </signal>
+  </interface>
+</node>
+"#;
+
+thread_local! {
+    /// Session bus connection the interface is exported on, for emitting signals
+    static CONNECTION: RefCell<Option<gio::DBusConnection>> = const { RefCell::new(None) };
+}
+
+/// Export the scripting interface on the application's bus connection, e.g.
+///
+/// `gdbus call --session --dest com.toasterrepair.Grapevine --object-path /com/toasterrepair/Grapevine --method com.toasterrepair.Grapevine.SearchNews "climate"`
+///
+/// Methods go through the same app actions as the menus and shortcuts.
+/// `current_query` is read for GetCurrentQuery.
+pub fn export(app: &gtk::Application, current_query: impl Fn() -> String + 'static) {
+    let Some(connection) = app.dbus_connection() else {
+        tracing::warn!("No D-Bus connection, scripting interface not exported");
+        return;
+    };
+
+    let interface = match gio::DBusNodeInfo::for_xml(&INTERFACE_XML.replace("@INTERFACE_NAME@", INTERFACE_NAME)) {
+        Ok(node) => node.lookup_interface(INTERFACE_NAME),
+        Err(e) => {
+            tracing::warn!("Invalid D-Bus interface description: {}", e);
+            return;
+        }
+    };
+    let Some(interface) = interface else {
+        return;
+    };
+
+    let app_weak = app.downgrade();
+    let registration = connection
+        .register_object(&object_path(), &interface)
+        .method_call(move |_, sender, _, _, method, parameters, invocation| {
+            let Some(app) = app_weak.upgrade() else {
+                invocation.return_dbus_error("org.freedesktop.DBus.Error.Failed", "Grapevine is shutting down");
+                return;
+            };
+            tracing::debug!("D-Bus call {} from {}", method, sender.unwrap_or("unknown"));
+
+            let argument = parameters.child_value(0).get::<String>().unwrap_or_default();
+            let action = match method {
+                "SearchNews" => "search",
+                "AddFirehoseFilter" => "add-filter",
+                "ShowPage" => "switch-view",
+                "GetCurrentQuery" => {
+                    invocation.return_value(Some(&(current_query(),).to_variant()));
+                    return;
+                }
+                _ => {
+                    invocation.return_dbus_error("org.freedesktop.DBus.Error.UnknownMethod", "No such method");
+                    return;
+                }
+            };
+
+            if argument.trim().is_empty() {
+                invocation.return_dbus_error("org.freedesktop.DBus.Error.InvalidArgs", "Argument is empty");
+                return;
+            }
+            app.activate_action(action, Some(&argument.trim().to_variant()));
+            invocation.return_value(None);
+        })
+        .build();
+
+    match registration {
+        Ok(_) => CONNECTION.with(|current| *current.borrow_mut() = Some(connection)),
+        Err(e) => tracing::warn!("Failed to export the scripting interface: {}", e),
+    }
+}
+
+/// Tell listeners a firehose post matched a split's keyword
+pub fn emit_keyword_match(keyword: &str, post: &FirehosePost) {
+    CONNECTION.with(|connection| {
+        let Some(ref connection) = *connection.borrow() else {
+            return;
+        };
+        let uri = format!("at://{}/app.bsky.feed.post/{}", post.did, post.rkey);
+        let parameters = (keyword, post.did.as_str(), uri.as_str(), post.text.as_str()).to_variant();
+        if let Err(e) = connection.emit_signal(None, &object_path(), INTERFACE_NAME, "KeywordMatch", Some(&parameters)) {
+            tracing::debug!("Failed to emit KeywordMatch: {}", e);
+        }
+    });
+}
+
@@ -0,0 +1,232 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::data::{FacetType, FirehosePost, PostFacet};
+
+/// Set once at startup by `--demo`
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Average synthetic posts per second
+const POSTS_PER_SECOND: u64 = 20;
+
+/// Outlets the canned articles come from: (domain, source country, language)
+const OUTLETS: &[(&str, &str, &str)] = &[
+    ("example-times.example", "United States", "English"),
+    ("dailyledger.example", "United States", "English"),
+    ("thechronicle.example", "United Kingdom", "English"),
+    ("lemonde-demo.example", "France", "French"),
+    ("tagesblatt.example", "Germany", "German"),
+    ("asahi-demo.example", "Japan", "Japanese"),
+    ("folha-demo.example", "Brazil", "Portuguese"),
+    ("hindustan-demo.example", "India", "English"),
+    ("nation-demo.example", "Kenya", "English"),
+    ("abc-demo.example", "Australia", "English"),
+    ("globe-demo.example", "Canada", "English"),
+    ("contentfarm.example", "United States", "English"),
+];
+
+/// Headline templates; {} is replaced with a country or topic
+const HEADLINES: &[&str] = &[
+    "Leaders gather for climate summit as {} pledges new targets",
+    "Markets rally after {} central bank holds rates steady",
+    "Flooding displaces thousands across {}",
+    "Elections in {} draw record turnout",
+    "{} announces trade agreement with regional partners",
+    "Protests continue in {} over fuel prices",
+    "Tech firms in {} face new privacy rules",
+    "Heatwave strains power grid in {}",
+    "{} unveils plan to expand high-speed rail",
+    "Aid agencies warn of food shortages in {}",
+];
+
+const POST_TEMPLATES: &[(&str, &str)] = &[
+    ("Watching the {} coverage tonight, this is a big one", "en"),
+    ("Can't believe what's happening with {} right now", "en"),
+    ("Great news about {} this morning!", "en"),
+    ("Terrible situation developing around {}, stay safe everyone", "en"),
+    ("Thread on why {} matters more than people think 🧵", "en"),
+    ("Anyone else following {}? Thoughts?", "en"),
+    ("Siguiendo las noticias sobre {} desde aquí", "es"),
+    ("Die Lage in {} wird immer ernster", "de"),
+    ("{} のニュースを見ています", "ja"),
+    ("Acompanhando tudo sobre {} hoje", "pt"),
+];
+
+const TOPICS: &[&str] = &[
+    "the climate summit", "the election", "the flooding", "the rail plan",
+    "the trade deal", "the heatwave", "the protests", "the rate decision",
+];
+
+const HASHTAGS: &[&str] = &["news", "climate", "politics", "economy", "breaking", "weather"];
+
+/// Turn on demo mode for the rest of the session
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether GDELT and the firehose are served from canned and synthetic data
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Small xorshift generator; demo data only needs to look varied, not be random
+struct Rng(u64);
+
+impl Rng {
+    fn from_time() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or(0x2545_f491_4f6c_dd1d);
+        Self(seed | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+/// Canned DOC API response for a request URL, as GDELT would send it
+pub fn gdelt_response(url: &str) -> String {
+    let mut rng = Rng::from_time();
+    if url.contains("/geo/") {
+        geo_response(&mut rng)
+    } else if url.contains("mode=timelinelang") {
+        timeline_response(&mut rng, &["English", "French", "German", "Spanish", "Japanese"])
+    } else if url.contains("mode=timelinevol") {
+        timeline_response(&mut rng, &["Volume Intensity"])
+    } else {
+        articles_response(&mut rng)
+    }
+}
+
+fn articles_response(rng: &mut Rng) -> String {
+    let now = chrono::Utc::now();
+    let articles: Vec<serde_json::Value> = (0..50)
+        .map(|index| {
+            let (domain, country, language) = *rng.pick(OUTLETS);
+            let subject = if rng.below(2) == 0 { country } else { *rng.pick(TOPICS) };
+            let title = rng.pick(HEADLINES).replace("{}", subject);
+            let seen = now - chrono::Duration::minutes(rng.below(120) as i64);
+            serde_json::json!({
+                "url": format!("https://{}/demo/{}", domain, index),
+                "title": title,
+                "seendate": seen.format("%Y%m%dT%H%M%SZ").to_string(),
+                "socialimage": "",
+                "domain": domain,
+                "language": language,
+                "sourcecountry": country,
+            })
+        })
+        .collect();
+
+    serde_json::json!({ "articles": articles }).to_string()
+}
+
+/// Places scattered around the demo outlets' countries, as the GEO API's PointData
+fn geo_response(rng: &mut Rng) -> String {
+    let features: Vec<serde_json::Value> = (0..120)
+        .filter_map(|_| {
+            let (_, country, _) = *rng.pick(OUTLETS);
+            let (latitude, longitude) = crate::coordinates::get_country_coordinates(country)?;
+            let jitter = |rng: &mut Rng| (rng.below(600) as f64 - 300.0) / 100.0;
+            Some(serde_json::json!({
+                "type": "Feature",
+                "properties": { "name": country, "count": 1 + rng.below(40) },
+                "geometry": {
+                    "type": "Point",
+                    "coordinates": [longitude + jitter(rng), latitude + jitter(rng)],
+                },
+            }))
+        })
+        .collect();
+
+    serde_json::json!({ "type": "FeatureCollection", "features": features }).to_string()
+}
+
+fn timeline_response(rng: &mut Rng, series: &[&str]) -> String {
+    let now = chrono::Utc::now();
+    let timeline: Vec<serde_json::Value> = series
+        .iter()
+        .map(|name| {
+            let data: Vec<serde_json::Value> = (0..24)
+                .rev()
+                .map(|hours_ago| {
+                    let date = now - chrono::Duration::hours(hours_ago);
+                    serde_json::json!({
+                        "date": date.format("%Y%m%dT%H%M%SZ").to_string(),
+                        "value": rng.below(1000) as f64 / 100.0,
+                    })
+                })
+                .collect();
+            serde_json::json!({ "series": name, "data": data })
+        })
+        .collect();
+
+    serde_json::json!({ "timeline": timeline }).to_string()
+}
+
+/// Endless stream of made-up posts shaped like the ones parsed from Jetstream
+pub struct DemoFirehose {
+    rng: Rng,
+    sequence: u64,
+}
+
+impl DemoFirehose {
+    pub fn new() -> Self {
+        Self { rng: Rng::from_time(), sequence: 0 }
+    }
+
+    /// Pause before the next post, jittered around POSTS_PER_SECOND
+    pub fn next_delay(&mut self) -> Duration {
+        let mean_ms = 1000 / POSTS_PER_SECOND;
+        Duration::from_millis(mean_ms / 2 + self.rng.below(mean_ms as usize) as u64)
+    }
+
+    pub fn next_post(&mut self) -> FirehosePost {
+        self.sequence += 1;
+
+        let (template, lang) = *self.rng.pick(POST_TEMPLATES);
+        let topic = *self.rng.pick(TOPICS);
+        let mut text = template.replace("{}", topic);
+
+        // Hashtags get a tag facet like real posts, with byte offsets
+        let mut facets = Vec::new();
+        if self.rng.below(3) == 0 {
+            let tag = *self.rng.pick(HASHTAGS);
+            text.push(' ');
+            let start = text.len();
+            text.push('#');
+            text.push_str(tag);
+            facets.push(PostFacet {
+                start,
+                end: text.len(),
+                facet_type: FacetType::Tag(tag.to_string()),
+            });
+        }
+
+        FirehosePost {
+            timestamp: chrono::Utc::now().format("%H:%M:%S").to_string(),
+            did: format!("did:plc:demo{:04}", self.rng.below(500)),
+            rkey: format!("demo{:x}", self.sequence),
+            cid: String::new(),
+            sentiment: crate::sentiment::score(&text),
+            text,
+            embed: None,
+            facets: (!facets.is_empty()).then_some(facets),
+            reply: None,
+            langs: vec![lang.to_string()],
+        }
+    }
+}
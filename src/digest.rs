@@ -0,0 +1,318 @@
+use gtk::prelude::*;
+use gtk::glib;
+use libadwaita::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::clustering::{cluster_stories, StoryCluster};
+use crate::data::{CurrencyInfo, GdeltArticle};
+use crate::error::FetchError;
+use crate::gdelt::{self, GdeltQuery, GdeltSort, Timespan};
+use crate::export::escape_html;
+use crate::settings::{Settings, WatchedPair};
+
+/// Stories, domains and countries listed per search
+const TOP_STORIES: usize = 10;
+const TOP_SOURCES: usize = 8;
+
+/// Volume chart dimensions in pixels
+const CHART_WIDTH: f64 = 600.0;
+const CHART_HEIGHT: f64 = 160.0;
+
+/// A week of coverage for one search
+pub struct Digest {
+    pub query: String,
+    pub article_count: usize,
+    pub stories: Vec<StoryCluster>,
+    pub volume: Vec<(chrono::NaiveDateTime, f64)>,
+    pub top_domains: Vec<(String, usize)>,
+    pub top_countries: Vec<(String, usize)>,
+}
+
+/// GDELT query for a saved search over the past week, matching the Global Affairs view's defaults
+fn week_query(query: &str) -> GdeltQuery {
+    GdeltQuery::new(query)
+        .or_default_terms("world")
+        .language("english")
+        .timespan(Timespan::days(7))
+}
+
+async fn fetch_week_articles(query: &str) -> Result<Vec<GdeltArticle>, FetchError> {
+    let url = week_query(query).max_records(250).sort(GdeltSort::HybridRel).url()?;
+    gdelt::fetch_articles(&url).await
+}
+
+async fn fetch_week_volume(query: &str) -> Result<Vec<(chrono::NaiveDateTime, f64)>, FetchError> {
+    gdelt::fetch_timeline(&week_query(query)).await
+}
+
+/// Fetch the past week of coverage for a search; fails if the articles could not be fetched
+pub async fn fetch_digest(query: &str) -> Result<Digest, FetchError> {
+    let articles = fetch_week_articles(query).await?;
+    // The chart is optional; a digest without it is still useful
+    let volume = fetch_week_volume(query).await.unwrap_or_else(|e| {
+        tracing::warn!("No digest volume for '{}': {}", query, e);
+        Vec::new()
+    });
+
+    Ok(Digest {
+        query: query.to_string(),
+        article_count: articles.len(),
+        top_domains: top_counts(articles.iter().map(|article| article.domain.as_str())),
+        top_countries: top_counts(articles.iter().map(|article| article.sourcecountry.as_str())),
+        stories: top_stories(articles),
+        volume,
+    })
+}
+
+/// The largest stories of the week
+fn top_stories(articles: Vec<GdeltArticle>) -> Vec<StoryCluster> {
+    let mut stories = cluster_stories(articles);
+    // Stable sort keeps GDELT's relevance order among equally sized stories
+    stories.sort_by_key(|story| std::cmp::Reverse(story.size));
+    stories.truncate(TOP_STORIES);
+    stories
+}
+
+/// Stories listed under each country in the daily digest
+const DAILY_STORIES_PER_COUNTRY: usize = 3;
+
+/// Today at a glance, from what the app already has: the loaded articles,
+/// the watched currencies and the hashtags streaming past
+pub struct DailyDigest {
+    pub article_count: usize,
+    /// Countries with the most coverage, each with its largest stories
+    pub countries: Vec<(String, Vec<StoryCluster>)>,
+    pub currencies: Vec<CurrencyInfo>,
+    pub hashtags: Vec<(String, usize)>,
+}
+
+/// Whether GDELT saw the article in the past day; undated articles are kept
+fn seen_in_last_day(article: &GdeltArticle) -> bool {
+    chrono::NaiveDateTime::parse_from_str(&article.seendate, "%Y%m%dT%H%M%SZ")
+        .map(|seen| chrono::Utc::now().naive_utc() - seen < chrono::Duration::days(1))
+        .unwrap_or(true)
+}
+
+/// Gather the daily digest; currencies that fail to load are left out
+pub async fn build_daily_digest(pairs: &[WatchedPair]) -> DailyDigest {
+    let articles: Vec<GdeltArticle> = crate::global_affairs::current_articles()
+        .into_iter()
+        .filter(seen_in_last_day)
+        .collect();
+
+    let countries = top_counts(articles.iter().map(|article| article.sourcecountry.as_str()))
+        .into_iter()
+        .map(|(country, _)| {
+            let coverage: Vec<GdeltArticle> = articles
+                .iter()
+                .filter(|article| article.sourcecountry == country)
+                .cloned()
+                .collect();
+            let mut stories = cluster_stories(coverage);
+            stories.sort_by_key(|story| std::cmp::Reverse(story.size));
+            stories.truncate(DAILY_STORIES_PER_COUNTRY);
+            (country, stories)
+        })
+        .collect();
+
+    let mut currencies = Vec::new();
+    for pair in pairs {
+        match crate::global_affairs::fetch_currency_pair(&pair.base, &pair.target).await {
+            Ok(info) => currencies.push(info),
+            Err(e) => tracing::warn!("Leaving {}/{} out of the daily digest: {}", pair.base, pair.target, e),
+        }
+    }
+
+    DailyDigest {
+        article_count: articles.len(),
+        countries,
+        currencies,
+        hashtags: crate::history::top_hashtags(),
+    }
+}
+
+/// Most common non-empty values with their counts, most frequent first
+pub fn top_counts<'a>(values: impl Iterator<Item = &'a str>) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for value in values.filter(|value| !value.is_empty()) {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+
+    let mut counts: Vec<(String, usize)> = counts.into_iter().map(|(value, count)| (value.to_string(), count)).collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(TOP_SOURCES);
+    counts
+}
+
+/// "Jan 1"
+fn format_volume_date(date: chrono::NaiveDateTime) -> String {
+    date.format("%b %-d").to_string()
+}
+
+/// Standalone SVG bar chart of the search's share of global coverage
+pub fn volume_chart_svg(volume: &[(chrono::NaiveDateTime, f64)]) -> String {
+    let max = volume.iter().map(|(_, value)| *value).fold(0.0, f64::max);
+    let label_height = 20.0;
+    let plot_height = CHART_HEIGHT - label_height;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">",
+        CHART_WIDTH, CHART_HEIGHT
+    );
+    svg.push_str(&format!(
+        "<rect width=\"{}\" height=\"{}\" fill=\"#f6f5f4\"/>",
+        CHART_WIDTH, CHART_HEIGHT
+    ));
+
+    if !volume.is_empty() && max > 0.0 {
+        let bar_width = CHART_WIDTH / volume.len() as f64;
+        for (i, (_, value)) in volume.iter().enumerate() {
+            let height = value / max * (plot_height - 4.0);
+            svg.push_str(&format!(
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"#3584e4\"/>",
+                i as f64 * bar_width,
+                plot_height - height,
+                (bar_width - 0.5).max(0.5),
+                height
+            ));
+        }
+
+        let label_y = CHART_HEIGHT - 6.0;
+        svg.push_str(&format!(
+            "<text x=\"4\" y=\"{:.1}\" font-family=\"sans-serif\" font-size=\"11\" fill=\"#5e5c64\">{}</text>",
+            label_y,
+            escape_html(&format_volume_date(volume[0].0))
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\" font-family=\"sans-serif\" font-size=\"11\" fill=\"#5e5c64\">{}</text>",
+            CHART_WIDTH - 4.0,
+            label_y,
+            escape_html(&format_volume_date(volume[volume.len() - 1].0))
+        ));
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Ask which saved search to summarize, fetch its past week and export it
+pub fn present_digest_dialog(parent: &libadwaita::ApplicationWindow, settings: Rc<RefCell<Settings>>, current_query: &str) {
+    let mut searches = settings.borrow().saved_searches.clone();
+    if !searches.iter().any(|search| search == current_query) {
+        searches.push(current_query.to_string());
+    }
+
+    // With several saved searches the first choice covers all of them in one report
+    let saved_count = settings.borrow().saved_searches.len();
+    let mut choices: Vec<String> = Vec::new();
+    if saved_count > 1 {
+        choices.push("All saved searches".to_string());
+    }
+    choices.extend(searches.iter().map(|search| crate::export::digest_heading(search)));
+
+    let choice_refs: Vec<&str> = choices.iter().map(String::as_str).collect();
+    let dropdown = gtk::DropDown::from_strings(&choice_refs);
+
+    let dialog = libadwaita::MessageDialog::builder()
+        .transient_for(parent)
+        .modal(true)
+        .heading("Weekly Digest")
+        .body("Summarize the past week of coverage: top stories, volume, domains and countries. Star a search to save it.")
+        .extra_child(&dropdown)
+        .build();
+    dialog.add_responses(&[("cancel", "Cancel"), ("export", "Export…")]);
+    dialog.set_response_appearance("export", libadwaita::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("export"));
+    dialog.set_close_response("cancel");
+
+    let parent = parent.clone();
+    dialog.connect_response(None, move |_, response| {
+        if response != "export" {
+            return;
+        }
+
+        let selected = dropdown.selected() as usize;
+        let queries: Vec<String> = if saved_count > 1 && selected == 0 {
+            settings.borrow().saved_searches.clone()
+        } else {
+            let index = if saved_count > 1 { selected - 1 } else { selected };
+            searches.get(index).cloned().into_iter().collect()
+        };
+
+        let parent = parent.clone();
+        glib::spawn_future_local(async move {
+            let mut digests = Vec::new();
+            for query in &queries {
+                match fetch_digest(query).await {
+                    Ok(digest) => digests.push(digest),
+                    Err(e) => tracing::warn!("Skipping '{}' in weekly digest: {}", query, e),
+                }
+            }
+
+            if digests.is_empty() {
+                tracing::info!("Weekly digest has no searches to export");
+                return;
+            }
+
+            let title = format!("Grapevine Weekly Digest: {}", chrono::Local::now().format("%Y-%m-%d"));
+            let suggested_name = format!("grapevine-digest-{}.md", chrono::Local::now().format("%Y-%m-%d"));
+            crate::export::save_document(&parent, &suggested_name, move |is_html| {
+                if is_html {
+                    crate::export::digests_to_html(&title, &digests)
+                } else {
+                    crate::export::digests_to_markdown(&title, &digests)
+                }
+            });
+        });
+    });
+
+    dialog.present();
+}
+
+/// Offer the daily digest as Markdown on the clipboard or as a saved document
+pub fn present_daily_digest_dialog(parent: &libadwaita::ApplicationWindow, settings: Rc<RefCell<Settings>>) {
+    let dialog = libadwaita::MessageDialog::builder()
+        .transient_for(parent)
+        .modal(true)
+        .heading("Daily Digest")
+        .body("Today's top stories per country from the loaded articles, your watched currencies and the firehose's most used hashtags.")
+        .build();
+    dialog.add_responses(&[("cancel", "Cancel"), ("copy", "Copy Markdown"), ("save", "Save…")]);
+    dialog.set_response_appearance("save", libadwaita::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("save"));
+    dialog.set_close_response("cancel");
+
+    let parent = parent.clone();
+    dialog.connect_response(None, move |_, response| {
+        if response == "cancel" {
+            return;
+        }
+
+        let copy = response == "copy";
+        let pairs = settings.borrow().currency_watchlist.clone();
+        let parent = parent.clone();
+        glib::spawn_future_local(async move {
+            let digest = build_daily_digest(&pairs).await;
+            let title = format!("Grapevine Daily Digest: {}", chrono::Local::now().format("%Y-%m-%d"));
+
+            if copy {
+                parent.clipboard().set_text(&crate::export::daily_digest_to_markdown(&title, &digest));
+                crate::toasts::show("Daily digest copied");
+                return;
+            }
+
+            let suggested_name = format!("grapevine-daily-{}.md", chrono::Local::now().format("%Y-%m-%d"));
+            crate::export::save_document(&parent, &suggested_name, move |is_html| {
+                if is_html {
+                    crate::export::daily_digest_to_html(&title, &digest)
+                } else {
+                    crate::export::daily_digest_to_markdown(&title, &digest)
+                }
+            });
+        });
+    });
+
+    dialog.present();
+}
@@ -0,0 +1,387 @@
+use gtk::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use gtk::{glib, Label, Orientation, Popover};
+use libshumate::prelude::{MarkerExt, LocationExt};
+
+use crate::data::{GdacsFeature, GdacsFeed, UsgsFeature, UsgsFeed, GDACS_EVENTS_URL, USGS_EARTHQUAKE_FEED_URL};
+use crate::error::{get_json, FetchError};
+use crate::global_affairs::format_time_ago;
+
+/// The GDACS feed is large and slow to generate
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+thread_local! {
+    /// GDACS events already counted, so refreshes don't re-count ongoing alerts
+    static SEEN_ALERTS: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+/// Map layers for natural hazards: USGS earthquakes and GDACS disaster alerts
+#[derive(Clone)]
+pub struct DisasterLayers {
+    pub earthquakes: libshumate::MarkerLayer,
+    pub alerts: libshumate::MarkerLayer,
+}
+
+impl DisasterLayers {
+    /// Create both marker layers and add them to the map
+    pub fn new(map_view: &libshumate::Map, viewport: &libshumate::Viewport) -> Self {
+        let earthquakes = libshumate::MarkerLayer::new(viewport);
+        let alerts = libshumate::MarkerLayer::new(viewport);
+
+        map_view.add_layer(&earthquakes);
+        map_view.add_layer(&alerts);
+
+        Self { earthquakes, alerts }
+    }
+
+    /// Re-fetch both feeds and rebuild their markers
+    pub fn refresh(&self) {
+        let earthquakes = self.earthquakes.clone();
+        glib::spawn_future_local(async move {
+            fetch_earthquakes(earthquakes).await;
+        });
+
+        let alerts = self.alerts.clone();
+        glib::spawn_future_local(async move {
+            fetch_gdacs_alerts(alerts).await;
+        });
+    }
+}
+
+async fn fetch_feed<T: serde::de::DeserializeOwned>(service: &'static str, url: &str) -> Result<T, FetchError> {
+    get_json(service, crate::http::client().get(url).timeout(TIMEOUT)).await
+}
+
+/// Fetch the USGS "M2.5+ past day" feed and place a marker for each quake
+pub async fn fetch_earthquakes(layer: libshumate::MarkerLayer) {
+    let feed: UsgsFeed = match fetch_feed("USGS", USGS_EARTHQUAKE_FEED_URL).await {
+        Ok(feed) => feed,
+        Err(e) => {
+            tracing::warn!("Earthquake feed unavailable: {}", e);
+            return;
+        }
+    };
+
+    layer.remove_all();
+
+    tracing::debug!("Placing {} earthquake markers", feed.features.len());
+    for feature in feed.features.iter() {
+        create_earthquake_marker(&layer, feature);
+    }
+}
+
+/// Fetch current GDACS alerts (cyclones, floods, volcanoes, ...) and place a marker for each
+pub async fn fetch_gdacs_alerts(layer: libshumate::MarkerLayer) {
+    let feed: GdacsFeed = match fetch_feed("GDACS", GDACS_EVENTS_URL).await {
+        Ok(feed) => feed,
+        Err(e) => {
+            tracing::warn!("Disaster alerts unavailable: {}", e);
+            return;
+        }
+    };
+
+    layer.remove_all();
+
+    let new_alerts = SEEN_ALERTS.with(|seen| {
+        let mut seen = seen.borrow_mut();
+        feed.features
+            .iter()
+            .filter(|feature| {
+                let props = &feature.properties;
+                seen.insert(format!("{}:{}:{}", props.eventtype, props.name, props.fromdate))
+            })
+            .count()
+    });
+    crate::stats::record_alerts(new_alerts);
+
+    tracing::debug!("Placing {} GDACS alert markers", feed.features.len());
+    for feature in feed.features.iter() {
+        create_alert_marker(&layer, feature);
+    }
+}
+
+/// Marker diameter in pixels, growing with magnitude (M2.5 -> 14px, M7+ -> 48px)
+fn magnitude_to_size(magnitude: f64) -> i32 {
+    (14.0 + (magnitude - 2.5).max(0.0) * 7.5).min(48.0) as i32
+}
+
+fn create_earthquake_marker(layer: &libshumate::MarkerLayer, feature: &UsgsFeature) {
+    let Some(ref geometry) = feature.geometry else {
+        return;
+    };
+    if geometry.coordinates.len() < 2 {
+        return;
+    }
+
+    let lon = geometry.coordinates[0];
+    let lat = geometry.coordinates[1];
+    let depth = geometry.coordinates.get(2).copied();
+    let magnitude = feature.properties.mag.unwrap_or(0.0);
+
+    // Round marker whose size reflects the magnitude
+    let size = magnitude_to_size(magnitude);
+    let marker_button = gtk::Button::builder()
+        .label(format!("{:.1}", magnitude))
+        .width_request(size)
+        .height_request(size)
+        .tooltip_text(feature.properties.title.as_str())
+        .build();
+    marker_button.add_css_class("quake-marker");
+    if magnitude >= 6.0 {
+        marker_button.add_css_class("quake-major");
+    } else if magnitude >= 4.5 {
+        marker_button.add_css_class("quake-moderate");
+    }
+
+    // Popover with quake details
+    let popover_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    let title_label = Label::builder()
+        .label(format!("M{:.1} Earthquake", magnitude))
+        .xalign(0.0)
+        .build();
+    title_label.add_css_class("title-3");
+    popover_box.append(&title_label);
+
+    if let Some(ref place) = feature.properties.place {
+        let place_label = Label::builder()
+            .label(place.as_str())
+            .xalign(0.0)
+            .wrap(true)
+            .max_width_chars(40)
+            .build();
+        popover_box.append(&place_label);
+    }
+
+    // Badges for time, depth and alerts
+    let badges_box = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(4)
+        .build();
+
+    if let Some(dt) = chrono::DateTime::from_timestamp_millis(feature.properties.time) {
+        let time_badge = Label::builder()
+            .label(format_time_ago(dt.naive_utc()))
+            .build();
+        time_badge.add_css_class("badge");
+        time_badge.add_css_class("badge-time");
+        badges_box.append(&time_badge);
+    }
+
+    if let Some(depth) = depth {
+        let depth_badge = Label::builder()
+            .label(format!("{:.0} km deep", depth))
+            .build();
+        depth_badge.add_css_class("badge");
+        depth_badge.add_css_class("badge-neutral");
+        badges_box.append(&depth_badge);
+    }
+
+    if feature.properties.tsunami != 0 {
+        let tsunami_badge = Label::builder()
+            .label("Tsunami")
+            .build();
+        tsunami_badge.add_css_class("badge");
+        tsunami_badge.add_css_class("badge-negative");
+        badges_box.append(&tsunami_badge);
+    }
+
+    if let Some(ref alert) = feature.properties.alert {
+        let alert_badge = Label::builder()
+            .label(format!("PAGER {}", alert))
+            .build();
+        alert_badge.add_css_class("badge");
+        alert_badge.add_css_class("badge-lang");
+        badges_box.append(&alert_badge);
+    }
+
+    popover_box.append(&badges_box);
+
+    if !feature.properties.url.is_empty() {
+        let link_button = gtk::LinkButton::builder()
+            .uri(feature.properties.url.as_str())
+            .label("View on USGS")
+            .halign(gtk::Align::Start)
+            .build();
+        popover_box.append(&link_button);
+    }
+
+    place_marker_with_popover(layer, marker_button, popover_box, lat, lon);
+}
+
+/// Emoji used on the marker for each GDACS event type
+fn gdacs_event_icon(event_type: &str) -> &'static str {
+    match event_type {
+        "EQ" => "🌐",
+        "TC" => "🌀",
+        "FL" => "🌊",
+        "VO" => "🌋",
+        "DR" => "☀️",
+        "WF" => "🔥",
+        "TS" => "🌊",
+        _ => "⚠️",
+    }
+}
+
+fn gdacs_event_name(event_type: &str) -> &'static str {
+    match event_type {
+        "EQ" => "Earthquake",
+        "TC" => "Tropical Cyclone",
+        "FL" => "Flood",
+        "VO" => "Volcano",
+        "DR" => "Drought",
+        "WF" => "Wildfire",
+        "TS" => "Tsunami",
+        _ => "Disaster",
+    }
+}
+
+fn create_alert_marker(layer: &libshumate::MarkerLayer, feature: &GdacsFeature) {
+    let Some(ref geometry) = feature.geometry else {
+        return;
+    };
+    if geometry.coordinates.len() < 2 {
+        return;
+    }
+
+    let lon = geometry.coordinates[0];
+    let lat = geometry.coordinates[1];
+    let props = &feature.properties;
+
+    let marker_button = gtk::Button::builder()
+        .label(gdacs_event_icon(&props.eventtype))
+        .tooltip_text(props.name.as_str())
+        .build();
+    marker_button.add_css_class("disaster-marker");
+    match props.alertlevel.as_str() {
+        "Red" => marker_button.add_css_class("disaster-red"),
+        "Orange" => marker_button.add_css_class("disaster-orange"),
+        _ => marker_button.add_css_class("disaster-green"),
+    }
+
+    let popover_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    let title_label = Label::builder()
+        .label(gdacs_event_name(&props.eventtype))
+        .xalign(0.0)
+        .build();
+    title_label.add_css_class("title-3");
+    popover_box.append(&title_label);
+
+    let name_label = Label::builder()
+        .label(if props.description.is_empty() { &props.name } else { &props.description })
+        .xalign(0.0)
+        .wrap(true)
+        .max_width_chars(40)
+        .build();
+    popover_box.append(&name_label);
+
+    let badges_box = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(4)
+        .build();
+
+    if !props.alertlevel.is_empty() {
+        let level_badge = Label::builder()
+            .label(format!("{} alert", props.alertlevel))
+            .build();
+        level_badge.add_css_class("badge");
+        level_badge.add_css_class(match props.alertlevel.as_str() {
+            "Red" => "badge-negative",
+            "Orange" => "badge-lang",
+            _ => "badge-positive",
+        });
+        badges_box.append(&level_badge);
+    }
+
+    if !props.country.is_empty() {
+        let country_badge = Label::builder()
+            .label(props.country.as_str())
+            .build();
+        country_badge.add_css_class("badge");
+        country_badge.add_css_class("badge-country");
+        badges_box.append(&country_badge);
+    }
+
+    // GDACS dates look like 2025-10-24T07:45:00
+    if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(&props.fromdate, "%Y-%m-%dT%H:%M:%S") {
+        let time_badge = Label::builder()
+            .label(format_time_ago(dt))
+            .build();
+        time_badge.add_css_class("badge");
+        time_badge.add_css_class("badge-time");
+        badges_box.append(&time_badge);
+    }
+
+    popover_box.append(&badges_box);
+
+    if !props.severitydata.severitytext.is_empty() {
+        let severity_label = Label::builder()
+            .label(props.severitydata.severitytext.as_str())
+            .xalign(0.0)
+            .wrap(true)
+            .max_width_chars(40)
+            .build();
+        severity_label.add_css_class("caption");
+        severity_label.add_css_class("dim-label");
+        popover_box.append(&severity_label);
+    }
+
+    if !props.url.report.is_empty() {
+        let link_button = gtk::LinkButton::builder()
+            .uri(props.url.report.as_str())
+            .label("View GDACS report")
+            .halign(gtk::Align::Start)
+            .build();
+        popover_box.append(&link_button);
+    }
+
+    place_marker_with_popover(layer, marker_button, popover_box, lat, lon);
+}
+
+/// Attach a popover to the marker button and add it to the layer at the given location
+pub fn place_marker_with_popover(
+    layer: &libshumate::MarkerLayer,
+    marker_button: gtk::Button,
+    popover_content: gtk::Box,
+    lat: f64,
+    lon: f64,
+) {
+    let popover = Popover::builder()
+        .child(&popover_content)
+        .build();
+    popover.add_css_class("map-popover");
+
+    let popover_clone = popover.clone();
+    marker_button.connect_clicked(move |_| {
+        popover_clone.popup();
+    });
+
+    popover.set_parent(&marker_button);
+
+    // Clean up popover when button is destroyed
+    let popover_for_cleanup = popover.clone();
+    marker_button.connect_destroy(move |_| {
+        popover_for_cleanup.unparent();
+    });
+
+    let marker = libshumate::Marker::new();
+    marker.set_child(Some(&marker_button));
+    marker.set_location(lat, lon);
+    layer.add_marker(&marker);
+}
@@ -0,0 +1,91 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::settings::Settings;
+
+thread_local! {
+    /// App settings, registered at startup so each fetch reads the current domain rules
+    static SETTINGS: RefCell<Option<Rc<RefCell<Settings>>>> = const { RefCell::new(None) };
+}
+
+/// Make the app settings available to the Global Affairs results list
+pub fn register_settings(settings: Rc<RefCell<Settings>>) {
+    SETTINGS.with(|current| *current.borrow_mut() = Some(settings));
+}
+
+/// Which outlets' articles are listed, and how many of each before the rest are folded away
+#[derive(Debug, Clone)]
+pub struct DomainFilter {
+    /// Articles shown per domain before a "show more" expander; 0 shows them all
+    pub max_per_domain: usize,
+    blocked: Vec<String>,
+    allowed: Vec<String>,
+    /// Outlets an imported rating list scores below this are hidden; unrated ones stay
+    min_score: Option<f64>,
+}
+
+impl DomainFilter {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            max_per_domain: settings.max_articles_per_domain as usize,
+            blocked: settings.blocked_domains.clone(),
+            allowed: settings.allowed_domains.clone(),
+            min_score: settings.hide_low_rated_sources.then_some(settings.min_source_score),
+        }
+    }
+
+    /// Snapshot of the current settings; defaults when none are registered
+    pub fn current() -> Self {
+        SETTINGS.with(|current| match current.borrow().as_ref() {
+            Some(settings) => Self::from_settings(&settings.borrow()),
+            None => Self::from_settings(&Settings::default()),
+        })
+    }
+
+    /// Whether articles from this domain are listed at all. Blocked domains
+    /// always lose; a non-empty allowlist hides everything not on it, and a
+    /// minimum score hides rated outlets below it.
+    pub fn allows(&self, domain: &str) -> bool {
+        let domain = domain.to_lowercase();
+        if self.blocked.iter().any(|entry| matches_domain(&domain, entry)) {
+            return false;
+        }
+        if let Some(min_score) = self.min_score {
+            if crate::credibility::rating(&domain).is_some_and(|(_, rating)| rating.score < min_score) {
+                return false;
+            }
+        }
+        self.allowed.is_empty() || self.allowed.iter().any(|entry| matches_domain(&domain, entry))
+    }
+}
+
+/// An entry covers the domain itself and its subdomains
+fn matches_domain(domain: &str, entry: &str) -> bool {
+    domain == entry
+        || domain
+            .strip_suffix(entry)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// Bare lowercase domain from a domain or URL, without its scheme, path or a leading "www."
+pub fn normalize_domain(entry: &str) -> String {
+    let entry = entry.trim().to_lowercase();
+    let entry = entry
+        .strip_prefix("https://")
+        .or_else(|| entry.strip_prefix("http://"))
+        .unwrap_or(&entry);
+    let host = entry.split('/').next().unwrap_or_default();
+    host.strip_prefix("www.").unwrap_or(host).to_string()
+}
+
+/// Split a comma- or space-separated list typed into preferences into bare domains
+pub fn parse_domain_list(text: &str) -> Vec<String> {
+    let mut domains: Vec<String> = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(normalize_domain)
+        .filter(|domain| !domain.is_empty())
+        .collect();
+    domains.sort();
+    domains.dedup();
+    domains
+}
@@ -0,0 +1,176 @@
+use gtk::glib;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use crate::compose;
+use crate::json_store::JsonStore;
+use crate::toasts;
+
+/// How often scheduled drafts are checked for being due
+const SCHEDULER_INTERVAL_SECONDS: u32 = 20;
+
+const FILE: JsonStore = JsonStore::new("drafts.json", "drafts");
+
+/// A post saved in the composer to finish or send later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Draft {
+    /// Creation time in microseconds; identifies the draft
+    pub id: String,
+    pub text: String,
+    /// Link shown as a card, looked up again when the draft is sent
+    #[serde(default)]
+    pub card_url: Option<String>,
+    /// RFC 3339 time to post at while the app is running; None for a plain draft
+    #[serde(default)]
+    pub scheduled_at: Option<String>,
+    /// Why the last scheduled send failed
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// RFC 3339 timestamp
+    pub saved_at: String,
+}
+
+impl Draft {
+    pub fn new(text: &str, card_url: Option<String>) -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            id: now.timestamp_micros().to_string(),
+            text: text.to_string(),
+            card_url,
+            scheduled_at: None,
+            last_error: None,
+            saved_at: now.to_rfc3339(),
+        }
+    }
+
+    pub fn scheduled_time(&self) -> Option<chrono::DateTime<chrono::Local>> {
+        self.scheduled_at
+            .as_deref()
+            .and_then(|time| chrono::DateTime::parse_from_rfc3339(time).ok())
+            .map(|time| time.with_timezone(&chrono::Local))
+    }
+
+    fn is_due(&self) -> bool {
+        self.scheduled_time().is_some_and(|time| time <= chrono::Local::now())
+    }
+}
+
+thread_local! {
+    /// Drafts, newest first; loaded from disk on first use
+    static STORE: RefCell<Option<Vec<Draft>>> = const { RefCell::new(None) };
+    /// Called whenever drafts change so open composers can refresh
+    static LISTENERS: RefCell<Vec<Box<dyn Fn()>>> = const { RefCell::new(Vec::new()) };
+    /// Drafts being sent right now, so a slow send isn't started twice
+    static SENDING: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+fn load() -> Vec<Draft> {
+    FILE.load()
+}
+
+fn save(drafts: &[Draft]) {
+    FILE.save(drafts);
+}
+
+/// Run `f` on the draft list, then persist and notify listeners
+fn modify(f: impl FnOnce(&mut Vec<Draft>)) {
+    STORE.with(|store| {
+        let mut store = store.borrow_mut();
+        let drafts = store.get_or_insert_with(load);
+        f(drafts);
+        save(drafts);
+    });
+
+    LISTENERS.with(|listeners| {
+        for listener in listeners.borrow().iter() {
+            listener();
+        }
+    });
+}
+
+/// All drafts, newest first
+pub fn all() -> Vec<Draft> {
+    STORE.with(|store| store.borrow_mut().get_or_insert_with(load).clone())
+}
+
+/// Save a draft, replacing the one with the same id
+pub fn save_draft(draft: Draft) {
+    modify(|drafts| {
+        drafts.retain(|d| d.id != draft.id);
+        drafts.insert(0, draft);
+    });
+}
+
+pub fn remove(id: &str) {
+    modify(|drafts| drafts.retain(|d| d.id != id));
+}
+
+/// Call `f` whenever drafts are added, changed or removed
+pub fn connect_changed(f: impl Fn() + 'static) {
+    LISTENERS.with(|listeners| listeners.borrow_mut().push(Box::new(f)));
+}
+
+/// Post scheduled drafts once they're due, for as long as the app runs.
+/// Drafts whose time passed while the app was closed are marked missed and
+/// kept as plain drafts for the user to send or discard.
+pub fn start_scheduler() {
+    mark_missed_drafts();
+    glib::timeout_add_seconds_local(SCHEDULER_INTERVAL_SECONDS, || {
+        send_due_drafts();
+        glib::ControlFlow::Continue
+    });
+}
+
+/// Unschedule drafts that came due while the app was closed; posting them
+/// late without asking could be worse than not posting them at all
+fn mark_missed_drafts() {
+    let missed = all().into_iter().filter(Draft::is_due).count();
+    if missed == 0 {
+        return;
+    }
+
+    modify(|drafts| {
+        for draft in drafts.iter_mut().filter(|draft| draft.is_due()) {
+            let time = draft.scheduled_time().map(|time| time.format("%a %d %b, %H:%M").to_string());
+            draft.scheduled_at = None;
+            draft.last_error = Some(format!(
+                "missed its time{} while Grapevine was closed",
+                time.map(|time| format!(" ({})", time)).unwrap_or_default()
+            ));
+        }
+    });
+    toasts::show(&if missed == 1 {
+        "A scheduled post was missed while Grapevine was closed; it's in Drafts".to_string()
+    } else {
+        format!("{} scheduled posts were missed while Grapevine was closed; they're in Drafts", missed)
+    });
+}
+
+fn send_due_drafts() {
+    for draft in all().into_iter().filter(Draft::is_due) {
+        if !SENDING.with(|sending| sending.borrow_mut().insert(draft.id.clone())) {
+            continue;
+        }
+
+        glib::spawn_future_local(async move {
+            match compose::publish_with_link(draft.text.clone(), draft.card_url.clone()).await {
+                Ok(uri) => {
+                    tracing::info!("Posted scheduled draft {} as {}", draft.id, uri);
+                    remove(&draft.id);
+                    toasts::show("Scheduled post published");
+                }
+                Err(e) => {
+                    // Keep it as a plain draft rather than retrying every few seconds
+                    toasts::show(&format!("Scheduled post failed: {}", e));
+                    save_draft(Draft {
+                        scheduled_at: None,
+                        last_error: Some(e.to_string()),
+                        ..draft.clone()
+                    });
+                }
+            }
+            SENDING.with(|sending| sending.borrow_mut().remove(&draft.id));
+        });
+    }
+}
@@ -0,0 +1,153 @@
+use gtk::prelude::*;
+use gtk::{glib, Label, Orientation};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::data::{WorldBankObservation, WORLD_BANK_API_URL};
+use crate::error::{get_json, FetchError};
+
+const SERVICE: &str = "World Bank";
+
+/// Indicators shown in country popovers: World Bank code and label. The World
+/// Bank has no central-bank policy rate, so the lending rate banks charge
+/// stands in for it.
+const INDICATORS: &[(&str, &str)] = &[
+    ("FP.CPI.TOTL.ZG", "Inflation (CPI)"),
+    ("FR.INR.LEND", "Lending rate"),
+];
+
+/// One indicator's latest annual value and the year before's
+#[derive(Debug, Clone)]
+pub struct Indicator {
+    pub label: &'static str,
+    pub year: String,
+    pub value: f64,
+    /// Change from the previous year, in percentage points
+    pub change: Option<f64>,
+}
+
+thread_local! {
+    /// Indicators by ISO country code; annual figures, so kept for the session
+    static INDICATOR_CACHE: RefCell<HashMap<String, Vec<Indicator>>> = RefCell::new(HashMap::new());
+}
+
+/// The two most recent years with a value, newest first
+async fn fetch_observations(country_code: &str, indicator: &str) -> Result<Vec<WorldBankObservation>, FetchError> {
+    let url = format!("{}/country/{}/indicator/{}", WORLD_BANK_API_URL, country_code, indicator);
+    let request = crate::http::client()
+        .get(&url)
+        .query(&[("format", "json"), ("mrnev", "2")]);
+
+    // A page header then the observations, or only a message for unknown countries
+    let response: Vec<serde_json::Value> = get_json(SERVICE, request).await?;
+    let observations = response.into_iter().nth(1).ok_or(FetchError::Empty { service: SERVICE })?;
+    serde_json::from_value(observations).map_err(|e| FetchError::parse(SERVICE, e))
+}
+
+/// Latest inflation and interest rate for a country, by ISO 3166-1 alpha-2
+/// code; indicators the World Bank has no figures for are left out
+pub async fn fetch_indicators(country_code: &str) -> Result<Vec<Indicator>, FetchError> {
+    if let Some(indicators) = INDICATOR_CACHE.with(|cache| cache.borrow().get(country_code).cloned()) {
+        return Ok(indicators);
+    }
+
+    let mut indicators = Vec::new();
+    let mut last_error = None;
+    for (code, label) in INDICATORS {
+        let observations = match fetch_observations(country_code, code).await {
+            Ok(observations) => observations,
+            Err(e) => {
+                last_error = Some(e);
+                continue;
+            }
+        };
+        let mut values = observations.into_iter().filter_map(|obs| obs.value.map(|value| (obs.date, value)));
+        let Some((year, value)) = values.next() else {
+            continue;
+        };
+        let change = values.next().map(|(_, previous)| value - previous);
+        indicators.push(Indicator { label, year, value, change });
+    }
+
+    // Only a complete failure is an error; some countries just lack a figure
+    if indicators.is_empty() {
+        if let Some(e) = last_error {
+            return Err(e);
+        }
+    }
+    INDICATOR_CACHE.with(|cache| cache.borrow_mut().insert(country_code.to_string(), indicators.clone()));
+    Ok(indicators)
+}
+
+/// Fill a popover section with a country's indicators; it stays hidden when
+/// there are none
+pub async fn load_section(section: gtk::Box, country_code: String) {
+    let indicators = match fetch_indicators(&country_code).await {
+        Ok(indicators) => indicators,
+        Err(e) => {
+            tracing::warn!("No economic indicators for {}: {}", country_code, e);
+            crate::toasts::show_error(&e, move || {
+                glib::spawn_future_local(load_section(section.clone(), country_code.clone()));
+            });
+            return;
+        }
+    };
+    if indicators.is_empty() {
+        return;
+    }
+
+    let heading = Label::builder()
+        .label("Economy")
+        .xalign(0.0)
+        .build();
+    heading.add_css_class("title-4");
+    section.append(&heading);
+
+    for indicator in indicators {
+        let row = gtk::Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(8)
+            .build();
+        let label = Label::builder()
+            .label(format!("{} ({})", indicator.label, indicator.year))
+            .xalign(0.0)
+            .hexpand(true)
+            .build();
+        label.add_css_class("dim-label");
+        row.append(&label);
+
+        let value_label = Label::builder()
+            .label(format!("{:.1}%", indicator.value))
+            .build();
+        value_label.add_css_class("heading");
+        row.append(&value_label);
+
+        if let Some(change) = indicator.change {
+            let badge = Label::builder()
+                .label(format!("{}{:.1} pp YoY", if change > 0.0 { "+" } else { "" }, change))
+                .tooltip_text("Change from the year before, in percentage points")
+                .build();
+            badge.add_css_class("badge");
+            // Rising prices and borrowing costs read as bad news
+            badge.add_css_class(if change > 0.0 {
+                "badge-negative"
+            } else if change < 0.0 {
+                "badge-positive"
+            } else {
+                "badge-neutral"
+            });
+            row.append(&badge);
+        }
+        section.append(&row);
+    }
+
+    let source_label = Label::builder()
+        .label("Annual figures from the World Bank")
+        .xalign(0.0)
+        .build();
+    source_label.add_css_class("caption");
+    source_label.add_css_class("dim-label");
+    section.append(&source_label);
+
+    section.set_visible(true);
+}
@@ -0,0 +1,191 @@
+use std::fmt;
+use std::time::Duration;
+
+/// Wait suggested when a service rate limits us without saying for how long
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Why a request to one of the services Grapevine reads from failed.
+/// The Display text is written to be shown to the user as is.
+#[derive(Debug, Clone)]
+pub enum FetchError {
+    /// The service couldn't be reached (offline, DNS, timeout)
+    Network { service: &'static str, timed_out: bool },
+    /// The response didn't have the expected shape
+    Parse { service: &'static str, message: String },
+    /// Too many requests; try again after `retry_after`
+    RateLimited { service: &'static str, retry_after: Duration },
+    /// The service refused our credentials, or needs some
+    Auth { service: &'static str },
+    /// Any other HTTP error status
+    Upstream { service: &'static str, status: u16 },
+    /// The request worked but there was nothing in it
+    Empty { service: &'static str },
+    /// Not contacted because `--demo` keeps Grapevine offline
+    Demo { service: &'static str },
+    /// The request couldn't be built from what the user asked for
+    InvalidRequest { service: &'static str, message: String },
+}
+
+impl FetchError {
+    /// How long to wait before retrying automatically, for errors worth retrying
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            FetchError::RateLimited { retry_after, .. } => Some(*retry_after),
+            _ => None,
+        }
+    }
+
+    /// Classify a reqwest error from sending a request or reading its body
+    pub fn from_reqwest(service: &'static str, error: reqwest::Error) -> Self {
+        if error.is_decode() {
+            FetchError::Parse { service, message: error.to_string() }
+        } else if let Some(status) = error.status() {
+            FetchError::from_status(service, status, None)
+        } else {
+            FetchError::Network { service, timed_out: error.is_timeout() }
+        }
+    }
+
+    /// Classify an HTTP error status; `retry_after` is the Retry-After header in seconds
+    fn from_status(service: &'static str, status: reqwest::StatusCode, retry_after: Option<u64>) -> Self {
+        match status.as_u16() {
+            429 => FetchError::RateLimited {
+                service,
+                retry_after: retry_after.map(Duration::from_secs).unwrap_or(DEFAULT_RETRY_AFTER),
+            },
+            401 | 403 => FetchError::Auth { service },
+            status => FetchError::Upstream { service, status },
+        }
+    }
+
+    /// A body that couldn't be decoded
+    pub fn parse(service: &'static str, error: impl fmt::Display) -> Self {
+        FetchError::Parse { service, message: error.to_string() }
+    }
+
+    /// Which service failed
+    pub fn service(&self) -> &'static str {
+        match self {
+            FetchError::Network { service, .. }
+            | FetchError::Parse { service, .. }
+            | FetchError::RateLimited { service, .. }
+            | FetchError::Auth { service }
+            | FetchError::Upstream { service, .. }
+            | FetchError::Empty { service }
+            | FetchError::Demo { service }
+            | FetchError::InvalidRequest { service, .. } => service,
+        }
+    }
+
+    /// Short machine-readable name for the kind of failure, for metrics labels
+    pub fn kind(&self) -> &'static str {
+        match self {
+            FetchError::Network { timed_out: true, .. } => "timeout",
+            FetchError::Network { .. } => "network",
+            FetchError::Parse { .. } => "parse",
+            FetchError::RateLimited { .. } => "rate_limited",
+            FetchError::Auth { .. } => "auth",
+            FetchError::Upstream { .. } => "upstream",
+            FetchError::Empty { .. } => "empty",
+            FetchError::Demo { .. } => "demo",
+            FetchError::InvalidRequest { .. } => "invalid_request",
+        }
+    }
+
+    /// Rate limited without a Retry-After hint
+    pub fn rate_limited(service: &'static str) -> Self {
+        FetchError::RateLimited { service, retry_after: DEFAULT_RETRY_AFTER }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Network { service, timed_out: true } => {
+                write!(f, "{} took too long to answer — check your connection", service)
+            }
+            FetchError::Network { service, timed_out: false } => {
+                write!(f, "Couldn't reach {} — check your connection", service)
+            }
+            FetchError::Parse { service, message } => {
+                write!(f, "{} sent data Grapevine doesn't understand ({})", service, message)
+            }
+            FetchError::RateLimited { service, retry_after } => {
+                write!(f, "{} rate limited — retrying in {}s", service, retry_after.as_secs())
+            }
+            FetchError::Auth { service } => {
+                write!(f, "{} refused access — check the API key in Preferences if it needs one", service)
+            }
+            FetchError::Upstream { service, status } if *status >= 500 => {
+                write!(f, "{} is having problems (HTTP {}) — try again later", service, status)
+            }
+            FetchError::Upstream { service, status } => write!(f, "{} returned HTTP {}", service, status),
+            FetchError::Empty { service } => write!(f, "{} had no results", service),
+            FetchError::Demo { service } => write!(f, "{} is not contacted in demo mode", service),
+            FetchError::InvalidRequest { service, message } => write!(f, "Invalid {} search: {}", service, message),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// Send a request and turn error statuses into a FetchError
+pub async fn send(service: &'static str, request: reqwest::RequestBuilder) -> Result<reqwest::Response, FetchError> {
+    if crate::demo::is_enabled() {
+        return Err(FetchError::Demo { service });
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| FetchError::from_reqwest(service, e))
+        .inspect_err(crate::metrics::record_error)?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok());
+    let error = FetchError::from_status(service, status, retry_after);
+    crate::metrics::record_error(&error);
+    Err(error)
+}
+
+/// Send a request and decode its JSON body
+pub async fn get_json<T: serde::de::DeserializeOwned>(
+    service: &'static str,
+    request: reqwest::RequestBuilder,
+) -> Result<T, FetchError> {
+    crate::http::send_with_retry(service, request)
+        .await?
+        .json::<T>()
+        .await
+        .map_err(|e| FetchError::from_reqwest(service, e))
+        .inspect_err(crate::metrics::record_error)
+}
+
+/// Send a request and read its body as text
+pub async fn get_text(service: &'static str, request: reqwest::RequestBuilder) -> Result<String, FetchError> {
+    crate::http::send_with_retry(service, request)
+        .await?
+        .text()
+        .await
+        .map_err(|e| FetchError::from_reqwest(service, e))
+        .inspect_err(crate::metrics::record_error)
+}
+
+/// Send a request and read its raw body
+pub async fn get_bytes(service: &'static str, request: reqwest::RequestBuilder) -> Result<Vec<u8>, FetchError> {
+    crate::http::send_with_retry(service, request)
+        .await?
+        .bytes()
+        .await
+        .map(|bytes| bytes.to_vec())
+        .map_err(|e| FetchError::from_reqwest(service, e))
+        .inspect_err(crate::metrics::record_error)
+}
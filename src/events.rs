@@ -0,0 +1,338 @@
+use gtk::prelude::*;
+use gtk::{gio, glib, Label, Orientation};
+use std::io::Read;
+
+use crate::data::GDELT_EVENTS_LAST_UPDATE_URL;
+use crate::disasters::place_marker_with_popover;
+use crate::error::{get_bytes, get_text, FetchError};
+use crate::global_affairs::format_time_ago;
+
+const SERVICE: &str = "GDELT Events";
+
+/// Markers placed per refresh, the most reported events first
+const MAX_EVENTS: usize = 300;
+
+/// Columns of the GDELT 2.0 Events export used here
+const COL_ACTOR1_NAME: usize = 6;
+const COL_ACTOR2_NAME: usize = 16;
+const COL_IS_ROOT_EVENT: usize = 25;
+const COL_EVENT_CODE: usize = 26;
+const COL_EVENT_ROOT_CODE: usize = 28;
+const COL_NUM_MENTIONS: usize = 31;
+const COL_PLACE: usize = 52;
+const COL_LAT: usize = 56;
+const COL_LONG: usize = 57;
+const COL_DATE_ADDED: usize = 59;
+const COL_SOURCE_URL: usize = 60;
+const COLUMNS: usize = 61;
+
+/// Kinds of CAMEO events plotted, each on its own layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventCategory {
+    Protest,
+    Coercion,
+    Posture,
+    Assault,
+    Conflict,
+}
+
+impl EventCategory {
+    pub const ALL: [EventCategory; 5] = [
+        EventCategory::Protest,
+        EventCategory::Coercion,
+        EventCategory::Posture,
+        EventCategory::Assault,
+        EventCategory::Conflict,
+    ];
+
+    /// The category of a CAMEO root code; other codes aren't plotted
+    fn from_root_code(code: &str) -> Option<Self> {
+        match code {
+            "14" => Some(EventCategory::Protest),
+            "13" | "17" => Some(EventCategory::Coercion),
+            "15" => Some(EventCategory::Posture),
+            "18" => Some(EventCategory::Assault),
+            "19" | "20" => Some(EventCategory::Conflict),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EventCategory::Protest => "Protests",
+            EventCategory::Coercion => "Threats and coercion",
+            EventCategory::Posture => "Military posturing",
+            EventCategory::Assault => "Assaults",
+            EventCategory::Conflict => "Armed conflict",
+        }
+    }
+
+    fn icon(&self) -> &'static str {
+        match self {
+            EventCategory::Protest => "✊",
+            EventCategory::Coercion => "⚠️",
+            EventCategory::Posture => "🛡️",
+            EventCategory::Assault => "👊",
+            EventCategory::Conflict => "💥",
+        }
+    }
+
+    fn css_class(&self) -> &'static str {
+        match self {
+            EventCategory::Protest => "event-protest",
+            EventCategory::Coercion => "event-coercion",
+            EventCategory::Posture => "event-posture",
+            EventCategory::Assault => "event-assault",
+            EventCategory::Conflict => "event-conflict",
+        }
+    }
+}
+
+/// Name of a CAMEO root event code, e.g. "14" → "Protest"
+fn root_code_name(code: &str) -> &'static str {
+    match code {
+        "13" => "Threat",
+        "14" => "Protest",
+        "15" => "Show of force",
+        "17" => "Coercion",
+        "18" => "Assault",
+        "19" => "Fighting",
+        "20" => "Mass violence",
+        _ => "Event",
+    }
+}
+
+/// One row of the Events export
+#[derive(Debug, Clone)]
+struct GdeltEvent {
+    category: EventCategory,
+    root_code: String,
+    code: String,
+    actor1: String,
+    actor2: String,
+    place: String,
+    mentions: u32,
+    lat: f64,
+    lon: f64,
+    added: Option<chrono::NaiveDateTime>,
+    source_url: String,
+}
+
+impl GdeltEvent {
+    fn from_row(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() < COLUMNS || fields[COL_IS_ROOT_EVENT] != "1" {
+            return None;
+        }
+        let category = EventCategory::from_root_code(fields[COL_EVENT_ROOT_CODE])?;
+        let lat: f64 = fields[COL_LAT].parse().ok()?;
+        let lon: f64 = fields[COL_LONG].parse().ok()?;
+
+        Some(Self {
+            category,
+            root_code: fields[COL_EVENT_ROOT_CODE].to_string(),
+            code: fields[COL_EVENT_CODE].to_string(),
+            actor1: fields[COL_ACTOR1_NAME].to_string(),
+            actor2: fields[COL_ACTOR2_NAME].to_string(),
+            place: fields[COL_PLACE].to_string(),
+            mentions: fields[COL_NUM_MENTIONS].parse().unwrap_or(0),
+            lat,
+            lon,
+            added: chrono::NaiveDateTime::parse_from_str(fields[COL_DATE_ADDED], "%Y%m%d%H%M%S").ok(),
+            source_url: fields[COL_SOURCE_URL].to_string(),
+        })
+    }
+
+    /// "PROTESTER → POLICE", or whichever actor is known
+    fn actors(&self) -> Option<String> {
+        match (self.actor1.is_empty(), self.actor2.is_empty()) {
+            (false, false) => Some(format!("{} → {}", self.actor1, self.actor2)),
+            (false, true) => Some(self.actor1.clone()),
+            (true, false) => Some(self.actor2.clone()),
+            (true, true) => None,
+        }
+    }
+}
+
+/// Map layers for GDELT's coded events, one per category so each can be
+/// toggled from the layers menu, apart from the news source-country markers
+#[derive(Clone)]
+pub struct EventLayers {
+    layers: Vec<(EventCategory, libshumate::MarkerLayer)>,
+}
+
+impl EventLayers {
+    /// Create a marker layer per category and add them to the map
+    pub fn new(map_view: &libshumate::Map, viewport: &libshumate::Viewport) -> Self {
+        let layers = EventCategory::ALL
+            .iter()
+            .map(|category| {
+                let layer = libshumate::MarkerLayer::new(viewport);
+                map_view.add_layer(&layer);
+                (*category, layer)
+            })
+            .collect();
+        Self { layers }
+    }
+
+    /// Each category's name and layer, for the layers menu
+    pub fn named_layers(&self) -> Vec<(&'static str, libshumate::MarkerLayer)> {
+        self.layers
+            .iter()
+            .map(|(category, layer)| (category.label(), layer.clone()))
+            .collect()
+    }
+
+    /// Fetch the latest 15 minutes of events and rebuild the markers
+    pub async fn fetch(self) {
+        let events = match fetch_latest_events().await {
+            Ok(events) => events,
+            Err(e) => {
+                tracing::warn!("GDELT events unavailable: {}", e);
+                return;
+            }
+        };
+
+        for (_, layer) in self.layers.iter() {
+            layer.remove_all();
+        }
+
+        tracing::debug!("Placing {} GDELT event markers", events.len());
+        for event in events.iter() {
+            if let Some((_, layer)) = self.layers.iter().find(|(category, _)| *category == event.category) {
+                create_event_marker(layer, event);
+            }
+        }
+    }
+
+    pub fn refresh(&self) {
+        glib::spawn_future_local(self.clone().fetch());
+    }
+}
+
+/// Download the newest Events export and keep the plotted kinds of root
+/// events, most mentioned first
+async fn fetch_latest_events() -> Result<Vec<GdeltEvent>, FetchError> {
+    let index = get_text(SERVICE, crate::http::client().get(GDELT_EVENTS_LAST_UPDATE_URL)).await?;
+    // Lines read "<size> <md5> <url>"; the first is the Events export
+    let url = index
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(2))
+        .find(|url| url.ends_with(".export.CSV.zip"))
+        .ok_or(FetchError::Empty { service: SERVICE })?
+        .to_string();
+
+    let archive = get_bytes(SERVICE, crate::http::client().get(url)).await?;
+    let mut events = gio::spawn_blocking(move || parse_export(&archive))
+        .await
+        .map_err(|_| FetchError::parse(SERVICE, "export parsing panicked"))??;
+
+    events.sort_by_key(|event| std::cmp::Reverse(event.mentions));
+    events.truncate(MAX_EVENTS);
+    Ok(events)
+}
+
+/// Unzip an Events export and read its rows
+fn parse_export(archive: &[u8]) -> Result<Vec<GdeltEvent>, FetchError> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(archive))
+        .map_err(|e| FetchError::parse(SERVICE, e))?;
+    let mut file = archive.by_index(0).map_err(|e| FetchError::parse(SERVICE, e))?;
+
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).map_err(|e| FetchError::parse(SERVICE, e))?;
+    Ok(String::from_utf8_lossy(&bytes)
+        .lines()
+        .filter_map(GdeltEvent::from_row)
+        .collect())
+}
+
+fn create_event_marker(layer: &libshumate::MarkerLayer, event: &GdeltEvent) {
+    let title = root_code_name(&event.root_code);
+
+    let marker_button = gtk::Button::builder()
+        .label(event.category.icon())
+        .tooltip_text(if event.place.is_empty() { title.to_string() } else { format!("{} — {}", title, event.place) })
+        .build();
+    marker_button.add_css_class("event-marker");
+    marker_button.add_css_class(event.category.css_class());
+
+    let popover_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    let title_label = Label::builder()
+        .label(format!("{} {}", event.category.icon(), title))
+        .xalign(0.0)
+        .build();
+    title_label.add_css_class("title-3");
+    popover_box.append(&title_label);
+
+    if let Some(actors) = event.actors() {
+        let actors_label = Label::builder()
+            .label(actors)
+            .xalign(0.0)
+            .wrap(true)
+            .max_width_chars(40)
+            .build();
+        popover_box.append(&actors_label);
+    }
+
+    if !event.place.is_empty() {
+        let place_label = Label::builder()
+            .label(event.place.as_str())
+            .xalign(0.0)
+            .wrap(true)
+            .max_width_chars(40)
+            .build();
+        place_label.add_css_class("dim-label");
+        popover_box.append(&place_label);
+    }
+
+    // Badges for time, mentions and the CAMEO code
+    let badges_box = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(4)
+        .build();
+
+    if let Some(added) = event.added {
+        let time_badge = Label::builder()
+            .label(format_time_ago(added))
+            .build();
+        time_badge.add_css_class("badge");
+        time_badge.add_css_class("badge-time");
+        badges_box.append(&time_badge);
+    }
+
+    let mentions_badge = Label::builder()
+        .label(format!("{} mentions", event.mentions))
+        .build();
+    mentions_badge.add_css_class("badge");
+    mentions_badge.add_css_class("badge-neutral");
+    badges_box.append(&mentions_badge);
+
+    let code_badge = Label::builder()
+        .label(format!("CAMEO {}", event.code))
+        .tooltip_text(event.category.label())
+        .build();
+    code_badge.add_css_class("badge");
+    code_badge.add_css_class("badge-lang");
+    badges_box.append(&code_badge);
+
+    popover_box.append(&badges_box);
+
+    if !event.source_url.is_empty() {
+        let link_button = gtk::LinkButton::builder()
+            .uri(event.source_url.as_str())
+            .label("Read source article")
+            .halign(gtk::Align::Start)
+            .build();
+        popover_box.append(&link_button);
+    }
+
+    place_marker_with_popover(layer, marker_button, popover_box, event.lat, event.lon);
+}
@@ -0,0 +1,415 @@
+use gtk::prelude::*;
+use gtk::{gio, glib};
+
+use crate::bookmarks::{Bookmark, BookmarkKind};
+use crate::data::{CurrencyInfo, GdeltArticle};
+use crate::digest::{DailyDigest, Digest};
+
+/// Render articles as a Markdown document
+pub fn articles_to_markdown(title: &str, articles: &[GdeltArticle]) -> String {
+    let mut markdown = format!("# {}\n\n", title);
+    markdown.push_str(&format!(
+        "_Exported from Grapevine on {}_\n\n",
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    ));
+
+    for article in articles {
+        markdown.push_str(&format!("- [{}]({})", article.title.replace(']', "\\]"), article.url));
+
+        let mut details = Vec::new();
+        if !article.domain.is_empty() {
+            details.push(article.domain.as_str());
+        }
+        if !article.sourcecountry.is_empty() {
+            details.push(article.sourcecountry.as_str());
+        }
+        if !details.is_empty() {
+            markdown.push_str(&format!(" — {}", details.join(", ")));
+        }
+        markdown.push('\n');
+    }
+
+    markdown
+}
+
+/// Render articles as a standalone HTML document
+pub fn articles_to_html(title: &str, articles: &[GdeltArticle]) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        escape_html(title)
+    );
+    html.push_str(&format!(
+        "<p><em>Exported from Grapevine on {}</em></p>\n<ul>\n",
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    ));
+
+    for article in articles {
+        html.push_str(&format!(
+            "<li><a href=\"{}\">{}</a>",
+            escape_html(&article.url),
+            escape_html(&article.title)
+        ));
+        if !article.domain.is_empty() {
+            html.push_str(&format!(" — {}", escape_html(&article.domain)));
+        }
+        if !article.sourcecountry.is_empty() {
+            html.push_str(&format!(", {}", escape_html(&article.sourcecountry)));
+        }
+        html.push_str("</li>\n");
+    }
+
+    html.push_str("</ul>\n</body>\n</html>\n");
+    html
+}
+
+fn bookmark_kind_label(kind: BookmarkKind) -> &'static str {
+    match kind {
+        BookmarkKind::Article => "article",
+        BookmarkKind::Post => "post",
+    }
+}
+
+/// Render bookmarks as a Markdown document, with tags as hashtags
+pub fn bookmarks_to_markdown(title: &str, bookmarks: &[Bookmark]) -> String {
+    let mut markdown = format!("# {}\n\n", title);
+    markdown.push_str(&format!(
+        "_Exported from Grapevine on {}_\n\n",
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    ));
+
+    for bookmark in bookmarks {
+        // Post text can span lines; keep each bookmark on one list item
+        let link_text = bookmark.title.replace('\n', " ").replace(']', "\\]");
+        markdown.push_str(&format!("- [{}]({}) — {}", link_text, bookmark.url, bookmark_kind_label(bookmark.kind)));
+        if !bookmark.source.is_empty() {
+            markdown.push_str(&format!(", {}", bookmark.source));
+        }
+        if !bookmark.tags.is_empty() {
+            let tags: Vec<String> = bookmark.tags.iter().map(|tag| format!("#{}", tag)).collect();
+            markdown.push_str(&format!(" {}", tags.join(" ")));
+        }
+        markdown.push('\n');
+    }
+
+    markdown
+}
+
+/// Render bookmarks as a standalone HTML document
+pub fn bookmarks_to_html(title: &str, bookmarks: &[Bookmark]) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        escape_html(title)
+    );
+    html.push_str(&format!(
+        "<p><em>Exported from Grapevine on {}</em></p>\n<ul>\n",
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    ));
+
+    for bookmark in bookmarks {
+        html.push_str(&format!(
+            "<li><a href=\"{}\">{}</a> — {}",
+            escape_html(&bookmark.url),
+            escape_html(&bookmark.title),
+            bookmark_kind_label(bookmark.kind)
+        ));
+        if !bookmark.source.is_empty() {
+            html.push_str(&format!(", {}", escape_html(&bookmark.source)));
+        }
+        if !bookmark.tags.is_empty() {
+            html.push_str(&format!(" <small>{}</small>", escape_html(&bookmark.tags.join(", "))));
+        }
+        html.push_str("</li>\n");
+    }
+
+    html.push_str("</ul>\n</body>\n</html>\n");
+    html
+}
+
+/// Section heading for a digest's search
+pub fn digest_heading(query: &str) -> String {
+    if query.trim().is_empty() {
+        "Latest World News".to_string()
+    } else {
+        query.to_string()
+    }
+}
+
+/// Render weekly digests as a Markdown document; charts are embedded as SVG data URIs
+pub fn digests_to_markdown(title: &str, digests: &[Digest]) -> String {
+    let mut markdown = format!("# {}\n\n", title);
+    markdown.push_str(&format!(
+        "_Past 7 days, exported from Grapevine on {}_\n\n",
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    ));
+
+    for digest in digests {
+        markdown.push_str(&format!("## {}\n\n", digest_heading(&digest.query)));
+        markdown.push_str(&format!("{} articles this week.\n\n", digest.article_count));
+
+        if !digest.volume.is_empty() {
+            let chart = glib::base64_encode(crate::digest::volume_chart_svg(&digest.volume).as_bytes());
+            markdown.push_str(&format!("![Coverage volume](data:image/svg+xml;base64,{})\n\n", chart));
+        }
+
+        if !digest.stories.is_empty() {
+            markdown.push_str("### Top Stories\n\n");
+            for (i, story) in digest.stories.iter().enumerate() {
+                markdown.push_str(&format!(
+                    "{}. [{}]({})",
+                    i + 1,
+                    story.lead.title.replace(']', "\\]"),
+                    story.lead.url
+                ));
+                if story.size > 1 {
+                    markdown.push_str(&format!(" — {} articles from {}", story.size, story.domains.join(", ")));
+                } else if !story.lead.domain.is_empty() {
+                    markdown.push_str(&format!(" — {}", story.lead.domain));
+                }
+                markdown.push('\n');
+            }
+            markdown.push('\n');
+        }
+
+        for (heading, counts) in [("Top Domains", &digest.top_domains), ("Top Countries", &digest.top_countries)] {
+            if counts.is_empty() {
+                continue;
+            }
+            markdown.push_str(&format!("### {}\n\n| | Articles |\n|---|---:|\n", heading));
+            for (value, count) in counts {
+                markdown.push_str(&format!("| {} | {} |\n", value.replace('|', "\\|"), count));
+            }
+            markdown.push('\n');
+        }
+    }
+
+    markdown
+}
+
+/// Render weekly digests as a standalone HTML document with inline SVG charts
+pub fn digests_to_html(title: &str, digests: &[Digest]) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        escape_html(title)
+    );
+    html.push_str(&format!(
+        "<p><em>Past 7 days, exported from Grapevine on {}</em></p>\n",
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    ));
+
+    for digest in digests {
+        html.push_str(&format!("<h2>{}</h2>\n", escape_html(&digest_heading(&digest.query))));
+        html.push_str(&format!("<p>{} articles this week.</p>\n", digest.article_count));
+
+        if !digest.volume.is_empty() {
+            html.push_str(&crate::digest::volume_chart_svg(&digest.volume));
+            html.push('\n');
+        }
+
+        if !digest.stories.is_empty() {
+            html.push_str("<h3>Top Stories</h3>\n<ol>\n");
+            for story in &digest.stories {
+                html.push_str(&format!(
+                    "<li><a href=\"{}\">{}</a>",
+                    escape_html(&story.lead.url),
+                    escape_html(&story.lead.title)
+                ));
+                if story.size > 1 {
+                    html.push_str(&format!(
+                        " — {} articles from {}",
+                        story.size,
+                        escape_html(&story.domains.join(", "))
+                    ));
+                } else if !story.lead.domain.is_empty() {
+                    html.push_str(&format!(" — {}", escape_html(&story.lead.domain)));
+                }
+                html.push_str("</li>\n");
+            }
+            html.push_str("</ol>\n");
+        }
+
+        for (heading, counts) in [("Top Domains", &digest.top_domains), ("Top Countries", &digest.top_countries)] {
+            if counts.is_empty() {
+                continue;
+            }
+            html.push_str(&format!("<h3>{}</h3>\n<table>\n", heading));
+            for (value, count) in counts {
+                html.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>\n", escape_html(value), count));
+            }
+            html.push_str("</table>\n");
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// e.g. "EUR/USD 1.0842 (+0.31% day, -1.20% week)"
+fn describe_currency(info: &CurrencyInfo) -> String {
+    let changes: Vec<String> = [("day", info.change_24h), ("week", info.change_7d)]
+        .into_iter()
+        .filter_map(|(period, change)| change.map(|change| format!("{:+.2}% {}", change, period)))
+        .collect();
+    let mut line = format!("{}/{} {:.4}", info.code, info.target, info.rate);
+    if !changes.is_empty() {
+        line.push_str(&format!(" ({})", changes.join(", ")));
+    }
+    line
+}
+
+/// Render the daily digest as a Markdown document
+pub fn daily_digest_to_markdown(title: &str, digest: &DailyDigest) -> String {
+    let mut markdown = format!("# {}\n\n", title);
+    markdown.push_str(&format!(
+        "_{} articles from the past day, exported from Grapevine on {}_\n\n",
+        digest.article_count,
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    ));
+
+    if !digest.countries.is_empty() {
+        markdown.push_str("## Top Stories by Country\n\n");
+        for (country, stories) in &digest.countries {
+            markdown.push_str(&format!("### {}\n\n", country));
+            for story in stories {
+                markdown.push_str(&format!("- [{}]({})", story.lead.title.replace(']', "\\]"), story.lead.url));
+                if story.size > 1 {
+                    markdown.push_str(&format!(" — {} articles", story.size));
+                } else if !story.lead.domain.is_empty() {
+                    markdown.push_str(&format!(" — {}", story.lead.domain));
+                }
+                markdown.push('\n');
+            }
+            markdown.push('\n');
+        }
+    }
+
+    if !digest.currencies.is_empty() {
+        markdown.push_str("## Currencies\n\n");
+        for info in &digest.currencies {
+            markdown.push_str(&format!("- {}\n", describe_currency(info)));
+        }
+        markdown.push('\n');
+    }
+
+    if !digest.hashtags.is_empty() {
+        markdown.push_str("## Trending on Bluesky\n\n| Hashtag | Posts |\n|---|---:|\n");
+        for (tag, count) in &digest.hashtags {
+            markdown.push_str(&format!("| #{} | {} |\n", tag.replace('|', "\\|"), count));
+        }
+        markdown.push('\n');
+    }
+
+    markdown
+}
+
+/// Render the daily digest as a standalone HTML document
+pub fn daily_digest_to_html(title: &str, digest: &DailyDigest) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        escape_html(title)
+    );
+    html.push_str(&format!(
+        "<p><em>{} articles from the past day, exported from Grapevine on {}</em></p>\n",
+        digest.article_count,
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    ));
+
+    if !digest.countries.is_empty() {
+        html.push_str("<h2>Top Stories by Country</h2>\n");
+        for (country, stories) in &digest.countries {
+            html.push_str(&format!("<h3>{}</h3>\n<ul>\n", escape_html(country)));
+            for story in stories {
+                html.push_str(&format!(
+                    "<li><a href=\"{}\">{}</a>",
+                    escape_html(&story.lead.url),
+                    escape_html(&story.lead.title)
+                ));
+                if story.size > 1 {
+                    html.push_str(&format!(" — {} articles", story.size));
+                } else if !story.lead.domain.is_empty() {
+                    html.push_str(&format!(" — {}", escape_html(&story.lead.domain)));
+                }
+                html.push_str("</li>\n");
+            }
+            html.push_str("</ul>\n");
+        }
+    }
+
+    if !digest.currencies.is_empty() {
+        html.push_str("<h2>Currencies</h2>\n<ul>\n");
+        for info in &digest.currencies {
+            html.push_str(&format!("<li>{}</li>\n", escape_html(&describe_currency(info))));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    if !digest.hashtags.is_empty() {
+        html.push_str("<h2>Trending on Bluesky</h2>\n<table>\n");
+        for (tag, count) in &digest.hashtags {
+            html.push_str(&format!("<tr><td>#{}</td><td>{}</td></tr>\n", escape_html(tag), count));
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Ask for a destination and write the document; the format follows the
+/// chosen extension (.html/.htm for HTML, anything else Markdown)
+pub fn save_document(
+    parent: &impl IsA<gtk::Window>,
+    suggested_name: &str,
+    render: impl Fn(bool) -> String + 'static,
+) {
+    let filters = gio::ListStore::new::<gtk::FileFilter>();
+
+    let markdown_filter = gtk::FileFilter::new();
+    markdown_filter.set_name(Some("Markdown"));
+    markdown_filter.add_pattern("*.md");
+    filters.append(&markdown_filter);
+
+    let html_filter = gtk::FileFilter::new();
+    html_filter.set_name(Some("HTML"));
+    html_filter.add_pattern("*.html");
+    html_filter.add_pattern("*.htm");
+    filters.append(&html_filter);
+
+    let dialog = gtk::FileDialog::builder()
+        .title("Export")
+        .initial_name(suggested_name)
+        .filters(&filters)
+        .modal(true)
+        .build();
+
+    dialog.save(Some(parent), gio::Cancellable::NONE, move |result| {
+        let file = match result {
+            Ok(file) => file,
+            // Dismissed by the user
+            Err(_) => return,
+        };
+
+        let Some(path) = file.path() else {
+            tracing::warn!("Export destination has no local path");
+            return;
+        };
+
+        let is_html = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"))
+            .unwrap_or(false);
+
+        if let Err(e) = std::fs::write(&path, render(is_html)) {
+            tracing::warn!("Failed to export to {}: {}", path.display(), e);
+        } else {
+            tracing::info!("Exported to {}", path.display());
+        }
+    });
+}
@@ -0,0 +1,263 @@
+use gtk::gio;
+use gtk::glib;
+use gtk::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::data::GdeltArticle;
+use crate::error::FetchError;
+use crate::export::escape_html;
+use crate::gdelt::{self, GdeltQuery, GdeltSort, Timespan};
+use crate::metrics;
+use crate::settings::Settings;
+
+/// Feeds are rebuilt at most this often, however often readers poll
+const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+/// Largest request head read before giving up on a client
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+struct FeedServer {
+    service: gio::SocketService,
+    port: u16,
+}
+
+thread_local! {
+    static SERVER: RefCell<Option<FeedServer>> = const { RefCell::new(None) };
+    /// Rendered feeds by query, with when they were built
+    static CACHE: RefCell<HashMap<String, (Instant, String)>> = RefCell::new(HashMap::new());
+}
+
+/// URL a saved search's feed is served at
+pub fn feed_url(port: u16, query: &str) -> String {
+    format!("http://127.0.0.1:{}/search/{}.atom", port, urlencoding::encode(query))
+}
+
+/// Start or stop serving saved searches and `/metrics` on 127.0.0.1 to match the preferences
+pub fn apply_settings(settings: Rc<RefCell<Settings>>) {
+    let (enabled, port) = {
+        let settings = settings.borrow();
+        (settings.feed_server_enabled, settings.feed_server_port)
+    };
+
+    SERVER.with(|server| {
+        let mut server = server.borrow_mut();
+        if let Some(running) = server.as_ref() {
+            if enabled && running.port == port {
+                return;
+            }
+            running.service.stop();
+            running.service.close();
+            *server = None;
+        }
+
+        if enabled {
+            *server = start(port, settings.clone());
+        }
+    });
+}
+
+fn start(port: u16, settings: Rc<RefCell<Settings>>) -> Option<FeedServer> {
+    let service = gio::SocketService::new();
+    let address = gio::InetSocketAddress::new(&gio::InetAddress::new_loopback(gio::SocketFamily::Ipv4), port);
+    if let Err(e) = service.add_address(
+        &address,
+        gio::SocketType::Stream,
+        gio::SocketProtocol::Tcp,
+        None::<&glib::Object>,
+    ) {
+        tracing::warn!("Feed server can't listen on port {}: {}", port, e);
+        return None;
+    }
+
+    service.connect_incoming(move |_, connection, _| {
+        let connection = connection.clone();
+        let settings = settings.clone();
+        glib::spawn_future_local(async move {
+            if let Err(e) = handle_connection(&connection, port, &settings).await {
+                tracing::warn!("Feed server connection failed: {}", e);
+            }
+            let _ = connection.close_future(glib::Priority::DEFAULT).await;
+        });
+        true
+    });
+    service.start();
+
+    tracing::info!("Serving saved search feeds on http://127.0.0.1:{}/", port);
+    Some(FeedServer { service, port })
+}
+
+/// Read the request line, ignoring headers and body
+async fn read_request_path(connection: &gio::SocketConnection) -> Result<Option<String>, glib::Error> {
+    let input = connection.input_stream();
+    let mut head = Vec::new();
+
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") && head.len() < MAX_REQUEST_BYTES {
+        let chunk = input.read_bytes_future(1024, glib::Priority::DEFAULT).await?;
+        if chunk.is_empty() {
+            break;
+        }
+        head.extend_from_slice(&chunk);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some(path)) => Ok(Some(path.to_string())),
+        _ => Ok(None),
+    }
+}
+
+async fn handle_connection(
+    connection: &gio::SocketConnection,
+    port: u16,
+    settings: &Rc<RefCell<Settings>>,
+) -> Result<(), glib::Error> {
+    let response = match read_request_path(connection).await? {
+        None => response(405, "text/plain", "Only GET is supported\n"),
+        Some(path) if path == "/" => response(200, "text/html; charset=utf-8", &index_page(port, settings)),
+        Some(path) if path == "/metrics" => response(200, "text/plain; version=0.0.4; charset=utf-8", &metrics::render()),
+        Some(path) => match saved_query_for_path(&path, settings) {
+            Some(query) => match feed_for_query(&query, port).await {
+                Ok(feed) => response(200, "application/atom+xml; charset=utf-8", &feed),
+                Err(e) => response(502, "text/plain; charset=utf-8", &format!("{}\n", e)),
+            },
+            None => response(404, "text/plain", "No saved search at this address\n"),
+        },
+    };
+
+    connection
+        .output_stream()
+        .write_all_future(response.into_bytes(), glib::Priority::DEFAULT)
+        .await
+        .map(|_| ())
+        .map_err(|(_, e)| e)
+}
+
+fn response(status: u16, content_type: &str, body: &str) -> String {
+    let reason = match status {
+        200 => "OK",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Bad Gateway",
+    };
+    format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+/// `/search/<query>.atom` for a query that is still saved; anything else is refused
+fn saved_query_for_path(path: &str, settings: &Rc<RefCell<Settings>>) -> Option<String> {
+    let encoded = path.strip_prefix("/search/")?.strip_suffix(".atom")?;
+    let query = urlencoding::decode(encoded).ok()?.into_owned();
+    settings.borrow().saved_searches.contains(&query).then_some(query)
+}
+
+fn index_page(port: u16, settings: &Rc<RefCell<Settings>>) -> String {
+    let mut page = String::from(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Grapevine feeds</title></head><body>\n<h1>Grapevine saved searches</h1>\n",
+    );
+
+    let settings = settings.borrow();
+    if settings.saved_searches.is_empty() {
+        page.push_str("<p>No saved searches yet. Save one from the Global Affairs search bar.</p>\n");
+    } else {
+        page.push_str("<ul>\n");
+        for query in settings.saved_searches.iter() {
+            let url = feed_url(port, query);
+            page.push_str(&format!(
+                "<li><a href=\"{}\">{}</a></li>\n",
+                escape_html(&url),
+                escape_html(query)
+            ));
+        }
+        page.push_str("</ul>\n");
+    }
+
+    page.push_str("<p><a href=\"/metrics\">Metrics</a> for monitoring this instance</p>\n");
+    page.push_str("</body></html>\n");
+    page
+}
+
+/// The feed for a saved search, from the cache while it is fresh
+async fn feed_for_query(query: &str, port: u16) -> Result<String, FetchError> {
+    let cached = CACHE.with(|cache| {
+        cache
+            .borrow()
+            .get(query)
+            .filter(|(built, _)| built.elapsed() < CACHE_TTL)
+            .map(|(_, feed)| feed.clone())
+    });
+    if let Some(feed) = cached {
+        return Ok(feed);
+    }
+
+    let url = GdeltQuery::new(query)
+        .language("english")
+        .timespan(Timespan::days(1))
+        .max_records(75)
+        .sort(GdeltSort::DateDesc)
+        .url()?;
+    let articles = gdelt::fetch_articles(&url).await?;
+
+    let feed = atom_feed(query, &feed_url(port, query), &articles);
+    CACHE.with(|cache| cache.borrow_mut().insert(query.to_string(), (Instant::now(), feed.clone())));
+    Ok(feed)
+}
+
+/// "20240101T120000Z" -> "2024-01-01T12:00:00Z"
+fn atom_date(seendate: &str) -> Option<String> {
+    chrono::NaiveDateTime::parse_from_str(seendate, "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|date| date.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
+
+fn atom_feed(query: &str, self_url: &str, articles: &[GdeltArticle]) -> String {
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let updated = articles
+        .iter()
+        .filter_map(|article| atom_date(&article.seendate))
+        .max()
+        .unwrap_or_else(|| now.clone());
+
+    let mut feed = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    feed.push_str(&format!("  <title>Grapevine: {}</title>\n", escape_html(query)));
+    feed.push_str(&format!("  <id>urn:grapevine:search:{}</id>\n", urlencoding::encode(query)));
+    feed.push_str(&format!("  <link rel=\"self\" href=\"{}\"/>\n", escape_html(self_url)));
+    feed.push_str(&format!("  <updated>{}</updated>\n", updated));
+    feed.push_str("  <generator>Grapevine</generator>\n");
+
+    for article in articles {
+        let summary = [article.domain.as_str(), article.sourcecountry.as_str(), article.language.as_str()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" · ");
+
+        feed.push_str("  <entry>\n");
+        feed.push_str(&format!("    <title>{}</title>\n", escape_html(&article.title)));
+        feed.push_str(&format!("    <link href=\"{}\"/>\n", escape_html(&article.url)));
+        feed.push_str(&format!("    <id>{}</id>\n", escape_html(&article.url)));
+        feed.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            atom_date(&article.seendate).unwrap_or_else(|| now.clone())
+        ));
+        feed.push_str(&format!(
+            "    <author><name>{}</name></author>\n",
+            escape_html(if article.domain.is_empty() { "GDELT" } else { &article.domain })
+        ));
+        if !summary.is_empty() {
+            feed.push_str(&format!("    <summary>{}</summary>\n", escape_html(&summary)));
+        }
+        feed.push_str("  </entry>\n");
+    }
+
+    feed.push_str("</feed>\n");
+    feed
+}
@@ -2,15 +2,119 @@ use gtk::prelude::*;
 use gtk::{glib, Label, Orientation, ScrolledWindow, ListBox, SearchEntry};
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
 use jetstream_oxide::{
-    events::{JetstreamEvent, commit::CommitEvent},
+    events::{JetstreamEvent, account::AccountStatus, commit::CommitEvent},
     DefaultJetstreamEndpoints, JetstreamCompression, JetstreamConfig, JetstreamConnector,
 };
 use atrium_api::record::KnownRecord;
 use atrium_api::types::string::Nsid;
 use atrium_api::app::bsky::feed::post::RecordData as PostRecord;
 
-use crate::data::{FirehosePost, PostEmbed, PostFacet, FacetType};
+use crate::data::{bsky_image_url, FirehosePost, PostEmbed, PostFacet, FacetType, ReplyContext, StreamAction, StreamRecord};
+use crate::account::{self, Interaction};
+use crate::alt_audit;
+use crate::appview;
+use crate::bookmarks::{self, Bookmark, BookmarkKind};
+use crate::char_stats;
+use crate::connection::{self, ConnectionReporter, ConnectionState, SessionEnd};
+use crate::country_mentions;
+use crate::demo;
+use crate::governor::Governor;
+use crate::hashtag_feed;
+use crate::history;
+use crate::stream_notices::{self, StreamNotice};
+use crate::error::FetchError;
+use crate::lightbox::{self, LightboxImage};
+use crate::profiles;
+use crate::safe_mode;
+use crate::slow_mode;
+use crate::spam;
+use crate::startup::{self, Priority};
+use crate::stats;
+use crate::toasts;
+use crate::sentiment::{self, Sentiment};
+use crate::settings::{AltTextFilter, RecordType, SavedSplit, Settings, SplitColor};
+use crate::split_rules::{self, SplitRules};
+use crate::translate::{self, TranslationConfig};
+use crate::video;
+
+/// Posts decoded on the Jetstream thread, read by the throughput meter
+static POSTS_RECEIVED: AtomicU64 = AtomicU64::new(0);
+
+/// Posts the oldest were dropped to make room for, read by the throughput meter
+static POSTS_DROPPED: AtomicU64 = AtomicU64::new(0);
+
+/// Record types besides posts that some split shows, one bit per type;
+/// Jetstream is resubscribed when it changes
+static WANTED_RECORDS: AtomicU8 = AtomicU8::new(0);
+
+/// Posts waiting in the channel before the meter shows the UI as falling behind
+const BACKLOG_WARNING: usize = 500;
+
+/// Posts the channel holds while the UI is paused or busy; beyond this the
+/// oldest are dropped so memory stays bounded
+const CHANNEL_CAPACITY: usize = 5_000;
+
+/// Deletes and account changes the channel holds; beyond this new ones are dropped
+const NOTICE_CAPACITY: usize = 1_000;
+/// Quiet time after a filter edit before a pane is rebuilt from the history
+const REFILL_DELAY: std::time::Duration = std::time::Duration::from_millis(300);
+
+/// Set at shutdown so the Jetstream thread stops instead of streaming to nobody
+static UI_CLOSED: AtomicBool = AtomicBool::new(false);
+
+/// Sending end of the post channel that drops the oldest record when it is full.
+/// Not Clone: it holds the one overflow receiver, so it is shared behind an Arc
+/// and the channel's receiver count stays the UI's plus that one.
+struct PostSender {
+    tx: flume::Sender<StreamRecord>,
+    /// Receiver used only to take the oldest record out of a full channel
+    overflow: flume::Receiver<StreamRecord>,
+    /// Deletes and account changes, for marking posts already shown
+    notices: flume::Sender<StreamNotice>,
+}
+
+impl PostSender {
+    fn bounded() -> (Arc<Self>, flume::Receiver<StreamRecord>, flume::Receiver<StreamNotice>) {
+        let (tx, rx) = flume::bounded(CHANNEL_CAPACITY);
+        let (notices, notices_rx) = flume::bounded(NOTICE_CAPACITY);
+        (Arc::new(Self { tx, overflow: rx.clone(), notices }), rx, notices_rx)
+    }
+
+    /// Queue a notice for the UI; dropped when the UI is behind, as it only
+    /// marks posts that are likely scrolled away by then
+    fn notify(&self, notice: StreamNotice) {
+        let _ = self.notices.try_send(notice);
+    }
+
+    /// Queue a post, like, repost or follow for the UI; Err once the UI has gone away
+    fn send(&self, mut record: StreamRecord) -> Result<(), ()> {
+        loop {
+            // The overflow receiver keeps the channel open, so count the UI's
+            if UI_CLOSED.load(Ordering::Relaxed) || self.tx.receiver_count() <= 1 {
+                return Err(());
+            }
+            match self.tx.try_send(record) {
+                Ok(()) => return Ok(()),
+                Err(flume::TrySendError::Full(returned)) => {
+                    if self.overflow.try_recv().is_ok() {
+                        POSTS_DROPPED.fetch_add(1, Ordering::Relaxed);
+                    }
+                    record = returned;
+                }
+                Err(flume::TrySendError::Disconnected(_)) => return Err(()),
+            }
+        }
+    }
+
+    /// Records waiting for the UI
+    fn len(&self) -> usize {
+        self.tx.len()
+    }
+}
 
 #[derive(Clone)]
 struct SplitPane {
@@ -18,49 +122,506 @@ struct SplitPane {
     list: ListBox,
     search_entry: SearchEntry,
     filter_keyword: Rc<RefCell<String>>,
+    sentiment_filter: Rc<RefCell<Option<Sentiment>>>,
+    translate: Rc<RefCell<bool>>,
+    /// Custom column title; empty to show the keyword
+    title: Rc<RefCell<String>>,
+    color: Rc<RefCell<SplitColor>>,
+    /// Regex, language, author and mute rules
+    rules: Rc<RefCell<SplitRules>>,
+    /// Refill waiting for filter edits to pause
+    pending_refill: Rc<RefCell<Option<glib::SourceId>>>,
+}
+
+impl SplitPane {
+    /// Whether any filter is set (splits without one stay empty)
+    fn has_filter(&self) -> bool {
+        !self.filter_keyword.borrow().is_empty()
+            || self.sentiment_filter.borrow().is_some()
+            || self.rules.borrow().has_filter()
+    }
+
+    /// The pane's settings as persisted and exported
+    fn to_saved(&self) -> SavedSplit {
+        let mut saved = SavedSplit {
+            keyword: self.filter_keyword.borrow().clone(),
+            title: self.title.borrow().trim().to_string(),
+            color: *self.color.borrow(),
+            sentiment: *self.sentiment_filter.borrow(),
+            translate: *self.translate.borrow(),
+            ..SavedSplit::default()
+        };
+        self.rules.borrow().write_to(&mut saved);
+        saved
+    }
+
+    /// Title shown in the column header
+    fn display_title(&self) -> String {
+        let title = self.title.borrow();
+        let keyword = self.filter_keyword.borrow();
+        if !title.trim().is_empty() {
+            title.trim().to_string()
+        } else if !keyword.is_empty() {
+            keyword.clone()
+        } else if self.rules.borrow().record_type() != RecordType::Posts {
+            self.rules.borrow().record_type().label().to_string()
+        } else {
+            "New split".to_string()
+        }
+    }
+
+    /// Show the pane's accent on its column
+    fn apply_color(&self) {
+        for color in SplitColor::ALL {
+            if let Some(class) = color.css_class() {
+                self.container.remove_css_class(class);
+            }
+        }
+        if let Some(class) = self.color.borrow().css_class() {
+            self.container.add_css_class(class);
+        }
+    }
+
+    /// Whether a post passes this pane's keyword, sentiment and rule filters
+    fn matches(&self, post: &FirehosePost) -> bool {
+        let keyword = self.filter_keyword.borrow();
+        let keyword_matches = keyword.is_empty() || post.text.to_lowercase().contains(&keyword.to_lowercase());
+
+        let sentiment_matches = match *self.sentiment_filter.borrow() {
+            Some(wanted) => Sentiment::from_score(post.sentiment) == wanted,
+            None => true,
+        };
+
+        keyword_matches && sentiment_matches && self.rules.borrow().matches(post)
+    }
+
+    /// Whether a like, repost or follow belongs in this pane; the keyword
+    /// is looked for in the DIDs and the subject URI
+    fn matches_action(&self, action: &StreamAction) -> bool {
+        let keyword = self.filter_keyword.borrow().to_lowercase();
+        let keyword_matches = keyword.is_empty()
+            || action.did.to_lowercase().contains(&keyword)
+            || action.subject.to_lowercase().contains(&keyword);
+
+        keyword_matches && self.rules.borrow().matches_action(action)
+    }
+
+    /// Clear the list and fill it with the matching posts still in the
+    /// history, so a changed filter starts from what already streamed past.
+    /// `require_filter` keeps splits without a filter empty, as they are live.
+    fn refill(&self, settings: &Settings, require_filter: bool) {
+        while let Some(child) = self.list.first_child() {
+            self.list.remove(&child);
+        }
+        if require_filter && !self.has_filter() {
+            return;
+        }
+
+        let translation = self.translate.borrow().then(|| TranslationConfig::from_settings(settings));
+        for post in history::matching(|post| self.matches(post)) {
+            add_message_to_list(&self.list, &post, translation.as_ref());
+        }
+    }
+
+    /// Refill once filter edits pause, rather than rebuilding the list on every keystroke
+    fn schedule_refill(&self, settings: &Rc<RefCell<Settings>>, require_filter: bool) {
+        if let Some(source) = self.pending_refill.borrow_mut().take() {
+            source.remove();
+        }
+
+        let pane = self.clone();
+        let settings = settings.clone();
+        let source = glib::timeout_add_local_once(REFILL_DELAY, move || {
+            pane.pending_refill.borrow_mut().take();
+            pane.refill(&settings.borrow(), require_filter);
+        });
+        *self.pending_refill.borrow_mut() = Some(source);
+    }
+}
+
+/// Dropdown for restricting a pane to posts of one sentiment
+fn create_sentiment_dropdown(sentiment_filter: Rc<RefCell<Option<Sentiment>>>) -> gtk::DropDown {
+    let dropdown = gtk::DropDown::from_strings(&["Any mood", "Positive", "Neutral", "Negative"]);
+    dropdown.set_tooltip_text(Some("Filter by sentiment"));
+    dropdown.set_selected(match *sentiment_filter.borrow() {
+        Some(Sentiment::Positive) => 1,
+        Some(Sentiment::Neutral) => 2,
+        Some(Sentiment::Negative) => 3,
+        None => 0,
+    });
+
+    // Connected first, so the pane's own handler refills with the new mood
+    dropdown.connect_selected_notify(move |dropdown| {
+        *sentiment_filter.borrow_mut() = match dropdown.selected() {
+            1 => Some(Sentiment::Positive),
+            2 => Some(Sentiment::Neutral),
+            3 => Some(Sentiment::Negative),
+            _ => None,
+        };
+    });
+
+    dropdown
+}
+
+/// A column in the deck: a header (drag handle, title, collapse toggle) above the pane body
+struct DeckColumn {
+    column: gtk::Box,
+    header: gtk::Box,
+    title: Label,
+}
+
+/// Wrap a pane body in a deck column that can be collapsed and dragged to a new position
+fn create_deck_column(deck: &gtk::Box, title: &str, body: &gtk::Box) -> DeckColumn {
+    // Fixed width from the layout config, set explicitly so the expanding title
+    // doesn't stretch it: the deck scrolls sideways instead of squeezing columns
+    let column = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(8)
+        .hexpand(false)
+        .vexpand(true)
+        .css_classes(vec!["deck-column".to_string()])
+        .build();
+
+    // Header doubles as the drag handle for reordering
+    let header = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .css_classes(vec!["deck-column-header".to_string()])
+        .build();
+
+    let handle = gtk::Image::builder()
+        .icon_name("list-drag-handle-symbolic")
+        .tooltip_text("Drag to reorder")
+        .css_classes(vec!["dim-label".to_string()])
+        .build();
+
+    let title_label = Label::builder()
+        .label(title)
+        .xalign(0.0)
+        .hexpand(true)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .css_classes(vec!["heading".to_string()])
+        .build();
+
+    let collapse_button = gtk::ToggleButton::builder()
+        .icon_name("go-previous-symbolic")
+        .tooltip_text("Collapse column")
+        .css_classes(vec!["flat".to_string()])
+        .build();
+
+    header.append(&handle);
+    header.append(&title_label);
+    header.append(&collapse_button);
+
+    column.append(&header);
+    column.append(body);
+
+    // Collapsed columns shrink to a strip of header buttons
+    let column_for_collapse = column.clone();
+    let header_for_collapse = header.clone();
+    let title_for_collapse = title_label.clone();
+    let body_for_collapse = body.clone();
+    collapse_button.connect_toggled(move |button| {
+        let collapsed = button.is_active();
+
+        body_for_collapse.set_visible(!collapsed);
+        title_for_collapse.set_visible(!collapsed);
+        header_for_collapse.set_orientation(if collapsed {
+            Orientation::Vertical
+        } else {
+            Orientation::Horizontal
+        });
+        // The expanded width comes from the stylesheet's layout config
+        if collapsed {
+            column_for_collapse.add_css_class("collapsed");
+        } else {
+            column_for_collapse.remove_css_class("collapsed");
+        }
+
+        if collapsed {
+            button.set_icon_name("go-next-symbolic");
+            button.set_tooltip_text(Some(&format!("Expand {}", title_for_collapse.text())));
+        } else {
+            button.set_icon_name("go-previous-symbolic");
+            button.set_tooltip_text(Some("Collapse column"));
+        }
+    });
+
+    // Drag the header to move the column
+    let drag_source = gtk::DragSource::builder()
+        .actions(gtk::gdk::DragAction::MOVE)
+        .build();
+
+    let column_weak = column.downgrade();
+    drag_source.connect_prepare(move |_, _, _| {
+        let column = column_weak.upgrade()?;
+        Some(gtk::gdk::ContentProvider::for_value(&column.to_value()))
+    });
+
+    let column_weak = column.downgrade();
+    drag_source.connect_drag_begin(move |source, _| {
+        if let Some(column) = column_weak.upgrade() {
+            let paintable = gtk::WidgetPaintable::new(Some(&column));
+            source.set_icon(Some(&paintable), 0, 0);
+        }
+    });
+    header.add_controller(drag_source);
+
+    // Dropping on a column places the dragged one before or after it
+    let drop_target = gtk::DropTarget::new(gtk::Box::static_type(), gtk::gdk::DragAction::MOVE);
+
+    let deck_clone = deck.clone();
+    let column_weak = column.downgrade();
+    drop_target.connect_drop(move |_, value, x, _| {
+        let (Ok(dragged), Some(target)) = (value.get::<gtk::Box>(), column_weak.upgrade()) else {
+            return false;
+        };
+
+        if dragged == target || dragged.parent().as_ref() != Some(deck_clone.upcast_ref()) {
+            return false;
+        }
+
+        if x < target.width() as f64 / 2.0 {
+            let previous = target.prev_sibling();
+            if previous.as_ref() != Some(dragged.upcast_ref()) {
+                deck_clone.reorder_child_after(&dragged, previous.as_ref());
+            }
+        } else {
+            deck_clone.reorder_child_after(&dragged, Some(&target));
+        }
+
+        true
+    });
+    column.add_controller(drop_target);
+
+    DeckColumn {
+        column,
+        header,
+        title: title_label,
+    }
+}
+
+/// Controls in a split's settings popover
+struct SplitSettingsPopover {
+    button: gtk::MenuButton,
+    title_entry: gtk::Entry,
+    swatches: Vec<(SplitColor, gtk::ToggleButton)>,
+    record_type_dropdown: gtk::DropDown,
+    regex_entry: gtk::Entry,
+    languages_entry: gtk::Entry,
+    authors_entry: gtk::Entry,
+    mute_entry: gtk::Entry,
+    links_entry: gtk::Entry,
+    alt_text_dropdown: gtk::DropDown,
+    export_button: gtk::Button,
+}
+
+/// Header button whose popover renames a split, picks its accent color and
+/// edits its rules
+fn create_split_settings_button() -> SplitSettingsPopover {
+    let popover_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(8)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(6)
+        .margin_end(6)
+        .build();
+
+    let title_entry = gtk::Entry::builder()
+        .placeholder_text("Title (defaults to the keyword)")
+        .build();
+    popover_box.append(&title_entry);
+
+    let swatch_box = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .halign(gtk::Align::Center)
+        .build();
+
+    let mut swatches: Vec<(SplitColor, gtk::ToggleButton)> = Vec::new();
+    for color in SplitColor::ALL {
+        let swatch = gtk::ToggleButton::builder()
+            .tooltip_text(color.label())
+            .css_classes(vec!["split-swatch".to_string()])
+            .build();
+        if let Some(class) = color.css_class() {
+            swatch.add_css_class(class);
+        }
+        if let Some((_, first)) = swatches.first() {
+            swatch.set_group(Some(first));
+        }
+        swatch_box.append(&swatch);
+        swatches.push((color, swatch));
+    }
+    popover_box.append(&swatch_box);
+
+    let rules_label = Label::builder()
+        .label("Rules")
+        .xalign(0.0)
+        .margin_top(6)
+        .build();
+    rules_label.add_css_class("heading");
+    popover_box.append(&rules_label);
+
+    let record_type_labels: Vec<&str> = RecordType::ALL.iter().map(|record_type| record_type.label()).collect();
+    let record_type_dropdown = gtk::DropDown::from_strings(&record_type_labels);
+    record_type_dropdown.set_tooltip_text(Some("Show posts, or likes, reposts or follows as they happen"));
+    popover_box.append(&record_type_dropdown);
+
+    let rule_entry = |placeholder: &str, tooltip: &str| {
+        let entry = gtk::Entry::builder()
+            .placeholder_text(placeholder)
+            .tooltip_text(tooltip)
+            .build();
+        popover_box.append(&entry);
+        entry
+    };
+    let regex_entry = rule_entry("Regular expression", "Only show posts matching this pattern (case-insensitive)");
+    let languages_entry = rule_entry("Languages, e.g. en, de", "Only show posts declaring one of these languages");
+    let authors_entry = rule_entry("Author DIDs", "Only show posts by these accounts, comma-separated");
+    let mute_entry = rule_entry("Mute terms", "Hide posts containing any of these, comma-separated");
+    let links_entry = rule_entry("Link domains, e.g. reuters.com", "Only show posts linking to one of these sites");
+
+    let alt_text_labels: Vec<&str> = AltTextFilter::ALL.iter().map(|filter| filter.label()).collect();
+    let alt_text_dropdown = gtk::DropDown::from_strings(&alt_text_labels);
+    alt_text_dropdown.set_tooltip_text(Some("Only show image posts with, or without, alt text"));
+    popover_box.append(&alt_text_dropdown);
+
+    let export_button = gtk::Button::builder()
+        .label("Export Rules…")
+        .margin_top(6)
+        .build();
+    popover_box.append(&export_button);
+
+    let popover = gtk::Popover::builder()
+        .child(&popover_box)
+        .build();
+
+    let button = gtk::MenuButton::builder()
+        .icon_name("document-edit-symbolic")
+        .tooltip_text("Split settings")
+        .css_classes(vec!["flat".to_string()])
+        .popover(&popover)
+        .build();
+
+    SplitSettingsPopover {
+        button,
+        title_entry,
+        swatches,
+        record_type_dropdown,
+        regex_entry,
+        languages_entry,
+        authors_entry,
+        mute_entry,
+        links_entry,
+        alt_text_dropdown,
+        export_button,
+    }
 }
 
 #[derive(Clone)]
 pub struct FirehoseControl {
-    root_container: gtk::Box,
+    deck: gtk::Box,
+    /// Swipeable stand-in for the deck in narrow windows
+    carousel: libadwaita::Carousel,
+    /// Shows the deck or the carousel
+    layout: gtk::Stack,
+    compact: Rc<RefCell<bool>>,
     main_pane: SplitPane,
     splits: Rc<RefCell<Vec<SplitPane>>>,
-    message_sender: flume::Sender<FirehosePost>,
+    /// Followed hashtags and their columns, in the order they were followed
+    hashtag_feeds: Rc<RefCell<Vec<(String, gtk::Box)>>>,
+    message_sender: Arc<PostSender>,
     scroll_paused_until: Rc<RefCell<std::time::Instant>>,
+    settings: Rc<RefCell<Settings>>,
+    /// Shows the stream, or a blank page with a resume button in focus mode
+    focus_stack: gtk::Stack,
 }
 
 impl FirehoseControl {
+    /// Statusline label showing posts per second from Jetstream, red while the
+    /// UI thread lets posts pile up in the channel
+    pub fn create_throughput_meter(&self) -> Label {
+        let label = Label::builder()
+            .label("0/s")
+            .width_chars(6)
+            .xalign(1.0)
+            .tooltip_text("Waiting for Jetstream")
+            .build();
+        label.add_css_class("monospace");
+        label.add_css_class("throughput-meter");
+
+        let sender = self.message_sender.clone();
+        let label_for_tick = label.clone();
+        let mut last_count = POSTS_RECEIVED.load(Ordering::Relaxed);
+        glib::timeout_add_seconds_local(1, move || {
+            let count = POSTS_RECEIVED.load(Ordering::Relaxed);
+            let rate = count.saturating_sub(last_count);
+            last_count = count;
+
+            // Posts sent by the Jetstream thread but not yet picked up by the UI,
+            // and those dropped since the last tick because the channel was full
+            let backlog = sender.len();
+            let dropped = POSTS_DROPPED.swap(0, Ordering::Relaxed);
+            stats::record_dropped(dropped);
+
+            label_for_tick.set_label(&format!("{}/s", rate));
+            label_for_tick.set_tooltip_text(Some(&format!(
+                "{} posts per second from Jetstream, {} waiting for the UI, {} dropped this session",
+                rate,
+                backlog,
+                stats::session().posts_dropped,
+            )));
+
+            if backlog > BACKLOG_WARNING || dropped > 0 {
+                label_for_tick.add_css_class("error");
+            } else {
+                label_for_tick.remove_css_class("error");
+            }
+            if rate == 0 {
+                label_for_tick.add_css_class("dim-label");
+            } else {
+                label_for_tick.remove_css_class("dim-label");
+            }
+
+            glib::ControlFlow::Continue
+        });
+
+        label
+    }
+
     pub fn add_split(&self) {
-        let mut splits = self.splits.borrow_mut();
+        self.add_split_from(&SavedSplit::default(), true);
+        self.save_splits();
+    }
 
-        // Create a new split pane
+    /// Add a split with a saved keyword, title and color; `focus` moves the
+    /// keyboard to its filter entry
+    fn add_split_from(&self, saved: &SavedSplit, focus: bool) {
+        // Create the body of the new split
         let split_box = gtk::Box::builder()
             .orientation(Orientation::Vertical)
             .spacing(8)
-            .hexpand(true)
+            .vexpand(true)
             .build();
 
-        // Create header box with search and close button
+        // Create header box with search and sentiment filter
         let header_box = gtk::Box::builder()
             .orientation(Orientation::Horizontal)
             .spacing(8)
+            .margin_start(8)
+            .margin_end(8)
             .build();
 
         let search_entry = SearchEntry::builder()
             .placeholder_text("Filter messages by keyword...")
             .hexpand(true)
-            .margin_start(8)
-            .margin_end(0)
             .build();
 
-        let close_button = gtk::Button::builder()
-            .icon_name("window-close-symbolic")
-            .tooltip_text("Close this split")
-            .margin_end(8)
-            .build();
+        let sentiment_filter = Rc::new(RefCell::new(saved.sentiment));
 
         header_box.append(&search_entry);
-        header_box.append(&close_button);
 
         // Create list for this split
         let split_list = ListBox::builder()
@@ -69,7 +630,7 @@ impl FirehoseControl {
 
         let split_scrolled = ScrolledWindow::builder()
             .vexpand(true)
-            .hexpand(true)
+            .hscrollbar_policy(gtk::PolicyType::Never)
             .build();
         split_scrolled.set_child(Some(&split_list));
 
@@ -81,191 +642,566 @@ impl FirehoseControl {
             *scroll_paused_clone.borrow_mut() = std::time::Instant::now() + std::time::Duration::from_secs(2);
         });
 
+        let sentiment_dropdown = create_sentiment_dropdown(sentiment_filter.clone());
+        header_box.append(&sentiment_dropdown);
+
+        // Translate foreign-language matches into the configured target language
+        let translate = Rc::new(RefCell::new(saved.translate));
+        let translate_button = gtk::ToggleButton::builder()
+            .icon_name("preferences-desktop-locale-symbolic")
+            .tooltip_text("Translate foreign-language posts")
+            .active(saved.translate)
+            .build();
+
+        let translate_for_toggle = translate.clone();
+        translate_button.connect_toggled(move |button| {
+            *translate_for_toggle.borrow_mut() = button.is_active();
+        });
+        header_box.append(&translate_button);
+
+        // Alt text adoption across the stream, for splits auditing it
+        let alt_audit_chart = alt_audit::create_audit_chart();
+        alt_audit_chart.set_visible(saved.alt_text != AltTextFilter::Any);
+
         split_box.append(&header_box);
+        split_box.append(&alt_audit_chart);
         split_box.append(&split_scrolled);
 
-        // Create filter keyword storage
-        let filter_keyword = Rc::new(RefCell::new(String::new()));
+        // Wrap the split in a deck column with rename and close buttons
+        let deck_column = create_deck_column(&self.deck, "New split", &split_box);
+
+        let popover = create_split_settings_button();
+        deck_column.header.append(&popover.button);
+
+        let close_button = gtk::Button::builder()
+            .icon_name("window-close-symbolic")
+            .tooltip_text("Close this split")
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        deck_column.header.append(&close_button);
+
+        let column = deck_column.column;
+        let split = SplitPane {
+            container: column.clone(),
+            list: split_list,
+            search_entry: search_entry.clone(),
+            filter_keyword: Rc::new(RefCell::new(String::new())),
+            sentiment_filter,
+            translate,
+            title: Rc::new(RefCell::new(saved.title.clone())),
+            color: Rc::new(RefCell::new(saved.color)),
+            rules: Rc::new(RefCell::new(SplitRules::from_saved(saved))),
+            pending_refill: Rc::new(RefCell::new(None)),
+        };
 
-        // Set up search filtering
-        let split_list_for_search = split_list.clone();
-        let filter_keyword_for_search = filter_keyword.clone();
+        // Set up search filtering; the column title follows the keyword unless renamed
+        let control_for_search = self.clone();
+        let split_for_search = split.clone();
+        let title_for_search = deck_column.title.clone();
         search_entry.connect_search_changed(move |entry| {
-            let keyword = entry.text().to_string();
-            *filter_keyword_for_search.borrow_mut() = keyword;
+            *split_for_search.filter_keyword.borrow_mut() = entry.text().to_string();
+            title_for_search.set_label(&split_for_search.display_title());
 
-            // Clear the list when search changes
-            while let Some(child) = split_list_for_search.first_child() {
-                split_list_for_search.remove(&child);
-            }
+            split_for_search.schedule_refill(&control_for_search.settings, true);
+            control_for_search.save_splits();
         });
 
-        // Add the new split pane
-        let split_pane = SplitPane {
-            container: split_box.clone(),
-            list: split_list.clone(),
-            search_entry: search_entry.clone(),
-            filter_keyword: filter_keyword.clone(),
+        let control_for_rename = self.clone();
+        let split_for_rename = split.clone();
+        let title_for_rename = deck_column.title.clone();
+        popover.title_entry.set_text(&saved.title);
+        popover.title_entry.connect_changed(move |entry| {
+            *split_for_rename.title.borrow_mut() = entry.text().to_string();
+            title_for_rename.set_label(&split_for_rename.display_title());
+            control_for_rename.save_splits();
+        });
+
+        for (color, swatch) in popover.swatches {
+            swatch.set_active(color == saved.color);
+
+            let control_for_color = self.clone();
+            let split_for_color = split.clone();
+            swatch.connect_toggled(move |swatch| {
+                if swatch.is_active() {
+                    *split_for_color.color.borrow_mut() = color;
+                    split_for_color.apply_color();
+                    control_for_color.save_splits();
+                }
+            });
+        }
+
+        // Mood and translation are saved with the rest of the split
+        let control_for_mood = self.clone();
+        let split_for_mood = split.clone();
+        sentiment_dropdown.connect_selected_notify(move |_| {
+            split_for_mood.schedule_refill(&control_for_mood.settings, true);
+            control_for_mood.save_splits();
+        });
+        let control_for_translate = self.clone();
+        translate_button.connect_toggled(move |_| control_for_translate.save_splits());
+
+        popover.regex_entry.set_text(&saved.regex);
+        popover.languages_entry.set_text(&saved.languages.join(", "));
+        popover.authors_entry.set_text(&saved.authors.join(", "));
+        popover.mute_entry.set_text(&saved.mute_terms.join(", "));
+        popover.links_entry.set_text(&saved.link_domains.join(", "));
+        popover.alt_text_dropdown.set_selected(
+            AltTextFilter::ALL.iter().position(|filter| *filter == saved.alt_text).unwrap_or(0) as u32,
+        );
+        popover.record_type_dropdown.set_selected(
+            RecordType::ALL.iter().position(|record_type| *record_type == saved.record_type).unwrap_or(0) as u32,
+        );
+
+        let update_rules: Rc<dyn Fn()> = {
+            let control = self.clone();
+            let split = split.clone();
+            let regex_entry = popover.regex_entry.clone();
+            let languages_entry = popover.languages_entry.clone();
+            let authors_entry = popover.authors_entry.clone();
+            let mute_entry = popover.mute_entry.clone();
+            let links_entry = popover.links_entry.clone();
+            let alt_text_dropdown = popover.alt_text_dropdown.clone();
+            let record_type_dropdown = popover.record_type_dropdown.clone();
+            let title_label = deck_column.title.clone();
+            Rc::new(move || {
+                // Keep the last valid rules while a pattern is being typed
+                if let Err(e) = split_rules::compile_pattern(&regex_entry.text()) {
+                    regex_entry.add_css_class("error");
+                    regex_entry.set_tooltip_text(Some(&e.to_string()));
+                    return;
+                }
+                regex_entry.remove_css_class("error");
+                regex_entry.set_tooltip_text(Some("Only show posts matching this pattern (case-insensitive)"));
+
+                let edited = SavedSplit {
+                    regex: regex_entry.text().to_string(),
+                    languages: split_rules::parse_list(&languages_entry.text()),
+                    authors: split_rules::parse_list(&authors_entry.text()),
+                    mute_terms: split_rules::parse_list(&mute_entry.text()),
+                    link_domains: split_rules::parse_list(&links_entry.text()),
+                    alt_text: AltTextFilter::ALL
+                        .get(alt_text_dropdown.selected() as usize)
+                        .copied()
+                        .unwrap_or_default(),
+                    record_type: RecordType::ALL
+                        .get(record_type_dropdown.selected() as usize)
+                        .copied()
+                        .unwrap_or_default(),
+                    ..SavedSplit::default()
+                };
+                alt_audit_chart.set_visible(edited.alt_text != AltTextFilter::Any);
+                *split.rules.borrow_mut() = SplitRules::from_saved(&edited);
+                title_label.set_label(&split.display_title());
+
+                split.schedule_refill(&control.settings, true);
+                control.save_splits();
+            })
         };
+        for entry in [
+            &popover.regex_entry,
+            &popover.languages_entry,
+            &popover.authors_entry,
+            &popover.mute_entry,
+            &popover.links_entry,
+        ] {
+            let update_rules = update_rules.clone();
+            entry.connect_changed(move |_| update_rules());
+        }
+        for dropdown in [&popover.alt_text_dropdown, &popover.record_type_dropdown] {
+            let update_rules = update_rules.clone();
+            dropdown.connect_selected_notify(move |_| update_rules());
+        }
 
-        splits.push(split_pane);
+        let split_for_export = split.clone();
+        let menu_button = popover.button.clone();
+        popover.export_button.connect_clicked(move |button| {
+            menu_button.popdown();
+            let window = button.root().and_downcast::<gtk::Window>();
+            split_rules::export_rules(window.as_ref(), split_for_export.to_saved());
+        });
 
-        // Rebuild the entire paned structure
-        drop(splits); // Drop the borrow before rebuilding
-        self.rebuild_layout();
+        split.apply_color();
+        deck_column.title.set_label(&split.display_title());
+        self.append_column(&column);
+        self.splits.borrow_mut().push(split);
+
+        // Restoring the keyword runs the search handler above, which also saves
+        search_entry.set_text(&saved.keyword);
+
+        // Focusing the entry also scrolls the new column into view
+        if focus {
+            if *self.compact.borrow() {
+                self.carousel.scroll_to(&column, true);
+            }
+            search_entry.grab_focus();
+        }
 
         // Set up close button
         let control_clone = self.clone();
-        let split_box_clone = split_box.clone();
         close_button.connect_clicked(move |_| {
-            // Find and remove this split
-            let mut splits = control_clone.splits.borrow_mut();
-            if let Some(pos) = splits.iter().position(|s| s.container == split_box_clone) {
-                splits.remove(pos);
-                drop(splits); // Drop the borrow before rebuilding
-                control_clone.rebuild_layout();
+            control_clone.remove_split(&column);
+        });
+    }
+
+    /// Add a split filtering by a keyword, e.g. for a script over D-Bus
+    pub fn add_keyword_split(&self, keyword: &str) {
+        let saved = SavedSplit { keyword: keyword.to_string(), ..SavedSplit::default() };
+        self.add_split_from(&saved, false);
+        self.save_splits();
+    }
+
+    /// Add a split watching the reaction to a news story
+    pub fn watch_story(&self, title: &str, domain: &str) {
+        let saved = split_rules::story_split(title, domain);
+        self.add_split_from(&saved, true);
+        self.save_splits();
+    }
+
+    /// Add a column of the latest posts with a hashtag, searched on the AppView;
+    /// following a tag twice just scrolls to its column
+    pub fn follow_hashtag(&self, text: &str) {
+        let tag = hashtag_feed::normalize_tag(text);
+        if tag.is_empty() {
+            return;
+        }
+        let existing = self.hashtag_feeds.borrow().iter().find(|(followed, _)| *followed == tag).map(|(_, column)| column.clone());
+        let column = match existing {
+            Some(column) => column,
+            None => {
+                let column = self.add_hashtag_column(&tag);
+                self.save_hashtag_feeds();
+                column
             }
+        };
+        if *self.compact.borrow() {
+            self.carousel.scroll_to(&column, true);
+        }
+    }
+
+    fn add_hashtag_column(&self, tag: &str) -> gtk::Box {
+        let body = hashtag_feed::create_feed_body(tag);
+        let deck_column = create_deck_column(&self.deck, &format!("#{}", tag), &body);
+        deck_column.title.set_tooltip_text(Some("Latest posts with this hashtag, from Bluesky search"));
+
+        let close_button = gtk::Button::builder()
+            .icon_name("window-close-symbolic")
+            .tooltip_text("Unfollow this hashtag")
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        deck_column.header.append(&close_button);
+
+        let column = deck_column.column;
+        self.append_column(&column);
+        self.hashtag_feeds.borrow_mut().push((tag.to_string(), column.clone()));
+
+        let control = self.clone();
+        let column_for_close = column.clone();
+        close_button.connect_clicked(move |_| {
+            control.hashtag_feeds.borrow_mut().retain(|(_, column)| column != &column_for_close);
+            if *control.compact.borrow() {
+                control.carousel.remove(&column_for_close);
+            } else {
+                control.deck.remove(&column_for_close);
+            }
+            control.save_hashtag_feeds();
         });
+        column
+    }
+
+    fn save_hashtag_feeds(&self) {
+        let mut settings = self.settings.borrow_mut();
+        settings.hashtag_feeds = self.hashtag_feeds.borrow().iter().map(|(tag, _)| tag.clone()).collect();
+        settings.save();
     }
 
-    fn rebuild_layout(&self) {
-        // Remove all children from root container
-        while let Some(child) = self.root_container.first_child() {
-            self.root_container.remove(&child);
+    /// Open a rules file exported from another split and add it as a new one
+    pub fn import_split(&self, parent: Option<&gtk::Window>) {
+        let control = self.clone();
+        split_rules::import_rules(parent, move |saved| {
+            control.add_split_from(&saved, true);
+            control.save_splits();
+        });
+    }
+
+    /// Bar offering the starter splits on a first visit, so a new deck shows
+    /// something narrower than the full-speed stream; None once it was answered
+    /// or when there are splits already
+    fn create_starter_bar(&self) -> Option<gtk::Revealer> {
+        {
+            let settings = self.settings.borrow();
+            if settings.firehose_onboarding_done || !settings.splits.is_empty() {
+                return None;
+            }
+        }
+
+        let bar = gtk::Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(8)
+            .build();
+        bar.add_css_class("catch-up-bar");
+
+        let label = Label::builder()
+            .label("The firehose is every public post. Start with a filtered split:")
+            .xalign(0.0)
+            .hexpand(true)
+            .wrap(true)
+            .build();
+        bar.append(&label);
+
+        let revealer = gtk::Revealer::builder()
+            .child(&bar)
+            .reveal_child(true)
+            .build();
+
+        let mark_done = {
+            let settings = self.settings.clone();
+            move || {
+                let mut settings = settings.borrow_mut();
+                settings.firehose_onboarding_done = true;
+                settings.save();
+            }
+        };
+
+        for (description, saved) in split_rules::starter_splits() {
+            let button = gtk::Button::builder()
+                .label(saved.title.as_str())
+                .tooltip_text(description)
+                .build();
+            bar.append(&button);
+
+            let control = self.clone();
+            let mark_done = mark_done.clone();
+            button.connect_clicked(move |button| {
+                control.add_split_from(&saved, false);
+                control.save_splits();
+                mark_done();
+                button.set_sensitive(false);
+            });
         }
 
+        let dismiss_button = gtk::Button::builder()
+            .label("No Thanks")
+            .build();
+        dismiss_button.add_css_class("flat");
+        bar.append(&dismiss_button);
+
+        let revealer_for_dismiss = revealer.clone();
+        dismiss_button.connect_clicked(move |_| {
+            mark_done();
+            revealer_for_dismiss.set_reveal_child(false);
+        });
+
+        Some(revealer)
+    }
+
+    /// Persist the splits' filters, rules and appearance in deck order
+    fn save_splits(&self) {
         let splits = self.splits.borrow();
+        let mut saved = Vec::new();
+        for widget in self.columns() {
+            if let Some(split) = splits.iter().find(|split| split.container.upcast_ref::<gtk::Widget>() == &widget) {
+                saved.push(split.to_saved());
+            }
+        }
+        set_wanted_records(&saved);
+
+        let mut settings = self.settings.borrow_mut();
+        settings.splits = saved;
+        settings.save();
+    }
 
-        // Unparent all widgets before rebuilding
-        if let Some(parent) = self.main_pane.container.parent() {
-            if let Some(paned) = parent.downcast_ref::<gtk::Paned>() {
-                paned.set_start_child(None::<&gtk::Widget>);
-                paned.set_end_child(None::<&gtk::Widget>);
+    /// Remove the split whose column matches
+    fn remove_split(&self, container: &gtk::Box) {
+        let mut splits = self.splits.borrow_mut();
+        if let Some(pos) = splits.iter().position(|s| &s.container == container) {
+            splits.remove(pos);
+            if *self.compact.borrow() {
+                self.carousel.remove(container);
+            } else {
+                self.deck.remove(container);
             }
         }
+        drop(splits);
+        self.save_splits();
+    }
 
-        for split in splits.iter() {
-            if let Some(parent) = split.container.parent() {
-                if let Some(paned) = parent.downcast_ref::<gtk::Paned>() {
-                    paned.set_start_child(None::<&gtk::Widget>);
-                    paned.set_end_child(None::<&gtk::Widget>);
-                }
+    /// Columns in order, from whichever of the deck or carousel holds them
+    fn columns(&self) -> Vec<gtk::Widget> {
+        if *self.compact.borrow() {
+            (0..self.carousel.n_pages()).map(|index| self.carousel.nth_page(index)).collect()
+        } else {
+            let mut columns = Vec::new();
+            let mut child = self.deck.first_child();
+            while let Some(widget) = child {
+                child = widget.next_sibling();
+                columns.push(widget);
             }
+            columns
         }
+    }
 
-        if splits.is_empty() {
-            // Only show the main pane
-            self.root_container.append(&self.main_pane.container);
+    fn append_column(&self, column: &gtk::Box) {
+        if *self.compact.borrow() {
+            self.carousel.append(column);
         } else {
-            // Create nested paned widgets
-            let orientation = if self.root_container.width() > self.root_container.height() {
-                Orientation::Horizontal
+            self.deck.append(column);
+        }
+    }
+
+    /// Swipe between full-width columns in narrow windows instead of
+    /// scrolling the deck sideways
+    pub fn set_compact(&self, compact: bool) {
+        if *self.compact.borrow() == compact {
+            return;
+        }
+
+        let columns = self.columns();
+        for column in &columns {
+            if compact {
+                self.deck.remove(column);
+                self.carousel.append(column);
             } else {
-                Orientation::Vertical
-            };
+                self.carousel.remove(column);
+                self.deck.append(column);
+            }
+        }
+        *self.compact.borrow_mut() = compact;
+        self.layout.set_visible_child_name(if compact { "carousel" } else { "deck" });
+    }
 
-            // Start with the main pane
-            let mut current_widget: gtk::Widget = self.main_pane.container.clone().into();
-
-            // Add each split with a paned separator
-            for split in splits.iter() {
-                let paned = gtk::Paned::builder()
-                    .orientation(orientation)
-                    .wide_handle(true)
-                    .resize_start_child(true)
-                    .shrink_start_child(false)
-                    .resize_end_child(true)
-                    .shrink_end_child(false)
-                    .build();
+    /// Close the most recently added split, if any
+    pub fn close_last_split(&self) {
+        let last = self.splits.borrow().last().map(|s| s.container.clone());
+        if let Some(container) = last {
+            self.remove_split(&container);
+        }
+    }
 
-                paned.set_start_child(Some(&current_widget));
-                paned.set_end_child(Some(&split.container));
-
-                // Set position to split evenly
-                let paned_weak = paned.downgrade();
-                paned.add_tick_callback(move |_widget, _clock| {
-                    if let Some(paned) = paned_weak.upgrade() {
-                        let total_size = if paned.orientation() == Orientation::Horizontal {
-                            paned.width()
-                        } else {
-                            paned.height()
-                        };
-
-                        if total_size > 0 && paned.position() == 0 {
-                            paned.set_position(total_size / 2);
-                        }
-                    }
-                    glib::ControlFlow::Continue
-                });
+    /// Keywords of the splits that filter by one, in deck order
+    pub fn split_keywords(&self) -> Vec<String> {
+        self.splits
+            .borrow()
+            .iter()
+            .map(|split| split.filter_keyword.borrow().trim().to_string())
+            .filter(|keyword| !keyword.is_empty())
+            .collect()
+    }
 
-                current_widget = paned.into();
+    /// Blank the view while focus mode has the stream paused, and clear out
+    /// what was showing so the stream starts afresh when it's over
+    pub fn set_focus_mode(&self, enabled: bool) {
+        self.focus_stack.set_visible_child_name(if enabled { "focus" } else { "stream" });
+        if !enabled {
+            return;
+        }
+        history::clear();
+        for list in std::iter::once(&self.main_pane.list).chain(self.splits.borrow().iter().map(|split| &split.list)) {
+            while let Some(child) = list.first_child() {
+                list.remove(&child);
             }
-
-            self.root_container.append(&current_widget);
         }
+    }
 
-        // Add tick callback to handle orientation changes
-        let root_weak = self.root_container.downgrade();
-        let control_clone = self.clone();
+    /// Move keyboard focus to the filter entry of the main pane
+    pub fn focus_search(&self) {
+        self.main_pane.search_entry.grab_focus();
+    }
 
-        self.root_container.add_tick_callback(move |_widget, _clock| {
-            if let Some(root) = root_weak.upgrade() {
-                let width = root.width();
-                let height = root.height();
+    fn broadcast_message(&self, post: &FirehosePost) {
+        let splits = self.splits.borrow();
+        let mut translation: Option<TranslationConfig> = None;
 
-                if width > 0 && height > 0 {
-                    let should_be_horizontal = width > height;
+        for split in splits.iter() {
+            if split.has_filter() && split.matches(post) {
+                stats::record_filter_match();
 
-                    // Check if we need to rebuild due to orientation change
-                    if let Some(first_child) = root.first_child() {
-                        if let Some(paned) = first_child.downcast_ref::<gtk::Paned>() {
-                            let is_horizontal = paned.orientation() == Orientation::Horizontal;
+                let keyword = split.filter_keyword.borrow();
+                if !keyword.is_empty() {
+                    crate::dbus::emit_keyword_match(&keyword, post);
+                }
 
-                            if should_be_horizontal != is_horizontal {
-                                control_clone.rebuild_layout();
-                            }
-                        }
-                    }
+                if *split.translate.borrow() {
+                    let config = translation
+                        .get_or_insert_with(|| TranslationConfig::from_settings(&self.settings.borrow()));
+                    add_message_to_list(&split.list, post, Some(config));
+                } else {
+                    add_message_to_list(&split.list, post, None);
                 }
             }
-            glib::ControlFlow::Continue
-        });
+        }
     }
 
-    fn broadcast_message(&self, post: &FirehosePost) {
-        let splits = self.splits.borrow();
-        for split in splits.iter() {
-            let keyword = split.filter_keyword.borrow().clone();
-            if !keyword.is_empty() && post.text.to_lowercase().contains(&keyword.to_lowercase()) {
-                add_message_to_list(&split.list, post);
+    /// Whether some split shows this like, repost or follow
+    fn wants_action(&self, action: &StreamAction) -> bool {
+        self.splits.borrow().iter().any(|split| split.matches_action(action))
+    }
+
+    fn broadcast_action(&self, action: &StreamAction) {
+        for split in self.splits.borrow().iter() {
+            if split.matches_action(action) {
+                stats::record_filter_match();
+                add_action_to_list(&split.list, action);
             }
         }
     }
 }
 
-pub fn create_firehose_view() -> (gtk::Box, FirehoseControl) {
+/// Stop the Jetstream thread; call once as the app shuts down
+pub fn shutdown() {
+    UI_CLOSED.store(true, Ordering::Relaxed);
+}
+
+/// Bit for a record type in `WANTED_RECORDS`
+fn record_bit(record_type: RecordType) -> u8 {
+    1 << RecordType::ALL.iter().position(|candidate| *candidate == record_type).unwrap_or(0)
+}
+
+/// Note which record types the splits show, so the stream subscribes to
+/// their collections too
+fn set_wanted_records(splits: &[SavedSplit]) {
+    let wanted = splits
+        .iter()
+        .filter(|split| split.record_type != RecordType::Posts)
+        .fold(0, |wanted, split| wanted | record_bit(split.record_type));
+    WANTED_RECORDS.store(wanted, Ordering::Relaxed);
+}
+
+pub fn create_firehose_view(settings: Rc<RefCell<Settings>>) -> (gtk::Box, FirehoseControl) {
     let container = gtk::Box::builder()
         .orientation(Orientation::Vertical)
         .build();
 
-    // Create root container that will hold the dynamic paned structure
-    let root_container = gtk::Box::builder()
+    // Create the deck: a horizontally scrolling row of columns
+    let deck = gtk::Box::builder()
         .orientation(Orientation::Horizontal)
         .spacing(0)
         .hexpand(true)
         .vexpand(true)
         .build();
 
+    let deck_scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Automatic)
+        .vscrollbar_policy(gtk::PolicyType::Never)
+        .hexpand(true)
+        .vexpand(true)
+        .build();
+    deck_scrolled.set_child(Some(&deck));
+
     // Create the main firehose box with search entry
     let main_box = gtk::Box::builder()
         .orientation(Orientation::Vertical)
         .spacing(8)
-        .hexpand(true)
         .vexpand(true)
         .build();
 
     let main_search = SearchEntry::builder()
         .placeholder_text("Filter messages by keyword...")
+        .hexpand(true)
+        .build();
+
+    let main_header = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
         .margin_start(8)
         .margin_end(8)
         .build();
@@ -277,20 +1213,78 @@ pub fn create_firehose_view() -> (gtk::Box, FirehoseControl) {
 
     let main_scrolled = ScrolledWindow::builder()
         .vexpand(true)
-        .hexpand(true)
+        .hscrollbar_policy(gtk::PolicyType::Never)
         .build();
     main_scrolled.set_child(Some(&main_list));
 
-    main_box.append(&main_search);
+    let main_sentiment_filter = Rc::new(RefCell::new(None));
+    let main_sentiment_dropdown = create_sentiment_dropdown(main_sentiment_filter.clone());
+
+    // Opens the slow mode reader (app.slow-mode)
+    let slow_mode_button = gtk::Button::builder()
+        .icon_name("media-playlist-shuffle-symbolic")
+        .tooltip_text("Slow mode: one random post at a time")
+        .action_name("app.slow-mode")
+        .build();
+
+    main_header.append(&main_search);
+    main_header.append(&main_sentiment_dropdown);
+    main_header.append(&slow_mode_button);
+
+    main_box.append(&main_header);
     main_box.append(&main_scrolled);
 
-    // Initially add main box to root container
-    root_container.append(&main_box);
+    // The main pane is always the first column of the deck
+    let main_column = create_deck_column(&deck, "All posts", &main_box);
+    deck.append(&main_column.column);
 
-    container.append(&root_container);
+    // Connection problems show up in a banner above the deck
+    let (reporter, monitor) = connection::channel();
+    // The synthetic stream has nothing to catch up on
+    if !demo::is_enabled() {
+        if let Some(catch_up_bar) = connection::create_catch_up_bar(&monitor) {
+            container.append(&catch_up_bar);
+        }
+    }
+    container.append(&connection::create_connection_banner(monitor));
+
+    let sampling_label = Label::builder()
+        .visible(false)
+        .tooltip_text("Limited by the firehose sampling and row rate preferences")
+        .margin_top(2)
+        .margin_bottom(2)
+        .build();
+    sampling_label.add_css_class("dim-label");
+    sampling_label.add_css_class("caption");
+    container.append(&sampling_label);
+
+    // Keep the saved cursor fresh in case the app doesn't shut down cleanly
+    glib::timeout_add_seconds_local(30, || {
+        connection::save_cursor();
+        glib::ControlFlow::Continue
+    });
+
+    // Narrow windows show one column at a time, swiped between with dots below
+    let carousel = libadwaita::Carousel::builder()
+        .hexpand(true)
+        .vexpand(true)
+        .build();
+    let carousel_dots = libadwaita::CarouselIndicatorDots::builder()
+        .carousel(&carousel)
+        .build();
+    let carousel_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .build();
+    carousel_box.append(&carousel);
+    carousel_box.append(&carousel_dots);
+
+    let layout = gtk::Stack::new();
+    layout.add_named(&deck_scrolled, Some("deck"));
+    layout.add_named(&carousel_box, Some("carousel"));
+    container.append(&layout);
 
     // Create channels for message passing
-    let (tx, rx) = flume::unbounded::<FirehosePost>();
+    let (tx, rx, notices_rx) = PostSender::bounded();
     let main_filter_keyword = Rc::new(RefCell::new(String::new()));
 
     // Create shared state for scroll pause tracking
@@ -306,53 +1300,170 @@ pub fn create_firehose_view() -> (gtk::Box, FirehoseControl) {
 
     // Create the main pane structure
     let main_pane = SplitPane {
-        container: main_box.clone(),
+        container: main_column.column.clone(),
         list: main_list.clone(),
         search_entry: main_search.clone(),
-        filter_keyword: main_filter_keyword.clone(),
+        filter_keyword: main_filter_keyword,
+        sentiment_filter: main_sentiment_filter,
+        translate: Rc::new(RefCell::new(false)),
+        title: Rc::new(RefCell::new(String::new())),
+        color: Rc::new(RefCell::new(SplitColor::None)),
+        rules: Rc::new(RefCell::new(SplitRules::default())),
+        pending_refill: Rc::new(RefCell::new(None)),
     };
 
+    // Focus mode swaps the whole view for a page offering to resume
+    let focus_page = libadwaita::StatusPage::builder()
+        .icon_name("weather-clear-night-symbolic")
+        .title("Focus Mode")
+        .description("The firehose is disconnected and notifications are held. Global Affairs keeps updating.")
+        .vexpand(true)
+        .build();
+    let resume_button = gtk::Button::builder()
+        .label("Resume Firehose")
+        .halign(gtk::Align::Center)
+        .action_name("app.focus-mode")
+        .build();
+    resume_button.add_css_class("pill");
+    resume_button.add_css_class("suggested-action");
+    focus_page.set_child(Some(&resume_button));
+
+    let focus_stack = gtk::Stack::builder()
+        .transition_type(gtk::StackTransitionType::Crossfade)
+        .vexpand(true)
+        .build();
+    focus_stack.add_named(&container, Some("stream"));
+    focus_stack.add_named(&focus_page, Some("focus"));
+
     // Create the control before setting up the receiver
     let control = FirehoseControl {
-        root_container: root_container.clone(),
+        deck: deck.clone(),
+        carousel,
+        layout,
+        compact: Rc::new(RefCell::new(false)),
         main_pane,
         splits: Rc::new(RefCell::new(Vec::new())),
+        hashtag_feeds: Rc::new(RefCell::new(Vec::new())),
         message_sender: tx.clone(),
         scroll_paused_until: scroll_paused_until.clone(),
+        settings: settings.clone(),
+        focus_stack: focus_stack.clone(),
     };
 
+    // Bring back the splits from the last session
+    let saved_splits = settings.borrow().splits.clone();
+    for saved in saved_splits.iter() {
+        control.add_split_from(saved, false);
+    }
+    set_wanted_records(&saved_splits);
+
+    let followed_hashtags = settings.borrow().hashtag_feeds.clone();
+    for tag in followed_hashtags.iter() {
+        control.add_hashtag_column(tag);
+    }
+
+    // First visit: offer starter splits above the deck
+    if let Some(starter_bar) = control.create_starter_bar() {
+        container.insert_child_after(&starter_bar, Some(&sampling_label));
+    }
+
     // Store references for the UI update
     let main_list_clone = main_list.clone();
-    let main_filter_keyword_clone = main_filter_keyword.clone();
     let control_clone = control.clone();
 
     // Create a buffer for batching messages
     let message_buffer = Rc::new(RefCell::new(Vec::new()));
     let message_buffer_clone = message_buffer.clone();
+    // Likes, reposts and follows for the splits showing them
+    let action_buffer: Rc<RefCell<Vec<StreamAction>>> = Rc::new(RefCell::new(Vec::new()));
+    let action_buffer_clone = action_buffer.clone();
 
     // Set up receiver to collect incoming posts into buffer
+    let settings_for_receiver = settings.clone();
     glib::spawn_future_local(async move {
-        while let Ok(post) = rx.recv_async().await {
+        while let Ok(record) = rx.recv_async().await {
+            // Posts still in the channel when focus mode starts are dropped
+            if crate::focus::is_enabled() {
+                continue;
+            }
+            let post = match record {
+                StreamRecord::Post(post) => *post,
+                StreamRecord::Action(action) => {
+                    action_buffer_clone.borrow_mut().push(action);
+                    continue;
+                }
+            };
+            // Index country mentions even while the view is paused
+            country_mentions::record(&post);
+            alt_audit::record(&post);
+            stats::record_post();
+            // Likely spam is kept out of every pane
+            if spam::is_hidden(&post, &settings_for_receiver.borrow()) {
+                continue;
+            }
+            slow_mode::offer(&post);
+            history::record(&post);
             message_buffer_clone.borrow_mut().push(post);
         }
     });
 
+    // Mark shown posts that were deleted or whose authors changed
+    glib::spawn_future_local(async move {
+        while let Ok(notice) = notices_rx.recv_async().await {
+            if !crate::focus::is_enabled() {
+                stream_notices::apply(&notice);
+            }
+        }
+    });
+
+    // Sampling and rate limiting of rendered rows, with a note when part of the stream is hidden
+    let governor = Rc::new(RefCell::new(Governor::new()));
+    let governor_for_timer = governor.clone();
+    glib::timeout_add_seconds_local(1, move || {
+        match governor.borrow_mut().take_shown_share() {
+            Some(share) if share < 0.995 => {
+                sampling_label.set_label(&format!("Showing {:.0}% of stream", (share * 100.0).max(1.0)));
+                sampling_label.set_visible(true);
+            }
+            // Keep the last note while the stream is quiet or paused
+            None => {}
+            Some(_) => sampling_label.set_visible(false),
+        }
+        glib::ControlFlow::Continue
+    });
+
     // Set up a timer to process batched messages 5 times per second (every 200ms)
     let scroll_paused_for_timer = scroll_paused_until.clone();
     glib::timeout_add_local(std::time::Duration::from_millis(200), move || {
         // Check if we're currently paused due to scrolling
         let is_paused = *scroll_paused_for_timer.borrow() > std::time::Instant::now();
 
+        // Headless while the window is hidden in background mode: posts are still
+        // counted and indexed on arrival, but no rows are built
+        let window_hidden = main_list_clone.root().is_some_and(|root| !root.is_visible());
+        if window_hidden {
+            message_buffer.borrow_mut().clear();
+            action_buffer.borrow_mut().clear();
+            return glib::ControlFlow::Continue;
+        }
+
         if !is_paused {
             let mut buffer = message_buffer.borrow_mut();
 
             if !buffer.is_empty() {
+                let settings = control_clone.settings.borrow();
+                let mut governor = governor_for_timer.borrow_mut();
+                governor.refill(&settings);
+
                 // Process all buffered posts
                 for post in buffer.iter() {
+                    if !governor.admit(&settings) {
+                        continue;
+                    }
+
                     // Add to main list if it matches the main filter
-                    let main_keyword = main_filter_keyword_clone.borrow().clone();
-                    if main_keyword.is_empty() || post.text.to_lowercase().contains(&main_keyword.to_lowercase()) {
-                        add_message_to_list(&main_list_clone, post);
+                    if control_clone.main_pane.matches(post) {
+                        add_message_to_list(&main_list_clone, post, None);
                     }
 
                     // Broadcast to all splits
@@ -362,40 +1473,185 @@ pub fn create_firehose_view() -> (gtk::Box, FirehoseControl) {
                 // Clear the buffer
                 buffer.clear();
             }
+
+            // Actions no split shows don't count against the row rate
+            let mut actions = action_buffer.borrow_mut();
+            if !actions.is_empty() {
+                let settings = control_clone.settings.borrow();
+                let mut governor = governor_for_timer.borrow_mut();
+                governor.refill(&settings);
+
+                for action in actions.iter() {
+                    if control_clone.wants_action(action) && governor.admit(&settings) {
+                        control_clone.broadcast_action(action);
+                    }
+                }
+                actions.clear();
+            }
         }
         // If paused, messages remain in buffer and will be processed after pause ends
 
         glib::ControlFlow::Continue
     });
 
-    // Start the Jetstream connection in a background task
+    // Start the Jetstream connection in a background task, unless a bad
+    // configuration is being recovered from in safe mode
     let tx_clone = tx.clone();
-    std::thread::spawn(move || {
-        let rt = tokio::runtime::Runtime::new().unwrap();
-        rt.block_on(async {
-            if let Err(e) = start_jetstream(tx_clone).await {
-                eprintln!("Jetstream error: {}", e);
-            }
+    if safe_mode::is_enabled() {
+        tracing::info!("Safe mode: not connecting to Jetstream");
+    } else {
+        startup::schedule(Priority::Normal, "Jetstream", async move {
+            std::thread::spawn(move || {
+                let rt = tokio::runtime::Runtime::new().unwrap();
+                if demo::is_enabled() {
+                    reporter.run(|reporter| stream_demo_posts(&tx_clone, reporter));
+                } else {
+                    reporter.run(|reporter| rt.block_on(stream_posts(&tx_clone, reporter)));
+                }
+            });
         });
-    });
+    }
 
     // Handle main search filter
-    let main_list_for_search = main_list.clone();
-    let main_filter_keyword_for_search = main_filter_keyword.clone();
+    let control_for_search = control.clone();
     main_search.connect_search_changed(move |entry| {
         let keyword = entry.text().to_string();
-        *main_filter_keyword_for_search.borrow_mut() = keyword;
+        *control_for_search.main_pane.filter_keyword.borrow_mut() = keyword;
+        control_for_search.main_pane.schedule_refill(&control_for_search.settings, false);
+    });
+    let control_for_mood = control.clone();
+    main_sentiment_dropdown.connect_selected_notify(move |_| {
+        control_for_mood.main_pane.schedule_refill(&control_for_mood.settings, false);
+    });
+
+    // Profiles opened from posts are pushed over the stream
+    let view = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .build();
+    view.append(&crate::profile_page::create_navigation(&focus_stack));
+    (view, control)
+}
+
+fn add_message_to_list(list: &ListBox, post: &FirehosePost, translation: Option<&TranslationConfig>) {
+    let row = create_post_card(post, translation);
+
+    // Prepend to show newest messages at the top
+    list.prepend(&row);
+    trim_list(list);
+}
 
-        // Clear the main list when search changes
-        while let Some(child) = main_list_for_search.first_child() {
-            main_list_for_search.remove(&child);
+fn add_action_to_list(list: &ListBox, action: &StreamAction) {
+    list.prepend(&create_action_card(action));
+    trim_list(list);
+}
+
+fn trim_list(list: &ListBox) {
+    // Limit to 100 messages to prevent memory issues
+    let mut count = 0;
+    let mut child = list.first_child();
+    while let Some(current) = child {
+        count += 1;
+        if count > 100 {
+            let next = current.next_sibling();
+            list.remove(&current);
+            child = next;
+        } else {
+            child = current.next_sibling();
         }
-    });
+    }
+}
+
+/// One line for a like, repost or follow: who, what, and a link to the post
+/// or account it was about
+fn create_action_card(action: &StreamAction) -> gtk::Box {
+    let row = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .margin_top(2)
+        .margin_bottom(2)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+    row.add_css_class("firehose-action");
+
+    let timestamp_label = Label::builder()
+        .label(&action.timestamp)
+        .build();
+    timestamp_label.add_css_class("caption");
+    timestamp_label.add_css_class("monospace");
+    timestamp_label.add_css_class("firehose-timestamp");
+    row.append(&timestamp_label);
+
+    let (icon, verb) = match action.kind {
+        RecordType::Likes => ("emblem-favorite-symbolic", "liked"),
+        RecordType::Reposts => ("media-playlist-repeat-symbolic", "reposted"),
+        RecordType::Follows => ("contact-new-symbolic", "followed"),
+        RecordType::Posts => ("document-edit-symbolic", "posted"),
+    };
+    let icon = gtk::Image::from_icon_name(icon);
+    icon.add_css_class("dim-label");
+    row.append(&icon);
+
+    row.append(&create_account_button(&action.did));
+    row.append(&profiles::create_author_badge(&action.did));
 
-    (container, control)
+    let verb_label = Label::new(Some(verb));
+    verb_label.add_css_class("caption");
+    row.append(&verb_label);
+
+    // Follows are about an account; likes and reposts about a post
+    if action.kind == RecordType::Follows {
+        row.append(&create_account_button(&action.subject));
+        row.append(&profiles::create_author_badge(&action.subject));
+    } else {
+        let post_button = gtk::Button::builder()
+            .label("a post")
+            .tooltip_text(&action.subject)
+            .build();
+        post_button.add_css_class("flat");
+        post_button.add_css_class("caption");
+        let url = appview::post_web_url(&action.subject);
+        post_button.set_sensitive(url.is_some());
+        post_button.connect_clicked(move |_| {
+            if let Some(url) = url.as_deref() {
+                crate::links::open(url);
+            }
+        });
+        row.append(&post_button);
+    }
+
+    row
+}
+
+/// Compact DID button opening the account's profile over the firehose
+fn create_account_button(did: &str) -> gtk::Button {
+    let did_id = did
+        .strip_prefix("did:plc:")
+        .or_else(|| did.strip_prefix("did:web:"))
+        .unwrap_or(did);
+    let label = Label::new(Some(&truncate_middle(did_id, 9)));
+    label.add_css_class("caption");
+    label.add_css_class("monospace");
+    label.add_css_class("firehose-rkey");
+
+    let button = gtk::Button::builder()
+        .child(&label)
+        .tooltip_text(did)
+        .build();
+    button.add_css_class("flat");
+    button.add_css_class("identifier-button");
+
+    let did = did.to_string();
+    button.connect_clicked(move |button| {
+        let _ = button.activate_action("app.switch-view", Some(&"firehose".to_variant()));
+        crate::profile_page::show(&did);
+    });
+    button
 }
 
-fn add_message_to_list(list: &ListBox, post: &FirehosePost) {
+/// Card for a post: embeds, header with timestamp, identifier and bookmark star,
+/// reply context, text and facet badges
+pub fn create_post_card(post: &FirehosePost, translation: Option<&TranslationConfig>) -> gtk::Box {
     // Create main container with card styling (similar to news articles)
     let row = gtk::Box::builder()
         .orientation(Orientation::Vertical)
@@ -405,121 +1661,18 @@ fn add_message_to_list(list: &ListBox, post: &FirehosePost) {
         .margin_start(6)
         .margin_end(6)
         .build();
-    row.add_css_class("firehose-message");
-
-    // Handle embeds first (images, external links)
-    if let Some(ref embed) = post.embed {
-        match embed {
-            PostEmbed::Images { count, alt_texts } => {
-                // Create a simple indicator box showing image count and alt text
-                let image_indicator = gtk::Box::builder()
-                    .orientation(Orientation::Vertical)
-                    .spacing(4)
-                    .margin_top(6)
-                    .margin_bottom(6)
-                    .margin_start(8)
-                    .margin_end(8)
-                    .build();
-                image_indicator.add_css_class("popover-currency-section");
-
-                // Image count badge
-                let count_badge = Label::builder()
-                    .label(&format!("🖼️ {} image{}", count, if *count > 1 { "s" } else { "" }))
-                    .xalign(0.0)
-                    .build();
-                count_badge.add_css_class("badge");
-                count_badge.add_css_class("badge-country");
-                image_indicator.append(&count_badge);
-
-                // Show alt text if available
-                for (i, alt) in alt_texts.iter().enumerate() {
-                    if !alt.is_empty() {
-                        let alt_label = Label::builder()
-                            .label(&format!("[{}] {}", i + 1, alt))
-                            .xalign(0.0)
-                            .wrap(true)
-                            .wrap_mode(gtk::pango::WrapMode::WordChar)
-                            .build();
-                        alt_label.add_css_class("caption");
-                        image_indicator.append(&alt_label);
-                    }
-                }
-
-                row.append(&image_indicator);
-            }
-            PostEmbed::External { uri, title, description } => {
-                // Create a compact external link preview
-                let external_box = gtk::Box::builder()
-                    .orientation(Orientation::Vertical)
-                    .spacing(4)
-                    .margin_top(6)
-                    .margin_bottom(6)
-                    .margin_start(8)
-                    .margin_end(8)
-                    .build();
-                external_box.add_css_class("popover-currency-section");
-
-                // Link icon/badge
-                let link_badge = Label::builder()
-                    .label("🔗 External Link")
-                    .xalign(0.0)
-                    .build();
-                link_badge.add_css_class("badge");
-                link_badge.add_css_class("badge-lang");
-                external_box.append(&link_badge);
-
-                // Link title
-                if !title.is_empty() {
-                    let link_title = Label::builder()
-                        .label(title)
-                        .xalign(0.0)
-                        .ellipsize(gtk::pango::EllipsizeMode::End)
-                        .lines(1)
-                        .build();
-                    link_title.add_css_class("caption");
-                    external_box.append(&link_title);
-                }
+    row.add_css_class("firehose-message");
 
-                // Link description
-                if !description.is_empty() {
-                    let link_desc = Label::builder()
-                        .label(description)
-                        .xalign(0.0)
-                        .ellipsize(gtk::pango::EllipsizeMode::End)
-                        .lines(2)
-                        .build();
-                    link_desc.add_css_class("caption");
-                    link_desc.add_css_class("dim-label");
-                    external_box.append(&link_desc);
-                }
+    // Colored edge reflecting the post's sentiment
+    match Sentiment::from_score(post.sentiment) {
+        Sentiment::Positive => row.add_css_class("sentiment-positive"),
+        Sentiment::Negative => row.add_css_class("sentiment-negative"),
+        Sentiment::Neutral => {}
+    }
 
-                // Make clickable
-                let gesture = gtk::GestureClick::new();
-                let uri_clone = uri.clone();
-                gesture.connect_released(move |_, _, _, _| {
-                    if let Err(e) = open::that(&uri_clone) {
-                        eprintln!("Failed to open URL: {}", e);
-                    }
-                });
-                external_box.add_controller(gesture);
-                external_box.add_css_class("activatable");
-
-                row.append(&external_box);
-            }
-            PostEmbed::Video => {
-                // Show a video indicator badge
-                let video_badge = Label::builder()
-                    .label("📹 Video")
-                    .margin_start(8)
-                    .margin_end(8)
-                    .margin_top(6)
-                    .margin_bottom(6)
-                    .build();
-                video_badge.add_css_class("badge");
-                video_badge.add_css_class("badge-lang");
-                row.append(&video_badge);
-            }
-        }
+    // Handle embeds first (images, external links, quoted posts)
+    if let Some(ref embed) = post.embed {
+        append_embed(&row, &post.did, embed);
     }
 
     // Content container with padding
@@ -547,27 +1700,62 @@ fn add_message_to_list(list: &ListBox, post: &FirehosePost) {
     timestamp_label.add_css_class("monospace");
     timestamp_label.add_css_class("firehose-timestamp");
 
-    // DID/rkey label with accent color (show last 8 chars of DID + rkey)
-    let did_short = if post.did.len() > 12 {
-        format!("{}...{}", &post.did[..8], &post.rkey[..8.min(post.rkey.len())])
-    } else {
-        post.rkey.clone()
-    };
+    // DID/rkey identifier, shortened for display and copyable as an at:// URI
+    let identifier_button = create_identifier_button(&post.did, &post.rkey);
 
-    let rkey_label = Label::builder()
-        .label(&did_short)
-        .xalign(0.0)
-        .ellipsize(gtk::pango::EllipsizeMode::End)
-        .max_width_chars(20)
-        .build();
-    rkey_label.add_css_class("caption");
-    rkey_label.add_css_class("monospace");
-    rkey_label.add_css_class("firehose-rkey");
+    // Author's avatar and handle, filled in once the DID resolves
+    let author_badge = profiles::create_author_badge(&post.did);
 
     header.append(&timestamp_label);
-    header.append(&rkey_label);
+    header.append(&author_badge);
+    header.append(&identifier_button);
+
+    // GDELT coverage of what the post is about, in the Global Affairs view
+    if let Some(query) = related_news_query(post) {
+        let news_button = gtk::Button::builder()
+            .icon_name("system-search-symbolic")
+            .tooltip_text(format!("Search related news: {}", query))
+            .build();
+        news_button.add_css_class("flat");
+        news_button.add_css_class("related-news-button");
+        news_button.connect_clicked(move |button| {
+            if let Err(e) = button.activate_action("app.search", Some(&query.to_variant())) {
+                tracing::warn!("Failed to search for {}: {}", query, e);
+            }
+        });
+        header.append(&news_button);
+    }
+
+    // Bookmark star, pushed to the end of the header
+    let post_uri = format!("at://{}/app.bsky.feed.post/{}", post.did, post.rkey);
+    if let Some(web_url) = appview::post_web_url(&post_uri) {
+        let star_button = bookmarks::create_star_button(Bookmark::new(BookmarkKind::Post, &web_url, &post.text, &post.did));
+        star_button.set_hexpand(true);
+        star_button.set_halign(gtk::Align::End);
+        header.append(&star_button);
+    }
+
+    // Like and repost for the signed-in account
+    if !post.cid.is_empty() && account::is_configured() {
+        header.append(&create_interaction_button(Interaction::Like, &post_uri, &post.cid));
+        header.append(&create_interaction_button(Interaction::Repost, &post_uri, &post.cid));
+    }
     content_box.append(&header);
 
+    // Deletes and account changes that arrive later are noted here
+    let notes = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(4)
+        .visible(false)
+        .build();
+    content_box.append(&notes);
+    stream_notices::track(&post.did, &post.rkey, &row, &notes);
+
+    // Expandable parent post for replies, fetched on first expand
+    if let Some(ref reply) = post.reply {
+        content_box.append(&create_reply_expander(reply));
+    }
+
     // Show post text
     let message_label = Label::builder()
         .label(&post.text)
@@ -579,6 +1767,14 @@ fn add_message_to_list(list: &ListBox, post: &FirehosePost) {
     message_label.add_css_class("firehose-text");
     content_box.append(&message_label);
 
+    // Swap in a translation when the pane asks for one; the original stays under an expander.
+    // Otherwise foreign-language posts get a button to translate on demand
+    if let Some(config) = translation.filter(|config| config.needs_translation(&post.langs)) {
+        content_box.append(&create_translation_expander(&message_label, post, config.clone()));
+    } else if translate::is_foreign(&post.langs) {
+        header.append(&translate::create_translate_button(&message_label, post.langs.first().cloned()));
+    }
+
     // Show facets as badges if present
     if let Some(ref facets) = post.facets {
         if !facets.is_empty() {
@@ -634,113 +1830,829 @@ fn add_message_to_list(list: &ListBox, post: &FirehosePost) {
     }
 
     row.append(&content_box);
+    row
+}
 
-    // Prepend to show newest messages at the top
-    list.prepend(&row);
+/// Most terms a related news search is built from
+const MAX_RELATED_TERMS: usize = 3;
+
+/// GDELT query for news related to a post: its hashtags, then names in its
+/// text, then its longest words, any of which may match. None when the post
+/// has nothing worth searching for.
+fn related_news_query(post: &FirehosePost) -> Option<String> {
+    let mut terms: Vec<String> = Vec::new();
+    let add = |terms: &mut Vec<String>, term: String| {
+        // GDELT rejects very short keywords
+        if term.chars().count() >= 4
+            && terms.len() < MAX_RELATED_TERMS
+            && !terms.iter().any(|existing| existing.eq_ignore_ascii_case(&term))
+        {
+            terms.push(term);
+        }
+    };
 
-    // Limit to 100 messages to prevent memory issues
-    let mut count = 0;
-    let mut child = list.first_child();
-    while let Some(current) = child {
-        count += 1;
-        if count > 100 {
-            let next = current.next_sibling();
-            list.remove(&current);
-            child = next;
-        } else {
-            child = current.next_sibling();
+    for facet in post.facets.iter().flatten() {
+        if let FacetType::Tag(tag) = &facet.facet_type {
+            add(&mut terms, tag.trim_start_matches('#').to_string());
+        }
+    }
+    for entity in crate::themes::entities_for(&post.text) {
+        add(&mut terms, format!("\"{}\"", entity));
+    }
+    if terms.is_empty() {
+        let mut words: Vec<&str> = post
+            .text
+            .split_whitespace()
+            .filter(|word| !word.contains("://") && !word.starts_with('@'))
+            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+            .filter(|word| word.chars().count() > 5 && word.chars().all(char::is_alphanumeric))
+            .collect();
+        words.sort_by_key(|word| std::cmp::Reverse(word.chars().count()));
+        for word in words {
+            add(&mut terms, word.to_lowercase());
+        }
+    }
+
+    match terms.len() {
+        0 => None,
+        1 => terms.pop(),
+        _ => Some(format!("({})", terms.join(" OR "))),
+    }
+}
+
+/// Toggle that likes or reposts a post, and undoes it when toggled off. The
+/// button flips right away and flips back with a toast if the request fails.
+pub fn create_interaction_button(interaction: Interaction, uri: &str, cid: &str) -> gtk::ToggleButton {
+    let (icon, tooltip) = match interaction {
+        Interaction::Like => ("emblem-favorite-symbolic", "Like"),
+        Interaction::Repost => ("media-playlist-repeat-symbolic", "Repost"),
+    };
+    let button = gtk::ToggleButton::builder()
+        .icon_name(icon)
+        .tooltip_text(tooltip)
+        .valign(gtk::Align::Center)
+        .css_classes(vec!["flat".to_string(), "interaction-button".to_string()])
+        .build();
+
+    // URI of our like or repost record, once created
+    let record_uri: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+    // Set while the button is flipped back after a failure, so that doesn't send a request
+    let reverting = Rc::new(RefCell::new(false));
+    let uri = uri.to_string();
+    let cid = cid.to_string();
+    button.connect_toggled(move |button| {
+        if *reverting.borrow() {
+            return;
+        }
+
+        let active = button.is_active();
+        let button = button.clone();
+        let record_uri = record_uri.clone();
+        let reverting = reverting.clone();
+        let uri = uri.clone();
+        let cid = cid.clone();
+        // Insensitive until the request resolves, so undoing a like can't race
+        // its creation and leave the record behind
+        button.set_sensitive(false);
+        glib::spawn_future_local(async move {
+            let result = if active {
+                account::interact(interaction, &uri, &cid)
+                    .await
+                    .map(|created| *record_uri.borrow_mut() = Some(created))
+            } else {
+                let created = record_uri.borrow().clone();
+                match created {
+                    Some(created) => account::delete_record(&created)
+                        .await
+                        .map(|()| *record_uri.borrow_mut() = None),
+                    None => Ok(()),
+                }
+            };
+
+            if let Err(e) = result {
+                let action = match (interaction, active) {
+                    (Interaction::Like, true) => "like",
+                    (Interaction::Like, false) => "unlike",
+                    (Interaction::Repost, true) => "repost",
+                    (Interaction::Repost, false) => "undo repost",
+                };
+                toasts::show(&format!("Couldn't {}: {}", action, e));
+                *reverting.borrow_mut() = true;
+                button.set_active(!active);
+                *reverting.borrow_mut() = false;
+            }
+            button.set_sensitive(true);
+        });
+    });
+
+    button
+}
+
+/// Shorten `text` to at most `max_graphemes` characters as seen on screen by
+/// replacing its middle with an ellipsis; never splits an emoji or accent
+pub fn truncate_middle(text: &str, max_graphemes: usize) -> String {
+    // Byte offset where each grapheme starts
+    let starts: Vec<usize> = text.grapheme_indices(true).map(|(start, _)| start).collect();
+    let count = starts.len();
+    if count <= max_graphemes || max_graphemes < 3 {
+        return text.to_string();
+    }
+
+    let keep = max_graphemes - 1;
+    let head = keep.div_ceil(2);
+    let tail = keep - head;
+
+    format!("{}…{}", &text[..starts[head]], &text[starts[count - tail]..])
+}
+
+/// Compact "did/rkey" label for a post; the full at:// URI is in the tooltip.
+/// Clicking opens the author's profile and right-clicking copies the URI
+pub fn create_identifier_button(did: &str, rkey: &str) -> gtk::Button {
+    // The method prefix is the same for nearly every account, so only the unique part is shown
+    let did_id = did
+        .strip_prefix("did:plc:")
+        .or_else(|| did.strip_prefix("did:web:"))
+        .unwrap_or(did);
+    let short = format!("{}/{}", truncate_middle(did_id, 9), truncate_middle(rkey, 7));
+    let uri = format!("at://{}/app.bsky.feed.post/{}", did, rkey);
+
+    let label = Label::builder()
+        .label(&short)
+        .xalign(0.0)
+        .build();
+    label.add_css_class("caption");
+    label.add_css_class("monospace");
+    label.add_css_class("firehose-rkey");
+
+    let button = gtk::Button::builder()
+        .child(&label)
+        .tooltip_text(format!("{}\nClick to show the profile, right-click to copy", uri))
+        .build();
+    button.add_css_class("flat");
+    button.add_css_class("identifier-button");
+
+    // Profiles open over the firehose, so cards elsewhere switch to it first
+    let did = did.to_string();
+    button.connect_clicked(move |button| {
+        let _ = button.activate_action("app.switch-view", Some(&"firehose".to_variant()));
+        crate::profile_page::show(&did);
+    });
+
+    let copy_gesture = gtk::GestureClick::builder()
+        .button(gtk::gdk::BUTTON_SECONDARY)
+        .build();
+    copy_gesture.connect_pressed(move |_, _, _, _| {
+        label.clipboard().set_text(&uri);
+
+        // Brief confirmation in place of the identifier
+        label.set_label("Copied");
+        let label = label.clone();
+        let short = short.clone();
+        glib::timeout_add_local_once(std::time::Duration::from_millis(1200), move || {
+            label.set_label(&short);
+        });
+    });
+    button.add_controller(copy_gesture);
+
+    button
+}
+
+/// Create an "Original" expander and translate the post's text into message_label
+fn create_translation_expander(message_label: &Label, post: &FirehosePost, config: TranslationConfig) -> gtk::Expander {
+    let source = post.langs.first().cloned();
+
+    let expander = gtk::Expander::builder()
+        .label(format!("Translating from {}…", source.as_deref().unwrap_or("unknown")))
+        .sensitive(false)
+        .build();
+    expander.add_css_class("caption");
+
+    let original_label = Label::builder()
+        .label(&post.text)
+        .wrap(true)
+        .wrap_mode(gtk::pango::WrapMode::WordChar)
+        .xalign(0.0)
+        .selectable(true)
+        .margin_top(4)
+        .margin_start(8)
+        .build();
+    original_label.add_css_class("dim-label");
+    expander.set_child(Some(&original_label));
+
+    let text = post.text.clone();
+    let message_label = message_label.clone();
+    let expander_clone = expander.clone();
+    glib::spawn_future_local(async move {
+        match translate::translate(&text, source.as_deref(), &config).await {
+            Ok(translated) => {
+                message_label.set_label(&translated);
+                expander_clone.set_label(Some(&format!(
+                    "Original ({} → {})",
+                    source.as_deref().unwrap_or("?"),
+                    config.target
+                )));
+                expander_clone.set_sensitive(true);
+            }
+            Err(e) => {
+                tracing::warn!("Translation failed: {}", e);
+                expander_clone.set_label(Some("Translation unavailable"));
+                expander_clone.set_tooltip_text(Some(&e.to_string()));
+            }
+        }
+    });
+
+    expander
+}
+
+/// Append the widgets for a post's embed to its row
+fn append_embed(row: &gtk::Box, did: &str, embed: &PostEmbed) {
+    match embed {
+        PostEmbed::Images { count, alt_texts, cids } => {
+            // Create a simple indicator box showing image count and alt text
+            let image_indicator = gtk::Box::builder()
+                .orientation(Orientation::Vertical)
+                .spacing(4)
+                .margin_top(6)
+                .margin_bottom(6)
+                .margin_start(8)
+                .margin_end(8)
+                .build();
+            image_indicator.add_css_class("popover-currency-section");
+
+            // Image count badge
+            let count_badge = Label::builder()
+                .label(&format!("🖼️ {} image{}", count, if *count > 1 { "s" } else { "" }))
+                .xalign(0.0)
+                .build();
+            count_badge.add_css_class("badge");
+            count_badge.add_css_class("badge-country");
+            image_indicator.append(&count_badge);
+
+            // Show alt text if available
+            for (i, alt) in alt_texts.iter().enumerate() {
+                if !alt.is_empty() {
+                    let alt_label = Label::builder()
+                        .label(&format!("[{}] {}", i + 1, alt))
+                        .xalign(0.0)
+                        .wrap(true)
+                        .wrap_mode(gtk::pango::WrapMode::WordChar)
+                        .build();
+                    alt_label.add_css_class("caption");
+                    image_indicator.append(&alt_label);
+                }
+            }
+
+            // Double-click opens the images in the viewer
+            if !cids.is_empty() {
+                image_indicator.set_tooltip_text(Some("Double-click to view"));
+                let images: Vec<LightboxImage> = cids.iter()
+                    .enumerate()
+                    .map(|(i, cid)| LightboxImage {
+                        url: bsky_image_url(did, cid),
+                        caption: alt_texts.get(i).cloned().unwrap_or_default(),
+                    })
+                    .collect();
+                let gesture = gtk::GestureClick::new();
+                gesture.connect_pressed(move |_, n_press, _, _| {
+                    if n_press == 2 {
+                        lightbox::show(images.clone(), 0);
+                    }
+                });
+                image_indicator.add_controller(gesture);
+            }
+
+            row.append(&image_indicator);
+        }
+        PostEmbed::External { uri, title, description } => {
+            // Create a compact external link preview
+            let external_box = gtk::Box::builder()
+                .orientation(Orientation::Vertical)
+                .spacing(4)
+                .margin_top(6)
+                .margin_bottom(6)
+                .margin_start(8)
+                .margin_end(8)
+                .build();
+            external_box.add_css_class("popover-currency-section");
+
+            // Link icon/badge
+            let link_badge = Label::builder()
+                .label("🔗 External Link")
+                .xalign(0.0)
+                .build();
+            link_badge.add_css_class("badge");
+            link_badge.add_css_class("badge-lang");
+            external_box.append(&link_badge);
+
+            // Link title
+            if !title.is_empty() {
+                let link_title = Label::builder()
+                    .label(title)
+                    .xalign(0.0)
+                    .ellipsize(gtk::pango::EllipsizeMode::End)
+                    .lines(1)
+                    .build();
+                link_title.add_css_class("caption");
+                external_box.append(&link_title);
+            }
+
+            // Link description
+            if !description.is_empty() {
+                let link_desc = Label::builder()
+                    .label(description)
+                    .xalign(0.0)
+                    .ellipsize(gtk::pango::EllipsizeMode::End)
+                    .lines(2)
+                    .build();
+                link_desc.add_css_class("caption");
+                link_desc.add_css_class("dim-label");
+                external_box.append(&link_desc);
+            }
+
+            // Make clickable
+            let gesture = gtk::GestureClick::new();
+            let uri_clone = uri.clone();
+            gesture.connect_released(move |_, _, _, _| {
+                crate::links::open(&uri_clone);
+            });
+            external_box.add_controller(gesture);
+            external_box.add_css_class("activatable");
+
+            row.append(&external_box);
+        }
+        PostEmbed::Video { cid, alt } => {
+            row.append(&video::create_video_embed(did, cid, alt));
+        }
+        PostEmbed::Record { uri, media } => {
+            if let Some(media) = media {
+                append_embed(row, did, media);
+            }
+            row.append(&create_quote_card(uri));
         }
     }
 }
 
-async fn start_jetstream(tx: flume::Sender<FirehosePost>) -> anyhow::Result<()> {
-    let nsid: Nsid = "app.bsky.feed.post".parse()
-        .map_err(|e| anyhow::anyhow!("Failed to parse NSID: {}", e))?;
+/// Nested card for a quoted post, filled in from the AppView once the row is shown
+fn create_quote_card(uri: &str) -> gtk::Box {
+    let card = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+    card.add_css_class("firehose-quote");
+
+    // Quotes of feeds, lists and starter packs have no post text to show
+    let Some(web_url) = appview::post_web_url(uri) else {
+        let label = Label::builder()
+            .label("❝ Quoted a feed or list")
+            .xalign(0.0)
+            .build();
+        label.add_css_class("caption");
+        label.add_css_class("dim-label");
+        card.append(&label);
+        return card;
+    };
+
+    let loading_label = Label::builder()
+        .label("❝ Loading quoted post…")
+        .xalign(0.0)
+        .build();
+    loading_label.add_css_class("caption");
+    loading_label.add_css_class("dim-label");
+    card.append(&loading_label);
+
+    // Clicking the card opens the quoted post on bsky.app
+    let gesture = gtk::GestureClick::new();
+    gesture.connect_released(move |_, _, _, _| {
+        if let Err(e) = open::that(&web_url) {
+            tracing::warn!("Failed to open URL: {}", e);
+        }
+    });
+    card.add_controller(gesture);
+    card.add_css_class("activatable");
+
+    let loaded = Rc::new(RefCell::new(false));
+    let uri = uri.to_string();
+    card.connect_map(move |card| {
+        if *loaded.borrow() {
+            return;
+        }
+        *loaded.borrow_mut() = true;
+
+        let card = card.clone();
+        let loading_label = loading_label.clone();
+        let uri = uri.clone();
+        glib::spawn_future_local(async move {
+            match appview::fetch_post_batched(&uri).await {
+                Ok(quoted) => {
+                    card.remove(&loading_label);
+
+                    let author = match quoted.author.display_name {
+                        Some(ref name) if !name.is_empty() => format!("{} (@{})", name, quoted.author.handle),
+                        _ => format!("@{}", quoted.author.handle),
+                    };
+                    let author_label = Label::builder()
+                        .label(author)
+                        .xalign(0.0)
+                        .ellipsize(gtk::pango::EllipsizeMode::End)
+                        .build();
+                    author_label.add_css_class("caption");
+                    author_label.add_css_class("firehose-rkey");
+                    card.append(&author_label);
+
+                    let text_label = Label::builder()
+                        .label(quoted.text())
+                        .xalign(0.0)
+                        .wrap(true)
+                        .wrap_mode(gtk::pango::WrapMode::WordChar)
+                        .lines(6)
+                        .ellipsize(gtk::pango::EllipsizeMode::End)
+                        .build();
+                    text_label.add_css_class("firehose-text");
+                    card.append(&text_label);
+                }
+                Err(FetchError::Empty { .. }) => {
+                    loading_label.set_label("❝ Quoted post unavailable (deleted or blocked)");
+                }
+                Err(e) => {
+                    loading_label.set_label(&format!("❝ {}", e));
+                    loading_label.set_wrap(true);
+                }
+            }
+        });
+    });
+
+    card
+}
+
+/// Create an "In reply to" expander that lazily loads the parent post from the AppView
+fn create_reply_expander(reply: &ReplyContext) -> gtk::Expander {
+    let expander = gtk::Expander::builder()
+        .label("↩ In reply to…")
+        .build();
+    expander.add_css_class("caption");
+
+    let parent_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .margin_top(4)
+        .margin_start(8)
+        .build();
+    parent_box.add_css_class("firehose-reply-context");
+    expander.set_child(Some(&parent_box));
+
+    let loading_label = Label::builder()
+        .label("Loading…")
+        .xalign(0.0)
+        .build();
+    loading_label.add_css_class("dim-label");
+    parent_box.append(&loading_label);
+
+    let loaded = Rc::new(RefCell::new(false));
+    let parent_uri = reply.parent_uri.clone();
+    let root_uri = reply.root_uri.clone();
+    expander.connect_expanded_notify(move |expander| {
+        if !expander.is_expanded() || *loaded.borrow() {
+            return;
+        }
+        *loaded.borrow_mut() = true;
+
+        let parent_box = parent_box.clone();
+        let loading_label = loading_label.clone();
+        let parent_uri = parent_uri.clone();
+        let root_uri = root_uri.clone();
+        glib::spawn_future_local(async move {
+            parent_box.remove(&loading_label);
+
+            match appview::fetch_post(&parent_uri).await {
+                Ok(parent) => {
+                    let author = match parent.author.display_name {
+                        Some(ref name) if !name.is_empty() => format!("{} (@{})", name, parent.author.handle),
+                        _ => format!("@{}", parent.author.handle),
+                    };
+                    let author_label = Label::builder()
+                        .label(author)
+                        .xalign(0.0)
+                        .ellipsize(gtk::pango::EllipsizeMode::End)
+                        .build();
+                    author_label.add_css_class("caption");
+                    author_label.add_css_class("firehose-rkey");
+                    parent_box.append(&author_label);
+
+                    let text_label = Label::builder()
+                        .label(parent.text())
+                        .xalign(0.0)
+                        .wrap(true)
+                        .wrap_mode(gtk::pango::WrapMode::WordChar)
+                        .selectable(true)
+                        .build();
+                    text_label.add_css_class("firehose-text");
+                    parent_box.append(&text_label);
+                }
+                Err(FetchError::Empty { .. }) => {
+                    let error_label = Label::builder()
+                        .label("Parent post unavailable (deleted or blocked)")
+                        .xalign(0.0)
+                        .build();
+                    error_label.add_css_class("dim-label");
+                    parent_box.append(&error_label);
+                }
+                Err(e) => {
+                    let error_label = Label::builder()
+                        .label(e.to_string())
+                        .xalign(0.0)
+                        .wrap(true)
+                        .build();
+                    error_label.add_css_class("dim-label");
+                    parent_box.append(&error_label);
+                }
+            }
+
+            // Link to the full thread on bsky.app
+            if let Some(thread_url) = appview::post_web_url(&root_uri) {
+                let thread_link = gtk::LinkButton::builder()
+                    .uri(thread_url.as_str())
+                    .label("Open thread")
+                    .halign(gtk::Align::Start)
+                    .build();
+                parent_box.append(&thread_link);
+            }
+        });
+    });
+
+    expander
+}
+
+/// Stream posts over one Jetstream connection until it drops; reconnecting is
+/// left to the caller so the UI can follow the connection state
+async fn stream_posts(tx: &PostSender, reporter: &ConnectionReporter) -> SessionEnd {
+    if crate::focus::is_enabled() {
+        return SessionEnd::Paused;
+    }
+    connection::apply_catch_up();
+
+    // Posts always, plus likes, reposts or follows while a split shows them
+    let wanted_records = WANTED_RECORDS.load(Ordering::Relaxed);
+    let mut wanted_collections = Vec::new();
+    for record_type in RecordType::ALL {
+        if record_type != RecordType::Posts && wanted_records & record_bit(record_type) == 0 {
+            continue;
+        }
+        match record_type.collection().parse::<Nsid>() {
+            Ok(nsid) => wanted_collections.push(nsid),
+            Err(e) => return SessionEnd::Error(format!("Failed to parse NSID: {}", e)),
+        }
+    }
 
     let config = JetstreamConfig {
         endpoint: DefaultJetstreamEndpoints::USEastOne.into(),
-        wanted_collections: vec![nsid],
+        wanted_collections,
         wanted_dids: vec![],
         compression: JetstreamCompression::Zstd,
-        cursor: None,
-        max_retries: 10,
+        // Resume where the last connection stopped so no posts are missed
+        cursor: connection::cursor(),
+        // Retries happen in ConnectionReporter::run; the connector gives up after one try
+        max_retries: 1,
         max_delay_ms: 30_000,
         base_delay_ms: 1_000,
         reset_retries_min_ms: 30_000,
     };
 
-    let jetstream = JetstreamConnector::new(config)?;
-    let receiver = jetstream.connect().await?;
+    let jetstream = match JetstreamConnector::new(config) {
+        Ok(jetstream) => jetstream,
+        Err(e) => return SessionEnd::Error(format!("Invalid Jetstream config: {}", e)),
+    };
+    let receiver = match jetstream.connect().await {
+        Ok(receiver) => receiver,
+        Err(e) => return SessionEnd::Error(format!("Failed to connect: {}", e)),
+    };
 
-    eprintln!("Connected to Bluesky Jetstream!");
+    let mut received = 0u64;
 
     while let Ok(event) = receiver.recv_async().await {
-        if let JetstreamEvent::Commit(commit_event) = &event {
-            match commit_event {
+        // Resubscribing keeps the cursor, so nothing is missed in between
+        if connection::catch_up_pending() || WANTED_RECORDS.load(Ordering::Relaxed) != wanted_records {
+            return SessionEnd::Restart;
+        }
+        // Dropping the receiver closes the connection
+        if crate::focus::is_enabled() {
+            return SessionEnd::Paused;
+        }
+
+        match &event {
+            JetstreamEvent::Commit(commit_event) => match commit_event {
                 CommitEvent::Create { commit, info } => {
-                    if let KnownRecord::AppBskyFeedPost(post) = &commit.record {
-                        let timestamp = chrono::Utc::now().format("%H:%M:%S").to_string();
-
-                        // Parse embeds
-                        let embed = post.embed.as_ref().and_then(|e| parse_embed(e));
-
-                        // Parse facets
-                        let facets = post.facets.as_ref().map(|f| parse_facets(f));
-
-                        let firehose_post = FirehosePost {
-                            timestamp,
-                            did: info.did.to_string(),
-                            rkey: commit.info.rkey.clone(),
-                            text: post.text.clone(),
-                            embed,
-                            facets,
-                        };
-
-                        // Send to UI thread
-                        if tx.send(firehose_post).is_err() {
-                            break; // UI is gone, stop streaming
+                    connection::record_event(info.time_us);
+
+                    // Event time rather than arrival time, which differs while catching up
+                    let timestamp = chrono::DateTime::from_timestamp_micros(info.time_us as i64)
+                        .unwrap_or_else(chrono::Utc::now)
+                        .format("%H:%M:%S")
+                        .to_string();
+                    let did = info.did.as_ref().to_string();
+                    let action = |kind: RecordType, subject: String| {
+                        StreamRecord::Action(StreamAction { kind, did: did.clone(), subject, timestamp: timestamp.clone() })
+                    };
+
+                    let record = match &commit.record {
+                        KnownRecord::AppBskyFeedPost(post) => {
+                            POSTS_RECEIVED.fetch_add(1, Ordering::Relaxed);
+                            let post = post_from_record(
+                                post,
+                                &did,
+                                &commit.info.rkey,
+                                &commit.cid.as_ref().to_string(),
+                                timestamp.clone(),
+                            );
+                            char_stats::record(&post.text);
+                            StreamRecord::Post(Box::new(post))
                         }
+                        KnownRecord::AppBskyFeedLike(like) => action(RecordType::Likes, like.subject.uri.clone()),
+                        KnownRecord::AppBskyFeedRepost(repost) => action(RecordType::Reposts, repost.subject.uri.clone()),
+                        KnownRecord::AppBskyGraphFollow(follow) => action(RecordType::Follows, follow.subject.as_ref().to_string()),
+                        _ => continue,
+                    };
+
+                    if received == 0 {
+                        tracing::info!("Connected to Bluesky Jetstream!");
+                        reporter.report(ConnectionState::Connected);
+                    }
+                    received += 1;
+
+                    // Send to UI thread
+                    if tx.send(record).is_err() {
+                        return SessionEnd::Closed; // UI is gone, stop streaming
                     }
                 }
+                CommitEvent::Delete { commit, info } if commit.collection.as_str() == "app.bsky.feed.post" => {
+                    connection::record_event(info.time_us);
+                    tx.notify(StreamNotice::Deleted {
+                        did: info.did.as_ref().to_string(),
+                        rkey: commit.rkey.clone(),
+                    });
+                }
                 _ => {}
+            },
+            JetstreamEvent::Identity(identity) => {
+                connection::record_event(identity.info.time_us);
+                tx.notify(StreamNotice::HandleChanged {
+                    did: identity.identity.did.as_ref().to_string(),
+                    handle: identity.identity.handle.as_ref().map(|handle| handle.as_ref().to_string()),
+                });
+            }
+            JetstreamEvent::Account(account) => {
+                connection::record_event(account.info.time_us);
+                if let Some(status) = account.account.status.as_ref().filter(|_| !account.account.active) {
+                    let status = match status {
+                        AccountStatus::Deactivated => "deactivated",
+                        AccountStatus::Deleted => "deleted",
+                        AccountStatus::Suspended => "suspended",
+                        AccountStatus::TakenDown => "taken down",
+                    };
+                    tx.notify(StreamNotice::AccountInactive {
+                        did: account.account.did.as_ref().to_string(),
+                        status: status.to_string(),
+                    });
+                }
             }
         }
     }
 
-    Ok(())
+    SessionEnd::Disconnected { received }
+}
+
+/// Decode a post record for display; also used for posts fetched outside Jetstream
+pub fn post_from_record(post: &PostRecord, did: &str, rkey: &str, cid: &str, timestamp: String) -> FirehosePost {
+    // Parse embeds
+    let embed = post.embed.as_ref().and_then(|e| parse_embed(e));
+
+    // Parse facets
+    let facets = post.facets.as_ref().map(|f| parse_facets(f));
+
+    // Score sentiment here, off the UI thread
+    let sentiment = sentiment::score(&post.text);
+
+    // Keep reply refs so the UI can fetch the parent on demand
+    let reply = post.reply.as_ref().map(|reply| ReplyContext {
+        parent_uri: reply.parent.uri.clone(),
+        root_uri: reply.root.uri.clone(),
+    });
+
+    // Primary subtags only, so "en-US" matches an "en" target
+    let langs = post.langs.as_ref()
+        .map(|langs| {
+            langs.iter()
+                .filter_map(|lang| lang.as_ref().as_str().split('-').next().map(str::to_lowercase))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    FirehosePost {
+        timestamp,
+        did: did.to_string(),
+        rkey: rkey.to_string(),
+        cid: cid.to_string(),
+        text: post.text.clone(),
+        embed,
+        facets,
+        sentiment,
+        reply,
+        langs,
+    }
+}
+
+/// Feed synthetic posts through the same channel as Jetstream, for `--demo`
+fn stream_demo_posts(tx: &PostSender, reporter: &ConnectionReporter) -> SessionEnd {
+    reporter.report(ConnectionState::Connected);
+
+    let mut generator = demo::DemoFirehose::new();
+    loop {
+        std::thread::sleep(generator.next_delay());
+        if crate::focus::is_enabled() {
+            return SessionEnd::Paused;
+        }
+        POSTS_RECEIVED.fetch_add(1, Ordering::Relaxed);
+        let post = generator.next_post();
+        char_stats::record(&post.text);
+        if tx.send(StreamRecord::Post(Box::new(post))).is_err() {
+            return SessionEnd::Closed;
+        }
+    }
 }
 
 fn parse_embed(embed: &atrium_api::types::Union<atrium_api::app::bsky::feed::post::RecordEmbedRefs>) -> Option<PostEmbed> {
+    use atrium_api::app::bsky::embed::record_with_media::MainMediaRefs;
     use atrium_api::app::bsky::feed::post::RecordEmbedRefs;
     use atrium_api::types::Union;
 
     match embed {
-        Union::Refs(RecordEmbedRefs::AppBskyEmbedImagesMain(images)) => {
-            let count = images.images.len();
-            if count > 0 {
-                // Extract alt text from images
-                let alt_texts: Vec<String> = images.images.iter()
-                    .map(|img| img.alt.clone())
-                    .collect();
-                Some(PostEmbed::Images { count, alt_texts })
-            } else {
-                None
-            }
-        }
-        Union::Refs(RecordEmbedRefs::AppBskyEmbedExternalMain(external)) => {
-            Some(PostEmbed::External {
-                uri: external.external.uri.clone(),
-                title: external.external.title.clone(),
-                description: external.external.description.clone(),
+        Union::Refs(RecordEmbedRefs::AppBskyEmbedImagesMain(images)) => parse_images(images),
+        Union::Refs(RecordEmbedRefs::AppBskyEmbedExternalMain(external)) => Some(parse_external(external)),
+        Union::Refs(RecordEmbedRefs::AppBskyEmbedVideoMain(video)) => Some(parse_video(video)),
+        // Quote posts, optionally with their own images, video or link card
+        Union::Refs(RecordEmbedRefs::AppBskyEmbedRecordMain(record)) => Some(PostEmbed::Record {
+            uri: record.record.uri.clone(),
+            media: None,
+        }),
+        Union::Refs(RecordEmbedRefs::AppBskyEmbedRecordWithMediaMain(record)) => {
+            let media = match &record.media {
+                Union::Refs(MainMediaRefs::AppBskyEmbedImagesMain(images)) => parse_images(images),
+                Union::Refs(MainMediaRefs::AppBskyEmbedExternalMain(external)) => Some(parse_external(external)),
+                Union::Refs(MainMediaRefs::AppBskyEmbedVideoMain(video)) => Some(parse_video(video)),
+                _ => None,
+            };
+            Some(PostEmbed::Record {
+                uri: record.record.record.uri.clone(),
+                media: media.map(Box::new),
             })
         }
-        Union::Refs(RecordEmbedRefs::AppBskyEmbedVideoMain(_video)) => {
-            Some(PostEmbed::Video)
-        }
         _ => None,
     }
 }
 
+fn blob_cid(blob: &atrium_api::types::BlobRef) -> String {
+    use atrium_api::types::{BlobRef, TypedBlobRef};
+
+    match blob {
+        BlobRef::Typed(TypedBlobRef::Blob(blob)) => blob.r#ref.0.to_string(),
+        BlobRef::Untyped(blob) => blob.cid.clone(),
+    }
+}
+
+fn parse_images(images: &atrium_api::app::bsky::embed::images::Main) -> Option<PostEmbed> {
+    let count = images.images.len();
+    if count == 0 {
+        return None;
+    }
+
+    // Extract alt text from images
+    let alt_texts: Vec<String> = images.images.iter()
+        .map(|img| img.alt.clone())
+        .collect();
+    let cids: Vec<String> = images.images.iter()
+        .map(|img| blob_cid(&img.image))
+        .collect();
+    Some(PostEmbed::Images { count, alt_texts, cids })
+}
+
+fn parse_external(external: &atrium_api::app::bsky::embed::external::Main) -> PostEmbed {
+    PostEmbed::External {
+        uri: external.external.uri.clone(),
+        title: external.external.title.clone(),
+        description: external.external.description.clone(),
+    }
+}
+
+fn parse_video(video: &atrium_api::app::bsky::embed::video::Main) -> PostEmbed {
+    PostEmbed::Video {
+        cid: blob_cid(&video.video),
+        alt: video.alt.clone().unwrap_or_default(),
+    }
+}
+
 fn parse_facets(facets: &[atrium_api::app::bsky::richtext::facet::Main]) -> Vec<PostFacet> {
     use atrium_api::app::bsky::richtext::facet::MainFeaturesItem;
     use atrium_api::types::Union;
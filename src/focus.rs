@@ -0,0 +1,15 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Read by the streaming thread to drop its Jetstream connection
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turn focus mode on or off: the firehose disconnects and blanks, and desktop
+/// notifications and the notifications badge hold off until it's turned off
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the firehose is paused for focus mode
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
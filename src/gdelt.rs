@@ -0,0 +1,378 @@
+use std::fmt;
+use std::time::Duration;
+
+use chrono::NaiveDateTime;
+use serde::de::DeserializeOwned;
+
+use crate::coordinates::lookup_country;
+use crate::data::{GdeltArticle, GdeltResponse, GdeltTimelineResponse, GDELT_API_URL, GDELT_GEO_API_URL};
+use crate::error::{get_text, FetchError};
+
+pub const SERVICE: &str = "GDELT";
+
+/// Most records the DOC API returns for one request
+const MAX_RECORDS: u32 = 250;
+/// The DOC API searches a rolling window of about three months
+const MAX_TIMESPAN_MINUTES: u32 = 3 * 30 * 24 * 60;
+/// The GEO API only keeps the past week of mentioned places
+const MAX_GEO_TIMESPAN_MINUTES: u32 = 7 * 24 * 60;
+/// Shortest window the API accepts
+const MIN_TIMESPAN_MINUTES: u32 = 15;
+/// Broad queries over long windows can take the DOC API a good while
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Output mode of the DOC API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GdeltMode {
+    /// List of matching articles
+    ArtList,
+    /// Coverage volume over time, as a share of all monitored coverage
+    TimelineVol,
+    /// Coverage volume over time, one series per source language
+    TimelineLang,
+}
+
+impl GdeltMode {
+    fn as_str(self) -> &'static str {
+        match self {
+            GdeltMode::ArtList => "artlist",
+            GdeltMode::TimelineVol => "timelinevol",
+            GdeltMode::TimelineLang => "timelinelang",
+        }
+    }
+}
+
+/// Article order for `ArtList`; without one GDELT sorts by relevance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GdeltSort {
+    /// Relevance weighted toward recent articles
+    HybridRel,
+    /// Newest first
+    DateDesc,
+    /// Oldest first
+    DateAsc,
+}
+
+impl GdeltSort {
+    fn as_str(self) -> &'static str {
+        match self {
+            GdeltSort::HybridRel => "hybridrel",
+            GdeltSort::DateDesc => "datedesc",
+            GdeltSort::DateAsc => "dateasc",
+        }
+    }
+}
+
+/// How far back to search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timespan {
+    minutes: u32,
+}
+
+impl Timespan {
+    pub const fn hours(hours: u32) -> Self {
+        Self { minutes: hours * 60 }
+    }
+
+    pub const fn days(days: u32) -> Self {
+        Self { minutes: days * 24 * 60 }
+    }
+
+    /// Largest whole unit the API understands, e.g. "2h" or "7d"
+    fn as_param(self) -> String {
+        if self.minutes.is_multiple_of(24 * 60) {
+            format!("{}d", self.minutes / (24 * 60))
+        } else if self.minutes.is_multiple_of(60) {
+            format!("{}h", self.minutes / 60)
+        } else {
+            format!("{}min", self.minutes)
+        }
+    }
+}
+
+/// Why a query can't be sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GdeltQueryError {
+    EmptyQuery,
+    MaxRecordsOutOfRange(u32),
+    TimespanOutOfRange(u32),
+    UnknownCountry(String),
+}
+
+impl fmt::Display for GdeltQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GdeltQueryError::EmptyQuery => write!(f, "the search has no terms"),
+            GdeltQueryError::MaxRecordsOutOfRange(count) => {
+                write!(f, "maxrecords must be between 1 and {}, got {}", MAX_RECORDS, count)
+            }
+            GdeltQueryError::TimespanOutOfRange(minutes) => write!(
+                f,
+                "timespan must be between {} minutes and 3 months, got {} minutes",
+                MIN_TIMESPAN_MINUTES, minutes
+            ),
+            GdeltQueryError::UnknownCountry(country) => write!(f, "unknown country '{}'", country),
+        }
+    }
+}
+
+impl std::error::Error for GdeltQueryError {}
+
+impl From<GdeltQueryError> for FetchError {
+    fn from(error: GdeltQueryError) -> Self {
+        FetchError::InvalidRequest { service: SERVICE, message: error.to_string() }
+    }
+}
+
+/// A DOC API request, built up field by field and turned into a URL with `url()`
+///
+/// Languages and countries set here are only added when the terms don't already
+/// carry a `sourcelang:` or `sourcecountry:` operator, so a search typed with its
+/// own filters keeps them.
+#[derive(Debug, Clone)]
+pub struct GdeltQuery {
+    terms: String,
+    languages: Vec<String>,
+    countries: Vec<String>,
+    timespan: Timespan,
+    mode: GdeltMode,
+    max_records: u32,
+    sort: Option<GdeltSort>,
+    /// Fixed UTC start and end, searched instead of the rolling timespan
+    window: Option<(NaiveDateTime, NaiveDateTime)>,
+}
+
+impl GdeltQuery {
+    /// Query for the given search terms, in GDELT's query syntax
+    pub fn new(terms: &str) -> Self {
+        Self {
+            terms: terms.trim().to_string(),
+            languages: Vec::new(),
+            countries: Vec::new(),
+            timespan: Timespan::days(1),
+            mode: GdeltMode::ArtList,
+            max_records: 75,
+            sort: None,
+            window: None,
+        }
+    }
+
+    /// Terms to search when the user hasn't typed any
+    pub fn or_default_terms(mut self, terms: &str) -> Self {
+        if self.terms.is_empty() {
+            self.terms = terms.to_string();
+        }
+        self
+    }
+
+    /// Restrict to a source language, e.g. "english"; several are ORed together
+    pub fn language(mut self, language: &str) -> Self {
+        self.languages.push(language.trim().to_lowercase());
+        self
+    }
+
+    /// Restrict to outlets in a country, given by ISO code, name or alias
+    pub fn country(mut self, country: &str) -> Self {
+        self.countries.push(country.trim().to_string());
+        self
+    }
+
+    pub fn timespan(mut self, timespan: Timespan) -> Self {
+        self.timespan = timespan;
+        self
+    }
+
+    /// Search between two UTC times instead of back from now
+    pub fn between(mut self, start: NaiveDateTime, end: NaiveDateTime) -> Self {
+        self.window = Some((start, end));
+        self
+    }
+
+    pub fn mode(mut self, mode: GdeltMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn max_records(mut self, max_records: u32) -> Self {
+        self.max_records = max_records;
+        self
+    }
+
+    pub fn sort(mut self, sort: GdeltSort) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// `sourcelang:a` or `(sourcelang:a OR sourcelang:b)`
+    fn operator_group(operator: &str, values: &[String]) -> Option<String> {
+        match values {
+            [] => None,
+            [value] => Some(format!("{}:{}", operator, value)),
+            values => Some(format!(
+                "({})",
+                values
+                    .iter()
+                    .map(|value| format!("{}:{}", operator, value))
+                    .collect::<Vec<_>>()
+                    .join(" OR ")
+            )),
+        }
+    }
+
+    /// The full query string sent as the `query` parameter
+    fn query_text(&self) -> Result<String, GdeltQueryError> {
+        let mut parts = Vec::new();
+        if !self.terms.is_empty() {
+            parts.push(self.terms.clone());
+        }
+
+        let lowercase_terms = self.terms.to_lowercase();
+        if !lowercase_terms.contains("sourcelang:") {
+            parts.extend(Self::operator_group("sourcelang", &self.languages));
+        }
+        if !lowercase_terms.contains("sourcecountry:") {
+            let countries = self
+                .countries
+                .iter()
+                .map(|country| {
+                    lookup_country(country)
+                        .map(|info| info.gdelt_name.clone())
+                        .ok_or_else(|| GdeltQueryError::UnknownCountry(country.clone()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            parts.extend(Self::operator_group("sourcecountry", &countries));
+        }
+
+        if parts.is_empty() {
+            return Err(GdeltQueryError::EmptyQuery);
+        }
+        Ok(parts.join(" "))
+    }
+
+    /// Validate the parameters and build the request URL
+    pub fn url(&self) -> Result<String, GdeltQueryError> {
+        if !(1..=MAX_RECORDS).contains(&self.max_records) {
+            return Err(GdeltQueryError::MaxRecordsOutOfRange(self.max_records));
+        }
+        let minutes = match self.window {
+            Some((start, end)) => (end - start).num_minutes().clamp(0, u32::MAX as i64) as u32,
+            None => self.timespan.minutes,
+        };
+        if !(MIN_TIMESPAN_MINUTES..=MAX_TIMESPAN_MINUTES).contains(&minutes) {
+            return Err(GdeltQueryError::TimespanOutOfRange(minutes));
+        }
+
+        let mut url = format!(
+            "{}?query={}&mode={}&format=json",
+            GDELT_API_URL,
+            urlencoding::encode(&self.query_text()?),
+            self.mode.as_str(),
+        );
+        match self.window {
+            Some((start, end)) => url.push_str(&format!(
+                "&startdatetime={}&enddatetime={}",
+                start.format("%Y%m%d%H%M%S"),
+                end.format("%Y%m%d%H%M%S")
+            )),
+            None => url.push_str(&format!("&timespan={}", self.timespan.as_param())),
+        }
+        if self.mode == GdeltMode::ArtList {
+            url.push_str(&format!("&maxrecords={}", self.max_records));
+            if let Some(sort) = self.sort {
+                url.push_str(&format!("&sort={}", sort.as_str()));
+            }
+        }
+
+        Ok(url)
+    }
+
+    /// Request URL for the GEO API's points of the places the matching coverage
+    /// mentions. Only the terms, filters and timespan apply, and the GEO API
+    /// looks back at most a week, so longer timespans are shortened to one.
+    pub fn geo_url(&self) -> Result<String, GdeltQueryError> {
+        if !(MIN_TIMESPAN_MINUTES..=MAX_TIMESPAN_MINUTES).contains(&self.timespan.minutes) {
+            return Err(GdeltQueryError::TimespanOutOfRange(self.timespan.minutes));
+        }
+        let timespan = Timespan { minutes: self.timespan.minutes.min(MAX_GEO_TIMESPAN_MINUTES) };
+        Ok(format!(
+            "{}?query={}&mode=PointData&format=GeoJSON&timespan={}",
+            GDELT_GEO_API_URL,
+            urlencoding::encode(&self.query_text()?),
+            timespan.as_param(),
+        ))
+    }
+}
+
+/// Fetch a DOC API body. GDELT answers over-eager clients with HTTP 200 and a
+/// plain-text notice, and malformed queries with a plain-text explanation,
+/// so both are caught here before anything tries to read it as JSON.
+async fn fetch_body(url: &str) -> Result<String, FetchError> {
+    let text = if crate::demo::is_enabled() {
+        crate::demo::gdelt_response(url)
+    } else {
+        get_text(SERVICE, crate::http::client().get(url).timeout(TIMEOUT)).await?
+    };
+    let trimmed = text.trim();
+
+    if trimmed.is_empty() || trimmed == "null" {
+        return Err(FetchError::Empty { service: SERVICE });
+    }
+    // These arrive as successful responses, so they're counted here rather than in `send`
+    if trimmed.contains("limit requests to one every") {
+        let error = FetchError::rate_limited(SERVICE);
+        crate::metrics::record_error(&error);
+        return Err(error);
+    }
+    if !trimmed.starts_with('{') && !trimmed.starts_with('[') {
+        let error = FetchError::parse(SERVICE, trimmed.chars().take(120).collect::<String>());
+        crate::metrics::record_error(&error);
+        return Err(error);
+    }
+
+    Ok(text)
+}
+
+/// Fetch and decode a DOC API response, e.g. a `GdeltTimelineResponse`
+pub async fn fetch_json<T: DeserializeOwned>(url: &str) -> Result<T, FetchError> {
+    let text = fetch_body(url).await?;
+    serde_json::from_str(&text)
+        .map_err(|e| FetchError::parse(SERVICE, e))
+        .inspect_err(crate::metrics::record_error)
+}
+
+/// Fetch a query's coverage volume as (time, percent of all coverage) points,
+/// oldest first; the query's mode is set to `TimelineVol`
+pub async fn fetch_timeline(query: &GdeltQuery) -> Result<Vec<(NaiveDateTime, f64)>, FetchError> {
+    let url = query.clone().mode(GdeltMode::TimelineVol).url()?;
+    let data: GdeltTimelineResponse = fetch_json(&url).await?;
+    Ok(data
+        .timeline
+        .into_iter()
+        .next()
+        .map(|series| series.data)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|point| {
+            NaiveDateTime::parse_from_str(&point.date, "%Y%m%dT%H%M%SZ")
+                .ok()
+                .map(|at| (at, point.value))
+        })
+        .collect())
+}
+
+/// Fetch an `ArtList` query's articles; a search with no matches gives an empty list
+pub async fn fetch_articles(url: &str) -> Result<Vec<GdeltArticle>, FetchError> {
+    let text = match fetch_body(url).await {
+        Ok(text) => text,
+        Err(FetchError::Empty { .. }) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    // Usually wrapped in {"articles": [...]}, occasionally a bare array
+    match serde_json::from_str::<GdeltResponse>(&text) {
+        Ok(data) => Ok(data.articles),
+        Err(e) => serde_json::from_str::<Vec<GdeltArticle>>(&text)
+            .map_err(|_| FetchError::parse(SERVICE, e))
+            .inspect_err(crate::metrics::record_error),
+    }
+}
@@ -1,21 +1,32 @@
 use gtk::prelude::*;
-use gtk::{glib, Label, Orientation, ScrolledWindow, ListBox, SearchEntry, Popover, EventControllerKey};
-use gdk::{Key, ModifierType};
+use gtk::{glib, Label, Orientation, ScrolledWindow, ListBox, SearchEntry, Popover};
 use libshumate::prelude::{MarkerExt, LocationExt};
 use std::collections::HashMap;
 use std::cell::RefCell;
 use std::rc::Rc;
 use chrono::NaiveDateTime;
 
-use crate::data::{GdeltArticle, GdeltResponse, CurrencyInfo, GDELT_API_URL};
+use crate::clustering::{cluster_stories, StoryCluster};
+use crate::data::{GdeltArticle, GdeltResponse, CurrencyInfo, FirehosePost};
+use crate::gdelt::{self, GdeltQuery, Timespan};
 use crate::coordinates::{get_country_coordinates, get_country_currency, get_country_timezone};
+use crate::disasters::DisasterLayers;
+use crate::domain_filter::DomainFilter;
+use crate::error::{get_json, FetchError};
+use crate::lightbox::LightboxImage;
+use crate::settings::{Settings, ThumbnailFit, ThumbnailSize};
+use crate::startup::Priority;
 
 pub fn create_global_affairs_view(
     current_query: Rc<RefCell<String>>,
     results_list_ref: Rc<RefCell<Option<ListBox>>>,
     marker_layer_ref: Rc<RefCell<Option<libshumate::MarkerLayer>>>,
+    search_entry_ref: Rc<RefCell<Option<SearchEntry>>>,
     use_12_hour: Rc<RefCell<bool>>,
+    settings: Rc<RefCell<Settings>>,
 ) -> gtk::Box {
+    SETTINGS.with(|current| *current.borrow_mut() = Some(settings.clone()));
+
     // Create a responsive container that switches orientation based on window size
     let container = gtk::Box::builder()
         .orientation(Orientation::Vertical)
@@ -36,9 +47,24 @@ pub fn create_global_affairs_view(
     let search_entry = SearchEntry::builder()
         .placeholder_text("Search GDELT news...")
         .visible(false)
+        .hexpand(true)
+        .build();
+
+    // Search row with the query builder next to the entry, shown and hidden along with it
+    let search_row = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
         .margin_start(8)
         .margin_end(8)
         .build();
+    search_row.append(&search_entry);
+    search_row.append(&crate::query_builder::create_query_builder_button(&search_entry));
+    search_row.append(&create_save_search_button(&search_entry, settings.clone()));
+    search_row.append(&create_multilingual_button(&search_entry, settings.clone()));
+    search_entry
+        .bind_property("visible", &search_row, "visible")
+        .sync_create()
+        .build();
 
     // Create a list box for search results
     let results_list = ListBox::builder()
@@ -49,23 +75,39 @@ pub fn create_global_affairs_view(
     // Store results_list in the shared reference
     *results_list_ref.borrow_mut() = Some(results_list.clone());
 
-    scrollbox_content.append(&search_entry);
+    // Articles already opened can be left out, re-checked whenever the toggle flips
+    let settings_for_filter = settings.clone();
+    results_list.set_filter_func(move |row| {
+        !settings_for_filter.borrow().hide_read_articles || !crate::read_articles::is_read(row.widget_name().as_str())
+    });
+
+    scrollbox_content.append(&search_row);
+    scrollbox_content.append(&crate::volume_chart::create_volume_chart());
+    scrollbox_content.append(&create_hide_read_button(&results_list, settings.clone()));
     scrollbox_content.append(&results_list);
     scrolled_window.set_child(Some(&scrollbox_content));
 
     // Create the map widget using libshumate
     let map = libshumate::SimpleMap::new();
 
-    // Create a custom dark-themed map source using CartoDB Dark Matter tiles
-    let map_source = libshumate::RasterRenderer::from_url(
-        "https://a.basemaps.cartocdn.com/dark_all/{z}/{x}/{y}.png"
-    );
-
-    map.set_map_source(Some(&map_source));
+    // Tiles start loading once the window is up, ahead of the network fetches
+    let map_for_tiles = map.clone();
+    crate::startup::schedule(Priority::Critical, "Map tiles", async move {
+        crate::tiles::attach(&map_for_tiles);
+    });
 
-    // Get the viewport to create the marker layer
+    // Get the viewport to create the marker layers
+    let mut disaster_layers = None;
+    let mut event_layers = None;
+    let mut map_handles = None;
     let marker_layer_opt = if let Some(map_view) = map.map() {
         if let Some(viewport) = map_view.viewport() {
+            // Hazard layers sit below the news markers; safe mode leaves them out
+            if !crate::safe_mode::is_enabled() {
+                disaster_layers = Some(DisasterLayers::new(&map_view, &viewport));
+                event_layers = Some(crate::events::EventLayers::new(&map_view, &viewport));
+            }
+
             // Create a marker layer for country markers
             let marker_layer = libshumate::MarkerLayer::new(&viewport);
 
@@ -80,6 +122,7 @@ pub fn create_global_affairs_view(
             // Set initial zoom level to 2 (good overview of world)
             map_view.go_to_full(0.0, 0.0, 2.0);
 
+            map_handles = Some((map_view.clone(), viewport.clone()));
             Some(marker_layer)
         } else {
             None
@@ -95,22 +138,87 @@ pub fn create_global_affairs_view(
     map.set_vexpand(true);
     map.set_hexpand(true);
 
+    // Overlay the map with a layers menu in the top-right corner
+    let map_overlay = gtk::Overlay::new();
+    map_overlay.set_child(Some(&map));
+
+    let mut toggleable_layers = Vec::new();
+    if let Some(ref layer) = marker_layer_opt {
+        toggleable_layers.push(("News", layer.clone()));
+    }
+    if let Some(ref layers) = disaster_layers {
+        toggleable_layers.push(("Earthquakes", layers.earthquakes.clone()));
+        toggleable_layers.push(("Disaster alerts", layers.alerts.clone()));
+
+        // Initial hazard fetch
+        crate::startup::schedule(Priority::Normal, "Earthquakes", crate::disasters::fetch_earthquakes(layers.earthquakes.clone()));
+        crate::startup::schedule(Priority::Normal, "Disaster alerts", crate::disasters::fetch_gdacs_alerts(layers.alerts.clone()));
+    }
+    let event_toggles = event_layers.as_ref().map(|layers| layers.named_layers()).unwrap_or_default();
+    if let Some(ref layers) = event_layers {
+        crate::startup::schedule(Priority::Low, "GDELT events", layers.clone().fetch());
+    }
+    // The heatmap sits above the markers but lets clicks through to them
+    if let Some((ref map_view, ref viewport)) = map_handles {
+        crate::heatmap::install(&map_overlay, map_view, viewport);
+    }
+    map_overlay.add_overlay(&create_layers_button(&toggleable_layers, &event_toggles, map_handles.as_ref()));
+
+    // j/k through the articles, with Enter, b and m acting on the selected one
+    crate::article_nav::attach(&results_list, map_handles.as_ref().map(|(map_view, _)| map_view.clone()));
+
+    // Place search at the top of the map that flies the viewport to a country or city
+    if let Some((ref map_view, _)) = map_handles {
+        map_overlay.add_overlay(&crate::map_search::create_map_search_entry(map_view));
+    }
+
+    // Container for country popovers pinned as floating panels
+    let pinned_panels = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .halign(gtk::Align::Start)
+        .valign(gtk::Align::Start)
+        .margin_top(8)
+        .margin_start(8)
+        .build();
+    map_overlay.add_overlay(&pinned_panels);
+    PINNED_PANELS.with(|panels| *panels.borrow_mut() = Some(pinned_panels));
+
+    // Shown once two or more countries are picked for comparison
+    map_overlay.add_overlay(&crate::compare::create_compare_bar(use_12_hour.clone()));
+
     // Clone marker layer for use in async callback
     let marker_layer_clone = marker_layer_opt.clone();
     let results_list_clone = results_list.clone();
     let use_12_hour_clone = use_12_hour.clone();
 
-    // Perform initial search with empty query to get latest news
-    glib::spawn_future_local(async move {
-        fetch_gdelt_articles("", results_list_clone, marker_layer_clone, use_12_hour_clone).await;
+    // Pick up the search from the last session, or get the latest news
+    let last_query = settings.borrow().last_query.clone();
+    if !last_query.is_empty() {
+        *current_query.borrow_mut() = last_query.clone();
+        search_entry.set_text(&last_query);
+        search_entry.set_visible(true);
+    }
+    crate::startup::schedule(Priority::High, "GDELT articles", async move {
+        fetch_gdelt_articles(&last_query, results_list_clone, marker_layer_clone, use_12_hour_clone).await;
     });
 
-    // Set up automatic refresh every 15 minutes
+    // Set up automatic refresh, checking every minute against the configured interval
     let current_query_for_refresh = current_query.clone();
     let results_list_for_refresh = results_list.clone();
     let marker_layer_for_refresh = marker_layer_opt.clone();
     let use_12_hour_for_refresh = use_12_hour.clone();
-    glib::timeout_add_seconds_local(15 * 60, move || {
+    let disaster_layers_for_refresh = disaster_layers.clone();
+    let event_layers_for_refresh = event_layers.clone();
+    let settings_for_refresh = settings.clone();
+    let last_refresh = Rc::new(RefCell::new(std::time::Instant::now()));
+    glib::timeout_add_seconds_local(60, move || {
+        let interval = std::time::Duration::from_secs(settings_for_refresh.borrow().refresh_interval_minutes as u64 * 60);
+        if last_refresh.borrow().elapsed() < interval {
+            return glib::ControlFlow::Continue;
+        }
+        *last_refresh.borrow_mut() = std::time::Instant::now();
+
         let query = current_query_for_refresh.borrow().clone();
         let results_list = results_list_for_refresh.clone();
         let marker_layer = marker_layer_for_refresh.clone();
@@ -120,6 +228,13 @@ pub fn create_global_affairs_view(
             fetch_gdelt_articles(&query, results_list, marker_layer, use_12_hour).await;
         });
 
+        if let Some(ref layers) = disaster_layers_for_refresh {
+            layers.refresh();
+        }
+        if let Some(ref layers) = event_layers_for_refresh {
+            layers.refresh();
+        }
+
         glib::ControlFlow::Continue
     });
 
@@ -128,11 +243,13 @@ pub fn create_global_affairs_view(
     let marker_layer_for_search = marker_layer_opt.clone();
     let current_query_for_search = current_query.clone();
     let use_12_hour_for_search = use_12_hour.clone();
+    let settings_for_search = settings.clone();
     search_entry.connect_activate(move |entry| {
         let query = entry.text().to_string();
 
-        // Update the current query
+        // Update the current query, remembered for the next session
         *current_query_for_search.borrow_mut() = query.clone();
+        settings_for_search.borrow_mut().last_query = query.clone();
 
         let results_list = results_list_for_search.clone();
         let marker_layer = marker_layer_for_search.clone();
@@ -155,15 +272,16 @@ pub fn create_global_affairs_view(
     paned.set_shrink_start_child(false);
 
     // Set the map as the second child (bottom in vertical, right in horizontal)
-    paned.set_end_child(Some(&map));
+    paned.set_end_child(Some(&map_overlay));
     paned.set_resize_end_child(true);
     paned.set_shrink_end_child(false);
 
-    // Set initial position (200px for scrollbox in vertical mode)
-    paned.set_position(200);
+    // Set initial position (the list's height in vertical mode), as left last session
+    paned.set_position(settings.borrow().global_affairs_list_height);
 
     // Add a tick callback to change orientation based on window size
     let paned_weak = paned.downgrade();
+    let settings_for_orientation = settings.clone();
     paned.add_tick_callback(move |_widget, _clock| {
         if let Some(paned) = paned_weak.upgrade() {
             let width = paned.width();
@@ -174,12 +292,16 @@ pub fn create_global_affairs_view(
                 let is_horizontal = paned.orientation() == Orientation::Horizontal;
 
                 if should_be_horizontal != is_horizontal {
+                    let (list_height, map_width) = {
+                        let settings = settings_for_orientation.borrow();
+                        (settings.global_affairs_list_height, settings.global_affairs_map_width)
+                    };
                     if should_be_horizontal {
                         paned.set_orientation(Orientation::Horizontal);
-                        paned.set_position(width - 500); // 250px from right for scrollbox
+                        paned.set_position(width - map_width); // Map keeps its width on the right
                     } else {
                         paned.set_orientation(Orientation::Vertical);
-                        paned.set_position(200); // 200px from top for scrollbox
+                        paned.set_position(list_height); // List keeps its height at the top
                     }
                 }
             }
@@ -187,27 +309,277 @@ pub fn create_global_affairs_view(
         glib::ControlFlow::Continue
     });
 
-    // Set up keyboard shortcut for toggling search (Ctrl+F)
-    let search_entry_clone = search_entry.clone();
-    let key_controller = EventControllerKey::new();
-    key_controller.connect_key_pressed(move |_, key, _, modifier| {
-        if key == Key::f && modifier == ModifierType::CONTROL_MASK {
-            let is_visible = search_entry_clone.is_visible();
-            search_entry_clone.set_visible(!is_visible);
-            if !is_visible {
-                search_entry_clone.grab_focus();
+    // Remember where the divider was dragged to; saved with the window state on close
+    let settings_for_divider = settings.clone();
+    paned.connect_position_notify(move |paned| {
+        let position = paned.position();
+        if position <= 0 {
+            return;
+        }
+        let mut settings = settings_for_divider.borrow_mut();
+        if paned.orientation() == Orientation::Horizontal {
+            if paned.width() > position {
+                settings.global_affairs_map_width = paned.width() - position;
             }
-            glib::Propagation::Stop
         } else {
-            glib::Propagation::Proceed
+            settings.global_affairs_list_height = position;
         }
     });
-    container.add_controller(key_controller);
 
-    container.append(&paned);
+    // Store search entry so the app.focus-search action (Ctrl+F) can toggle it
+    *search_entry_ref.borrow_mut() = Some(search_entry.clone());
+
+    // In narrow windows the list and map take turns filling the page
+    let map_toggle = gtk::ToggleButton::builder()
+        .icon_name("mark-location-symbolic")
+        .tooltip_text("Show map")
+        .halign(gtk::Align::End)
+        .valign(gtk::Align::End)
+        .margin_end(12)
+        .margin_bottom(12)
+        .visible(false)
+        .css_classes(vec!["circular".to_string(), "osd".to_string()])
+        .build();
+    map_toggle.connect_toggled(|button| {
+        button.set_tooltip_text(Some(if button.is_active() { "Show articles" } else { "Show map" }));
+        button.set_icon_name(if button.is_active() { "view-list-symbolic" } else { "mark-location-symbolic" });
+        apply_compact_layout();
+    });
+
+    let paned_overlay = gtk::Overlay::new();
+    paned_overlay.set_child(Some(&paned));
+    paned_overlay.add_overlay(&map_toggle);
+
+    COMPACT_LAYOUT.with(|layout| {
+        *layout.borrow_mut() = Some(CompactLayout {
+            list: scrolled_window.clone().upcast(),
+            map: map_overlay.clone().upcast(),
+            map_toggle,
+            compact: false,
+        });
+    });
+
+    container.append(&crate::country_page::create_navigation(&paned_overlay));
     container
 }
 
+/// Widgets swapped around when the window gets too narrow for list and map side by side
+struct CompactLayout {
+    list: gtk::Widget,
+    map: gtk::Widget,
+    map_toggle: gtk::ToggleButton,
+    compact: bool,
+}
+
+/// Show only the list or the map, picked with a floating toggle, in narrow windows
+pub fn set_compact(compact: bool) {
+    COMPACT_LAYOUT.with(|layout| {
+        if let Some(ref mut layout) = *layout.borrow_mut() {
+            layout.compact = compact;
+        }
+    });
+    apply_compact_layout();
+}
+
+fn apply_compact_layout() {
+    COMPACT_LAYOUT.with(|layout| {
+        let Some(ref layout) = *layout.borrow() else {
+            return;
+        };
+        let show_map = layout.map_toggle.is_active();
+        layout.map_toggle.set_visible(layout.compact);
+        layout.list.set_visible(!layout.compact || !show_map);
+        layout.map.set_visible(!layout.compact || show_map);
+    });
+}
+
+/// Toggle that saves the entry's query for weekly digests, or forgets it again
+/// Toggle between English sources only and coverage in every language GDELT
+/// translates, searching again when flipped
+fn create_multilingual_button(search_entry: &SearchEntry, settings: Rc<RefCell<Settings>>) -> gtk::ToggleButton {
+    let button = gtk::ToggleButton::builder()
+        .icon_name("preferences-desktop-locale-symbolic")
+        .tooltip_text("Search coverage in all languages")
+        .active(settings.borrow().multilingual_search)
+        .build();
+    button.add_css_class("flat");
+
+    let search_entry = search_entry.clone();
+    button.connect_toggled(move |button| {
+        {
+            let mut settings = settings.borrow_mut();
+            settings.multilingual_search = button.is_active();
+            settings.save();
+        }
+        search_entry.emit_activate();
+    });
+
+    button
+}
+
+/// Toggle above the results that filters out articles already opened
+fn create_hide_read_button(results_list: &ListBox, settings: Rc<RefCell<Settings>>) -> gtk::ToggleButton {
+    let button = gtk::ToggleButton::builder()
+        .label("Hide Read")
+        .tooltip_text("Leave out articles you've already opened")
+        .active(settings.borrow().hide_read_articles)
+        .halign(gtk::Align::End)
+        .margin_end(8)
+        .build();
+    button.add_css_class("flat");
+    button.add_css_class("caption");
+
+    let results_list = results_list.clone();
+    button.connect_toggled(move |button| {
+        {
+            let mut settings = settings.borrow_mut();
+            settings.hide_read_articles = button.is_active();
+            settings.save();
+        }
+        results_list.invalidate_filter();
+    });
+
+    button
+}
+
+fn create_save_search_button(search_entry: &SearchEntry, settings: Rc<RefCell<Settings>>) -> gtk::ToggleButton {
+    let button = gtk::ToggleButton::builder()
+        .icon_name("non-starred-symbolic")
+        .tooltip_text("Save search for weekly digests")
+        .sensitive(false)
+        .build();
+    button.add_css_class("flat");
+
+    // Track whether the typed query is already saved; `syncing` keeps that from
+    // being treated as a click
+    let syncing = Rc::new(RefCell::new(false));
+
+    let settings_for_sync = settings.clone();
+    let button_for_sync = button.clone();
+    let syncing_for_sync = syncing.clone();
+    search_entry.connect_changed(move |entry| {
+        let query = entry.text().trim().to_string();
+        let saved = settings_for_sync.borrow().saved_searches.contains(&query);
+
+        *syncing_for_sync.borrow_mut() = true;
+        button_for_sync.set_sensitive(!query.is_empty());
+        button_for_sync.set_active(saved);
+        button_for_sync.set_icon_name(if saved { "starred-symbolic" } else { "non-starred-symbolic" });
+        *syncing_for_sync.borrow_mut() = false;
+    });
+
+    let search_entry = search_entry.clone();
+    button.connect_toggled(move |button| {
+        if *syncing.borrow() {
+            return;
+        }
+
+        let query = search_entry.text().trim().to_string();
+        if query.is_empty() {
+            return;
+        }
+
+        let mut settings = settings.borrow_mut();
+        if button.is_active() {
+            if !settings.saved_searches.contains(&query) {
+                settings.saved_searches.push(query);
+            }
+            button.set_icon_name("starred-symbolic");
+        } else {
+            settings.saved_searches.retain(|saved| saved != &query);
+            button.set_icon_name("non-starred-symbolic");
+        }
+        settings.save();
+    });
+
+    button
+}
+
+/// Create the map's layers menu with a check button per marker layer, a
+/// section filtering GDELT events by category, plus importing of user
+/// datasets when the map view is available
+fn create_layers_button(
+    layers: &[(&str, libshumate::MarkerLayer)],
+    event_layers: &[(&str, libshumate::MarkerLayer)],
+    map_handles: Option<&(libshumate::Map, libshumate::Viewport)>,
+) -> gtk::MenuButton {
+    let layers_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(6)
+        .margin_end(6)
+        .build();
+
+    let header = Label::builder()
+        .label("Map Layers")
+        .xalign(0.0)
+        .build();
+    header.add_css_class("heading");
+    layers_box.append(&header);
+
+    for (name, layer) in layers.iter() {
+        layers_box.append(&create_layer_toggle(name, layer));
+    }
+    if let Some(check) = crate::heatmap::create_toggle() {
+        layers_box.append(&check);
+    }
+
+    if !event_layers.is_empty() {
+        let events_header = Label::builder()
+            .label("Events")
+            .xalign(0.0)
+            .margin_top(6)
+            .tooltip_text("Protests and conflict coded by GDELT in the last 15 minutes")
+            .build();
+        events_header.add_css_class("heading");
+        layers_box.append(&events_header);
+        for (name, layer) in event_layers.iter() {
+            layers_box.append(&create_layer_toggle(name, layer));
+        }
+    }
+
+    // Imported layers append their rows below the import button
+    if let Some((map_view, viewport)) = map_handles.filter(|_| !crate::safe_mode::is_enabled()) {
+        layers_box.append(&crate::overlays::create_import_button(map_view, viewport, &layers_box));
+    }
+
+    let popover = Popover::builder()
+        .child(&layers_box)
+        .build();
+
+    let button = gtk::MenuButton::builder()
+        .label("Layers")
+        .tooltip_text("Toggle map layers")
+        .popover(&popover)
+        .halign(gtk::Align::End)
+        .valign(gtk::Align::Start)
+        .margin_top(8)
+        .margin_end(8)
+        .build();
+    button.add_css_class("osd");
+
+    button
+}
+
+/// Check button showing or hiding a marker layer
+fn create_layer_toggle(name: &str, layer: &libshumate::MarkerLayer) -> gtk::CheckButton {
+    let check = gtk::CheckButton::builder()
+        .label(name)
+        .active(layer.is_visible())
+        .build();
+
+    let layer_clone = layer.clone();
+    check.connect_toggled(move |check| {
+        layer_clone.set_visible(check.is_active());
+    });
+    check
+}
+
+/// Times a rate-limited search is retried before giving up
+const GDELT_MAX_RETRIES: u32 = 2;
+
 pub async fn fetch_gdelt_articles(query: &str, results_list: ListBox, marker_layer: Option<libshumate::MarkerLayer>, use_12_hour: Rc<RefCell<bool>>) {
     // Clear existing results
     while let Some(child) = results_list.first_child() {
@@ -216,6 +588,9 @@ pub async fn fetch_gdelt_articles(query: &str, results_list: ListBox, marker_lay
 
     // Create a shared map to store marker buttons by country code
     let marker_buttons_map: Rc<RefCell<HashMap<String, gtk::Button>>> = Rc::new(RefCell::new(HashMap::new()));
+    COUNTRY_MARKERS.with(|markers| *markers.borrow_mut() = Some(marker_buttons_map.clone()));
+    CURRENT_QUERY.with(|current| *current.borrow_mut() = query.to_string());
+    crate::heatmap::set_query(query);
 
     // Clear existing markers if marker layer is provided
     if let Some(ref layer) = marker_layer {
@@ -236,109 +611,93 @@ pub async fn fetch_gdelt_articles(query: &str, results_list: ListBox, marker_lay
     loading_row.append(&loading_label);
     results_list.append(&loading_row);
 
-    // Build the API URL with English language filter
-    // Use timespan=2h to get only the most recent articles
-    let url = if query.is_empty() {
-        // For empty queries, use "world" as default query to get broader news coverage
-        format!(
-            "{}?query=world sourcelang:english&mode=artlist&maxrecords=50&timespan=2h&format=json",
-            GDELT_API_URL
-        )
-    } else {
-        format!(
-            "{}?query={} sourcelang:english&mode=artlist&maxrecords=50&timespan=2h&format=json",
-            GDELT_API_URL,
-            urlencoding::encode(query)
-        )
+    // Articles from the last two hours, or the window zoomed to on the volume
+    // chart; "world" stands in for an empty search. Multilingual searches leave
+    // out the language filter: GDELT matches the terms against its English
+    // translations of coverage in other languages.
+    let multilingual = search_settings().0;
+    let mut gdelt_query = GdeltQuery::new(query)
+        .or_default_terms("world")
+        .timespan(Timespan::hours(2))
+        .max_records(50);
+    match crate::volume_chart::zoom_window(query) {
+        Some((start, end)) => gdelt_query = gdelt_query.between(start, end),
+        None => {
+            let query = query.to_string();
+            glib::spawn_future_local(async move {
+                crate::volume_chart::load(&query, multilingual).await;
+            });
+        }
+    }
+    if !multilingual {
+        gdelt_query = gdelt_query.language("english");
+    }
+    let url = match gdelt_query.url() {
+        Ok(url) => url,
+        Err(e) => {
+            show_results_message(&results_list, &format!("Invalid search: {}", e));
+            return;
+        }
     };
 
-    eprintln!("Fetching from URL: {}", url);
-
-    // Fetch data from GDELT API
-    match reqwest::get(&url).await {
-        Ok(response) => {
-            // Get the raw text first to help debug
-            match response.text().await {
-                Ok(text) => {
-                    eprintln!("Response text (first 500 chars): {}", &text.chars().take(500).collect::<String>());
-
-                    // Check if response is empty or null
-                    if text.trim().is_empty() || text.trim() == "null" {
-                        // Clear all children (including loading indicator)
-                        while let Some(child) = results_list.first_child() {
-                            results_list.remove(&child);
-                        }
-                        let no_results = Label::builder()
-                            .label("No articles found for this search")
-                            .margin_top(12)
-                            .margin_bottom(12)
-                            .build();
-                        results_list.append(&no_results);
-                        return;
-                    }
+    tracing::debug!("Fetching from URL: {}", url);
 
-                    // Try to parse the JSON
-                    match serde_json::from_str::<GdeltResponse>(&text) {
-                        Ok(data) => {
-                            process_gdelt_articles(data, results_list, marker_layer, marker_buttons_map, use_12_hour.clone());
-                        }
-                        Err(e) => {
-                            // Try parsing as a direct array of articles
-                            match serde_json::from_str::<Vec<GdeltArticle>>(&text) {
-                                Ok(articles) => {
-                                    let data = GdeltResponse { articles };
-                                    process_gdelt_articles(data, results_list, marker_layer, marker_buttons_map, use_12_hour.clone());
-                                }
-                                Err(_) => {
-                                    // Clear all children (including loading indicator)
-                                    while let Some(child) = results_list.first_child() {
-                                        results_list.remove(&child);
-                                    }
-                                    eprintln!("JSON parse error: {}", e);
-                                    eprintln!("Response preview: {}", &text.chars().take(200).collect::<String>());
-                                    let error_label = Label::builder()
-                                        .label("Error: Could not parse news feed. The API may be unavailable or returned unexpected data.")
-                                        .wrap(true)
-                                        .margin_top(12)
-                                        .margin_bottom(12)
-                                        .build();
-                                    results_list.append(&error_label);
-                                }
-                            }
-                        }
-                    }
+    let mut retries = 0;
+    loop {
+        match gdelt::fetch_articles(&url).await {
+            Ok(articles) => {
+                let data = GdeltResponse { articles };
+                process_gdelt_articles(data, results_list, marker_layer, marker_buttons_map, use_12_hour);
+                return;
+            }
+            Err(e) => {
+                tracing::warn!("Error fetching articles: {}", e);
+
+                let Some(wait) = e.retry_after() else {
+                    show_results_message(&results_list, "Couldn't load articles");
+                    report_search_error(&results_list, &e);
+                    return;
+                };
+                if retries == GDELT_MAX_RETRIES {
+                    show_results_message(&results_list, "Couldn't load articles");
+                    report_search_error(&results_list, &e);
+                    return;
                 }
-                Err(e) => {
-                    // Clear all children (including loading indicator)
-                    while let Some(child) = results_list.first_child() {
-                        results_list.remove(&child);
-                    }
-                    eprintln!("Error reading response text: {}", e);
-                    let error_label = Label::builder()
-                        .label(&format!("Error reading response: {}", e))
-                        .margin_top(12)
-                        .margin_bottom(12)
-                        .build();
-                    results_list.append(&error_label);
+                show_results_message(&results_list, &e.to_string());
+                retries += 1;
+                glib::timeout_future(wait).await;
+
+                // A newer search replaced this one while it waited
+                if CURRENT_QUERY.with(|current| *current.borrow() != query) {
+                    return;
                 }
             }
         }
-        Err(e) => {
-            // Clear all children (including loading indicator)
-            while let Some(child) = results_list.first_child() {
-                results_list.remove(&child);
-            }
-            eprintln!("Error fetching articles: {}", e);
-            let error_label = Label::builder()
-                .label(&format!("Error fetching articles: {}", e))
-                .margin_top(12)
-                .margin_bottom(12)
-                .build();
-            results_list.append(&error_label);
-        }
     }
 }
 
+/// Toast a failed search, retrying it through the refresh action
+fn report_search_error(results_list: &ListBox, error: &FetchError) {
+    let results_list = results_list.clone();
+    crate::toasts::show_error(error, move || {
+        let _ = results_list.activate_action("app.refresh", None);
+    });
+}
+
+/// Replace the results list with a single message, e.g. an error
+fn show_results_message(results_list: &ListBox, message: &str) {
+    while let Some(child) = results_list.first_child() {
+        results_list.remove(&child);
+    }
+    let label = Label::builder()
+        .label(message)
+        .wrap(true)
+        .margin_top(12)
+        .margin_bottom(12)
+        .build();
+    results_list.append(&label);
+}
+
 fn process_gdelt_articles(
     data: GdeltResponse,
     results_list: ListBox,
@@ -346,6 +705,8 @@ fn process_gdelt_articles(
     marker_buttons_map: Rc<RefCell<HashMap<String, gtk::Button>>>,
     use_12_hour: Rc<RefCell<bool>>,
 ) {
+    crate::stats::record_articles(data.articles.len());
+
     // Clear all children (including loading indicator)
     while let Some(child) = results_list.first_child() {
         results_list.remove(&child);
@@ -363,30 +724,81 @@ fn process_gdelt_articles(
         let mut sorted_articles = data.articles.clone();
         sorted_articles.sort_by(|a, b| b.seendate.cmp(&a.seendate));
 
-        // Deduplicate by domain - limit to 3 articles per domain
+        // Drop filtered outlets and group the same story from different outlets
+        // into one card, then fold stories past the per-domain limit into an expander
+        let domain_filter = DomainFilter::current();
+        sorted_articles.retain(|article| domain_filter.allows(&article.domain));
+        let stories = cluster_stories(sorted_articles);
+
         let mut domain_counts: HashMap<String, usize> = HashMap::new();
-        let max_per_domain = 3;
+        let mut overflow: HashMap<String, Vec<StoryCluster>> = HashMap::new();
+        let mut last_row_index: HashMap<String, i32> = HashMap::new();
+        let mut filterable_rows = Vec::new();
+        let mut shown_articles = Vec::new();
+        let mut row_count = 0;
+
+        for story in stories {
+            let domain = story.lead.domain.clone();
+            let count = domain_counts.entry(domain.clone()).or_insert(0);
+            if domain_filter.max_per_domain > 0 && *count >= domain_filter.max_per_domain {
+                overflow.entry(domain).or_default().push(story);
+                continue;
+            }
 
-        for article in sorted_articles.iter() {
-            let count = domain_counts.entry(article.domain.clone()).or_insert(0);
-            if *count < max_per_domain {
-                let marker_data = if marker_layer.is_some() {
-                    Some((marker_buttons_map.clone(), marker_layer.clone().unwrap()))
-                } else {
-                    None
-                };
-                let article_row = create_article_row_with_markers(article, marker_data);
-                results_list.append(&article_row);
-                *count += 1;
+            let marker_data = marker_layer.clone().map(|layer| (marker_buttons_map.clone(), layer));
+            let story_row = create_story_row(&story, marker_data);
+            results_list.append(&story_row);
+            *count += 1;
+            last_row_index.insert(domain.clone(), row_count);
+            row_count += 1;
+
+            // Remember the list row wrapping this card for the quick filters
+            if let Some(row) = story_row.parent() {
+                // Looked up by the hide-read filter
+                row.set_widget_name(&story.lead.url);
+                filterable_rows.push(FilterableRow {
+                    row,
+                    country: story.lead.sourcecountry.clone(),
+                    domain,
+                });
+            }
+
+            shown_articles.push(story.lead);
+            shown_articles.extend(story.others);
+        }
+
+        // Insert from the bottom up so earlier positions stay valid
+        let mut expanders: Vec<(i32, String, Vec<StoryCluster>)> = overflow
+            .into_iter()
+            .filter_map(|(domain, stories)| Some((*last_row_index.get(&domain)?, domain, stories)))
+            .collect();
+        expanders.sort_by_key(|(index, _, _)| std::cmp::Reverse(*index));
+        for (index, domain, stories) in expanders {
+            let marker_data = marker_layer.clone().map(|layer| (marker_buttons_map.clone(), layer));
+            let expander = create_domain_overflow_expander(&domain, stories, marker_data);
+            results_list.insert(&expander, index + 1);
+            if let Some(row) = expander.parent() {
+                filterable_rows.push(FilterableRow {
+                    row,
+                    country: String::new(),
+                    domain,
+                });
             }
         }
 
+        // Quick filter chips go above the articles
+        if let Some(filter_bar) = create_quick_filters(filterable_rows) {
+            results_list.prepend(&filter_bar);
+        }
+
+        CURRENT_ARTICLES.with(|current| *current.borrow_mut() = shown_articles);
+
         // Group articles by country and place markers on the map
         if let Some(ref layer) = marker_layer {
             let mut articles_by_country: HashMap<String, Vec<GdeltArticle>> = HashMap::new();
 
-            // Group ALL articles by country (not just unique ones)
-            for article in data.articles.iter() {
+            // Group all listed or folded articles by country, leaving out filtered outlets
+            for article in data.articles.iter().filter(|article| domain_filter.allows(&article.domain)) {
                 if !article.sourcecountry.is_empty() {
                     articles_by_country
                         .entry(article.sourcecountry.clone())
@@ -395,20 +807,429 @@ fn process_gdelt_articles(
                 }
             }
 
-            eprintln!("Found {} countries with articles", articles_by_country.len());
+            tracing::debug!("Found {} countries with articles", articles_by_country.len());
 
             // Create markers for each country
             for (country_code, articles) in articles_by_country.iter() {
                 if let Some((lat, lon)) = get_country_coordinates(country_code) {
-                    eprintln!("Creating marker for {} with {} articles at ({}, {})",
+                    tracing::debug!("Creating marker for {} with {} articles at ({}, {})",
                              country_code, articles.len(), lat, lon);
                     create_country_marker(layer, country_code, lat, lon, articles, marker_buttons_map.clone(), use_12_hour.clone());
                 } else {
-                    eprintln!("No coordinates found for country code: {}", country_code);
+                    tracing::warn!("No coordinates found for country code: {}", country_code);
+                }
+            }
+        }
+    }
+}
+
+/// "N more from domain" expander holding the stories past the per-domain limit
+fn create_domain_overflow_expander(
+    domain: &str,
+    stories: Vec<StoryCluster>,
+    marker_data: Option<(MarkerButtons, libshumate::MarkerLayer)>,
+) -> gtk::Expander {
+    let expander = gtk::Expander::builder()
+        .label(format!("{} more from {}", stories.len(), domain))
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+    expander.add_css_class("domain-overflow");
+
+    let stories_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .margin_top(6)
+        .build();
+    expander.set_child(Some(&stories_box));
+
+    // Cards are only built the first time the expander opens
+    expander.connect_expanded_notify(move |expander| {
+        if !expander.is_expanded() || stories_box.first_child().is_some() {
+            return;
+        }
+        for story in stories.iter() {
+            stories_box.append(&create_story_row(story, marker_data.clone()));
+        }
+    });
+
+    expander
+}
+
+/// The lead article's card, followed by an expander listing the story's other
+/// sources when more than one outlet covered it
+fn create_story_row(
+    story: &StoryCluster,
+    marker_data: Option<(MarkerButtons, libshumate::MarkerLayer)>,
+) -> gtk::Box {
+    let card = create_article_row_with_markers(&story.lead, marker_data);
+    if story.others.is_empty() {
+        return card;
+    }
+
+    let story_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .build();
+    story_box.append(&card);
+
+    // Outlets, not articles: one site often runs several takes on a story
+    let label = match story.domains.len() {
+        1 => "1 source".to_string(),
+        count => format!("{} sources", count),
+    };
+    let expander = gtk::Expander::builder()
+        .label(label)
+        .tooltip_text(story.domains.join(", "))
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+    expander.add_css_class("story-sources");
+
+    let sources_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .margin_top(4)
+        .build();
+    for article in story.others.iter() {
+        sources_box.append(&create_story_source_row(article));
+    }
+    expander.set_child(Some(&sources_box));
+
+    let timeline_button = gtk::Button::builder()
+        .icon_name("document-open-recent-symbolic")
+        .tooltip_text("Coverage Timeline")
+        .valign(gtk::Align::Start)
+        .margin_end(12)
+        .build();
+    timeline_button.add_css_class("flat");
+    let (title, terms) = (story.lead.title.clone(), crate::clustering::story_terms(story));
+    let country = story.lead.sourcecountry.clone();
+    timeline_button.connect_clicked(move |button| {
+        let window = button.root().and_downcast::<gtk::Window>();
+        let pairs = crate::timeline::related_pairs(Some(&country), &currency_watchlist());
+        crate::timeline::present_timeline(window.as_ref(), &title, &terms, pairs);
+    });
+
+    let sources_row = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .build();
+    expander.set_hexpand(true);
+    sources_row.append(&expander);
+    sources_row.append(&timeline_button);
+    story_box.append(&sources_row);
+
+    story_box
+}
+
+/// Currency pairs on the Markets page, for annotating story timelines
+fn currency_watchlist() -> Vec<crate::settings::WatchedPair> {
+    SETTINGS.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .map(|settings| settings.borrow().currency_watchlist.clone())
+            .unwrap_or_default()
+    })
+}
+
+/// One line per other article in a story: outlet and headline, opening the article on click
+fn create_story_source_row(article: &GdeltArticle) -> gtk::Button {
+    let row = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .build();
+
+    let domain_label = Label::builder()
+        .label(&article.domain)
+        .xalign(0.0)
+        .width_chars(18)
+        .max_width_chars(18)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build();
+    domain_label.add_css_class("article-domain");
+    row.append(&domain_label);
+
+    let title_label = Label::builder()
+        .label(&article.title)
+        .xalign(0.0)
+        .hexpand(true)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build();
+    row.append(&title_label);
+
+    let button = gtk::Button::builder()
+        .child(&row)
+        .tooltip_text(article.title.as_str())
+        .build();
+    button.add_css_class("flat");
+
+    let url = article.url.clone();
+    button.connect_clicked(move |_| {
+        crate::read_articles::mark_read(&url);
+        crate::links::open(&url);
+    });
+
+    button
+}
+
+/// Country marker buttons keyed by source country
+type MarkerButtons = Rc<RefCell<HashMap<String, gtk::Button>>>;
+
+thread_local! {
+    /// App settings, for preferences read while building cards
+    static SETTINGS: RefCell<Option<Rc<RefCell<Settings>>>> = const { RefCell::new(None) };
+
+    /// Map overlay box holding country popovers pinned as floating panels
+    static PINNED_PANELS: RefCell<Option<gtk::Box>> = const { RefCell::new(None) };
+
+    /// Countries whose popovers are pinned, in the order they were pinned
+    static PINNED_COUNTRIES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+
+    /// List and map panes for switching to the narrow layout
+    static COMPACT_LAYOUT: RefCell<Option<CompactLayout>> = const { RefCell::new(None) };
+
+    /// Articles currently shown in the results list, in display order
+    static CURRENT_ARTICLES: RefCell<Vec<GdeltArticle>> = const { RefCell::new(Vec::new()) };
+
+    /// Marker buttons of the latest fetch, keyed by source country
+    static COUNTRY_MARKERS: RefCell<Option<MarkerButtons>> = const { RefCell::new(None) };
+
+    /// Search text of the latest fetch
+    static CURRENT_QUERY: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Countries pinned as floating panels on the map
+pub fn pinned_countries() -> Vec<String> {
+    PINNED_COUNTRIES.with(|pinned| pinned.borrow().clone())
+}
+
+/// Open the popover of the news marker for a country, ignoring case;
+/// returns false if no marker is shown for it
+pub fn open_country_marker(country: &str) -> bool {
+    let marker_button = COUNTRY_MARKERS.with(|markers| {
+        markers.borrow().as_ref().and_then(|markers| {
+            markers
+                .borrow()
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(country))
+                .map(|(_, button)| button.clone())
+        })
+    });
+
+    match marker_button {
+        Some(button) => {
+            button.emit_clicked();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Highlight the markers of countries picked for comparison
+pub fn set_compare_marks(selection: &[String]) {
+    COUNTRY_MARKERS.with(|markers| {
+        if let Some(markers) = markers.borrow().as_ref() {
+            for (country, button) in markers.borrow().iter() {
+                if selection.contains(country) {
+                    button.add_css_class("compare-selected");
+                } else {
+                    button.remove_css_class("compare-selected");
                 }
             }
         }
+    });
+}
+
+/// Thumbnail preferences for new article cards
+fn thumbnail_settings() -> (ThumbnailSize, ThumbnailFit) {
+    SETTINGS.with(|current| match current.borrow().as_ref() {
+        Some(settings) => {
+            let settings = settings.borrow();
+            (settings.thumbnail_size, settings.thumbnail_fit)
+        }
+        None => (ThumbnailSize::Large, ThumbnailFit::Crop),
+    })
+}
+
+/// Whether searches cover all languages, and whether foreign titles are translated
+pub fn search_settings() -> (bool, bool) {
+    SETTINGS.with(|current| match current.borrow().as_ref() {
+        Some(settings) => {
+            let settings = settings.borrow();
+            (settings.multilingual_search, settings.translate_article_titles)
+        }
+        None => (false, false),
+    })
+}
+
+/// Search text the current results were fetched with; empty for the default feed
+pub fn current_query() -> String {
+    CURRENT_QUERY.with(|current| current.borrow().clone())
+}
+
+/// Snapshot of the articles currently shown in the results list
+pub fn current_articles() -> Vec<GdeltArticle> {
+    CURRENT_ARTICLES.with(|current| current.borrow().clone())
+}
+
+/// A results row along with the values the quick filter chips match against
+struct FilterableRow {
+    row: gtk::Widget,
+    country: String,
+    domain: String,
+}
+
+/// Active quick filter selections; an empty set means "no restriction"
+#[derive(Default)]
+struct QuickFilterState {
+    countries: std::collections::HashSet<String>,
+    domains: std::collections::HashSet<String>,
+}
+
+impl QuickFilterState {
+    fn matches(&self, row: &FilterableRow) -> bool {
+        (self.countries.is_empty() || self.countries.contains(&row.country))
+            && (self.domains.is_empty() || self.domains.contains(&row.domain))
+    }
+}
+
+/// Maximum number of domain chips shown (the most frequent ones)
+const MAX_DOMAIN_CHIPS: usize = 8;
+
+/// Create a bar of toggleable country/domain chips that filter the result rows in place.
+/// Returns None when there is nothing meaningful to filter by.
+fn create_quick_filters(rows: Vec<FilterableRow>) -> Option<gtk::FlowBox> {
+    // Count values so chips can be ordered by frequency
+    let mut country_counts: HashMap<String, usize> = HashMap::new();
+    let mut domain_counts: HashMap<String, usize> = HashMap::new();
+    for row in rows.iter() {
+        if !row.country.is_empty() {
+            *country_counts.entry(row.country.clone()).or_default() += 1;
+        }
+        if !row.domain.is_empty() {
+            *domain_counts.entry(row.domain.clone()).or_default() += 1;
+        }
+    }
+
+    if country_counts.len() < 2 && domain_counts.len() < 2 {
+        return None;
+    }
+
+    let mut countries: Vec<(String, usize)> = country_counts.into_iter().collect();
+    countries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut domains: Vec<(String, usize)> = domain_counts.into_iter().collect();
+    domains.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    domains.truncate(MAX_DOMAIN_CHIPS);
+
+    let filter_bar = gtk::FlowBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .max_children_per_line(30)
+        .column_spacing(4)
+        .row_spacing(4)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(6)
+        .margin_end(6)
+        .build();
+
+    let rows = Rc::new(rows);
+    let state = Rc::new(RefCell::new(QuickFilterState::default()));
+
+    let apply_filters = {
+        let rows = rows.clone();
+        let state = state.clone();
+        move || {
+            let state = state.borrow();
+            for row in rows.iter() {
+                row.row.set_visible(state.matches(row));
+            }
+        }
+    };
+
+    let chips = countries.into_iter().map(|(value, count)| (value, count, true))
+        .chain(domains.into_iter().map(|(value, count)| (value, count, false)));
+
+    for (value, count, is_country) in chips {
+        let chip = gtk::ToggleButton::builder()
+            .label(format!("{} {}", value, count))
+            .tooltip_text(if is_country { "Filter by source country" } else { "Filter by domain" })
+            .build();
+        chip.add_css_class("badge");
+        chip.add_css_class("filter-chip");
+        chip.add_css_class(if is_country { "badge-country" } else { "badge-time" });
+
+        let state = state.clone();
+        let apply_filters = apply_filters.clone();
+        chip.connect_toggled(move |chip| {
+            {
+                let mut state = state.borrow_mut();
+                let set = if is_country { &mut state.countries } else { &mut state.domains };
+                if chip.is_active() {
+                    set.insert(value.clone());
+                } else {
+                    set.remove(&value);
+                }
+            }
+            apply_filters();
+        });
+
+        filter_bar.append(&chip);
     }
+
+    Some(filter_bar)
+}
+
+/// Largest size article thumbnails are decoded at (cards crop to 140px high)
+const THUMBNAIL_MAX_WIDTH: i32 = 720;
+const THUMBNAIL_MAX_HEIGHT: i32 = 480;
+
+/// Double-clicking a thumbnail opens the social images of the shown articles in
+/// the viewer, starting at this one. A single click still opens the article, once
+/// it's clear no second click is coming.
+fn attach_lightbox_gesture(picture: &gtk::Picture, article: &GdeltArticle) {
+    let gesture = gtk::GestureClick::new();
+    let pending_open: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+    let article = article.clone();
+
+    gesture.connect_pressed(move |gesture, n_press, _, _| {
+        // Keep the card's own click handler from opening the article right away
+        gesture.set_state(gtk::EventSequenceState::Claimed);
+
+        if let Some(source) = pending_open.borrow_mut().take() {
+            source.remove();
+        }
+
+        if n_press >= 2 {
+            let images: Vec<LightboxImage> = current_articles()
+                .into_iter()
+                .filter(|shown| !shown.socialimage.is_empty())
+                .map(|shown| LightboxImage {
+                    url: shown.socialimage,
+                    caption: shown.title,
+                })
+                .collect();
+            let start = images.iter().position(|image| image.url == article.socialimage).unwrap_or(0);
+            crate::lightbox::show(images, start);
+            return;
+        }
+
+        let double_click_time = gtk::Settings::default()
+            .map(|settings| settings.gtk_double_click_time())
+            .unwrap_or(400);
+        let url = article.url.clone();
+        let pending_for_timeout = pending_open.clone();
+        let source = glib::timeout_add_local_once(
+            std::time::Duration::from_millis(double_click_time.max(0) as u64),
+            move || {
+                pending_for_timeout.borrow_mut().take();
+                crate::links::open(&url);
+            },
+        );
+        *pending_open.borrow_mut() = Some(source);
+    });
+
+    picture.add_controller(gesture);
 }
 
 /// Create a compact, modern article widget with vertical layout
@@ -428,54 +1249,36 @@ fn create_article_row_with_markers(
         .build();
     card.add_css_class("news-article-card");
 
-    // Image header (if available)
-    if !article.socialimage.is_empty() {
+    // Image header (if available and not turned off)
+    let (thumbnail_size, thumbnail_fit) = thumbnail_settings();
+    if !article.socialimage.is_empty() && thumbnail_size != ThumbnailSize::Off {
+        // Height comes from the stylesheet so the size preference and layout config can change it
         let picture = gtk::Picture::builder()
-            .height_request(140)
             .width_request(0)
             .hexpand(true)
             .can_shrink(true)
-            .content_fit(gtk::ContentFit::Cover)
+            .content_fit(match thumbnail_fit {
+                ThumbnailFit::Crop => gtk::ContentFit::Cover,
+                ThumbnailFit::Letterbox => gtk::ContentFit::Contain,
+            })
             .visible(false)
             .build();
         picture.add_css_class("article-thumbnail");
+        attach_lightbox_gesture(&picture, article);
 
         card.append(&picture);
 
-        // Load image from URL asynchronously with better error handling
+        // Load image from URL asynchronously; decoding and downscaling to
+        // roughly the card size happen on a worker thread
         let url = article.socialimage.clone();
         let picture_clone = picture.clone();
         glib::spawn_future_local(async move {
-            // Create client with timeout
-            if let Ok(client) = reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(15))
-                .connect_timeout(std::time::Duration::from_secs(5))
-                .build()
-            {
-                match client.get(&url).send().await {
-                    Ok(response) => {
-                        if response.status().is_success() {
-                            match response.bytes().await {
-                                Ok(bytes) => {
-                                    let bytes_vec = bytes.to_vec();
-                                    let glib_bytes = glib::Bytes::from_owned(bytes_vec);
-                                    if let Ok(texture) = gtk::gdk::Texture::from_bytes(&glib_bytes) {
-                                        picture_clone.set_paintable(Some(&texture));
-                                        picture_clone.set_visible(true);
-                                    }
-                                }
-                                Err(e) => {
-                                    eprintln!("Failed to read image bytes for {}: {}", url, e);
-                                }
-                            }
-                        } else {
-                            eprintln!("HTTP error loading image {}: {}", url, response.status());
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to fetch image {}: {}", url, e);
-                    }
+            match crate::images::load_texture(&url, THUMBNAIL_MAX_WIDTH, THUMBNAIL_MAX_HEIGHT).await {
+                Ok(texture) => {
+                    picture_clone.set_paintable(Some(&texture));
+                    picture_clone.set_visible(true);
                 }
+                Err(e) => tracing::debug!("Thumbnail {}: {}", url, e),
             }
         });
     }
@@ -522,9 +1325,9 @@ fn create_article_row_with_markers(
             country_button.connect_clicked(move |_| {
                 if let Some(marker_button) = marker_buttons_map.borrow().get(&country_code) {
                     marker_button.emit_by_name::<()>("clicked", &[]);
-                    eprintln!("Triggered map marker for {}", country_code);
+                    tracing::debug!("Triggered map marker for {}", country_code);
                 } else {
-                    eprintln!("No marker found for country: {}", country_code);
+                    tracing::debug!("No marker found for country: {}", country_code);
                 }
             });
         }
@@ -551,64 +1354,398 @@ fn create_article_row_with_markers(
         lang_badge.add_css_class("badge");
         lang_badge.add_css_class("badge-lang");
         badges_box.append(&lang_badge);
+
+        // GDELT reports language names, so let the provider detect the source
+        let translate_button = crate::translate::create_translate_button(&title_label, None);
+        badges_box.append(&translate_button);
+        if search_settings().1 && crate::translate::is_configured() {
+            translate_button.emit_clicked();
+        }
+    }
+
+    // Score from an imported source rating list
+    if let Some(credibility_badge) = crate::credibility::create_badge(&article.domain) {
+        badges_box.append(&credibility_badge);
+    }
+
+    // Shown once the article has been opened, here or in an earlier session
+    let read = crate::read_articles::is_read(&article.url);
+    let read_badge = gtk::Label::builder()
+        .label("Read")
+        .visible(read)
+        .build();
+    read_badge.add_css_class("badge");
+    read_badge.add_css_class("badge-neutral");
+    badges_box.append(&read_badge);
+    if read {
+        card.add_css_class("article-read");
     }
 
+    // Bookmark star, pushed to the end of the row
+    let star_button = crate::bookmarks::create_star_button(crate::bookmarks::Bookmark::new(
+        crate::bookmarks::BookmarkKind::Article,
+        &article.url,
+        &article.title,
+        &article.domain,
+    ));
+    star_button.set_hexpand(true);
+    star_button.set_halign(gtk::Align::End);
+    badges_box.append(&star_button);
+
+    // Split of firehose posts about the story
+    let watch_button = gtk::Button::builder()
+        .icon_name("view-reveal-symbolic")
+        .tooltip_text("Watch on Bluesky")
+        .build();
+    watch_button.add_css_class("flat");
+    let story = (article.title.clone(), article.domain.clone());
+    watch_button.connect_clicked(move |button| {
+        if let Err(e) = button.activate_action("app.watch-story", Some(&story.to_variant())) {
+            tracing::warn!("Failed to watch {}: {}", story.0, e);
+        }
+    });
+    badges_box.append(&watch_button);
+
     content_box.append(&badges_box);
 
-    // Domain footer
-    if !article.domain.is_empty() {
-        let domain_label = Label::builder()
-            .label(&article.domain)
-            .xalign(0.0)
-            .ellipsize(gtk::pango::EllipsizeMode::End)
-            .build();
-        domain_label.add_css_class("article-domain");
-        content_box.append(&domain_label);
-    }
+    // Themes and names in the headline, each a search of its own
+    if let Some(theme_badges) = crate::themes::create_badges(&article.title) {
+        content_box.append(&theme_badges);
+    }
+
+    // Domain footer
+    if !article.domain.is_empty() {
+        let domain_label = Label::builder()
+            .label(&article.domain)
+            .xalign(0.0)
+            .ellipsize(gtk::pango::EllipsizeMode::End)
+            .build();
+        domain_label.add_css_class("article-domain");
+        content_box.append(&domain_label);
+    }
+
+    card.append(&content_box);
+
+    // Make the entire card clickable to open article
+    let gesture = gtk::GestureClick::new();
+    let url = article.url.clone();
+    let card_for_read = card.downgrade();
+    gesture.connect_released(move |_, _, _, _| {
+        crate::read_articles::mark_read(&url);
+        if let Some(card) = card_for_read.upgrade() {
+            card.add_css_class("article-read");
+        }
+        read_badge.set_visible(true);
+        crate::links::open(&url);
+    });
+    card.add_controller(gesture);
+    crate::share::attach_article_menu(&card, &article.title, &article.url);
+
+    // Add hover styling
+    card.add_css_class("activatable");
+
+    // Show the article excerpt in a rich tooltip, fetched on first hover
+    attach_excerpt_tooltip(&card, article);
+
+    card
+}
+
+thread_local! {
+    /// Article excerpts by URL; None while the fetch is in flight, empty when nothing was found
+    static EXCERPT_CACHE: RefCell<HashMap<String, Option<String>>> = RefCell::new(HashMap::new());
+}
+
+/// GDELT's artlist mode carries no excerpt, so the page's own description
+/// (OpenGraph/meta tags) is fetched lazily the first time the card is hovered
+fn attach_excerpt_tooltip(card: &gtk::Box, article: &GdeltArticle) {
+    card.set_has_tooltip(true);
+
+    let url = article.url.clone();
+    let title = article.title.clone();
+    card.connect_query_tooltip(move |card, _x, _y, _keyboard_mode, tooltip| {
+        let cached = EXCERPT_CACHE.with(|cache| cache.borrow().get(&url).cloned());
+
+        match cached {
+            Some(Some(excerpt)) if excerpt.is_empty() => false,
+            Some(Some(excerpt)) => {
+                tooltip.set_custom(Some(&create_excerpt_tooltip(&title, &excerpt)));
+                true
+            }
+            Some(None) => {
+                tooltip.set_text(Some("Loading preview…"));
+                true
+            }
+            None => {
+                // Mark as in flight and fetch the page metadata
+                EXCERPT_CACHE.with(|cache| cache.borrow_mut().insert(url.clone(), None));
+
+                let url_clone = url.clone();
+                let card_weak = card.downgrade();
+                glib::spawn_future_local(async move {
+                    let excerpt = match crate::opengraph::fetch_page_metadata(&url_clone).await {
+                        Ok(metadata) => metadata.description,
+                        Err(e) => {
+                            tracing::warn!("No preview for {}: {}", url_clone, e);
+                            String::new()
+                        }
+                    };
+
+                    let found = !excerpt.is_empty();
+                    EXCERPT_CACHE.with(|cache| cache.borrow_mut().insert(url_clone, Some(excerpt)));
+
+                    // Refresh the tooltip if the pointer is still over the card
+                    if found {
+                        if let Some(card) = card_weak.upgrade() {
+                            card.trigger_tooltip_query();
+                        }
+                    }
+                });
+
+                tooltip.set_text(Some("Loading preview…"));
+                true
+            }
+        }
+    });
+}
+
+fn create_excerpt_tooltip(title: &str, excerpt: &str) -> gtk::Box {
+    let tooltip_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .build();
+
+    let title_label = Label::builder()
+        .label(title)
+        .xalign(0.0)
+        .wrap(true)
+        .max_width_chars(50)
+        .build();
+    title_label.add_css_class("heading");
+    tooltip_box.append(&title_label);
+
+    let excerpt_label = Label::builder()
+        .label(excerpt)
+        .xalign(0.0)
+        .wrap(true)
+        .max_width_chars(50)
+        .lines(6)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build();
+    tooltip_box.append(&excerpt_label);
+
+    tooltip_box
+}
+
+fn parse_gdelt_timestamp(timestamp: &str) -> String {
+    // GDELT format: 20251024T074500Z (YYYYMMDDTHHMMSSZ)
+    if timestamp.len() < 15 {
+        return timestamp.to_string();
+    }
+
+    // Parse the timestamp
+    if let Ok(dt) = NaiveDateTime::parse_from_str(timestamp, "%Y%m%dT%H%M%SZ") {
+        format_time_ago(dt)
+    } else {
+        // Fallback if parsing fails
+        timestamp.to_string()
+    }
+}
+
+/// Format a UTC timestamp relative to now ("3 hours ago")
+pub fn format_time_ago(dt: NaiveDateTime) -> String {
+    let now = chrono::Utc::now().naive_utc();
+    let duration = now.signed_duration_since(dt);
+
+    if duration.num_days() > 0 {
+        format!("{} days ago", duration.num_days())
+    } else if duration.num_hours() > 0 {
+        format!("{} hours ago", duration.num_hours())
+    } else if duration.num_minutes() > 0 {
+        format!("{} minutes ago", duration.num_minutes())
+    } else {
+        "Just now".to_string()
+    }
+}
+
+/// Fill the popover header with the country's flag, population and Wikipedia summary
+pub async fn load_country_briefing(briefing_box: gtk::Box, flag_picture: gtk::Picture, country: String) {
+    let briefing = match crate::wikipedia::country_briefing(&country).await {
+        Ok(briefing) => briefing,
+        Err(e) => {
+            // The popover is complete without it, so this isn't worth a toast
+            tracing::warn!("No Wikipedia briefing for {}: {}", country, e);
+            return;
+        }
+    };
+
+    if !briefing.description.is_empty() {
+        let description_label = Label::builder()
+            .label(&briefing.description)
+            .xalign(0.0)
+            .wrap(true)
+            .build();
+        description_label.add_css_class("heading");
+        briefing_box.append(&description_label);
+    }
+
+    if let Some(population) = briefing.population {
+        let population_text = match briefing.population_year {
+            Some(year) => format!(
+                "Population {} ({})",
+                crate::wikipedia::format_population(population),
+                year
+            ),
+            None => format!("Population {}", crate::wikipedia::format_population(population)),
+        };
+        let population_label = Label::builder()
+            .label(population_text)
+            .xalign(0.0)
+            .build();
+        population_label.add_css_class("caption");
+        briefing_box.append(&population_label);
+    }
+
+    let extract_label = Label::builder()
+        .label(&briefing.extract)
+        .xalign(0.0)
+        .wrap(true)
+        .wrap_mode(gtk::pango::WrapMode::WordChar)
+        .lines(4)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .max_width_chars(40)
+        .tooltip_text(briefing.extract.as_str())
+        .build();
+    extract_label.add_css_class("country-briefing-extract");
+    briefing_box.append(&extract_label);
+
+    if !briefing.page_url.is_empty() {
+        let wikipedia_button = gtk::LinkButton::builder()
+            .label("Read more on Wikipedia")
+            .uri(&briefing.page_url)
+            .halign(gtk::Align::Start)
+            .build();
+        wikipedia_button.add_css_class("caption");
+        let page_url = briefing.page_url.clone();
+        wikipedia_button.connect_activate_link(move |_| {
+            crate::links::open(&page_url);
+            glib::Propagation::Stop
+        });
+        briefing_box.append(&wikipedia_button);
+    }
+
+    briefing_box.set_visible(true);
+
+    if let Some(flag_url) = briefing.flag_url {
+        match crate::images::load_texture(&flag_url, 96, 64).await {
+            Ok(texture) => {
+                flag_picture.set_paintable(Some(&texture));
+                flag_picture.set_visible(true);
+            }
+            Err(e) => tracing::warn!("No flag for {}: {}", country, e),
+        }
+    }
+}
+
+/// Fill a popover currency section with a pair's rate, offering a retry if the fetch fails
+async fn load_currency_section(currency_box: gtk::Box, base: String, target: String) {
+    let currency_info = match fetch_currency_pair(&base, &target).await {
+        Ok(currency_info) => currency_info,
+        Err(e) => {
+            tracing::warn!("No currency rates for {}/{}: {}", base, target, e);
+            crate::toasts::show_error(&e, move || {
+                glib::spawn_future_local(load_currency_section(currency_box.clone(), base.clone(), target.clone()));
+            });
+            return;
+        }
+    };
+
+    // Currency header with rate and last updated timestamp
+    let currency_header = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .build();
+
+    let currency_label = Label::builder()
+        .label(format!("{} to {}", currency_info.code, currency_info.target))
+        .xalign(0.0)
+        .hexpand(true)
+        .build();
+    currency_label.add_css_class("title-4");
+
+    currency_header.append(&currency_label);
+
+    // Add last updated timestamp (right-justified)
+    let updated_label = Label::builder()
+        .label(&format!("Updated: {}", chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")))
+        .xalign(1.0)
+        .build();
+    updated_label.add_css_class("dim-label");
+    updated_label.add_css_class("caption");
+    currency_header.append(&updated_label);
 
-    card.append(&content_box);
+    currency_box.append(&currency_header);
 
-    // Make the entire card clickable to open article
-    let gesture = gtk::GestureClick::new();
-    let url = article.url.clone();
-    gesture.connect_released(move |_, _, _, _| {
-        if let Err(e) = open::that(&url) {
-            eprintln!("Failed to open URL: {}", e);
-        }
-    });
-    card.add_controller(gesture);
+    // Rate display with 24hr change indicator
+    let rate_box = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .build();
 
-    // Add hover styling
-    card.add_css_class("activatable");
+    let rate_label = Label::builder()
+        .label(format_rate(currency_info.rate))
+        .xalign(0.0)
+        .build();
+    rate_label.add_css_class("title-3");
+    rate_label.add_css_class("currency-rate");
 
-    card
-}
+    rate_box.append(&rate_label);
 
-fn parse_gdelt_timestamp(timestamp: &str) -> String {
-    // GDELT format: 20251024T074500Z (YYYYMMDDTHHMMSSZ)
-    if timestamp.len() < 15 {
-        return timestamp.to_string();
+    // Add colored 24hr change next to rate
+    if let Some(change_24h) = currency_info.change_24h {
+        let change_label = Label::builder()
+            .label(&format!("({}{:.2}%)",
+                if change_24h > 0.0 { "+" } else { "" },
+                change_24h))
+            .build();
+        change_label.add_css_class("title-4");
+        if change_24h > 0.0 {
+            change_label.add_css_class("currency-change-positive");
+        } else if change_24h < 0.0 {
+            change_label.add_css_class("currency-change-negative");
+        }
+        // If change_24h == 0.0, don't add any color class (default color)
+        rate_box.append(&change_label);
     }
 
-    // Parse the timestamp
-    if let Ok(dt) = NaiveDateTime::parse_from_str(timestamp, "%Y%m%dT%H%M%SZ") {
-        // Calculate time ago
-        let now = chrono::Utc::now().naive_utc();
-        let duration = now.signed_duration_since(dt);
-
-        if duration.num_days() > 0 {
-            format!("{} days ago", duration.num_days())
-        } else if duration.num_hours() > 0 {
-            format!("{} hours ago", duration.num_hours())
-        } else if duration.num_minutes() > 0 {
-            format!("{} minutes ago", duration.num_minutes())
+    currency_box.append(&rate_box);
+
+    // 14-day change badge
+    if let Some(change_7d) = currency_info.change_7d {
+        let change_7d_badge = Label::builder()
+            .label(&format!("14d: {}{:.2}%",
+                if change_7d > 0.0 { "+" } else { "" },
+                change_7d))
+            .build();
+        change_7d_badge.add_css_class("badge");
+        if change_7d > 0.0 {
+            change_7d_badge.add_css_class("badge-positive");
+        } else if change_7d < 0.0 {
+            change_7d_badge.add_css_class("badge-negative");
         } else {
-            "Just now".to_string()
+            // Neutral - no change
+            change_7d_badge.add_css_class("badge-neutral");
         }
-    } else {
-        // Fallback if parsing fails
-        timestamp.to_string()
+        currency_box.append(&change_7d_badge);
+    }
+
+    // Simple sparkline visualization
+    if !currency_info.trend_data.is_empty() {
+        let sparkline = create_sparkline(&currency_info.trend_data);
+        currency_box.append(&sparkline);
     }
+
+    // Show the currency box
+    currency_box.set_visible(true);
 }
 
 /// Create a marker for a country with a popover showing articles
@@ -621,7 +1758,7 @@ fn create_country_marker(
     marker_buttons_map: Rc<RefCell<HashMap<String, gtk::Button>>>,
     use_12_hour: Rc<RefCell<bool>>,
 ) {
-    eprintln!("  Creating marker button for {}", country_code);
+    tracing::debug!("  Creating marker button for {}", country_code);
 
     // Create a more compact label - use abbreviated names for long countries
     let display_name = match country_code {
@@ -640,6 +1777,9 @@ fn create_country_marker(
         .label(&format!("{} {}", display_name, articles.len()))
         .build();
     marker_button.add_css_class("map-marker");
+    if crate::compare::is_selected(country_code) {
+        marker_button.add_css_class("compare-selected");
+    }
 
     // Store the button in the map for later access from article widgets
     marker_buttons_map.borrow_mut().insert(country_code.to_string(), marker_button.clone());
@@ -671,6 +1811,17 @@ fn create_country_marker(
         .spacing(8)
         .build();
 
+    // Filled in with the briefing from Wikipedia
+    let flag_picture = gtk::Picture::builder()
+        .content_fit(gtk::ContentFit::Contain)
+        .can_shrink(true)
+        .width_request(32)
+        .height_request(22)
+        .visible(false)
+        .build();
+    flag_picture.add_css_class("country-flag");
+    country_time_row.append(&flag_picture);
+
     let country_label = Label::builder()
         .label(country_code)
         .xalign(0.0)
@@ -688,6 +1839,27 @@ fn create_country_marker(
     time_label.add_css_class("dim-label");
     country_time_row.append(&time_label);
 
+    // Pin toggle detaches the popover content into a floating panel on the map
+    let pin_button = gtk::ToggleButton::builder()
+        .icon_name("view-pin-symbolic")
+        .tooltip_text("Pin as floating panel")
+        .build();
+    pin_button.add_css_class("flat");
+    country_time_row.append(&pin_button);
+
+    // Pick the country for a side-by-side comparison with others
+    let compare_button = gtk::ToggleButton::builder()
+        .icon_name("view-dual-symbolic")
+        .tooltip_text("Add to comparison")
+        .active(crate::compare::is_selected(country_code))
+        .build();
+    compare_button.add_css_class("flat");
+    let country_for_compare = country_code.to_string();
+    compare_button.connect_toggled(move |button| {
+        crate::compare::set_selected(&country_for_compare, button.is_active());
+    });
+    country_time_row.append(&compare_button);
+
     header_box.append(&country_time_row);
 
     let articles_count_label = Label::builder()
@@ -698,8 +1870,30 @@ fn create_country_marker(
     articles_count_label.add_css_class("caption");
     header_box.append(&articles_count_label);
 
+    let briefing_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .visible(false)
+        .build();
+    briefing_box.add_css_class("country-briefing");
+    header_box.append(&briefing_box);
+
     popover_box.append(&header_box);
 
+    // The briefing is only fetched the first time the country is opened, in a popover or a pinned panel
+    let briefing_requested = std::cell::Cell::new(false);
+    let country_for_briefing = country_code.to_string();
+    popover_box.connect_map(move |_| {
+        if briefing_requested.replace(true) {
+            return;
+        }
+        crate::startup::schedule(
+            Priority::Low,
+            "Country briefing",
+            load_country_briefing(briefing_box.clone(), flag_picture.clone(), country_for_briefing.clone()),
+        );
+    });
+
     // Set up timezone and time update
     if let Some(tz_str) = get_country_timezone(country_code) {
         if let Ok(tz) = tz_str.parse::<chrono_tz::Tz>() {
@@ -725,115 +1919,45 @@ fn create_country_marker(
         }
     }
 
-    // Currency section placeholder (will be populated asynchronously)
-    let currency_box = gtk::Box::builder()
-        .orientation(Orientation::Vertical)
-        .spacing(6)
-        .visible(false)
-        .build();
-    currency_box.add_css_class("popover-currency-section");
-
-    popover_box.append(&currency_box);
-
-    // Load currency data asynchronously
-    if let Some(currency_code) = get_country_currency(country_code) {
-        let currency_box_clone = currency_box.clone();
-        let currency_code = currency_code.to_string();
-        glib::spawn_future_local(async move {
-            if let Some(currency_info) = fetch_currency_info(&currency_code).await {
-                // Currency header with rate and last updated timestamp
-                let currency_header = gtk::Box::builder()
-                    .orientation(Orientation::Horizontal)
-                    .spacing(8)
-                    .build();
-
-                let currency_label = Label::builder()
-                    .label(&if currency_info.code == "USD" {
-                        "EUR to USD".to_string()
-                    } else {
-                        format!("{} to USD", currency_info.code)
-                    })
-                    .xalign(0.0)
-                    .hexpand(true)
-                    .build();
-                currency_label.add_css_class("title-4");
-
-                currency_header.append(&currency_label);
-
-                // Add last updated timestamp (right-justified)
-                let updated_label = Label::builder()
-                    .label(&format!("Updated: {}", chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")))
-                    .xalign(1.0)
-                    .build();
-                updated_label.add_css_class("dim-label");
-                updated_label.add_css_class("caption");
-                currency_header.append(&updated_label);
-
-                currency_box_clone.append(&currency_header);
-
-                // Rate display with 24hr change indicator
-                let rate_box = gtk::Box::builder()
-                    .orientation(Orientation::Horizontal)
-                    .spacing(8)
-                    .build();
-
-                let rate_label = Label::builder()
-                    .label(&format!("{:.4}", currency_info.rate_to_usd))
-                    .xalign(0.0)
-                    .build();
-                rate_label.add_css_class("title-3");
-                rate_label.add_css_class("currency-rate");
-
-                rate_box.append(&rate_label);
-
-                // Add colored 24hr change next to rate
-                if let Some(change_24h) = currency_info.change_24h {
-                    let change_label = Label::builder()
-                        .label(&format!("({}{:.2}%)",
-                            if change_24h > 0.0 { "+" } else { "" },
-                            change_24h))
-                        .build();
-                    change_label.add_css_class("title-4");
-                    if change_24h > 0.0 {
-                        change_label.add_css_class("currency-change-positive");
-                    } else if change_24h < 0.0 {
-                        change_label.add_css_class("currency-change-negative");
-                    }
-                    // If change_24h == 0.0, don't add any color class (default color)
-                    rate_box.append(&change_label);
-                }
-
-                currency_box_clone.append(&rate_box);
-
-                // 14-day change badge
-                if let Some(change_7d) = currency_info.change_7d {
-                    let change_7d_badge = Label::builder()
-                        .label(&format!("14d: {}{:.2}%",
-                            if change_7d > 0.0 { "+" } else { "" },
-                            change_7d))
-                        .build();
-                    change_7d_badge.add_css_class("badge");
-                    if change_7d > 0.0 {
-                        change_7d_badge.add_css_class("badge-positive");
-                    } else if change_7d < 0.0 {
-                        change_7d_badge.add_css_class("badge-negative");
-                    } else {
-                        // Neutral - no change
-                        change_7d_badge.add_css_class("badge-neutral");
-                    }
-                    currency_box_clone.append(&change_7d_badge);
-                }
-
-                // Simple sparkline visualization
-                if !currency_info.trend_data.is_empty() {
-                    let sparkline = create_sparkline(&currency_info.trend_data);
-                    currency_box_clone.append(&sparkline);
-                }
+    // Currency sections, populated asynchronously behind the articles and the stream:
+    // the country's currency, and coin prices in it when the search is about crypto
+    let currency = get_country_currency(country_code);
+    let mut pairs: Vec<(&str, &str)> = currency.map(country_currency_pair).into_iter().collect();
+    if crate::crypto::is_crypto_query(&current_query()) {
+        let priced_in = currency.unwrap_or("USD");
+        pairs.extend(crate::crypto::COINS.iter().map(|(coin, _)| (*coin, priced_in)));
+    }
+    for (base, target) in pairs {
+        let currency_box = gtk::Box::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .visible(false)
+            .build();
+        currency_box.add_css_class("popover-currency-section");
+        popover_box.append(&currency_box);
+
+        crate::startup::schedule(
+            Priority::Low,
+            "Currency rates",
+            load_currency_section(currency_box, base.to_string(), target.to_string()),
+        );
+    }
 
-                // Show the currency box
-                currency_box_clone.set_visible(true);
-            }
-        });
+    // Inflation and interest rates from the World Bank, under the currency
+    if let Some(country) = crate::coordinates::lookup_country(country_code) {
+        let economy_box = gtk::Box::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .visible(false)
+            .build();
+        economy_box.add_css_class("popover-currency-section");
+        popover_box.append(&economy_box);
+
+        crate::startup::schedule(
+            Priority::Low,
+            "Economic indicators",
+            crate::economy::load_section(economy_box, country.code.clone()),
+        );
     }
 
     // Separator
@@ -844,13 +1968,16 @@ fn create_country_marker(
         .build();
     popover_box.append(&separator);
 
-    // Articles section header
-    let news_header = Label::builder()
-        .label("Recent News")
-        .xalign(0.0)
+    // News and firehose posts share the lower half of the popover as tabs
+    let tabs = gtk::Stack::builder()
+        .transition_type(gtk::StackTransitionType::Crossfade)
         .build();
-    news_header.add_css_class("title-4");
-    popover_box.append(&news_header);
+
+    let tab_switcher = gtk::StackSwitcher::builder()
+        .stack(&tabs)
+        .halign(gtk::Align::Start)
+        .build();
+    popover_box.append(&tab_switcher);
 
     // Create a scrolled window for the articles
     let scrolled = ScrolledWindow::builder()
@@ -873,24 +2000,160 @@ fn create_country_marker(
     let mut sorted_articles = articles.to_vec();
     sorted_articles.sort_by(|a, b| b.seendate.cmp(&a.seendate));
 
+    // A digest of the country's coverage, when a summary server is set up
+    if let Some(summary) = crate::summarize::create_summary_section(crate::summarize::SummaryRequest::Country {
+        country: country_code.to_string(),
+        articles: sorted_articles.clone(),
+    }) {
+        articles_box.append(&summary);
+    }
+
     // Add each article to the popover - limit to 8 most recent
-    eprintln!("  Adding {} articles to popover for {}", sorted_articles.len(), country_code);
+    tracing::debug!("  Adding {} articles to popover for {}", sorted_articles.len(), country_code);
     for article in sorted_articles.iter().take(8) {
         let article_widget = create_popover_article_row(article);
         articles_box.append(&article_widget);
     }
 
+    // The popover only has room for the latest few; the country page lists them all and searches for more
+    let see_all_button = gtk::Button::builder()
+        .label(if sorted_articles.len() > 8 {
+            format!("See All {} Articles", sorted_articles.len())
+        } else {
+            format!("More from {}", country_code)
+        })
+        .halign(gtk::Align::Center)
+        .margin_top(4)
+        .build();
+    see_all_button.add_css_class("flat");
+    let country_for_page = country_code.to_string();
+    let popover_for_page = popover.clone();
+    let use_12_hour_for_page = use_12_hour.clone();
+    see_all_button.connect_clicked(move |_| {
+        popover_for_page.popdown();
+        crate::country_page::show_country(&country_for_page, &sorted_articles, use_12_hour_for_page.clone());
+    });
+    articles_box.append(&see_all_button);
+
     scrolled.set_child(Some(&articles_box));
-    popover_box.append(&scrolled);
+    tabs.add_titled(&scrolled, Some("news"), "Recent News");
+
+    // Languages and outlets covering the country
+    let sources_scrolled = ScrolledWindow::builder()
+        .min_content_height(120)
+        .max_content_height(280)
+        .max_content_width(320)
+        .propagate_natural_width(true)
+        .vscrollbar_policy(gtk::PolicyType::Automatic)
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .vexpand(true)
+        .build();
+    sources_scrolled.set_child(Some(&crate::country_breakdown::create_breakdown(country_code, articles)));
+    tabs.add_titled(&sources_scrolled, Some("sources"), "Sources");
+
+    // Firehose posts mentioning the country, reloaded from the rolling index each time the tab opens
+    let posts_scrolled = ScrolledWindow::builder()
+        .min_content_height(120)
+        .max_content_height(280)
+        .max_content_width(320)
+        .propagate_natural_width(true)
+        .vscrollbar_policy(gtk::PolicyType::Automatic)
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .vexpand(true)
+        .build();
+
+    let posts_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .build();
+    posts_scrolled.set_child(Some(&posts_box));
+    tabs.add_titled(&posts_scrolled, Some("posts"), "Posts");
+
+    let country_for_posts = country_code.to_string();
+    tabs.connect_visible_child_name_notify(move |tabs| {
+        if tabs.visible_child_name().as_deref() != Some("posts") {
+            return;
+        }
+
+        while let Some(child) = posts_box.first_child() {
+            posts_box.remove(&child);
+        }
+
+        let posts = crate::country_mentions::recent_posts(&country_for_posts);
+        if posts.is_empty() {
+            let empty_label = Label::builder()
+                .label("No recent firehose posts mention this country")
+                .wrap(true)
+                .margin_top(12)
+                .build();
+            empty_label.add_css_class("dim-label");
+            empty_label.add_css_class("caption");
+            posts_box.append(&empty_label);
+        }
+
+        for post in posts.iter().take(15) {
+            posts_box.append(&create_popover_post_row(post));
+        }
+    });
+
+    popover_box.append(&tabs);
 
     popover.set_child(Some(&popover_box));
 
-    // Connect button click to show popover
+    // Move the content between the popover and a pinned panel
+    let pinned_panel: Rc<RefCell<Option<gtk::Box>>> = Rc::new(RefCell::new(None));
+    let popover_for_pin = popover.clone();
+    let popover_box_for_pin = popover_box.clone();
+    let country_for_pin = country_code.to_string();
+    pin_button.connect_toggled(move |button| {
+        PINNED_COUNTRIES.with(|pinned| {
+            let mut pinned = pinned.borrow_mut();
+            pinned.retain(|country| *country != country_for_pin);
+            if button.is_active() {
+                pinned.push(country_for_pin.clone());
+            }
+        });
+
+        if button.is_active() {
+            popover_for_pin.popdown();
+            popover_for_pin.set_child(None::<&gtk::Widget>);
+
+            let panel = gtk::Box::builder()
+                .orientation(Orientation::Vertical)
+                .width_request(340)
+                .build();
+            panel.add_css_class("pinned-panel");
+            panel.append(&popover_box_for_pin);
+
+            PINNED_PANELS.with(|panels| {
+                if let Some(container) = panels.borrow().as_ref() {
+                    container.append(&panel);
+                }
+            });
+            button.set_tooltip_text(Some("Unpin"));
+            *pinned_panel.borrow_mut() = Some(panel);
+        } else if let Some(panel) = pinned_panel.borrow_mut().take() {
+            panel.remove(&popover_box_for_pin);
+            if let Some(container) = panel.parent().and_downcast::<gtk::Box>() {
+                container.remove(&panel);
+            }
+            popover_for_pin.set_child(Some(&popover_box_for_pin));
+            button.set_tooltip_text(Some("Pin as floating panel"));
+        }
+    });
+
+    // Connect button click to show popover (pinned countries are already visible)
     let country_code_clone = country_code.to_string();
     let popover_clone = popover.clone();
+    let pin_button_clone = pin_button.clone();
+    let compare_button_clone = compare_button.clone();
     marker_button.connect_clicked(move |_| {
-        eprintln!("Marker clicked for {}", country_code_clone);
-        popover_clone.popup();
+        tracing::debug!("Marker clicked for {}", country_code_clone);
+        // The comparison may have been cleared since the popover was last open
+        compare_button_clone.set_active(crate::compare::is_selected(&country_code_clone));
+        if !pin_button_clone.is_active() {
+            popover_clone.popup();
+        }
     });
 
     // Set popover parent after connecting click handler
@@ -907,15 +2170,27 @@ fn create_country_marker(
     marker.set_child(Some(&marker_button));
     marker.set_location(lat, lon);
 
-    eprintln!("  Adding marker to layer for {}", country_code);
+    tracing::debug!("  Adding marker to layer for {}", country_code);
     // Add marker to the layer
     marker_layer.add_marker(&marker);
 
-    eprintln!("  Marker added successfully for {}", country_code);
+    tracing::debug!("  Marker added successfully for {}", country_code);
+}
+
+/// A rate to four decimals, or to more significant digits when it's tiny, as
+/// fiat priced in bitcoin is, and to cents when it's large
+pub fn format_rate(rate: f64) -> String {
+    if rate != 0.0 && rate.abs() < 0.01 {
+        format!("{:.8}", rate)
+    } else if rate.abs() >= 1000.0 {
+        format!("{:.2}", rate)
+    } else {
+        format!("{:.4}", rate)
+    }
 }
 
 /// Create a simple sparkline visualization for currency trend with axis labels
-fn create_sparkline(data: &[f64]) -> gtk::Box {
+pub fn create_sparkline(data: &[f64]) -> gtk::Box {
     let container = gtk::Box::builder()
         .orientation(Orientation::Vertical)
         .spacing(4)
@@ -1035,12 +2310,12 @@ fn create_sparkline(data: &[f64]) -> gtk::Box {
         cr.set_font_size(9.0);
 
         // Max value label (top)
-        let max_text = format!("{:.4}", max);
+        let max_text = format_rate(max);
         cr.move_to(margin_left, margin_top - 2.0);
         let _ = cr.show_text(&max_text);
 
         // Min value label (bottom)
-        let min_text = format!("{:.4}", min);
+        let min_text = format_rate(min);
         cr.move_to(margin_left, margin_top + plot_height + 12.0);
         let _ = cr.show_text(&min_text);
     });
@@ -1108,11 +2383,11 @@ fn create_sparkline(data: &[f64]) -> gtk::Box {
         let days_ago = data_for_tooltip.len() - 1 - closest_idx;
 
         let tooltip_text = if days_ago == 0 {
-            format!("Today: {:.4}", value)
+            format!("Today: {}", format_rate(value))
         } else if days_ago == 1 {
-            format!("Yesterday: {:.4}", value)
+            format!("Yesterday: {}", format_rate(value))
         } else {
-            format!("{} days ago: {:.4}", days_ago, value)
+            format!("{} days ago: {}", days_ago, format_rate(value))
         };
 
         tooltip.set_text(Some(&tooltip_text));
@@ -1134,7 +2409,7 @@ fn create_sparkline(data: &[f64]) -> gtk::Box {
 }
 
 /// Create a compact article row for the popover
-fn create_popover_article_row(article: &GdeltArticle) -> gtk::Box {
+pub fn create_popover_article_row(article: &GdeltArticle) -> gtk::Box {
     let row = gtk::Box::builder()
         .orientation(Orientation::Vertical)
         .spacing(4)
@@ -1195,11 +2470,10 @@ fn create_popover_article_row(article: &GdeltArticle) -> gtk::Box {
     let gesture = gtk::GestureClick::new();
     let url = article.url.clone();
     gesture.connect_released(move |_, _, _, _| {
-        if let Err(e) = open::that(&url) {
-            eprintln!("Failed to open URL: {}", e);
-        }
+        crate::links::open(&url);
     });
     row.add_controller(gesture);
+    crate::share::attach_article_menu(&row, &article.title, &article.url);
 
     // Add hover styling
     row.add_css_class("activatable");
@@ -1207,58 +2481,186 @@ fn create_popover_article_row(article: &GdeltArticle) -> gtk::Box {
     row
 }
 
-/// Fetch currency information from Frankfurter API
+/// Compact firehose post row for the popover's Posts tab; clicking opens it on bsky.app
+fn create_popover_post_row(post: &FirehosePost) -> gtk::Box {
+    let row = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(6)
+        .margin_end(6)
+        .build();
+    row.add_css_class("popover-article-row");
+
+    let text_label = Label::builder()
+        .label(&post.text)
+        .wrap(true)
+        .wrap_mode(gtk::pango::WrapMode::WordChar)
+        .xalign(0.0)
+        .lines(3)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .max_width_chars(45)
+        .width_chars(45)
+        .build();
+    row.append(&text_label);
+
+    let metadata_box = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .build();
+
+    let time_label = Label::builder()
+        .label(&post.timestamp)
+        .xalign(0.0)
+        .build();
+    time_label.add_css_class("popover-article-time");
+    time_label.add_css_class("monospace");
+    metadata_box.append(&time_label);
+    metadata_box.append(&crate::firehose::create_identifier_button(&post.did, &post.rkey));
+    row.append(&metadata_box);
+
+    let uri = format!("at://{}/app.bsky.feed.post/{}", post.did, post.rkey);
+    if let Some(web_url) = crate::appview::post_web_url(&uri) {
+        let gesture = gtk::GestureClick::new();
+        gesture.connect_released(move |_, _, _, _| {
+            if let Err(e) = open::that(&web_url) {
+                tracing::warn!("Failed to open URL: {}", e);
+            }
+        });
+        row.add_controller(gesture);
+        row.add_css_class("activatable");
+    }
+
+    row
+}
+
+/// Fetch a country currency's rate to USD from Frankfurter API, or EUR/USD for the US
 /// Returns currency info with current rate and trend data
-async fn fetch_currency_info(currency_code: &str) -> Option<CurrencyInfo> {
+pub async fn fetch_currency_info(currency_code: &str) -> Result<CurrencyInfo, FetchError> {
+    let (base, target) = country_currency_pair(currency_code);
+    fetch_currency_pair(base, target).await
+}
+
+/// The pair shown for a country's currency: against USD, or EUR/USD for the US
+fn country_currency_pair(currency_code: &str) -> (&str, &str) {
+    if currency_code == "USD" {
+        ("EUR", "USD")
+    } else {
+        (currency_code, "USD")
+    }
+}
+
+/// Frankfurter publishes reference rates once a working day, so fetched rates stay good for a while
+const CURRENCY_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+
+type CurrencyWaiter = flume::Sender<Result<CurrencyInfo, FetchError>>;
+
+/// A currency pair's rates, or the lookups waiting for the request already under way
+enum CachedRates {
+    Fetched { info: CurrencyInfo, fetched_at: std::time::Instant },
+    InFlight(Vec<CurrencyWaiter>),
+}
+
+thread_local! {
+    /// Rates by pair, e.g. "EUR/USD", shared by every popover, comparison and watchlist card
+    static CURRENCY_CACHE: RefCell<HashMap<String, CachedRates>> = RefCell::new(HashMap::new());
+}
+
+/// Forget fetched rates so the next lookups ask Frankfurter again
+pub fn clear_currency_cache() {
+    CURRENCY_CACHE.with(|cache| {
+        cache.borrow_mut().retain(|_, cached| matches!(cached, CachedRates::InFlight(_)));
+    });
+}
+
+/// Fetch the rate of any currency pair from Frankfurter API, with its 14-day trend.
+/// Pairs fetched in the last half hour come from the cache, and lookups made while
+/// a pair is being fetched share that request.
+pub async fn fetch_currency_pair(base_currency: &str, target_currency: &str) -> Result<CurrencyInfo, FetchError> {
+    enum Lookup {
+        Cached(CurrencyInfo),
+        Wait(flume::Receiver<Result<CurrencyInfo, FetchError>>),
+        Fetch,
+    }
+
+    let key = format!("{}/{}", base_currency, target_currency);
+    let lookup = CURRENCY_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        match cache.get_mut(&key) {
+            Some(CachedRates::Fetched { info, fetched_at }) if fetched_at.elapsed() < CURRENCY_CACHE_TTL => {
+                Lookup::Cached(info.clone())
+            }
+            Some(CachedRates::InFlight(waiters)) => {
+                let (tx, rx) = flume::bounded(1);
+                waiters.push(tx);
+                Lookup::Wait(rx)
+            }
+            _ => {
+                cache.insert(key.clone(), CachedRates::InFlight(Vec::new()));
+                Lookup::Fetch
+            }
+        }
+    });
+
+    match lookup {
+        Lookup::Cached(info) => Ok(info),
+        Lookup::Wait(rx) => rx
+            .recv_async()
+            .await
+            .unwrap_or(Err(FetchError::Empty { service: "Frankfurter" })),
+        Lookup::Fetch => {
+            let result = request_currency_pair(base_currency, target_currency).await;
+
+            // Failures aren't cached, so the next lookup tries again
+            let previous = CURRENCY_CACHE.with(|cache| {
+                let mut cache = cache.borrow_mut();
+                match result {
+                    Ok(ref info) => cache.insert(
+                        key,
+                        CachedRates::Fetched { info: info.clone(), fetched_at: std::time::Instant::now() },
+                    ),
+                    Err(_) => cache.remove(&key),
+                }
+            });
+            if let Some(CachedRates::InFlight(waiters)) = previous {
+                for waiter in waiters {
+                    let _ = waiter.send(result.clone());
+                }
+            }
+            result
+        }
+    }
+}
+
+async fn request_currency_pair(base_currency: &str, target_currency: &str) -> Result<CurrencyInfo, FetchError> {
     use crate::data::{FrankfurterLatestResponse, FrankfurterHistoricalResponse};
+    const SERVICE: &str = "Frankfurter";
+
+    // Coins are priced by CoinGecko; Frankfurter only has fiat reference rates
+    if crate::crypto::is_crypto(base_currency) || crate::crypto::is_crypto(target_currency) {
+        return crate::crypto::request_pair(base_currency, target_currency).await;
+    }
 
-    // Create a client with timeout and retry settings
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .connect_timeout(std::time::Duration::from_secs(5))
-        .build()
-        .ok()?;
+    let client = crate::http::client();
 
     // Get today's date and 14 days ago (for better trend visualization)
     let today = chrono::Utc::now().date_naive();
     let fourteen_days_ago = today - chrono::Duration::days(14);
 
-    let (base_currency, target_currency) = if currency_code == "USD" {
-        // When US is selected, show EUR/USD pair
-        ("EUR", "USD")
-    } else {
-        // For other currencies, show currency/USD pair
-        (currency_code, "USD")
-    };
-
     // Fetch latest rate
     let latest_url = format!(
         "https://api.frankfurter.dev/v1/latest?from={}&to={}",
         base_currency, target_currency
     );
 
-    let latest_rate = match client.get(&latest_url).send().await {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.json::<FrankfurterLatestResponse>().await {
-                    Ok(data) => data.rates.rates.get(target_currency).copied(),
-                    Err(e) => {
-                        eprintln!("Failed to parse latest currency data for {}/{}: {}", base_currency, target_currency, e);
-                        None
-                    }
-                }
-            } else {
-                eprintln!("HTTP error fetching latest currency data for {}/{}: {}", base_currency, target_currency, response.status());
-                None
-            }
-        }
-        Err(e) => {
-            eprintln!("Failed to fetch latest currency data for {}/{}: {}", base_currency, target_currency, e);
-            None
-        }
-    };
-
-    let latest_rate = latest_rate?;
+    let latest: FrankfurterLatestResponse = get_json(SERVICE, client.get(&latest_url)).await?;
+    let latest_rate = latest
+        .rates
+        .rates
+        .get(target_currency)
+        .copied()
+        .ok_or(FetchError::Empty { service: SERVICE })?;
 
     // Fetch 14-day historical data for trend with better error handling
     let historical_url = format!(
@@ -1268,57 +2670,47 @@ async fn fetch_currency_info(currency_code: &str) -> Option<CurrencyInfo> {
         base_currency, target_currency
     );
 
-    let (change_24h, change_7d, trend_data) = match client.get(&historical_url).send().await {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.json::<FrankfurterHistoricalResponse>().await {
-                    Ok(data) => {
-                        // Extract rates sorted by date
-                        let mut dates: Vec<_> = data.rates.keys().collect();
-                        dates.sort();
-
-                        let rates: Vec<f64> = dates
-                            .iter()
-                            .filter_map(|date| {
-                                data.rates.get(*date).and_then(|r| r.rates.get(target_currency).copied())
-                            })
-                            .collect();
-
-                        let change_24h = if rates.len() >= 2 {
-                            let yesterday = rates[rates.len() - 2];
-                            Some(((latest_rate - yesterday) / yesterday) * 100.0)
-                        } else {
-                            None
-                        };
-
-                        let change_7d = if !rates.is_empty() {
-                            let week_ago = rates[0];
-                            Some(((latest_rate - week_ago) / week_ago) * 100.0)
-                        } else {
-                            None
-                        };
-
-                        (change_24h, change_7d, rates)
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to parse historical currency data for {}/{}: {}", base_currency, target_currency, e);
-                        (None, None, vec![])
-                    }
-                }
-            } else {
-                eprintln!("HTTP error fetching historical currency data for {}/{}: {}", base_currency, target_currency, response.status());
+    // The trend is optional; the current rate alone is still worth showing
+    let (change_24h, change_7d, trend_data) =
+        match get_json::<FrankfurterHistoricalResponse>(SERVICE, client.get(&historical_url)).await {
+            Ok(data) => {
+                // Extract rates sorted by date
+                let mut dates: Vec<_> = data.rates.keys().collect();
+                dates.sort();
+
+                let rates: Vec<f64> = dates
+                    .iter()
+                    .filter_map(|date| {
+                        data.rates.get(*date).and_then(|r| r.rates.get(target_currency).copied())
+                    })
+                    .collect();
+
+                let change_24h = if rates.len() >= 2 {
+                    let yesterday = rates[rates.len() - 2];
+                    Some(((latest_rate - yesterday) / yesterday) * 100.0)
+                } else {
+                    None
+                };
+
+                let change_7d = if !rates.is_empty() {
+                    let week_ago = rates[0];
+                    Some(((latest_rate - week_ago) / week_ago) * 100.0)
+                } else {
+                    None
+                };
+
+                (change_24h, change_7d, rates)
+            }
+            Err(e) => {
+                tracing::warn!("No currency trend for {}/{}: {}", base_currency, target_currency, e);
                 (None, None, vec![])
             }
-        }
-        Err(e) => {
-            eprintln!("Failed to fetch historical currency data for {}/{}: {}", base_currency, target_currency, e);
-            (None, None, vec![])
-        }
-    };
+        };
 
-    Some(CurrencyInfo {
-        code: currency_code.to_string(),
-        rate_to_usd: latest_rate,
+    Ok(CurrencyInfo {
+        code: base_currency.to_string(),
+        target: target_currency.to_string(),
+        rate: latest_rate,
         change_24h,
         change_7d,
         trend_data,
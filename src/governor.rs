@@ -0,0 +1,70 @@
+use std::time::Instant;
+
+use crate::settings::Settings;
+
+/// Decides which incoming posts get rows, so slow machines can keep up with
+/// the firehose. Posts it turns away are still counted and indexed; they
+/// just aren't rendered.
+pub struct Governor {
+    /// Percent credit towards the next sampled post
+    sample_credit: u32,
+    /// Rows that may still be added before the rate cap kicks in
+    row_budget: f64,
+    last_refill: Instant,
+    /// Posts offered and rendered since the last `take_shown_share`
+    offered: u64,
+    admitted: u64,
+}
+
+impl Governor {
+    pub fn new() -> Self {
+        Self {
+            sample_credit: 0,
+            row_budget: 0.0,
+            last_refill: Instant::now(),
+            offered: 0,
+            admitted: 0,
+        }
+    }
+
+    /// Top the row budget up for the time since the last call; at most one
+    /// second's worth is kept so a pause doesn't release a burst afterwards
+    pub fn refill(&mut self, settings: &Settings) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.last_refill = now;
+
+        let max_rate = settings.max_rows_per_second as f64;
+        self.row_budget = (self.row_budget + elapsed * max_rate).min(max_rate);
+    }
+
+    /// Whether a post should be rendered. Sampling keeps an evenly spread share
+    /// of the stream; the rate cap then drops whatever exceeds the budget.
+    pub fn admit(&mut self, settings: &Settings) -> bool {
+        self.offered += 1;
+
+        self.sample_credit += settings.sample_percent.clamp(1, 100);
+        if self.sample_credit < 100 {
+            return false;
+        }
+        self.sample_credit -= 100;
+
+        if settings.max_rows_per_second > 0 {
+            if self.row_budget < 1.0 {
+                return false;
+            }
+            self.row_budget -= 1.0;
+        }
+
+        self.admitted += 1;
+        true
+    }
+
+    /// Share of offered posts rendered since the last call, None if nothing arrived
+    pub fn take_shown_share(&mut self) -> Option<f64> {
+        let share = (self.offered > 0).then(|| self.admitted as f64 / self.offered as f64);
+        self.offered = 0;
+        self.admitted = 0;
+        share
+    }
+}
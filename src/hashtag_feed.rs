@@ -0,0 +1,282 @@
+use gtk::prelude::*;
+use libadwaita::prelude::*;
+use gtk::{glib, Label, ListBox, Orientation, ScrolledWindow};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::account::{self, Interaction};
+use crate::appview;
+use crate::data::AppViewPost;
+use crate::firehose::create_interaction_button;
+
+/// How often a followed hashtag is searched again
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Posts asked for per search; enough to cover a minute of most tags
+const PAGE_SIZE: u8 = 25;
+/// Rows kept per feed, oldest dropped first
+const MAX_ROWS: usize = 100;
+const AVATAR_SIZE: i32 = 32;
+
+/// "#Climate " → "climate"; Bluesky matches tags without regard to case
+pub fn normalize_tag(text: &str) -> String {
+    text.trim().trim_start_matches('#').trim().to_lowercase()
+}
+
+/// A followed hashtag's column: posts found by searching the AppView, so
+/// unlike the Jetstream rows they come with authors, avatars and counts
+struct HashtagFeed {
+    tag: String,
+    list: ListBox,
+    status: Label,
+    /// URIs of the rows shown, so each search only adds what's new
+    shown: RefCell<HashSet<String>>,
+}
+
+impl HashtagFeed {
+    async fn refresh(self: Rc<Self>) {
+        let posts = match appview::search_hashtag(&self.tag, PAGE_SIZE).await {
+            Ok(posts) => posts,
+            Err(e) => {
+                // Polled every minute, so a toast each time would nag; the column says it instead
+                tracing::warn!("Failed to search #{}: {}", self.tag, e);
+                self.status.set_label(&format!("Couldn't search #{}: {}", self.tag, e));
+                self.status.set_visible(true);
+                return;
+            }
+        };
+
+        // Results are newest first; prepend oldest first so the newest ends on top
+        for post in posts.iter().rev() {
+            if !self.shown.borrow_mut().insert(post.uri.clone()) {
+                continue;
+            }
+            self.list.prepend(&create_feed_row(post));
+        }
+
+        while self.shown.borrow().len() > MAX_ROWS {
+            let Some(last) = self.list.last_child() else {
+                break;
+            };
+            self.shown.borrow_mut().remove(last.widget_name().as_str());
+            self.list.remove(&last);
+        }
+
+        let empty = self.shown.borrow().is_empty();
+        self.status.set_label(&format!("No posts tagged #{} yet", self.tag));
+        self.status.set_visible(empty);
+    }
+}
+
+/// Local time a post was indexed, e.g. "14:02"
+fn format_time(indexed_at: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(indexed_at)
+        .map(|time| time.with_timezone(&chrono::Local).format("%H:%M").to_string())
+        .unwrap_or_default()
+}
+
+pub fn create_feed_row(post: &AppViewPost) -> gtk::ListBoxRow {
+    let content = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+
+    let author = &post.author;
+    let name = match author.display_name {
+        Some(ref name) if !name.is_empty() => name.clone(),
+        _ => author.handle.clone(),
+    };
+
+    let header = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .build();
+
+    let avatar = libadwaita::Avatar::new(AVATAR_SIZE, Some(&name), true);
+    if let Some(url) = author.avatar.clone() {
+        let avatar = avatar.clone();
+        glib::spawn_future_local(async move {
+            match crate::images::load_texture(&url, AVATAR_SIZE * 2, AVATAR_SIZE * 2).await {
+                Ok(texture) => avatar.set_custom_image(Some(&texture)),
+                Err(e) => tracing::warn!("Failed to load avatar: {}", e),
+            }
+        });
+    }
+    header.append(&avatar);
+
+    let names = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .hexpand(true)
+        .valign(gtk::Align::Center)
+        .build();
+    let name_label = Label::builder()
+        .label(&name)
+        .xalign(0.0)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build();
+    name_label.add_css_class("heading");
+    names.append(&name_label);
+    let handle_label = Label::builder()
+        .label(format!("@{}", author.handle))
+        .xalign(0.0)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build();
+    handle_label.add_css_class("caption");
+    handle_label.add_css_class("dim-label");
+    names.append(&handle_label);
+
+    // The author's name leads on to their profile
+    names.set_tooltip_text(Some("Show profile"));
+    names.set_cursor_from_name(Some("pointer"));
+    let did = author.did.clone();
+    let gesture = gtk::GestureClick::new();
+    gesture.connect_released(move |_, _, _, _| crate::profile_page::show(&did));
+    names.add_controller(gesture);
+    header.append(&names);
+
+    let time_label = Label::new(Some(&format_time(&post.indexed_at)));
+    time_label.add_css_class("badge");
+    time_label.add_css_class("badge-time");
+    time_label.set_valign(gtk::Align::Start);
+    header.append(&time_label);
+    content.append(&header);
+
+    let text_label = Label::builder()
+        .label(post.text())
+        .wrap(true)
+        .wrap_mode(gtk::pango::WrapMode::WordChar)
+        .xalign(0.0)
+        .selectable(true)
+        .build();
+    text_label.add_css_class("firehose-text");
+    content.append(&text_label);
+
+    let footer = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    let counts_label = Label::builder()
+        .label(format!("♥ {}  🔁 {}  💬 {}", post.like_count, post.repost_count, post.reply_count))
+        .xalign(0.0)
+        .hexpand(true)
+        .build();
+    counts_label.add_css_class("caption");
+    counts_label.add_css_class("dim-label");
+    footer.append(&counts_label);
+
+    if !post.cid.is_empty() && account::is_configured() {
+        footer.append(&create_interaction_button(Interaction::Like, &post.uri, &post.cid));
+        footer.append(&create_interaction_button(Interaction::Repost, &post.uri, &post.cid));
+    }
+    if let Some(url) = appview::post_web_url(&post.uri) {
+        let open_button = gtk::Button::builder()
+            .icon_name("web-browser-symbolic")
+            .tooltip_text("Open on Bluesky")
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        open_button.connect_clicked(move |_| {
+            if let Err(e) = open::that(&url) {
+                tracing::warn!("Failed to open URL: {}", e);
+            }
+        });
+        footer.append(&open_button);
+    }
+    content.append(&footer);
+
+    let row = gtk::ListBoxRow::builder()
+        .child(&content)
+        .activatable(false)
+        .build();
+    row.add_css_class("hashtag-feed-row");
+    // Read back when trimming, to forget the rows dropped
+    row.set_widget_name(&post.uri);
+    row
+}
+
+/// Body of a followed hashtag's column, searched again every minute while
+/// it's on screen
+pub fn create_feed_body(tag: &str) -> gtk::Box {
+    let body = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(8)
+        .vexpand(true)
+        .build();
+
+    let status = Label::builder()
+        .label(format!("Searching #{}…", tag))
+        .wrap(true)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+    status.add_css_class("dim-label");
+    body.append(&status);
+
+    let list = ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .build();
+    let scrolled = ScrolledWindow::builder()
+        .vexpand(true)
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .child(&list)
+        .build();
+    body.append(&scrolled);
+
+    let feed = Rc::new(HashtagFeed {
+        tag: tag.to_string(),
+        list,
+        status,
+        shown: RefCell::new(HashSet::new()),
+    });
+    glib::spawn_future_local(feed.clone().refresh());
+
+    // The timer holds the only strong reference besides in-flight searches, so
+    // a closed column lets go of its feed on the next tick
+    let body_for_poll = body.downgrade();
+    glib::timeout_add_local(POLL_INTERVAL, move || {
+        let Some(body) = body_for_poll.upgrade() else {
+            return glib::ControlFlow::Break;
+        };
+        if body.root().is_none() {
+            return glib::ControlFlow::Break;
+        }
+        if body.is_mapped() && !crate::focus::is_enabled() {
+            glib::spawn_future_local(feed.clone().refresh());
+        }
+        glib::ControlFlow::Continue
+    });
+
+    body
+}
+
+/// Ask which hashtag to follow and hand it on once confirmed
+pub fn present_follow_dialog(parent: &impl IsA<gtk::Window>, on_follow: impl Fn(&str) + 'static) {
+    let entry = gtk::Entry::builder()
+        .placeholder_text("e.g. #climate")
+        .activates_default(true)
+        .build();
+
+    let dialog = libadwaita::MessageDialog::builder()
+        .transient_for(parent)
+        .modal(true)
+        .heading("Follow Hashtag")
+        .body("Adds a column of the latest posts with the hashtag, with their authors and like counts. It's searched again every minute.")
+        .extra_child(&entry)
+        .build();
+    dialog.add_responses(&[("cancel", "Cancel"), ("follow", "Follow")]);
+    dialog.set_response_appearance("follow", libadwaita::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("follow"));
+    dialog.set_close_response("cancel");
+
+    dialog.connect_response(None, move |_, response| {
+        let tag = normalize_tag(&entry.text());
+        if response == "follow" && !tag.is_empty() {
+            on_follow(&tag);
+        }
+    });
+    dialog.present();
+}
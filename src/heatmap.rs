@@ -0,0 +1,363 @@
+use gtk::prelude::*;
+use gtk::{cairo, glib, Label, Orientation};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::data::GdeltGeoResponse;
+use crate::error::FetchError;
+use crate::gdelt::{self, GdeltQuery, Timespan};
+
+/// Radius of one place's glow at zoom level 1, in pixels; it grows as the map zooms in
+const BASE_RADIUS: f64 = 14.0;
+const RADIUS_PER_ZOOM: f64 = 6.0;
+/// Opacity of the densest part of the heatmap, so the map still shows through
+const MAX_OPACITY: f64 = 0.75;
+
+/// Colour ramp from the faintest to the densest mentions
+const RAMP: &[(f64, (f64, f64, f64))] = &[
+    (0.0, (0.20, 0.40, 1.00)),
+    (0.35, (0.00, 0.85, 0.90)),
+    (0.55, (0.25, 0.85, 0.25)),
+    (0.75, (1.00, 0.85, 0.00)),
+    (1.0, (0.95, 0.15, 0.10)),
+];
+
+const LEGEND_WIDTH: i32 = 140;
+const LEGEND_HEIGHT: i32 = 10;
+
+/// A place mentioned in the coverage and how strongly it shows on the heatmap
+struct Point {
+    name: String,
+    latitude: f64,
+    longitude: f64,
+    /// Article count scaled to 0..1 against the most-mentioned place
+    weight: f64,
+}
+
+/// Density overlay of the places the current search's coverage mentions
+struct Heatmap {
+    drawing_area: gtk::DrawingArea,
+    legend: gtk::Box,
+    legend_caption: Label,
+    map_view: libshumate::Map,
+    viewport: libshumate::Viewport,
+    points: RefCell<Vec<Point>>,
+    enabled: Cell<bool>,
+    /// Search the points are for, and the one most recently asked for
+    query: RefCell<String>,
+}
+
+thread_local! {
+    static HEATMAP: RefCell<Option<Rc<Heatmap>>> = const { RefCell::new(None) };
+}
+
+fn with_heatmap<T>(f: impl FnOnce(&Rc<Heatmap>) -> T) -> Option<T> {
+    HEATMAP.with(|heatmap| heatmap.borrow().as_ref().map(f))
+}
+
+/// Colour at a point along the ramp, from 0 (faint) to 1 (dense)
+fn ramp(t: f64) -> (f64, f64, f64) {
+    let t = t.clamp(0.0, 1.0);
+    for pair in RAMP.windows(2) {
+        let ((start, from), (end, to)) = (pair[0], pair[1]);
+        if t <= end {
+            let f = (t - start) / (end - start);
+            return (
+                from.0 + (to.0 - from.0) * f,
+                from.1 + (to.1 - from.1) * f,
+                from.2 + (to.2 - from.2) * f,
+            );
+        }
+    }
+    RAMP[RAMP.len() - 1].1
+}
+
+impl Heatmap {
+    fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+        self.drawing_area.set_visible(enabled);
+        self.legend.set_visible(enabled && !self.points.borrow().is_empty());
+        if enabled {
+            let query = self.query.borrow().clone();
+            glib::spawn_future_local(load(query));
+        }
+    }
+
+    /// Glows for every place are added up in an alpha-only surface, then the
+    /// summed density is coloured through the ramp
+    fn draw(&self, cr: &cairo::Context, width: i32, height: i32) {
+        let points = self.points.borrow();
+        if points.is_empty() || width <= 0 || height <= 0 {
+            return;
+        }
+        let radius = BASE_RADIUS + RADIUS_PER_ZOOM * (self.viewport.zoom_level() - 1.0).max(0.0);
+
+        let Ok(mut density) = cairo::ImageSurface::create(cairo::Format::A8, width, height) else {
+            return;
+        };
+        {
+            let Ok(dc) = cairo::Context::new(&density) else {
+                return;
+            };
+            dc.set_operator(cairo::Operator::Add);
+            for point in points.iter() {
+                let (x, y) = self
+                    .viewport
+                    .location_to_widget_coords(&self.map_view, point.latitude, point.longitude);
+                if x < -radius || y < -radius || x > width as f64 + radius || y > height as f64 + radius {
+                    continue;
+                }
+                let glow = cairo::RadialGradient::new(x, y, 0.0, x, y, radius);
+                // Even the least-mentioned places stay visible
+                glow.add_color_stop_rgba(0.0, 0.0, 0.0, 0.0, 0.15 + 0.85 * point.weight);
+                glow.add_color_stop_rgba(1.0, 0.0, 0.0, 0.0, 0.0);
+                if dc.set_source(&glow).is_err() {
+                    return;
+                }
+                dc.arc(x, y, radius, 0.0, std::f64::consts::TAU);
+                let _ = dc.fill();
+            }
+        }
+        density.flush();
+        let density_stride = density.stride() as usize;
+        let Ok(density_data) = density.data() else {
+            return;
+        };
+
+        let Ok(mut colored) = cairo::ImageSurface::create(cairo::Format::ARgb32, width, height) else {
+            return;
+        };
+        let stride = colored.stride() as usize;
+        {
+            let Ok(mut pixels) = colored.data() else {
+                return;
+            };
+            for y in 0..height as usize {
+                for x in 0..width as usize {
+                    let value = density_data[y * density_stride + x];
+                    if value == 0 {
+                        continue;
+                    }
+                    let t = value as f64 / 255.0;
+                    let (r, g, b) = ramp(t);
+                    // Premultiplied, in the native-endian 0xAARRGGBB cairo expects
+                    let alpha = t.sqrt() * MAX_OPACITY;
+                    let channel = |c: f64| (c * alpha * 255.0).round() as u32;
+                    let pixel = (((alpha * 255.0).round() as u32) << 24) | (channel(r) << 16) | (channel(g) << 8) | channel(b);
+                    let offset = y * stride + x * 4;
+                    pixels[offset..offset + 4].copy_from_slice(&pixel.to_ne_bytes());
+                }
+            }
+        }
+        colored.mark_dirty();
+
+        if cr.set_source_surface(&colored, 0.0, 0.0).is_ok() {
+            let _ = cr.paint();
+        }
+    }
+}
+
+fn draw_legend_bar(cr: &cairo::Context, width: f64, height: f64) {
+    let gradient = cairo::LinearGradient::new(0.0, 0.0, width, 0.0);
+    for (offset, (r, g, b)) in RAMP {
+        gradient.add_color_stop_rgb(*offset, *r, *g, *b);
+    }
+    if cr.set_source(&gradient).is_ok() {
+        cr.rectangle(0.0, 0.0, width, height);
+        let _ = cr.fill();
+    }
+}
+
+fn create_legend() -> (gtk::Box, Label) {
+    let legend = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .halign(gtk::Align::Start)
+        .valign(gtk::Align::End)
+        .margin_start(8)
+        .margin_bottom(8)
+        .visible(false)
+        .build();
+    legend.add_css_class("osd");
+    legend.add_css_class("heatmap-legend");
+
+    let title = Label::builder()
+        .label("Places Mentioned")
+        .xalign(0.0)
+        .build();
+    title.add_css_class("caption-heading");
+    legend.append(&title);
+
+    let bar = gtk::DrawingArea::builder()
+        .content_width(LEGEND_WIDTH)
+        .content_height(LEGEND_HEIGHT)
+        .build();
+    bar.set_draw_func(|_, cr, width, height| draw_legend_bar(cr, width as f64, height as f64));
+    legend.append(&bar);
+
+    let scale = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .build();
+    let few = Label::builder()
+        .label("Few")
+        .xalign(0.0)
+        .hexpand(true)
+        .build();
+    few.add_css_class("caption");
+    scale.append(&few);
+    let many = Label::builder()
+        .label("Many")
+        .xalign(1.0)
+        .build();
+    many.add_css_class("caption");
+    scale.append(&many);
+    legend.append(&scale);
+
+    let caption = Label::builder()
+        .xalign(0.0)
+        .wrap(true)
+        .max_width_chars(24)
+        .build();
+    caption.add_css_class("caption");
+    caption.add_css_class("dim-label");
+    legend.append(&caption);
+
+    (legend, caption)
+}
+
+/// Lay the heatmap and its legend over the map; it stays hidden until turned
+/// on from the layers menu
+pub fn install(map_overlay: &gtk::Overlay, map_view: &libshumate::Map, viewport: &libshumate::Viewport) {
+    // Clicks go through to the markers and the map underneath
+    let drawing_area = gtk::DrawingArea::builder()
+        .can_target(false)
+        .visible(false)
+        .build();
+    map_overlay.add_overlay(&drawing_area);
+
+    let (legend, legend_caption) = create_legend();
+    map_overlay.add_overlay(&legend);
+
+    let heatmap = Rc::new(Heatmap {
+        drawing_area: drawing_area.clone(),
+        legend,
+        legend_caption,
+        map_view: map_view.clone(),
+        viewport: viewport.clone(),
+        points: RefCell::new(Vec::new()),
+        enabled: Cell::new(false),
+        query: RefCell::new(String::new()),
+    });
+
+    let heatmap_for_draw = Rc::downgrade(&heatmap);
+    drawing_area.set_draw_func(move |_, cr, width, height| {
+        if let Some(heatmap) = heatmap_for_draw.upgrade() {
+            heatmap.draw(cr, width, height);
+        }
+    });
+
+    // Panning and zooming move the places, so the glows are drawn again
+    let area_for_viewport = drawing_area.downgrade();
+    viewport.connect_notify_local(None, move |_, _| {
+        if let Some(area) = area_for_viewport.upgrade().filter(|area| area.is_visible()) {
+            area.queue_draw();
+        }
+    });
+
+    HEATMAP.with(|current| *current.borrow_mut() = Some(heatmap));
+}
+
+/// Check button for the layers menu that turns the heatmap on and off
+pub fn create_toggle() -> Option<gtk::CheckButton> {
+    with_heatmap(|heatmap| {
+        let check = gtk::CheckButton::builder()
+            .label("Mention heatmap")
+            .tooltip_text("Where the coverage's events take place, rather than where it's published")
+            .active(heatmap.enabled.get())
+            .build();
+        let heatmap = Rc::downgrade(heatmap);
+        check.connect_toggled(move |check| {
+            if let Some(heatmap) = heatmap.upgrade() {
+                heatmap.set_enabled(check.is_active());
+            }
+        });
+        check
+    })
+}
+
+/// Remember the search the results are showing, and fetch its places when the heatmap is on
+pub fn set_query(query: &str) {
+    let enabled = with_heatmap(|heatmap| {
+        *heatmap.query.borrow_mut() = query.to_string();
+        heatmap.enabled.get()
+    });
+    if enabled == Some(true) {
+        glib::spawn_future_local(load(query.to_string()));
+    }
+}
+
+async fn fetch_points(query: &str) -> Result<Vec<Point>, FetchError> {
+    let mut gdelt_query = GdeltQuery::new(query)
+        .or_default_terms("world")
+        .timespan(Timespan::days(1));
+    if !crate::global_affairs::search_settings().0 {
+        gdelt_query = gdelt_query.language("english");
+    }
+    let data: GdeltGeoResponse = gdelt::fetch_json(&gdelt_query.geo_url()?).await?;
+
+    // Counts run from one mention to thousands, so they're compared on a log scale
+    let max = data.features.iter().map(|feature| feature.properties.count).max().unwrap_or(0);
+    let scale = (1.0 + max as f64).ln().max(f64::EPSILON);
+    Ok(data
+        .features
+        .into_iter()
+        .filter_map(|feature| match feature.geometry.coordinates[..] {
+            [longitude, latitude, ..] => Some(Point {
+                name: feature.properties.name,
+                latitude,
+                longitude,
+                weight: (1.0 + feature.properties.count as f64).ln() / scale,
+            }),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Fetch the places a search's coverage mentions and redraw the heatmap
+async fn load(query: String) {
+    let Some(heatmap) = with_heatmap(Rc::clone) else {
+        return;
+    };
+
+    let result = fetch_points(&query).await;
+    // A newer search may have started, or the heatmap been turned off, meanwhile
+    if *heatmap.query.borrow() != query || !heatmap.enabled.get() {
+        return;
+    }
+
+    match result {
+        Ok(points) => {
+            let places = match points.len() {
+                1 => "1 place in the past day".to_string(),
+                count => format!("{} places in the past day", count),
+            };
+            let busiest = points.iter().max_by(|a, b| a.weight.total_cmp(&b.weight));
+            heatmap.legend_caption.set_label(&match busiest {
+                Some(point) if !point.name.is_empty() => format!("{}, most of all {}", places, point.name),
+                _ => places,
+            });
+            heatmap.legend.set_visible(!points.is_empty());
+            *heatmap.points.borrow_mut() = points;
+        }
+        Err(e) => {
+            tracing::warn!("No mentioned places for '{}': {}", query, e);
+            // Places from an earlier search would be misleading
+            heatmap.points.borrow_mut().clear();
+            heatmap.legend.set_visible(false);
+            crate::toasts::show_error(&e, move || {
+                glib::spawn_future_local(load(query.clone()));
+            });
+        }
+    }
+    heatmap.drawing_area.queue_draw();
+}
@@ -0,0 +1,68 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use crate::data::{FacetType, FirehosePost};
+
+/// Posts kept from the stream for filling panes whose filters change
+const HISTORY_SIZE: usize = 5000;
+/// Most posts a pane is filled with, matching how many rows it keeps
+const MAX_MATCHES: usize = 100;
+
+thread_local! {
+    /// Oldest first
+    static HISTORY: RefCell<VecDeque<FirehosePost>> = RefCell::new(VecDeque::with_capacity(HISTORY_SIZE));
+}
+
+/// Keep a post that made it past the spam filter, dropping the oldest when full
+pub fn record(post: &FirehosePost) {
+    HISTORY.with(|history| {
+        let mut history = history.borrow_mut();
+        if history.len() >= HISTORY_SIZE {
+            history.pop_front();
+        }
+        history.push_back(post.clone());
+    });
+}
+
+/// The newest posts in the history that pass `matches`, oldest first so
+/// prepending them leaves the newest on top
+pub fn matching(matches: impl Fn(&FirehosePost) -> bool) -> Vec<FirehosePost> {
+    HISTORY.with(|history| {
+        let history = history.borrow();
+        let mut found: Vec<FirehosePost> = history
+            .iter()
+            .rev()
+            .filter(|post| matches(post))
+            .take(MAX_MATCHES)
+            .cloned()
+            .collect();
+        found.reverse();
+        found
+    })
+}
+
+/// The most used hashtags in the history, lowercased, most used first
+pub fn top_hashtags() -> Vec<(String, usize)> {
+    HISTORY.with(|history| {
+        let tags: Vec<String> = history
+            .borrow()
+            .iter()
+            .flat_map(|post| post.facets.iter().flatten())
+            .filter_map(|facet| match &facet.facet_type {
+                FacetType::Tag(tag) => Some(tag.trim_start_matches('#').to_lowercase()),
+                _ => None,
+            })
+            .collect();
+        crate::digest::top_counts(tags.iter().map(String::as_str))
+    })
+}
+
+/// Drop a post its author deleted
+pub fn remove(did: &str, rkey: &str) {
+    HISTORY.with(|history| history.borrow_mut().retain(|post| post.did != did || post.rkey != rkey));
+}
+
+/// Forget the stream, e.g. when focus mode blanks the firehose
+pub fn clear() {
+    HISTORY.with(|history| history.borrow_mut().clear());
+}
@@ -0,0 +1,112 @@
+use gtk::glib;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::error::FetchError;
+
+/// Sent with every request so services can tell who is calling
+pub const USER_AGENT: &str = concat!(
+    "Grapevine/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/toasterrepairman/grapevine)"
+);
+
+/// Default for the whole request; slow services such as GDELT set their own per request
+const TIMEOUT: Duration = Duration::from_secs(10);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tries for a request that failed for a reason that might pass, including the first
+const MAX_ATTEMPTS: u32 = 3;
+/// Wait before the first retry, doubled before each one after
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+struct Shared {
+    client: Option<reqwest::Client>,
+    /// Proxy every request goes through, from preferences; empty for a direct connection
+    proxy_url: String,
+}
+
+/// Built on first use and rebuilt when the proxy changes, so connections are pooled across services
+static SHARED: Mutex<Shared> = Mutex::new(Shared { client: None, proxy_url: String::new() });
+
+fn build(proxy_url: &str) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder()
+        .timeout(TIMEOUT)
+        .connect_timeout(CONNECT_TIMEOUT)
+        .user_agent(USER_AGENT);
+
+    if !proxy_url.is_empty() {
+        match reqwest::Proxy::all(proxy_url) {
+            Ok(proxy) => builder = builder.proxy(proxy),
+            Err(e) => tracing::warn!("Ignoring proxy {}: {}", proxy_url, e),
+        }
+    }
+
+    builder.build().unwrap_or_else(|e| {
+        tracing::warn!("Failed to build the HTTP client, using defaults: {}", e);
+        reqwest::Client::new()
+    })
+}
+
+/// The app's HTTP client; cheap to clone, since clones share one connection pool
+pub fn client() -> reqwest::Client {
+    let mut shared = SHARED.lock().unwrap_or_else(|e| e.into_inner());
+    if shared.client.is_none() {
+        shared.client = Some(build(&shared.proxy_url));
+    }
+    shared.client.clone().unwrap_or_default()
+}
+
+/// Send requests through an http:// or https:// proxy, or directly when empty.
+/// Requests already under way finish on the old connection.
+pub fn set_proxy(proxy_url: &str) {
+    let mut shared = SHARED.lock().unwrap_or_else(|e| e.into_inner());
+    let proxy_url = proxy_url.trim();
+    if shared.proxy_url != proxy_url {
+        shared.proxy_url = proxy_url.to_string();
+        shared.client = None;
+    }
+}
+
+/// Whether an error is worth another try straight away: the connection dropped
+/// or the service briefly fell over. Rate limits are left to the caller, who
+/// knows how long to wait.
+fn is_transient(error: &FetchError) -> bool {
+    match error {
+        FetchError::Network { .. } => true,
+        FetchError::Upstream { status, .. } => matches!(status, 502..=504),
+        _ => false,
+    }
+}
+
+/// Send a request, retrying transient failures with exponential backoff. Only
+/// idempotent requests are retried, so a post is never published twice.
+pub async fn send_with_retry(
+    service: &'static str,
+    request: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, FetchError> {
+    let idempotent = request
+        .try_clone()
+        .and_then(|request| request.build().ok())
+        .is_some_and(|request| request.method().is_idempotent());
+    if !idempotent {
+        return crate::error::send(service, request).await;
+    }
+
+    let mut delay = RETRY_BASE_DELAY;
+    for _ in 1..MAX_ATTEMPTS {
+        // try_clone only fails for streamed bodies, which were ruled out above
+        let Some(attempt) = request.try_clone() else {
+            break;
+        };
+        match crate::error::send(service, attempt).await {
+            Err(e) if is_transient(&e) => {
+                tracing::warn!("{}; retrying in {} ms", e, delay.as_millis());
+                glib::timeout_future(delay).await;
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+    crate::error::send(service, request).await
+}
@@ -0,0 +1,111 @@
+use gtk::gio;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+/// Most the cache directory may hold before the least recently used images go
+const MAX_CACHE_BYTES: u64 = 200 * 1024 * 1024;
+/// Bytes written between trims, so the cap is enforced without listing the directory on every write
+const TRIM_EVERY_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Written since the directory was last trimmed
+static WRITTEN_SINCE_TRIM: AtomicU64 = AtomicU64::new(0);
+
+fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("grapevine").join("images"))
+}
+
+/// FNV-1a, spelled out so file names stay the same across Rust releases
+fn hash_url(url: &str) -> u64 {
+    url.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn path_for(url: &str) -> Option<PathBuf> {
+    cache_dir().map(|dir| dir.join(format!("{:016x}", hash_url(url))))
+}
+
+/// Cached bytes of an image, if it was downloaded before and hasn't been evicted
+pub async fn read(url: &str) -> Option<Vec<u8>> {
+    let path = path_for(url)?;
+    gio::spawn_blocking(move || {
+        let bytes = std::fs::read(&path).ok()?;
+        // Touched on use, so eviction drops the images nobody has looked at lately
+        if let Ok(file) = std::fs::File::options().append(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        Some(bytes)
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+/// Keep a downloaded image for next time; failures only cost a re-download
+pub async fn write(url: &str, bytes: Vec<u8>) {
+    let Some(path) = path_for(url) else {
+        return;
+    };
+    let len = bytes.len() as u64;
+    let _ = gio::spawn_blocking(move || {
+        if let Some(dir) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                tracing::warn!("Failed to create image cache {}: {}", dir.display(), e);
+                return;
+            }
+        }
+        // Written aside and renamed so a reader never sees half an image
+        let partial = path.with_extension("part");
+        if let Err(e) = std::fs::write(&partial, &bytes).and_then(|_| std::fs::rename(&partial, &path)) {
+            tracing::warn!("Failed to cache image {}: {}", path.display(), e);
+        }
+    })
+    .await;
+
+    if WRITTEN_SINCE_TRIM.fetch_add(len, Ordering::Relaxed) + len >= TRIM_EVERY_BYTES {
+        trim().await;
+    }
+}
+
+/// Delete the least recently used images until the cache fits its cap
+pub async fn trim() {
+    WRITTEN_SINCE_TRIM.store(0, Ordering::Relaxed);
+    let Some(dir) = cache_dir() else {
+        return;
+    };
+
+    let _ = gio::spawn_blocking(move || {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            return; // Nothing cached yet
+        };
+        let mut files: Vec<(SystemTime, u64, PathBuf)> = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let metadata = entry.metadata().ok()?;
+                metadata
+                    .is_file()
+                    .then(|| (metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH), metadata.len(), entry.path()))
+            })
+            .collect();
+
+        let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+        if total <= MAX_CACHE_BYTES {
+            return;
+        }
+
+        files.sort_by_key(|(modified, _, _)| *modified);
+        let mut removed = 0;
+        for (_, len, path) in files {
+            if total <= MAX_CACHE_BYTES {
+                break;
+            }
+            if std::fs::remove_file(&path).is_ok() {
+                total -= len;
+                removed += 1;
+            }
+        }
+        tracing::info!("Evicted {} images from the cache", removed);
+    })
+    .await;
+}
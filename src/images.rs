@@ -0,0 +1,187 @@
+use gtk::prelude::*;
+use gtk::{gdk, gio, glib};
+use gtk::gdk_pixbuf::PixbufLoader;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+
+use crate::error::{get_bytes, FetchError};
+use crate::image_cache;
+
+/// Decoded pixels kept in memory, so a refresh redraws cards without decoding again
+const MAX_TEXTURE_BYTES: usize = 96 * 1024 * 1024;
+
+/// An image at the size it was asked for
+type TextureKey = (String, i32, i32);
+
+/// Recently decoded textures, evicted oldest first once over MAX_TEXTURE_BYTES
+#[derive(Default)]
+struct TextureCache {
+    textures: HashMap<TextureKey, gdk::Texture>,
+    order: VecDeque<TextureKey>,
+    bytes: usize,
+}
+
+impl TextureCache {
+    fn get(&mut self, key: &TextureKey) -> Option<gdk::Texture> {
+        let texture = self.textures.get(key)?.clone();
+        // Move to the back so it's evicted last
+        if let Some(position) = self.order.iter().position(|k| k == key) {
+            if let Some(key) = self.order.remove(position) {
+                self.order.push_back(key);
+            }
+        }
+        Some(texture)
+    }
+
+    fn insert(&mut self, key: TextureKey, texture: gdk::Texture) {
+        if let Some(old) = self.textures.insert(key.clone(), texture.clone()) {
+            self.bytes -= texture_bytes(&old);
+            self.order.retain(|k| k != &key);
+        }
+        self.bytes += texture_bytes(&texture);
+        self.order.push_back(key);
+
+        while self.bytes > MAX_TEXTURE_BYTES {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            if let Some(evicted) = self.textures.remove(&oldest) {
+                self.bytes -= texture_bytes(&evicted);
+            }
+        }
+    }
+}
+
+fn texture_bytes(texture: &gdk::Texture) -> usize {
+    texture.width() as usize * texture.height() as usize * 4
+}
+
+thread_local! {
+    static TEXTURES: RefCell<TextureCache> = RefCell::new(TextureCache::default());
+}
+
+/// Decoded pixels handed back from the worker thread (Pixbuf itself isn't Send)
+struct DecodedImage {
+    width: i32,
+    height: i32,
+    stride: usize,
+    has_alpha: bool,
+    pixels: glib::Bytes,
+}
+
+const SERVICE: &str = "Image host";
+
+/// Download an image and decode it off the main thread, downscaled to fit
+/// within max_width x max_height (never upscaled, aspect ratio preserved)
+pub async fn load_texture(url: &str, max_width: i32, max_height: i32) -> Result<gdk::Texture, FetchError> {
+    let key = (url.to_string(), max_width, max_height);
+    if let Some(texture) = TEXTURES.with(|cache| cache.borrow_mut().get(&key)) {
+        return Ok(texture);
+    }
+
+    let bytes = fetch_image_bytes(url).await?;
+    let texture = decode_texture(bytes, max_width, max_height)
+        .await
+        .ok_or_else(|| FetchError::parse(SERVICE, "unsupported image format"))?;
+    TEXTURES.with(|cache| cache.borrow_mut().insert(key, texture.clone()));
+    Ok(texture)
+}
+
+/// An image's encoded bytes, from the disk cache or downloaded and cached
+pub async fn fetch_image_bytes(url: &str) -> Result<Vec<u8>, FetchError> {
+    if let Some(bytes) = image_cache::read(url).await {
+        return Ok(bytes);
+    }
+
+    let bytes = get_bytes(SERVICE, crate::http::client().get(url)).await?;
+    image_cache::write(url, bytes.clone()).await;
+    Ok(bytes)
+}
+
+/// Decode encoded image bytes on a worker thread and build the texture on the main thread
+pub async fn decode_texture(bytes: Vec<u8>, max_width: i32, max_height: i32) -> Option<gdk::Texture> {
+    let decoded = gio::spawn_blocking(move || decode_scaled(&bytes, max_width, max_height))
+        .await
+        .ok()
+        .flatten()?;
+
+    let format = if decoded.has_alpha {
+        gdk::MemoryFormat::R8g8b8a8
+    } else {
+        gdk::MemoryFormat::R8g8b8
+    };
+
+    let texture = gdk::MemoryTexture::new(decoded.width, decoded.height, format, &decoded.pixels, decoded.stride);
+    Some(texture.upcast())
+}
+
+/// Re-encode an image as a JPEG under `max_bytes`, shrinking it until it fits
+pub async fn shrink_to_jpeg(bytes: Vec<u8>, max_bytes: usize) -> Option<Vec<u8>> {
+    gio::spawn_blocking(move || {
+        for (max_side, quality) in [(2000, "85"), (1200, "80"), (800, "70")] {
+            let pixbuf = load_pixbuf(&bytes, max_side, max_side)?;
+            match pixbuf.save_to_bufferv("jpeg", &[("quality", quality)]) {
+                Ok(jpeg) if jpeg.len() <= max_bytes => return Some(jpeg),
+                Ok(_) => continue,
+                Err(e) => {
+                    tracing::warn!("Failed to encode image: {}", e);
+                    return None;
+                }
+            }
+        }
+        None
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+fn decode_scaled(bytes: &[u8], max_width: i32, max_height: i32) -> Option<DecodedImage> {
+    let pixbuf = load_pixbuf(bytes, max_width, max_height)?;
+
+    // Only 8-bit RGB(A) pixbufs map directly onto a memory format
+    if pixbuf.bits_per_sample() != 8 {
+        return None;
+    }
+
+    Some(DecodedImage {
+        width: pixbuf.width(),
+        height: pixbuf.height(),
+        stride: pixbuf.rowstride() as usize,
+        has_alpha: pixbuf.has_alpha(),
+        pixels: pixbuf.read_pixel_bytes(),
+    })
+}
+
+/// Decode image bytes to fit within max_width x max_height
+fn load_pixbuf(bytes: &[u8], max_width: i32, max_height: i32) -> Option<gtk::gdk_pixbuf::Pixbuf> {
+    let loader = PixbufLoader::new();
+
+    // Shrink during decode so large images never get fully materialized
+    loader.connect_size_prepared(move |loader, width, height| {
+        if width <= 0 || height <= 0 {
+            return;
+        }
+        let scale = (max_width as f64 / width as f64)
+            .min(max_height as f64 / height as f64)
+            .min(1.0);
+        if scale < 1.0 {
+            loader.set_size(
+                ((width as f64 * scale).round() as i32).max(1),
+                ((height as f64 * scale).round() as i32).max(1),
+            );
+        }
+    });
+
+    if let Err(e) = loader.write(bytes) {
+        tracing::warn!("Failed to decode image: {}", e);
+        let _ = loader.close();
+        return None;
+    }
+    if let Err(e) = loader.close() {
+        tracing::warn!("Failed to finish decoding image: {}", e);
+        return None;
+    }
+
+    loader.pixbuf()
+}
@@ -0,0 +1,74 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::settings::write_private;
+
+/// A JSON file in Grapevine's data directory holding one value, such as the
+/// bookmarks list. Written so only the user can read it, as some hold
+/// unpublished posts.
+pub struct JsonStore {
+    file_name: &'static str,
+    /// What the file holds, for log messages, e.g. "bookmarks"
+    what: &'static str,
+    /// Written without indentation; for files too big to read by hand anyway
+    compact: bool,
+}
+
+impl JsonStore {
+    pub const fn new(file_name: &'static str, what: &'static str) -> Self {
+        Self { file_name, what, compact: false }
+    }
+
+    pub const fn compact(self) -> Self {
+        Self { compact: true, ..self }
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("grapevine").join(self.file_name))
+    }
+
+    /// The stored value, or the default if there is none yet or it can't be read
+    pub fn load<T: DeserializeOwned + Default>(&self) -> T {
+        let Some(path) = self.path() else {
+            return T::default();
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                tracing::warn!("Failed to parse {} at {}: {}", self.what, path.display(), e);
+                T::default()
+            }),
+            // Nothing saved yet
+            Err(_) => T::default(),
+        }
+    }
+
+    pub fn save<T: Serialize + ?Sized>(&self, value: &T) {
+        let Some(path) = self.path() else {
+            tracing::warn!("Could not determine data directory, {} not saved", self.what);
+            return;
+        };
+
+        if let Some(parent) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                tracing::warn!("Failed to create data directory {}: {}", parent.display(), e);
+                return;
+            }
+        }
+
+        let text = if self.compact {
+            serde_json::to_string(value)
+        } else {
+            serde_json::to_string_pretty(value)
+        };
+        match text {
+            Ok(text) => {
+                if let Err(e) = write_private(&path, &text) {
+                    tracing::warn!("Failed to write {} to {}: {}", self.what, path.display(), e);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to serialize {}: {}", self.what, e),
+        }
+    }
+}
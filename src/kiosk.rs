@@ -0,0 +1,151 @@
+use gtk::glib;
+use gtk::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::connection;
+use crate::settings::Settings;
+
+/// Pages kiosk mode can cycle through: (stack page name, title)
+pub const PAGES: &[(&str, &str)] = &[
+    ("global-affairs", "Global Affairs"),
+    ("firehose", "Firehose"),
+    ("bookmarks", "Bookmarks"),
+    ("markets", "Markets"),
+];
+
+/// Fullscreen, chrome-free display that cycles between pages on a timer,
+/// for wall displays nobody is sitting at
+pub struct Kiosk {
+    app: gtk::Application,
+    window: libadwaita::ApplicationWindow,
+    stack: libadwaita::ViewStack,
+    toolbar_view: libadwaita::ToolbarView,
+    /// Widgets hidden while in kiosk mode, e.g. the floating view switcher
+    chrome: Vec<gtk::Widget>,
+    settings: Rc<RefCell<Settings>>,
+    running: RefCell<Option<Running>>,
+}
+
+struct Running {
+    cycle: glib::SourceId,
+    /// Idle/suspend inhibitor; 0 if the session refused it
+    inhibit_cookie: u32,
+}
+
+impl Kiosk {
+    pub fn new(
+        app: &gtk::Application,
+        window: &libadwaita::ApplicationWindow,
+        stack: &libadwaita::ViewStack,
+        toolbar_view: &libadwaita::ToolbarView,
+        chrome: Vec<gtk::Widget>,
+        settings: Rc<RefCell<Settings>>,
+    ) -> Rc<Self> {
+        Rc::new(Self {
+            app: app.clone(),
+            window: window.clone(),
+            stack: stack.clone(),
+            toolbar_view: toolbar_view.clone(),
+            chrome,
+            settings,
+            running: RefCell::new(None),
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.running.borrow().is_some()
+    }
+
+    pub fn toggle(self: &Rc<Self>) {
+        if self.is_active() {
+            self.stop();
+        } else {
+            self.start();
+        }
+    }
+
+    pub fn start(self: &Rc<Self>) {
+        if self.is_active() {
+            return;
+        }
+
+        self.toolbar_view.set_reveal_top_bars(false);
+        self.toolbar_view.set_reveal_bottom_bars(false);
+        for widget in &self.chrome {
+            widget.set_visible(false);
+        }
+        self.window.fullscreen();
+        self.window.set_cursor_from_name(Some("none"));
+
+        // Goes through the Inhibit portal when sandboxed, the session manager otherwise
+        let inhibit_cookie = self.app.inhibit(
+            Some(&self.window),
+            gtk::ApplicationInhibitFlags::IDLE | gtk::ApplicationInhibitFlags::SUSPEND,
+            Some("Kiosk mode is showing live news"),
+        );
+        if inhibit_cookie == 0 {
+            tracing::warn!("Kiosk mode couldn't stop the screen from blanking");
+        }
+
+        // Nobody is there to press Retry, so the firehose reconnects on its own
+        connection::set_keep_retrying(true);
+
+        let interval = self.settings.borrow().kiosk_interval_seconds.max(5);
+        let kiosk = Rc::downgrade(self);
+        let cycle = glib::timeout_add_local(Duration::from_secs(interval as u64), move || {
+            match kiosk.upgrade() {
+                Some(kiosk) => {
+                    kiosk.show_next_page();
+                    glib::ControlFlow::Continue
+                }
+                None => glib::ControlFlow::Break,
+            }
+        });
+
+        *self.running.borrow_mut() = Some(Running { cycle, inhibit_cookie });
+    }
+
+    pub fn stop(&self) {
+        let Some(running) = self.running.borrow_mut().take() else {
+            return;
+        };
+
+        running.cycle.remove();
+        if running.inhibit_cookie != 0 {
+            self.app.uninhibit(running.inhibit_cookie);
+        }
+        connection::set_keep_retrying(false);
+
+        self.window.set_cursor_from_name(None);
+        self.window.unfullscreen();
+        for widget in &self.chrome {
+            widget.set_visible(true);
+        }
+        self.toolbar_view.set_reveal_top_bars(true);
+        self.toolbar_view.set_reveal_bottom_bars(true);
+    }
+
+    /// Move to the configured page after the visible one, wrapping around
+    fn show_next_page(&self) {
+        let pages: Vec<String> = self
+            .settings
+            .borrow()
+            .kiosk_pages
+            .iter()
+            .filter(|name| self.stack.child_by_name(name).is_some())
+            .cloned()
+            .collect();
+        if pages.is_empty() {
+            return;
+        }
+
+        let current = self.stack.visible_child_name();
+        let next = current
+            .and_then(|current| pages.iter().position(|name| *name == current.as_str()))
+            .map(|index| (index + 1) % pages.len())
+            .unwrap_or(0);
+        self.stack.set_visible_child_name(&pages[next]);
+    }
+}
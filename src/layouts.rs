@@ -0,0 +1,516 @@
+use gtk::prelude::*;
+use gtk::{gdk, gio, glib};
+use libadwaita::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::json_store::JsonStore;
+
+/// Stack pages in switcher order: (page name, title). Pages coming back from
+/// their own window return to this place.
+const PAGES: &[(&str, &str)] = &[
+    ("global-affairs", "Global Affairs"),
+    ("firehose", "Firehose"),
+    ("bookmarks", "Bookmarks"),
+    ("notifications", "Notifications"),
+    ("markets", "Markets"),
+];
+
+/// Role of the main window in a saved layout; other windows are named after their page
+const MAIN_ROLE: &str = "main";
+
+/// Where and how big one window was when the layout was saved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowPlacement {
+    /// "main", or the name of the page the window shows
+    pub role: String,
+    pub width: i32,
+    pub height: i32,
+    #[serde(default)]
+    pub maximized: bool,
+    #[serde(default)]
+    pub fullscreen: bool,
+    /// Connector of the monitor the window was on, e.g. "DP-2"
+    #[serde(default)]
+    pub monitor: Option<String>,
+}
+
+/// A named set of windows, e.g. the map on one monitor and the firehose on another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Layout {
+    pub name: String,
+    pub windows: Vec<WindowPlacement>,
+}
+
+/// A page moved out of the main window
+struct DetachedPage {
+    child: gtk::Widget,
+    window: libadwaita::Window,
+    toolbar_view: libadwaita::ToolbarView,
+    /// Badge the page had in the stack, put back when it returns
+    badge: u32,
+}
+
+struct Workspace {
+    window: libadwaita::ApplicationWindow,
+    stack: libadwaita::ViewStack,
+    detached: HashMap<String, DetachedPage>,
+    /// "Window Layouts" submenu, rebuilt when layouts are saved or forgotten
+    menu: gio::Menu,
+}
+
+/// Called when a page comes into view
+type ShownListener = (String, Rc<dyn Fn()>);
+
+thread_local! {
+    static WORKSPACE: RefCell<Option<Workspace>> = const { RefCell::new(None) };
+    /// Registered before the workspace exists, so kept apart from it
+    static SHOWN_LISTENERS: RefCell<Vec<ShownListener>> = const { RefCell::new(Vec::new()) };
+}
+
+const FILE: JsonStore = JsonStore::new("layouts.json", "window layouts");
+
+fn load() -> Vec<Layout> {
+    FILE.load()
+}
+
+fn save(layouts: &[Layout]) {
+    FILE.save(layouts);
+}
+
+fn page_title(name: &str) -> &'static str {
+    PAGES.iter().find(|(page, _)| *page == name).map(|(_, title)| *title).unwrap_or("Grapevine")
+}
+
+/// Call `f` whenever a page comes into view: picked in the main window's
+/// switcher, or its own window shown or focused
+pub fn connect_page_shown(name: &str, f: impl Fn() + 'static) {
+    SHOWN_LISTENERS.with(|listeners| listeners.borrow_mut().push((name.to_string(), Rc::new(f))));
+}
+
+fn notify_shown(name: &str) {
+    // Collected first, as a listener may register another
+    let listeners: Vec<Rc<dyn Fn()>> = SHOWN_LISTENERS.with(|listeners| {
+        listeners
+            .borrow()
+            .iter()
+            .filter(|(page, _)| page == name)
+            .map(|(_, listener)| listener.clone())
+            .collect()
+    });
+    for listener in listeners {
+        listener();
+    }
+}
+
+/// Whether a page is in view: the visible page of the shown main window, or
+/// a page in its own mapped window
+pub fn is_page_shown(name: &str) -> bool {
+    if let Some(window) = detached_window(name) {
+        return window.is_mapped();
+    }
+    WORKSPACE.with(|workspace| {
+        workspace.borrow().as_ref().is_some_and(|workspace| {
+            workspace.window.is_mapped() && workspace.stack.visible_child_name().as_deref() == Some(name)
+        })
+    })
+}
+
+/// Show a count on a page: a badge in the main window's switcher, or in the
+/// title of the page's own window
+pub fn set_page_badge(name: &str, count: usize) {
+    let detached = WORKSPACE.with(|workspace| {
+        let mut workspace = workspace.borrow_mut();
+        let page = workspace.as_mut()?.detached.get_mut(name)?;
+        page.badge = count as u32;
+        Some(page.window.clone())
+    });
+    if let Some(window) = detached {
+        let title = match count {
+            0 => page_title(name).to_string(),
+            count => format!("{} ({})", page_title(name), count),
+        };
+        window.set_title(Some(&title));
+        return;
+    }
+
+    // The page doesn't exist until main adds its view to the stack
+    let Some(stack) = stack() else {
+        return;
+    };
+    if let Some(child) = stack.child_by_name(name) {
+        let page = stack.page(&child);
+        page.set_badge_number(count as u32);
+        page.set_needs_attention(count > 0);
+    }
+}
+
+/// Register the main window, its page stack and the "Window Layouts" submenu
+/// of the primary menu, which is filled in here
+pub fn install(window: &libadwaita::ApplicationWindow, stack: &libadwaita::ViewStack, menu: &gio::Menu) {
+    fill_menu(menu);
+
+    stack.connect_visible_child_notify(|stack| {
+        if let Some(name) = stack.visible_child_name() {
+            notify_shown(&name);
+        }
+    });
+    // Coming back from the tray shows whatever page was left open
+    let stack_for_map = stack.clone();
+    window.connect_map(move |_| {
+        if let Some(name) = stack_for_map.visible_child_name() {
+            notify_shown(&name);
+        }
+    });
+
+    // Detached pages go with the main window rather than keeping the app alive on their own
+    window.connect_destroy(|_| {
+        let windows: Vec<libadwaita::Window> = WORKSPACE.with(|workspace| {
+            workspace
+                .borrow_mut()
+                .take()
+                .map(|workspace| workspace.detached.into_values().map(|page| page.window).collect())
+                .unwrap_or_default()
+        });
+        for window in windows {
+            window.destroy();
+        }
+    });
+
+    WORKSPACE.with(|workspace| {
+        *workspace.borrow_mut() = Some(Workspace {
+            window: window.clone(),
+            stack: stack.clone(),
+            detached: HashMap::new(),
+            menu: menu.clone(),
+        });
+    });
+}
+
+fn fill_menu(menu: &gio::Menu) {
+    menu.remove_all();
+    let layouts = load();
+
+    let restore_section = gio::Menu::new();
+    for layout in &layouts {
+        let item = gio::MenuItem::new(Some(&layout.name), None);
+        item.set_action_and_target_value(Some("app.restore-layout"), Some(&layout.name.to_variant()));
+        restore_section.append_item(&item);
+    }
+    menu.append_section(None, &restore_section);
+
+    let edit_section = gio::Menu::new();
+    edit_section.append(Some("Move Page to New Window"), Some("app.detach-page"));
+    edit_section.append(Some("Save Layout…"), Some("app.save-layout"));
+    if !layouts.is_empty() {
+        let forget_menu = gio::Menu::new();
+        for layout in &layouts {
+            let item = gio::MenuItem::new(Some(&layout.name), None);
+            item.set_action_and_target_value(Some("app.forget-layout"), Some(&layout.name.to_variant()));
+            forget_menu.append_item(&item);
+        }
+        edit_section.append_submenu(Some("Forget Layout"), &forget_menu);
+    }
+    menu.append_section(None, &edit_section);
+}
+
+fn refresh_menu() {
+    let menu = WORKSPACE.with(|workspace| workspace.borrow().as_ref().map(|workspace| workspace.menu.clone()));
+    if let Some(menu) = menu {
+        fill_menu(&menu);
+    }
+}
+
+fn stack() -> Option<libadwaita::ViewStack> {
+    WORKSPACE.with(|workspace| workspace.borrow().as_ref().map(|workspace| workspace.stack.clone()))
+}
+
+fn detached_window(name: &str) -> Option<libadwaita::Window> {
+    WORKSPACE.with(|workspace| {
+        workspace.borrow().as_ref().and_then(|workspace| workspace.detached.get(name).map(|page| page.window.clone()))
+    })
+}
+
+/// Show a page, wherever it is: switch the main window to it, or raise its own window
+pub fn show_page(name: &str) {
+    if let Some(window) = detached_window(name) {
+        window.present();
+    } else if let Some(stack) = stack() {
+        stack.set_visible_child_name(name);
+    }
+}
+
+/// Move the page the main window is showing into a window of its own
+pub fn detach_visible_page() {
+    if let Some(name) = stack().and_then(|stack| stack.visible_child_name()) {
+        detach(&name);
+    }
+}
+
+/// Move a page out of the main window into its own window, or raise that
+/// window if the page is already out
+pub fn detach(name: &str) {
+    if let Some(window) = detached_window(name) {
+        window.present();
+        return;
+    }
+
+    let Some((main_window, stack)) = WORKSPACE.with(|workspace| {
+        workspace.borrow().as_ref().map(|workspace| (workspace.window.clone(), workspace.stack.clone()))
+    }) else {
+        return;
+    };
+    let Some(child) = stack.child_by_name(name) else {
+        return;
+    };
+
+    // The main window keeps at least one page
+    let Some(remaining) = PAGES
+        .iter()
+        .map(|(page, _)| *page)
+        .find(|page| *page != name && stack.child_by_name(page).is_some())
+    else {
+        crate::toasts::show("The main window needs at least one page");
+        return;
+    };
+    if stack.visible_child_name().as_deref() == Some(name) {
+        stack.set_visible_child_name(remaining);
+    }
+    let badge = stack.page(&child).badge_number();
+    stack.remove(&child);
+
+    let toolbar_view = libadwaita::ToolbarView::new();
+    toolbar_view.add_top_bar(&libadwaita::HeaderBar::new());
+    toolbar_view.set_content(Some(&child));
+
+    let window = libadwaita::Window::builder()
+        .title(page_title(name))
+        .default_width(800)
+        .default_height(600)
+        .width_request(360)
+        .height_request(294)
+        .content(&toolbar_view)
+        .build();
+    window.set_application(main_window.application().as_ref());
+
+    let page_name = name.to_string();
+    window.connect_close_request(move |_| {
+        reattach(&page_name);
+        glib::Propagation::Proceed
+    });
+    let page_name = name.to_string();
+    window.connect_map(move |_| notify_shown(&page_name));
+    let page_name = name.to_string();
+    window.connect_is_active_notify(move |window| {
+        if window.is_active() {
+            notify_shown(&page_name);
+        }
+    });
+
+    WORKSPACE.with(|workspace| {
+        if let Some(workspace) = workspace.borrow_mut().as_mut() {
+            workspace.detached.insert(
+                name.to_string(),
+                DetachedPage { child, window: window.clone(), toolbar_view, badge },
+            );
+        }
+    });
+    window.present();
+}
+
+/// Put a detached page back into the main window, in its usual place
+fn reattach(name: &str) {
+    let Some((stack, page)) = WORKSPACE.with(|workspace| {
+        workspace
+            .borrow_mut()
+            .as_mut()
+            .and_then(|workspace| Some((workspace.stack.clone(), workspace.detached.remove(name)?)))
+    }) else {
+        return;
+    };
+    page.toolbar_view.set_content(None::<&gtk::Widget>);
+
+    // ViewStack can only append, so the pages after this one are added again behind it
+    let visible = stack.visible_child_name();
+    let mut pages = Vec::new();
+    for (page_name, title) in PAGES {
+        if *page_name == name {
+            pages.push((page.child.clone(), *page_name, *title, page.badge > 0, page.badge));
+        } else if let Some(child) = stack.child_by_name(page_name) {
+            let stack_page = stack.page(&child);
+            pages.push((child, *page_name, *title, stack_page.needs_attention(), stack_page.badge_number()));
+        }
+    }
+    for (child, ..) in &pages {
+        if child.parent().is_some() {
+            stack.remove(child);
+        }
+    }
+    for (child, page_name, title, needs_attention, badge_number) in pages {
+        let stack_page = stack.add_titled(&child, Some(page_name), title);
+        stack_page.set_icon_name(None);
+        stack_page.set_needs_attention(needs_attention);
+        stack_page.set_badge_number(badge_number);
+    }
+    if let Some(visible) = visible {
+        stack.set_visible_child_name(&visible);
+    }
+}
+
+/// Connector of the monitor a window is mostly on
+fn monitor_of(window: &gtk::Window) -> Option<String> {
+    let surface = window.surface()?;
+    let monitor = WidgetExt::display(window).monitor_at_surface(&surface)?;
+    monitor.connector().map(|connector| connector.to_string())
+}
+
+fn find_monitor(display: &gdk::Display, connector: &str) -> Option<gdk::Monitor> {
+    let monitors = display.monitors();
+    (0..monitors.n_items())
+        .filter_map(|index| monitors.item(index).and_downcast::<gdk::Monitor>())
+        .find(|monitor| monitor.connector().as_deref() == Some(connector))
+}
+
+fn placement(window: &gtk::Window, role: &str) -> WindowPlacement {
+    let (width, height) = window.default_size();
+    WindowPlacement {
+        role: role.to_string(),
+        width,
+        height,
+        maximized: window.is_maximized(),
+        fullscreen: window.is_fullscreen(),
+        monitor: monitor_of(window),
+    }
+}
+
+/// Size and state are restored everywhere; the monitor only for fullscreen
+/// windows, since GTK 4 leaves placing ordinary windows to the compositor
+fn apply(window: &gtk::Window, placement: &WindowPlacement) {
+    window.set_default_size(placement.width, placement.height);
+
+    let monitor = placement
+        .monitor
+        .as_deref()
+        .and_then(|connector| find_monitor(&WidgetExt::display(window), connector));
+    if placement.fullscreen {
+        match monitor {
+            Some(monitor) => window.fullscreen_on_monitor(&monitor),
+            None => window.fullscreen(),
+        }
+        return;
+    }
+
+    window.unfullscreen();
+    if placement.maximized {
+        window.maximize();
+    } else {
+        window.unmaximize();
+    }
+}
+
+/// Save the current windows as a layout, replacing any layout of the same name
+pub fn save_current(name: &str) {
+    let Some(windows) = WORKSPACE.with(|workspace| {
+        workspace.borrow().as_ref().map(|workspace| {
+            let mut windows = vec![placement(workspace.window.upcast_ref(), MAIN_ROLE)];
+            for (page_name, page) in &workspace.detached {
+                windows.push(placement(page.window.upcast_ref(), page_name));
+            }
+            windows
+        })
+    }) else {
+        return;
+    };
+
+    let mut layouts = load();
+    layouts.retain(|layout| layout.name != name);
+    layouts.push(Layout { name: name.to_string(), windows });
+    save(&layouts);
+    refresh_menu();
+    crate::toasts::show(&format!("Saved layout “{}”", name));
+}
+
+/// Bring back a saved layout: pages get the windows they had, and every
+/// window its size and state
+pub fn restore(name: &str) {
+    let Some(layout) = load().into_iter().find(|layout| layout.name == name) else {
+        return;
+    };
+
+    let wanted: Vec<&str> = layout
+        .windows
+        .iter()
+        .map(|window| window.role.as_str())
+        .filter(|role| *role != MAIN_ROLE)
+        .collect();
+
+    // Pages the layout keeps in the main window come back first, so the main
+    // window always has a page left to show
+    let detached: Vec<(String, libadwaita::Window)> = WORKSPACE.with(|workspace| {
+        workspace
+            .borrow()
+            .as_ref()
+            .map(|workspace| {
+                workspace.detached.iter().map(|(name, page)| (name.clone(), page.window.clone())).collect()
+            })
+            .unwrap_or_default()
+    });
+    for (page_name, window) in detached {
+        if !wanted.contains(&page_name.as_str()) {
+            window.close();
+        }
+    }
+
+    for placement in &layout.windows {
+        if placement.role == MAIN_ROLE {
+            if let Some(window) =
+                WORKSPACE.with(|workspace| workspace.borrow().as_ref().map(|workspace| workspace.window.clone()))
+            {
+                apply(window.upcast_ref(), placement);
+            }
+            continue;
+        }
+
+        detach(&placement.role);
+        if let Some(window) = detached_window(&placement.role) {
+            apply(window.upcast_ref(), placement);
+        }
+    }
+}
+
+pub fn forget(name: &str) {
+    let mut layouts = load();
+    layouts.retain(|layout| layout.name != name);
+    save(&layouts);
+    refresh_menu();
+}
+
+/// Ask for a name and save the current windows under it
+pub fn present_save_dialog(parent: &impl IsA<gtk::Window>) {
+    let entry = gtk::Entry::builder()
+        .placeholder_text("e.g. Two monitors")
+        .activates_default(true)
+        .build();
+
+    let dialog = libadwaita::MessageDialog::builder()
+        .transient_for(parent)
+        .modal(true)
+        .heading("Save Window Layout")
+        .body("Remembers which pages have their own window, and each window's size, state and monitor.")
+        .extra_child(&entry)
+        .build();
+    dialog.add_responses(&[("cancel", "Cancel"), ("save", "Save")]);
+    dialog.set_response_appearance("save", libadwaita::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("save"));
+    dialog.set_close_response("cancel");
+
+    dialog.connect_response(None, move |_, response| {
+        let name = entry.text().trim().to_string();
+        if response == "save" && !name.is_empty() {
+            save_current(&name);
+        }
+    });
+    dialog.present();
+}
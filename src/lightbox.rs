@@ -0,0 +1,464 @@
+use gtk::prelude::*;
+use gtk::{gdk, gio, glib, Label, Orientation};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Largest decoded size; enough for a full-window view without holding huge bitmaps
+const MAX_IMAGE_SIZE: i32 = 4096;
+const MAX_ZOOM: f64 = 8.0;
+const ZOOM_STEP: f64 = 1.25;
+
+/// One image in the viewer
+#[derive(Debug, Clone)]
+pub struct LightboxImage {
+    pub url: String,
+    /// Alt text or headline shown under the image
+    pub caption: String,
+}
+
+thread_local! {
+    /// Window-wide overlay the viewer is shown in
+    static ROOT_OVERLAY: RefCell<Option<gtk::Overlay>> = const { RefCell::new(None) };
+}
+
+/// Register the overlay covering the window content, so the viewer can fill it
+pub fn install(overlay: &gtk::Overlay) {
+    ROOT_OVERLAY.with(|root| *root.borrow_mut() = Some(overlay.clone()));
+}
+
+/// Viewer state shared by its controls
+struct Viewer {
+    images: Vec<LightboxImage>,
+    index: usize,
+    /// Multiple of the fitted size; 1.0 fits the whole image in the window
+    zoom: f64,
+    texture: Option<gdk::Texture>,
+    /// Encoded bytes of the current image, written out as-is when saving
+    bytes: Option<Vec<u8>>,
+    /// Bumped on every navigation so stale loads are dropped
+    generation: u64,
+}
+
+/// Widgets the viewer updates
+#[derive(Clone)]
+struct ViewerWidgets {
+    root: gtk::Box,
+    scrolled: gtk::ScrolledWindow,
+    picture: gtk::Picture,
+    spinner: gtk::Spinner,
+    counter: Label,
+    caption: Label,
+    previous: gtk::Button,
+    next: gtk::Button,
+    save: gtk::Button,
+}
+
+type SharedViewer = Rc<RefCell<Viewer>>;
+
+/// Show `images` over the whole window, starting at `start`
+pub fn show(images: Vec<LightboxImage>, start: usize) {
+    if images.is_empty() {
+        return;
+    }
+    let Some(overlay) = ROOT_OVERLAY.with(|root| root.borrow().clone()) else {
+        tracing::warn!("Image viewer is not installed");
+        return;
+    };
+
+    let viewer = Rc::new(RefCell::new(Viewer {
+        index: start.min(images.len() - 1),
+        images,
+        zoom: 1.0,
+        texture: None,
+        bytes: None,
+        generation: 0,
+    }));
+
+    let widgets = build_widgets();
+    connect_controls(&viewer, &widgets, &overlay);
+
+    overlay.add_overlay(&widgets.root);
+    widgets.root.grab_focus();
+    load_current(&viewer, &widgets);
+}
+
+fn build_widgets() -> ViewerWidgets {
+    let root = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .focusable(true)
+        .build();
+    root.add_css_class("lightbox");
+
+    let header = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .margin_top(8)
+        .margin_bottom(8)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+
+    let counter = Label::builder()
+        .xalign(0.0)
+        .build();
+    counter.add_css_class("numeric");
+    header.append(&counter);
+
+    let caption = Label::builder()
+        .hexpand(true)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build();
+    header.append(&caption);
+
+    let zoom_out = gtk::Button::builder()
+        .icon_name("zoom-out-symbolic")
+        .tooltip_text("Zoom out")
+        .action_name("lightbox.zoom-out")
+        .build();
+    let zoom_in = gtk::Button::builder()
+        .icon_name("zoom-in-symbolic")
+        .tooltip_text("Zoom in")
+        .action_name("lightbox.zoom-in")
+        .build();
+    let save = gtk::Button::builder()
+        .icon_name("document-save-symbolic")
+        .tooltip_text("Save image…")
+        .sensitive(false)
+        .build();
+    let close = gtk::Button::builder()
+        .icon_name("window-close-symbolic")
+        .tooltip_text("Close (Esc)")
+        .action_name("lightbox.close")
+        .build();
+    for button in [&zoom_out, &zoom_in, &save, &close] {
+        button.add_css_class("flat");
+        header.append(button);
+    }
+    root.append(&header);
+
+    let picture = gtk::Picture::builder()
+        .can_shrink(true)
+        .content_fit(gtk::ContentFit::Contain)
+        .hexpand(true)
+        .vexpand(true)
+        .halign(gtk::Align::Fill)
+        .valign(gtk::Align::Fill)
+        .build();
+
+    let scrolled = gtk::ScrolledWindow::builder()
+        .child(&picture)
+        .hexpand(true)
+        .vexpand(true)
+        .build();
+
+    let spinner = gtk::Spinner::builder()
+        .width_request(32)
+        .height_request(32)
+        .halign(gtk::Align::Center)
+        .valign(gtk::Align::Center)
+        .build();
+
+    let previous = gtk::Button::builder()
+        .icon_name("go-previous-symbolic")
+        .tooltip_text("Previous (←)")
+        .action_name("lightbox.previous")
+        .halign(gtk::Align::Start)
+        .valign(gtk::Align::Center)
+        .margin_start(12)
+        .build();
+    let next = gtk::Button::builder()
+        .icon_name("go-next-symbolic")
+        .tooltip_text("Next (→)")
+        .action_name("lightbox.next")
+        .halign(gtk::Align::End)
+        .valign(gtk::Align::Center)
+        .margin_end(12)
+        .build();
+    for button in [&previous, &next] {
+        button.add_css_class("osd");
+        button.add_css_class("circular");
+    }
+
+    let stage = gtk::Overlay::new();
+    stage.set_child(Some(&scrolled));
+    stage.add_overlay(&spinner);
+    stage.add_overlay(&previous);
+    stage.add_overlay(&next);
+    root.append(&stage);
+
+    ViewerWidgets {
+        root,
+        scrolled,
+        picture,
+        spinner,
+        counter,
+        caption,
+        previous,
+        next,
+        save,
+    }
+}
+
+fn connect_controls(viewer: &SharedViewer, widgets: &ViewerWidgets, overlay: &gtk::Overlay) {
+    let actions = gio::SimpleActionGroup::new();
+
+    let close_action = gio::SimpleAction::new("close", None);
+    let root = widgets.root.clone();
+    let overlay_for_close = overlay.clone();
+    close_action.connect_activate(move |_, _| {
+        overlay_for_close.remove_overlay(&root);
+    });
+    actions.add_action(&close_action);
+
+    for (name, step) in [("previous", -1i64), ("next", 1i64)] {
+        let action = gio::SimpleAction::new(name, None);
+        let viewer = viewer.clone();
+        let widgets = widgets.clone();
+        action.connect_activate(move |_, _| {
+            {
+                let mut state = viewer.borrow_mut();
+                let count = state.images.len() as i64;
+                state.index = (state.index as i64 + step).rem_euclid(count) as usize;
+            }
+            load_current(&viewer, &widgets);
+        });
+        actions.add_action(&action);
+    }
+
+    for (name, factor) in [("zoom-in", ZOOM_STEP), ("zoom-out", 1.0 / ZOOM_STEP)] {
+        let action = gio::SimpleAction::new(name, None);
+        let viewer = viewer.clone();
+        let widgets = widgets.clone();
+        action.connect_activate(move |_, _| {
+            let zoom = viewer.borrow().zoom * factor;
+            set_zoom(&viewer, &widgets, zoom);
+        });
+        actions.add_action(&action);
+    }
+
+    let reset_action = gio::SimpleAction::new("zoom-reset", None);
+    let viewer_for_reset = viewer.clone();
+    let widgets_for_reset = widgets.clone();
+    reset_action.connect_activate(move |_, _| {
+        set_zoom(&viewer_for_reset, &widgets_for_reset, 1.0);
+    });
+    actions.add_action(&reset_action);
+
+    widgets.root.insert_action_group("lightbox", Some(&actions));
+
+    // Keyboard: Esc closes, arrows navigate, +/-/0 zoom
+    let keys = gtk::EventControllerKey::new();
+    let root = widgets.root.clone();
+    keys.connect_key_pressed(move |_, key, _, _| {
+        let action = match key {
+            gdk::Key::Escape => "lightbox.close",
+            gdk::Key::Left => "lightbox.previous",
+            gdk::Key::Right => "lightbox.next",
+            gdk::Key::plus | gdk::Key::equal | gdk::Key::KP_Add => "lightbox.zoom-in",
+            gdk::Key::minus | gdk::Key::KP_Subtract => "lightbox.zoom-out",
+            gdk::Key::_0 | gdk::Key::KP_0 => "lightbox.zoom-reset",
+            _ => return glib::Propagation::Proceed,
+        };
+        let _ = root.activate_action(action, None);
+        glib::Propagation::Stop
+    });
+    widgets.root.add_controller(keys);
+
+    // Pinch to zoom on touchpads and touchscreens
+    let pinch = gtk::GestureZoom::new();
+    let zoom_at_begin = Rc::new(RefCell::new(1.0));
+    let viewer_for_begin = viewer.clone();
+    let zoom_at_begin_for_begin = zoom_at_begin.clone();
+    pinch.connect_begin(move |_, _| {
+        *zoom_at_begin_for_begin.borrow_mut() = viewer_for_begin.borrow().zoom;
+    });
+    let viewer_for_pinch = viewer.clone();
+    let widgets_for_pinch = widgets.clone();
+    pinch.connect_scale_changed(move |_, scale| {
+        let zoom = *zoom_at_begin.borrow() * scale;
+        set_zoom(&viewer_for_pinch, &widgets_for_pinch, zoom);
+    });
+    widgets.scrolled.add_controller(pinch);
+
+    // Ctrl+scroll zooms; plain scrolling pans the zoomed image
+    let scroll = gtk::EventControllerScroll::new(gtk::EventControllerScrollFlags::VERTICAL);
+    let viewer_for_scroll = viewer.clone();
+    let widgets_for_scroll = widgets.clone();
+    scroll.connect_scroll(move |controller, _, dy| {
+        if !controller.current_event_state().contains(gdk::ModifierType::CONTROL_MASK) {
+            return glib::Propagation::Proceed;
+        }
+        let zoom = viewer_for_scroll.borrow().zoom * ZOOM_STEP.powf(-dy);
+        set_zoom(&viewer_for_scroll, &widgets_for_scroll, zoom);
+        glib::Propagation::Stop
+    });
+    widgets.scrolled.add_controller(scroll);
+
+    // Drag to pan while zoomed in
+    let drag = gtk::GestureDrag::new();
+    let drag_origin = Rc::new(RefCell::new((0.0, 0.0)));
+    let scrolled_for_begin = widgets.scrolled.clone();
+    let drag_origin_for_begin = drag_origin.clone();
+    drag.connect_drag_begin(move |_, _, _| {
+        *drag_origin_for_begin.borrow_mut() = (
+            scrolled_for_begin.hadjustment().value(),
+            scrolled_for_begin.vadjustment().value(),
+        );
+    });
+    let scrolled_for_update = widgets.scrolled.clone();
+    drag.connect_drag_update(move |_, dx, dy| {
+        let (x, y) = *drag_origin.borrow();
+        scrolled_for_update.hadjustment().set_value(x - dx);
+        scrolled_for_update.vadjustment().set_value(y - dy);
+    });
+    widgets.scrolled.add_controller(drag);
+
+    // Double-click toggles between fitted and 2x
+    let double_click = gtk::GestureClick::new();
+    let viewer_for_click = viewer.clone();
+    let widgets_for_click = widgets.clone();
+    double_click.connect_pressed(move |_, n_press, _, _| {
+        if n_press == 2 {
+            let zoom = if viewer_for_click.borrow().zoom > 1.0 { 1.0 } else { 2.0 };
+            set_zoom(&viewer_for_click, &widgets_for_click, zoom);
+        }
+    });
+    widgets.picture.add_controller(double_click);
+
+    let viewer_for_save = viewer.clone();
+    widgets.save.connect_clicked(move |button| {
+        save_current(&viewer_for_save, button);
+    });
+}
+
+/// Resize the picture to `zoom` times its fitted size
+fn set_zoom(viewer: &SharedViewer, widgets: &ViewerWidgets, zoom: f64) {
+    let zoom = zoom.clamp(1.0, MAX_ZOOM);
+    let mut state = viewer.borrow_mut();
+    state.zoom = zoom;
+
+    let Some(texture) = state.texture.as_ref() else {
+        return;
+    };
+
+    if zoom <= 1.0 {
+        widgets.picture.set_size_request(-1, -1);
+        return;
+    }
+
+    let view_width = widgets.scrolled.width().max(1) as f64;
+    let view_height = widgets.scrolled.height().max(1) as f64;
+    let fit = (view_width / texture.width() as f64).min(view_height / texture.height() as f64);
+    widgets.picture.set_size_request(
+        (texture.width() as f64 * fit * zoom).round() as i32,
+        (texture.height() as f64 * fit * zoom).round() as i32,
+    );
+}
+
+/// Load the image at the current index and update the header
+fn load_current(viewer: &SharedViewer, widgets: &ViewerWidgets) {
+    let (image, generation) = {
+        let mut state = viewer.borrow_mut();
+        state.generation += 1;
+        state.texture = None;
+        state.bytes = None;
+
+        let count = state.images.len();
+        widgets.counter.set_label(&format!("{} / {}", state.index + 1, count));
+        widgets.previous.set_visible(count > 1);
+        widgets.next.set_visible(count > 1);
+        (state.images[state.index].clone(), state.generation)
+    };
+
+    widgets.caption.set_label(&image.caption);
+    widgets.caption.set_tooltip_text(Some(&image.caption));
+    widgets.picture.set_paintable(None::<&gdk::Paintable>);
+    widgets.save.set_sensitive(false);
+    widgets.spinner.set_visible(true);
+    widgets.spinner.start();
+    set_zoom(viewer, widgets, 1.0);
+
+    let viewer = viewer.clone();
+    let widgets = widgets.clone();
+    glib::spawn_future_local(async move {
+        let loaded = match crate::images::fetch_image_bytes(&image.url).await {
+            Ok(bytes) => crate::images::decode_texture(bytes.clone(), MAX_IMAGE_SIZE, MAX_IMAGE_SIZE)
+                .await
+                .map(|texture| (bytes, texture))
+                .ok_or(None),
+            Err(e) => Err(Some(e)),
+        };
+
+        // The user moved on while this was loading
+        if viewer.borrow().generation != generation {
+            return;
+        }
+
+        widgets.spinner.stop();
+        widgets.spinner.set_visible(false);
+
+        match loaded {
+            Ok((bytes, texture)) => {
+                widgets.picture.set_paintable(Some(&texture));
+                widgets.save.set_sensitive(true);
+                let mut state = viewer.borrow_mut();
+                state.texture = Some(texture);
+                state.bytes = Some(bytes);
+            }
+            Err(None) => widgets.caption.set_label("Could not decode image"),
+            Err(Some(e)) => {
+                widgets.caption.set_label(&e.to_string());
+                let viewer = viewer.clone();
+                let widgets = widgets.clone();
+                crate::toasts::show_error(&e, move || {
+                    // Only if the viewer is still on the image that failed
+                    if viewer.borrow().generation == generation {
+                        load_current(&viewer, &widgets);
+                    }
+                });
+            }
+        }
+    });
+}
+
+/// File name for saving, taken from the URL's last path segment
+fn suggested_file_name(url: &str) -> String {
+    let name = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .unwrap_or("")
+        // Bluesky CDN URLs end in "<cid>@jpeg"
+        .replace('@', ".");
+
+    if name.is_empty() { "image".to_string() } else { name }
+}
+
+fn save_current(viewer: &SharedViewer, button: &gtk::Button) {
+    let (url, bytes) = {
+        let state = viewer.borrow();
+        let Some(bytes) = state.bytes.clone() else {
+            return;
+        };
+        (state.images[state.index].url.clone(), bytes)
+    };
+
+    let dialog = gtk::FileDialog::builder()
+        .title("Save Image")
+        .initial_name(suggested_file_name(&url))
+        .build();
+
+    let window = button.root().and_downcast::<gtk::Window>();
+    dialog.save(window.as_ref(), gio::Cancellable::NONE, move |result| {
+        let Ok(file) = result else {
+            return; // Cancelled
+        };
+        let Some(path) = file.path() else {
+            tracing::warn!("Can only save images to local files");
+            return;
+        };
+        if let Err(e) = std::fs::write(&path, &bytes) {
+            tracing::warn!("Failed to save image to {}: {}", path.display(), e);
+        }
+    });
+}
@@ -0,0 +1,140 @@
+use gtk::glib;
+use reqwest::Url;
+use std::time::Duration;
+
+/// Query parameters that only track where a click came from
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "gclsrc", "dclid", "msclkid", "yclid", "twclid", "ttclid", "igshid", "igsh",
+    "mc_cid", "mc_eid", "_ga", "_gl", "_hsenc", "_hsmi", "mkt_tok", "oly_anon_id", "oly_enc_id",
+    "vero_id", "wickedid", "rb_clickid", "s_cid", "cmpid", "ocid", "ncid", "ito", "xtor",
+    "ref_src", "ref_url", "smid", "smtyp", "sr_share", "at_medium", "at_campaign", "cid_source",
+    "__twitter_impression", "share_id", "spm",
+];
+
+/// Prefixes covering whole families of tracking parameters
+const TRACKING_PREFIXES: &[&str] = &["utm_", "pk_", "mtm_", "hsa_", "oly_"];
+
+/// Redirect pages that carry their destination in a query parameter: host, path, parameter
+const REDIRECTORS: &[(&str, &str, &str)] = &[
+    ("www.google.com", "/url", "q"),
+    ("www.google.com", "/url", "url"),
+    ("google.com", "/url", "q"),
+    ("l.facebook.com", "/l.php", "u"),
+    ("lm.facebook.com", "/l.php", "u"),
+    ("l.instagram.com", "/", "u"),
+    ("l.messenger.com", "/l.php", "u"),
+    ("www.youtube.com", "/redirect", "q"),
+    ("out.reddit.com", "/", "url"),
+    ("href.li", "/", ""),
+    ("t.umblr.com", "/redirect", "z"),
+    ("exit.sc", "/", "url"),
+    ("steamcommunity.com", "/linkfilter/", "url"),
+    ("www.linkedin.com", "/redir/redirect", "url"),
+];
+
+/// Link shorteners whose destination is only known by following the redirect
+const SHORTENERS: &[&str] = &[
+    "t.co", "bit.ly", "buff.ly", "ow.ly", "dlvr.it", "tinyurl.com", "trib.al", "lnkd.in", "fb.me",
+    "apple.co", "amzn.to", "wp.me", "ift.tt", "shorturl.at", "rebrand.ly", "cutt.ly", "is.gd",
+];
+
+/// How long to wait for a shortener before opening the short link as is
+const RESOLVE_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// Most redirector wrappers unwrapped from one link, in case they nest
+const MAX_UNWRAP: usize = 4;
+
+fn is_tracking_param(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    TRACKING_PARAMS.contains(&name.as_str()) || TRACKING_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// The destination a redirect page points at, if the URL is one
+fn unwrap_redirector(url: &Url) -> Option<Url> {
+    let host = url.host_str()?;
+    let (_, _, param) = REDIRECTORS
+        .iter()
+        .find(|(redirect_host, path, _)| host == *redirect_host && url.path() == *path)?;
+
+    // href.li puts the whole destination after the "?"
+    let target = if param.is_empty() {
+        url.query().map(str::to_string)?
+    } else {
+        url.query_pairs().find(|(name, _)| name == param)?.1.into_owned()
+    };
+    Url::parse(&target).ok().filter(|target| matches!(target.scheme(), "http" | "https"))
+}
+
+/// The canonical form of a link: redirector pages unwrapped and tracking
+/// parameters removed. Anything that isn't an http(s) URL comes back unchanged.
+pub fn canonicalize(url: &str) -> String {
+    let Ok(mut parsed) = Url::parse(url.trim()) else {
+        return url.to_string();
+    };
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return url.to_string();
+    }
+
+    for _ in 0..MAX_UNWRAP {
+        match unwrap_redirector(&parsed) {
+            Some(target) => parsed = target,
+            None => break,
+        }
+    }
+
+    if parsed.query().is_some() {
+        let kept: Vec<(String, String)> = parsed
+            .query_pairs()
+            .filter(|(name, _)| !is_tracking_param(name))
+            .map(|(name, value)| (name.into_owned(), value.into_owned()))
+            .collect();
+        if kept.is_empty() {
+            parsed.set_query(None);
+        } else {
+            parsed.query_pairs_mut().clear().extend_pairs(kept);
+        }
+    }
+
+    // Fragments like #xtor=RSS-1 or #utm_source=... are tracking too
+    if parsed.fragment().is_some_and(|fragment| {
+        fragment.split('&').next().and_then(|pair| pair.split('=').next()).is_some_and(is_tracking_param)
+    }) {
+        parsed.set_fragment(None);
+    }
+
+    parsed.to_string()
+}
+
+fn is_shortened(url: &str) -> bool {
+    Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host.trim_start_matches("www.").to_string()))
+        .is_some_and(|host| SHORTENERS.contains(&host.as_str()))
+}
+
+/// Where a shortened link leads, found by following its redirects; other links,
+/// and shorteners that don't answer in time, come back as they are
+pub async fn resolve(url: &str) -> String {
+    if !is_shortened(url) || crate::demo::is_enabled() {
+        return url.to_string();
+    }
+
+    match crate::http::client().head(url).timeout(RESOLVE_TIMEOUT).send().await {
+        Ok(response) => response.url().to_string(),
+        Err(e) => {
+            tracing::warn!("Couldn't follow {}: {}", url, e);
+            url.to_string()
+        }
+    }
+}
+
+/// Open a link in the browser, following shorteners and stripping trackers first
+pub fn open(url: &str) {
+    let url = url.to_string();
+    glib::spawn_future_local(async move {
+        let url = canonicalize(&resolve(&canonicalize(&url)).await);
+        if let Err(e) = open::that(&url) {
+            tracing::warn!("Failed to open URL: {}", e);
+        }
+    });
+}
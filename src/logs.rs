@@ -0,0 +1,315 @@
+use gtk::prelude::*;
+use gtk::{glib, Label, Orientation};
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::{span, Event, Level, Metadata};
+
+/// Events kept for the log viewer; older ones are still on stderr
+const MAX_ENTRIES: usize = 2000;
+
+/// Rows drawn at once; the viewer shows the newest
+const MAX_SHOWN: usize = 500;
+
+/// Levels offered by the viewer's filter, most severe first
+const LEVELS: [Level; 4] = [Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG];
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub time: chrono::DateTime<chrono::Local>,
+    pub level: Level,
+    /// Module the event came from, e.g. grapevine::gdelt
+    pub target: String,
+    pub message: String,
+}
+
+impl LogEntry {
+    fn to_line(&self) -> String {
+        format!("{} {:5} {}: {}", self.time.format("%H:%M:%S%.3f"), self.level, self.target, self.message)
+    }
+
+    /// Whether the message or module contains `search`, which is already lowercase
+    fn matches(&self, search: &str) -> bool {
+        search.is_empty()
+            || self.message.to_lowercase().contains(search)
+            || self.target.to_lowercase().contains(search)
+    }
+}
+
+static ENTRIES: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+/// Bumped on every event so open viewers know when to redraw
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Collects an event's message and any other fields into one line
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+}
+
+/// Prints events to stderr, as before, and keeps the latest for the log viewer.
+/// Spans aren't used, so they're only given ids.
+struct LogSubscriber {
+    next_span: AtomicU64,
+}
+
+impl tracing::Subscriber for LogSubscriber {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        // Dependencies only get a say when something goes wrong
+        if metadata.target().starts_with(env!("CARGO_PKG_NAME")) {
+            *metadata.level() <= Level::DEBUG
+        } else {
+            *metadata.level() <= Level::WARN
+        }
+    }
+
+    fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(self.next_span.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let entry = LogEntry {
+            time: chrono::Local::now(),
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_string(),
+            message: visitor.message + &visitor.fields,
+        };
+        eprintln!("{}", entry.to_line());
+
+        let mut entries = ENTRIES.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() == MAX_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+        GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+}
+
+/// Route log events to stderr and the in-app log viewer; call once at startup
+pub fn init() {
+    let subscriber = LogSubscriber { next_span: AtomicU64::new(1) };
+    if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
+        eprintln!("Logging was already set up: {}", e);
+    }
+}
+
+/// Events at `max_level` or more severe, oldest first
+fn recent(max_level: Level) -> Vec<LogEntry> {
+    let entries = ENTRIES.lock().unwrap_or_else(|e| e.into_inner());
+    entries.iter().filter(|entry| entry.level <= max_level).cloned().collect()
+}
+
+fn level_css_class(level: Level) -> &'static str {
+    match level {
+        Level::ERROR => "error",
+        Level::WARN => "warning",
+        Level::INFO => "accent",
+        _ => "dim-label",
+    }
+}
+
+fn create_entry_row(entry: &LogEntry) -> gtk::Box {
+    let row = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .build();
+    row.add_css_class("log-row");
+
+    let time_label = Label::builder()
+        .label(entry.time.format("%H:%M:%S").to_string())
+        .valign(gtk::Align::Start)
+        .build();
+    time_label.add_css_class("monospace");
+    time_label.add_css_class("dim-label");
+    row.append(&time_label);
+
+    let level_label = Label::builder()
+        .label(entry.level.as_str())
+        .width_chars(5)
+        .xalign(0.0)
+        .valign(gtk::Align::Start)
+        .build();
+    level_label.add_css_class("monospace");
+    level_label.add_css_class(level_css_class(entry.level));
+    row.append(&level_label);
+
+    let module = entry.target.strip_prefix(concat!(env!("CARGO_PKG_NAME"), "::")).unwrap_or(&entry.target);
+    let message_label = Label::builder()
+        .label(format!("{}: {}", module, entry.message))
+        .xalign(0.0)
+        .hexpand(true)
+        .wrap(true)
+        .wrap_mode(gtk::pango::WrapMode::WordChar)
+        .selectable(true)
+        .build();
+    message_label.add_css_class("monospace");
+    row.append(&message_label);
+
+    row
+}
+
+/// Fill the list with the events passing the filters, newest at the bottom
+fn fill_list(list: &gtk::Box, max_level: Level, search: &str) {
+    while let Some(child) = list.first_child() {
+        list.remove(&child);
+    }
+
+    let search = search.to_lowercase();
+    let entries: Vec<LogEntry> = recent(max_level).into_iter().filter(|entry| entry.matches(&search)).collect();
+
+    if entries.is_empty() {
+        let empty = libadwaita::StatusPage::builder()
+            .icon_name("utilities-terminal-symbolic")
+            .title("No Log Events")
+            .description("Nothing at this level has been logged yet.")
+            .vexpand(true)
+            .build();
+        empty.add_css_class("compact");
+        list.append(&empty);
+        return;
+    }
+
+    for entry in &entries[entries.len().saturating_sub(MAX_SHOWN)..] {
+        list.append(&create_entry_row(entry));
+    }
+}
+
+/// Window listing recent log events, filtered by level and text, following new
+/// events as they arrive
+pub fn present_log_viewer(parent: Option<&gtk::Window>) {
+    let list = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+
+    let scrolled = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .vexpand(true)
+        .child(&list)
+        .build();
+
+    let level_names: Vec<String> = LEVELS.iter().map(|level| level.as_str().to_lowercase()).collect();
+    let level_names: Vec<&str> = level_names.iter().map(String::as_str).collect();
+    let level_dropdown = gtk::DropDown::from_strings(&level_names);
+    level_dropdown.set_tooltip_text(Some("Most detailed level shown"));
+    // Info and above by default; debug events are noisy
+    level_dropdown.set_selected(2);
+
+    let search_entry = gtk::SearchEntry::builder()
+        .placeholder_text("Filter events")
+        .build();
+
+    let copy_button = gtk::Button::builder()
+        .icon_name("edit-copy-symbolic")
+        .tooltip_text("Copy shown events")
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::builder()
+        .title_widget(&search_entry)
+        .build();
+    header_bar.pack_start(&level_dropdown);
+    header_bar.pack_end(&copy_button);
+
+    let toolbar_view = libadwaita::ToolbarView::new();
+    toolbar_view.add_top_bar(&header_bar);
+    toolbar_view.set_content(Some(&scrolled));
+
+    let window = libadwaita::Window::builder()
+        .title("Log")
+        .default_width(820)
+        .default_height(560)
+        .content(&toolbar_view)
+        .build();
+    window.set_transient_for(parent);
+
+    let selected_level = {
+        let level_dropdown = level_dropdown.clone();
+        move || LEVELS.get(level_dropdown.selected() as usize).copied().unwrap_or(Level::INFO)
+    };
+
+    // Keeps following new events only while already scrolled to the end
+    let refill = {
+        let list = list.clone();
+        let scrolled = scrolled.clone();
+        let search_entry = search_entry.clone();
+        let selected_level = selected_level.clone();
+        move || {
+            let adjustment = scrolled.vadjustment();
+            let at_end = adjustment.value() + adjustment.page_size() >= adjustment.upper() - 1.0;
+            fill_list(&list, selected_level(), &search_entry.text());
+            if at_end {
+                let adjustment = adjustment.clone();
+                glib::idle_add_local_once(move || adjustment.set_value(adjustment.upper()));
+            }
+        }
+    };
+    refill();
+
+    let refill_for_level = refill.clone();
+    level_dropdown.connect_selected_notify(move |_| refill_for_level());
+    let refill_for_search = refill.clone();
+    search_entry.connect_search_changed(move |_| refill_for_search());
+
+    let search_for_copy = search_entry.clone();
+    copy_button.connect_clicked(move |button| {
+        let search = search_for_copy.text().to_lowercase();
+        let text: Vec<String> = recent(selected_level())
+            .iter()
+            .filter(|entry| entry.matches(&search))
+            .map(LogEntry::to_line)
+            .collect();
+        button.clipboard().set_text(&text.join("\n"));
+        crate::toasts::show("Log copied");
+    });
+
+    let mut seen = GENERATION.load(Ordering::Relaxed);
+    let window_weak = window.downgrade();
+    glib::timeout_add_seconds_local(1, move || {
+        if window_weak.upgrade().is_none() {
+            return glib::ControlFlow::Break;
+        }
+        let generation = GENERATION.load(Ordering::Relaxed);
+        if generation != seen {
+            seen = generation;
+            refill();
+        }
+        glib::ControlFlow::Continue
+    });
+
+    window.present();
+}
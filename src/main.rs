@@ -1,11 +1,90 @@
+mod account;
+mod article_nav;
+mod alt_audit;
+mod alt_text;
+mod compare;
+mod compose;
+mod credibility;
+mod crypto;
 mod data;
+mod dbus;
+mod demo;
+mod drafts;
+mod economy;
+mod connection;
 mod coordinates;
+mod country_breakdown;
+mod country_page;
+mod domain_filter;
+mod error;
+mod gdelt;
+mod http;
 mod global_affairs;
+mod governor;
+mod kiosk;
+mod layouts;
 mod firehose;
+mod focus;
+mod disasters;
+mod events;
+mod hashtag_feed;
+mod history;
+mod heatmap;
+mod tiles;
+mod world_clock;
+mod shortcuts;
+mod opengraph;
+mod reader;
+mod share;
+mod settings;
+mod preferences;
+mod read_articles;
+mod profile_page;
+mod profiles;
+mod export;
+mod feed_server;
+mod sentiment;
+mod images;
+mod image_cache;
+mod lightbox;
+mod logs;
+mod links;
+mod appview;
+mod translate;
+mod video;
+mod volume_chart;
+mod waking_up;
+mod wikipedia;
+mod query_builder;
+mod clustering;
+mod country_mentions;
+mod style;
+mod stats;
+mod themes;
+mod timeline;
+mod toasts;
+mod tray;
+mod bookmarks;
+mod digest;
+mod overlays;
+mod map_search;
+mod markets;
+mod metrics;
+mod notifications;
+mod safe_mode;
+mod slow_mode;
+mod spam;
+mod split_rules;
+mod startup;
+mod stream_notices;
+mod char_stats;
+mod summarize;
+mod secrets;
+mod json_store;
 
 use gtk::prelude::*;
 use gtk::{glib, Application, Label, Orientation, Align};
-use libadwaita::{prelude::*, ViewSwitcher, HeaderBar, ToolbarView, ApplicationWindow, ViewStack, StyleManager, ColorScheme};
+use libadwaita::{prelude::*, ViewSwitcher, HeaderBar, ToolbarView, ApplicationWindow, ViewStack};
 use std::cell::RefCell;
 use std::rc::Rc;
 use chrono_tz::Tz;
@@ -15,15 +94,83 @@ use global_affairs::create_global_affairs_view;
 use firehose::create_firehose_view;
 
 fn main() -> glib::ExitCode {
+    logs::init();
+    stats::init();
+
     // Initialize Tokio runtime for async operations
     let rt = tokio::runtime::Runtime::new().unwrap();
     let _guard = rt.enter();
 
     let app = Application::builder()
         .application_id(APP_ID)
+        .flags(gtk::gio::ApplicationFlags::HANDLES_COMMAND_LINE)
         .build();
 
-    app.connect_activate(build_ui);
+    app.connect_activate(activate);
+
+    // Fold this session's counters into the all-time statistics
+    app.connect_shutdown(|_| {
+        stats::save_session();
+        firehose::shutdown();
+        connection::save_cursor();
+        safe_mode::mark_clean_exit();
+    });
+
+    // Command lines from later launches are forwarded here by GApplication,
+    // so a second launch reuses this instance instead of spawning another
+    app.connect_command_line(|app, command_line| {
+        let mut args: Vec<String> = command_line
+            .arguments()
+            .iter()
+            .skip(1)
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+
+        // Demo mode only applies to a fresh window; a running instance stays live
+        if let Some(index) = args.iter().position(|arg| arg == "--demo") {
+            args.remove(index);
+            if app.active_window().is_none() {
+                demo::enable();
+            }
+        }
+
+        // Safe mode also only applies to a fresh window
+        if let Some(index) = args.iter().position(|arg| arg == "--safe-mode") {
+            args.remove(index);
+            if app.active_window().is_none() {
+                safe_mode::enable();
+            }
+        }
+
+        // Like demo mode, kiosk mode is only entered when starting up
+        let kiosk = match args.iter().position(|arg| arg == "--kiosk") {
+            Some(index) => {
+                args.remove(index);
+                app.active_window().is_none()
+            }
+            None => false,
+        };
+
+        app.activate();
+
+        // The window waits for an answer when offering safe mode after a crash
+        if app.lookup_action("search").is_none() {
+            if kiosk || query_from_args(&args).is_some() {
+                tracing::warn!("Ignoring command line actions until the window is open");
+            }
+            return glib::ExitCode::SUCCESS;
+        }
+
+        if kiosk {
+            app.activate_action("kiosk", None);
+        }
+
+        if let Some(query) = query_from_args(&args) {
+            app.activate_action("search", Some(&query.to_variant()));
+        }
+
+        glib::ExitCode::SUCCESS
+    });
 
     let exit_code = app.run();
 
@@ -34,10 +181,45 @@ fn main() -> glib::ExitCode {
     exit_code
 }
 
+/// Present the existing window if there is one, otherwise build the UI
+fn activate(app: &Application) {
+    if let Some(window) = app.active_window() {
+        window.present();
+        return;
+    }
+
+    if !safe_mode::is_enabled() && safe_mode::previous_session_crashed() {
+        let app_for_start = app.clone();
+        safe_mode::offer_after_crash(app, move || build_ui(&app_for_start));
+        return;
+    }
+
+    build_ui(app);
+}
+
+/// Turn command line arguments into a GDELT query. Accepts either plain
+/// words (`grapevine climate summit`) or a `grapevine:search?q=...` URI.
+fn query_from_args(args: &[String]) -> Option<String> {
+    if let Some(uri) = args.iter().find(|arg| arg.starts_with("grapevine:")) {
+        let (_, params) = uri.split_once('?')?;
+        return params
+            .split('&')
+            .find_map(|param| param.strip_prefix("q="))
+            .map(|q| urlencoding::decode(&q.replace('+', " ")).map(|q| q.into_owned()).unwrap_or_default())
+            .filter(|q| !q.is_empty());
+    }
+
+    let query = args.join(" ");
+    if query.trim().is_empty() {
+        None
+    } else {
+        Some(query)
+    }
+}
+
 fn build_ui(app: &Application) {
-    // Enable dark theme support
-    let style_manager = StyleManager::default();
-    style_manager.set_color_scheme(ColorScheme::PreferDark);
+    // Cleared again on a clean shutdown, so a crash can be noticed next time
+    safe_mode::mark_running();
 
     // Create the main stack for content
     let stack = ViewStack::builder()
@@ -47,25 +229,64 @@ fn build_ui(app: &Application) {
     let current_query = Rc::new(RefCell::new(String::new()));
     let results_list_ref = Rc::new(RefCell::new(None::<gtk::ListBox>));
     let marker_layer_ref = Rc::new(RefCell::new(None::<libshumate::MarkerLayer>));
+    let search_entry_ref = Rc::new(RefCell::new(None::<gtk::SearchEntry>));
+
+    // Load persisted preferences
+    let settings = Rc::new(RefCell::new(settings::Settings::load()));
+    style::apply_theme(settings.borrow().theme);
+    http::set_proxy(&settings.borrow().http_proxy);
+    translate::register_settings(settings.clone());
+    summarize::register_settings(settings.clone());
+    tiles::register_settings(settings.clone());
+    account::register_settings(settings.clone());
+    alt_text::register_settings(settings.clone());
+    domain_filter::register_settings(settings.clone());
+    feed_server::apply_settings(settings.clone());
+    startup::schedule(startup::Priority::High, "Keyring", secrets::load_bluesky_app_password(settings.clone()));
+
+    // Images cached by earlier sessions may have pushed the cache over its cap
+    startup::schedule(startup::Priority::Low, "Image cache", image_cache::trim());
+
+    // Scheduled posts go out while the app runs; ones that came due while it was closed are held as missed
+    startup::schedule(startup::Priority::Low, "Scheduled posts", async {
+        drafts::start_scheduler();
+    });
 
     // State to track 12/24 hour format (default to 12-hour)
-    let use_12_hour = Rc::new(RefCell::new(true));
+    let use_12_hour = Rc::new(RefCell::new(!settings.borrow().use_24_hour));
 
     // Create Global Affairs view with map
     let global_affairs_view = create_global_affairs_view(
         current_query.clone(),
         results_list_ref.clone(),
         marker_layer_ref.clone(),
-        use_12_hour.clone()
+        search_entry_ref.clone(),
+        use_12_hour.clone(),
+        settings.clone()
     );
     let _global_affairs_page = stack.add_titled(&global_affairs_view, Some("global-affairs"), "Global Affairs");
     stack.page(&global_affairs_view).set_icon_name(None);
 
     // Create Firehose view
-    let (firehose_view, firehose_control) = create_firehose_view();
+    let (firehose_view, firehose_control) = create_firehose_view(settings.clone());
     let _firehose_page = stack.add_titled(&firehose_view, Some("firehose"), "Firehose");
     stack.page(&firehose_view).set_icon_name(None);
 
+    // Create Bookmarks view
+    let bookmarks_view = bookmarks::create_bookmarks_view();
+    let _bookmarks_page = stack.add_titled(&bookmarks_view, Some("bookmarks"), "Bookmarks");
+    stack.page(&bookmarks_view).set_icon_name(None);
+
+    // Create Notifications view for the signed-in account
+    let notifications_view = notifications::create_notifications_view();
+    let _notifications_page = stack.add_titled(&notifications_view, Some("notifications"), "Notifications");
+    stack.page(&notifications_view).set_icon_name(None);
+
+    // Create Markets view with the pinned currency pairs
+    let markets_view = markets::create_markets_view(settings.clone());
+    let _markets_page = stack.add_titled(&markets_view, Some("markets"), "Markets");
+    stack.page(&markets_view).set_icon_name(None);
+
     // Create floating ViewSwitcher (compact version)
     let view_switcher = ViewSwitcher::builder()
         .stack(&stack)
@@ -96,10 +317,10 @@ fn build_ui(app: &Application) {
 
     // Make the time label clickable to toggle between 12/24 hour format
     let time_label_gesture = gtk::GestureClick::new();
-    let use_12_hour_clone = use_12_hour.clone();
-    time_label_gesture.connect_released(move |_, _, _, _| {
-        let mut is_12_hour = use_12_hour_clone.borrow_mut();
-        *is_12_hour = !*is_12_hour;
+    time_label_gesture.connect_released(|gesture, _, _, _| {
+        if let Some(widget) = gesture.widget() {
+            let _ = widget.activate_action("app.toggle-clock-format", None);
+        }
     });
     time_label.add_controller(time_label_gesture);
 
@@ -147,20 +368,76 @@ fn build_ui(app: &Application) {
     stack.connect_visible_child_notify(move |stack| {
         if let Some(visible_child) = stack.visible_child() {
             if let Some(name) = stack.page(&visible_child).name() {
-                if name.as_str() == "firehose" {
-                    refresh_button_clone.set_visible(false);
-                    plus_button_clone.set_visible(true);
-                } else {
-                    refresh_button_clone.set_visible(true);
-                    plus_button_clone.set_visible(false);
-                }
+                refresh_button_clone.set_visible(name.as_str() == "global-affairs");
+                plus_button_clone.set_visible(name.as_str() == "firehose");
             }
         }
     });
 
+    // Open on the page the last session ended on
+    let last_page = settings.borrow().last_page.clone();
+    if !last_page.is_empty() && stack.child_by_name(&last_page).is_some() {
+        stack.set_visible_child_name(&last_page);
+    }
+
+    // Create primary menu
+    let primary_menu = gtk::gio::Menu::new();
+
+    let app_section = gtk::gio::Menu::new();
+    app_section.append(Some("Preferences"), Some("app.preferences"));
+    app_section.append(Some("Keyboard Shortcuts"), Some("app.shortcuts"));
+    app_section.append(Some("Export Articles…"), Some("app.export"));
+    app_section.append(Some("Weekly Digest…"), Some("app.digest"));
+    app_section.append(Some("Daily Digest…"), Some("app.daily-digest"));
+    app_section.append(Some("Coverage Timeline"), Some("app.timeline"));
+    app_section.append(Some("Import Split Rules…"), Some("app.import-split"));
+    app_section.append(Some("Follow Hashtag…"), Some("app.follow-hashtag"));
+    app_section.append(Some("Statistics"), Some("app.stats"));
+    app_section.append(Some("World Waking Up"), Some("app.waking-up"));
+    app_section.append(Some("Kiosk Mode"), Some("app.kiosk"));
+    app_section.append(Some("Focus Mode"), Some("app.focus-mode"));
+    app_section.append(Some("Log"), Some("app.logs"));
+    primary_menu.append_section(None, &app_section);
+
+    // Filled in by layouts::install once the window exists
+    let layouts_menu = gtk::gio::Menu::new();
+    let layouts_section = gtk::gio::Menu::new();
+    layouts_section.append_submenu(Some("Window Layouts"), &layouts_menu);
+    primary_menu.append_section(None, &layouts_section);
+
+    let about_section = gtk::gio::Menu::new();
+    about_section.append(Some("About Grapevine"), Some("app.about"));
+    about_section.append(Some("Quit"), Some("app.quit"));
+    primary_menu.append_section(None, &about_section);
+
+    let menu_button = gtk::MenuButton::builder()
+        .icon_name("open-menu-symbolic")
+        .tooltip_text("Main menu")
+        .menu_model(&primary_menu)
+        .build();
+
+    // Write a post to Bluesky (app.compose)
+    let compose_button = gtk::Button::builder()
+        .icon_name("document-edit-symbolic")
+        .tooltip_text("New post")
+        .action_name("app.compose")
+        .build();
+
+    // Live posts-per-second from the firehose
+    let throughput_meter = firehose_control.create_throughput_meter();
+
     // Pack widgets into headerbar
     header_bar.pack_start(&refresh_button);
-    header_bar.set_title_widget(Some(&time_label));
+    header_bar.pack_start(&throughput_meter);
+    let clock_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    clock_box.append(&time_label);
+    clock_box.append(&world_clock::create_world_clock(settings.clone(), use_12_hour.clone()));
+    header_bar.set_title_widget(Some(&clock_box));
+    header_bar.pack_end(&menu_button);
+    header_bar.pack_end(&compose_button);
     header_bar.pack_end(&plus_button);
 
     // Update time every second using local timezone with proper abbreviation
@@ -170,11 +447,11 @@ fn build_ui(app: &Application) {
     let tz: Tz = iana_time_zone::get_timezone()
         .ok()
         .and_then(|tz_str| {
-            eprintln!("Detected timezone: {}", tz_str);
+            tracing::debug!("Detected timezone: {}", tz_str);
             tz_str.parse().ok()
         })
         .unwrap_or_else(|| {
-            eprintln!("Failed to detect timezone, using UTC");
+            tracing::warn!("Failed to detect timezone, using UTC");
             chrono_tz::UTC
         });
 
@@ -200,197 +477,551 @@ fn build_ui(app: &Application) {
         .build();
 
     toolbar_view.add_top_bar(&header_bar);
+    toolbar_view.add_top_bar(&safe_mode::create_banner());
     toolbar_view.set_content(Some(&overlay));
 
+    // Bottom navigation that stands in for the floating switcher on phones
+    let switcher_bar = libadwaita::ViewSwitcherBar::builder()
+        .stack(&stack)
+        .build();
+    toolbar_view.add_bottom_bar(&switcher_bar);
+
     // Create main window
     let window = ApplicationWindow::builder()
         .application(app)
-        .title("Grapevine")
-        .default_width(800)
-        .default_height(600)
+        .title(if demo::is_enabled() {
+            "Grapevine (demo)"
+        } else if safe_mode::is_enabled() {
+            "Grapevine (safe mode)"
+        } else {
+            "Grapevine"
+        })
+        .default_width(settings.borrow().window_width)
+        .default_height(settings.borrow().window_height)
+        .maximized(settings.borrow().window_maximized)
+        .width_request(360)
+        .height_request(294)
         .build();
 
-    // Add Ctrl+Q keyboard shortcut to close the window
+    // Below phone width the map hides behind a toggle, splits become swipeable
+    // and the page switcher moves to the bottom
+    match libadwaita::BreakpointCondition::parse("max-width: 500sp") {
+        Ok(condition) => {
+            let breakpoint = libadwaita::Breakpoint::new(condition);
+            breakpoint.add_setters(&[
+                (&switcher_bar.clone().upcast::<glib::Object>(), "reveal", true),
+                (&view_switcher.clone().upcast::<glib::Object>(), "visible", false),
+            ]);
+
+            let firehose_control_for_apply = firehose_control.clone();
+            breakpoint.connect_apply(move |_| {
+                global_affairs::set_compact(true);
+                firehose_control_for_apply.set_compact(true);
+            });
+            let firehose_control_for_unapply = firehose_control.clone();
+            breakpoint.connect_unapply(move |_| {
+                global_affairs::set_compact(false);
+                firehose_control_for_unapply.set_compact(false);
+            });
+
+            window.add_breakpoint(breakpoint);
+        }
+        Err(e) => tracing::warn!("Invalid breakpoint condition: {}", e),
+    }
+
+    // Held while the window is hidden in background mode so the app keeps running
+    let background_hold: Rc<RefCell<Option<gtk::gio::ApplicationHoldGuard>>> = Rc::new(RefCell::new(None));
+    let quitting = Rc::new(RefCell::new(false));
+
+    // Add Ctrl+Q keyboard shortcut to close the window; this really quits even in background mode
     let quit_action = gtk::gio::SimpleAction::new("quit", None);
     let window_weak = window.downgrade();
+    let quitting_for_action = quitting.clone();
+    let background_hold_for_quit = background_hold.clone();
     quit_action.connect_activate(move |_, _| {
+        *quitting_for_action.borrow_mut() = true;
+        background_hold_for_quit.borrow_mut().take();
+        tray::hide();
         if let Some(window) = window_weak.upgrade() {
             window.close();
         }
     });
     app.add_action(&quit_action);
-    app.set_accels_for_action("app.quit", &["<Primary>q"]);
-
-    // Load custom CSS for floating switcher, map markers, statusline, firehose messages, and news articles
-    let css_provider = gtk::CssProvider::new();
-    css_provider.load_from_data(
-        ".floating-switcher {
-            background-color: alpha(@window_bg_color, 0.85);
-            border-radius: 12px;
-            padding: 8px;
-            box-shadow: 0 4px 12px alpha(black, 0.3);
-        }
-        .map-marker {
-            background-color: alpha(@accent_bg_color, 0.75);
-            border-radius: 16px;
-            padding: 4px 10px;
-            font-size: 11px;
-            font-weight: bold;
-            min-height: 0;
-            min-width: 0;
-            box-shadow: 0 2px 6px alpha(black, 0.4);
-        }
-        .map-marker:hover {
-            background-color: alpha(@accent_bg_color, 0.95);
-            box-shadow: 0 3px 8px alpha(black, 0.5);
-        }
-        .map-popover > contents {
-            background-color: alpha(@card_bg_color, 0.95);
-            border-radius: 12px;
-            box-shadow: 0 4px 16px alpha(black, 0.6);
-        }
-        .time-display {
-            font-size: 13px;
-            font-weight: 600;
-            padding: 4px 12px;
-            background-color: alpha(@accent_bg_color, 0.15);
-            border-radius: 6px;
-        }
-        .firehose-message {
-            background-color: alpha(@card_bg_color, 0.5);
-            border-radius: 8px;
-            padding: 3px 4px;
-            border: 1px solid alpha(@borders, 0.5);
-        }
-        .firehose-timestamp {
-            color: alpha(@window_fg_color, 0.55);
-        }
-        .firehose-rkey {
-            color: @accent_color;
-            font-weight: 600;
-        }
-        .firehose-text {
-            line-height: 1.4;
-        }
-        .news-article-card {
-            background-color: @card_bg_color;
-            border-radius: 12px;
-            overflow: hidden;
-            border: 1px solid alpha(@borders, 0.2);
-            transition: all 200ms cubic-bezier(0.4, 0, 0.2, 1);
-        }
-        .news-article-card:hover {
-            border-color: alpha(@accent_bg_color, 0.3);
-            box-shadow: 0 4px 12px alpha(black, 0.12);
-            transform: translateY(-2px);
-        }
-        .article-thumbnail {
-            background-color: alpha(@window_bg_color, 0.3);
-            height: 140px;
-            border-radius: 8px;
-            margin: 8px;
-        }
-        .article-title {
-            font-size: 14px;
-            font-weight: 600;
-            line-height: 1.35;
-            color: @window_fg_color;
-        }
-        .article-domain {
-            font-size: 11px;
-            font-weight: 500;
-            color: alpha(@window_fg_color, 0.5);
-            margin-top: 2px;
-        }
-        .badge {
-            background-color: alpha(@accent_bg_color, 0.15);
-            border-radius: 6px;
-            padding: 3px 8px;
-            font-size: 10px;
-            font-weight: 600;
-            min-height: 0;
-            text-transform: uppercase;
-            letter-spacing: 0.5px;
-        }
-        .badge-country {
-            background-color: alpha(@accent_bg_color, 0.25);
-            color: @accent_fg_color;
-            transition: all 150ms ease;
-        }
-        .badge-country:hover {
-            background-color: @accent_bg_color;
-            box-shadow: 0 2px 6px alpha(@accent_bg_color, 0.4);
-        }
-        .badge-time {
-            background-color: alpha(@window_fg_color, 0.08);
-            color: alpha(@window_fg_color, 0.7);
-        }
-        .badge-lang {
-            background-color: alpha(@warning_bg_color, 0.2);
-            color: @warning_fg_color;
-        }
-        .badge-positive {
-            background-color: alpha(@success_bg_color, 0.2);
-            color: @success_fg_color;
-        }
-        .badge-negative {
-            background-color: alpha(@error_bg_color, 0.2);
-            color: @error_fg_color;
-        }
-        .badge-neutral {
-            background-color: alpha(@window_fg_color, 0.08);
-            color: alpha(@window_fg_color, 0.7);
-        }
-        .popover-currency-section {
-            padding: 8px;
-            background-color: alpha(@accent_bg_color, 0.08);
-            border-radius: 8px;
-            border: 1px solid alpha(@accent_bg_color, 0.15);
-        }
-        .currency-rate {
-            font-family: monospace;
-            color: @accent_color;
-            font-weight: 700;
-        }
-        .currency-change-positive {
-            color: @success_color;
-        }
-        .currency-change-negative {
-            color: @error_color;
-        }
-        .popover-article-row {
-            background-color: alpha(@card_bg_color, 0.3);
-            border-radius: 6px;
-            border: 1px solid alpha(@borders, 0.15);
-            transition: all 150ms ease;
-        }
-        .popover-article-row:hover {
-            background-color: alpha(@card_bg_color, 0.6);
-            border-color: alpha(@accent_bg_color, 0.3);
-            box-shadow: 0 2px 6px alpha(black, 0.08);
-        }
-        .popover-article-title {
-            font-size: 13px;
-            font-weight: 600;
-            line-height: 1.3;
-        }
-        .popover-article-meta {
-            font-size: 11px;
-            color: alpha(@window_fg_color, 0.55);
-        }
-        .popover-article-time {
-            font-size: 10px;
-            color: alpha(@window_fg_color, 0.45);
-            font-weight: 500;
-        }"
-    );
 
-    gtk::style_context_add_provider_for_display(
-        &gtk::prelude::WidgetExt::display(&window),
-        &css_provider,
-        gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
+    // Restoring the window (from the tray or a relaunch) leaves background mode
+    let background_hold_for_restore = background_hold.clone();
+    window.connect_visible_notify(move |window| {
+        if window.is_visible() {
+            background_hold_for_restore.borrow_mut().take();
+            tray::hide();
+        }
+    });
+
+    // Remember the window and page for next launch; runs before the handler
+    // below, which may keep the window open
+    let settings_for_session = settings.clone();
+    let stack_for_session = stack.clone();
+    window.connect_close_request(move |window| {
+        let (width, height) = window.default_size();
+        let mut settings = settings_for_session.borrow_mut();
+        if width > 0 && height > 0 {
+            settings.window_width = width;
+            settings.window_height = height;
+        }
+        settings.window_maximized = window.is_maximized();
+        if let Some(page) = stack_for_session.visible_child_name() {
+            settings.last_page = page.to_string();
+        }
+        settings.save();
+        glib::Propagation::Proceed
+    });
+
+    // Optionally summarize the session before the window closes
+    let settings_for_close = settings.clone();
+    let app_for_close = app.clone();
+    let quit_confirmed = Rc::new(RefCell::new(false));
+    // Set when closing found no tray to hide in, so the close goes through
+    let no_tray_host = Rc::new(RefCell::new(false));
+    window.connect_close_request(move |window| {
+        // In background mode closing only hides the window; streaming continues behind a tray icon.
+        // Hidden only once a tray host is confirmed, or there'd be no way back to the window.
+        if settings_for_close.borrow().run_in_background && !*quitting.borrow() && !*no_tray_host.borrow() {
+            let window = window.clone();
+            let app = app_for_close.clone();
+            let background_hold = background_hold.clone();
+            let no_tray_host = no_tray_host.clone();
+            tray::check_host(move |available| {
+                if !available {
+                    tracing::warn!("No system tray available, closing instead of running in the background");
+                    *no_tray_host.borrow_mut() = true;
+                    window.close();
+                    return;
+                }
+
+                window.set_visible(false);
+                *background_hold.borrow_mut() = Some(app.hold());
+
+                let window_weak = window.downgrade();
+                tray::show(move || {
+                    if let Some(window) = window_weak.upgrade() {
+                        window.present();
+                    }
+                });
+            });
+            return glib::Propagation::Stop;
+        }
+
+        if *quit_confirmed.borrow() || !settings_for_close.borrow().show_session_summary {
+            return glib::Propagation::Proceed;
+        }
+
+        let dialog = libadwaita::MessageDialog::builder()
+            .transient_for(window)
+            .modal(true)
+            .heading("Session Summary")
+            .body(stats::session_summary())
+            .build();
+        dialog.add_responses(&[("cancel", "Keep Running"), ("quit", "Quit")]);
+        dialog.set_response_appearance("quit", libadwaita::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("quit"));
+        dialog.set_close_response("cancel");
+
+        let quit_confirmed = quit_confirmed.clone();
+        let window = window.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "quit" {
+                *quit_confirmed.borrow_mut() = true;
+                window.close();
+            }
+        });
+        dialog.present();
+
+        glib::Propagation::Stop
+    });
+
+    // Refresh the Global Affairs articles (same as the refresh button)
+    let refresh_action = gtk::gio::SimpleAction::new("refresh", None);
+    let refresh_button_for_action = refresh_button.clone();
+    refresh_action.connect_activate(move |_, _| {
+        refresh_button_for_action.emit_clicked();
+    });
+    app.add_action(&refresh_action);
+
+    // Pages can move out to windows of their own, and the arrangement saved as a layout
+    layouts::install(&window, &stack, &layouts_menu);
+
+    // Switch the visible page by name, e.g. app.switch-view::firehose
+    let switch_view_action = gtk::gio::SimpleAction::new("switch-view", Some(glib::VariantTy::STRING));
+    switch_view_action.connect_activate(move |_, param| {
+        if let Some(name) = param.and_then(|p| p.get::<String>()) {
+            layouts::show_page(&name);
+        }
+    });
+    app.add_action(&switch_view_action);
+
+    let detach_page_action = gtk::gio::SimpleAction::new("detach-page", None);
+    detach_page_action.connect_activate(move |_, _| {
+        layouts::detach_visible_page();
+    });
+    app.add_action(&detach_page_action);
+
+    let save_layout_action = gtk::gio::SimpleAction::new("save-layout", None);
+    let window_for_layout = window.clone();
+    save_layout_action.connect_activate(move |_, _| {
+        layouts::present_save_dialog(&window_for_layout);
+    });
+    app.add_action(&save_layout_action);
+
+    let restore_layout_action = gtk::gio::SimpleAction::new("restore-layout", Some(glib::VariantTy::STRING));
+    restore_layout_action.connect_activate(move |_, param| {
+        if let Some(name) = param.and_then(|p| p.get::<String>()) {
+            layouts::restore(&name);
+        }
+    });
+    app.add_action(&restore_layout_action);
+
+    let forget_layout_action = gtk::gio::SimpleAction::new("forget-layout", Some(glib::VariantTy::STRING));
+    forget_layout_action.connect_activate(move |_, param| {
+        if let Some(name) = param.and_then(|p| p.get::<String>()) {
+            layouts::forget(&name);
+        }
+    });
+    app.add_action(&forget_layout_action);
+
+    // Focus the search entry of the visible page
+    let focus_search_action = gtk::gio::SimpleAction::new("focus-search", None);
+    let stack_for_search = stack.clone();
+    let firehose_control_for_search = firehose_control.clone();
+    let search_entry_ref_clone = search_entry_ref.clone();
+    focus_search_action.connect_activate(move |_, _| {
+        if stack_for_search.visible_child_name().as_deref() == Some("firehose") {
+            firehose_control_for_search.focus_search();
+        } else if let Some(search_entry) = search_entry_ref_clone.borrow().as_ref() {
+            // Toggle the hidden GDELT search entry, focusing it when shown
+            if search_entry.is_visible() && search_entry.has_focus() {
+                search_entry.set_visible(false);
+            } else {
+                search_entry.set_visible(true);
+                search_entry.grab_focus();
+            }
+        }
+    });
+    app.add_action(&focus_search_action);
+
+    // Run a GDELT search, used when another launch passes a query
+    let search_action = gtk::gio::SimpleAction::new("search", Some(glib::VariantTy::STRING));
+    let search_entry_ref_for_query = search_entry_ref.clone();
+    search_action.connect_activate(move |_, param| {
+        if let Some(query) = param.and_then(|p| p.get::<String>()) {
+            layouts::show_page("global-affairs");
+            if let Some(search_entry) = search_entry_ref_for_query.borrow().as_ref() {
+                search_entry.set_text(&query);
+                search_entry.set_visible(true);
+                search_entry.emit_activate();
+            }
+        }
+    });
+    app.add_action(&search_action);
+
+    // Add and close firehose splits
+    let add_split_action = gtk::gio::SimpleAction::new("add-split", None);
+    let firehose_control_for_add = firehose_control.clone();
+    add_split_action.connect_activate(move |_, _| {
+        layouts::show_page("firehose");
+        firehose_control_for_add.add_split();
+    });
+    app.add_action(&add_split_action);
+
+    // Add a split filtering by a keyword, used by the D-Bus interface
+    let add_filter_action = gtk::gio::SimpleAction::new("add-filter", Some(glib::VariantTy::STRING));
+    let firehose_control_for_filter = firehose_control.clone();
+    add_filter_action.connect_activate(move |_, param| {
+        if let Some(keyword) = param.and_then(|p| p.get::<String>()) {
+            layouts::show_page("firehose");
+            firehose_control_for_filter.add_keyword_split(&keyword);
+        }
+    });
+    app.add_action(&add_filter_action);
+
+    // Split following the reaction to a news story, from article cards
+    let watch_story_action = gtk::gio::SimpleAction::new("watch-story", Some(glib::VariantTy::new("(ss)").unwrap()));
+    let firehose_control_for_story = firehose_control.clone();
+    watch_story_action.connect_activate(move |_, param| {
+        if let Some((title, domain)) = param.and_then(|p| p.get::<(String, String)>()) {
+            layouts::show_page("firehose");
+            firehose_control_for_story.watch_story(&title, &domain);
+        }
+    });
+    app.add_action(&watch_story_action);
+
+    // Columns of hydrated posts searched by hashtag, beside the raw stream
+    let follow_hashtag_action = gtk::gio::SimpleAction::new("follow-hashtag", None);
+    let firehose_control_for_hashtag = firehose_control.clone();
+    let window_for_hashtag = window.clone();
+    follow_hashtag_action.connect_activate(move |_, _| {
+        let firehose_control = firehose_control_for_hashtag.clone();
+        hashtag_feed::present_follow_dialog(&window_for_hashtag, move |tag| {
+            layouts::show_page("firehose");
+            firehose_control.follow_hashtag(tag);
+        });
+    });
+    app.add_action(&follow_hashtag_action);
+
+    let close_split_action = gtk::gio::SimpleAction::new("close-split", None);
+    let firehose_control_for_close = firehose_control.clone();
+    close_split_action.connect_activate(move |_, _| {
+        firehose_control_for_close.close_last_split();
+    });
+    app.add_action(&close_split_action);
+
+    let import_split_action = gtk::gio::SimpleAction::new("import-split", None);
+    let firehose_control_for_import = firehose_control.clone();
+    let window_for_import = window.clone();
+    import_split_action.connect_activate(move |_, _| {
+        layouts::show_page("firehose");
+        firehose_control_for_import.import_split(Some(window_for_import.upcast_ref()));
+    });
+    app.add_action(&import_split_action);
+
+    // Pinned countries ordered by when their business day starts
+    let waking_up_action = gtk::gio::SimpleAction::new("waking-up", None);
+    let window_for_waking_up = window.clone();
+    let use_12_hour_for_waking_up = use_12_hour.clone();
+    waking_up_action.connect_activate(move |_, _| {
+        waking_up::present_waking_up(Some(window_for_waking_up.upcast_ref()), use_12_hour_for_waking_up.clone());
+    });
+    app.add_action(&waking_up_action);
+
+    // Recent log events, for diagnosing failures without a terminal
+    let logs_action = gtk::gio::SimpleAction::new("logs", None);
+    let window_for_logs = window.clone();
+    logs_action.connect_activate(move |_, _| {
+        logs::present_log_viewer(Some(window_for_logs.upcast_ref()));
+    });
+    app.add_action(&logs_action);
+
+    // Toggle between 12 and 24 hour clocks
+    let toggle_clock_action = gtk::gio::SimpleAction::new("toggle-clock-format", None);
+    let use_12_hour_for_action = use_12_hour.clone();
+    let settings_for_clock = settings.clone();
+    toggle_clock_action.connect_activate(move |_, _| {
+        let mut is_12_hour = use_12_hour_for_action.borrow_mut();
+        *is_12_hour = !*is_12_hour;
+
+        let mut settings = settings_for_clock.borrow_mut();
+        settings.use_24_hour = !*is_12_hour;
+        settings.save();
+    });
+    app.add_action(&toggle_clock_action);
+
+    // Show the preferences window
+    let preferences_action = gtk::gio::SimpleAction::new("preferences", None);
+    let window_weak = window.downgrade();
+    let settings_for_prefs = settings.clone();
+    let use_12_hour_for_prefs = use_12_hour.clone();
+    preferences_action.connect_activate(move |_, _| {
+        if let Some(window) = window_weak.upgrade() {
+            preferences::create_preferences_window(
+                &window,
+                settings_for_prefs.clone(),
+                use_12_hour_for_prefs.clone(),
+            ).present();
+        }
+    });
+    app.add_action(&preferences_action);
+
+    // Write and publish a Bluesky post
+    let compose_action = gtk::gio::SimpleAction::new("compose", None);
+    let window_weak = window.downgrade();
+    compose_action.connect_activate(move |_, _| {
+        if let Some(window) = window_weak.upgrade() {
+            compose::present_compose_dialog(&window, None);
+        }
+    });
+    app.add_action(&compose_action);
+
+    // Export the current articles to Markdown or HTML
+    let export_action = gtk::gio::SimpleAction::new("export", None);
+    let window_weak = window.downgrade();
+    let current_query_for_export = current_query.clone();
+    export_action.connect_activate(move |_, _| {
+        if let Some(window) = window_weak.upgrade() {
+            let articles = global_affairs::current_articles();
+            let query = current_query_for_export.borrow().clone();
+            let title = if query.is_empty() {
+                "Grapevine: Latest World News".to_string()
+            } else {
+                format!("Grapevine: {}", query)
+            };
+            let suggested_name = format!("grapevine-{}.md", chrono::Local::now().format("%Y-%m-%d"));
+
+            export::save_document(&window, &suggested_name, move |is_html| {
+                if is_html {
+                    export::articles_to_html(&title, &articles)
+                } else {
+                    export::articles_to_markdown(&title, &articles)
+                }
+            });
+        }
+    });
+    app.add_action(&export_action);
+
+    // Export a weekly report for the saved searches
+    let digest_action = gtk::gio::SimpleAction::new("digest", None);
+    let window_weak = window.downgrade();
+    let settings_for_digest = settings.clone();
+    let current_query_for_digest = current_query.clone();
+    digest_action.connect_activate(move |_, _| {
+        if let Some(window) = window_weak.upgrade() {
+            digest::present_digest_dialog(
+                &window,
+                settings_for_digest.clone(),
+                &current_query_for_digest.borrow(),
+            );
+        }
+    });
+    app.add_action(&digest_action);
+
+    // Today's stories per country, currencies and hashtags, from what is already loaded
+    let daily_digest_action = gtk::gio::SimpleAction::new("daily-digest", None);
+    let window_weak = window.downgrade();
+    let settings_for_daily_digest = settings.clone();
+    daily_digest_action.connect_activate(move |_, _| {
+        if let Some(window) = window_weak.upgrade() {
+            digest::present_daily_digest_dialog(&window, settings_for_daily_digest.clone());
+        }
+    });
+    app.add_action(&daily_digest_action);
+
+    // Chart the current search's coverage over the past week
+    let timeline_action = gtk::gio::SimpleAction::new("timeline", None);
+    let window_weak = window.downgrade();
+    let settings_for_timeline = settings.clone();
+    let current_query_for_timeline = current_query.clone();
+    timeline_action.connect_activate(move |_, _| {
+        let Some(window) = window_weak.upgrade() else {
+            return;
+        };
+        let query = current_query_for_timeline.borrow().trim().to_string();
+        if query.is_empty() {
+            toasts::show("Search for a topic to chart its coverage");
+            return;
+        }
+        let pairs = timeline::related_pairs(None, &settings_for_timeline.borrow().currency_watchlist);
+        timeline::present_timeline(Some(window.upcast_ref()), &query, &query, pairs);
+    });
+    app.add_action(&timeline_action);
+
+    // Show the about window
+    let about_action = gtk::gio::SimpleAction::new("about", None);
+    let window_weak = window.downgrade();
+    about_action.connect_activate(move |_, _| {
+        if let Some(window) = window_weak.upgrade() {
+            let about = libadwaita::AboutWindow::builder()
+                .transient_for(&window)
+                .application_name("Grapevine")
+                .application_icon(APP_ID)
+                .developer_name("toasterrepairman")
+                .version(env!("CARGO_PKG_VERSION"))
+                .comments("Personal news terminal: GDELT world news on a map and the Bluesky firehose")
+                .website("https://github.com/toasterrepairman/grapevine")
+                .issue_url("https://github.com/toasterrepairman/grapevine/issues")
+                .license_type(gtk::License::Gpl30)
+                .build();
+            about.present();
+        }
+    });
+    app.add_action(&about_action);
+
+    // Show the keyboard shortcuts window
+    let shortcuts_action = gtk::gio::SimpleAction::new("shortcuts", None);
+    let window_weak = window.downgrade();
+    shortcuts_action.connect_activate(move |_, _| {
+        if let Some(window) = window_weak.upgrade() {
+            shortcuts::create_shortcuts_window(&window).present();
+        }
+    });
+    app.add_action(&shortcuts_action);
+
+    // Show session and all-time statistics
+    let stats_action = gtk::gio::SimpleAction::new("stats", None);
+    let window_weak = window.downgrade();
+    stats_action.connect_activate(move |_, _| {
+        if let Some(window) = window_weak.upgrade() {
+            stats::create_stats_window(&window).present();
+        }
+    });
+    app.add_action(&stats_action);
+
+    // Show one sampled post at a time at a relaxed pace
+    let slow_mode_action = gtk::gio::SimpleAction::new("slow-mode", None);
+    let window_weak = window.downgrade();
+    let settings_for_slow_mode = settings.clone();
+    let firehose_control_for_slow_mode = firehose_control.clone();
+    slow_mode_action.connect_activate(move |_, _| {
+        if let Some(window) = window_weak.upgrade() {
+            slow_mode::present_slow_mode_window(
+                &window,
+                settings_for_slow_mode.clone(),
+                firehose_control_for_slow_mode.split_keywords(),
+            );
+        }
+    });
+    app.add_action(&slow_mode_action);
+
+    // Fullscreen page cycling for wall displays, toggled from the menu, F11 or --kiosk
+    let kiosk = kiosk::Kiosk::new(
+        app,
+        &window,
+        &stack,
+        &toolbar_view,
+        vec![view_switcher.clone().upcast()],
+        settings.clone(),
     );
+    let kiosk_action = gtk::gio::SimpleAction::new("kiosk", None);
+    kiosk_action.connect_activate(move |_, _| {
+        kiosk.toggle();
+    });
+    app.add_action(&kiosk_action);
+
+    // Disconnects and blanks the firehose and holds notifications, leaving the news map running
+    let focus_action = gtk::gio::SimpleAction::new_stateful("focus-mode", None, &false.to_variant());
+    let firehose_control_for_focus = firehose_control.clone();
+    focus_action.connect_activate(move |action, _| {
+        let enabled = !action.state().and_then(|state| state.get::<bool>()).unwrap_or(false);
+        action.set_state(&enabled.to_variant());
+        focus::set_enabled(enabled);
+        firehose_control_for_focus.set_focus_mode(enabled);
+    });
+    app.add_action(&focus_action);
 
-    window.set_content(Some(&toolbar_view));
+    shortcuts::register_accels(app);
+
+    // Load custom CSS for floating switcher, map markers, statusline, firehose messages, and news articles,
+    // scaled to the appearance preferences
+    style::install(&gtk::prelude::WidgetExt::display(&window), &settings.borrow());
+    if settings.borrow().developer_mode {
+        style::set_live_reload(true, settings.clone());
+    }
+
+    // Full-window overlay for the image viewer
+    let root_overlay = gtk::Overlay::new();
+    root_overlay.set_child(Some(&toolbar_view));
+    lightbox::install(&root_overlay);
+
+    // Toasts for actions that finish in the background and for failed fetches,
+    // outermost so they stay visible over the image viewer
+    let toast_overlay = libadwaita::ToastOverlay::new();
+    toast_overlay.set_child(Some(&root_overlay));
+    toasts::install(&toast_overlay);
+
+    window.set_content(Some(&toast_overlay));
     window.present();
+
+    // Scripts and other apps drive Grapevine over D-Bus through the same actions
+    let current_query_for_dbus = current_query.clone();
+    dbus::export(app, move || current_query_for_dbus.borrow().clone());
+
+    // Queued network work starts only after the first frame
+    startup::start();
 }
@@ -0,0 +1,130 @@
+use gtk::prelude::*;
+use gtk::{glib, SearchEntry};
+
+use crate::coordinates::find_country_coordinates;
+use crate::data::{NominatimPlace, NOMINATIM_SEARCH_URL};
+use crate::error::{get_json, FetchError};
+
+/// Length of the fly-to animation in milliseconds
+const FLY_DURATION_MS: u32 = 800;
+
+/// A place to fly to; `country` is set when a news marker may exist for it
+struct Destination {
+    lat: f64,
+    lon: f64,
+    zoom: f64,
+    country: Option<String>,
+}
+
+const SERVICE: &str = "Nominatim";
+
+/// Look a place up with Nominatim, preferring English names
+async fn geocode(query: &str) -> Result<NominatimPlace, FetchError> {
+    // Nominatim's usage policy requires an identifying user agent, which the shared client sends
+    let client = crate::http::client();
+
+    let request = client.get(NOMINATIM_SEARCH_URL).query(&[
+        ("q", query),
+        ("format", "jsonv2"),
+        ("limit", "1"),
+        ("accept-language", "en"),
+    ]);
+
+    let places: Vec<NominatimPlace> = get_json(SERVICE, request).await?;
+    places.into_iter().next().ok_or(FetchError::Empty { service: SERVICE })
+}
+
+/// Resolve a typed name from the built-in country table, falling back to Nominatim
+async fn find_destination(query: &str) -> Result<Destination, FetchError> {
+    if let Some((name, (lat, lon))) = find_country_coordinates(query) {
+        return Ok(Destination {
+            lat,
+            lon,
+            zoom: 4.0,
+            country: Some(name.to_string()),
+        });
+    }
+
+    let place = geocode(query).await?;
+    let lat = place.lat.parse::<f64>().map_err(|e| FetchError::parse(SERVICE, e))?;
+    let lon = place.lon.parse::<f64>().map_err(|e| FetchError::parse(SERVICE, e))?;
+
+    // The viewport stops at zoom 6, which is already city level
+    let zoom = match place.addresstype.as_str() {
+        "country" => 4.0,
+        "state" | "region" | "province" => 5.0,
+        _ => 6.0,
+    };
+    let country = (place.addresstype == "country").then(|| {
+        if place.name.is_empty() {
+            place.display_name.clone()
+        } else {
+            place.name.clone()
+        }
+    });
+
+    Ok(Destination { lat, lon, zoom, country })
+}
+
+/// Search entry floating over the map: typing a country or city flies the
+/// map there and opens the country's news marker when one is shown
+pub fn create_map_search_entry(map_view: &libshumate::Map) -> SearchEntry {
+    let entry = SearchEntry::builder()
+        .placeholder_text("Fly to country or city…")
+        .width_chars(24)
+        .halign(gtk::Align::Center)
+        .valign(gtk::Align::Start)
+        .margin_top(8)
+        .build();
+    entry.add_css_class("osd");
+
+    // Clear the "not found" state once the text changes
+    entry.connect_search_changed(|entry| {
+        entry.remove_css_class("error");
+        entry.set_tooltip_text(None);
+    });
+
+    let map_view = map_view.clone();
+    entry.connect_activate(move |entry| {
+        let query = entry.text().trim().to_string();
+        if query.is_empty() {
+            return;
+        }
+
+        let entry = entry.clone();
+        let map_view = map_view.clone();
+        glib::spawn_future_local(async move {
+            entry.set_sensitive(false);
+            let destination = find_destination(&query).await;
+            entry.set_sensitive(true);
+
+            let destination = match destination {
+                Ok(destination) => destination,
+                Err(e) => {
+                    tracing::warn!("No location found for '{}': {}", query, e);
+                    entry.set_tooltip_text(Some(&e.to_string()));
+                    entry.add_css_class("error");
+                    return;
+                }
+            };
+
+            map_view.go_to_full_with_duration(destination.lat, destination.lon, destination.zoom, FLY_DURATION_MS);
+
+            // Open the news marker once the map has arrived
+            if let Some(country) = destination.country {
+                glib::timeout_add_local_once(
+                    std::time::Duration::from_millis(FLY_DURATION_MS as u64 + 100),
+                    move || {
+                        if !crate::global_affairs::open_country_marker(&country)
+                            && !crate::global_affairs::open_country_marker(&query)
+                        {
+                            tracing::info!("No news marker for {}", country);
+                        }
+                    },
+                );
+            }
+        });
+    });
+
+    entry
+}
@@ -0,0 +1,532 @@
+use gtk::prelude::*;
+use gtk::{gio, glib, Label, Orientation};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use crate::crypto;
+use crate::data::CurrencyInfo;
+use crate::global_affairs;
+use crate::settings::{Settings, WatchedPair};
+use crate::startup::{self, Priority};
+use crate::toasts;
+
+/// Currencies Frankfurter publishes reference rates for
+const CURRENCIES: &[&str] = &[
+    "AUD", "BGN", "BRL", "CAD", "CHF", "CNY", "CZK", "DKK", "EUR", "GBP", "HKD",
+    "HUF", "IDR", "ILS", "INR", "ISK", "JPY", "KRW", "MXN", "MYR", "NOK", "NZD",
+    "PHP", "PLN", "RON", "SEK", "SGD", "THB", "TRY", "USD", "ZAR",
+];
+
+/// Reference rates change once a working day, so there's no point asking often
+const REFRESH_MINUTES: u32 = 30;
+
+/// The Markets page and what it has fetched
+struct MarketsPage {
+    settings: Rc<RefCell<Settings>>,
+    grid: gtk::FlowBox,
+    updated_label: Label,
+    /// Latest rates by pair name, kept so reordering doesn't refetch
+    rates: RefCell<HashMap<String, CurrencyInfo>>,
+    /// Alerts that already fired, e.g. "EUR/USD above"; cleared once the rate moves back
+    triggered: RefCell<HashSet<String>>,
+}
+
+impl MarketsPage {
+    fn watchlist(&self) -> Vec<WatchedPair> {
+        self.settings.borrow().currency_watchlist.clone()
+    }
+
+    /// Change the watchlist, save it and redraw the grid
+    fn modify(self: &Rc<Self>, f: impl FnOnce(&mut Vec<WatchedPair>)) {
+        {
+            let mut settings = self.settings.borrow_mut();
+            f(&mut settings.currency_watchlist);
+            settings.save();
+        }
+        self.rebuild();
+    }
+
+    /// Redraw every card from the watchlist, fetching pairs not seen yet
+    fn rebuild(self: &Rc<Self>) {
+        while let Some(child) = self.grid.first_child() {
+            self.grid.remove(&child);
+        }
+
+        let watchlist = self.watchlist();
+        if watchlist.is_empty() {
+            let empty = libadwaita::StatusPage::builder()
+                .icon_name("mail-send-receive-symbolic")
+                .title("No Currency Pairs")
+                .description("Pick two currencies above to watch their exchange rate.")
+                .build();
+            self.grid.append(&empty);
+            return;
+        }
+
+        for (index, pair) in watchlist.iter().enumerate() {
+            self.grid.append(&self.create_pair_card(index, pair, watchlist.len()));
+        }
+    }
+
+    /// Drop the fetched rates and load every pair again
+    fn refresh(self: &Rc<Self>) {
+        global_affairs::clear_currency_cache();
+        self.rates.borrow_mut().clear();
+        self.rebuild();
+        self.updated_label.set_label(&format!("Updated {}", chrono::Local::now().format("%H:%M")));
+    }
+
+    fn create_pair_card(self: &Rc<Self>, index: usize, pair: &WatchedPair, count: usize) -> gtk::Box {
+        let card = gtk::Box::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .width_request(300)
+            .build();
+        card.add_css_class("card");
+        card.add_css_class("market-card");
+
+        let header = gtk::Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .build();
+        let name_label = Label::builder()
+            .label(pair.name())
+            .xalign(0.0)
+            .hexpand(true)
+            .build();
+        name_label.add_css_class("title-4");
+        header.append(&name_label);
+        header.append(&self.create_pair_menu(index, pair, count));
+        card.append(&header);
+
+        let body = gtk::Box::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(4)
+            .build();
+        card.append(&body);
+
+        let alerts = describe_alerts(pair);
+        if !alerts.is_empty() {
+            let alerts_label = Label::builder()
+                .label(alerts)
+                .xalign(0.0)
+                .build();
+            alerts_label.add_css_class("caption");
+            alerts_label.add_css_class("dim-label");
+            card.append(&alerts_label);
+        }
+
+        let cached = self.rates.borrow().get(&pair.name()).cloned();
+        match cached {
+            Some(info) => fill_pair_body(&body, &info),
+            None => {
+                let spinner = gtk::Spinner::builder()
+                    .spinning(true)
+                    .halign(gtk::Align::Start)
+                    .build();
+                body.append(&spinner);
+
+                let page = self.clone();
+                let pair = pair.clone();
+                glib::spawn_future_local(async move {
+                    let result = global_affairs::fetch_currency_pair(&pair.base, &pair.target).await;
+                    body.remove(&spinner);
+                    match result {
+                        Ok(info) => {
+                            fill_pair_body(&body, &info);
+                            page.check_alerts(&pair, info.rate);
+                            page.rates.borrow_mut().insert(pair.name(), info);
+                        }
+                        Err(e) => {
+                            let error_label = Label::builder()
+                                .label("Rate unavailable")
+                                .xalign(0.0)
+                                .build();
+                            error_label.add_css_class("error");
+                            error_label.add_css_class("caption");
+                            body.append(&error_label);
+                            crate::toasts::show_error(&e, move || page.refresh());
+                        }
+                    }
+                });
+            }
+        }
+
+        card
+    }
+
+    /// Menu with the pair's alert thresholds, reordering and removal
+    fn create_pair_menu(self: &Rc<Self>, index: usize, pair: &WatchedPair, count: usize) -> gtk::MenuButton {
+        let popover_box = gtk::Box::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(8)
+            .margin_top(6)
+            .margin_bottom(6)
+            .margin_start(6)
+            .margin_end(6)
+            .build();
+
+        let threshold_heading = Label::builder()
+            .label("Alert Thresholds")
+            .xalign(0.0)
+            .build();
+        threshold_heading.add_css_class("heading");
+        popover_box.append(&threshold_heading);
+
+        let above_entry = create_threshold_entry("Notify at or above", pair.alert_above);
+        let below_entry = create_threshold_entry("Notify at or below", pair.alert_below);
+        popover_box.append(&above_entry);
+        popover_box.append(&below_entry);
+
+        let save_button = gtk::Button::builder()
+            .label("Save Alerts")
+            .css_classes(vec!["suggested-action".to_string()])
+            .build();
+        popover_box.append(&save_button);
+
+        let move_box = gtk::Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .homogeneous(true)
+            .build();
+        let earlier_button = gtk::Button::builder()
+            .icon_name("go-previous-symbolic")
+            .tooltip_text("Move earlier")
+            .sensitive(index > 0)
+            .build();
+        let later_button = gtk::Button::builder()
+            .icon_name("go-next-symbolic")
+            .tooltip_text("Move later")
+            .sensitive(index + 1 < count)
+            .build();
+        let remove_button = gtk::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text("Stop watching")
+            .css_classes(vec!["destructive-action".to_string()])
+            .build();
+        move_box.append(&earlier_button);
+        move_box.append(&later_button);
+        move_box.append(&remove_button);
+        popover_box.append(&move_box);
+
+        let popover = gtk::Popover::builder()
+            .child(&popover_box)
+            .build();
+        let menu_button = gtk::MenuButton::builder()
+            .icon_name("view-more-symbolic")
+            .tooltip_text("Alerts and order")
+            .popover(&popover)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+
+        // Changes redraw the grid, so each handler closes the popover first
+        let page = self.clone();
+        let popover_for_save = popover.clone();
+        save_button.connect_clicked(move |_| {
+            popover_for_save.popdown();
+            let (above, below) = (parse_threshold(&above_entry), parse_threshold(&below_entry));
+            page.modify(|watchlist| {
+                if let Some(pair) = watchlist.get_mut(index) {
+                    pair.alert_above = above;
+                    pair.alert_below = below;
+                }
+            });
+        });
+
+        let page = self.clone();
+        let popover_for_earlier = popover.clone();
+        earlier_button.connect_clicked(move |_| {
+            popover_for_earlier.popdown();
+            page.modify(|watchlist| {
+                if index > 0 && index < watchlist.len() {
+                    watchlist.swap(index - 1, index);
+                }
+            });
+        });
+
+        let page = self.clone();
+        let popover_for_later = popover.clone();
+        later_button.connect_clicked(move |_| {
+            popover_for_later.popdown();
+            page.modify(|watchlist| {
+                if index + 1 < watchlist.len() {
+                    watchlist.swap(index, index + 1);
+                }
+            });
+        });
+
+        let page = self.clone();
+        remove_button.connect_clicked(move |_| {
+            popover.popdown();
+            page.modify(|watchlist| {
+                if index < watchlist.len() {
+                    watchlist.remove(index);
+                }
+            });
+        });
+
+        menu_button
+    }
+
+    /// Notify when a rate crosses one of the pair's thresholds, once per crossing
+    fn check_alerts(&self, pair: &WatchedPair, rate: f64) {
+        let checks = [
+            ("above", pair.alert_above.is_some_and(|threshold| rate >= threshold), pair.alert_above),
+            ("below", pair.alert_below.is_some_and(|threshold| rate <= threshold), pair.alert_below),
+        ];
+
+        for (direction, crossed, threshold) in checks {
+            let key = format!("{} {}", pair.name(), direction);
+            if !crossed {
+                self.triggered.borrow_mut().remove(&key);
+                continue;
+            }
+            if !self.triggered.borrow_mut().insert(key.clone()) {
+                continue;
+            }
+
+            let message = format!(
+                "{} is at {}, {} your alert at {}",
+                pair.name(),
+                global_affairs::format_rate(rate),
+                direction,
+                global_affairs::format_rate(threshold.unwrap_or_default()),
+            );
+            toasts::show(&message);
+
+            if crate::focus::is_enabled() {
+                continue;
+            }
+            if let Some(app) = gio::Application::default() {
+                let notification = gio::Notification::new(&format!("{} Rate Alert", pair.name()));
+                notification.set_body(Some(&message));
+                notification.set_default_action_and_target_value("app.switch-view", Some(&"markets".to_variant()));
+                app.send_notification(Some(&format!("currency-alert-{}", key)), &notification);
+            }
+        }
+    }
+}
+
+/// e.g. "Alerts: above 1.1000 · below 1.0500"
+fn describe_alerts(pair: &WatchedPair) -> String {
+    let mut parts = Vec::new();
+    if let Some(above) = pair.alert_above {
+        parts.push(format!("above {}", global_affairs::format_rate(above)));
+    }
+    if let Some(below) = pair.alert_below {
+        parts.push(format!("below {}", global_affairs::format_rate(below)));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("Alerts: {}", parts.join(" · "))
+    }
+}
+
+fn create_threshold_entry(placeholder: &str, value: Option<f64>) -> gtk::Entry {
+    let entry = gtk::Entry::builder()
+        .placeholder_text(placeholder)
+        .input_purpose(gtk::InputPurpose::Number)
+        .text(value.map(|value| value.to_string()).unwrap_or_default())
+        .build();
+    entry.connect_changed(|entry| {
+        if entry.text().trim().is_empty() || parse_threshold(entry).is_some() {
+            entry.remove_css_class("error");
+        } else {
+            entry.add_css_class("error");
+        }
+    });
+    entry
+}
+
+/// A positive rate typed into a threshold entry; None when empty or invalid
+fn parse_threshold(entry: &gtk::Entry) -> Option<f64> {
+    entry
+        .text()
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value > 0.0)
+}
+
+/// Rate, 24-hour change and 14-day sparkline of a fetched pair
+fn fill_pair_body(body: &gtk::Box, info: &CurrencyInfo) {
+    let rate_box = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .build();
+    let rate_label = Label::builder()
+        .label(global_affairs::format_rate(info.rate))
+        .xalign(0.0)
+        .build();
+    rate_label.add_css_class("title-2");
+    rate_label.add_css_class("currency-rate");
+    rate_box.append(&rate_label);
+
+    if let Some(change) = info.change_24h {
+        let badge = Label::builder()
+            .label(format!("24h: {}{:.2}%", if change > 0.0 { "+" } else { "" }, change))
+            .valign(gtk::Align::Center)
+            .build();
+        badge.add_css_class("badge");
+        badge.add_css_class(if change > 0.0 {
+            "badge-positive"
+        } else if change < 0.0 {
+            "badge-negative"
+        } else {
+            "badge-neutral"
+        });
+        rate_box.append(&badge);
+    }
+    body.append(&rate_box);
+
+    if !info.trend_data.is_empty() {
+        body.append(&global_affairs::create_sparkline(&info.trend_data));
+    }
+}
+
+/// Bar for picking a new pair to watch
+fn create_add_bar(page: &Rc<MarketsPage>) -> gtk::Box {
+    let bar = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .build();
+
+    // Coins priced by CoinGecko follow the fiat currencies
+    let currencies: Vec<&'static str> = CURRENCIES
+        .iter()
+        .copied()
+        .chain(crypto::COINS.iter().map(|(code, _)| *code))
+        .collect();
+
+    let base_dropdown = gtk::DropDown::from_strings(&currencies);
+    base_dropdown.set_tooltip_text(Some("Currency priced"));
+    base_dropdown.set_selected(currencies.iter().position(|c| *c == "EUR").unwrap_or(0) as u32);
+    let swap_button = gtk::Button::builder()
+        .icon_name("object-flip-horizontal-symbolic")
+        .tooltip_text("Swap currencies")
+        .css_classes(vec!["flat".to_string()])
+        .build();
+    let target_dropdown = gtk::DropDown::from_strings(&currencies);
+    target_dropdown.set_tooltip_text(Some("Currency quoted in"));
+    target_dropdown.set_selected(currencies.iter().position(|c| *c == "USD").unwrap_or(0) as u32);
+
+    let add_button = gtk::Button::builder()
+        .label("Watch Pair")
+        .css_classes(vec!["suggested-action".to_string()])
+        .build();
+
+    let base_for_swap = base_dropdown.clone();
+    let target_for_swap = target_dropdown.clone();
+    swap_button.connect_clicked(move |_| {
+        let base = base_for_swap.selected();
+        base_for_swap.set_selected(target_for_swap.selected());
+        target_for_swap.set_selected(base);
+    });
+
+    let page = page.clone();
+    let base_for_add = base_dropdown.clone();
+    let target_for_add = target_dropdown.clone();
+    add_button.connect_clicked(move |_| {
+        let (Some(base), Some(target)) = (
+            currencies.get(base_for_add.selected() as usize),
+            currencies.get(target_for_add.selected() as usize),
+        ) else {
+            return;
+        };
+        if base == target {
+            toasts::show("Pick two different currencies");
+            return;
+        }
+        let pair = WatchedPair::new(base, target);
+        if page.watchlist().iter().any(|watched| watched.name() == pair.name()) {
+            toasts::show(&format!("{} is already on the watchlist", pair.name()));
+            return;
+        }
+        page.modify(|watchlist| watchlist.push(pair));
+    });
+
+    bar.append(&base_dropdown);
+    bar.append(&swap_button);
+    bar.append(&target_dropdown);
+    bar.append(&add_button);
+    bar
+}
+
+/// The Markets page: a grid of pinned currency pairs with their trends and alerts
+pub fn create_markets_view(settings: Rc<RefCell<Settings>>) -> gtk::Box {
+    let container = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .build();
+
+    let toolbar = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .margin_top(8)
+        .margin_bottom(8)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+
+    let updated_label = Label::builder()
+        .xalign(1.0)
+        .hexpand(true)
+        .build();
+    updated_label.add_css_class("dim-label");
+    updated_label.add_css_class("caption");
+
+    let refresh_button = gtk::Button::builder()
+        .icon_name("view-refresh-symbolic")
+        .tooltip_text("Refresh rates")
+        .css_classes(vec!["flat".to_string()])
+        .build();
+
+    let grid = gtk::FlowBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .homogeneous(true)
+        .min_children_per_line(1)
+        .max_children_per_line(4)
+        .row_spacing(12)
+        .column_spacing(12)
+        .margin_start(12)
+        .margin_end(12)
+        .margin_bottom(12)
+        .valign(gtk::Align::Start)
+        .build();
+
+    let page = Rc::new(MarketsPage {
+        settings,
+        grid: grid.clone(),
+        updated_label: updated_label.clone(),
+        rates: RefCell::new(HashMap::new()),
+        triggered: RefCell::new(HashSet::new()),
+    });
+
+    toolbar.append(&create_add_bar(&page));
+    toolbar.append(&updated_label);
+    toolbar.append(&refresh_button);
+    container.append(&toolbar);
+
+    let scrolled = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .vexpand(true)
+        .child(&grid)
+        .build();
+    container.append(&scrolled);
+
+    let page_for_refresh = page.clone();
+    refresh_button.connect_clicked(move |_| page_for_refresh.refresh());
+
+    // Alerts need fresh rates even while the page isn't open
+    let page_for_startup = page.clone();
+    startup::schedule(Priority::Low, "Currency watchlist", async move {
+        page_for_startup.refresh();
+    });
+    glib::timeout_add_seconds_local(REFRESH_MINUTES * 60, move || {
+        page.refresh();
+        glib::ControlFlow::Continue
+    });
+
+    container
+}
@@ -0,0 +1,104 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+use crate::error::FetchError;
+
+/// Failed requests by (service, kind), counted from whichever thread made them
+static FETCH_ERRORS: Mutex<BTreeMap<(&'static str, &'static str), u64>> = Mutex::new(BTreeMap::new());
+
+/// Count a failed request. Requests skipped in demo mode aren't failures.
+pub fn record_error(error: &FetchError) {
+    if matches!(error, FetchError::Demo { .. }) {
+        return;
+    }
+    if let Ok(mut errors) = FETCH_ERRORS.lock() {
+        *errors.entry((error.service(), error.kind())).or_insert(0) += 1;
+    }
+}
+
+/// Label values are quoted; backslashes, quotes and newlines must be escaped
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn write_metric(out: &mut String, name: &str, kind: &str, help: &str, value: impl std::fmt::Display) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+/// This session's counters in the Prometheus text exposition format
+pub fn render() -> String {
+    let stats = crate::stats::session();
+    let mut out = String::new();
+
+    write_metric(
+        &mut out,
+        "grapevine_uptime_seconds",
+        "gauge",
+        "Seconds since Grapevine started",
+        // Counted from stats::init at launch, not from the first scrape
+        format!("{:.3}", crate::stats::session_elapsed().as_secs_f64()),
+    );
+    write_metric(
+        &mut out,
+        "grapevine_firehose_events_total",
+        "counter",
+        "Firehose posts received",
+        stats.posts_seen,
+    );
+    write_metric(
+        &mut out,
+        "grapevine_firehose_dropped_total",
+        "counter",
+        "Firehose posts dropped because the UI fell behind",
+        stats.posts_dropped,
+    );
+    write_metric(
+        &mut out,
+        "grapevine_filter_matches_total",
+        "counter",
+        "Posts that matched a split's filters",
+        stats.filter_matches,
+    );
+    write_metric(
+        &mut out,
+        "grapevine_articles_fetched_total",
+        "counter",
+        "GDELT articles fetched",
+        stats.articles_fetched,
+    );
+    write_metric(
+        &mut out,
+        "grapevine_disaster_alerts_total",
+        "counter",
+        "GDACS disaster alerts received",
+        stats.alerts_fired,
+    );
+    if let Some(lag) = crate::connection::lag() {
+        write_metric(
+            &mut out,
+            "grapevine_firehose_lag_seconds",
+            "gauge",
+            "How far the newest firehose event is behind the wall clock",
+            lag.as_secs_f64(),
+        );
+    }
+
+    out.push_str("# HELP grapevine_fetch_errors_total Failed requests to outside services\n");
+    out.push_str("# TYPE grapevine_fetch_errors_total counter\n");
+    if let Ok(errors) = FETCH_ERRORS.lock() {
+        for ((service, kind), count) in errors.iter() {
+            let _ = writeln!(
+                out,
+                "grapevine_fetch_errors_total{{service=\"{}\",kind=\"{}\"}} {}",
+                escape_label(service),
+                kind,
+                count
+            );
+        }
+    }
+
+    out
+}
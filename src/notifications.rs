@@ -0,0 +1,360 @@
+use gtk::prelude::*;
+use gtk::{glib, Label, ListBox, Orientation, ScrolledWindow};
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::rc::Rc;
+use std::time::Duration;
+
+use atrium_api::app::bsky::feed::post::RecordData as PostRecord;
+
+use crate::account;
+use crate::appview;
+use crate::compose::{self, ReplyTarget};
+use crate::data::BskyNotification;
+use crate::firehose;
+use crate::layouts;
+use crate::startup::{self, Priority};
+
+/// Name of the page in the main window's stack and layouts
+const PAGE_NAME: &str = "notifications";
+/// How often the account's notifications are checked
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// Notifications requested per check
+const PAGE_SIZE: u8 = 40;
+/// Rows kept in the list, oldest dropped first
+const MAX_ROWS: usize = 200;
+
+/// What the pane shows and has shown
+struct NotificationsPane {
+    content: gtk::Stack,
+    list: ListBox,
+    status_label: Label,
+    /// URIs of the notifications in the list, newest first
+    shown: RefCell<VecDeque<String>>,
+    /// Unread notifications the page badge counts
+    unread: RefCell<HashSet<String>>,
+}
+
+impl NotificationsPane {
+    /// In view in the main window or its own one
+    fn is_visible(&self) -> bool {
+        layouts::is_page_shown(PAGE_NAME)
+    }
+
+    fn set_badge(&self, count: usize) {
+        layouts::set_page_badge(PAGE_NAME, count);
+    }
+
+    /// Clear the badge and tell Bluesky the notifications were read
+    fn mark_seen(&self) {
+        if self.unread.borrow().is_empty() {
+            return;
+        }
+        self.unread.borrow_mut().clear();
+        self.set_badge(0);
+        glib::spawn_future_local(async {
+            if let Err(e) = account::update_seen().await {
+                tracing::warn!("Failed to mark notifications read: {}", e);
+            }
+        });
+    }
+
+    async fn refresh(self: Rc<Self>) {
+        if !account::is_configured() {
+            self.content.set_visible_child_name("signed-out");
+            self.set_badge(0);
+            return;
+        }
+        self.content.set_visible_child_name("list");
+
+        let response = match account::list_notifications(PAGE_SIZE).await {
+            Ok(response) => response,
+            Err(e) => {
+                self.status_label.set_label(&e.to_string());
+                self.status_label.set_visible(true);
+                return;
+            }
+        };
+        self.status_label.set_visible(false);
+
+        // Oldest first, so each new row is prepended above the one before it
+        for notification in response.notifications.iter().rev() {
+            if self.shown.borrow().contains(&notification.uri) {
+                continue;
+            }
+            if !notification.is_read {
+                self.unread.borrow_mut().insert(notification.uri.clone());
+            }
+            self.shown.borrow_mut().push_front(notification.uri.clone());
+            self.list.prepend(&create_notification_row(notification));
+        }
+
+        while self.shown.borrow().len() > MAX_ROWS {
+            self.shown.borrow_mut().pop_back();
+            if let Some(last) = self.list.last_child() {
+                self.list.remove(&last);
+            }
+        }
+
+        if self.is_visible() {
+            self.mark_seen();
+        } else {
+            self.set_badge(self.unread.borrow().len());
+        }
+    }
+}
+
+/// Icon and phrase for a notification reason, e.g. ("❤", "liked your post")
+fn describe_reason(reason: &str) -> (&'static str, &'static str) {
+    match reason {
+        "like" => ("❤", "liked your post"),
+        "repost" => ("🔁", "reposted your post"),
+        "follow" => ("👤", "followed you"),
+        "mention" => ("@", "mentioned you"),
+        "reply" => ("↩", "replied to you"),
+        "quote" => ("❝", "quoted your post"),
+        "starterpack-joined" => ("📦", "joined via your starter pack"),
+        _ => ("•", "interacted with you"),
+    }
+}
+
+/// Local time a notification arrived, e.g. "14:02:33"
+fn format_time(indexed_at: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(indexed_at)
+        .map(|time| time.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+fn create_notification_row(notification: &BskyNotification) -> gtk::Box {
+    let row = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .margin_top(4)
+        .margin_bottom(4)
+        .margin_start(6)
+        .margin_end(6)
+        .build();
+    row.add_css_class("notification-row");
+    if !notification.is_read {
+        row.add_css_class("notification-unread");
+    }
+
+    let author = &notification.author;
+    let name = match author.display_name {
+        Some(ref name) if !name.is_empty() => name.clone(),
+        _ => format!("@{}", author.handle),
+    };
+    let (icon, phrase) = describe_reason(&notification.reason);
+
+    let header = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    let summary_label = Label::builder()
+        .label(format!("{} {} {}", icon, name, phrase))
+        .xalign(0.0)
+        .hexpand(true)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .tooltip_text(format!("@{}", author.handle))
+        .build();
+    summary_label.add_css_class("heading");
+    header.append(&summary_label);
+
+    let time_label = Label::builder()
+        .label(format_time(&notification.indexed_at))
+        .build();
+    time_label.add_css_class("caption");
+    time_label.add_css_class("monospace");
+    time_label.add_css_class("firehose-timestamp");
+    header.append(&time_label);
+    row.append(&header);
+
+    let actions = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .halign(gtk::Align::End)
+        .build();
+
+    match notification.reason.as_str() {
+        // Posts addressed to the account: show them in full, ready to answer
+        "mention" | "reply" | "quote" => {
+            match serde_json::from_value::<PostRecord>(notification.record.clone()) {
+                Ok(record) => {
+                    let rkey = notification.uri.rsplit('/').next().unwrap_or_default();
+                    let post = firehose::post_from_record(
+                        &record,
+                        &author.did,
+                        rkey,
+                        &notification.cid,
+                        format_time(&notification.indexed_at),
+                    );
+                    row.append(&firehose::create_post_card(&post, None));
+                }
+                Err(e) => tracing::warn!("Skipping unreadable post in notification {}: {}", notification.uri, e),
+            }
+
+            if let Some(web_url) = appview::post_web_url(&notification.uri) {
+                actions.append(&create_open_button("Open Thread", web_url));
+            }
+
+            if let Some(target) = ReplyTarget::from_post(
+                &notification.uri,
+                &notification.cid,
+                &notification.record,
+                &author.handle,
+            ) {
+                let reply_button = gtk::Button::builder()
+                    .label("Reply")
+                    .css_classes(vec!["flat".to_string()])
+                    .build();
+                reply_button.connect_clicked(move |button| {
+                    if let Some(window) = button.root().and_downcast::<gtk::Window>() {
+                        compose::present_compose_dialog(&window, Some(target.clone()));
+                    }
+                });
+                actions.append(&reply_button);
+            }
+        }
+        // Likes and reposts point at one of the account's own posts
+        "like" | "repost" => {
+            if let Some(ref subject) = notification.reason_subject {
+                row.append(&create_subject_label(subject));
+                if let Some(web_url) = appview::post_web_url(subject) {
+                    actions.append(&create_open_button("Open Post", web_url));
+                }
+            }
+        }
+        _ => {
+            let profile_url = format!("https://bsky.app/profile/{}", author.did);
+            actions.append(&create_open_button("Open Profile", profile_url));
+        }
+    }
+
+    if actions.first_child().is_some() {
+        row.append(&actions);
+    }
+
+    row
+}
+
+fn create_open_button(label: &str, url: String) -> gtk::Button {
+    let button = gtk::Button::builder()
+        .label(label)
+        .css_classes(vec!["flat".to_string()])
+        .build();
+    button.connect_clicked(move |_| {
+        if let Err(e) = open::that(&url) {
+            tracing::warn!("Failed to open URL: {}", e);
+        }
+    });
+    button
+}
+
+/// Text of the post a like or repost refers to, fetched once the row is shown
+fn create_subject_label(uri: &str) -> Label {
+    let label = Label::builder()
+        .xalign(0.0)
+        .wrap(true)
+        .wrap_mode(gtk::pango::WrapMode::WordChar)
+        .lines(3)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build();
+    label.add_css_class("dim-label");
+
+    let loaded = Rc::new(RefCell::new(false));
+    let uri = uri.to_string();
+    label.connect_map(move |label| {
+        if *loaded.borrow() {
+            return;
+        }
+        *loaded.borrow_mut() = true;
+
+        let label = label.clone();
+        let uri = uri.clone();
+        glib::spawn_future_local(async move {
+            match appview::fetch_post_batched(&uri).await {
+                Ok(post) => label.set_label(post.text()),
+                Err(e) => tracing::warn!("Failed to fetch notification subject {}: {}", uri, e),
+            }
+        });
+    });
+
+    label
+}
+
+/// The Notifications page: mentions, replies, likes, reposts and follows for
+/// the signed-in account, checked every 30 seconds
+pub fn create_notifications_view() -> gtk::Box {
+    let container = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .build();
+
+    let sign_in_button = gtk::Button::builder()
+        .label("Open Preferences")
+        .halign(gtk::Align::Center)
+        .css_classes(vec!["pill".to_string(), "suggested-action".to_string()])
+        .action_name("app.preferences")
+        .build();
+    let signed_out_page = libadwaita::StatusPage::builder()
+        .icon_name("preferences-system-notifications-symbolic")
+        .title("Sign In for Notifications")
+        .description("Add your Bluesky handle and an app password in Preferences to see mentions, replies and likes here.")
+        .child(&sign_in_button)
+        .vexpand(true)
+        .build();
+
+    let list_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(8)
+        .build();
+
+    let status_label = Label::builder()
+        .xalign(0.0)
+        .wrap(true)
+        .margin_top(8)
+        .margin_start(8)
+        .margin_end(8)
+        .visible(false)
+        .build();
+    status_label.add_css_class("caption");
+    status_label.add_css_class("error");
+    list_box.append(&status_label);
+
+    let list = ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .build();
+    let scrolled = ScrolledWindow::builder()
+        .vexpand(true)
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .child(&list)
+        .build();
+    list_box.append(&scrolled);
+
+    let content = gtk::Stack::new();
+    content.add_named(&signed_out_page, Some("signed-out"));
+    content.add_named(&list_box, Some("list"));
+    container.append(&content);
+
+    let pane = Rc::new(NotificationsPane {
+        content,
+        list,
+        status_label,
+        shown: RefCell::new(VecDeque::new()),
+        unread: RefCell::new(HashSet::new()),
+    });
+
+    // Opening the page reads what's there, wherever it is shown
+    let pane_for_visible = pane.clone();
+    layouts::connect_page_shown(PAGE_NAME, move || pane_for_visible.mark_seen());
+
+    startup::schedule(Priority::Low, "Notifications", pane.clone().refresh());
+    glib::timeout_add_local(POLL_INTERVAL, move || {
+        // Focus mode holds new notifications until it's over
+        if !crate::focus::is_enabled() {
+            glib::spawn_future_local(pane.clone().refresh());
+        }
+        glib::ControlFlow::Continue
+    });
+
+    container
+}
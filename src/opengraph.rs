@@ -0,0 +1,182 @@
+use crate::error::{send, FetchError};
+
+/// Metadata scraped from a web page's <head> (OpenGraph tags with HTML fallbacks)
+#[derive(Debug, Clone, Default)]
+pub struct PageMetadata {
+    pub title: String,
+    pub description: String,
+    pub image: String,
+}
+
+const SERVICE: &str = "Article site";
+
+/// Only the <head> is needed, so stop reading after this many bytes
+const MAX_HEAD_BYTES: usize = 256 * 1024;
+
+/// Fetch a page and extract its title, description and preview image
+pub async fn fetch_page_metadata(url: &str) -> Result<PageMetadata, FetchError> {
+    let mut response = send(SERVICE, crate::http::client().get(url)).await?;
+    // Image paths are relative to where any redirects ended up
+    let page_url = response.url().clone();
+
+    // Read in chunks until the head has been seen
+    let mut body = Vec::new();
+    while let Ok(Some(chunk)) = response.chunk().await {
+        body.extend_from_slice(&chunk);
+        if body.len() > MAX_HEAD_BYTES || contains_head_end(&body) {
+            break;
+        }
+    }
+
+    let html = String::from_utf8_lossy(&body);
+    let mut metadata = parse_page_metadata(&html);
+    if !metadata.image.is_empty() {
+        metadata.image = page_url
+            .join(&metadata.image)
+            .map(String::from)
+            .unwrap_or_default();
+    }
+    Ok(metadata)
+}
+
+fn contains_head_end(body: &[u8]) -> bool {
+    body.windows(7).any(|w| w.eq_ignore_ascii_case(b"</head>"))
+}
+
+/// Extract metadata from raw HTML using the <meta> and <title> tags
+pub fn parse_page_metadata(html: &str) -> PageMetadata {
+    // ASCII lowercasing keeps byte offsets identical to the original
+    let lower = html.to_ascii_lowercase();
+
+    let mut metadata = PageMetadata::default();
+    let mut fallback_description = String::new();
+
+    let mut search_from = 0;
+    while let Some(start) = lower[search_from..].find("<meta") {
+        let tag_start = search_from + start;
+        let Some(tag_len) = lower[tag_start..].find('>') else {
+            break;
+        };
+        let tag_end = tag_start + tag_len;
+        let tag = &html[tag_start..tag_end];
+        let tag_lower = &lower[tag_start..tag_end];
+
+        let key = attribute_value(tag, tag_lower, "property")
+            .or_else(|| attribute_value(tag, tag_lower, "name"))
+            .map(|k| k.to_ascii_lowercase());
+        let content = attribute_value(tag, tag_lower, "content");
+
+        if let (Some(key), Some(content)) = (key, content) {
+            let content = decode_entities(content.trim());
+            match key.as_str() {
+                "og:title" | "twitter:title" if metadata.title.is_empty() => metadata.title = content,
+                "og:description" | "twitter:description" if metadata.description.is_empty() => metadata.description = content,
+                "description" if fallback_description.is_empty() => fallback_description = content,
+                "og:image" | "twitter:image" if metadata.image.is_empty() => metadata.image = content,
+                _ => {}
+            }
+        }
+
+        search_from = tag_end;
+    }
+
+    if metadata.description.is_empty() {
+        metadata.description = fallback_description;
+    }
+
+    // Fall back to the <title> element
+    if metadata.title.is_empty() {
+        if let Some(start) = lower.find("<title") {
+            if let Some(open_end) = lower[start..].find('>') {
+                let text_start = start + open_end + 1;
+                if let Some(text_len) = lower[text_start..].find("</title") {
+                    metadata.title = decode_entities(html[text_start..text_start + text_len].trim());
+                }
+            }
+        }
+    }
+
+    metadata
+}
+
+/// Read a quoted (or bare) attribute value from a single tag
+fn attribute_value<'a>(tag: &'a str, tag_lower: &str, name: &str) -> Option<&'a str> {
+    let mut search_from = 0;
+    while let Some(pos) = tag_lower[search_from..].find(name) {
+        let name_start = search_from + pos;
+        let after_name = name_start + name.len();
+        search_from = after_name;
+
+        // Must be a whole attribute name followed by '='
+        let preceded_ok = tag_lower[..name_start].ends_with(|c: char| c.is_whitespace());
+        let rest = tag_lower[after_name..].trim_start();
+        if !preceded_ok || !rest.starts_with('=') {
+            continue;
+        }
+
+        let value_start = tag_lower.len() - rest.len() + 1;
+        let value = tag[value_start..].trim_start();
+        let value_offset = tag.len() - value.len();
+
+        return match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let inner = &tag[value_offset + 1..];
+                inner.find(quote).map(|end| &inner[..end])
+            }
+            Some(_) => {
+                let end = value.find(|c: char| c.is_whitespace() || c == '/').unwrap_or(value.len());
+                Some(&value[..end])
+            }
+            None => None,
+        };
+    }
+    None
+}
+
+/// Decode the named HTML entities common in meta tags and feeds, and numeric ones
+pub fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find(';').filter(|end| *end <= 10) else {
+            decoded.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+
+        let entity = &rest[1..end];
+        let character = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+
+        match character {
+            Some(character) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
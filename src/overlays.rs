@@ -0,0 +1,719 @@
+use gtk::prelude::*;
+use gtk::{gdk, gio, Label, Orientation};
+use std::cell::Cell;
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::opengraph::decode_entities;
+
+/// Colors cycled through for successive imports: (css class suffix, rgb)
+const LAYER_COLORS: [(&str, (f32, f32, f32)); 4] = [
+    ("blue", (0.21, 0.52, 0.89)),
+    ("green", (0.18, 0.76, 0.49)),
+    ("orange", (0.96, 0.47, 0.0)),
+    ("purple", (0.57, 0.25, 0.67)),
+];
+
+/// Longest marker label before it is cut short
+const MAX_LABEL_CHARS: usize = 24;
+
+/// Shape of an imported feature; positions are (latitude, longitude)
+#[derive(Debug, Clone)]
+pub enum Geometry {
+    Point(f64, f64),
+    Line(Vec<(f64, f64)>),
+    Polygon(Vec<(f64, f64)>),
+}
+
+/// A labelled shape from an imported dataset
+#[derive(Debug, Clone)]
+pub struct Feature {
+    pub name: String,
+    pub description: String,
+    pub geometry: Geometry,
+}
+
+fn is_valid_position(lat: f64, lon: f64) -> bool {
+    (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon)
+}
+
+/// Parse a KML, GeoRSS or GeoJSON document, picking the format from the
+/// file extension and falling back to sniffing the content
+pub fn parse_dataset(path: &Path, text: &str) -> Result<Vec<Feature>, String> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+
+    let is_json = match extension.as_str() {
+        "geojson" | "json" => true,
+        "kml" | "xml" | "rss" | "atom" | "georss" => false,
+        _ => text.trim_start().starts_with('{'),
+    };
+
+    let features = if is_json {
+        parse_geojson(text)?
+    } else {
+        let root = parse_xml(text)?;
+        if root.find_first("kml").is_some() || root.find_first("Placemark").is_some() {
+            parse_kml(&root)
+        } else {
+            parse_georss(&root)
+        }
+    };
+
+    if features.is_empty() {
+        Err("No points or shapes found".to_string())
+    } else {
+        Ok(features)
+    }
+}
+
+// GeoJSON
+
+fn parse_geojson(text: &str) -> Result<Vec<Feature>, String> {
+    let value: serde_json::Value = serde_json::from_str(text).map_err(|e| format!("Invalid GeoJSON: {}", e))?;
+    let mut features = Vec::new();
+    collect_geojson(&value, "", "", &mut features);
+    Ok(features)
+}
+
+fn collect_geojson(value: &serde_json::Value, name: &str, description: &str, features: &mut Vec<Feature>) {
+    match value["type"].as_str() {
+        Some("FeatureCollection") => {
+            for feature in value["features"].as_array().into_iter().flatten() {
+                collect_geojson(feature, "", "", features);
+            }
+        }
+        Some("Feature") => {
+            let properties = &value["properties"];
+            let first_string = |keys: &[&str]| {
+                keys.iter()
+                    .find_map(|key| properties[*key].as_str())
+                    .unwrap_or("")
+                    .to_string()
+            };
+            let name = first_string(&["name", "Name", "title", "label"]);
+            let description = first_string(&["description", "desc", "popupContent"]);
+            collect_geojson(&value["geometry"], &name, &description, features);
+        }
+        Some("GeometryCollection") => {
+            for geometry in value["geometries"].as_array().into_iter().flatten() {
+                collect_geojson(geometry, name, description, features);
+            }
+        }
+        Some(kind) => {
+            for geometry in geojson_geometries(kind, &value["coordinates"]) {
+                features.push(Feature {
+                    name: name.to_string(),
+                    description: description.to_string(),
+                    geometry,
+                });
+            }
+        }
+        None => {}
+    }
+}
+
+/// GeoJSON positions are [longitude, latitude(, altitude)]
+fn geojson_position(value: &serde_json::Value) -> Option<(f64, f64)> {
+    let position = value.as_array()?;
+    let lon = position.first()?.as_f64()?;
+    let lat = position.get(1)?.as_f64()?;
+    is_valid_position(lat, lon).then_some((lat, lon))
+}
+
+fn geojson_positions(value: &serde_json::Value) -> Vec<(f64, f64)> {
+    value
+        .as_array()
+        .map(|positions| positions.iter().filter_map(geojson_position).collect())
+        .unwrap_or_default()
+}
+
+fn geojson_geometries(kind: &str, coordinates: &serde_json::Value) -> Vec<Geometry> {
+    let parts = || coordinates.as_array().into_iter().flatten();
+
+    match kind {
+        "Point" => geojson_position(coordinates)
+            .map(|(lat, lon)| vec![Geometry::Point(lat, lon)])
+            .unwrap_or_default(),
+        "MultiPoint" => geojson_positions(coordinates)
+            .into_iter()
+            .map(|(lat, lon)| Geometry::Point(lat, lon))
+            .collect(),
+        "LineString" => vec![Geometry::Line(geojson_positions(coordinates))],
+        "MultiLineString" => parts().map(|line| Geometry::Line(geojson_positions(line))).collect(),
+        // Only the outer ring of a polygon is drawn
+        "Polygon" => vec![Geometry::Polygon(geojson_positions(&coordinates[0]))],
+        "MultiPolygon" => parts()
+            .map(|polygon| Geometry::Polygon(geojson_positions(&polygon[0])))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// XML (KML and GeoRSS)
+
+/// Minimal XML element: local name without namespace prefix, text content and children.
+/// Attributes are not needed for KML or GeoRSS geometry and are skipped.
+struct XmlElement {
+    name: String,
+    text: String,
+    children: Vec<XmlElement>,
+}
+
+impl XmlElement {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            text: String::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn child_text(&self, name: &str) -> String {
+        self.children
+            .iter()
+            .find(|child| child.name == name)
+            .map(|child| child.text.trim().to_string())
+            .unwrap_or_default()
+    }
+
+    fn find_first(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find_map(|child| {
+            if child.name == name {
+                Some(child)
+            } else {
+                child.find_first(name)
+            }
+        })
+    }
+
+    fn find_all<'a>(&'a self, name: &str, found: &mut Vec<&'a XmlElement>) {
+        for child in &self.children {
+            if child.name == name {
+                found.push(child);
+            } else {
+                child.find_all(name, found);
+            }
+        }
+    }
+}
+
+/// Index of the '>' closing the tag at the start of `text`, skipping quoted attribute values
+fn find_tag_end(text: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse XML leniently into a tree under a synthetic root; mismatched or
+/// unclosed tags are tolerated since map exports are often hand-edited
+fn parse_xml(text: &str) -> Result<XmlElement, String> {
+    let mut stack = vec![XmlElement::new("#document")];
+    let mut rest = text;
+
+    while let Some(start) = rest.find('<') {
+        let content = decode_entities(&rest[..start]);
+        if let Some(top) = stack.last_mut() {
+            top.text.push_str(&content);
+        }
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("<!--") {
+            let end = after.find("-->").ok_or("Unterminated comment")?;
+            rest = &after[end + 3..];
+        } else if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let end = after.find("]]>").ok_or("Unterminated CDATA section")?;
+            if let Some(top) = stack.last_mut() {
+                top.text.push_str(&after[..end]);
+            }
+            rest = &after[end + 3..];
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            let end = rest.find('>').ok_or("Unterminated declaration")?;
+            rest = &rest[end + 1..];
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let end = after.find('>').ok_or("Unterminated closing tag")?;
+            if stack.len() > 1 {
+                if let Some(element) = stack.pop() {
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(element);
+                    }
+                }
+            }
+            rest = &after[end + 1..];
+        } else {
+            let end = find_tag_end(rest).ok_or("Unterminated tag")?;
+            let tag = &rest[1..end];
+            let self_closing = tag.ends_with('/');
+            let qualified_name = tag.trim_end_matches('/').split_whitespace().next().unwrap_or("");
+            let name = qualified_name.rsplit(':').next().unwrap_or(qualified_name);
+
+            let element = XmlElement::new(name);
+            if self_closing {
+                if let Some(top) = stack.last_mut() {
+                    top.children.push(element);
+                }
+            } else {
+                stack.push(element);
+            }
+            rest = &rest[end + 1..];
+        }
+    }
+
+    while stack.len() > 1 {
+        if let Some(element) = stack.pop() {
+            if let Some(parent) = stack.last_mut() {
+                parent.children.push(element);
+            }
+        }
+    }
+
+    stack.pop().ok_or_else(|| "Empty document".to_string())
+}
+
+/// Descriptions are often HTML; keep just the text
+fn strip_markup(text: &str) -> String {
+    let mut plain = String::new();
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                plain.push(' ');
+            }
+            _ if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&plain).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// KML coordinates are whitespace-separated "lon,lat[,alt]" tuples
+fn kml_coordinates(text: &str) -> Vec<(f64, f64)> {
+    text.split_whitespace()
+        .filter_map(|tuple| {
+            let mut parts = tuple.split(',');
+            let lon = parts.next()?.parse::<f64>().ok()?;
+            let lat = parts.next()?.parse::<f64>().ok()?;
+            is_valid_position(lat, lon).then_some((lat, lon))
+        })
+        .collect()
+}
+
+fn parse_kml(root: &XmlElement) -> Vec<Feature> {
+    let mut placemarks = Vec::new();
+    root.find_all("Placemark", &mut placemarks);
+
+    let mut features = Vec::new();
+    for placemark in placemarks {
+        let name = placemark.child_text("name");
+        let description = strip_markup(&placemark.child_text("description"));
+        let mut push = |geometry| {
+            features.push(Feature {
+                name: name.clone(),
+                description: description.clone(),
+                geometry,
+            })
+        };
+
+        let mut points = Vec::new();
+        placemark.find_all("Point", &mut points);
+        for point in points {
+            if let Some((lat, lon)) = point
+                .find_first("coordinates")
+                .and_then(|coordinates| kml_coordinates(&coordinates.text).first().copied())
+            {
+                push(Geometry::Point(lat, lon));
+            }
+        }
+
+        let mut lines = Vec::new();
+        placemark.find_all("LineString", &mut lines);
+        for line in lines {
+            if let Some(coordinates) = line.find_first("coordinates") {
+                push(Geometry::Line(kml_coordinates(&coordinates.text)));
+            }
+        }
+
+        let mut polygons = Vec::new();
+        placemark.find_all("Polygon", &mut polygons);
+        for polygon in polygons {
+            let boundary = polygon.find_first("outerBoundaryIs").unwrap_or(polygon);
+            if let Some(coordinates) = boundary.find_first("coordinates") {
+                push(Geometry::Polygon(kml_coordinates(&coordinates.text)));
+            }
+        }
+    }
+
+    features
+}
+
+/// GeoRSS and GML list positions as "lat lon lat lon ..."
+fn lat_lon_pairs(text: &str) -> Vec<(f64, f64)> {
+    let numbers: Vec<f64> = text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter_map(|number| number.parse().ok())
+        .collect();
+
+    numbers
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .filter(|&(lat, lon)| is_valid_position(lat, lon))
+        .collect()
+}
+
+fn georss_geometries(item: &XmlElement) -> Vec<Geometry> {
+    let mut geometries = Vec::new();
+
+    // GeoRSS Simple
+    if let Some((lat, lon)) = item.find_first("point").and_then(|point| lat_lon_pairs(&point.text).first().copied()) {
+        geometries.push(Geometry::Point(lat, lon));
+    }
+    if let Some(line) = item.find_first("line") {
+        geometries.push(Geometry::Line(lat_lon_pairs(&line.text)));
+    }
+    if let Some(polygon) = item.find_first("polygon") {
+        geometries.push(Geometry::Polygon(lat_lon_pairs(&polygon.text)));
+    }
+
+    // GeoRSS GML, inside <georss:where>
+    if let Some(place) = item.find_first("where") {
+        if let Some((lat, lon)) = place
+            .find_first("Point")
+            .and_then(|point| point.find_first("pos"))
+            .and_then(|pos| lat_lon_pairs(&pos.text).first().copied())
+        {
+            geometries.push(Geometry::Point(lat, lon));
+        }
+        if let Some(list) = place.find_first("LineString").and_then(|line| line.find_first("posList")) {
+            geometries.push(Geometry::Line(lat_lon_pairs(&list.text)));
+        }
+        if let Some(list) = place.find_first("Polygon").and_then(|polygon| polygon.find_first("posList")) {
+            geometries.push(Geometry::Polygon(lat_lon_pairs(&list.text)));
+        }
+    }
+
+    // W3C Basic Geo
+    if geometries.is_empty() {
+        let lat = item.find_first("lat").and_then(|lat| lat.text.trim().parse::<f64>().ok());
+        let lon = item.find_first("long").and_then(|lon| lon.text.trim().parse::<f64>().ok());
+        if let (Some(lat), Some(lon)) = (lat, lon) {
+            if is_valid_position(lat, lon) {
+                geometries.push(Geometry::Point(lat, lon));
+            }
+        }
+    }
+
+    geometries
+}
+
+fn parse_georss(root: &XmlElement) -> Vec<Feature> {
+    // RSS items and Atom entries
+    let mut items = Vec::new();
+    root.find_all("item", &mut items);
+    root.find_all("entry", &mut items);
+
+    let mut features = Vec::new();
+    for item in items {
+        let name = strip_markup(&item.child_text("title"));
+        let description = ["description", "summary", "content"]
+            .iter()
+            .map(|field| item.child_text(field))
+            .find(|text| !text.is_empty())
+            .map(|text| strip_markup(&text))
+            .unwrap_or_default();
+
+        for geometry in georss_geometries(item) {
+            features.push(Feature {
+                name: name.clone(),
+                description: description.clone(),
+                geometry,
+            });
+        }
+    }
+
+    features
+}
+
+// Map layers
+
+/// An imported dataset drawn on the map: markers for points and labels, a path layer per shape
+pub struct ImportedLayer {
+    pub name: String,
+    pub feature_count: usize,
+    map_view: libshumate::Map,
+    markers: libshumate::MarkerLayer,
+    paths: Vec<libshumate::PathLayer>,
+}
+
+impl ImportedLayer {
+    /// Draw the features on new layers of the map
+    pub fn new(
+        map_view: &libshumate::Map,
+        viewport: &libshumate::Viewport,
+        name: &str,
+        features: &[Feature],
+        color_index: usize,
+    ) -> Self {
+        let (color_name, (red, green, blue)) = LAYER_COLORS[color_index % LAYER_COLORS.len()];
+        let stroke = gdk::RGBA::new(red, green, blue, 0.9);
+        let fill = gdk::RGBA::new(red, green, blue, 0.25);
+
+        let mut paths = Vec::new();
+        for feature in features {
+            let (nodes, closed) = match &feature.geometry {
+                Geometry::Point(..) => continue,
+                Geometry::Line(nodes) => (nodes, false),
+                Geometry::Polygon(nodes) => (nodes, true),
+            };
+            if nodes.len() < 2 {
+                continue;
+            }
+
+            let path = libshumate::PathLayer::new(viewport);
+            path.set_stroke_color(Some(&stroke));
+            path.set_stroke_width(2.0);
+            path.set_closed(closed);
+            path.set_fill(closed);
+            path.set_fill_color(Some(&fill));
+            for &(lat, lon) in nodes {
+                path.add_node(&libshumate::Coordinate::new_full(lat, lon));
+            }
+            map_view.add_layer(&path);
+            paths.push(path);
+        }
+
+        // Markers go above the shapes so labels stay clickable
+        let markers = libshumate::MarkerLayer::new(viewport);
+        for feature in features {
+            let anchor = match &feature.geometry {
+                Geometry::Point(lat, lon) => Some((*lat, *lon)),
+                // Shapes are labelled at their centroid when they have a name
+                Geometry::Line(nodes) | Geometry::Polygon(nodes) if !feature.name.is_empty() && !nodes.is_empty() => {
+                    let count = nodes.len() as f64;
+                    Some((
+                        nodes.iter().map(|(lat, _)| lat).sum::<f64>() / count,
+                        nodes.iter().map(|(_, lon)| lon).sum::<f64>() / count,
+                    ))
+                }
+                _ => None,
+            };
+
+            if let Some((lat, lon)) = anchor {
+                create_feature_marker(&markers, feature, color_name, lat, lon);
+            }
+        }
+        map_view.add_layer(&markers);
+
+        Self {
+            name: name.to_string(),
+            feature_count: features.len(),
+            map_view: map_view.clone(),
+            markers,
+            paths,
+        }
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.markers.set_visible(visible);
+        for path in &self.paths {
+            path.set_visible(visible);
+        }
+    }
+
+    /// Take the layer off the map
+    pub fn remove(&self) {
+        self.map_view.remove_layer(&self.markers);
+        for path in &self.paths {
+            self.map_view.remove_layer(path);
+        }
+    }
+}
+
+fn create_feature_marker(layer: &libshumate::MarkerLayer, feature: &Feature, color_name: &str, lat: f64, lon: f64) {
+    let label = if feature.name.is_empty() {
+        "•".to_string()
+    } else if feature.name.chars().count() > MAX_LABEL_CHARS {
+        format!("{}…", feature.name.chars().take(MAX_LABEL_CHARS - 1).collect::<String>())
+    } else {
+        feature.name.clone()
+    };
+
+    let marker_button = gtk::Button::builder()
+        .label(label)
+        .build();
+    marker_button.add_css_class("overlay-marker");
+    marker_button.add_css_class(&format!("overlay-{}", color_name));
+    if !feature.name.is_empty() {
+        marker_button.set_tooltip_text(Some(&feature.name));
+    }
+
+    // Popover with the feature's name and description
+    let popover_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    let title_label = Label::builder()
+        .label(if feature.name.is_empty() { "Unnamed feature" } else { feature.name.as_str() })
+        .xalign(0.0)
+        .wrap(true)
+        .max_width_chars(40)
+        .build();
+    title_label.add_css_class("title-4");
+    popover_box.append(&title_label);
+
+    if !feature.description.is_empty() {
+        let description_label = Label::builder()
+            .label(feature.description.as_str())
+            .xalign(0.0)
+            .wrap(true)
+            .max_width_chars(40)
+            .selectable(true)
+            .build();
+        popover_box.append(&description_label);
+    }
+
+    let coordinates_label = Label::builder()
+        .label(format!("{:.4}, {:.4}", lat, lon))
+        .xalign(0.0)
+        .build();
+    coordinates_label.add_css_class("dim-label");
+    coordinates_label.add_css_class("caption");
+    popover_box.append(&coordinates_label);
+
+    crate::disasters::place_marker_with_popover(layer, marker_button, popover_box, lat, lon);
+}
+
+/// Button for the layers menu that imports a KML, GeoRSS or GeoJSON file as a
+/// new map layer, adding a toggle row for it to `layers_box`
+pub fn create_import_button(
+    map_view: &libshumate::Map,
+    viewport: &libshumate::Viewport,
+    layers_box: &gtk::Box,
+) -> gtk::Button {
+    let button = gtk::Button::builder()
+        .label("Import Layer…")
+        .tooltip_text("Overlay points and shapes from a KML, GeoRSS or GeoJSON file")
+        .margin_top(4)
+        .build();
+
+    let imported_count = Rc::new(Cell::new(0usize));
+    let map_view = map_view.clone();
+    let viewport = viewport.clone();
+    let layers_box = layers_box.clone();
+    button.connect_clicked(move |button| {
+        let parent = button.root().and_downcast::<gtk::Window>();
+        if let Some(popover) = button.ancestor(gtk::Popover::static_type()).and_downcast::<gtk::Popover>() {
+            popover.popdown();
+        }
+
+        let filters = gio::ListStore::new::<gtk::FileFilter>();
+        let dataset_filter = gtk::FileFilter::new();
+        dataset_filter.set_name(Some("Map data (KML, GeoRSS, GeoJSON)"));
+        for pattern in ["*.kml", "*.geojson", "*.json", "*.xml", "*.rss", "*.atom"] {
+            dataset_filter.add_pattern(pattern);
+        }
+        filters.append(&dataset_filter);
+
+        let dialog = gtk::FileDialog::builder()
+            .title("Import Map Layer")
+            .filters(&filters)
+            .modal(true)
+            .build();
+
+        let map_view = map_view.clone();
+        let viewport = viewport.clone();
+        let layers_box = layers_box.clone();
+        let imported_count = imported_count.clone();
+        dialog.open(parent.as_ref(), gio::Cancellable::NONE, move |result| {
+            let file = match result {
+                Ok(file) => file,
+                // Dismissed by the user
+                Err(_) => return,
+            };
+
+            let Some(path) = file.path() else {
+                tracing::warn!("Imported layer has no local path");
+                return;
+            };
+
+            let features = match std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| parse_dataset(&path, &text))
+            {
+                Ok(features) => features,
+                Err(e) => {
+                    tracing::warn!("Failed to import {}: {}", path.display(), e);
+                    return;
+                }
+            };
+
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Imported layer".to_string());
+            let layer = ImportedLayer::new(&map_view, &viewport, &name, &features, imported_count.get());
+            imported_count.set(imported_count.get() + 1);
+            tracing::info!("Imported {} features from {}", layer.feature_count, path.display());
+
+            layers_box.append(&create_imported_layer_row(layer));
+        });
+    });
+
+    button
+}
+
+/// Layers menu row: visibility check and a remove button
+fn create_imported_layer_row(layer: ImportedLayer) -> gtk::Box {
+    let row = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(4)
+        .build();
+
+    let check = gtk::CheckButton::builder()
+        .label(format!("{} ({})", layer.name, layer.feature_count))
+        .active(true)
+        .hexpand(true)
+        .build();
+
+    let remove_button = gtk::Button::builder()
+        .icon_name("user-trash-symbolic")
+        .tooltip_text("Remove layer")
+        .build();
+    remove_button.add_css_class("flat");
+
+    let layer = Rc::new(layer);
+    let layer_for_toggle = layer.clone();
+    check.connect_toggled(move |check| {
+        layer_for_toggle.set_visible(check.is_active());
+    });
+
+    let row_weak = row.downgrade();
+    remove_button.connect_clicked(move |_| {
+        layer.remove();
+        if let Some(row) = row_weak.upgrade() {
+            if let Some(parent) = row.parent().and_downcast::<gtk::Box>() {
+                parent.remove(&row);
+            }
+        }
+    });
+
+    row.append(&check);
+    row.append(&remove_button);
+    row
+}
@@ -0,0 +1,908 @@
+use gtk::prelude::*;
+use libadwaita::prelude::*;
+use libadwaita::{ComboRow, EntryRow, PasswordEntryRow, PreferencesGroup, PreferencesPage, PreferencesWindow, SpinRow, SwitchRow};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::domain_filter::parse_domain_list;
+use crate::settings::{Density, Settings, Theme, ThumbnailFit, ThumbnailSize};
+use crate::tiles::{MapTiles, TileConfig};
+use crate::translate::TranslationProvider;
+
+/// Build the preferences window; every change is applied immediately and saved
+pub fn create_preferences_window(
+    parent: &impl IsA<gtk::Window>,
+    settings: Rc<RefCell<Settings>>,
+    use_12_hour: Rc<RefCell<bool>>,
+) -> PreferencesWindow {
+    let window = PreferencesWindow::builder()
+        .title("Preferences")
+        .transient_for(parent)
+        .modal(true)
+        .search_enabled(false)
+        .build();
+
+    let general_page = PreferencesPage::builder()
+        .title("General")
+        .icon_name("preferences-system-symbolic")
+        .build();
+
+    // Clock preferences
+    let clock_group = PreferencesGroup::builder()
+        .title("Clock")
+        .build();
+
+    let clock_row = SwitchRow::builder()
+        .title("24-hour clock")
+        .subtitle("Also toggled by clicking the clock")
+        .active(!*use_12_hour.borrow())
+        .build();
+
+    let settings_clone = settings.clone();
+    clock_row.connect_active_notify(move |row| {
+        *use_12_hour.borrow_mut() = !row.is_active();
+        let mut settings = settings_clone.borrow_mut();
+        settings.use_24_hour = row.is_active();
+        settings.save();
+    });
+    clock_group.add(&clock_row);
+    general_page.add(&clock_group);
+
+    // Session preferences
+    let session_group = PreferencesGroup::builder()
+        .title("Session")
+        .build();
+
+    let summary_row = SwitchRow::builder()
+        .title("Summary on quit")
+        .subtitle("Show posts seen, filter matches and articles fetched before closing")
+        .active(settings.borrow().show_session_summary)
+        .build();
+
+    let settings_clone = settings.clone();
+    summary_row.connect_active_notify(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.show_session_summary = row.is_active();
+        settings.save();
+    });
+    session_group.add(&summary_row);
+
+    let background_row = SwitchRow::builder()
+        .title("Run in background")
+        .subtitle("Closing the window keeps the firehose streaming behind a tray icon")
+        .active(settings.borrow().run_in_background)
+        .build();
+
+    let settings_clone = settings.clone();
+    background_row.connect_active_notify(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.run_in_background = row.is_active();
+        settings.save();
+    });
+    session_group.add(&background_row);
+    general_page.add(&session_group);
+
+    // Appearance preferences, applied by regenerating the stylesheet
+    let appearance_group = PreferencesGroup::builder()
+        .title("Appearance")
+        .build();
+
+    let themes = [Theme::System, Theme::Light, Theme::Dark];
+    let theme_row = ComboRow::builder()
+        .title("Style")
+        .subtitle("The map switches to light or dark tiles to match")
+        .model(&gtk::StringList::new(&["Follow system", "Light", "Dark"]))
+        .build();
+    let current_theme = settings.borrow().theme;
+    if let Some(index) = themes.iter().position(|theme| *theme == current_theme) {
+        theme_row.set_selected(index as u32);
+    }
+
+    let settings_clone = settings.clone();
+    theme_row.connect_selected_notify(move |row| {
+        if let Some(theme) = themes.get(row.selected() as usize) {
+            let mut settings = settings_clone.borrow_mut();
+            settings.theme = *theme;
+            settings.save();
+            crate::style::apply_theme(*theme);
+        }
+    });
+    appearance_group.add(&theme_row);
+
+    let font_scale_row = SpinRow::with_range(80.0, 200.0, 10.0);
+    font_scale_row.set_title("Text size");
+    font_scale_row.set_subtitle("Percent of the default size");
+    font_scale_row.set_value((settings.borrow().font_scale * 100.0).round());
+
+    let settings_clone = settings.clone();
+    font_scale_row.connect_value_notify(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.font_scale = row.value() / 100.0;
+        settings.save();
+        crate::style::reload(&settings);
+    });
+    appearance_group.add(&font_scale_row);
+
+    let density_row = ComboRow::builder()
+        .title("Density")
+        .model(&gtk::StringList::new(&["Compact", "Comfortable"]))
+        .selected(match settings.borrow().density {
+            Density::Compact => 0,
+            Density::Comfortable => 1,
+        })
+        .build();
+
+    let settings_clone = settings.clone();
+    density_row.connect_selected_notify(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.density = if row.selected() == 0 { Density::Compact } else { Density::Comfortable };
+        settings.save();
+        crate::style::reload(&settings);
+    });
+    appearance_group.add(&density_row);
+
+    let thumbnail_sizes = [ThumbnailSize::Off, ThumbnailSize::Small, ThumbnailSize::Large];
+    let thumbnail_size_row = ComboRow::builder()
+        .title("Article images")
+        .subtitle("Off skips downloading them")
+        .model(&gtk::StringList::new(&["Off", "Small", "Large"]))
+        .build();
+    let current_size = settings.borrow().thumbnail_size;
+    if let Some(index) = thumbnail_sizes.iter().position(|size| *size == current_size) {
+        thumbnail_size_row.set_selected(index as u32);
+    }
+
+    let settings_clone = settings.clone();
+    thumbnail_size_row.connect_selected_notify(move |row| {
+        if let Some(size) = thumbnail_sizes.get(row.selected() as usize) {
+            let mut settings = settings_clone.borrow_mut();
+            settings.thumbnail_size = *size;
+            settings.save();
+            crate::style::reload(&settings);
+        }
+    });
+    appearance_group.add(&thumbnail_size_row);
+
+    let letterbox_row = SwitchRow::builder()
+        .title("Show whole images")
+        .subtitle("Letterbox instead of cropping, so charts and captions stay visible")
+        .active(settings.borrow().thumbnail_fit == ThumbnailFit::Letterbox)
+        .build();
+
+    let settings_clone = settings.clone();
+    letterbox_row.connect_active_notify(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.thumbnail_fit = if row.is_active() { ThumbnailFit::Letterbox } else { ThumbnailFit::Crop };
+        settings.save();
+    });
+    appearance_group.add(&letterbox_row);
+    general_page.add(&appearance_group);
+
+    // Map tiles; a custom server is only switched to once a test tile loads from it
+    let map_group = PreferencesGroup::builder()
+        .title("Map")
+        .description("Custom URL templates use {z}, {x} and {y} for the tile, e.g. https://tiles.example.com/{z}/{x}/{y}.png")
+        .build();
+
+    let tile_labels: Vec<&str> = MapTiles::ALL.iter().map(|tiles| tiles.label()).collect();
+    let tiles_row = ComboRow::builder()
+        .title("Tiles")
+        .model(&gtk::StringList::new(&tile_labels))
+        .build();
+    let current_tiles = settings.borrow().map_tiles;
+    if let Some(index) = MapTiles::ALL.iter().position(|tiles| *tiles == current_tiles) {
+        tiles_row.set_selected(index as u32);
+    }
+    map_group.add(&tiles_row);
+
+    let tile_url_row = EntryRow::builder()
+        .title("Custom URL template")
+        .text(settings.borrow().tile_url_template.as_str())
+        .show_apply_button(true)
+        .build();
+    map_group.add(&tile_url_row);
+
+    let tile_header_row = EntryRow::builder()
+        .title("API key header (e.g. Authorization)")
+        .text(settings.borrow().tile_api_key_header.as_str())
+        .show_apply_button(true)
+        .build();
+    map_group.add(&tile_header_row);
+
+    let tile_key_row = PasswordEntryRow::builder()
+        .title("API key")
+        .text(settings.borrow().tile_api_key.as_str())
+        .show_apply_button(true)
+        .build();
+    map_group.add(&tile_key_row);
+
+    let custom_rows = [tile_url_row.clone().upcast::<gtk::Widget>(), tile_header_row.clone().upcast(), tile_key_row.clone().upcast()];
+    for row in &custom_rows {
+        row.set_sensitive(current_tiles == MapTiles::Custom);
+    }
+
+    // Test the custom server with what's in the rows, and only save and switch to it if a tile loads
+    let apply_custom_tiles = {
+        let settings = settings.clone();
+        let window = window.downgrade();
+        let (tile_url_row, tile_header_row, tile_key_row) = (tile_url_row.clone(), tile_header_row.clone(), tile_key_row.clone());
+        Rc::new(move || {
+            let config = TileConfig {
+                tiles: MapTiles::Custom,
+                url_template: tile_url_row.text().trim().to_string(),
+                api_key_header: tile_header_row.text().trim().to_string(),
+                api_key: tile_key_row.text().trim().to_string(),
+            };
+            let settings = settings.clone();
+            let window = window.clone();
+            gtk::glib::spawn_future_local(async move {
+                let message = match crate::tiles::test_custom(&config).await {
+                    Ok(()) => {
+                        let mut settings = settings.borrow_mut();
+                        settings.map_tiles = MapTiles::Custom;
+                        settings.tile_url_template = config.url_template;
+                        settings.tile_api_key_header = config.api_key_header;
+                        settings.tile_api_key = config.api_key;
+                        settings.save();
+                        drop(settings);
+                        crate::tiles::reload();
+                        "Map tiles loaded from the custom server".to_string()
+                    }
+                    Err(e) => format!("Map tiles unchanged: {}", e),
+                };
+                if let Some(window) = window.upgrade() {
+                    window.add_toast(libadwaita::Toast::new(&message));
+                }
+            });
+        })
+    };
+
+    let settings_clone = settings.clone();
+    let apply = apply_custom_tiles.clone();
+    tiles_row.connect_selected_notify(move |row| {
+        let Some(tiles) = MapTiles::ALL.get(row.selected() as usize).copied() else {
+            return;
+        };
+        for row in &custom_rows {
+            row.set_sensitive(tiles == MapTiles::Custom);
+        }
+        if tiles == MapTiles::Custom {
+            apply();
+            return;
+        }
+        let mut settings = settings_clone.borrow_mut();
+        settings.map_tiles = tiles;
+        settings.save();
+        drop(settings);
+        crate::tiles::reload();
+    });
+
+    for row in [&tile_url_row, &tile_header_row, tile_key_row.upcast_ref::<EntryRow>()] {
+        let apply = apply_custom_tiles.clone();
+        row.connect_apply(move |_| apply());
+    }
+
+    general_page.add(&map_group);
+
+    // Global Affairs preferences
+    let news_group = PreferencesGroup::builder()
+        .title("Global Affairs")
+        .build();
+
+    let refresh_row = SpinRow::with_range(5.0, 120.0, 5.0);
+    refresh_row.set_title("Auto-refresh interval");
+    refresh_row.set_subtitle("Minutes between article refreshes");
+    refresh_row.set_value(settings.borrow().refresh_interval_minutes as f64);
+
+    let settings_clone = settings.clone();
+    refresh_row.connect_value_notify(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.refresh_interval_minutes = row.value() as u32;
+        settings.save();
+    });
+    news_group.add(&refresh_row);
+
+    let per_domain_row = SpinRow::with_range(0.0, 20.0, 1.0);
+    per_domain_row.set_title("Articles per outlet");
+    per_domain_row.set_subtitle("More are folded under \"show more\"; 0 lists them all");
+    per_domain_row.set_value(settings.borrow().max_articles_per_domain as f64);
+
+    let settings_clone = settings.clone();
+    per_domain_row.connect_value_notify(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.max_articles_per_domain = row.value() as u32;
+        settings.save();
+    });
+    news_group.add(&per_domain_row);
+
+    let blocked_row = EntryRow::builder()
+        .title("Hidden outlets (comma-separated domains)")
+        .text(settings.borrow().blocked_domains.join(", "))
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    blocked_row.connect_apply(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.blocked_domains = parse_domain_list(&row.text());
+        settings.save();
+    });
+    news_group.add(&blocked_row);
+
+    let allowed_row = EntryRow::builder()
+        .title("Only these outlets (empty for all)")
+        .text(settings.borrow().allowed_domains.join(", "))
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    allowed_row.connect_apply(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.allowed_domains = parse_domain_list(&row.text());
+        settings.save();
+    });
+    news_group.add(&allowed_row);
+    general_page.add(&news_group);
+    general_page.add(&create_source_ratings_group(settings.clone()));
+
+    // Local feed server for saved searches
+    let feeds_group = PreferencesGroup::builder()
+        .title("Feeds")
+        .description("Let feed readers and monitoring on this computer follow your saved searches and activity")
+        .build();
+
+    let feed_server_row = SwitchRow::builder()
+        .title("Serve saved searches and metrics")
+        .subtitle(format!("Listed at http://127.0.0.1:{}/", settings.borrow().feed_server_port))
+        .active(settings.borrow().feed_server_enabled)
+        .build();
+
+    let settings_clone = settings.clone();
+    feed_server_row.connect_active_notify(move |row| {
+        {
+            let mut settings = settings_clone.borrow_mut();
+            settings.feed_server_enabled = row.is_active();
+            settings.save();
+        }
+        crate::feed_server::apply_settings(settings_clone.clone());
+    });
+    feeds_group.add(&feed_server_row);
+
+    let feed_port_row = SpinRow::with_range(1024.0, 65535.0, 1.0);
+    feed_port_row.set_title("Port");
+    feed_port_row.set_value(settings.borrow().feed_server_port as f64);
+
+    let settings_clone = settings.clone();
+    let feed_server_row_clone = feed_server_row.clone();
+    feed_port_row.connect_value_notify(move |row| {
+        let port = row.value() as u16;
+        {
+            let mut settings = settings_clone.borrow_mut();
+            settings.feed_server_port = port;
+            settings.save();
+        }
+        feed_server_row_clone.set_subtitle(&format!("Listed at http://127.0.0.1:{}/", port));
+        crate::feed_server::apply_settings(settings_clone.clone());
+    });
+    feeds_group.add(&feed_port_row);
+    general_page.add(&feeds_group);
+
+    // Kiosk mode for unattended wall displays
+    let kiosk_group = PreferencesGroup::builder()
+        .title("Kiosk Mode")
+        .description("Fullscreen with no controls, cycling between pages; F11 enters and leaves it")
+        .build();
+
+    let kiosk_interval_row = SpinRow::with_range(10.0, 600.0, 5.0);
+    kiosk_interval_row.set_title("Seconds per page");
+    kiosk_interval_row.set_value(settings.borrow().kiosk_interval_seconds as f64);
+
+    let settings_clone = settings.clone();
+    kiosk_interval_row.connect_value_notify(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.kiosk_interval_seconds = row.value() as u32;
+        settings.save();
+    });
+    kiosk_group.add(&kiosk_interval_row);
+
+    for (name, title) in crate::kiosk::PAGES {
+        let page_row = SwitchRow::builder()
+            .title(*title)
+            .active(settings.borrow().kiosk_pages.iter().any(|page| page == name))
+            .build();
+
+        let settings_clone = settings.clone();
+        page_row.connect_active_notify(move |row| {
+            let mut settings = settings_clone.borrow_mut();
+            settings.kiosk_pages.retain(|page| page != name);
+            if row.is_active() {
+                settings.kiosk_pages.push(name.to_string());
+            }
+            // Keep the tab order however the switches were flipped
+            settings.kiosk_pages.sort_by_key(|page| crate::kiosk::PAGES.iter().position(|(known, _)| known == page));
+            settings.save();
+        });
+        kiosk_group.add(&page_row);
+    }
+    general_page.add(&kiosk_group);
+
+    // Firehose rendering limits for machines that can't keep up with the stream
+    let firehose_group = PreferencesGroup::builder()
+        .title("Firehose")
+        .description("Posts over these limits are still counted, just not shown")
+        .build();
+
+    let max_rows_row = SpinRow::with_range(0.0, 500.0, 10.0);
+    max_rows_row.set_title("Rows per second");
+    max_rows_row.set_subtitle("Most posts added to the lists each second; 0 for no limit");
+    max_rows_row.set_value(settings.borrow().max_rows_per_second as f64);
+
+    let settings_clone = settings.clone();
+    max_rows_row.connect_value_notify(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.max_rows_per_second = row.value() as u32;
+        settings.save();
+    });
+    firehose_group.add(&max_rows_row);
+
+    let sample_scale = gtk::Scale::with_range(gtk::Orientation::Horizontal, 1.0, 100.0, 1.0);
+    sample_scale.set_value(settings.borrow().sample_percent as f64);
+    sample_scale.set_draw_value(true);
+    sample_scale.set_value_pos(gtk::PositionType::Left);
+    sample_scale.set_width_request(200);
+    sample_scale.set_valign(gtk::Align::Center);
+    sample_scale.set_format_value_func(|_, value| format!("{:.0}%", value));
+
+    let settings_clone = settings.clone();
+    sample_scale.connect_value_changed(move |scale| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.sample_percent = scale.value().round() as u32;
+        settings.save();
+    });
+
+    let sample_row = libadwaita::ActionRow::builder()
+        .title("Sampling")
+        .subtitle("Share of the stream shown")
+        .build();
+    sample_row.add_suffix(&sample_scale);
+    firehose_group.add(&sample_row);
+
+    let spam_scale = gtk::Scale::with_range(gtk::Orientation::Horizontal, 0.0, 100.0, 5.0);
+    spam_scale.set_value(settings.borrow().spam_filter_strictness as f64);
+    spam_scale.set_draw_value(true);
+    spam_scale.set_value_pos(gtk::PositionType::Left);
+    spam_scale.set_width_request(200);
+    spam_scale.set_valign(gtk::Align::Center);
+    spam_scale.set_format_value_func(|_, value| if value < 0.5 { "Off".to_string() } else { format!("{:.0}%", value) });
+
+    let settings_clone = settings.clone();
+    spam_scale.connect_value_changed(move |scale| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.spam_filter_strictness = scale.value().round() as u32;
+        settings.save();
+    });
+
+    let spam_row = libadwaita::ActionRow::builder()
+        .title("Spam filter")
+        .subtitle("Hide posts that look automated: repeated text, bare links, emoji walls, accounts posting nonstop")
+        .build();
+    spam_row.add_suffix(&spam_scale);
+    firehose_group.add(&spam_row);
+    general_page.add(&firehose_group);
+
+    // Translation preferences, used by firehose splits with translation turned on
+    let translation_group = PreferencesGroup::builder()
+        .title("Translation")
+        .description("Foreign-language posts in splits with translation enabled, and article titles")
+        .build();
+
+    let provider_labels: Vec<&str> = TranslationProvider::ALL.iter().map(|p| p.label()).collect();
+    let provider_row = ComboRow::builder()
+        .title("Provider")
+        .model(&gtk::StringList::new(&provider_labels))
+        .build();
+    let current_provider = settings.borrow().translation_provider;
+    if let Some(index) = TranslationProvider::ALL.iter().position(|p| *p == current_provider) {
+        provider_row.set_selected(index as u32);
+    }
+
+    let settings_clone = settings.clone();
+    provider_row.connect_selected_notify(move |row| {
+        if let Some(provider) = TranslationProvider::ALL.get(row.selected() as usize) {
+            let mut settings = settings_clone.borrow_mut();
+            settings.translation_provider = *provider;
+            settings.save();
+        }
+    });
+    translation_group.add(&provider_row);
+
+    let target_row = EntryRow::builder()
+        .title("Target language (ISO 639-1, e.g. en)")
+        .text(settings.borrow().translation_target.as_str())
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    target_row.connect_apply(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.translation_target = row.text().trim().to_lowercase();
+        settings.save();
+    });
+    translation_group.add(&target_row);
+
+    let server_row = EntryRow::builder()
+        .title("Server URL")
+        .text(settings.borrow().translation_server_url.as_str())
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    server_row.connect_apply(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.translation_server_url = row.text().trim().to_string();
+        settings.save();
+    });
+    translation_group.add(&server_row);
+
+    let api_key_row = PasswordEntryRow::builder()
+        .title("API key (LibreTranslate only)")
+        .text(settings.borrow().translation_api_key.as_str())
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    api_key_row.connect_apply(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.translation_api_key = row.text().trim().to_string();
+        settings.save();
+    });
+    translation_group.add(&api_key_row);
+
+    let titles_row = SwitchRow::builder()
+        .title("Translate article titles")
+        .subtitle("Translate foreign-language Global Affairs headlines as they are listed")
+        .active(settings.borrow().translate_article_titles)
+        .build();
+
+    let settings_clone = settings.clone();
+    titles_row.connect_active_notify(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.translate_article_titles = row.is_active();
+        settings.save();
+    });
+    translation_group.add(&titles_row);
+
+    general_page.add(&translation_group);
+
+    // Summaries from an OpenAI-compatible API, hosted or a local llama.cpp server
+    let summary_group = PreferencesGroup::builder()
+        .title("Summaries")
+        .description("Two-sentence summaries of articles in the reader and of a country's coverage on the map, from an OpenAI-compatible API such as a local llama.cpp server. Leave the server empty to turn them off.")
+        .build();
+
+    let summary_server_row = EntryRow::builder()
+        .title("Server URL, e.g. http://localhost:8080/v1")
+        .text(settings.borrow().summary_server_url.as_str())
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    summary_server_row.connect_apply(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.summary_server_url = row.text().trim().to_string();
+        settings.save();
+    });
+    summary_group.add(&summary_server_row);
+
+    let summary_model_row = EntryRow::builder()
+        .title("Model (optional for local servers)")
+        .text(settings.borrow().summary_model.as_str())
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    summary_model_row.connect_apply(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.summary_model = row.text().trim().to_string();
+        settings.save();
+    });
+    summary_group.add(&summary_model_row);
+
+    let summary_api_key_row = PasswordEntryRow::builder()
+        .title("API key (hosted services only)")
+        .text(settings.borrow().summary_api_key.as_str())
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    summary_api_key_row.connect_apply(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.summary_api_key = row.text().trim().to_string();
+        settings.save();
+    });
+    summary_group.add(&summary_api_key_row);
+
+    general_page.add(&summary_group);
+
+    // Bluesky account, needed only for posting
+    let account_group = PreferencesGroup::builder()
+        .title("Bluesky Account")
+        .description("Used to publish posts. Create an app password in Bluesky's privacy and security settings rather than using your main password.")
+        .build();
+
+    let identifier_row = EntryRow::builder()
+        .title("Handle or email")
+        .text(settings.borrow().bluesky_identifier.as_str())
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    identifier_row.connect_apply(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.bluesky_identifier = row.text().trim().trim_start_matches('@').to_string();
+        settings.save();
+        crate::account::sign_out();
+    });
+    account_group.add(&identifier_row);
+
+    let app_password_row = PasswordEntryRow::builder()
+        .title("App password")
+        .text(settings.borrow().bluesky_app_password.as_str())
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    app_password_row.connect_apply(move |row| {
+        let password = row.text().trim().to_string();
+        settings_clone.borrow_mut().bluesky_app_password = password.clone();
+        crate::account::sign_out();
+        gtk::glib::spawn_future_local(async move {
+            if let Err(e) = crate::secrets::store_bluesky_app_password(&password).await {
+                tracing::warn!("Failed to save the app password to the keyring: {}", e);
+                crate::toasts::show(&format!("Couldn't save the app password to the keyring: {}", e));
+            }
+        });
+    });
+    account_group.add(&app_password_row);
+
+    let pds_row = EntryRow::builder()
+        .title("Hosting provider")
+        .text(settings.borrow().bluesky_pds_url.as_str())
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    pds_row.connect_apply(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.bluesky_pds_url = row.text().trim().trim_end_matches('/').to_string();
+        settings.save();
+        crate::account::sign_out();
+    });
+    account_group.add(&pds_row);
+
+    general_page.add(&account_group);
+
+    // Alt text for images attached in the composer
+    let alt_text_group = PreferencesGroup::builder()
+        .title("Image Descriptions")
+        .description("Alt text lets people using screen readers know what your images show")
+        .build();
+
+    let require_alt_row = SwitchRow::builder()
+        .title("Require alt text")
+        .subtitle("Don't post until every attached image is described")
+        .active(settings.borrow().require_alt_text)
+        .build();
+
+    let settings_clone = settings.clone();
+    require_alt_row.connect_active_notify(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.require_alt_text = row.is_active();
+        settings.save();
+    });
+    alt_text_group.add(&require_alt_row);
+
+    let caption_server_row = EntryRow::builder()
+        .title("Captioning server (Ollama API, e.g. http://localhost:11434)")
+        .text(settings.borrow().caption_server_url.as_str())
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    caption_server_row.connect_apply(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.caption_server_url = row.text().trim().trim_end_matches('/').to_string();
+        settings.save();
+    });
+    alt_text_group.add(&caption_server_row);
+
+    let caption_model_row = EntryRow::builder()
+        .title("Vision model")
+        .text(settings.borrow().caption_model.as_str())
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    caption_model_row.connect_apply(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.caption_model = row.text().trim().to_string();
+        settings.save();
+    });
+    alt_text_group.add(&caption_model_row);
+
+    general_page.add(&alt_text_group);
+
+    // Network preferences, for connections that have to go through a proxy
+    let network_group = PreferencesGroup::builder()
+        .title("Network")
+        .build();
+
+    let proxy_row = EntryRow::builder()
+        .title("Proxy (e.g. http://proxy.example:3128)")
+        .text(settings.borrow().http_proxy.as_str())
+        .show_apply_button(true)
+        .build();
+
+    let settings_clone = settings.clone();
+    proxy_row.connect_apply(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.http_proxy = row.text().trim().to_string();
+        settings.save();
+        crate::http::set_proxy(&settings.http_proxy);
+    });
+    network_group.add(&proxy_row);
+    general_page.add(&network_group);
+
+    // Developer preferences, for iterating on the UI without rebuilding
+    let developer_group = PreferencesGroup::builder()
+        .title("Developer")
+        .build();
+
+    let live_reload_row = SwitchRow::builder()
+        .title("Live-reload styles")
+        .subtitle("Apply edits to ~/.config/grapevine/style.css and layout.toml as soon as they are saved")
+        .active(settings.borrow().developer_mode)
+        .build();
+
+    let settings_clone = settings.clone();
+    live_reload_row.connect_active_notify(move |row| {
+        {
+            let mut settings = settings_clone.borrow_mut();
+            settings.developer_mode = row.is_active();
+            settings.save();
+        }
+        crate::style::set_live_reload(row.is_active(), settings_clone.clone());
+    });
+    developer_group.add(&live_reload_row);
+    general_page.add(&developer_group);
+
+    window.add(&general_page);
+
+    window
+}
+
+/// Imported outlet rating lists, and the minimum score for outlets they rate
+fn create_source_ratings_group(settings: Rc<RefCell<Settings>>) -> PreferencesGroup {
+    let group = PreferencesGroup::builder()
+        .title("Source Ratings")
+        .description("Import CSV lists of domain, score and notes to show ratings on article cards. Outlets a list doesn't rate are never hidden.")
+        .build();
+
+    let import_button = gtk::Button::builder()
+        .label("Import…")
+        .valign(gtk::Align::Center)
+        .build();
+    let import_row = libadwaita::ActionRow::builder()
+        .title("Rating lists")
+        .subtitle("Importing a file with the same name replaces its list")
+        .build();
+    import_row.add_suffix(&import_button);
+    group.add(&import_row);
+
+    let hide_row = SwitchRow::builder()
+        .title("Hide low-rated outlets")
+        .active(settings.borrow().hide_low_rated_sources)
+        .build();
+
+    let settings_clone = settings.clone();
+    hide_row.connect_active_notify(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.hide_low_rated_sources = row.is_active();
+        settings.save();
+    });
+    group.add(&hide_row);
+
+    let min_score_row = SpinRow::with_range(-1000.0, 1000.0, 1.0);
+    min_score_row.set_title("Minimum score");
+    min_score_row.set_subtitle("On the lists' own scale");
+    min_score_row.set_digits(2);
+    min_score_row.set_value(settings.borrow().min_source_score);
+
+    let settings_clone = settings.clone();
+    min_score_row.connect_value_notify(move |row| {
+        let mut settings = settings_clone.borrow_mut();
+        settings.min_source_score = row.value();
+        settings.save();
+    });
+    hide_row.bind_property("active", &min_score_row, "sensitive")
+        .sync_create()
+        .build();
+    group.add(&min_score_row);
+
+    // One row per imported list, rebuilt after each import or removal
+    let list_rows: Rc<RefCell<Vec<libadwaita::ActionRow>>> = Rc::new(RefCell::new(Vec::new()));
+    fill_rating_lists(&group, &list_rows);
+
+    let group_for_import = group.clone();
+    import_button.connect_clicked(move |button| {
+        let filters = gtk::gio::ListStore::new::<gtk::FileFilter>();
+        let csv_filter = gtk::FileFilter::new();
+        csv_filter.set_name(Some("CSV files"));
+        csv_filter.add_pattern("*.csv");
+        csv_filter.add_mime_type("text/csv");
+        filters.append(&csv_filter);
+
+        let dialog = gtk::FileDialog::builder()
+            .title("Import Rating List")
+            .filters(&filters)
+            .modal(true)
+            .build();
+
+        let parent = button.root().and_downcast::<gtk::Window>();
+        let import_row = import_row.clone();
+        let group = group_for_import.clone();
+        let list_rows = list_rows.clone();
+        dialog.open(parent.as_ref(), gtk::gio::Cancellable::NONE, move |result| {
+            let Ok(file) = result else {
+                return; // Dismissed by the user
+            };
+            let Some(path) = file.path() else {
+                tracing::warn!("Rating list has no local path");
+                return;
+            };
+
+            match crate::credibility::import_csv(&path) {
+                Ok((name, count)) => {
+                    import_row.set_subtitle(&format!("Imported {} ratings from {}", count, name));
+                    fill_rating_lists(&group, &list_rows);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to import {}: {}", path.display(), e);
+                    import_row.set_subtitle(&format!("Couldn't import that file: {}", e));
+                }
+            }
+        });
+    });
+
+    group
+}
+
+/// Replace the rows listing imported rating lists, each with a button to remove it
+fn fill_rating_lists(group: &PreferencesGroup, list_rows: &Rc<RefCell<Vec<libadwaita::ActionRow>>>) {
+    for row in list_rows.borrow_mut().drain(..) {
+        group.remove(&row);
+    }
+
+    for (name, count) in crate::credibility::lists() {
+        let row = libadwaita::ActionRow::builder()
+            .title(name.as_str())
+            .subtitle(format!("{} outlets", count))
+            .build();
+        let remove_button = gtk::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text("Remove list")
+            .valign(gtk::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+
+        let group_for_remove = group.clone();
+        let list_rows_for_remove = list_rows.clone();
+        remove_button.connect_clicked(move |_| {
+            crate::credibility::remove_list(&name);
+            fill_rating_lists(&group_for_remove, &list_rows_for_remove);
+        });
+        row.add_suffix(&remove_button);
+        group.add(&row);
+        list_rows.borrow_mut().push(row);
+    }
+}
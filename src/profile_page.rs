@@ -0,0 +1,289 @@
+use gtk::prelude::*;
+use gtk::{glib, Label, Orientation};
+use libadwaita::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::appview;
+use crate::data::{AppViewPost, AppViewProfile};
+use crate::error::FetchError;
+use crate::hashtag_feed::create_feed_row;
+
+/// How long a fetched profile is shown again without asking the AppView
+const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+/// Recent posts listed under the profile
+const RECENT_POSTS: u8 = 25;
+const AVATAR_SIZE: i32 = 64;
+
+/// A profile with its recent posts, as fetched together
+struct ProfileView {
+    profile: AppViewProfile,
+    posts: Vec<AppViewPost>,
+}
+
+thread_local! {
+    /// Navigation view holding the firehose and any profile pages pushed on top
+    static NAVIGATION: RefCell<Option<libadwaita::NavigationView>> = const { RefCell::new(None) };
+    /// Fetched profiles by DID, with when they were fetched
+    static CACHE: RefCell<HashMap<String, (Instant, Rc<ProfileView>)>> = RefCell::new(HashMap::new());
+}
+
+/// Put the firehose at the root of a navigation view that profile pages are pushed onto
+pub fn create_navigation(stream: &impl IsA<gtk::Widget>) -> libadwaita::NavigationView {
+    let root_page = libadwaita::NavigationPage::builder()
+        .title("Firehose")
+        .tag("stream")
+        .child(stream)
+        .build();
+
+    let navigation = libadwaita::NavigationView::builder()
+        .vexpand(true)
+        .build();
+    navigation.add(&root_page);
+
+    NAVIGATION.with(|current| *current.borrow_mut() = Some(navigation.clone()));
+    navigation
+}
+
+/// Open the profile page for a DID, or go back to it if it's already in the stack
+pub fn show(did: &str) {
+    let Some(navigation) = NAVIGATION.with(|current| current.borrow().clone()) else {
+        return;
+    };
+
+    let tag = format!("profile-{}", did);
+    if navigation.visible_page().and_then(|page| page.tag()).as_deref() == Some(tag.as_str()) {
+        return;
+    }
+    // Page tags are unique, so a profile further down is returned to
+    if navigation.find_page(&tag).is_some() {
+        navigation.pop_to_tag(&tag);
+        return;
+    }
+
+    let page = libadwaita::NavigationPage::builder()
+        .title("Profile")
+        .tag(tag.as_str())
+        .child(&create_profile_content(did))
+        .build();
+    navigation.push(&page);
+}
+
+fn cached(did: &str) -> Option<Rc<ProfileView>> {
+    CACHE.with(|cache| {
+        cache
+            .borrow()
+            .get(did)
+            .filter(|(fetched, _)| fetched.elapsed() < CACHE_TTL)
+            .map(|(_, view)| view.clone())
+    })
+}
+
+async fn fetch_view(did: &str) -> Result<Rc<ProfileView>, FetchError> {
+    if let Some(view) = cached(did) {
+        return Ok(view);
+    }
+
+    let profile = appview::fetch_profile(did).await?;
+    let posts = appview::fetch_author_feed(did, RECENT_POSTS).await?;
+    let view = Rc::new(ProfileView { profile, posts });
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        cache.retain(|_, (fetched, _)| fetched.elapsed() < CACHE_TTL);
+        cache.insert(did.to_string(), (Instant::now(), view.clone()));
+    });
+    Ok(view)
+}
+
+fn create_section_heading(text: &str) -> Label {
+    let label = Label::builder()
+        .label(text)
+        .xalign(0.0)
+        .margin_top(12)
+        .build();
+    label.add_css_class("heading");
+    label
+}
+
+fn create_profile_content(did: &str) -> gtk::Widget {
+    let content = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .margin_top(18)
+        .margin_bottom(18)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+
+    glib::spawn_future_local(load_profile(content.clone(), did.to_string()));
+
+    let clamp = libadwaita::Clamp::builder()
+        .maximum_size(640)
+        .child(&content)
+        .build();
+    let scrolled = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .vexpand(true)
+        .child(&clamp)
+        .build();
+
+    // The window's own buttons are already in the main header bar
+    let header_bar = libadwaita::HeaderBar::builder()
+        .show_start_title_buttons(false)
+        .show_end_title_buttons(false)
+        .build();
+    let toolbar_view = libadwaita::ToolbarView::new();
+    toolbar_view.add_top_bar(&header_bar);
+    toolbar_view.set_content(Some(&scrolled));
+    toolbar_view.upcast()
+}
+
+/// Fill the page with the profile and its recent posts, with a spinner meanwhile
+async fn load_profile(content: gtk::Box, did: String) {
+    while let Some(child) = content.first_child() {
+        content.remove(&child);
+    }
+    let spinner = gtk::Spinner::builder()
+        .spinning(true)
+        .halign(gtk::Align::Center)
+        .margin_top(24)
+        .build();
+    content.append(&spinner);
+
+    let result = fetch_view(&did).await;
+    content.remove(&spinner);
+
+    let view = match result {
+        Ok(view) => view,
+        Err(e) => {
+            tracing::warn!("Failed to load profile {}: {}", did, e);
+            let error_label = Label::builder()
+                .label(format!("Couldn't load {}", did))
+                .wrap(true)
+                .xalign(0.0)
+                .build();
+            error_label.add_css_class("dim-label");
+            content.append(&error_label);
+            crate::toasts::show_error(&e, move || {
+                glib::spawn_future_local(load_profile(content.clone(), did.clone()));
+            });
+            return;
+        }
+    };
+
+    append_profile_header(&content, &view.profile);
+
+    content.append(&create_section_heading("Recent Posts"));
+    if view.posts.is_empty() {
+        let empty_label = Label::builder()
+            .label("No posts yet")
+            .xalign(0.0)
+            .build();
+        empty_label.add_css_class("dim-label");
+        content.append(&empty_label);
+        return;
+    }
+    let posts_list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .build();
+    posts_list.add_css_class("boxed-list");
+    for post in view.posts.iter() {
+        posts_list.append(&create_feed_row(post));
+    }
+    content.append(&posts_list);
+}
+
+fn append_profile_header(content: &gtk::Box, profile: &AppViewProfile) {
+    let name = match profile.display_name {
+        Some(ref name) if !name.trim().is_empty() => name.clone(),
+        _ => profile.handle.clone(),
+    };
+
+    let title_row = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(12)
+        .build();
+    let avatar = libadwaita::Avatar::new(AVATAR_SIZE, Some(&name), true);
+    if let Some(url) = profile.avatar.clone() {
+        let avatar = avatar.clone();
+        glib::spawn_future_local(async move {
+            match crate::images::load_texture(&url, AVATAR_SIZE * 2, AVATAR_SIZE * 2).await {
+                Ok(texture) => avatar.set_custom_image(Some(&texture)),
+                Err(e) => tracing::warn!("Failed to load avatar: {}", e),
+            }
+        });
+    }
+    title_row.append(&avatar);
+
+    let names = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .valign(gtk::Align::Center)
+        .hexpand(true)
+        .build();
+    let name_label = Label::builder()
+        .label(&name)
+        .xalign(0.0)
+        .wrap(true)
+        .selectable(true)
+        .build();
+    name_label.add_css_class("title-2");
+    names.append(&name_label);
+    let handle_label = Label::builder()
+        .label(format!("@{}", profile.handle))
+        .xalign(0.0)
+        .selectable(true)
+        .build();
+    handle_label.add_css_class("dim-label");
+    names.append(&handle_label);
+    title_row.append(&names);
+
+    let open_button = gtk::Button::builder()
+        .icon_name("web-browser-symbolic")
+        .tooltip_text("Open on Bluesky")
+        .valign(gtk::Align::Center)
+        .css_classes(vec!["flat".to_string()])
+        .build();
+    let url = format!("https://bsky.app/profile/{}", profile.did);
+    open_button.connect_clicked(move |_| {
+        if let Err(e) = open::that(&url) {
+            tracing::warn!("Failed to open URL: {}", e);
+        }
+    });
+    title_row.append(&open_button);
+    content.append(&title_row);
+
+    let counts_label = Label::builder()
+        .label(format!(
+            "{} followers · {} following · {} posts",
+            profile.followers_count, profile.follows_count, profile.posts_count
+        ))
+        .xalign(0.0)
+        .build();
+    counts_label.add_css_class("caption");
+    content.append(&counts_label);
+
+    if let Some(bio) = profile.description.as_ref().filter(|bio| !bio.trim().is_empty()) {
+        let bio_label = Label::builder()
+            .label(bio.trim())
+            .xalign(0.0)
+            .wrap(true)
+            .wrap_mode(gtk::pango::WrapMode::WordChar)
+            .selectable(true)
+            .margin_top(6)
+            .build();
+        content.append(&bio_label);
+    }
+
+    let did_label = Label::builder()
+        .label(profile.did.as_str())
+        .xalign(0.0)
+        .selectable(true)
+        .ellipsize(gtk::pango::EllipsizeMode::Middle)
+        .build();
+    did_label.add_css_class("caption");
+    did_label.add_css_class("monospace");
+    did_label.add_css_class("dim-label");
+    content.append(&did_label);
+}
@@ -0,0 +1,288 @@
+use gtk::prelude::*;
+use gtk::{glib, Label};
+use serde::Deserialize;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::data::{AppViewAuthor, APPVIEW_API_URL};
+use crate::demo;
+use crate::error::{get_json, FetchError};
+
+const SERVICE: &str = "Bluesky profiles";
+const PLC_SERVICE: &str = "PLC directory";
+const PLC_DIRECTORY_URL: &str = "https://plc.directory";
+
+/// Accounts remembered, least recently shown evicted first
+const MAX_PROFILES: usize = 5000;
+/// Least recently used profiles dropped together once the cache is full
+const EVICTION_BATCH: usize = 500;
+/// getProfiles accepts at most this many actors per request
+const MAX_ACTORS_PER_REQUEST: usize = 25;
+/// How long lookups are collected before they're sent together
+const BATCH_DELAY: Duration = Duration::from_millis(500);
+/// Least time between two requests, so a fast stream can't flood the AppView
+const MIN_REQUEST_GAP: Duration = Duration::from_millis(250);
+/// Lookups waiting beyond this are refused; those rows keep showing the DID
+const MAX_PENDING: usize = 250;
+
+const AVATAR_SIZE: i32 = 20;
+
+/// Who is behind a DID, as shown in a post's header
+#[derive(Debug, Clone)]
+pub struct Profile {
+    pub handle: String,
+    pub display_name: Option<String>,
+    pub avatar: Option<String>,
+}
+
+impl From<AppViewAuthor> for Profile {
+    fn from(author: AppViewAuthor) -> Self {
+        Self {
+            handle: author.handle,
+            display_name: author.display_name.filter(|name| !name.trim().is_empty()),
+            avatar: author.avatar,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ProfilesResponse {
+    #[serde(default)]
+    profiles: Vec<AppViewAuthor>,
+}
+
+/// The parts of a DID document that name the account
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PlcDocument {
+    #[serde(default)]
+    also_known_as: Vec<String>,
+}
+
+/// Resolved DIDs, None for ones that couldn't be, evicted least recently used first
+#[derive(Default)]
+struct ProfileCache {
+    /// Each profile with the stamp of its last use
+    profiles: HashMap<String, (Option<Profile>, u64)>,
+    /// Bumped on every use; higher stamps were used more recently
+    clock: u64,
+}
+
+impl ProfileCache {
+    fn get(&mut self, did: &str) -> Option<Option<Profile>> {
+        self.clock += 1;
+        let (profile, stamp) = self.profiles.get_mut(did)?;
+        *stamp = self.clock;
+        Some(profile.clone())
+    }
+
+    fn insert(&mut self, did: String, profile: Option<Profile>) {
+        self.clock += 1;
+        self.profiles.insert(did, (profile, self.clock));
+
+        // Evict a batch at a time, so the scan for the oldest stamps is rare
+        if self.profiles.len() > MAX_PROFILES {
+            let mut stamps: Vec<u64> = self.profiles.values().map(|(_, stamp)| *stamp).collect();
+            let evicted = self.profiles.len() - MAX_PROFILES + EVICTION_BATCH;
+            let (_, cutoff, _) = stamps.select_nth_unstable(evicted - 1);
+            let cutoff = *cutoff;
+            self.profiles.retain(|_, (_, stamp)| *stamp > cutoff);
+        }
+    }
+}
+
+type Waiter = flume::Sender<Option<Profile>>;
+
+thread_local! {
+    static CACHE: RefCell<ProfileCache> = RefCell::new(ProfileCache::default());
+    /// DIDs waiting for the next batch, each with everyone waiting on it
+    static PENDING: RefCell<HashMap<String, Vec<Waiter>>> = RefCell::new(HashMap::new());
+    static BATCH_SCHEDULED: Cell<bool> = const { Cell::new(false) };
+    static LAST_REQUEST: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// The handle, name and avatar behind a DID, from the cache or looked up with
+/// others requested around the same time; None when it can't be resolved
+pub async fn resolve(did: &str) -> Option<Profile> {
+    if let Some(profile) = CACHE.with(|cache| cache.borrow_mut().get(did)) {
+        return profile;
+    }
+    // Demo posts come from made-up accounts
+    if demo::is_enabled() {
+        return None;
+    }
+
+    let (tx, rx) = flume::bounded(1);
+    let queued = PENDING.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        if let Some(waiters) = pending.get_mut(did) {
+            waiters.push(tx);
+            return true;
+        }
+        if pending.len() >= MAX_PENDING {
+            return false;
+        }
+        pending.insert(did.to_string(), vec![tx]);
+        true
+    });
+    if !queued {
+        return None;
+    }
+
+    if !BATCH_SCHEDULED.with(|scheduled| scheduled.replace(true)) {
+        glib::timeout_add_local_once(BATCH_DELAY, || {
+            glib::spawn_future_local(send_pending());
+        });
+    }
+
+    rx.recv_async().await.ok().flatten()
+}
+
+/// Drop what's remembered of an account, e.g. after its handle changed
+pub fn forget(did: &str) {
+    CACHE.with(|cache| cache.borrow_mut().profiles.remove(did));
+}
+
+/// Hold off until the last request is `MIN_REQUEST_GAP` old
+async fn wait_for_turn() {
+    if let Some(last) = LAST_REQUEST.with(Cell::get) {
+        let elapsed = last.elapsed();
+        if elapsed < MIN_REQUEST_GAP {
+            glib::timeout_future(MIN_REQUEST_GAP - elapsed).await;
+        }
+    }
+    LAST_REQUEST.with(|last| last.set(Some(Instant::now())));
+}
+
+async fn send_pending() {
+    loop {
+        // Lookups made while a batch is out join the next one
+        let batch: Vec<(String, Vec<Waiter>)> = PENDING.with(|pending| {
+            let mut pending = pending.borrow_mut();
+            let dids: Vec<String> = pending.keys().take(MAX_ACTORS_PER_REQUEST).cloned().collect();
+            dids.into_iter()
+                .filter_map(|did| pending.remove_entry(&did))
+                .collect()
+        });
+        if batch.is_empty() {
+            BATCH_SCHEDULED.with(|scheduled| scheduled.set(false));
+            return;
+        }
+
+        wait_for_turn().await;
+        let dids: Vec<&str> = batch.iter().map(|(did, _)| did.as_str()).collect();
+        let mut profiles = match fetch_profiles(&dids).await {
+            Ok(profiles) => profiles,
+            Err(e) => {
+                tracing::warn!("Failed to look up {} profiles: {}", dids.len(), e);
+                // Not remembered, so they're tried again when seen next
+                for (_, waiters) in batch {
+                    for waiter in waiters {
+                        let _ = waiter.send(None);
+                    }
+                }
+                continue;
+            }
+        };
+
+        for (did, waiters) in batch {
+            let profile = match profiles.remove(&did) {
+                Some(profile) => Some(profile),
+                // Accounts the AppView doesn't serve may still have a handle
+                None => {
+                    wait_for_turn().await;
+                    resolve_handle(&did).await.map(|handle| Profile { handle, display_name: None, avatar: None })
+                }
+            };
+            CACHE.with(|cache| cache.borrow_mut().insert(did, profile.clone()));
+            for waiter in waiters {
+                let _ = waiter.send(profile.clone());
+            }
+        }
+    }
+}
+
+/// Profiles for up to `MAX_ACTORS_PER_REQUEST` DIDs from the public AppView, by DID
+async fn fetch_profiles(dids: &[&str]) -> Result<HashMap<String, Profile>, FetchError> {
+    let query: Vec<(&str, &str)> = dids.iter().map(|did| ("actors", *did)).collect();
+    let request = crate::http::client()
+        .get(format!("{}/app.bsky.actor.getProfiles", APPVIEW_API_URL))
+        .query(&query);
+    let data: ProfilesResponse = get_json(SERVICE, request).await?;
+    Ok(data
+        .profiles
+        .into_iter()
+        .map(|author| (author.did.clone(), Profile::from(author)))
+        .collect())
+}
+
+/// A DID's handle from its DID document: the PLC directory's for did:plc,
+/// the domain itself for did:web
+async fn resolve_handle(did: &str) -> Option<String> {
+    if let Some(domain) = did.strip_prefix("did:web:") {
+        return Some(domain.to_string());
+    }
+    if !did.starts_with("did:plc:") {
+        return None;
+    }
+
+    let request = crate::http::client().get(format!("{}/{}", PLC_DIRECTORY_URL, did));
+    match get_json::<PlcDocument>(PLC_SERVICE, request).await {
+        Ok(document) => document
+            .also_known_as
+            .iter()
+            .find_map(|alias| alias.strip_prefix("at://"))
+            .map(str::to_string),
+        Err(e) => {
+            tracing::warn!("Failed to resolve {}: {}", did, e);
+            None
+        }
+    }
+}
+
+/// Avatar and handle for a post's header, hidden until the author's DID
+/// resolves and then filled in place
+pub fn create_author_badge(did: &str) -> gtk::Box {
+    let badge = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(4)
+        .visible(false)
+        .build();
+    badge.add_css_class("author-badge");
+
+    let avatar = libadwaita::Avatar::new(AVATAR_SIZE, None, true);
+    let handle_label = Label::builder()
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .max_width_chars(28)
+        .build();
+    handle_label.add_css_class("caption");
+    badge.append(&avatar);
+    badge.append(&handle_label);
+
+    let did = did.to_string();
+    let badge_weak = badge.downgrade();
+    glib::spawn_future_local(async move {
+        let Some(profile) = resolve(&did).await else {
+            return;
+        };
+        // The row may have scrolled out of the list meanwhile
+        let Some(badge) = badge_weak.upgrade() else {
+            return;
+        };
+        let name = profile.display_name.clone().unwrap_or_else(|| profile.handle.clone());
+        avatar.set_text(Some(&name));
+        handle_label.set_label(&format!("@{}", profile.handle));
+        badge.set_tooltip_text(Some(&format!("{}\n@{}", name, profile.handle)));
+        badge.set_visible(true);
+
+        if let Some(url) = profile.avatar {
+            match crate::images::load_texture(&url, AVATAR_SIZE * 2, AVATAR_SIZE * 2).await {
+                Ok(texture) => avatar.set_custom_image(Some(&texture)),
+                Err(e) => tracing::warn!("Failed to load avatar: {}", e),
+            }
+        }
+    });
+
+    badge
+}
@@ -0,0 +1,290 @@
+use gtk::prelude::*;
+use gtk::{Label, Orientation, SearchEntry};
+use std::sync::OnceLock;
+
+/// Source languages as (label, GDELT `sourcelang:` value)
+const LANGUAGES: &[(&str, &str)] = &[
+    ("Arabic", "arabic"),
+    ("Chinese", "chinese"),
+    ("English", "english"),
+    ("French", "french"),
+    ("German", "german"),
+    ("Hindi", "hindi"),
+    ("Italian", "italian"),
+    ("Japanese", "japanese"),
+    ("Korean", "korean"),
+    ("Portuguese", "portuguese"),
+    ("Russian", "russian"),
+    ("Spanish", "spanish"),
+    ("Turkish", "turkish"),
+    ("Ukrainian", "ukrainian"),
+];
+
+/// Source countries as (label, GDELT `sourcecountry:` value), from the bundled country dataset
+fn country_options() -> &'static [(&'static str, &'static str)] {
+    static OPTIONS: OnceLock<Vec<(&'static str, &'static str)>> = OnceLock::new();
+
+    OPTIONS.get_or_init(|| {
+        let mut options: Vec<(&'static str, &'static str)> = crate::coordinates::all_countries()
+            .iter()
+            .map(|country| (country.name.as_str(), country.gdelt_name.as_str()))
+            .collect();
+        options.sort_by_key(|(label, _)| *label);
+        options
+    })
+}
+
+/// GKG themes as (label, GDELT `theme:` value)
+const THEMES: &[(&str, &str)] = &[
+    ("Climate change", "ENV_CLIMATECHANGE"),
+    ("Cyber attacks", "CYBER_ATTACK"),
+    ("Economy: inflation", "ECON_INFLATION"),
+    ("Elections", "ELECTION"),
+    ("Health: pandemic", "HEALTH_PANDEMIC"),
+    ("Military", "MILITARY"),
+    ("Natural disasters", "NATURAL_DISASTER"),
+    ("Protests", "PROTEST"),
+    ("Refugees", "REFUGEES"),
+    ("Terrorism", "TERROR"),
+];
+
+/// Selections made in the builder, turned into a GDELT query string
+#[derive(Default)]
+struct QueryParts {
+    keywords: String,
+    language: Option<&'static str>,
+    country: Option<&'static str>,
+    domain: String,
+    theme: Option<&'static str>,
+    tone: Option<(f64, f64)>,
+}
+
+impl QueryParts {
+    fn to_query(&self) -> String {
+        let mut terms = Vec::new();
+
+        let keywords = self.keywords.trim();
+        if !keywords.is_empty() {
+            terms.push(keywords.to_string());
+        }
+        if let Some(language) = self.language {
+            terms.push(format!("sourcelang:{}", language));
+        }
+        if let Some(country) = self.country {
+            terms.push(format!("sourcecountry:{}", country));
+        }
+        let domain = self.domain.trim();
+        if !domain.is_empty() {
+            terms.push(format!("domain:{}", domain));
+        }
+        if let Some(theme) = self.theme {
+            terms.push(format!("theme:{}", theme));
+        }
+        if let Some((min, max)) = self.tone {
+            terms.push(format!("tone>{} tone<{}", min, max));
+        }
+
+        terms.join(" ")
+    }
+}
+
+/// Dropdown with an "Any" entry followed by the given options
+fn create_option_dropdown(options: &'static [(&'static str, &'static str)]) -> gtk::DropDown {
+    let mut labels = vec!["Any"];
+    labels.extend(options.iter().map(|(label, _)| *label));
+    let dropdown = gtk::DropDown::from_strings(&labels);
+    dropdown.set_hexpand(true);
+    dropdown
+}
+
+/// Value behind a dropdown created by create_option_dropdown, None for "Any"
+fn selected_option(dropdown: &gtk::DropDown, options: &'static [(&'static str, &'static str)]) -> Option<&'static str> {
+    let index = dropdown.selected() as usize;
+    if index == 0 {
+        None
+    } else {
+        options.get(index - 1).map(|(_, value)| *value)
+    }
+}
+
+/// Label + control row for the builder grid
+fn attach_row(grid: &gtk::Grid, row: i32, label: &str, widget: &impl IsA<gtk::Widget>) {
+    let label = Label::builder()
+        .label(label)
+        .xalign(1.0)
+        .build();
+    label.add_css_class("dim-label");
+    grid.attach(&label, 0, row, 1, 1);
+    grid.attach(widget, 1, row, 1, 1);
+}
+
+/// Button with a popover that assembles a GDELT query into the search entry;
+/// the entry still accepts raw query syntax typed by hand
+pub fn create_query_builder_button(search_entry: &SearchEntry) -> gtk::MenuButton {
+    let grid = gtk::Grid::builder()
+        .row_spacing(8)
+        .column_spacing(12)
+        .margin_top(12)
+        .margin_bottom(12)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+
+    let title = Label::builder()
+        .label("Query builder")
+        .xalign(0.0)
+        .build();
+    title.add_css_class("heading");
+    grid.attach(&title, 0, 0, 2, 1);
+
+    let keywords_entry = gtk::Entry::builder()
+        .placeholder_text("Words or \"exact phrase\"")
+        .hexpand(true)
+        .build();
+    attach_row(&grid, 1, "Keywords", &keywords_entry);
+
+    let language_dropdown = create_option_dropdown(LANGUAGES);
+    attach_row(&grid, 2, "Language", &language_dropdown);
+
+    let country_dropdown = create_option_dropdown(country_options());
+    attach_row(&grid, 3, "Country", &country_dropdown);
+
+    let domain_entry = gtk::Entry::builder()
+        .placeholder_text("example.com")
+        .hexpand(true)
+        .build();
+    attach_row(&grid, 4, "Domain", &domain_entry);
+
+    let theme_dropdown = create_option_dropdown(THEMES);
+    attach_row(&grid, 5, "Theme", &theme_dropdown);
+
+    // Tone range, from very negative (-10) to very positive (+10)
+    let tone_box = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    let tone_check = gtk::CheckButton::new();
+    let tone_min = gtk::SpinButton::with_range(-10.0, 10.0, 1.0);
+    tone_min.set_value(-10.0);
+    let tone_max = gtk::SpinButton::with_range(-10.0, 10.0, 1.0);
+    tone_max.set_value(10.0);
+    tone_min.set_sensitive(false);
+    tone_max.set_sensitive(false);
+    tone_box.append(&tone_check);
+    tone_box.append(&tone_min);
+    tone_box.append(&Label::new(Some("to")));
+    tone_box.append(&tone_max);
+    attach_row(&grid, 6, "Tone", &tone_box);
+
+    let tone_min_clone = tone_min.clone();
+    let tone_max_clone = tone_max.clone();
+    tone_check.connect_toggled(move |check| {
+        tone_min_clone.set_sensitive(check.is_active());
+        tone_max_clone.set_sensitive(check.is_active());
+    });
+
+    // Live preview of the assembled query
+    let preview_label = Label::builder()
+        .xalign(0.0)
+        .wrap(true)
+        .wrap_mode(gtk::pango::WrapMode::WordChar)
+        .selectable(true)
+        .max_width_chars(40)
+        .build();
+    preview_label.add_css_class("caption");
+    preview_label.add_css_class("monospace");
+    grid.attach(&preview_label, 0, 7, 2, 1);
+
+    let button_box = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .halign(gtk::Align::End)
+        .build();
+    let reset_button = gtk::Button::with_label("Reset");
+    let search_button = gtk::Button::with_label("Search");
+    search_button.add_css_class("suggested-action");
+    button_box.append(&reset_button);
+    button_box.append(&search_button);
+    grid.attach(&button_box, 0, 8, 2, 1);
+
+    let popover = gtk::Popover::builder()
+        .child(&grid)
+        .build();
+
+    let menu_button = gtk::MenuButton::builder()
+        .icon_name("document-edit-symbolic")
+        .tooltip_text("Build a query")
+        .popover(&popover)
+        .build();
+
+    // Gather the current selections
+    let collect_parts = {
+        let keywords_entry = keywords_entry.clone();
+        let language_dropdown = language_dropdown.clone();
+        let country_dropdown = country_dropdown.clone();
+        let domain_entry = domain_entry.clone();
+        let theme_dropdown = theme_dropdown.clone();
+        let tone_check = tone_check.clone();
+        let tone_min = tone_min.clone();
+        let tone_max = tone_max.clone();
+        move || QueryParts {
+            keywords: keywords_entry.text().to_string(),
+            language: selected_option(&language_dropdown, LANGUAGES),
+            country: selected_option(&country_dropdown, country_options()),
+            domain: domain_entry.text().to_string(),
+            theme: selected_option(&theme_dropdown, THEMES),
+            tone: tone_check.is_active().then(|| {
+                let (min, max) = (tone_min.value(), tone_max.value());
+                (min.min(max), min.max(max))
+            }),
+        }
+    };
+
+    let update_preview = {
+        let collect_parts = collect_parts.clone();
+        let preview_label = preview_label.clone();
+        move || {
+            let query = collect_parts().to_query();
+            preview_label.set_label(if query.is_empty() { "Latest news (no filters)" } else { &query });
+        }
+    };
+    update_preview();
+
+    let update = update_preview.clone();
+    keywords_entry.connect_changed(move |_| update());
+    let update = update_preview.clone();
+    domain_entry.connect_changed(move |_| update());
+    for dropdown in [&language_dropdown, &country_dropdown, &theme_dropdown] {
+        let update = update_preview.clone();
+        dropdown.connect_selected_notify(move |_| update());
+    }
+    let update = update_preview.clone();
+    tone_check.connect_toggled(move |_| update());
+    for spin in [&tone_min, &tone_max] {
+        let update = update_preview.clone();
+        spin.connect_value_changed(move |_| update());
+    }
+
+    // Reset every control back to "Any"
+    reset_button.connect_clicked(move |_| {
+        keywords_entry.set_text("");
+        domain_entry.set_text("");
+        language_dropdown.set_selected(0);
+        country_dropdown.set_selected(0);
+        theme_dropdown.set_selected(0);
+        tone_check.set_active(false);
+        tone_min.set_value(-10.0);
+        tone_max.set_value(10.0);
+    });
+
+    // Write the query into the search entry and run it
+    let search_entry_clone = search_entry.clone();
+    let popover_clone = popover.clone();
+    search_button.connect_clicked(move |_| {
+        search_entry_clone.set_text(&collect_parts().to_query());
+        search_entry_clone.emit_activate();
+        popover_clone.popdown();
+    });
+
+    menu_button
+}
@@ -0,0 +1,65 @@
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+
+use crate::json_store::JsonStore;
+
+/// Articles remembered as read, the longest ago forgotten first
+const MAX_READ: usize = 10_000;
+
+/// Hashes of the canonical URLs of opened articles, in the order they were opened
+#[derive(Default)]
+struct ReadStore {
+    hashes: HashSet<u64>,
+    order: VecDeque<u64>,
+}
+
+thread_local! {
+    /// Loaded from disk on first use
+    static STORE: RefCell<Option<ReadStore>> = const { RefCell::new(None) };
+}
+
+const FILE: JsonStore = JsonStore::new("read_articles.json", "read articles").compact();
+
+/// FNV-1a of the canonical URL; unlike the std hasher it stays the same
+/// across builds, so the file written by one version is read by the next
+fn url_hash(url: &str) -> u64 {
+    crate::links::canonicalize(url)
+        .bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+fn load() -> ReadStore {
+    let order: VecDeque<u64> = FILE.load();
+    ReadStore {
+        hashes: order.iter().copied().collect(),
+        order,
+    }
+}
+
+fn save(store: &ReadStore) {
+    FILE.save(&store.order);
+}
+
+pub fn is_read(url: &str) -> bool {
+    let hash = url_hash(url);
+    STORE.with(|store| store.borrow_mut().get_or_insert_with(load).hashes.contains(&hash))
+}
+
+/// Remember an article as opened, so later refreshes show it as read
+pub fn mark_read(url: &str) {
+    let hash = url_hash(url);
+    STORE.with(|store| {
+        let mut store = store.borrow_mut();
+        let store = store.get_or_insert_with(load);
+        if !store.hashes.insert(hash) {
+            return;
+        }
+        store.order.push_back(hash);
+        while store.order.len() > MAX_READ {
+            if let Some(oldest) = store.order.pop_front() {
+                store.hashes.remove(&oldest);
+            }
+        }
+        save(store);
+    });
+}
@@ -0,0 +1,186 @@
+use gtk::prelude::*;
+use gtk::{glib, Label, Orientation};
+
+use crate::error::{get_text, FetchError};
+use crate::opengraph::decode_entities;
+
+const SERVICE: &str = "Article site";
+/// Whole articles are bigger than the pages most services return
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// Paragraphs shorter than this are usually bylines, captions or navigation
+const MIN_PARAGRAPH_CHARS: usize = 60;
+
+/// Fetch an article and pull out the text of its paragraphs
+pub async fn fetch_article_text(url: &str) -> Result<Vec<String>, FetchError> {
+    let html = get_text(SERVICE, crate::http::client().get(url).timeout(TIMEOUT)).await?;
+    let paragraphs = extract_paragraphs(&html);
+    if paragraphs.is_empty() {
+        return Err(FetchError::Empty { service: SERVICE });
+    }
+    Ok(paragraphs)
+}
+
+/// Text of the <p> elements inside the page's <article>, or the whole page if
+/// it has none, without markup
+fn extract_paragraphs(html: &str) -> Vec<String> {
+    // ASCII lowercasing keeps byte offsets identical to the original
+    let lower = html.to_ascii_lowercase();
+    let (start, end) = match (lower.find("<article"), lower.rfind("</article")) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        _ => (0, html.len()),
+    };
+
+    let mut paragraphs = Vec::new();
+    let mut search_from = start;
+    while let Some(open) = lower[search_from..end].find("<p") {
+        let tag_start = search_from + open;
+        search_from = tag_start + 2;
+
+        // Skip <pre>, <picture> and the like
+        if !matches!(lower.as_bytes().get(tag_start + 2), Some(b'>' | b' ' | b'\t' | b'\n')) {
+            continue;
+        }
+        let Some(tag_len) = lower[tag_start..end].find('>') else {
+            break;
+        };
+        let text_start = tag_start + tag_len + 1;
+        let text_end = lower[text_start..end].find("</p").map_or(end, |len| text_start + len);
+
+        let text = strip_tags(&html[text_start..text_end]);
+        if text.chars().count() >= MIN_PARAGRAPH_CHARS {
+            paragraphs.push(text);
+        }
+        search_from = text_end;
+    }
+    paragraphs
+}
+
+/// Drop tags from a fragment of HTML and collapse its whitespace
+fn strip_tags(fragment: &str) -> String {
+    let mut text = String::with_capacity(fragment.len());
+    let mut in_tag = false;
+    for c in fragment.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Show an article's text in a distraction-free window, without leaving the app
+pub fn present_reader(parent: Option<&gtk::Window>, title: &str, url: &str) {
+    let content = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(12)
+        .margin_top(24)
+        .margin_bottom(24)
+        .margin_start(18)
+        .margin_end(18)
+        .build();
+
+    let title_label = Label::builder()
+        .label(title)
+        .xalign(0.0)
+        .wrap(true)
+        .selectable(true)
+        .build();
+    title_label.add_css_class("title-1");
+    content.append(&title_label);
+
+    let domain = reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default();
+    let domain_label = Label::builder()
+        .label(&domain)
+        .xalign(0.0)
+        .build();
+    domain_label.add_css_class("dim-label");
+    content.append(&domain_label);
+
+    // Two-sentence summary on request, when a summary server is set up
+    if let Some(summary) = crate::summarize::create_summary_section(crate::summarize::SummaryRequest::Article {
+        title: title.to_string(),
+        url: url.to_string(),
+    }) {
+        content.append(&summary);
+    }
+
+    let spinner = gtk::Spinner::builder()
+        .spinning(true)
+        .halign(gtk::Align::Center)
+        .margin_top(24)
+        .build();
+    content.append(&spinner);
+
+    let clamp = libadwaita::Clamp::builder()
+        .maximum_size(720)
+        .child(&content)
+        .build();
+    let scrolled = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .vexpand(true)
+        .child(&clamp)
+        .build();
+
+    let open_button = gtk::Button::builder()
+        .icon_name("web-browser-symbolic")
+        .tooltip_text("Open in Browser")
+        .build();
+    let url_for_open = url.to_string();
+    open_button.connect_clicked(move |_| {
+        crate::links::open(&url_for_open);
+    });
+
+    let header_bar = libadwaita::HeaderBar::new();
+    header_bar.pack_end(&open_button);
+
+    let toolbar_view = libadwaita::ToolbarView::new();
+    toolbar_view.add_top_bar(&header_bar);
+    toolbar_view.set_content(Some(&scrolled));
+
+    let window = libadwaita::Window::builder()
+        .title(if domain.is_empty() { "Reader" } else { domain.as_str() })
+        .default_width(760)
+        .default_height(720)
+        .content(&toolbar_view)
+        .build();
+    window.set_transient_for(parent);
+    window.present();
+
+    let url = url.to_string();
+    glib::spawn_future_local(async move {
+        let result = fetch_article_text(&url).await;
+        content.remove(&spinner);
+
+        match result {
+            Ok(paragraphs) => {
+                for paragraph in paragraphs {
+                    let label = Label::builder()
+                        .label(&paragraph)
+                        .xalign(0.0)
+                        .wrap(true)
+                        .wrap_mode(gtk::pango::WrapMode::WordChar)
+                        .selectable(true)
+                        .build();
+                    label.add_css_class("reader-paragraph");
+                    content.append(&label);
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load {} for reading: {}", url, e);
+                let status = libadwaita::StatusPage::builder()
+                    .icon_name("document-open-symbolic")
+                    .title("Can't Show This Article")
+                    .description(format!("{}. Open it in the browser instead.", e))
+                    .build();
+                status.add_css_class("compact");
+                content.append(&status);
+            }
+        }
+    });
+}
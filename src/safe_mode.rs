@@ -0,0 +1,84 @@
+use libadwaita::prelude::*;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once at startup by `--safe-mode` or after a crash
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Written while the app runs and removed on a clean shutdown; still there at
+/// startup means the last session crashed
+fn lock_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("grapevine").join("session.lock"))
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether the firehose, hazard layers and user stylesheet are left off
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Whether the last session ended without a clean shutdown
+pub fn previous_session_crashed() -> bool {
+    lock_path().is_some_and(|path| path.exists())
+}
+
+/// Note that a session is running, until `mark_clean_exit`
+pub fn mark_running() {
+    let Some(path) = lock_path() else {
+        return;
+    };
+
+    if let Some(parent) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            tracing::warn!("Failed to create data directory {}: {}", parent.display(), e);
+            return;
+        }
+    }
+    if let Err(e) = std::fs::write(&path, std::process::id().to_string()) {
+        tracing::warn!("Failed to write session lock {}: {}", path.display(), e);
+    }
+}
+
+pub fn mark_clean_exit() {
+    if let Some(path) = lock_path() {
+        // Missing when the session never got as far as the window
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Ask whether to start in safe mode after a crash, then call `start`
+/// with safe mode set as chosen
+pub fn offer_after_crash(app: &gtk::Application, start: impl Fn() + 'static) {
+    let dialog = libadwaita::MessageDialog::builder()
+        .application(app)
+        .heading("Grapevine Closed Unexpectedly")
+        .body(
+            "Safe mode starts without the firehose, map hazard layers and your custom \
+             stylesheet, so you can fix a setting that stops Grapevine from working. \
+             Restart Grapevine to leave safe mode.",
+        )
+        .build();
+    dialog.add_responses(&[("normal", "Start Normally"), ("safe", "Start in Safe Mode")]);
+    dialog.set_response_appearance("safe", libadwaita::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("safe"));
+    dialog.set_close_response("normal");
+
+    dialog.connect_response(None, move |_, response| {
+        if response == "safe" {
+            enable();
+        }
+        start();
+    });
+    dialog.present();
+}
+
+/// Banner reminding that safe mode is on, shown above the pages
+pub fn create_banner() -> libadwaita::Banner {
+    libadwaita::Banner::builder()
+        .title("Safe mode: the firehose, hazard layers and your stylesheet are off until Grapevine restarts")
+        .revealed(is_enabled())
+        .build()
+}
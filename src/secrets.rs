@@ -0,0 +1,58 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::data::APP_ID;
+use crate::settings::Settings;
+
+/// Keyring entry holding the Bluesky app password
+const BLUESKY_ACCOUNT: &str = "bluesky";
+
+fn attributes(account: &str) -> HashMap<&'static str, String> {
+    HashMap::from([("application", APP_ID.to_string()), ("account", account.to_string())])
+}
+
+async fn lookup(account: &str) -> Result<Option<String>, oo7::Error> {
+    let keyring = oo7::Keyring::new().await?;
+    keyring.unlock().await?;
+    let Some(item) = keyring.search_items(&attributes(account)).await?.into_iter().next() else {
+        return Ok(None);
+    };
+    let secret = item.secret().await?;
+    Ok(Some(String::from_utf8_lossy(&secret).into_owned()))
+}
+
+/// Save a secret in the desktop keyring, or remove it when empty
+async fn store(account: &str, label: &str, secret: &str) -> Result<(), oo7::Error> {
+    let keyring = oo7::Keyring::new().await?;
+    if secret.is_empty() {
+        return keyring.delete(&attributes(account)).await;
+    }
+    keyring.unlock().await?;
+    keyring.create_item(label, &attributes(account), secret, true).await
+}
+
+/// Save the Bluesky app password in the desktop keyring
+pub async fn store_bluesky_app_password(password: &str) -> Result<(), oo7::Error> {
+    store(BLUESKY_ACCOUNT, "Grapevine Bluesky app password", password).await
+}
+
+/// Read the Bluesky app password from the keyring into the settings. A password
+/// left in settings.toml by an older version is moved to the keyring instead.
+pub async fn load_bluesky_app_password(settings: Rc<RefCell<Settings>>) {
+    let legacy = settings.borrow().bluesky_app_password.clone();
+    if !legacy.is_empty() {
+        match store_bluesky_app_password(&legacy).await {
+            // Saving again writes settings.toml without the password
+            Ok(()) => settings.borrow().save(),
+            Err(e) => tracing::warn!("Failed to move the app password to the keyring: {}", e),
+        }
+        return;
+    }
+
+    match lookup(BLUESKY_ACCOUNT).await {
+        Ok(Some(password)) => settings.borrow_mut().bluesky_app_password = password,
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to read the app password from the keyring: {}", e),
+    }
+}
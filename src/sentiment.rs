@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Coarse sentiment class derived from a compound score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sentiment {
+    Positive,
+    Neutral,
+    Negative,
+}
+
+impl Sentiment {
+    /// Classify a compound score using VADER's conventional ±0.05 thresholds
+    pub fn from_score(score: f32) -> Self {
+        if score >= 0.05 {
+            Sentiment::Positive
+        } else if score <= -0.05 {
+            Sentiment::Negative
+        } else {
+            Sentiment::Neutral
+        }
+    }
+}
+
+/// Word valences on VADER's -4..4 scale (a small subset of its lexicon)
+const LEXICON: &[(&str, f32)] = &[
+    // Positive
+    ("good", 1.9), ("great", 3.1), ("excellent", 2.7), ("amazing", 2.8), ("awesome", 3.1),
+    ("love", 3.2), ("loved", 2.9), ("loving", 2.9), ("lovely", 2.8), ("like", 1.5),
+    ("happy", 2.7), ("glad", 2.0), ("joy", 2.8), ("fun", 2.3), ("nice", 1.8),
+    ("beautiful", 2.9), ("best", 3.2), ("better", 1.9), ("win", 2.8), ("won", 2.7),
+    ("winning", 2.4), ("success", 2.7), ("successful", 2.8), ("hope", 1.9), ("hopeful", 2.3),
+    ("thanks", 1.9), ("thank", 1.5), ("grateful", 2.0), ("congrats", 2.4), ("congratulations", 2.9),
+    ("excited", 1.4), ("exciting", 2.2), ("proud", 2.1), ("wonderful", 2.7), ("fantastic", 2.6),
+    ("brilliant", 2.8), ("cool", 1.3), ("safe", 1.9), ("peace", 2.5), ("support", 1.7),
+    ("helpful", 1.8), ("kind", 2.4), ("free", 2.3), ("strong", 2.3), ("celebrate", 2.7),
+    ("perfect", 2.7), ("enjoy", 2.2), ("enjoyed", 2.3), ("favorite", 2.0), ("yay", 2.4),
+    ("lol", 1.8), ("haha", 2.0), ("cute", 2.0), ("sweet", 2.0), ("agree", 1.5),
+    ("recovery", 1.4), ("rescued", 1.5), ("saved", 1.7), ("victory", 2.9), ("improve", 1.9),
+    // Negative
+    ("bad", -2.5), ("terrible", -2.1), ("awful", -2.0), ("horrible", -2.5), ("worst", -3.1),
+    ("hate", -2.7), ("hated", -3.2), ("angry", -2.3), ("sad", -2.1), ("cry", -2.1),
+    ("crying", -2.1), ("upset", -1.6), ("fear", -2.2), ("afraid", -2.0), ("scared", -1.9),
+    ("war", -2.9), ("attack", -2.1), ("attacks", -1.6), ("killed", -3.5), ("kill", -3.7),
+    ("death", -2.9), ("dead", -3.3), ("die", -2.9), ("died", -2.6), ("dying", -2.9),
+    ("crisis", -3.1), ("disaster", -3.1), ("tragedy", -3.4), ("tragic", -3.4), ("violence", -3.1),
+    ("crash", -1.7), ("fail", -2.5), ("failed", -2.3), ("failure", -2.3), ("lose", -1.6),
+    ("lost", -1.3), ("loss", -1.3), ("hurt", -2.4), ("pain", -2.3), ("sick", -2.3),
+    ("wrong", -2.1), ("problem", -1.7), ("danger", -2.4), ("dangerous", -2.1), ("threat", -2.4),
+    ("corrupt", -3.0), ("corruption", -2.8), ("lie", -1.6), ("lies", -1.8), ("liar", -3.1),
+    ("stupid", -2.4), ("disgusting", -2.4), ("annoying", -1.7), ("broken", -2.1), ("worse", -2.1),
+    ("sorry", -0.3), ("unfortunately", -1.5), ("shame", -2.1), ("scandal", -1.9), ("protest", -1.0),
+    ("outrage", -2.3), ("chaos", -2.7), ("collapse", -2.2), ("flood", -1.6), ("earthquake", -2.1),
+    ("injured", -1.7), ("victims", -2.6), ("shooting", -1.4), ("murder", -3.7), ("terror", -3.3),
+    ("ugh", -1.8), ("wtf", -2.8), ("damn", -1.7), ("evil", -3.4), ("hell", -3.6),
+];
+
+/// Words that flip the valence of the following sentiment word
+const NEGATIONS: &[&str] = &[
+    "not", "no", "never", "none", "nobody", "nothing", "neither", "nor", "without",
+    "isn't", "aren't", "wasn't", "weren't", "don't", "doesn't", "didn't", "won't",
+    "can't", "cannot", "couldn't", "shouldn't", "wouldn't", "ain't", "hardly",
+];
+
+/// Words that amplify the following sentiment word
+const BOOSTERS: &[&str] = &[
+    "very", "really", "so", "extremely", "incredibly", "totally", "absolutely",
+    "super", "truly", "completely", "most", "highly", "deeply",
+];
+
+const NEGATION_SCALAR: f32 = -0.74;
+const BOOSTER_INCREMENT: f32 = 0.293;
+const CAPS_INCREMENT: f32 = 0.733;
+const EXCLAMATION_INCREMENT: f32 = 0.292;
+
+fn lexicon() -> &'static HashMap<&'static str, f32> {
+    static LEXICON_MAP: OnceLock<HashMap<&'static str, f32>> = OnceLock::new();
+    LEXICON_MAP.get_or_init(|| LEXICON.iter().copied().collect())
+}
+
+/// Compute a VADER-style compound sentiment score in -1.0..=1.0
+pub fn score(text: &str) -> f32 {
+    let lexicon = lexicon();
+
+    let tokens: Vec<&str> = text
+        .split_whitespace()
+        .map(|token| token.trim_matches(|c: char| !c.is_alphanumeric() && c != '\''))
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    // ALL CAPS emphasis only counts when the post isn't shouting throughout
+    let caps_count = tokens.iter().filter(|t| is_all_caps(t)).count();
+    let caps_differential = caps_count > 0 && caps_count < tokens.len();
+
+    let mut sum = 0.0;
+    for (i, token) in tokens.iter().enumerate() {
+        let lower = token.to_lowercase();
+        let Some(&valence) = lexicon.get(lower.as_str()) else {
+            continue;
+        };
+
+        let mut valence = valence;
+
+        if caps_differential && is_all_caps(token) {
+            valence += CAPS_INCREMENT * valence.signum();
+        }
+
+        // Look back up to three words for boosters and negations
+        for distance in 1..=3 {
+            if i < distance {
+                break;
+            }
+            let previous = tokens[i - distance].to_lowercase();
+
+            if BOOSTERS.contains(&previous.as_str()) {
+                // Boosters further away have less effect
+                let damping = 1.0 - 0.05 * (distance - 1) as f32;
+                valence += BOOSTER_INCREMENT * damping * valence.signum();
+            }
+
+            if NEGATIONS.contains(&previous.as_str()) || previous.ends_with("n't") {
+                valence *= NEGATION_SCALAR;
+            }
+        }
+
+        sum += valence;
+    }
+
+    if sum == 0.0 {
+        return 0.0;
+    }
+
+    // Exclamation marks intensify the overall tone (capped at four)
+    let exclamations = text.matches('!').count().min(4) as f32;
+    sum += exclamations * EXCLAMATION_INCREMENT * sum.signum();
+
+    normalize(sum)
+}
+
+fn is_all_caps(token: &str) -> bool {
+    token.chars().any(|c| c.is_alphabetic())
+        && token.chars().filter(|c| c.is_alphabetic()).all(|c| c.is_uppercase())
+        && token.chars().count() > 1
+}
+
+/// Map an unbounded sum onto -1..1 the same way VADER does (alpha = 15)
+fn normalize(sum: f32) -> f32 {
+    let normalized = sum / (sum * sum + 15.0).sqrt();
+    normalized.clamp(-1.0, 1.0)
+}
@@ -0,0 +1,471 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::sentiment::Sentiment;
+use crate::tiles::MapTiles;
+use crate::translate::TranslationProvider;
+
+/// Light or dark style for the app and its map tiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    /// Follow the desktop's preference
+    System,
+    Light,
+    Dark,
+}
+
+/// Spacing of cards, badges and rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Density {
+    Compact,
+    Comfortable,
+}
+
+/// Size of article card images in Global Affairs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThumbnailSize {
+    Off,
+    Small,
+    Large,
+}
+
+/// How article images that don't match the card's shape are fitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThumbnailFit {
+    /// Fill the card, cropping the edges
+    Crop,
+    /// Show the whole image with bars at the sides
+    Letterbox,
+}
+
+/// Accent a firehose split's column is marked with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SplitColor {
+    #[default]
+    None,
+    Blue,
+    Green,
+    Yellow,
+    Orange,
+    Red,
+    Purple,
+}
+
+impl SplitColor {
+    pub const ALL: [SplitColor; 7] = [
+        SplitColor::None,
+        SplitColor::Blue,
+        SplitColor::Green,
+        SplitColor::Yellow,
+        SplitColor::Orange,
+        SplitColor::Red,
+        SplitColor::Purple,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SplitColor::None => "No color",
+            SplitColor::Blue => "Blue",
+            SplitColor::Green => "Green",
+            SplitColor::Yellow => "Yellow",
+            SplitColor::Orange => "Orange",
+            SplitColor::Red => "Red",
+            SplitColor::Purple => "Purple",
+        }
+    }
+
+    /// Style class for the column and its swatch, e.g. "split-blue"
+    pub fn css_class(&self) -> Option<&'static str> {
+        match self {
+            SplitColor::None => None,
+            SplitColor::Blue => Some("split-blue"),
+            SplitColor::Green => Some("split-green"),
+            SplitColor::Yellow => Some("split-yellow"),
+            SplitColor::Orange => Some("split-orange"),
+            SplitColor::Red => Some("split-red"),
+            SplitColor::Purple => Some("split-purple"),
+        }
+    }
+}
+
+/// Whether a split narrows the stream to image posts by their alt text, for
+/// auditing how much of what's posted is described
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AltTextFilter {
+    #[default]
+    Any,
+    /// Posts with at least one image lacking alt text
+    Missing,
+    /// Posts whose images all have alt text
+    Described,
+}
+
+impl AltTextFilter {
+    pub const ALL: [AltTextFilter; 3] = [AltTextFilter::Any, AltTextFilter::Missing, AltTextFilter::Described];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AltTextFilter::Any => "Any posts",
+            AltTextFilter::Missing => "Images missing alt text",
+            AltTextFilter::Described => "Images with alt text",
+        }
+    }
+}
+
+/// Which kind of Jetstream record a split shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordType {
+    #[default]
+    Posts,
+    Likes,
+    Reposts,
+    Follows,
+}
+
+impl RecordType {
+    pub const ALL: [RecordType; 4] = [RecordType::Posts, RecordType::Likes, RecordType::Reposts, RecordType::Follows];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecordType::Posts => "Posts",
+            RecordType::Likes => "Likes",
+            RecordType::Reposts => "Reposts",
+            RecordType::Follows => "Follows",
+        }
+    }
+
+    /// The Jetstream collection the records come from
+    pub fn collection(&self) -> &'static str {
+        match self {
+            RecordType::Posts => "app.bsky.feed.post",
+            RecordType::Likes => "app.bsky.feed.like",
+            RecordType::Reposts => "app.bsky.feed.repost",
+            RecordType::Follows => "app.bsky.graph.follow",
+        }
+    }
+}
+
+/// A firehose split's rules and appearance, restored at startup and shared as JSON
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedSplit {
+    pub keyword: String,
+    /// Column title; empty to show the keyword
+    pub title: String,
+    pub color: SplitColor,
+    pub sentiment: Option<Sentiment>,
+    /// Translate foreign-language matches
+    pub translate: bool,
+    /// Case-insensitive pattern posts must match; empty for none
+    pub regex: String,
+    /// Declared post languages (ISO 639-1), any of which may match
+    pub languages: Vec<String>,
+    /// Author DIDs, any of which may match
+    pub authors: Vec<String>,
+    /// Posts containing any of these are left out
+    pub mute_terms: Vec<String>,
+    /// Domains, any of which the post must link to; subdomains count
+    pub link_domains: Vec<String>,
+    /// Only image posts with, or without, alt text
+    pub alt_text: AltTextFilter,
+    /// Likes, reposts or follows instead of posts
+    pub record_type: RecordType,
+}
+
+/// A currency pair pinned to the Markets page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchedPair {
+    /// Currency priced, e.g. "EUR" in EUR/USD
+    pub base: String,
+    /// Currency the rate is quoted in
+    pub target: String,
+    /// Notify once the rate reaches this or higher
+    pub alert_above: Option<f64>,
+    /// Notify once the rate falls to this or lower
+    pub alert_below: Option<f64>,
+}
+
+impl WatchedPair {
+    pub fn new(base: &str, target: &str) -> Self {
+        Self {
+            base: base.to_string(),
+            target: target.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// e.g. "EUR/USD"
+    pub fn name(&self) -> String {
+        format!("{}/{}", self.base, self.target)
+    }
+}
+
+/// User preferences persisted to ~/.config/grapevine/settings.toml
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Show the statusline clock in 24-hour format
+    pub use_24_hour: bool,
+    /// IANA timezones pinned next to the statusline clock, e.g. "Asia/Tokyo"
+    pub world_clocks: Vec<String>,
+    /// Show one pinned timezone at a time, in turn, rather than all side by side
+    pub rotate_world_clocks: bool,
+    /// Minutes between automatic Global Affairs refreshes
+    pub refresh_interval_minutes: u32,
+    /// Show a summary of the session's activity before quitting
+    pub show_session_summary: bool,
+    /// Keep streaming with a tray icon when the window is closed
+    pub run_in_background: bool,
+    /// Light, dark, or whichever the desktop prefers
+    pub theme: Theme,
+    /// Multiplier for all text sizes (1.0 = 100%)
+    pub font_scale: f64,
+    /// Spacing of cards, badges and rows
+    pub density: Density,
+    /// Tiles the Global Affairs map is drawn with
+    pub map_tiles: MapTiles,
+    /// URL template of a custom tile server, with {z}, {x} and {y} placeholders
+    pub tile_url_template: String,
+    /// Header the custom tile server's API key is sent in
+    pub tile_api_key_header: String,
+    /// API key for the custom tile server; empty to send none
+    pub tile_api_key: String,
+    /// Size of article card images, or Off to skip loading them
+    pub thumbnail_size: ThumbnailSize,
+    /// Crop article images to the card or letterbox them
+    pub thumbnail_fit: ThumbnailFit,
+    /// Backend used to translate foreign-language posts
+    pub translation_provider: TranslationProvider,
+    /// ISO 639-1 code posts are translated into
+    pub translation_target: String,
+    /// Base URL of the translation server
+    pub translation_server_url: String,
+    /// API key for LibreTranslate servers that require one
+    pub translation_api_key: String,
+    /// Base URL of an OpenAI-compatible API for summaries, e.g.
+    /// http://localhost:8080/v1 for llama.cpp's server; empty turns them off
+    pub summary_server_url: String,
+    /// Model named in summary requests; local servers ignore it
+    pub summary_model: String,
+    /// Bearer token for hosted summary APIs
+    pub summary_api_key: String,
+    /// Bluesky handle or email used to post
+    pub bluesky_identifier: String,
+    /// App password for posting (Settings → Privacy and security → App passwords).
+    /// Kept in the desktop keyring; only read here to move older plaintext files over
+    #[serde(skip_serializing)]
+    pub bluesky_app_password: String,
+    /// Server hosting the account
+    pub bluesky_pds_url: String,
+    /// Hold posts back until every attached image has alt text
+    pub require_alt_text: bool,
+    /// Ollama-compatible server used to suggest alt text; empty to turn suggestions off
+    pub caption_server_url: String,
+    /// Vision model the captioning server runs, e.g. llava
+    pub caption_model: String,
+    /// Most firehose posts rendered per second; 0 for no limit
+    pub max_rows_per_second: u32,
+    /// Percent of firehose posts rendered, spread evenly over the stream
+    pub sample_percent: u32,
+    /// How readily likely spam is hidden from every pane, 0 (off) to 100
+    pub spam_filter_strictness: u32,
+    /// Firehose splits, in deck order
+    pub splits: Vec<SavedSplit>,
+    /// Hashtags followed in their own firehose columns, without the '#'
+    pub hashtag_feeds: Vec<String>,
+    /// The starter splits were offered on a first visit to the firehose
+    pub firehose_onboarding_done: bool,
+    /// Seconds each post stays on screen in slow mode
+    pub slow_mode_interval_seconds: u32,
+    /// GDELT queries saved for weekly digests
+    pub saved_searches: Vec<String>,
+    /// Global Affairs articles listed per outlet before the rest fold away; 0 lists them all
+    pub max_articles_per_domain: u32,
+    /// Outlets never listed in Global Affairs, e.g. content farms
+    pub blocked_domains: Vec<String>,
+    /// When not empty, only these outlets are listed in Global Affairs
+    pub allowed_domains: Vec<String>,
+    /// Hide outlets an imported rating list scores below `min_source_score`
+    pub hide_low_rated_sources: bool,
+    /// Lowest rating shown when hiding low-rated outlets, on the lists' own scale
+    pub min_source_score: f64,
+    /// Serve saved searches as Atom feeds on 127.0.0.1
+    pub feed_server_enabled: bool,
+    /// Local port of the feed server
+    pub feed_server_port: u16,
+    /// Pages kiosk mode cycles through, by name
+    pub kiosk_pages: Vec<String>,
+    /// Seconds each page stays up in kiosk mode
+    pub kiosk_interval_seconds: u32,
+    /// Watch style.css and layout.toml in the config directory and apply edits live
+    pub developer_mode: bool,
+    /// Currency pairs on the Markets page, in display order
+    pub currency_watchlist: Vec<WatchedPair>,
+    /// Proxy for all web requests (http:// or https://); empty to connect directly
+    pub http_proxy: String,
+    /// Main window size when last closed, unmaximized
+    pub window_width: i32,
+    pub window_height: i32,
+    pub window_maximized: bool,
+    /// Page the main window showed when last closed, by name
+    pub last_page: String,
+    /// Global Affairs search when last closed; empty for the latest news
+    pub last_query: String,
+    /// Height of the Global Affairs article list above the map in tall windows
+    pub global_affairs_list_height: i32,
+    /// Width of the map beside the article list in wide windows
+    pub global_affairs_map_width: i32,
+    /// Search coverage in every language GDELT translates, not only English sources
+    pub multilingual_search: bool,
+    /// Translate foreign-language article titles as they are listed
+    pub translate_article_titles: bool,
+    /// Leave articles already opened out of the Global Affairs list
+    pub hide_read_articles: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            use_24_hour: false,
+            world_clocks: Vec::new(),
+            rotate_world_clocks: false,
+            refresh_interval_minutes: 15,
+            show_session_summary: false,
+            run_in_background: false,
+            theme: Theme::Dark,
+            font_scale: 1.0,
+            density: Density::Comfortable,
+            map_tiles: MapTiles::Carto,
+            tile_url_template: String::new(),
+            tile_api_key_header: "Authorization".to_string(),
+            tile_api_key: String::new(),
+            thumbnail_size: ThumbnailSize::Large,
+            thumbnail_fit: ThumbnailFit::Crop,
+            translation_provider: TranslationProvider::Lingva,
+            translation_target: "en".to_string(),
+            translation_server_url: "https://lingva.ml".to_string(),
+            translation_api_key: String::new(),
+            summary_server_url: String::new(),
+            summary_model: String::new(),
+            summary_api_key: String::new(),
+            bluesky_identifier: String::new(),
+            bluesky_app_password: String::new(),
+            bluesky_pds_url: "https://bsky.social".to_string(),
+            require_alt_text: false,
+            caption_server_url: String::new(),
+            caption_model: "llava".to_string(),
+            max_rows_per_second: 0,
+            sample_percent: 100,
+            spam_filter_strictness: 0,
+            splits: Vec::new(),
+            hashtag_feeds: Vec::new(),
+            firehose_onboarding_done: false,
+            slow_mode_interval_seconds: 8,
+            saved_searches: Vec::new(),
+            max_articles_per_domain: 3,
+            blocked_domains: Vec::new(),
+            allowed_domains: Vec::new(),
+            hide_low_rated_sources: false,
+            min_source_score: 50.0,
+            feed_server_enabled: false,
+            feed_server_port: 8737,
+            kiosk_pages: vec!["global-affairs".to_string(), "firehose".to_string()],
+            kiosk_interval_seconds: 60,
+            developer_mode: false,
+            currency_watchlist: vec![
+                WatchedPair::new("EUR", "USD"),
+                WatchedPair::new("USD", "JPY"),
+                WatchedPair::new("GBP", "USD"),
+            ],
+            http_proxy: String::new(),
+            window_width: 800,
+            window_height: 600,
+            window_maximized: false,
+            last_page: String::new(),
+            last_query: String::new(),
+            global_affairs_list_height: 200,
+            global_affairs_map_width: 500,
+            multilingual_search: false,
+            translate_article_titles: false,
+            hide_read_articles: false,
+        }
+    }
+}
+
+/// Directory holding Grapevine's config files
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("grapevine"))
+}
+
+/// Write a file only the user can read; settings hold API keys
+pub fn write_private(path: &Path, text: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // The mode only applies to new files, so tighten ones written by older versions
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    file.write_all(text.as_bytes())
+}
+
+fn settings_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("settings.toml"))
+}
+
+impl Settings {
+    /// Load settings from disk, falling back to defaults if missing or invalid
+    pub fn load() -> Self {
+        let Some(path) = settings_path() else {
+            return Self::default();
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(text) => match toml::from_str(&text) {
+                Ok(settings) => settings,
+                Err(e) => {
+                    tracing::warn!("Failed to parse settings at {}: {}", path.display(), e);
+                    Self::default()
+                }
+            },
+            // No settings file yet
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Write settings to disk
+    pub fn save(&self) {
+        let Some(path) = settings_path() else {
+            tracing::warn!("Could not determine config directory, settings not saved");
+            return;
+        };
+
+        if let Some(parent) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                tracing::warn!("Failed to create config directory {}: {}", parent.display(), e);
+                return;
+            }
+        }
+
+        match toml::to_string_pretty(self) {
+            Ok(text) => {
+                if let Err(e) = write_private(&path, &text) {
+                    tracing::warn!("Failed to write settings to {}: {}", path.display(), e);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to serialize settings: {}", e),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use gtk::prelude::*;
+use gtk::{gdk, gio, glib};
+
+use crate::reader;
+use crate::toasts;
+
+/// Give an article card or row a context menu, opened by right-click or a long
+/// press, to copy or share the article or read it in the app
+pub fn attach_article_menu(widget: &impl IsA<gtk::Widget>, title: &str, url: &str) {
+    let widget = widget.as_ref();
+    // Copied and shared links go out without the trackers they came with
+    let url = crate::links::canonicalize(url);
+    let url = url.as_str();
+
+    let actions = gio::SimpleActionGroup::new();
+    let add_action = |name: &str, activate: Box<dyn Fn(&gtk::Widget)>| {
+        let action = gio::SimpleAction::new(name, None);
+        let widget = widget.downgrade();
+        action.connect_activate(move |_, _| {
+            if let Some(widget) = widget.upgrade() {
+                activate(&widget);
+            }
+        });
+        actions.add_action(&action);
+    };
+
+    let url_for_copy = url.to_string();
+    add_action("copy-link", Box::new(move |widget| {
+        widget.clipboard().set_text(&url_for_copy);
+        toasts::show("Link copied");
+    }));
+
+    let text = format!("{}\n{}", title, url);
+    add_action("copy-title-link", Box::new(move |widget| {
+        widget.clipboard().set_text(&text);
+        toasts::show("Title and link copied");
+    }));
+
+    let (title_for_share, url_for_share) = (title.to_string(), url.to_string());
+    add_action("share", Box::new(move |_| {
+        share_via_portal(&title_for_share, &url_for_share);
+    }));
+
+    let (title_for_reader, url_for_reader) = (title.to_string(), url.to_string());
+    add_action("open-reader", Box::new(move |widget| {
+        let window = widget.root().and_downcast::<gtk::Window>();
+        reader::present_reader(window.as_ref(), &title_for_reader, &url_for_reader);
+    }));
+
+    let url_for_browser = url.to_string();
+    add_action("open-browser", Box::new(move |_| {
+        crate::links::open(&url_for_browser);
+    }));
+
+    widget.insert_action_group("article", Some(&actions));
+
+    let menu = gio::Menu::new();
+    let copy_section = gio::Menu::new();
+    copy_section.append(Some("Copy Link"), Some("article.copy-link"));
+    copy_section.append(Some("Copy Title and Link"), Some("article.copy-title-link"));
+    menu.append_section(None, &copy_section);
+    let open_section = gio::Menu::new();
+    open_section.append(Some("Share…"), Some("article.share"));
+    open_section.append(Some("Open in Reader"), Some("article.open-reader"));
+    open_section.append(Some("Open in Browser"), Some("article.open-browser"));
+    menu.append_section(None, &open_section);
+
+    let popover = gtk::PopoverMenu::builder()
+        .menu_model(&menu)
+        .has_arrow(false)
+        .halign(gtk::Align::Start)
+        .build();
+    popover.set_parent(widget);
+
+    let right_click = gtk::GestureClick::builder()
+        .button(gdk::BUTTON_SECONDARY)
+        .build();
+    let popover_for_click = popover.clone();
+    right_click.connect_pressed(move |gesture, _, x, y| {
+        gesture.set_state(gtk::EventSequenceState::Claimed);
+        popup_at(&popover_for_click, x, y);
+    });
+    widget.add_controller(right_click);
+
+    // Claiming the long press keeps the click handler from also opening the article
+    let long_press = gtk::GestureLongPress::builder()
+        .touch_only(true)
+        .build();
+    long_press.connect_pressed(move |gesture, x, y| {
+        gesture.set_state(gtk::EventSequenceState::Claimed);
+        popup_at(&popover, x, y);
+    });
+    widget.add_controller(long_press);
+}
+
+fn popup_at(popover: &gtk::PopoverMenu, x: f64, y: f64) {
+    popover.set_pointing_to(Some(&gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+    popover.popup();
+}
+
+/// Hand the article to the desktop's email composer through the portal,
+/// falling back to a mailto: link outside portal-enabled sessions
+fn share_via_portal(title: &str, url: &str) {
+    let (title, url) = (title.to_string(), url.to_string());
+    glib::spawn_future_local(async move {
+        let options = glib::VariantDict::new(None);
+        options.insert("subject", &title);
+        options.insert("body", format!("{}\n\n{}", title, url));
+        let parameters = glib::Variant::tuple_from_iter(["".to_variant(), options.end()]);
+
+        let result = match gio::bus_get_future(gio::BusType::Session).await {
+            Ok(connection) => connection
+                .call_future(
+                    Some("org.freedesktop.portal.Desktop"),
+                    "/org/freedesktop/portal/desktop",
+                    "org.freedesktop.portal.Email",
+                    "ComposeEmail",
+                    Some(&parameters),
+                    None,
+                    gio::DBusCallFlags::NONE,
+                    -1,
+                )
+                .await
+                .map(|_| ()),
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = result {
+            tracing::warn!("Share portal unavailable, opening a mailto: link: {}", e);
+            let mailto = format!(
+                "mailto:?subject={}&body={}",
+                urlencoding::encode(&title),
+                urlencoding::encode(&url),
+            );
+            if let Err(e) = open::that(&mailto) {
+                tracing::warn!("Failed to share article: {}", e);
+                toasts::show("Couldn't open a way to share this article");
+            }
+        }
+    });
+}
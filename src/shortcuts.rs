@@ -0,0 +1,101 @@
+use gtk::prelude::*;
+use gtk::Application;
+
+/// An application action with its keyboard accelerators
+pub struct Shortcut {
+    pub action: &'static str,
+    pub accels: &'static [&'static str],
+    pub title: &'static str,
+    pub group: &'static str,
+}
+
+/// All keyboard shortcuts, used both to register accelerators and to build the shortcuts window
+pub const SHORTCUTS: &[Shortcut] = &[
+    Shortcut { action: "app.shortcuts", accels: &["<Primary>question"], title: "Show keyboard shortcuts", group: "General" },
+    Shortcut { action: "app.preferences", accels: &["<Primary>comma"], title: "Preferences", group: "General" },
+    Shortcut { action: "app.compose", accels: &["<Primary>n"], title: "New post", group: "General" },
+    Shortcut { action: "app.export", accels: &["<Primary>e"], title: "Export articles", group: "General" },
+    Shortcut { action: "app.toggle-clock-format", accels: &["<Primary><Shift>c"], title: "Toggle 12/24 hour clock", group: "General" },
+    Shortcut { action: "app.kiosk", accels: &["F11"], title: "Enter or leave kiosk mode", group: "General" },
+    Shortcut { action: "app.focus-mode", accels: &["<Primary><Shift>f"], title: "Enter or leave focus mode", group: "General" },
+    Shortcut { action: "app.quit", accels: &["<Primary>q"], title: "Quit", group: "General" },
+    Shortcut { action: "app.switch-view::global-affairs", accels: &["<Primary>1"], title: "Go to Global Affairs", group: "Navigation" },
+    Shortcut { action: "app.switch-view::firehose", accels: &["<Primary>2"], title: "Go to Firehose", group: "Navigation" },
+    Shortcut { action: "app.switch-view::bookmarks", accels: &["<Primary>3"], title: "Go to Bookmarks", group: "Navigation" },
+    Shortcut { action: "app.switch-view::notifications", accels: &["<Primary>4"], title: "Go to Notifications", group: "Navigation" },
+    Shortcut { action: "app.switch-view::markets", accels: &["<Primary>5"], title: "Go to Markets", group: "Navigation" },
+    Shortcut { action: "app.focus-search", accels: &["<Primary>f"], title: "Search / filter", group: "Navigation" },
+    Shortcut { action: "app.refresh", accels: &["<Primary>r", "F5"], title: "Refresh articles", group: "Global Affairs" },
+    Shortcut { action: "app.add-split", accels: &["<Primary>t"], title: "Add filtered split", group: "Firehose" },
+    Shortcut { action: "app.close-split", accels: &["<Primary>w"], title: "Close last split", group: "Firehose" },
+    Shortcut { action: "app.slow-mode", accels: &["<Primary><Shift>s"], title: "Slow mode reader", group: "Firehose" },
+];
+
+/// Register the accelerators for every shortcut on the application
+pub fn register_accels(app: &Application) {
+    for shortcut in SHORTCUTS {
+        app.set_accels_for_action(shortcut.action, shortcut.accels);
+    }
+}
+
+/// Build a ShortcutsWindow listing every shortcut, grouped by section
+pub fn create_shortcuts_window(parent: &impl IsA<gtk::Window>) -> gtk::ShortcutsWindow {
+    // Collect groups in the order they first appear
+    let mut groups: Vec<&str> = Vec::new();
+    for shortcut in SHORTCUTS {
+        if !groups.contains(&shortcut.group) {
+            groups.push(shortcut.group);
+        }
+    }
+
+    let mut groups_xml = String::new();
+    for group in groups {
+        let mut shortcuts_xml = String::new();
+        for shortcut in SHORTCUTS.iter().filter(|s| s.group == group) {
+            shortcuts_xml.push_str(&format!(
+                "<child><object class=\"GtkShortcutsShortcut\">\
+                 <property name=\"title\">{}</property>\
+                 <property name=\"accelerator\">{}</property>\
+                 </object></child>",
+                xml_escape(shortcut.title),
+                xml_escape(&shortcut.accels.join(" ")),
+            ));
+        }
+
+        groups_xml.push_str(&format!(
+            "<child><object class=\"GtkShortcutsGroup\">\
+             <property name=\"title\">{}</property>{}\
+             </object></child>",
+            xml_escape(group),
+            shortcuts_xml,
+        ));
+    }
+
+    let ui = format!(
+        "<interface>\
+         <object class=\"GtkShortcutsWindow\" id=\"shortcuts_window\">\
+         <property name=\"modal\">1</property>\
+         <child><object class=\"GtkShortcutsSection\">\
+         <property name=\"section-name\">shortcuts</property>\
+         <property name=\"max-height\">12</property>{}\
+         </object></child>\
+         </object>\
+         </interface>",
+        groups_xml
+    );
+
+    let builder = gtk::Builder::from_string(&ui);
+    let window: gtk::ShortcutsWindow = builder
+        .object("shortcuts_window")
+        .expect("shortcuts window should be defined in the UI description");
+    window.set_transient_for(Some(parent));
+
+    window
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
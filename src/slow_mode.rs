@@ -0,0 +1,276 @@
+use gtk::prelude::*;
+use gtk::{glib, Label, Orientation};
+use libadwaita::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::data::FirehosePost;
+use crate::settings::Settings;
+
+/// Crossfade between posts in milliseconds
+const TRANSITION_MS: u32 = 600;
+
+/// Uniform random sample of the posts that arrived since the last one was shown
+struct Reservoir {
+    /// Lowercased keyword posts must contain; empty for the whole stream
+    keyword: String,
+    seen: i32,
+    sample: Option<FirehosePost>,
+}
+
+thread_local! {
+    /// Present while the slow mode window is open
+    static RESERVOIR: RefCell<Option<Reservoir>> = const { RefCell::new(None) };
+    static WINDOW: RefCell<Option<glib::WeakRef<libadwaita::Window>>> = const { RefCell::new(None) };
+}
+
+/// Offer an incoming post to the slow mode reader; cheap when it is closed
+pub fn offer(post: &FirehosePost) {
+    RESERVOIR.with(|reservoir| {
+        let mut reservoir = reservoir.borrow_mut();
+        let Some(reservoir) = reservoir.as_mut() else {
+            return;
+        };
+
+        if !reservoir.keyword.is_empty() && !post.text.to_lowercase().contains(&reservoir.keyword) {
+            return;
+        }
+
+        // Reservoir sampling: the n-th candidate replaces the sample with probability 1/n
+        reservoir.seen = reservoir.seen.saturating_add(1);
+        if glib::random_int_range(0, reservoir.seen) == 0 {
+            reservoir.sample = Some(post.clone());
+        }
+    });
+}
+
+fn take_sample() -> Option<FirehosePost> {
+    RESERVOIR.with(|reservoir| {
+        reservoir.borrow_mut().as_mut().and_then(|reservoir| {
+            reservoir.seen = 0;
+            reservoir.sample.take()
+        })
+    })
+}
+
+fn set_keyword(keyword: &str) {
+    RESERVOIR.with(|reservoir| {
+        *reservoir.borrow_mut() = Some(Reservoir {
+            keyword: keyword.trim().to_lowercase(),
+            seen: 0,
+            sample: None,
+        });
+    });
+}
+
+/// Card for a single post, shown large and centered
+fn create_post_card(post: &FirehosePost) -> gtk::Box {
+    let card = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(16)
+        .valign(gtk::Align::Center)
+        .margin_start(48)
+        .margin_end(48)
+        .margin_top(24)
+        .margin_bottom(24)
+        .build();
+
+    let text_label = Label::builder()
+        .label(post.text.as_str())
+        .wrap(true)
+        .wrap_mode(gtk::pango::WrapMode::WordChar)
+        .justify(gtk::Justification::Center)
+        .max_width_chars(50)
+        .selectable(true)
+        .build();
+    text_label.add_css_class("slow-mode-text");
+    card.append(&text_label);
+
+    let meta_box = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .halign(gtk::Align::Center)
+        .build();
+
+    let timestamp_label = Label::builder()
+        .label(post.timestamp.as_str())
+        .build();
+    timestamp_label.add_css_class("caption");
+    timestamp_label.add_css_class("dim-label");
+    meta_box.append(&timestamp_label);
+    meta_box.append(&crate::firehose::create_identifier_button(&post.did, &post.rkey));
+
+    let post_url = format!("https://bsky.app/profile/{}/post/{}", post.did, post.rkey);
+    let open_button = gtk::LinkButton::builder()
+        .uri(post_url.as_str())
+        .label("Open on Bluesky")
+        .build();
+    meta_box.append(&open_button);
+
+    card.append(&meta_box);
+    card
+}
+
+fn create_waiting_page() -> libadwaita::StatusPage {
+    libadwaita::StatusPage::builder()
+        .icon_name("media-playlist-shuffle-symbolic")
+        .title("Waiting for Posts")
+        .description("A random post from the stream appears here every few seconds")
+        .build()
+}
+
+/// Show the slow mode reader, drawing random posts from the stream or from
+/// posts matching one of `keywords` (the current split filters)
+pub fn present_slow_mode_window(
+    parent: &impl IsA<gtk::Window>,
+    settings: Rc<RefCell<Settings>>,
+    keywords: Vec<String>,
+) {
+    // Only one reader at a time
+    if let Some(window) = WINDOW.with(|window| window.borrow().as_ref().and_then(|weak| weak.upgrade())) {
+        window.present();
+        return;
+    }
+
+    set_keyword("");
+
+    let window = libadwaita::Window::builder()
+        .title("Slow Mode")
+        .transient_for(parent)
+        .default_width(640)
+        .default_height(420)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+
+    // Source: the whole stream or one of the split filters
+    let mut sources = vec!["All posts".to_string()];
+    sources.extend(keywords.iter().map(|keyword| format!("“{}”", keyword)));
+    let source_refs: Vec<&str> = sources.iter().map(String::as_str).collect();
+    let source_dropdown = gtk::DropDown::from_strings(&source_refs);
+    source_dropdown.set_tooltip_text(Some("Draw posts from"));
+    header_bar.pack_start(&source_dropdown);
+
+    let pause_button = gtk::ToggleButton::builder()
+        .icon_name("media-playback-pause-symbolic")
+        .tooltip_text("Pause")
+        .build();
+    header_bar.pack_start(&pause_button);
+
+    let skip_button = gtk::Button::builder()
+        .icon_name("media-skip-forward-symbolic")
+        .tooltip_text("Next post")
+        .build();
+    header_bar.pack_start(&skip_button);
+
+    // Seconds each post stays on screen
+    let interval_spin = gtk::SpinButton::with_range(3.0, 120.0, 1.0);
+    interval_spin.set_value(settings.borrow().slow_mode_interval_seconds as f64);
+    interval_spin.set_tooltip_text(Some("Seconds per post"));
+    header_bar.pack_end(&interval_spin);
+
+    let stack = gtk::Stack::builder()
+        .transition_type(gtk::StackTransitionType::Crossfade)
+        .transition_duration(TRANSITION_MS)
+        .vexpand(true)
+        .build();
+    stack.add_child(&create_waiting_page());
+
+    let toolbar_view = libadwaita::ToolbarView::new();
+    toolbar_view.add_top_bar(&header_bar);
+    toolbar_view.set_content(Some(&stack));
+    window.set_content(Some(&toolbar_view));
+
+    // Swap in the next sampled post, dropping the previous card after the fade
+    let show_next = {
+        let stack = stack.clone();
+        move || {
+            let Some(post) = take_sample() else {
+                return;
+            };
+
+            let previous = stack.visible_child();
+            let card = create_post_card(&post);
+            stack.add_child(&card);
+            stack.set_visible_child(&card);
+
+            if let Some(previous) = previous {
+                let stack = stack.clone();
+                glib::timeout_add_local_once(std::time::Duration::from_millis(TRANSITION_MS as u64), move || {
+                    if previous.parent().as_ref() == Some(stack.upcast_ref::<gtk::Widget>()) {
+                        stack.remove(&previous);
+                    }
+                });
+            }
+        }
+    };
+    let show_next = Rc::new(show_next);
+
+    let keywords_for_source = keywords.clone();
+    source_dropdown.connect_selected_notify(move |dropdown| {
+        let selected = dropdown.selected() as usize;
+        let keyword = selected
+            .checked_sub(1)
+            .and_then(|index| keywords_for_source.get(index))
+            .map(String::as_str)
+            .unwrap_or("");
+        set_keyword(keyword);
+    });
+
+    let settings_for_interval = settings.clone();
+    interval_spin.connect_value_changed(move |spin| {
+        let mut settings = settings_for_interval.borrow_mut();
+        settings.slow_mode_interval_seconds = spin.value() as u32;
+        settings.save();
+    });
+
+    pause_button.connect_toggled(|button| {
+        if button.is_active() {
+            button.set_icon_name("media-playback-start-symbolic");
+            button.set_tooltip_text(Some("Resume"));
+        } else {
+            button.set_icon_name("media-playback-pause-symbolic");
+            button.set_tooltip_text(Some("Pause"));
+        }
+    });
+
+    // Tick every second against the configured interval so changes apply immediately
+    let elapsed = Rc::new(RefCell::new(0u32));
+
+    let elapsed_for_skip = elapsed.clone();
+    let show_next_for_skip = show_next.clone();
+    skip_button.connect_clicked(move |_| {
+        *elapsed_for_skip.borrow_mut() = 0;
+        show_next_for_skip();
+    });
+
+    let window_weak = window.downgrade();
+    glib::timeout_add_seconds_local(1, move || {
+        if window_weak.upgrade().is_none() {
+            return glib::ControlFlow::Break;
+        }
+        if pause_button.is_active() {
+            return glib::ControlFlow::Continue;
+        }
+
+        let mut elapsed = elapsed.borrow_mut();
+        *elapsed += 1;
+        // Show the first post as soon as one arrives, then keep to the cadence
+        let waiting = stack.visible_child().is_some_and(|child| child.is::<libadwaita::StatusPage>());
+        if waiting || *elapsed >= settings.borrow().slow_mode_interval_seconds {
+            *elapsed = 0;
+            drop(elapsed);
+            show_next();
+        }
+        glib::ControlFlow::Continue
+    });
+
+    window.connect_close_request(|_| {
+        RESERVOIR.with(|reservoir| *reservoir.borrow_mut() = None);
+        WINDOW.with(|window| *window.borrow_mut() = None);
+        glib::Propagation::Proceed
+    });
+
+    WINDOW.with(|current| *current.borrow_mut() = Some(window.downgrade()));
+    window.present();
+}
@@ -0,0 +1,140 @@
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+use crate::data::{FacetType, FirehosePost, PostEmbed};
+use crate::settings::Settings;
+
+/// How far back repeated texts and each account's posting rate are looked at
+const WINDOW: Duration = Duration::from_secs(10 * 60);
+/// Posts per account within the window that start to look automated
+const FREQUENT_POSTS: usize = 10;
+/// Posts per account within the window that count as fully automated
+const FLOOD_POSTS: usize = 40;
+/// Share of emoji among a post's visible characters that starts to count
+const EMOJI_DENSITY: f64 = 0.3;
+/// Posts between sweeps of accounts and texts that dropped out of the window
+const SWEEP_EVERY: u32 = 2000;
+
+/// Weights of each signal in the 0–1 score
+const REPEAT_WEIGHT: f64 = 0.4;
+const LINK_ONLY_WEIGHT: f64 = 0.25;
+const EMOJI_WEIGHT: f64 = 0.15;
+const FREQUENCY_WEIGHT: f64 = 0.4;
+
+/// Recent posting seen on the stream, for telling spam and bots from people
+#[derive(Default)]
+struct Tracker {
+    /// When each account posted within the window
+    posts_by_did: HashMap<String, VecDeque<Instant>>,
+    /// When each normalized text was posted within the window, by anyone
+    texts: HashMap<u64, VecDeque<Instant>>,
+    since_sweep: u32,
+}
+
+thread_local! {
+    static TRACKER: RefCell<Tracker> = RefCell::new(Tracker::default());
+}
+
+/// Drop times that fell out of the window, returning how many are left
+fn trim(times: &mut VecDeque<Instant>, now: Instant) -> usize {
+    while times.front().is_some_and(|time| now.duration_since(*time) > WINDOW) {
+        times.pop_front();
+    }
+    times.len()
+}
+
+/// Lowercased words without links, so copies with different links or case match
+fn normalized_text(text: &str) -> String {
+    text.split_whitespace()
+        .filter(|word| !word.starts_with("http://") && !word.starts_with("https://"))
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn text_hash(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn has_link(post: &FirehosePost) -> bool {
+    matches!(post.embed, Some(PostEmbed::External { .. }))
+        || post
+            .facets
+            .iter()
+            .flatten()
+            .any(|facet| matches!(facet.facet_type, FacetType::Link(_)))
+}
+
+/// Pictographs, symbols and the joiners and selectors emoji are built from
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32, 0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0xFE0F | 0x200D)
+}
+
+fn emoji_density(text: &str) -> f64 {
+    let visible: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if visible.is_empty() {
+        return 0.0;
+    }
+    visible.iter().filter(|c| is_emoji(**c)).count() as f64 / visible.len() as f64
+}
+
+impl Tracker {
+    /// Note a post and score how likely it is spam, from 0 to 1
+    fn observe(&mut self, post: &FirehosePost) -> f64 {
+        let now = Instant::now();
+
+        self.since_sweep += 1;
+        if self.since_sweep >= SWEEP_EVERY {
+            self.since_sweep = 0;
+            self.posts_by_did.retain(|_, times| trim(times, now) > 0);
+            self.texts.retain(|_, times| trim(times, now) > 0);
+        }
+
+        let mut score = 0.0;
+
+        // The same text posted again, by the same account or a ring of them
+        let text = normalized_text(&post.text);
+        if text.chars().count() >= 8 {
+            let times = self.texts.entry(text_hash(&text)).or_default();
+            let earlier = trim(times, now);
+            times.push_back(now);
+            if earlier > 0 {
+                score += REPEAT_WEIGHT * (earlier as f64 / 3.0).min(1.0);
+            }
+        }
+
+        // A link with nothing said about it
+        if has_link(post) && text.is_empty() {
+            score += LINK_ONLY_WEIGHT;
+        }
+
+        let density = emoji_density(&post.text);
+        if density > EMOJI_DENSITY {
+            score += EMOJI_WEIGHT * ((density - EMOJI_DENSITY) / (1.0 - EMOJI_DENSITY)).min(1.0);
+        }
+
+        // Faster than people post, sustained over the window
+        let times = self.posts_by_did.entry(post.did.clone()).or_default();
+        let earlier = trim(times, now);
+        times.push_back(now);
+        if earlier >= FREQUENT_POSTS {
+            let excess = (earlier - FREQUENT_POSTS) as f64 / (FLOOD_POSTS - FREQUENT_POSTS) as f64;
+            score += FREQUENCY_WEIGHT * excess.clamp(0.25, 1.0);
+        }
+
+        score.min(1.0)
+    }
+}
+
+/// Note a post from the stream and tell whether the spam filter hides it.
+/// Every post is noted, so posting rates count the hidden ones too.
+pub fn is_hidden(post: &FirehosePost, settings: &Settings) -> bool {
+    let score = TRACKER.with(|tracker| tracker.borrow_mut().observe(post));
+    let strictness = settings.spam_filter_strictness.min(100);
+    strictness > 0 && score > 1.0 - strictness as f64 / 100.0
+}
@@ -0,0 +1,370 @@
+use gtk::gio;
+use gtk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::data::{FacetType, FirehosePost, PostEmbed, StreamAction};
+use crate::domain_filter::normalize_domain;
+use crate::settings::{AltTextFilter, RecordType, SavedSplit, SplitColor};
+
+/// Version of the rules file format written by `export_rules`
+const FORMAT_VERSION: u32 = 1;
+
+/// A split's rules as written to a .json file for sharing
+#[derive(Serialize, Deserialize)]
+struct RulesFile {
+    /// Marks the file as Grapevine split rules, and its format version
+    grapevine_split_rules: u32,
+    #[serde(flatten)]
+    split: SavedSplit,
+}
+
+/// A split's filters beyond the keyword and sentiment, ready to match posts
+#[derive(Debug, Clone, Default)]
+pub struct SplitRules {
+    /// Declared post languages, any of which may match
+    languages: Vec<String>,
+    /// Author DIDs, any of which may match
+    authors: Vec<String>,
+    /// Posts containing any of these are left out
+    mute_terms: Vec<String>,
+    /// Linked domains, any of which may match
+    link_domains: Vec<String>,
+    alt_text: AltTextFilter,
+    record_type: RecordType,
+    /// The pattern as typed, kept for saving
+    regex_source: String,
+    pattern: Option<regex::Regex>,
+}
+
+impl SplitRules {
+    /// Compile a saved split's rules; an invalid pattern is ignored
+    pub fn from_saved(saved: &SavedSplit) -> Self {
+        Self {
+            languages: saved.languages.iter().map(|lang| lang.to_lowercase()).collect(),
+            authors: saved.authors.clone(),
+            mute_terms: saved.mute_terms.iter().map(|term| term.to_lowercase()).collect(),
+            link_domains: saved.link_domains.iter().map(|domain| normalize_domain(domain)).collect(),
+            alt_text: saved.alt_text,
+            record_type: saved.record_type,
+            regex_source: saved.regex.trim().to_string(),
+            pattern: compile_pattern(&saved.regex).ok().flatten(),
+        }
+    }
+
+    /// Copy the rules into a split being saved
+    pub fn write_to(&self, saved: &mut SavedSplit) {
+        saved.languages = self.languages.clone();
+        saved.authors = self.authors.clone();
+        saved.mute_terms = self.mute_terms.clone();
+        saved.link_domains = self.link_domains.clone();
+        saved.alt_text = self.alt_text;
+        saved.record_type = self.record_type;
+        saved.regex = self.regex_source.clone();
+    }
+
+    /// Whether any rule narrows the split (mute terms alone don't)
+    pub fn has_filter(&self) -> bool {
+        !self.languages.is_empty()
+            || !self.authors.is_empty()
+            || !self.link_domains.is_empty()
+            || self.alt_text != AltTextFilter::Any
+            || self.record_type != RecordType::Posts
+            || self.pattern.is_some()
+    }
+
+    pub fn record_type(&self) -> RecordType {
+        self.record_type
+    }
+
+    /// Whether a like, repost or follow belongs in the split; only the
+    /// record type and author rules apply to them
+    pub fn matches_action(&self, action: &StreamAction) -> bool {
+        action.kind == self.record_type && (self.authors.is_empty() || self.authors.contains(&action.did))
+    }
+
+    pub fn matches(&self, post: &FirehosePost) -> bool {
+        if self.record_type != RecordType::Posts {
+            return false;
+        }
+        if !self.languages.is_empty() && !post.langs.iter().any(|lang| self.languages.contains(lang)) {
+            return false;
+        }
+        if !self.authors.is_empty() && !self.authors.contains(&post.did) {
+            return false;
+        }
+        if !self.link_domains.is_empty()
+            && !linked_domains(post).iter().any(|domain| {
+                self.link_domains.iter().any(|wanted| {
+                    domain == wanted || domain.strip_suffix(wanted.as_str()).is_some_and(|rest| rest.ends_with('.'))
+                })
+            })
+        {
+            return false;
+        }
+        if !alt_text_matches(self.alt_text, post) {
+            return false;
+        }
+        if let Some(ref pattern) = self.pattern {
+            if !pattern.is_match(&post.text) {
+                return false;
+            }
+        }
+        if !self.mute_terms.is_empty() {
+            let text = post.text.to_lowercase();
+            if self.mute_terms.iter().any(|term| text.contains(term.as_str())) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Domains a post links to, from its link card and any links in its text
+fn linked_domains(post: &FirehosePost) -> Vec<String> {
+    let mut urls: Vec<&str> = Vec::new();
+    match &post.embed {
+        Some(PostEmbed::External { uri, .. }) => urls.push(uri),
+        Some(PostEmbed::Record { media: Some(media), .. }) => {
+            if let PostEmbed::External { uri, .. } = media.as_ref() {
+                urls.push(uri);
+            }
+        }
+        _ => {}
+    }
+    for facet in post.facets.iter().flatten() {
+        if let FacetType::Link(uri) = &facet.facet_type {
+            urls.push(uri);
+        }
+    }
+    urls.into_iter().map(normalize_domain).filter(|domain| !domain.is_empty()).collect()
+}
+
+/// Alt text of each image a post carries, including images posted alongside
+/// a quote; None for posts without images
+pub fn image_alt_texts(post: &FirehosePost) -> Option<&[String]> {
+    match &post.embed {
+        Some(PostEmbed::Images { alt_texts, .. }) => Some(alt_texts),
+        Some(PostEmbed::Record { media: Some(media), .. }) => match media.as_ref() {
+            PostEmbed::Images { alt_texts, .. } => Some(alt_texts),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether a post passes an alt text filter; any filter leaves out posts without images
+fn alt_text_matches(filter: AltTextFilter, post: &FirehosePost) -> bool {
+    let described = |alt: &String| !alt.trim().is_empty();
+    match (filter, image_alt_texts(post)) {
+        (AltTextFilter::Any, _) => true,
+        (_, None) => false,
+        (AltTextFilter::Missing, Some(alt_texts)) => !alt_texts.iter().all(described),
+        (AltTextFilter::Described, Some(alt_texts)) => alt_texts.iter().all(described),
+    }
+}
+
+/// Compile a case-insensitive pattern; Ok(None) when it is empty
+pub fn compile_pattern(pattern: &str) -> Result<Option<regex::Regex>, regex::Error> {
+    if pattern.trim().is_empty() {
+        return Ok(None);
+    }
+    regex::RegexBuilder::new(pattern.trim())
+        .case_insensitive(true)
+        .build()
+        .map(Some)
+}
+
+/// Split a comma-separated list typed into a rule field
+pub fn parse_list(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn json_filters() -> gio::ListStore {
+    let filters = gio::ListStore::new::<gtk::FileFilter>();
+    let json_filter = gtk::FileFilter::new();
+    json_filter.set_name(Some("Split rules (JSON)"));
+    json_filter.add_pattern("*.json");
+    json_filter.add_mime_type("application/json");
+    filters.append(&json_filter);
+    filters
+}
+
+/// File name a split's rules are offered under, e.g. "climate-rules.json"
+fn suggested_file_name(split: &SavedSplit) -> String {
+    let name = if split.title.is_empty() { &split.keyword } else { &split.title };
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        "split-rules.json".to_string()
+    } else {
+        format!("{}-rules.json", slug)
+    }
+}
+
+/// Ask where to save a split's rules and write them as JSON
+pub fn export_rules(parent: Option<&gtk::Window>, split: SavedSplit) {
+    let dialog = gtk::FileDialog::builder()
+        .title("Export Split Rules")
+        .initial_name(suggested_file_name(&split))
+        .filters(&json_filters())
+        .modal(true)
+        .build();
+
+    dialog.save(parent, gio::Cancellable::NONE, move |result| {
+        let Ok(file) = result else {
+            // Dismissed by the user
+            return;
+        };
+        let Some(path) = file.path() else {
+            tracing::warn!("Export destination has no local path");
+            return;
+        };
+
+        let rules = RulesFile { grapevine_split_rules: FORMAT_VERSION, split: split.clone() };
+        match serde_json::to_string_pretty(&rules) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    tracing::warn!("Failed to write split rules to {}: {}", path.display(), e);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to serialize split rules: {}", e),
+        }
+    });
+}
+
+/// Read split rules written by `export_rules`, rejecting other JSON and bad patterns
+fn parse_rules(json: &str) -> Result<SavedSplit, String> {
+    let rules: RulesFile = serde_json::from_str(json).map_err(|e| format!("Not a split rules file: {}", e))?;
+    if rules.grapevine_split_rules > FORMAT_VERSION {
+        return Err("These rules were exported by a newer version of Grapevine".to_string());
+    }
+    compile_pattern(&rules.split.regex).map_err(|e| format!("Invalid pattern: {}", e))?;
+    Ok(rules.split)
+}
+
+/// Ask for a rules file and hand the split it describes to `on_import`
+pub fn import_rules(parent: Option<&gtk::Window>, on_import: impl Fn(SavedSplit) + 'static) {
+    let dialog = gtk::FileDialog::builder()
+        .title("Import Split Rules")
+        .filters(&json_filters())
+        .modal(true)
+        .build();
+
+    dialog.open(parent, gio::Cancellable::NONE, move |result| {
+        let Ok(file) = result else {
+            return;
+        };
+        let Some(path) = file.path() else {
+            tracing::warn!("Import source has no local path");
+            return;
+        };
+
+        match std::fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|json| parse_rules(&json)) {
+            Ok(split) => on_import(split),
+            Err(e) => tracing::warn!("Failed to import split rules from {}: {}", path.display(), e),
+        }
+    });
+}
+
+/// Outlets the "Links from news" starter split follows
+const NEWS_DOMAINS: &[&str] = &[
+    "apnews.com", "reuters.com", "bbc.co.uk", "bbc.com", "theguardian.com", "nytimes.com",
+    "washingtonpost.com", "npr.org", "aljazeera.com", "france24.com", "dw.com", "cnn.com",
+    "bloomberg.com", "ft.com", "economist.com", "politico.com", "axios.com", "lemonde.fr",
+    "spiegel.de", "elpais.com", "abc.net.au", "cbc.ca", "nhk.or.jp", "scmp.com",
+];
+
+/// Two-letter code of the desktop's language, e.g. "en" for en_GB.UTF-8
+fn desktop_language() -> Option<String> {
+    gtk::glib::language_names()
+        .iter()
+        .map(|name| name.split(['_', '.', '@']).next().unwrap_or_default().to_lowercase())
+        .find(|code| code.len() == 2 && code.chars().all(|c| c.is_ascii_lowercase()))
+}
+
+/// Ready-made splits offered to people new to the firehose: a description
+/// for the button's tooltip, and the split it adds
+pub fn starter_splits() -> Vec<(&'static str, SavedSplit)> {
+    let language = desktop_language().unwrap_or_else(|| "en".to_string());
+    vec![
+        (
+            "Posts saying breaking, just in or developing",
+            SavedSplit {
+                title: "Breaking News".to_string(),
+                color: SplitColor::Red,
+                regex: r"\b(breaking|just in|developing|urgent|live updates)\b".to_string(),
+                mute_terms: vec!["breaking bad".to_string()],
+                ..SavedSplit::default()
+            },
+        ),
+        (
+            "Only posts declared in your desktop's language",
+            SavedSplit {
+                title: format!("Your Language ({})", language),
+                color: SplitColor::Blue,
+                languages: vec![language],
+                ..SavedSplit::default()
+            },
+        ),
+        (
+            "Posts linking to major news outlets",
+            SavedSplit {
+                title: "Links from News".to_string(),
+                color: SplitColor::Green,
+                link_domains: NEWS_DOMAINS.iter().map(|domain| domain.to_string()).collect(),
+                ..SavedSplit::default()
+            },
+        ),
+    ]
+}
+
+/// Most terms a story's split listens for, besides the outlet's domain
+const MAX_STORY_TERMS: usize = 4;
+
+/// A split following Bluesky's reaction to a news story: posts naming the
+/// headline's people and places, using its longest words, or mentioning
+/// the outlet's domain
+pub fn story_split(title: &str, domain: &str) -> SavedSplit {
+    let mut terms: Vec<String> = crate::themes::entities_for(title);
+    let mut words: Vec<&str> = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 4)
+        .collect();
+    words.sort_by_key(|word| std::cmp::Reverse(word.chars().count()));
+    for word in words {
+        let covered = terms.iter().any(|term| term.to_lowercase().contains(&word.to_lowercase()));
+        if !covered {
+            terms.push(word.to_string());
+        }
+    }
+    terms.truncate(MAX_STORY_TERMS);
+
+    let domain = normalize_domain(domain);
+    let mut alternatives: Vec<String> = terms.iter().map(|term| regex::escape(term)).collect();
+    if !domain.is_empty() {
+        alternatives.push(regex::escape(&domain));
+    }
+
+    let mut short_title: String = title.chars().take(40).collect();
+    if short_title.len() < title.len() {
+        short_title = format!("{}…", short_title.trim_end());
+    }
+
+    SavedSplit {
+        title: short_title,
+        color: SplitColor::Purple,
+        regex: if alternatives.is_empty() {
+            String::new()
+        } else {
+            format!(r"\b({})\b", alternatives.join("|"))
+        },
+        ..SavedSplit::default()
+    }
+}
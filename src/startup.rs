@@ -0,0 +1,147 @@
+use gtk::glib;
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+/// Tasks allowed to run at once
+const MAX_CONCURRENT: usize = 2;
+
+/// Pause between starting tasks, so requests don't all go out in the same instant
+const STAGGER: Duration = Duration::from_millis(150);
+
+/// Order in which queued work starts; ties start in the order they were queued
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Needed for the first frame to look right
+    Critical,
+    /// Content the user is waiting for
+    High,
+    Normal,
+    /// Warm-up work nothing is waiting on yet
+    Low,
+}
+
+impl Priority {
+    /// How long a task may hold a slot before the next one starts regardless
+    fn time_box(self) -> Duration {
+        match self {
+            Priority::Critical => Duration::from_secs(1),
+            Priority::High | Priority::Normal => Duration::from_secs(3),
+            Priority::Low => Duration::from_secs(5),
+        }
+    }
+}
+
+struct Task {
+    priority: Priority,
+    name: &'static str,
+    future: Pin<Box<dyn Future<Output = ()>>>,
+}
+
+#[derive(Default)]
+struct Scheduler {
+    /// False until the window is shown; tasks queue up until then
+    started: bool,
+    /// Whether the drive loop is currently spawned
+    driving: bool,
+    running: usize,
+    queue: Vec<Task>,
+}
+
+impl Scheduler {
+    /// Next task to start, if a slot is free
+    fn take_next(&mut self) -> Option<Task> {
+        if self.running >= MAX_CONCURRENT {
+            return None;
+        }
+
+        // The queue is in arrival order, so the first match keeps ties FIFO
+        let best = self.queue.iter().map(|task| task.priority).min()?;
+        let index = self.queue.iter().position(|task| task.priority == best)?;
+        self.running += 1;
+        Some(self.queue.remove(index))
+    }
+}
+
+thread_local! {
+    static SCHEDULER: RefCell<Scheduler> = RefCell::new(Scheduler::default());
+}
+
+/// Queue work behind the startup scheduler. Before the window is shown tasks wait;
+/// afterwards they start in priority order, a couple at a time, each time-boxed
+/// so a slow request doesn't hold up the rest.
+pub fn schedule(priority: Priority, name: &'static str, future: impl Future<Output = ()> + 'static) {
+    SCHEDULER.with(|scheduler| {
+        scheduler.borrow_mut().queue.push(Task {
+            priority,
+            name,
+            future: Box::pin(future),
+        });
+    });
+    ensure_driving();
+}
+
+/// Start running queued tasks once the window has drawn its first frame
+pub fn start() {
+    glib::idle_add_local_once(|| {
+        SCHEDULER.with(|scheduler| scheduler.borrow_mut().started = true);
+        ensure_driving();
+    });
+}
+
+fn ensure_driving() {
+    let spawn = SCHEDULER.with(|scheduler| {
+        let mut scheduler = scheduler.borrow_mut();
+        if !scheduler.started || scheduler.driving {
+            return false;
+        }
+        scheduler.driving = true;
+        true
+    });
+
+    if spawn {
+        glib::spawn_future_local(drive());
+    }
+}
+
+/// Start tasks until the queue is empty, waiting for a free slot when all are busy
+async fn drive() {
+    loop {
+        let (next, idle) = SCHEDULER.with(|scheduler| {
+            let mut scheduler = scheduler.borrow_mut();
+            let next = scheduler.take_next();
+            let idle = next.is_none() && scheduler.queue.is_empty();
+            if idle {
+                scheduler.driving = false;
+            }
+            (next, idle)
+        });
+
+        if idle {
+            return;
+        }
+
+        match next {
+            Some(task) => {
+                run(task);
+                glib::timeout_future(STAGGER).await;
+            }
+            None => glib::timeout_future(STAGGER).await,
+        }
+    }
+}
+
+/// Run a task, releasing its slot when it finishes or its time box runs out
+fn run(task: Task) {
+    let Task { priority, name, future } = task;
+    let handle = glib::spawn_future_local(future);
+
+    glib::spawn_future_local(async move {
+        // The task keeps running past its time box; it just stops holding a slot
+        if glib::future_with_timeout(priority.time_box(), handle).await.is_err() {
+            tracing::warn!("Startup task '{}' exceeded its time box, starting the next one", name);
+        }
+        SCHEDULER.with(|scheduler| scheduler.borrow_mut().running -= 1);
+    });
+}
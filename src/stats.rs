@@ -0,0 +1,223 @@
+use gtk::prelude::*;
+use libadwaita::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::settings::config_dir;
+
+/// Activity counters, used both for the current session and the all-time totals
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    /// Firehose posts received
+    pub posts_seen: u64,
+    /// Posts that matched a split's filters
+    pub filter_matches: u64,
+    /// GDELT articles fetched
+    pub articles_fetched: u64,
+    /// GDACS disaster alerts received
+    pub alerts_fired: u64,
+    /// Firehose posts dropped because the UI fell too far behind
+    pub posts_dropped: u64,
+}
+
+impl Stats {
+    fn add(&mut self, other: &Stats) {
+        self.posts_seen += other.posts_seen;
+        self.filter_matches += other.filter_matches;
+        self.articles_fetched += other.articles_fetched;
+        self.alerts_fired += other.alerts_fired;
+        self.posts_dropped += other.posts_dropped;
+    }
+
+    /// (label, value) pairs in display order
+    fn rows(&self) -> [(&'static str, u64); 5] {
+        [
+            ("Posts seen", self.posts_seen),
+            ("Filter matches", self.filter_matches),
+            ("Articles fetched", self.articles_fetched),
+            ("Disaster alerts", self.alerts_fired),
+            ("Posts dropped while behind", self.posts_dropped),
+        ]
+    }
+}
+
+/// All-time totals persisted to ~/.config/grapevine/stats.toml
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct CumulativeStats {
+    sessions: u64,
+    first_session: String,
+    totals: Stats,
+}
+
+thread_local! {
+    static SESSION: RefCell<Stats> = RefCell::new(Stats::default());
+}
+
+/// When Grapevine started, set by `init` from main
+static SESSION_STARTED: OnceLock<Instant> = OnceLock::new();
+
+/// Start the session clock; call once at launch
+pub fn init() {
+    SESSION_STARTED.get_or_init(Instant::now);
+}
+
+pub fn record_post() {
+    SESSION.with(|stats| stats.borrow_mut().posts_seen += 1);
+}
+
+pub fn record_filter_match() {
+    SESSION.with(|stats| stats.borrow_mut().filter_matches += 1);
+}
+
+pub fn record_articles(count: usize) {
+    SESSION.with(|stats| stats.borrow_mut().articles_fetched += count as u64);
+}
+
+pub fn record_alerts(count: usize) {
+    SESSION.with(|stats| stats.borrow_mut().alerts_fired += count as u64);
+}
+
+pub fn record_dropped(count: u64) {
+    SESSION.with(|stats| stats.borrow_mut().posts_dropped += count);
+}
+
+/// Counters for the current session
+pub fn session() -> Stats {
+    SESSION.with(|stats| stats.borrow().clone())
+}
+
+/// Time since Grapevine started
+pub fn session_elapsed() -> Duration {
+    SESSION_STARTED.get_or_init(Instant::now).elapsed()
+}
+
+fn session_duration() -> String {
+    let minutes = session_elapsed().as_secs() / 60;
+    if minutes < 60 {
+        format!("{} min", minutes)
+    } else {
+        format!("{} h {} min", minutes / 60, minutes % 60)
+    }
+}
+
+fn stats_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("stats.toml"))
+}
+
+fn load_cumulative() -> CumulativeStats {
+    let Some(path) = stats_path() else {
+        return CumulativeStats::default();
+    };
+
+    match std::fs::read_to_string(&path) {
+        Ok(text) => toml::from_str(&text).unwrap_or_else(|e| {
+            tracing::warn!("Failed to parse stats at {}: {}", path.display(), e);
+            CumulativeStats::default()
+        }),
+        // No stats recorded yet
+        Err(_) => CumulativeStats::default(),
+    }
+}
+
+/// Fold this session into the all-time totals; call once at shutdown
+pub fn save_session() {
+    let Some(path) = stats_path() else {
+        return;
+    };
+
+    let mut cumulative = load_cumulative();
+    cumulative.sessions += 1;
+    if cumulative.first_session.is_empty() {
+        cumulative.first_session = chrono::Local::now().format("%Y-%m-%d").to_string();
+    }
+    cumulative.totals.add(&session());
+
+    if let Some(parent) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            tracing::warn!("Failed to create config directory {}: {}", parent.display(), e);
+            return;
+        }
+    }
+
+    match toml::to_string_pretty(&cumulative) {
+        Ok(text) => {
+            if let Err(e) = std::fs::write(&path, text) {
+                tracing::warn!("Failed to write stats to {}: {}", path.display(), e);
+            }
+        }
+        Err(e) => tracing::warn!("Failed to serialize stats: {}", e),
+    }
+}
+
+/// One-paragraph summary of the current session for the quit dialog
+pub fn session_summary() -> String {
+    let stats = session();
+    format!(
+        "In {} you saw {} posts ({} matched your filters), fetched {} articles and received {} disaster alerts.",
+        session_duration(),
+        stats.posts_seen,
+        stats.filter_matches,
+        stats.articles_fetched,
+        stats.alerts_fired,
+    )
+}
+
+fn create_stats_group(title: &str, description: Option<&str>, stats: &Stats) -> libadwaita::PreferencesGroup {
+    let group = libadwaita::PreferencesGroup::builder()
+        .title(title)
+        .build();
+    if let Some(description) = description {
+        group.set_description(Some(description));
+    }
+
+    for (label, value) in stats.rows() {
+        let value_label = gtk::Label::builder()
+            .label(value.to_string())
+            .build();
+        value_label.add_css_class("monospace");
+
+        let row = libadwaita::ActionRow::builder()
+            .title(label)
+            .build();
+        row.add_suffix(&value_label);
+        group.add(&row);
+    }
+
+    group
+}
+
+/// Window with this session's counters and the all-time totals
+pub fn create_stats_window(parent: &impl IsA<gtk::Window>) -> libadwaita::PreferencesWindow {
+    let window = libadwaita::PreferencesWindow::builder()
+        .title("Statistics")
+        .transient_for(parent)
+        .modal(true)
+        .search_enabled(false)
+        .build();
+
+    let page = libadwaita::PreferencesPage::new();
+
+    let session = session();
+    let session_description = format!("Running for {}", session_duration());
+    page.add(&create_stats_group("This Session", Some(&session_description), &session));
+
+    // All-time totals include the current session, which is only saved at exit
+    let cumulative = load_cumulative();
+    let mut totals = cumulative.totals.clone();
+    totals.add(&session);
+    let all_time_description = if cumulative.first_session.is_empty() {
+        "First session".to_string()
+    } else {
+        format!("{} sessions since {}", cumulative.sessions + 1, cumulative.first_session)
+    };
+    page.add(&create_stats_group("All Time", Some(&all_time_description), &totals));
+    page.add(&crate::char_stats::create_char_stats_group());
+
+    window.add(&page);
+    window
+}
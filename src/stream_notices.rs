@@ -0,0 +1,119 @@
+use gtk::prelude::*;
+use gtk::{glib, Label};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+/// Cards tracked between sweeps of the ones no longer shown
+const SWEEP_EVERY: u32 = 500;
+
+/// Changes Jetstream reports about posts and accounts already seen, besides new posts
+#[derive(Debug, Clone)]
+pub enum StreamNotice {
+    /// A post was deleted by its author
+    Deleted { did: String, rkey: String },
+    /// An account's handle changed, or went missing when None
+    HandleChanged { did: String, handle: Option<String> },
+    /// An account stopped being active: deactivated, suspended, taken down or deleted
+    AccountInactive { did: String, status: String },
+}
+
+/// A post card on screen, with the row its notes go in
+struct TrackedCard {
+    rkey: String,
+    card: glib::WeakRef<gtk::Box>,
+    notes: glib::WeakRef<gtk::Box>,
+}
+
+thread_local! {
+    /// Cards shown for each account, by DID
+    static CARDS: RefCell<HashMap<String, Vec<TrackedCard>>> = RefCell::new(HashMap::new());
+    static SINCE_SWEEP: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Index a post's card so deletes and account changes that arrive later can mark it
+pub fn track(did: &str, rkey: &str, card: &gtk::Box, notes: &gtk::Box) {
+    CARDS.with(|cards| {
+        let mut cards = cards.borrow_mut();
+
+        // Forget cards that scrolled out of their lists now and then
+        let since_sweep = SINCE_SWEEP.with(|since| since.replace(since.get() + 1));
+        if since_sweep >= SWEEP_EVERY {
+            SINCE_SWEEP.with(|since| since.set(0));
+            cards.retain(|_, tracked| {
+                tracked.retain(|card| card.card.upgrade().is_some());
+                !tracked.is_empty()
+            });
+        }
+
+        cards.entry(did.to_string()).or_default().push(TrackedCard {
+            rkey: rkey.to_string(),
+            card: card.downgrade(),
+            notes: notes.downgrade(),
+        });
+    });
+}
+
+/// Show a note on a card, replacing an earlier one of the same kind
+fn set_note(notes: &gtk::Box, kind: &str, text: &str) {
+    let mut child = notes.first_child();
+    while let Some(current) = child {
+        child = current.next_sibling();
+        if current.widget_name() == kind {
+            notes.remove(&current);
+        }
+    }
+
+    let label = Label::builder()
+        .label(text)
+        .name(kind)
+        .build();
+    label.add_css_class("badge");
+    label.add_css_class("badge-neutral");
+    label.add_css_class("stream-note");
+    notes.append(&label);
+    notes.set_visible(true);
+}
+
+/// Run `f` on each card still shown for an account
+fn for_each_card(did: &str, mut f: impl FnMut(&TrackedCard, &gtk::Box, &gtk::Box)) {
+    CARDS.with(|cards| {
+        let cards = cards.borrow();
+        for tracked in cards.get(did).into_iter().flatten() {
+            if let (Some(card), Some(notes)) = (tracked.card.upgrade(), tracked.notes.upgrade()) {
+                f(tracked, &card, &notes);
+            }
+        }
+    });
+}
+
+/// Mark what a notice changes on the cards shown, and keep deleted posts
+/// out of the history panes are refilled from
+pub fn apply(notice: &StreamNotice) {
+    match notice {
+        StreamNotice::Deleted { did, rkey } => {
+            crate::history::remove(did, rkey);
+            for_each_card(did, |tracked, card, notes| {
+                if tracked.rkey == *rkey {
+                    card.add_css_class("post-deleted");
+                    set_note(notes, "deleted", "Deleted by author");
+                }
+            });
+        }
+        StreamNotice::HandleChanged { did, handle } => {
+            // The author badges pick up the new handle from here on
+            crate::profiles::forget(did);
+            let text = match handle {
+                Some(handle) => format!("Handle now @{}", handle),
+                None => "Handle removed".to_string(),
+            };
+            for_each_card(did, |_, _, notes| set_note(notes, "handle", &text));
+        }
+        StreamNotice::AccountInactive { did, status } => {
+            let text = format!("Account {}", status);
+            for_each_card(did, |_, card, notes| {
+                card.add_css_class("account-inactive");
+                set_note(notes, "account", &text);
+            });
+        }
+    }
+}
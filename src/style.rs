@@ -0,0 +1,858 @@
+use gtk::gdk;
+use gtk::gio;
+use gtk::prelude::*;
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::settings::{config_dir, Density, Settings, Theme, ThumbnailSize};
+
+/// Layout knobs read from ~/.config/grapevine/layout.toml, for tuning the UI without rebuilding
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+struct LayoutConfig {
+    /// Multiplier applied to paddings on top of the density
+    spacing_scale: f64,
+    /// Width of an expanded deck column in px
+    column_width: u32,
+    /// Height of article thumbnails in px
+    thumbnail_height: u32,
+    /// Corner radius of article cards in px
+    card_radius: u32,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            spacing_scale: 1.0,
+            column_width: 380,
+            thumbnail_height: 140,
+            card_radius: 12,
+        }
+    }
+}
+
+fn layout_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("layout.toml"))
+}
+
+fn user_css_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("style.css"))
+}
+
+impl LayoutConfig {
+    /// Load the layout config, falling back to defaults if missing or invalid
+    fn load() -> Self {
+        let Some(path) = layout_path() else {
+            return Self::default();
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(text) => match toml::from_str(&text) {
+                Ok(layout) => layout,
+                Err(e) => {
+                    tracing::warn!("Failed to parse layout config at {}: {}", path.display(), e);
+                    Self::default()
+                }
+            },
+            Err(_) => Self::default(),
+        }
+    }
+}
+
+/// Stylesheet at 100% text size and comfortable density; px sizes are rescaled on load
+const BASE_CSS: &str = "
+.floating-switcher {
+    background-color: alpha(@window_bg_color, 0.85);
+    border-radius: 12px;
+    padding: 8px;
+    box-shadow: 0 4px 12px alpha(black, 0.3);
+}
+.map-marker {
+    background-color: alpha(@accent_bg_color, 0.75);
+    border-radius: 16px;
+    padding: 4px 10px;
+    font-size: 11px;
+    font-weight: bold;
+    min-height: 0;
+    min-width: 0;
+    box-shadow: 0 2px 6px alpha(black, 0.4);
+}
+.map-marker.compare-selected {
+    box-shadow: 0 0 0 2px @accent_color, 0 3px 8px alpha(black, 0.5);
+}
+.map-marker:hover {
+    background-color: alpha(@accent_bg_color, 0.95);
+    box-shadow: 0 3px 8px alpha(black, 0.5);
+}
+.quake-marker {
+    background-color: alpha(@warning_bg_color, 0.7);
+    color: @warning_fg_color;
+    border-radius: 999px;
+    padding: 0;
+    font-size: 9px;
+    font-weight: bold;
+    min-height: 0;
+    min-width: 0;
+    box-shadow: 0 1px 4px alpha(black, 0.4);
+}
+.quake-moderate {
+    background-color: alpha(@accent_bg_color, 0.8);
+    color: @accent_fg_color;
+}
+.quake-major {
+    background-color: alpha(@error_bg_color, 0.85);
+    color: @error_fg_color;
+}
+.disaster-marker {
+    border-radius: 8px;
+    padding: 2px 6px;
+    min-height: 0;
+    min-width: 0;
+    box-shadow: 0 2px 6px alpha(black, 0.4);
+}
+.disaster-green {
+    background-color: alpha(@success_bg_color, 0.7);
+}
+.disaster-orange {
+    background-color: alpha(@warning_bg_color, 0.8);
+}
+.disaster-red {
+    background-color: alpha(@error_bg_color, 0.85);
+}
+.event-marker {
+    border-radius: 999px;
+    padding: 2px 4px;
+    min-height: 0;
+    min-width: 0;
+    box-shadow: 0 2px 6px alpha(black, 0.4);
+}
+.event-protest {
+    background-color: alpha(#1c71d8, 0.75);
+}
+.event-coercion {
+    background-color: alpha(@warning_bg_color, 0.75);
+}
+.event-posture {
+    background-color: alpha(#813d9c, 0.75);
+}
+.event-assault {
+    background-color: alpha(#c64600, 0.8);
+}
+.event-conflict {
+    background-color: alpha(@error_bg_color, 0.85);
+}
+.overlay-marker {
+    border-radius: 8px;
+    padding: 2px 8px;
+    font-size: 10px;
+    font-weight: bold;
+    color: white;
+    min-height: 0;
+    min-width: 0;
+    box-shadow: 0 2px 6px alpha(black, 0.4);
+}
+.overlay-blue {
+    background-color: alpha(#3584e4, 0.85);
+}
+.overlay-green {
+    background-color: alpha(#2ec27e, 0.85);
+}
+.overlay-orange {
+    background-color: alpha(#f57900, 0.85);
+}
+.overlay-purple {
+    background-color: alpha(#9141ac, 0.85);
+}
+.map-popover > contents {
+    background-color: alpha(@card_bg_color, 0.95);
+    border-radius: 12px;
+    box-shadow: 0 4px 16px alpha(black, 0.6);
+}
+.compare-column {
+    padding: 12px;
+}
+.market-card {
+    padding: 12px;
+}
+.waking-up-row {
+    padding: 10px 12px;
+}
+.waking-up-row.waking-up-open {
+    border-left: 3px solid @success_color;
+}
+.pinned-panel {
+    background-color: alpha(@card_bg_color, 0.95);
+    border-radius: 12px;
+    box-shadow: 0 4px 16px alpha(black, 0.6);
+}
+.world-clock {
+    font-size: 12px;
+    padding: 4px 8px;
+    border-radius: 6px;
+    color: alpha(@window_fg_color, 0.8);
+    background-color: alpha(@window_fg_color, 0.06);
+}
+.time-display {
+    font-size: 13px;
+    font-weight: 600;
+    padding: 4px 12px;
+    background-color: alpha(@accent_bg_color, 0.15);
+    border-radius: 6px;
+}
+.catch-up-bar {
+    background-color: alpha(@accent_bg_color, 0.15);
+    padding: 6px 12px;
+}
+.throughput-meter {
+    font-size: 11px;
+    padding: 2px 6px;
+}
+.domain-overflow > title,
+.story-sources > title {
+    font-size: 11px;
+    opacity: 0.7;
+}
+.compose-card-thumbnail {
+    border-radius: 6px;
+}
+.video-embed picture,
+.video-embed video {
+    border-radius: 8px;
+}
+.lightbox {
+    background-color: alpha(black, 0.92);
+    color: white;
+}
+.slow-mode-text {
+    font-size: 20px;
+    line-height: 1.4;
+}
+.reader-paragraph {
+    font-size: 16px;
+    line-height: 1.5;
+}
+.log-row {
+    padding: 2px 0;
+    font-size: 12px;
+    border-bottom: 1px solid alpha(@window_fg_color, 0.06);
+}
+.deck-column {
+    border-right: 1px solid alpha(currentColor, 0.1);
+    padding-bottom: 4px;
+}
+.deck-column-header {
+    padding: 6px 8px 0 8px;
+}
+.deck-column.split-blue > .deck-column-header { box-shadow: inset 0 3px @blue_3; }
+.deck-column.split-green > .deck-column-header { box-shadow: inset 0 3px @green_3; }
+.deck-column.split-yellow > .deck-column-header { box-shadow: inset 0 3px @yellow_3; }
+.deck-column.split-orange > .deck-column-header { box-shadow: inset 0 3px @orange_3; }
+.deck-column.split-red > .deck-column-header { box-shadow: inset 0 3px @red_3; }
+.deck-column.split-purple > .deck-column-header { box-shadow: inset 0 3px @purple_3; }
+.alt-audit {
+    margin-bottom: 4px;
+}
+.split-swatch {
+    min-width: 20px;
+    min-height: 20px;
+    padding: 2px;
+    border-radius: 999px;
+    background: alpha(currentColor, 0.1);
+}
+.split-swatch:checked {
+    outline: 2px solid @accent_color;
+    outline-offset: 1px;
+}
+.split-swatch.split-blue { background: @blue_3; }
+.split-swatch.split-green { background: @green_3; }
+.split-swatch.split-yellow { background: @yellow_3; }
+.split-swatch.split-orange { background: @orange_3; }
+.split-swatch.split-red { background: @red_3; }
+.split-swatch.split-purple { background: @purple_3; }
+.firehose-message {
+    background-color: alpha(@card_bg_color, 0.5);
+    border-radius: 8px;
+    padding: 3px 4px;
+    border: 1px solid alpha(@borders, 0.5);
+}
+.interaction-button:checked {
+    color: @accent_color;
+    background: none;
+}
+.notification-row {
+    padding: 6px 8px;
+    border-radius: 8px;
+}
+.notification-row.notification-unread {
+    background-color: alpha(@accent_bg_color, 0.08);
+    border-left: 3px solid @accent_color;
+}
+.firehose-message.sentiment-positive {
+    border-left: 3px solid @success_color;
+}
+.firehose-message.sentiment-negative {
+    border-left: 3px solid @error_color;
+}
+.firehose-reply-context {
+    border-left: 2px solid alpha(@window_fg_color, 0.2);
+    padding-left: 6px;
+}
+.firehose-quote {
+    border: 1px solid alpha(@window_fg_color, 0.15);
+    border-radius: 8px;
+    padding: 6px 8px;
+}
+.firehose-timestamp {
+    color: alpha(@window_fg_color, 0.55);
+}
+.firehose-rkey {
+    color: @accent_color;
+    font-weight: 600;
+}
+.identifier-button {
+    padding: 0 4px;
+    min-height: 0;
+}
+.author-badge {
+    font-weight: 600;
+}
+.hashtag-feed-row {
+    border-radius: 8px;
+    background-color: alpha(@card_bg_color, 0.6);
+}
+.firehose-text {
+    line-height: 1.4;
+}
+.post-deleted {
+    opacity: 0.5;
+}
+.post-deleted .firehose-text {
+    text-decoration: line-through;
+}
+.account-inactive {
+    opacity: 0.7;
+}
+.summary-text {
+    font-style: italic;
+    padding: 4px 8px;
+    border-left: 3px solid alpha(@accent_color, 0.6);
+}
+.char-stats-emoji {
+    font-size: 1.4em;
+}
+.firehose-action {
+    min-height: 28px;
+}
+.firehose-action .identifier-button {
+    padding: 0 4px;
+    min-height: 0;
+}
+.news-article-card {
+    background-color: @card_bg_color;
+    border-radius: 12px;
+    overflow: hidden;
+    border: 1px solid alpha(@borders, 0.2);
+    transition: all 200ms cubic-bezier(0.4, 0, 0.2, 1);
+}
+.news-article-card:hover {
+    border-color: alpha(@accent_bg_color, 0.3);
+    box-shadow: 0 4px 12px alpha(black, 0.12);
+    transform: translateY(-2px);
+}
+.article-nav > row:selected {
+    background-color: transparent;
+}
+.article-nav > row:selected .news-article-card {
+    border-color: @accent_color;
+    box-shadow: inset 0 0 0 1px @accent_color;
+}
+.news-article-card.article-read {
+    opacity: 0.6;
+}
+.news-article-card.article-read:hover {
+    opacity: 1;
+}
+.article-thumbnail {
+    background-color: alpha(@window_bg_color, 0.3);
+    height: 140px;
+    border-radius: 8px;
+    margin: 8px;
+}
+.article-title {
+    font-size: 14px;
+    font-weight: 600;
+    line-height: 1.35;
+    color: @window_fg_color;
+}
+.article-domain {
+    font-size: 11px;
+    font-weight: 500;
+    color: alpha(@window_fg_color, 0.5);
+    margin-top: 2px;
+}
+.badge {
+    background-color: alpha(@accent_bg_color, 0.15);
+    border-radius: 6px;
+    padding: 3px 8px;
+    font-size: 10px;
+    font-weight: 600;
+    min-height: 0;
+    text-transform: uppercase;
+    letter-spacing: 0.5px;
+}
+.badge-country {
+    background-color: alpha(@accent_bg_color, 0.25);
+    color: @accent_fg_color;
+    transition: all 150ms ease;
+}
+.badge-country:hover {
+    background-color: @accent_bg_color;
+    box-shadow: 0 2px 6px alpha(@accent_bg_color, 0.4);
+}
+.filter-chip:checked {
+    background-color: @accent_bg_color;
+    color: @accent_fg_color;
+}
+.badge-theme {
+    background-color: alpha(@purple_3, 0.2);
+    color: @purple_1;
+}
+.badge-entity {
+    background-color: alpha(@window_fg_color, 0.05);
+    border: 1px solid alpha(@window_fg_color, 0.15);
+}
+.badge-theme:hover, .badge-entity:hover {
+    background-color: alpha(@accent_bg_color, 0.35);
+}
+.badge-time {
+    background-color: alpha(@window_fg_color, 0.08);
+    color: alpha(@window_fg_color, 0.7);
+}
+.badge-lang {
+    background-color: alpha(@warning_bg_color, 0.2);
+    color: @warning_fg_color;
+}
+.badge-positive {
+    background-color: alpha(@success_bg_color, 0.2);
+    color: @success_fg_color;
+}
+.badge-negative {
+    background-color: alpha(@error_bg_color, 0.2);
+    color: @error_fg_color;
+}
+.badge-neutral {
+    background-color: alpha(@window_fg_color, 0.08);
+    color: alpha(@window_fg_color, 0.7);
+}
+.badge-credibility {
+    background-color: transparent;
+    border: 1px solid alpha(@window_fg_color, 0.25);
+    color: alpha(@window_fg_color, 0.8);
+}
+.popover-currency-section {
+    padding: 8px;
+    background-color: alpha(@accent_bg_color, 0.08);
+    border-radius: 8px;
+    border: 1px solid alpha(@accent_bg_color, 0.15);
+}
+.country-flag {
+    border-radius: 3px;
+    border: 1px solid alpha(@window_fg_color, 0.15);
+}
+.country-briefing {
+    margin-top: 4px;
+}
+.country-briefing-extract {
+    color: alpha(@window_fg_color, 0.8);
+}
+.heatmap-legend {
+    padding: 6px 10px;
+    border-radius: 8px;
+}
+.currency-rate {
+    font-family: monospace;
+    color: @accent_color;
+    font-weight: 700;
+}
+.currency-change-positive {
+    color: @success_color;
+}
+.currency-change-negative {
+    color: @error_color;
+}
+.popover-article-row {
+    background-color: alpha(@card_bg_color, 0.3);
+    border-radius: 6px;
+    border: 1px solid alpha(@borders, 0.15);
+    transition: all 150ms ease;
+}
+.popover-article-row:hover {
+    background-color: alpha(@card_bg_color, 0.6);
+    border-color: alpha(@accent_bg_color, 0.3);
+    box-shadow: 0 2px 6px alpha(black, 0.08);
+}
+.popover-article-title {
+    font-size: 13px;
+    font-weight: 600;
+    line-height: 1.3;
+}
+.popover-article-meta {
+    font-size: 11px;
+    color: alpha(@window_fg_color, 0.55);
+}
+.popover-article-time {
+    font-size: 10px;
+    color: alpha(@window_fg_color, 0.45);
+    font-weight: 500;
+}
+";
+
+/// Overrides for high-contrast mode: the translucent fills above fade into the
+/// background there, so badges, cards and markers get solid colors and outlines
+/// Map overlays over the light tiles: pale land washes out translucent markers and
+/// makes the shadows meant for dark tiles look heavy
+const LIGHT_MAP_CSS: &str = "
+.map-marker {
+    background-color: alpha(@accent_bg_color, 0.9);
+    color: @accent_fg_color;
+    box-shadow: 0 1px 4px alpha(black, 0.2);
+}
+.map-marker:hover {
+    background-color: @accent_bg_color;
+    box-shadow: 0 2px 6px alpha(black, 0.25);
+}
+.map-marker.compare-selected {
+    box-shadow: 0 0 0 2px @accent_color, 0 2px 6px alpha(black, 0.25);
+}
+.quake-marker,
+.overlay-marker {
+    box-shadow: 0 1px 3px alpha(black, 0.2);
+}
+.map-popover > contents {
+    box-shadow: 0 4px 16px alpha(black, 0.25);
+}
+.floating-switcher {
+    box-shadow: 0 4px 12px alpha(black, 0.15);
+}
+";
+
+const HIGH_CONTRAST_CSS: &str = "
+.floating-switcher,
+.map-popover > contents,
+.pinned-panel {
+    background-color: @window_bg_color;
+    border: 2px solid @window_fg_color;
+}
+.map-marker,
+.map-marker:hover,
+.quake-marker,
+.disaster-marker,
+.event-marker,
+.overlay-marker {
+    border: 2px solid @window_fg_color;
+    box-shadow: none;
+}
+.map-marker,
+.map-marker:hover {
+    background-color: @accent_bg_color;
+    color: @accent_fg_color;
+}
+.map-marker.compare-selected {
+    box-shadow: 0 0 0 3px @window_fg_color;
+}
+.quake-marker { background-color: @warning_bg_color; }
+.quake-moderate { background-color: @accent_bg_color; }
+.quake-major { background-color: @error_bg_color; }
+.disaster-green { background-color: @success_bg_color; }
+.disaster-orange { background-color: @warning_bg_color; }
+.disaster-red { background-color: @error_bg_color; }
+.event-protest { background-color: #1c71d8; }
+.event-coercion { background-color: @warning_bg_color; }
+.event-posture { background-color: #813d9c; }
+.event-assault { background-color: #c64600; }
+.event-conflict { background-color: @error_bg_color; }
+.overlay-blue { background-color: #1c71d8; }
+.overlay-green { background-color: #26a269; }
+.overlay-orange { background-color: #c64600; }
+.overlay-purple { background-color: #813d9c; }
+.time-display,
+.catch-up-bar,
+.notification-row.notification-unread,
+.popover-currency-section {
+    background-color: @window_bg_color;
+    border: 1px solid @window_fg_color;
+}
+.deck-column {
+    border-right: 1px solid @borders;
+}
+.firehose-message,
+.news-article-card,
+.popover-article-row {
+    background-color: @card_bg_color;
+    border: 1px solid @window_fg_color;
+}
+.news-article-card:hover,
+.popover-article-row:hover {
+    border-color: @accent_color;
+    box-shadow: inset 0 0 0 1px @accent_color;
+}
+.firehose-reply-context {
+    border-left-color: @window_fg_color;
+}
+.firehose-quote {
+    border-color: @window_fg_color;
+}
+.firehose-timestamp,
+.article-domain,
+.popover-article-meta,
+.popover-article-time {
+    color: @window_fg_color;
+}
+.badge,
+.badge-time,
+.badge-neutral,
+.badge-entity {
+    background-color: @window_bg_color;
+    color: @window_fg_color;
+    border: 1px solid @window_fg_color;
+}
+.badge-country,
+.badge-theme,
+.badge-theme:hover,
+.badge-entity:hover,
+.badge-country:hover {
+    background-color: @accent_bg_color;
+    color: @accent_fg_color;
+    box-shadow: none;
+}
+.badge-lang {
+    background-color: @warning_bg_color;
+    color: @warning_fg_color;
+}
+.badge-positive {
+    background-color: @success_bg_color;
+    color: @success_fg_color;
+}
+.badge-negative {
+    background-color: @error_bg_color;
+    color: @error_fg_color;
+}
+";
+
+thread_local! {
+    /// The app's CSS provider, kept so appearance changes can regenerate it
+    static PROVIDER: RefCell<Option<gtk::CssProvider>> = const { RefCell::new(None) };
+    /// Provider for the user's style.css, layered above the app stylesheet
+    static USER_PROVIDER: RefCell<Option<gtk::CssProvider>> = const { RefCell::new(None) };
+    /// File monitors kept alive while live reload is on
+    static MONITORS: RefCell<Vec<gio::FileMonitor>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Create the app stylesheet for the display, scaled to the appearance settings,
+/// with the user's style.css on top
+pub fn install(display: &gdk::Display, settings: &Settings) {
+    let provider = gtk::CssProvider::new();
+    provider.load_from_data(&generate_css(settings, &LayoutConfig::load()));
+
+    gtk::style_context_add_provider_for_display(
+        display,
+        &provider,
+        gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
+    );
+
+    // Follows the theme, including the system switching while the app runs
+    let style_manager = libadwaita::StyleManager::for_display(display);
+    let theme_provider = gtk::CssProvider::new();
+    load_theme_css(&theme_provider, style_manager.is_dark());
+    let provider_for_notify = theme_provider.clone();
+    style_manager.connect_dark_notify(move |style_manager| {
+        load_theme_css(&provider_for_notify, style_manager.is_dark());
+    });
+
+    gtk::style_context_add_provider_for_display(
+        display,
+        &theme_provider,
+        gtk::STYLE_PROVIDER_PRIORITY_APPLICATION + 1,
+    );
+
+    // Follows the system setting, including changes while the app runs
+    let high_contrast_provider = gtk::CssProvider::new();
+    load_high_contrast_css(&high_contrast_provider, style_manager.is_high_contrast());
+    let provider_for_notify = high_contrast_provider.clone();
+    style_manager.connect_high_contrast_notify(move |style_manager| {
+        load_high_contrast_css(&provider_for_notify, style_manager.is_high_contrast());
+    });
+
+    gtk::style_context_add_provider_for_display(
+        display,
+        &high_contrast_provider,
+        gtk::STYLE_PROVIDER_PRIORITY_APPLICATION + 2,
+    );
+
+    let user_provider = gtk::CssProvider::new();
+    user_provider.connect_parsing_error(|_, section, error| {
+        tracing::warn!("Error in user stylesheet at {}: {}", section, error);
+    });
+    load_user_css(&user_provider);
+
+    gtk::style_context_add_provider_for_display(
+        display,
+        &user_provider,
+        gtk::STYLE_PROVIDER_PRIORITY_USER,
+    );
+
+    PROVIDER.with(|current| *current.borrow_mut() = Some(provider));
+    USER_PROVIDER.with(|current| *current.borrow_mut() = Some(user_provider));
+}
+
+/// Regenerate the stylesheet after the appearance settings, layout config or user CSS change
+pub fn reload(settings: &Settings) {
+    PROVIDER.with(|current| {
+        if let Some(provider) = current.borrow().as_ref() {
+            provider.load_from_data(&generate_css(settings, &LayoutConfig::load()));
+        }
+    });
+    USER_PROVIDER.with(|current| {
+        if let Some(provider) = current.borrow().as_ref() {
+            load_user_css(provider);
+        }
+    });
+}
+
+/// Switch the app between light and dark; the map tiles and theme CSS follow
+/// the style manager's notification
+pub fn apply_theme(theme: Theme) {
+    libadwaita::StyleManager::default().set_color_scheme(match theme {
+        Theme::System => libadwaita::ColorScheme::Default,
+        Theme::Light => libadwaita::ColorScheme::ForceLight,
+        Theme::Dark => libadwaita::ColorScheme::ForceDark,
+    });
+}
+
+fn load_theme_css(provider: &gtk::CssProvider, dark: bool) {
+    provider.load_from_data(if dark { "" } else { LIGHT_MAP_CSS });
+}
+
+fn load_high_contrast_css(provider: &gtk::CssProvider, high_contrast: bool) {
+    provider.load_from_data(if high_contrast { HIGH_CONTRAST_CSS } else { "" });
+}
+
+fn load_user_css(provider: &gtk::CssProvider) {
+    match user_css_path() {
+        // Safe mode recovers from a stylesheet that breaks the app
+        _ if crate::safe_mode::is_enabled() => provider.load_from_data(""),
+        Some(path) if path.exists() => provider.load_from_path(&path),
+        _ => provider.load_from_data(""),
+    }
+}
+
+/// Developer mode: watch style.css and layout.toml and reload the styles whenever either changes
+pub fn set_live_reload(enabled: bool, settings: Rc<RefCell<Settings>>) {
+    MONITORS.with(|monitors| {
+        let mut monitors = monitors.borrow_mut();
+        for monitor in monitors.drain(..) {
+            monitor.cancel();
+        }
+
+        if !enabled {
+            return;
+        }
+
+        for path in [user_css_path(), layout_path()].into_iter().flatten() {
+            // Monitoring works for files that don't exist yet, so they can be created later
+            let file = gio::File::for_path(&path);
+            match file.monitor_file(gio::FileMonitorFlags::WATCH_MOVES, gio::Cancellable::NONE) {
+                Ok(monitor) => {
+                    let settings = settings.clone();
+                    monitor.connect_changed(move |_, _, _, event| {
+                        // Editors that save by renaming a temp file report a move instead of a change
+                        if matches!(
+                            event,
+                            gio::FileMonitorEvent::ChangesDoneHint
+                                | gio::FileMonitorEvent::Created
+                                | gio::FileMonitorEvent::Deleted
+                                | gio::FileMonitorEvent::MovedIn
+                                | gio::FileMonitorEvent::Renamed
+                        ) {
+                            reload(&settings.borrow());
+                        }
+                    });
+                    monitors.push(monitor);
+                }
+                Err(e) => tracing::warn!("Failed to watch {}: {}", path.display(), e),
+            }
+        }
+
+        // Pick up edits made while live reload was off
+        reload(&settings.borrow());
+    });
+}
+
+/// Multiplier applied to paddings for a density
+fn density_factor(density: Density) -> f64 {
+    match density {
+        Density::Compact => 0.6,
+        Density::Comfortable => 1.0,
+    }
+}
+
+/// Build the stylesheet: font sizes follow the text scale, paddings the density and
+/// layout spacing, and unstyled text is scaled from the system font size
+fn generate_css(settings: &Settings, layout: &LayoutConfig) -> String {
+    let font_scale = settings.font_scale.clamp(0.5, 3.0);
+    let padding_factor = density_factor(settings.density) * layout.spacing_scale.clamp(0.0, 4.0);
+
+    let mut css = format!("window {{ font-size: {:.0}%; }}\n", font_scale * 100.0);
+
+    for line in BASE_CSS.lines() {
+        let property = line.trim_start();
+        if property.starts_with("font-size:") {
+            css.push_str(&scale_px_values(line, font_scale));
+        } else if property.starts_with("padding") {
+            css.push_str(&scale_px_values(line, padding_factor));
+        } else {
+            css.push_str(line);
+        }
+        css.push('\n');
+    }
+
+    // Small thumbnails keep the layout's height in proportion
+    let thumbnail_height = match settings.thumbnail_size {
+        ThumbnailSize::Small => layout.thumbnail_height * 3 / 5,
+        ThumbnailSize::Large | ThumbnailSize::Off => layout.thumbnail_height,
+    };
+
+    // Sizes from the layout config come last so they override the base rules
+    css.push_str(&format!(
+        ".deck-column {{ min-width: {}px; }}\n\
+         .deck-column.collapsed {{ min-width: 0; }}\n\
+         .article-thumbnail {{ min-height: {}px; }}\n\
+         .news-article-card {{ border-radius: {}px; }}\n",
+        layout.column_width, thumbnail_height, layout.card_radius,
+    ));
+
+    css
+}
+
+/// Multiply every `<number>px` value in a declaration by `factor`
+fn scale_px_values(line: &str, factor: f64) -> String {
+    line.split(' ')
+        .map(|token| {
+            let (value, suffix) = match token.strip_suffix("px;") {
+                Some(value) => (value, "px;"),
+                None => match token.strip_suffix("px") {
+                    Some(value) => (value, "px"),
+                    None => return token.to_string(),
+                },
+            };
+
+            match value.parse::<f64>() {
+                Ok(number) => format!("{}{}", (number * factor * 10.0).round() / 10.0, suffix),
+                Err(_) => token.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,241 @@
+use gtk::prelude::*;
+use gtk::{glib, Label, Orientation};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::data::GdeltArticle;
+use crate::error::{get_json, FetchError};
+use crate::settings::Settings;
+
+const SERVICE: &str = "Summaries";
+
+/// Article text sent for summarizing; the opening carries most news stories
+const MAX_ARTICLE_CHARS: usize = 6_000;
+/// Headlines a country digest is written from
+const MAX_DIGEST_HEADLINES: usize = 30;
+const MAX_SUMMARY_TOKENS: u32 = 200;
+/// Local models on modest hardware take a while
+const TIMEOUT: Duration = Duration::from_secs(90);
+
+const SYSTEM_PROMPT: &str = "You summarize news for a reader skimming many stories. Answer with exactly two plain sentences, no preamble, no lists and no markdown.";
+
+thread_local! {
+    /// App settings, registered at startup so summary buttons can read the current server
+    static SETTINGS: RefCell<Option<Rc<RefCell<Settings>>>> = const { RefCell::new(None) };
+    /// Summaries already written this session, by article URL or country
+    static CACHE: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+/// Make the app settings available to summary buttons
+pub fn register_settings(settings: Rc<RefCell<Settings>>) {
+    SETTINGS.with(|current| *current.borrow_mut() = Some(settings));
+}
+
+/// Snapshot of the summary settings, cheap to move into async tasks
+#[derive(Debug, Clone)]
+struct SummaryConfig {
+    server_url: String,
+    model: String,
+    api_key: String,
+}
+
+fn current_config() -> Option<SummaryConfig> {
+    SETTINGS.with(|current| {
+        current.borrow().as_ref().map(|settings| {
+            let settings = settings.borrow();
+            SummaryConfig {
+                server_url: settings.summary_server_url.trim().to_string(),
+                model: settings.summary_model.trim().to_string(),
+                api_key: settings.summary_api_key.trim().to_string(),
+            }
+        })
+    })
+}
+
+/// Whether a summary server is set up
+pub fn is_configured() -> bool {
+    current_config().is_some_and(|config| !config.server_url.is_empty())
+}
+
+/// What to summarize: one article, or a country's coverage from its headlines
+#[derive(Debug, Clone)]
+pub enum SummaryRequest {
+    Article { title: String, url: String },
+    Country { country: String, articles: Vec<GdeltArticle> },
+}
+
+impl SummaryRequest {
+    fn cache_key(&self) -> String {
+        match self {
+            SummaryRequest::Article { url, .. } => url.clone(),
+            SummaryRequest::Country { country, articles } => format!("country:{}:{}", country, articles.len()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    #[serde(skip_serializing_if = "str::is_empty")]
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    temperature: f32,
+    max_tokens: u32,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatResponseMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatResponseMessage {
+    content: String,
+}
+
+/// Ask the chat completions endpoint for a reply to one prompt
+async fn complete(prompt: &str, config: &SummaryConfig) -> Result<String, FetchError> {
+    if config.server_url.is_empty() {
+        return Err(FetchError::Empty { service: SERVICE });
+    }
+
+    let url = format!("{}/chat/completions", config.server_url.trim_end_matches('/'));
+    let mut request = crate::http::client()
+        .post(url)
+        .timeout(TIMEOUT)
+        .json(&ChatRequest {
+            model: &config.model,
+            messages: vec![
+                ChatMessage { role: "system", content: SYSTEM_PROMPT },
+                ChatMessage { role: "user", content: prompt },
+            ],
+            temperature: 0.2,
+            max_tokens: MAX_SUMMARY_TOKENS,
+        });
+    if !config.api_key.is_empty() {
+        request = request.bearer_auth(&config.api_key);
+    }
+
+    let response: ChatResponse = get_json(SERVICE, request).await?;
+    response
+        .choices
+        .into_iter()
+        .next()
+        .map(|choice| choice.message.content.trim().to_string())
+        .filter(|summary| !summary.is_empty())
+        .ok_or(FetchError::Empty { service: SERVICE })
+}
+
+async fn summarize(request: &SummaryRequest, config: &SummaryConfig) -> Result<String, FetchError> {
+    let prompt = match request {
+        SummaryRequest::Article { title, url } => {
+            let text: String = crate::reader::fetch_article_text(url)
+                .await?
+                .join("\n\n")
+                .chars()
+                .take(MAX_ARTICLE_CHARS)
+                .collect();
+            format!("Summarize this article in two sentences.\n\nTitle: {}\n\n{}", title, text)
+        }
+        SummaryRequest::Country { country, articles } => {
+            let headlines: Vec<String> = articles
+                .iter()
+                .take(MAX_DIGEST_HEADLINES)
+                .map(|article| format!("- {} ({})", article.title, article.domain))
+                .collect();
+            format!(
+                "These are recent news headlines from {}. Write a two-sentence digest of what is happening there.\n\n{}",
+                country,
+                headlines.join("\n")
+            )
+        }
+    };
+    complete(&prompt, config).await
+}
+
+/// "Summarize" button that writes a two-sentence summary below itself,
+/// or nothing when no summary server is set up
+pub fn create_summary_section(request: SummaryRequest) -> Option<gtk::Box> {
+    if !is_configured() {
+        return None;
+    }
+
+    let section = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .build();
+    section.add_css_class("summary-section");
+
+    let label = match request {
+        SummaryRequest::Article { .. } => "Summarize",
+        SummaryRequest::Country { .. } => "Summarize Coverage",
+    };
+    let button = gtk::Button::builder()
+        .label(label)
+        .halign(gtk::Align::Start)
+        .build();
+    button.add_css_class("flat");
+    section.append(&button);
+
+    let summary_label = Label::builder()
+        .xalign(0.0)
+        .wrap(true)
+        .wrap_mode(gtk::pango::WrapMode::WordChar)
+        .selectable(true)
+        .visible(false)
+        .build();
+    summary_label.add_css_class("summary-text");
+    section.append(&summary_label);
+
+    let key = request.cache_key();
+    if let Some(summary) = CACHE.with(|cache| cache.borrow().get(&key).cloned()) {
+        summary_label.set_label(&summary);
+        summary_label.set_visible(true);
+        button.set_visible(false);
+        return Some(section);
+    }
+
+    button.connect_clicked(move |button| {
+        let Some(config) = current_config() else {
+            return;
+        };
+        button.set_sensitive(false);
+        button.set_label("Summarizing…");
+
+        let request = request.clone();
+        let key = key.clone();
+        let button = button.clone();
+        let summary_label = summary_label.clone();
+        glib::spawn_future_local(async move {
+            match summarize(&request, &config).await {
+                Ok(summary) => {
+                    summary_label.set_label(&summary);
+                    summary_label.set_visible(true);
+                    button.set_visible(false);
+                    CACHE.with(|cache| cache.borrow_mut().insert(key, summary));
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to summarize: {}", e);
+                    button.set_label(label);
+                    button.set_tooltip_text(Some(&format!("{}, click to retry", e)));
+                    button.set_sensitive(true);
+                }
+            }
+        });
+    });
+
+    Some(section)
+}
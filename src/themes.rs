@@ -0,0 +1,141 @@
+use gtk::prelude::*;
+
+use crate::country_mentions::contains_term;
+
+/// Badges shown per article, so cards stay one row tall
+const MAX_THEMES: usize = 2;
+const MAX_ENTITIES: usize = 2;
+
+/// GKG theme codes with the lowercase headline terms that suggest them.
+/// The DOC API doesn't return themes per article, but it does filter by them
+/// with the `theme:` operator, so a badge's search is exact even when the
+/// match on the headline is a guess.
+const THEMES: &[(&str, &str, &[&str])] = &[
+    ("ELECTION", "Elections", &["election", "elections", "vote", "voters", "ballot", "polls", "runoff"]),
+    ("PROTEST", "Protests", &["protest", "protests", "protesters", "demonstration", "demonstrators", "rally"]),
+    ("TERROR", "Terrorism", &["terror", "terrorist", "terrorism", "militant", "militants", "extremist"]),
+    ("ARMEDCONFLICT", "Armed conflict", &["war", "airstrike", "airstrikes", "offensive", "shelling", "troops", "ceasefire"]),
+    ("MILITARY", "Military", &["military", "army", "navy", "defence", "defense", "missile", "missiles"]),
+    ("KILL", "Killings", &["killed", "dead", "death toll", "massacre", "murder"]),
+    ("ARREST", "Arrests", &["arrest", "arrested", "detained", "custody"]),
+    ("CORRUPTION", "Corruption", &["corruption", "bribery", "bribe", "embezzlement", "fraud"]),
+    ("LEGISLATION", "Legislation", &["bill", "law", "parliament", "congress", "senate", "legislation"]),
+    ("ECON_INFLATION", "Inflation", &["inflation", "prices", "cost of living"]),
+    ("ECON_STOCKMARKET", "Markets", &["stocks", "shares", "stock market", "nasdaq", "dow", "ftse"]),
+    ("ECON_INTEREST_RATES", "Interest rates", &["interest rate", "interest rates", "rate cut", "rate hike", "central bank"]),
+    ("ENV_CLIMATECHANGE", "Climate change", &["climate", "emissions", "carbon", "global warming"]),
+    ("NATURAL_DISASTER", "Natural disasters", &["earthquake", "flood", "floods", "hurricane", "typhoon", "cyclone", "wildfire", "tsunami"]),
+    ("HEALTH_PANDEMIC", "Epidemics", &["pandemic", "outbreak", "epidemic", "virus", "cholera", "measles"]),
+    ("REFUGEES", "Refugees", &["refugee", "refugees", "asylum", "migrants", "displaced"]),
+    ("CYBER_ATTACK", "Cyber attacks", &["cyberattack", "hack", "hackers", "ransomware", "data breach"]),
+    ("FOOD_SECURITY", "Food security", &["famine", "hunger", "food shortage", "food crisis"]),
+    ("ENERGY", "Energy", &["oil", "gas", "energy", "pipeline", "opec"]),
+];
+
+/// Capitalized words that start headlines or phrases without naming anything
+const NOT_ENTITIES: &[&str] = &[
+    "A", "An", "The", "In", "On", "At", "As", "After", "Before", "How", "Why", "What", "When",
+    "Who", "Watch", "Live", "Breaking", "Update", "Opinion", "Analysis", "Exclusive", "Video",
+];
+
+/// A GKG theme a headline seems to be about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub code: &'static str,
+    pub label: &'static str,
+}
+
+/// Themes whose terms appear in a headline, in table order
+pub fn themes_for(title: &str) -> Vec<Theme> {
+    let title = title.to_lowercase();
+    THEMES
+        .iter()
+        .filter(|(_, _, terms)| terms.iter().any(|term| contains_term(&title, term)))
+        .map(|&(code, label, _)| Theme { code, label })
+        .take(MAX_THEMES)
+        .collect()
+}
+
+/// Runs of two or more capitalized words, e.g. "European Central Bank".
+/// Headlines in title case capitalize everything, so they yield nothing.
+pub fn entities_for(title: &str) -> Vec<String> {
+    let words: Vec<&str> = title.split_whitespace().collect();
+    let capitalized = |word: &&str| word.chars().next().is_some_and(char::is_uppercase);
+    if words.len() < 3 || words.iter().filter(|w| capitalized(w)).count() * 3 > words.len() * 2 {
+        return Vec::new();
+    }
+
+    let mut entities: Vec<String> = Vec::new();
+    let mut run: Vec<&str> = Vec::new();
+    for word in words.iter().chain(std::iter::once(&"")) {
+        let cleaned = word.trim_matches(|c: char| !c.is_alphanumeric());
+        let ends_phrase = word.ends_with([',', ':', ';', '.', '?', '!']);
+        let continues = !cleaned.is_empty()
+            && capitalized(&cleaned)
+            && !(run.is_empty() && NOT_ENTITIES.contains(&cleaned));
+        if continues {
+            run.push(cleaned);
+        }
+        if !continues || ends_phrase {
+            if run.len() >= 2 {
+                let entity = run.join(" ");
+                if !entities.contains(&entity) {
+                    entities.push(entity);
+                }
+            }
+            run.clear();
+        }
+    }
+
+    entities.truncate(MAX_ENTITIES);
+    entities
+}
+
+fn create_search_badge(label: &str, tooltip: &str, css_class: &str, query: String) -> gtk::Button {
+    let button = gtk::Button::builder()
+        .label(label)
+        .tooltip_text(tooltip)
+        .build();
+    button.add_css_class("badge");
+    button.add_css_class(css_class);
+    button.connect_clicked(move |button| {
+        if let Err(e) = button.activate_action("app.search", Some(&query.to_variant())) {
+            tracing::warn!("Failed to search for {}: {}", query, e);
+        }
+    });
+    button
+}
+
+/// Row of theme and entity badges for a headline; clicking one searches GDELT
+/// for that theme or name. None when the headline yields neither.
+pub fn create_badges(title: &str) -> Option<gtk::Box> {
+    let themes = themes_for(title);
+    let entities = entities_for(title);
+    if themes.is_empty() && entities.is_empty() {
+        return None;
+    }
+
+    let badges = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(4)
+        .build();
+
+    for theme in themes {
+        badges.append(&create_search_badge(
+            theme.label,
+            &format!("Search articles with the GDELT theme {}", theme.code),
+            "badge-theme",
+            format!("theme:{}", theme.code),
+        ));
+    }
+    for entity in entities {
+        badges.append(&create_search_badge(
+            &entity,
+            &format!("Search articles mentioning {}", entity),
+            "badge-entity",
+            format!("\"{}\"", entity),
+        ));
+    }
+
+    Some(badges)
+}
@@ -0,0 +1,264 @@
+use gtk::prelude::*;
+use gtk::{gdk, gio, glib};
+use libshumate::subclass::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use crate::error::{get_bytes, FetchError};
+use crate::settings::Settings;
+
+const SERVICE: &str = "Tile server";
+
+const CARTO_LICENSE: &str = "© OpenStreetMap contributors © CARTO";
+const OSM_LICENSE: &str = "© OpenStreetMap contributors";
+const OSM_LICENSE_URI: &str = "https://www.openstreetmap.org/copyright";
+
+const TILE_SIZE: u32 = 256;
+
+/// Where the Global Affairs map's tiles come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MapTiles {
+    /// CartoDB Dark Matter or Positron, whichever matches the style
+    Carto,
+    CartoDark,
+    CartoLight,
+    #[serde(rename = "osm")]
+    OpenStreetMap,
+    /// A URL template from preferences, optionally with an API key
+    Custom,
+}
+
+impl MapTiles {
+    pub const ALL: [MapTiles; 5] = [
+        MapTiles::Carto,
+        MapTiles::CartoDark,
+        MapTiles::CartoLight,
+        MapTiles::OpenStreetMap,
+        MapTiles::Custom,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MapTiles::Carto => "CartoDB, matching the style",
+            MapTiles::CartoDark => "CartoDB Dark Matter",
+            MapTiles::CartoLight => "CartoDB Positron",
+            MapTiles::OpenStreetMap => "OpenStreetMap",
+            MapTiles::Custom => "Custom",
+        }
+    }
+}
+
+/// Snapshot of the tile settings, cheap to move into async tasks
+#[derive(Debug, Clone)]
+pub struct TileConfig {
+    pub tiles: MapTiles,
+    pub url_template: String,
+    pub api_key_header: String,
+    pub api_key: String,
+}
+
+impl TileConfig {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            tiles: settings.map_tiles,
+            url_template: settings.tile_url_template.trim().to_string(),
+            api_key_header: settings.tile_api_key_header.trim().to_string(),
+            api_key: settings.tile_api_key.trim().to_string(),
+        }
+    }
+}
+
+thread_local! {
+    /// App settings, registered at startup so the map can read its tile source
+    static SETTINGS: RefCell<Option<Rc<RefCell<Settings>>>> = const { RefCell::new(None) };
+    /// The map whose tiles are switched when the source or style changes
+    static MAP: RefCell<Option<glib::WeakRef<libshumate::SimpleMap>>> = const { RefCell::new(None) };
+}
+
+/// Make the app settings available to the map
+pub fn register_settings(settings: Rc<RefCell<Settings>>) {
+    SETTINGS.with(|current| *current.borrow_mut() = Some(settings));
+}
+
+fn current_config() -> Option<TileConfig> {
+    SETTINGS.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .map(|settings| TileConfig::from_settings(&settings.borrow()))
+    })
+}
+
+/// Draw a map with the configured tiles, switching Carto's with the light and dark styles
+pub fn attach(map: &libshumate::SimpleMap) {
+    MAP.with(|current| *current.borrow_mut() = Some(map.downgrade()));
+    reload();
+
+    libadwaita::StyleManager::default().connect_dark_notify(|_| {
+        if current_config().is_some_and(|config| config.tiles == MapTiles::Carto) {
+            reload();
+        }
+    });
+}
+
+/// Switch the map to the tile source currently in the settings
+pub fn reload() {
+    let Some(map) = MAP.with(|current| current.borrow().as_ref().and_then(|map| map.upgrade())) else {
+        return;
+    };
+    let config = current_config().unwrap_or_else(|| TileConfig::from_settings(&Settings::default()));
+    map.set_map_source(Some(&create_map_source(&config)));
+}
+
+fn carto_source(dark: bool) -> libshumate::RasterRenderer {
+    let (id, name, style) = if dark {
+        ("carto-dark", "CartoDB Dark Matter", "dark_all")
+    } else {
+        ("carto-light", "CartoDB Positron", "light_all")
+    };
+    libshumate::RasterRenderer::new_full_from_url(
+        id,
+        name,
+        CARTO_LICENSE,
+        OSM_LICENSE_URI,
+        0,
+        20,
+        TILE_SIZE,
+        libshumate::MapProjection::Mercator,
+        &format!("https://a.basemaps.cartocdn.com/{}/{{z}}/{{x}}/{{y}}.png", style),
+    )
+}
+
+fn create_map_source(config: &TileConfig) -> libshumate::RasterRenderer {
+    match config.tiles {
+        MapTiles::Carto => carto_source(libadwaita::StyleManager::default().is_dark()),
+        MapTiles::CartoDark => carto_source(true),
+        MapTiles::CartoLight => carto_source(false),
+        MapTiles::OpenStreetMap => libshumate::RasterRenderer::new_full_from_url(
+            "osm-mapnik",
+            "OpenStreetMap",
+            OSM_LICENSE,
+            OSM_LICENSE_URI,
+            0,
+            19,
+            TILE_SIZE,
+            libshumate::MapProjection::Mercator,
+            "https://tile.openstreetmap.org/{z}/{x}/{y}.png",
+        ),
+        // A template that was never tested falls back to the default tiles
+        MapTiles::Custom if validate_template(&config.url_template).is_err() => carto_source(true),
+        MapTiles::Custom if config.api_key.is_empty() => libshumate::RasterRenderer::from_url(&config.url_template),
+        MapTiles::Custom => libshumate::RasterRenderer::new_full(
+            "custom",
+            "Custom",
+            "",
+            "",
+            0,
+            20,
+            TILE_SIZE,
+            libshumate::MapProjection::Mercator,
+            &KeyedTileSource::new(config),
+        ),
+    }
+}
+
+/// Check a custom URL template has the placeholders tiles are addressed by
+pub fn validate_template(template: &str) -> Result<(), String> {
+    if !template.starts_with("https://") && !template.starts_with("http://") {
+        return Err("The tile URL has to start with http:// or https://".to_string());
+    }
+    let missing: Vec<&str> = ["{z}", "{x}", "{y}"]
+        .into_iter()
+        .filter(|placeholder| !template.contains(placeholder))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("The tile URL is missing {}", missing.join(", ")));
+    }
+    Ok(())
+}
+
+fn tile_url(template: &str, x: i32, y: i32, zoom_level: i32) -> String {
+    template
+        .replace("{z}", &zoom_level.to_string())
+        .replace("{x}", &x.to_string())
+        .replace("{y}", &y.to_string())
+}
+
+async fn fetch_tile(config: &TileConfig, x: i32, y: i32, zoom_level: i32) -> Result<Vec<u8>, FetchError> {
+    let mut request = crate::http::client().get(tile_url(&config.url_template, x, y, zoom_level));
+    if !config.api_key.is_empty() {
+        request = request.header(config.api_key_header.as_str(), config.api_key.as_str());
+    }
+    get_bytes(SERVICE, request).await
+}
+
+/// Fetch the whole-world tile from a custom server to check the template and
+/// key work and that it sends images the map can draw
+pub async fn test_custom(config: &TileConfig) -> Result<(), String> {
+    validate_template(&config.url_template)?;
+    if !config.api_key.is_empty() && reqwest::header::HeaderName::from_bytes(config.api_key_header.as_bytes()).is_err() {
+        return Err(format!("\"{}\" isn't a valid header name", config.api_key_header));
+    }
+
+    let bytes = fetch_tile(config, 0, 0, 0).await.map_err(|e| e.to_string())?;
+    gdk::Texture::from_bytes(&glib::Bytes::from_owned(bytes))
+        .map(|_| ())
+        .map_err(|_| "The tile server didn't send an image Grapevine can draw".to_string())
+}
+
+mod imp {
+    use super::*;
+
+    /// Tile data source that sends an API key header with every request,
+    /// which libshumate's own downloader can't
+    #[derive(Default)]
+    pub struct KeyedTileSource {
+        pub config: RefCell<Option<TileConfig>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for KeyedTileSource {
+        const NAME: &'static str = "GrapevineKeyedTileSource";
+        type Type = super::KeyedTileSource;
+        type ParentType = libshumate::DataSource;
+    }
+
+    impl ObjectImpl for KeyedTileSource {}
+
+    impl DataSourceImpl for KeyedTileSource {
+        fn tile_data_future(
+            &self,
+            x: i32,
+            y: i32,
+            zoom_level: i32,
+        ) -> Pin<Box<dyn Future<Output = Result<glib::Bytes, glib::Error>> + 'static>> {
+            let config = self.config.borrow().clone();
+            Box::pin(async move {
+                let Some(config) = config else {
+                    return Err(glib::Error::new(gio::IOErrorEnum::NotInitialized, "No tile server configured"));
+                };
+                fetch_tile(&config, x, y, zoom_level)
+                    .await
+                    .map(glib::Bytes::from_owned)
+                    .map_err(|e| glib::Error::new(gio::IOErrorEnum::Failed, &e.to_string()))
+            })
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct KeyedTileSource(ObjectSubclass<imp::KeyedTileSource>)
+        @extends libshumate::DataSource;
+}
+
+impl KeyedTileSource {
+    fn new(config: &TileConfig) -> Self {
+        let source: Self = glib::Object::new();
+        *source.imp().config.borrow_mut() = Some(config.clone());
+        source
+    }
+}
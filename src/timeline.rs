@@ -0,0 +1,546 @@
+use gtk::prelude::*;
+use gtk::{cairo, gdk, gio, glib, Label, Orientation};
+use chrono::{NaiveDate, NaiveDateTime};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::data::FrankfurterHistoricalResponse;
+use crate::error::{get_json, FetchError};
+use crate::gdelt::{self, GdeltQuery, GdeltSort, Timespan};
+use crate::settings::WatchedPair;
+
+/// How far back the timeline reaches
+const DAYS: u32 = 7;
+/// Daily currency moves at least this large, in percent, are annotated
+const MOVE_THRESHOLD: f64 = 1.0;
+/// Most currency pairs looked up for one timeline
+const MAX_PAIRS: usize = 4;
+/// Most spikes annotated, the largest first
+const MAX_SPIKES: usize = 5;
+/// Standard deviations above the mean a point needs to count as a spike
+const SPIKE_SIGMA: f64 = 2.0;
+
+/// Chart geometry in pixels; the chart grows wider with the number of points
+const POINT_WIDTH: f64 = 4.0;
+const MIN_WIDTH: f64 = 640.0;
+const CHART_HEIGHT: f64 = 280.0;
+const MARGIN_SIDE: f64 = 16.0;
+/// Room above the plot for three rows of annotations
+const MARGIN_TOP: f64 = 60.0;
+/// Room below the plot for day labels
+const MARGIN_BOTTOM: f64 = 28.0;
+const LABEL_ROW_HEIGHT: f64 = 16.0;
+const LABEL_ROWS: usize = 3;
+
+const VOLUME_COLOR: (f64, f64, f64) = (0.21, 0.52, 0.89);
+const FIRST_SEEN_COLOR: (f64, f64, f64) = (0.15, 0.64, 0.41);
+const SPIKE_COLOR: (f64, f64, f64) = (0.88, 0.11, 0.14);
+const MOVE_COLOR: (f64, f64, f64) = (0.90, 0.45, 0.0);
+
+/// A day a currency pair moved by more than `MOVE_THRESHOLD`
+struct CurrencyMove {
+    at: NaiveDateTime,
+    pair: String,
+    change: f64,
+}
+
+/// Everything drawn on a timeline
+struct Coverage {
+    /// Share of global coverage over time, oldest first
+    volume: Vec<(NaiveDateTime, f64)>,
+    /// When and where the story was first reported within the window
+    first_seen: Option<(NaiveDateTime, String)>,
+    moves: Vec<CurrencyMove>,
+}
+
+impl Coverage {
+    /// Indices of the largest local peaks well above the average
+    fn spikes(&self) -> Vec<usize> {
+        let count = self.volume.len();
+        if count < 3 {
+            return Vec::new();
+        }
+        let mean = self.volume.iter().map(|(_, value)| value).sum::<f64>() / count as f64;
+        let variance = self.volume.iter().map(|(_, value)| (value - mean).powi(2)).sum::<f64>() / count as f64;
+        let threshold = mean + SPIKE_SIGMA * variance.sqrt();
+
+        let mut spikes: Vec<usize> = (0..count)
+            .filter(|&i| {
+                let value = self.volume[i].1;
+                value > threshold
+                    && (i == 0 || value >= self.volume[i - 1].1)
+                    && (i + 1 == count || value > self.volume[i + 1].1)
+            })
+            .collect();
+        spikes.sort_by(|a, b| self.volume[*b].1.total_cmp(&self.volume[*a].1));
+        spikes.truncate(MAX_SPIKES);
+        spikes
+    }
+
+    fn width(&self) -> f64 {
+        (self.volume.len() as f64 * POINT_WIDTH + 2.0 * MARGIN_SIDE).max(MIN_WIDTH)
+    }
+}
+
+/// Currency pairs worth annotating: the country's currency against the dollar,
+/// then the Markets watchlist
+pub fn related_pairs(country: Option<&str>, watchlist: &[WatchedPair]) -> Vec<(String, String)> {
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    if let Some(currency) = country.and_then(crate::coordinates::get_country_currency) {
+        // Same as the country popovers: the dollar is shown against the euro
+        let base = if currency == "USD" { "EUR" } else { currency };
+        pairs.push((base.to_string(), "USD".to_string()));
+    }
+    for pair in watchlist {
+        let pair = (pair.base.clone(), pair.target.clone());
+        if !pairs.contains(&pair) {
+            pairs.push(pair);
+        }
+    }
+    pairs.truncate(MAX_PAIRS);
+    pairs
+}
+
+fn parse_gdelt_time(date: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(date, "%Y%m%dT%H%M%SZ").ok()
+}
+
+async fn fetch_volume(query: &str) -> Result<Vec<(NaiveDateTime, f64)>, FetchError> {
+    gdelt::fetch_timeline(&GdeltQuery::new(query).timespan(Timespan::days(DAYS))).await
+}
+
+/// The oldest matching article in the window
+async fn fetch_first_seen(query: &str) -> Result<Option<(NaiveDateTime, String)>, FetchError> {
+    let url = GdeltQuery::new(query)
+        .timespan(Timespan::days(DAYS))
+        .sort(GdeltSort::DateAsc)
+        .max_records(1)
+        .url()?;
+    let articles = gdelt::fetch_articles(&url).await?;
+    Ok(articles
+        .into_iter()
+        .next()
+        .and_then(|article| parse_gdelt_time(&article.seendate).map(|date| (date, article.domain))))
+}
+
+/// Days in the window a pair moved by more than the threshold
+async fn fetch_moves(base: &str, target: &str) -> Result<Vec<CurrencyMove>, FetchError> {
+    let today = chrono::Utc::now().date_naive();
+    // A day more than the window, so its first day has a previous rate to compare with
+    let start = today - chrono::Duration::days(DAYS as i64 + 1);
+    let url = format!(
+        "https://api.frankfurter.dev/v1/{}..{}?from={}&to={}",
+        start.format("%Y-%m-%d"),
+        today.format("%Y-%m-%d"),
+        base,
+        target
+    );
+    let data: FrankfurterHistoricalResponse = get_json("Frankfurter", crate::http::client().get(&url)).await?;
+
+    let mut rates: Vec<(NaiveDate, f64)> = data
+        .rates
+        .iter()
+        .filter_map(|(date, rates)| {
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+            Some((date, *rates.rates.get(target)?))
+        })
+        .collect();
+    rates.sort_by_key(|(date, _)| *date);
+
+    let pair = format!("{}/{}", base, target);
+    Ok(rates
+        .windows(2)
+        .filter_map(|window| {
+            let ((_, previous), (date, rate)) = (window[0], window[1]);
+            let change = (rate - previous) / previous * 100.0;
+            // Reference rates are set around 14:00 UTC
+            (change.abs() >= MOVE_THRESHOLD).then(|| CurrencyMove {
+                at: date.and_hms_opt(14, 0, 0).unwrap_or_default(),
+                pair: pair.clone(),
+                change,
+            })
+        })
+        .collect())
+}
+
+/// Fetch the coverage volume for a search along with its annotations; only the
+/// volume is required, first coverage and currency moves are left out if they fail
+async fn fetch_coverage(query: &str, pairs: &[(String, String)]) -> Result<Coverage, FetchError> {
+    let volume = fetch_volume(query).await?;
+    if volume.is_empty() {
+        return Err(FetchError::Empty { service: gdelt::SERVICE });
+    }
+
+    let first_seen = fetch_first_seen(query).await.unwrap_or_else(|e| {
+        tracing::warn!("No first coverage for '{}': {}", query, e);
+        None
+    });
+
+    let mut moves = Vec::new();
+    for (base, target) in pairs {
+        match fetch_moves(base, target).await {
+            Ok(pair_moves) => moves.extend(pair_moves),
+            Err(e) => tracing::warn!("No {}/{} moves for the timeline: {}", base, target, e),
+        }
+    }
+
+    Ok(Coverage { volume, first_seen, moves })
+}
+
+/// Horizontal position of a time on the chart, or None outside the plotted range
+fn x_for(coverage: &Coverage, width: f64, at: NaiveDateTime) -> Option<f64> {
+    let start = coverage.volume.first()?.0;
+    let end = coverage.volume.last()?.0;
+    if at < start || at > end {
+        return None;
+    }
+    let span = (end - start).num_seconds().max(1) as f64;
+    let offset = (at - start).num_seconds() as f64;
+    Some(MARGIN_SIDE + offset / span * (width - 2.0 * MARGIN_SIDE))
+}
+
+/// A labelled marker on the chart
+struct Annotation {
+    x: f64,
+    label: String,
+    color: (f64, f64, f64),
+    /// Height of the point marked on the volume line, for spikes
+    point: Option<f64>,
+}
+
+/// Rows of annotation labels, placed so neighbouring labels don't overlap
+struct LabelRows {
+    /// Right edge of the last label in each row
+    ends: [f64; LABEL_ROWS],
+}
+
+impl LabelRows {
+    /// Reserve room for a label starting at x and return its row's baseline
+    fn place(&mut self, cr: &cairo::Context, x: f64, text: &str) -> f64 {
+        let width = cr.text_extents(text).map(|extents| extents.x_advance()).unwrap_or(0.0) + 3.0;
+        let row = self.ends.iter().position(|end| *end + 8.0 <= x).unwrap_or(LABEL_ROWS - 1);
+        self.ends[row] = x + width;
+        14.0 + row as f64 * LABEL_ROW_HEIGHT
+    }
+}
+
+fn set_color(cr: &cairo::Context, (red, green, blue): (f64, f64, f64), alpha: f64) {
+    cr.set_source_rgba(red, green, blue, alpha);
+}
+
+/// Draw the volume, day labels and annotations; `foreground` is used for text
+fn draw(cr: &cairo::Context, coverage: &Coverage, width: f64, height: f64, foreground: &gdk::RGBA) {
+    let plot_bottom = height - MARGIN_BOTTOM;
+    let plot_height = plot_bottom - MARGIN_TOP;
+    let max = coverage.volume.iter().map(|(_, value)| *value).fold(0.0, f64::max).max(f64::EPSILON);
+    let y_for = |value: f64| plot_bottom - value / max * plot_height;
+    let points: Vec<(f64, f64)> = coverage
+        .volume
+        .iter()
+        .filter_map(|(at, value)| x_for(coverage, width, *at).map(|x| (x, y_for(*value))))
+        .collect();
+    let Some(&(first_x, _)) = points.first() else {
+        return;
+    };
+    let last_x = points.last().map(|(x, _)| *x).unwrap_or(first_x);
+
+    cr.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Normal);
+    cr.set_font_size(11.0);
+
+    // Midnight gridlines with the day beneath
+    let text_color = |alpha: f64| {
+        cr.set_source_rgba(
+            foreground.red() as f64,
+            foreground.green() as f64,
+            foreground.blue() as f64,
+            alpha,
+        )
+    };
+    let mut day = coverage.volume[0].0.date().succ_opt();
+    while let Some(date) = day {
+        let Some(x) = date.and_hms_opt(0, 0, 0).and_then(|midnight| x_for(coverage, width, midnight)) else {
+            break;
+        };
+        text_color(0.12);
+        cr.set_line_width(1.0);
+        cr.move_to(x, MARGIN_TOP);
+        cr.line_to(x, plot_bottom);
+        let _ = cr.stroke();
+        text_color(0.6);
+        cr.move_to(x + 4.0, height - 10.0);
+        let _ = cr.show_text(&date.format("%a %b %-d").to_string());
+        day = date.succ_opt();
+    }
+
+    // Coverage volume as a filled line
+    cr.move_to(first_x, plot_bottom);
+    for (x, y) in &points {
+        cr.line_to(*x, *y);
+    }
+    cr.line_to(last_x, plot_bottom);
+    cr.close_path();
+    set_color(cr, VOLUME_COLOR, 0.2);
+    let _ = cr.fill();
+
+    cr.move_to(first_x, points[0].1);
+    for (x, y) in &points[1..] {
+        cr.line_to(*x, *y);
+    }
+    set_color(cr, VOLUME_COLOR, 1.0);
+    cr.set_line_width(1.5);
+    let _ = cr.stroke();
+
+    let mut rows = LabelRows { ends: [f64::NEG_INFINITY; LABEL_ROWS] };
+    let mut annotations: Vec<Annotation> = Vec::new();
+
+    if let Some((at, domain)) = &coverage.first_seen {
+        if let Some(x) = x_for(coverage, width, *at) {
+            let label = if domain.is_empty() {
+                format!("First seen {}", at.format("%b %-d %H:%M"))
+            } else {
+                format!("First seen {} · {}", at.format("%b %-d %H:%M"), domain)
+            };
+            annotations.push(Annotation { x, label, color: FIRST_SEEN_COLOR, point: None });
+        }
+    }
+    for index in coverage.spikes() {
+        let (at, value) = coverage.volume[index];
+        if let Some(x) = x_for(coverage, width, at) {
+            annotations.push(Annotation {
+                x,
+                label: format!("Spike {}", at.format("%b %-d %H:%M")),
+                color: SPIKE_COLOR,
+                point: Some(y_for(value)),
+            });
+        }
+    }
+    for currency_move in &coverage.moves {
+        if let Some(x) = x_for(coverage, width, currency_move.at) {
+            annotations.push(Annotation {
+                x,
+                label: format!("{} {:+.1}%", currency_move.pair, currency_move.change),
+                color: MOVE_COLOR,
+                point: None,
+            });
+        }
+    }
+    annotations.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+    for Annotation { x, label, color, point } in annotations {
+        let label_y = rows.place(cr, x, &label);
+
+        set_color(cr, color, 0.8);
+        cr.set_line_width(1.0);
+        if point.is_none() {
+            cr.set_dash(&[4.0, 3.0], 0.0);
+        }
+        cr.move_to(x, label_y + 4.0);
+        cr.line_to(x, plot_bottom);
+        let _ = cr.stroke();
+        cr.set_dash(&[], 0.0);
+
+        if let Some(y) = point {
+            cr.arc(x, y, 3.5, 0.0, std::f64::consts::TAU);
+            let _ = cr.fill();
+        }
+
+        set_color(cr, color, 1.0);
+        cr.move_to(x + 3.0, label_y);
+        let _ = cr.show_text(&label);
+    }
+}
+
+/// Render the chart off screen on an opaque background and save it as a PNG
+fn save_png(coverage: &Coverage, path: &std::path::Path) -> Result<(), String> {
+    let width = coverage.width().ceil() as i32;
+    let height = CHART_HEIGHT as i32;
+    let dark = libadwaita::StyleManager::default().is_dark();
+    let (background, foreground) = if dark {
+        ((0.14, 0.14, 0.14), gdk::RGBA::WHITE)
+    } else {
+        ((1.0, 1.0, 1.0), gdk::RGBA::BLACK)
+    };
+
+    let mut surface = cairo::ImageSurface::create(cairo::Format::ARgb32, width, height).map_err(|e| e.to_string())?;
+    {
+        let cr = cairo::Context::new(&surface).map_err(|e| e.to_string())?;
+        set_color(&cr, background, 1.0);
+        let _ = cr.paint();
+        draw(&cr, coverage, width as f64, height as f64, &foreground);
+    }
+    surface.flush();
+
+    let stride = surface.stride() as usize;
+    let data = surface.data().map_err(|e| e.to_string())?;
+    // Cairo's ARGB32 is a native-endian 32-bit word per pixel
+    let format = if cfg!(target_endian = "little") {
+        gdk::MemoryFormat::B8g8r8a8Premultiplied
+    } else {
+        gdk::MemoryFormat::A8r8g8b8Premultiplied
+    };
+    let texture = gdk::MemoryTexture::new(width, height, format, &glib::Bytes::from(&*data), stride);
+    texture.save_to_png(path).map_err(|e| e.to_string())
+}
+
+fn export_png(parent: &gtk::Window, coverage: Rc<Coverage>, suggested_name: &str) {
+    let filters = gio::ListStore::new::<gtk::FileFilter>();
+    let png_filter = gtk::FileFilter::new();
+    png_filter.set_name(Some("PNG Image"));
+    png_filter.add_pattern("*.png");
+    filters.append(&png_filter);
+
+    let dialog = gtk::FileDialog::builder()
+        .title("Export Timeline")
+        .initial_name(suggested_name)
+        .filters(&filters)
+        .modal(true)
+        .build();
+
+    dialog.save(Some(parent), gio::Cancellable::NONE, move |result| {
+        let Ok(file) = result else {
+            return; // Dismissed by the user
+        };
+        let Some(path) = file.path() else {
+            tracing::warn!("Export destination has no local path");
+            return;
+        };
+        match save_png(&coverage, &path) {
+            Ok(()) => {
+                tracing::info!("Exported timeline to {}", path.display());
+                crate::toasts::show("Timeline exported");
+            }
+            Err(e) => {
+                tracing::warn!("Failed to export timeline to {}: {}", path.display(), e);
+                crate::toasts::show("Couldn't export the timeline");
+            }
+        }
+    });
+}
+
+/// Open a window charting a search's coverage over the past week: when it was
+/// first reported, when coverage spiked and which currency pairs moved meanwhile
+pub fn present_timeline(parent: Option<&gtk::Window>, title: &str, query: &str, pairs: Vec<(String, String)>) {
+    let content = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(12)
+        .margin_top(18)
+        .margin_bottom(18)
+        .margin_start(18)
+        .margin_end(18)
+        .build();
+
+    let title_label = Label::builder()
+        .label(title)
+        .xalign(0.0)
+        .wrap(true)
+        .selectable(true)
+        .build();
+    title_label.add_css_class("title-3");
+    content.append(&title_label);
+
+    let legend = Label::builder()
+        .label(format!(
+            "Share of global coverage over the past {} days. First coverage is marked in green, \
+             spikes in red and currency moves of {}% or more in orange.",
+            DAYS, MOVE_THRESHOLD
+        ))
+        .xalign(0.0)
+        .wrap(true)
+        .build();
+    legend.add_css_class("dim-label");
+    content.append(&legend);
+
+    let drawing_area = gtk::DrawingArea::builder()
+        .content_width(MIN_WIDTH as i32)
+        .content_height(CHART_HEIGHT as i32)
+        .build();
+    let coverage: Rc<RefCell<Option<Rc<Coverage>>>> = Rc::new(RefCell::new(None));
+    let coverage_for_draw = coverage.clone();
+    drawing_area.set_draw_func(move |area, cr, width, height| {
+        if let Some(ref coverage) = *coverage_for_draw.borrow() {
+            draw(cr, coverage, width as f64, height as f64, &area.color());
+        }
+    });
+
+    let chart = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Automatic)
+        .vscrollbar_policy(gtk::PolicyType::Never)
+        .child(&drawing_area)
+        .build();
+    chart.add_css_class("card");
+
+    let spinner = gtk::Spinner::builder()
+        .spinning(true)
+        .halign(gtk::Align::Center)
+        .valign(gtk::Align::Center)
+        .build();
+    let error_page = libadwaita::StatusPage::builder()
+        .icon_name("dialog-warning-symbolic")
+        .title("Can't Chart This Story")
+        .build();
+    error_page.add_css_class("compact");
+
+    let stack = gtk::Stack::builder().vexpand(true).build();
+    stack.add_named(&spinner, Some("loading"));
+    stack.add_named(&chart, Some("chart"));
+    stack.add_named(&error_page, Some("error"));
+    content.append(&stack);
+
+    let export_button = gtk::Button::builder()
+        .icon_name("document-save-symbolic")
+        .tooltip_text("Export as Image…")
+        .sensitive(false)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    header_bar.set_title_widget(Some(&libadwaita::WindowTitle::new("Coverage Timeline", query)));
+    header_bar.pack_end(&export_button);
+
+    let toolbar_view = libadwaita::ToolbarView::new();
+    toolbar_view.add_top_bar(&header_bar);
+    toolbar_view.set_content(Some(&content));
+
+    let window = libadwaita::Window::builder()
+        .title("Coverage Timeline")
+        .default_width(900)
+        .default_height(480)
+        .content(&toolbar_view)
+        .build();
+    window.set_transient_for(parent);
+    window.present();
+
+    let suggested_name = format!("grapevine-timeline-{}.png", chrono::Local::now().format("%Y-%m-%d"));
+    let coverage_for_export = coverage.clone();
+    let window_for_export = window.clone();
+    export_button.connect_clicked(move |_| {
+        if let Some(coverage) = coverage_for_export.borrow().clone() {
+            export_png(window_for_export.upcast_ref(), coverage, &suggested_name);
+        }
+    });
+
+    let query = query.to_string();
+    glib::spawn_future_local(async move {
+        match fetch_coverage(&query, &pairs).await {
+            Ok(fetched) => {
+                let fetched = Rc::new(fetched);
+                drawing_area.set_content_width(fetched.width().ceil() as i32);
+                *coverage.borrow_mut() = Some(fetched);
+                drawing_area.queue_draw();
+                export_button.set_sensitive(true);
+                stack.set_visible_child_name("chart");
+
+                // Start at the latest coverage, at the right-hand end
+                glib::idle_add_local_once(move || {
+                    let adjustment = chart.hadjustment();
+                    adjustment.set_value(adjustment.upper() - adjustment.page_size());
+                });
+            }
+            Err(e) => {
+                tracing::warn!("Failed to fetch the coverage timeline for '{}': {}", query, e);
+                error_page.set_description(Some(&e.to_string()));
+                stack.set_visible_child_name("error");
+            }
+        }
+    });
+}
+
@@ -0,0 +1,69 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::error::FetchError;
+
+thread_local! {
+    /// Overlay covering the window content that toasts are shown in
+    static TOAST_OVERLAY: RefCell<Option<libadwaita::ToastOverlay>> = const { RefCell::new(None) };
+    /// The error toast currently shown for each source, so a repeat failure replaces it
+    static ERROR_TOASTS: RefCell<HashMap<&'static str, libadwaita::Toast>> = RefCell::new(HashMap::new());
+}
+
+/// Register the window's toast overlay
+pub fn install(overlay: &libadwaita::ToastOverlay) {
+    TOAST_OVERLAY.with(|current| *current.borrow_mut() = Some(overlay.clone()));
+}
+
+/// Show a short message at the bottom of the window
+pub fn show(message: &str) {
+    TOAST_OVERLAY.with(|overlay| {
+        if let Some(ref overlay) = *overlay.borrow() {
+            overlay.add_toast(libadwaita::Toast::new(message));
+        } else {
+            tracing::info!("{}", message);
+        }
+    });
+}
+
+/// Report a failed fetch with a Retry button. Only the latest failure per
+/// service stays on screen, so a flaky service doesn't queue up a pile of
+/// identical toasts.
+pub fn show_error(error: &FetchError, retry: impl Fn() + 'static) {
+    show_failure(error.service(), &error.to_string(), retry);
+}
+
+/// Like `show_error`, for failures that aren't a single fetch, e.g. a stream
+/// that gave up reconnecting
+pub fn show_failure(source: &'static str, title: &str, retry: impl Fn() + 'static) {
+    TOAST_OVERLAY.with(|overlay| {
+        let Some(ref overlay) = *overlay.borrow() else {
+            tracing::warn!("{}", title);
+            return;
+        };
+
+        let toast = libadwaita::Toast::builder()
+            // Titles are Pango markup, and error text from servers isn't
+            .title(gtk::glib::markup_escape_text(title).as_str())
+            .button_label("Retry")
+            .priority(libadwaita::ToastPriority::High)
+            .timeout(8)
+            .build();
+        toast.connect_button_clicked(move |_| retry());
+        toast.connect_dismissed(move |toast| {
+            ERROR_TOASTS.with(|toasts| {
+                let mut toasts = toasts.borrow_mut();
+                if toasts.get(source) == Some(toast) {
+                    toasts.remove(source);
+                }
+            });
+        });
+
+        let previous = ERROR_TOASTS.with(|toasts| toasts.borrow_mut().insert(source, toast.clone()));
+        if let Some(previous) = previous {
+            previous.dismiss();
+        }
+        overlay.add_toast(toast);
+    });
+}
+
@@ -0,0 +1,240 @@
+use gtk::prelude::*;
+use gtk::{glib, Label};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::error::{get_json, FetchError};
+use crate::settings::Settings;
+
+/// Self-hosted translation servers are often small machines
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+/// Translations kept for the session; past this the cache starts over
+const MAX_CACHED: usize = 2_000;
+
+/// Cache key: the text, its source language and the target language
+type CacheKey = (String, String, String);
+
+thread_local! {
+    /// App settings, registered at startup so translate buttons can read the current provider
+    static SETTINGS: RefCell<Option<Rc<RefCell<Settings>>>> = const { RefCell::new(None) };
+    /// Translations already made, so refilled panes don't ask the provider again
+    static CACHE: RefCell<HashMap<CacheKey, String>> = RefCell::new(HashMap::new());
+}
+
+/// Make the app settings available to on-demand translation
+pub fn register_settings(settings: Rc<RefCell<Settings>>) {
+    SETTINGS.with(|current| *current.borrow_mut() = Some(settings));
+}
+
+fn current_config() -> Option<TranslationConfig> {
+    SETTINGS.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .map(|settings| TranslationConfig::from_settings(&settings.borrow()))
+    })
+}
+
+/// Machine translation backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslationProvider {
+    /// A LibreTranslate server (self-hosted or public, optionally with an API key)
+    LibreTranslate,
+    /// A Lingva Translate instance (keyless Google Translate frontend)
+    Lingva,
+}
+
+impl TranslationProvider {
+    pub const ALL: [TranslationProvider; 2] = [TranslationProvider::LibreTranslate, TranslationProvider::Lingva];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TranslationProvider::LibreTranslate => "LibreTranslate",
+            TranslationProvider::Lingva => "Lingva",
+        }
+    }
+}
+
+/// Snapshot of the translation settings, cheap to move into async tasks
+#[derive(Debug, Clone)]
+pub struct TranslationConfig {
+    pub provider: TranslationProvider,
+    pub target: String,
+    pub server_url: String,
+    pub api_key: String,
+}
+
+impl TranslationConfig {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            provider: settings.translation_provider,
+            target: settings.translation_target.trim().to_lowercase(),
+            server_url: settings.translation_server_url.trim().trim_end_matches('/').to_string(),
+            api_key: settings.translation_api_key.trim().to_string(),
+        }
+    }
+
+    /// Whether text tagged with these languages should be translated; untagged
+    /// text is left alone since its language is unknown
+    pub fn needs_translation(&self, langs: &[String]) -> bool {
+        !self.target.is_empty() && !langs.is_empty() && !langs.iter().any(|lang| lang == &self.target)
+    }
+}
+
+#[derive(Serialize)]
+struct LibreTranslateRequest<'a> {
+    q: &'a str,
+    source: &'a str,
+    target: &'a str,
+    format: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    api_key: &'a str,
+}
+
+#[derive(Deserialize)]
+struct LibreTranslateResponse {
+    #[serde(rename = "translatedText")]
+    translated_text: String,
+}
+
+#[derive(Deserialize)]
+struct LingvaResponse {
+    translation: String,
+}
+
+/// Translate text into the configured target language; `source` is an ISO 639-1
+/// code, or None to let the provider detect it
+pub async fn translate(text: &str, source: Option<&str>, config: &TranslationConfig) -> Result<String, FetchError> {
+    let service = config.provider.label();
+    // Nothing to translate, or nowhere to send it
+    if text.trim().is_empty() || config.server_url.is_empty() {
+        return Err(FetchError::Empty { service });
+    }
+
+    let source = source.unwrap_or("auto");
+    let key = (text.to_string(), source.to_string(), config.target.clone());
+    if let Some(translation) = CACHE.with(|cache| cache.borrow().get(&key).cloned()) {
+        return Ok(translation);
+    }
+
+    let translation = request_translation(text, source, config).await?;
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.len() >= MAX_CACHED {
+            cache.clear();
+        }
+        cache.insert(key, translation.clone());
+    });
+    Ok(translation)
+}
+
+async fn request_translation(text: &str, source: &str, config: &TranslationConfig) -> Result<String, FetchError> {
+    let service = config.provider.label();
+    let client = crate::http::client();
+
+    match config.provider {
+        TranslationProvider::LibreTranslate => {
+            let request = client
+                .post(format!("{}/translate", config.server_url))
+                .timeout(TIMEOUT)
+                .json(&LibreTranslateRequest {
+                    q: text,
+                    source,
+                    target: &config.target,
+                    format: "text",
+                    api_key: &config.api_key,
+                });
+            let data: LibreTranslateResponse = get_json(service, request).await?;
+            Ok(data.translated_text)
+        }
+        TranslationProvider::Lingva => {
+            let request = client
+                .get(format!(
+                    "{}/api/v1/{}/{}/{}",
+                    config.server_url,
+                    source,
+                    config.target,
+                    urlencoding::encode(text)
+                ))
+                .timeout(TIMEOUT);
+            let data: LingvaResponse = get_json(service, request).await?;
+            Ok(data.translation)
+        }
+    }
+}
+
+/// Whether a translation server and target language are set up
+pub fn is_configured() -> bool {
+    current_config().is_some_and(|config| !config.server_url.is_empty() && !config.target.is_empty())
+}
+
+/// Whether text in these languages differs from the configured target language
+pub fn is_foreign(langs: &[String]) -> bool {
+    current_config().is_some_and(|config| config.needs_translation(langs))
+}
+
+/// Button that swaps `label` between its original text and a translation,
+/// fetching the translation on first use and remembering it afterwards
+pub fn create_translate_button(label: &Label, source: Option<String>) -> gtk::Button {
+    let button = gtk::Button::builder()
+        .label("Translate")
+        .tooltip_text("Translate into your language")
+        .build();
+    button.add_css_class("badge");
+    button.add_css_class("flat");
+
+    let original = label.label().to_string();
+    let translated: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+    let showing_translation = Rc::new(RefCell::new(false));
+
+    let label = label.clone();
+    button.connect_clicked(move |button| {
+        if *showing_translation.borrow() {
+            label.set_label(&original);
+            button.set_label("Translate");
+            *showing_translation.borrow_mut() = false;
+            return;
+        }
+
+        if let Some(ref text) = *translated.borrow() {
+            label.set_label(text);
+            button.set_label("Show original");
+            *showing_translation.borrow_mut() = true;
+            return;
+        }
+
+        let Some(config) = current_config() else {
+            return;
+        };
+
+        button.set_sensitive(false);
+        button.set_label("Translating…");
+
+        let original = original.clone();
+        let source = source.clone();
+        let label = label.clone();
+        let button = button.clone();
+        let translated = translated.clone();
+        let showing_translation = showing_translation.clone();
+        glib::spawn_future_local(async move {
+            match translate(&original, source.as_deref(), &config).await {
+                Ok(text) => {
+                    label.set_label(&text);
+                    button.set_label("Show original");
+                    *translated.borrow_mut() = Some(text);
+                    *showing_translation.borrow_mut() = true;
+                }
+                Err(e) => {
+                    button.set_label("Translate");
+                    button.set_tooltip_text(Some(&format!("{}, click to retry", e)));
+                }
+            }
+            button.set_sensitive(true);
+        });
+    });
+
+    button
+}
@@ -0,0 +1,286 @@
+use gtk::gio;
+use gtk::glib;
+use gtk::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Seconds between tray tooltip updates
+const UPDATE_INTERVAL_SECS: u32 = 5;
+
+const ITEM_PATH: &str = "/StatusNotifierItem";
+const ITEM_INTERFACE: &str = "org.kde.StatusNotifierItem";
+
+/// The subset of the StatusNotifierItem interface Grapevine implements (no menu)
+const ITEM_XML: &str = r#"
+<node>
+  <interface name="org.kde.StatusNotifierItem">
+    <property name="Category" type="s" access="read"/>
+    <property name="Id" type="s" access="read"/>
+    <property name="Title" type="s" access="read"/>
+    <property name="Status" type="s" access="read"/>
+    <property name="IconName" type="s" access="read"/>
+    <property name="ToolTip" type="(sa(iiay)ss)" access="read"/>
+    <property name="ItemIsMenu" type="b" access="read"/>
+    <method name="Activate">
+      <arg name="x" type="i" direction="in"/>
+      <arg name="y" type="i" direction="in"/>
+    </method>
+    <method name="SecondaryActivate">
+      <arg name="x" type="i" direction="in"/>
+      <arg name="y" type="i" direction="in"/>
+    </method>
+    <method name="ContextMenu">
+      <arg name="x" type="i" direction="in"/>
+      <arg name="y" type="i" direction="in"/>
+    </method>
+    <method name="Scroll">
+      <arg name="delta" type="i" direction="in"/>
+      <arg name="orientation" type="s" direction="in"/>
+    </method>
+    <signal name="NewTitle"/>
+    <signal name="NewToolTip"/>
+  </interface>
+</node>
+"#;
+
+/// Text shown by the tray item, refreshed from the session statistics
+#[derive(Default)]
+struct TrayText {
+    title: String,
+    tooltip: String,
+}
+
+struct Tray {
+    connection: gio::DBusConnection,
+    registration: Option<gio::RegistrationId>,
+    owner: gio::OwnerId,
+}
+
+thread_local! {
+    static TRAY: RefCell<Option<Tray>> = const { RefCell::new(None) };
+    static TEXT: RefCell<TrayText> = RefCell::new(TrayText::default());
+    /// Bumped on every show so update timers from earlier shows stop
+    static GENERATION: Cell<u64> = const { Cell::new(0) };
+    /// Set between show() and hide(), so a hide during bus setup cancels the export
+    static WANTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether the tray item is currently exported
+pub fn is_shown() -> bool {
+    TRAY.with(|tray| tray.borrow().is_some())
+}
+
+/// Find out whether a tray host is running; without a StatusNotifierWatcher
+/// (stock GNOME, for one) an exported item would never be shown
+pub fn check_host(on_result: impl FnOnce(bool) + 'static) {
+    gio::bus_get(gio::BusType::Session, gio::Cancellable::NONE, move |result| {
+        let connection = match result {
+            Ok(connection) => connection,
+            Err(e) => {
+                tracing::warn!("Failed to connect to session bus for tray icon: {}", e);
+                on_result(false);
+                return;
+            }
+        };
+
+        connection.call(
+            Some("org.freedesktop.DBus"),
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+            "NameHasOwner",
+            Some(&("org.kde.StatusNotifierWatcher",).to_variant()),
+            Some(glib::VariantTy::new("(b)").unwrap()),
+            gio::DBusCallFlags::NONE,
+            -1,
+            gio::Cancellable::NONE,
+            move |result| {
+                let available = match result {
+                    Ok(reply) => reply.get::<(bool,)>().is_some_and(|(owned,)| owned),
+                    Err(e) => {
+                        tracing::warn!("Failed to look for a system tray: {}", e);
+                        false
+                    }
+                };
+                on_result(available);
+            },
+        );
+    });
+}
+
+/// Export a StatusNotifierItem showing posts per second and unread alerts;
+/// clicking it calls `on_activate`. Does nothing if already shown.
+pub fn show(on_activate: impl Fn() + 'static) {
+    if WANTED.with(|wanted| wanted.replace(true)) {
+        return;
+    }
+
+    let on_activate: Rc<dyn Fn()> = Rc::new(on_activate);
+
+    gio::bus_get(gio::BusType::Session, gio::Cancellable::NONE, move |result| {
+        let connection = match result {
+            Ok(connection) => connection,
+            Err(e) => {
+                tracing::warn!("Failed to connect to session bus for tray icon: {}", e);
+                WANTED.with(|wanted| wanted.set(false));
+                return;
+            }
+        };
+
+        // Hidden again before the bus connection was ready
+        if !WANTED.with(|wanted| wanted.get()) {
+            return;
+        }
+
+        let registration = match register_item(&connection, on_activate) {
+            Ok(id) => Some(id),
+            Err(e) => {
+                tracing::warn!("Failed to export tray icon: {}", e);
+                WANTED.with(|wanted| wanted.set(false));
+                return;
+            }
+        };
+
+        let name = format!("org.kde.StatusNotifierItem-{}-1", std::process::id());
+        let owner = gio::bus_own_name_on_connection(
+            &connection,
+            &name,
+            gio::BusNameOwnerFlags::NONE,
+            |connection, name| register_with_watcher(&connection, name),
+            |_, name| tracing::warn!("Lost tray bus name {}", name),
+        );
+
+        TRAY.with(|tray| {
+            *tray.borrow_mut() = Some(Tray {
+                connection,
+                registration,
+                owner,
+            })
+        });
+
+        start_updates();
+    });
+}
+
+/// Remove the tray item
+pub fn hide() {
+    WANTED.with(|wanted| wanted.set(false));
+
+    let Some(tray) = TRAY.with(|tray| tray.borrow_mut().take()) else {
+        return;
+    };
+
+    if let Some(registration) = tray.registration {
+        if let Err(e) = tray.connection.unregister_object(registration) {
+            tracing::warn!("Failed to remove tray icon: {}", e);
+        }
+    }
+    gio::bus_unown_name(tray.owner);
+}
+
+fn register_item(connection: &gio::DBusConnection, on_activate: Rc<dyn Fn()>) -> Result<gio::RegistrationId, glib::Error> {
+    let node = gio::DBusNodeInfo::for_xml(ITEM_XML)?;
+    let interface = node
+        .lookup_interface(ITEM_INTERFACE)
+        .ok_or_else(|| glib::Error::new(gio::IOErrorEnum::NotFound, "StatusNotifierItem interface missing"))?;
+
+    connection
+        .register_object(ITEM_PATH, &interface)
+        .method_call(move |_, _, _, _, method, _, invocation| {
+            match method {
+                "Activate" | "SecondaryActivate" => on_activate(),
+                // No menu or scroll behaviour
+                _ => {}
+            }
+            invocation.return_value(None);
+        })
+        .property(|_, _, _, _, property| {
+            TEXT.with(|text| {
+                let text = text.borrow();
+                match property {
+                    "Category" => "ApplicationStatus".to_variant(),
+                    "Id" => "grapevine".to_variant(),
+                    "Title" => text.title.to_variant(),
+                    "Status" => "Active".to_variant(),
+                    "IconName" => "icon-grapevine".to_variant(),
+                    "ToolTip" => (
+                        "icon-grapevine",
+                        Vec::<(i32, i32, Vec<u8>)>::new(),
+                        text.title.as_str(),
+                        text.tooltip.as_str(),
+                    )
+                        .to_variant(),
+                    "ItemIsMenu" => false.to_variant(),
+                    _ => "".to_variant(),
+                }
+            })
+        })
+        .build()
+}
+
+/// Announce the item to the desktop's tray host
+fn register_with_watcher(connection: &gio::DBusConnection, name: &str) {
+    connection.call(
+        Some("org.kde.StatusNotifierWatcher"),
+        "/StatusNotifierWatcher",
+        "org.kde.StatusNotifierWatcher",
+        "RegisterStatusNotifierItem",
+        Some(&(name,).to_variant()),
+        None,
+        gio::DBusCallFlags::NONE,
+        -1,
+        gio::Cancellable::NONE,
+        |result| {
+            if let Err(e) = result {
+                tracing::warn!("No system tray available: {}", e);
+            }
+        },
+    );
+}
+
+/// Refresh the tray text from the session statistics while the item is shown
+fn start_updates() {
+    let generation = GENERATION.with(|current| {
+        current.set(current.get() + 1);
+        current.get()
+    });
+
+    let start = crate::stats::session();
+    let last_posts = RefCell::new(start.posts_seen);
+    let alerts_when_hidden = start.alerts_fired;
+
+    let update = move || {
+        let stats = crate::stats::session();
+        let posts_per_second = (stats.posts_seen - *last_posts.borrow()) as f64 / UPDATE_INTERVAL_SECS as f64;
+        *last_posts.borrow_mut() = stats.posts_seen;
+        let unread_alerts = stats.alerts_fired - alerts_when_hidden;
+
+        TEXT.with(|text| {
+            let mut text = text.borrow_mut();
+            text.title = format!("Grapevine — {:.1} posts/s", posts_per_second);
+            text.tooltip = format!(
+                "{} new disaster alert{} since hidden\nClick to restore",
+                unread_alerts,
+                if unread_alerts == 1 { "" } else { "s" }
+            );
+        });
+
+        TRAY.with(|tray| {
+            if let Some(tray) = tray.borrow().as_ref() {
+                for signal in ["NewTitle", "NewToolTip"] {
+                    if let Err(e) = tray.connection.emit_signal(None, ITEM_PATH, ITEM_INTERFACE, signal, None) {
+                        tracing::warn!("Failed to update tray icon: {}", e);
+                    }
+                }
+            }
+        });
+    };
+
+    update();
+    glib::timeout_add_seconds_local(UPDATE_INTERVAL_SECS, move || {
+        if !is_shown() || GENERATION.with(|current| current.get()) != generation {
+            return glib::ControlFlow::Break;
+        }
+        update();
+        glib::ControlFlow::Continue
+    });
+}
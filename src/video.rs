@@ -0,0 +1,110 @@
+use gtk::prelude::*;
+use gtk::{gio, glib, Label};
+
+use crate::data::{bsky_video_playlist_url, bsky_video_thumbnail_url};
+
+/// Height of an inline video, poster or player
+const VIDEO_HEIGHT: i32 = 220;
+/// Poster frames are decoded no larger than this
+const POSTER_MAX_WIDTH: i32 = 640;
+const POSTER_MAX_HEIGHT: i32 = 360;
+
+/// Inline video for a post: a poster frame with a play button that swaps in a
+/// player on click, muted until the user turns the sound on
+pub fn create_video_embed(did: &str, cid: &str, alt: &str) -> gtk::Box {
+    let container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(4)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+    container.add_css_class("video-embed");
+
+    let poster = gtk::Picture::builder()
+        .height_request(VIDEO_HEIGHT)
+        .hexpand(true)
+        .can_shrink(true)
+        .content_fit(gtk::ContentFit::Cover)
+        .build();
+
+    let play_button = gtk::Button::builder()
+        .icon_name("media-playback-start-symbolic")
+        .tooltip_text("Play video (muted)")
+        .halign(gtk::Align::Center)
+        .valign(gtk::Align::Center)
+        .build();
+    play_button.add_css_class("osd");
+    play_button.add_css_class("circular");
+
+    let overlay = gtk::Overlay::builder()
+        .child(&poster)
+        .build();
+    overlay.add_overlay(&play_button);
+    container.append(&overlay);
+
+    if !alt.is_empty() {
+        let alt_label = Label::builder()
+            .label(alt)
+            .xalign(0.0)
+            .wrap(true)
+            .wrap_mode(gtk::pango::WrapMode::WordChar)
+            .build();
+        alt_label.add_css_class("caption");
+        container.append(&alt_label);
+    }
+
+    // The poster is only fetched once the row is actually shown
+    let thumbnail_url = bsky_video_thumbnail_url(did, cid);
+    let poster_for_map = poster.clone();
+    let loaded = std::cell::Cell::new(false);
+    container.connect_map(move |_| {
+        if loaded.replace(true) {
+            return;
+        }
+        let poster = poster_for_map.clone();
+        let url = thumbnail_url.clone();
+        glib::spawn_future_local(async move {
+            match crate::images::load_texture(&url, POSTER_MAX_WIDTH, POSTER_MAX_HEIGHT).await {
+                Ok(texture) => poster.set_paintable(Some(&texture)),
+                Err(e) => tracing::warn!("No video poster for {}: {}", url, e),
+            }
+        });
+    });
+
+    let playlist_url = bsky_video_playlist_url(did, cid);
+    let overlay_for_click = overlay.clone();
+    play_button.connect_clicked(move |_| {
+        overlay_for_click.set_child(Some(&create_player(&playlist_url)));
+        if let Some(button) = overlay_for_click.last_child().and_downcast::<gtk::Button>() {
+            overlay_for_click.remove_overlay(&button);
+        }
+    });
+
+    container
+}
+
+/// Player for an HLS playlist, started muted; playback goes through GStreamer,
+/// so formats depend on the plugins installed
+fn create_player(playlist_url: &str) -> gtk::Widget {
+    let media = gtk::MediaFile::for_file(&gio::File::for_uri(playlist_url));
+    media.set_muted(true);
+
+    let video = gtk::Video::builder()
+        .media_stream(&media)
+        .height_request(VIDEO_HEIGHT)
+        .hexpand(true)
+        .build();
+
+    let video_for_error = video.clone();
+    media.connect_error_notify(move |media| {
+        if let Some(error) = media.error() {
+            tracing::warn!("Video playback failed: {}", error);
+            video_for_error.set_tooltip_text(Some(&format!("Can't play this video: {}", error.message())));
+        }
+    });
+
+    media.play();
+    video.upcast()
+}
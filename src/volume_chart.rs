@@ -0,0 +1,294 @@
+use gtk::prelude::*;
+use gtk::{Label, Orientation};
+use chrono::NaiveDateTime;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::error::FetchError;
+use crate::gdelt::{self, GdeltQuery, Timespan};
+
+/// How far back the chart reaches when not zoomed in
+const CHART_HOURS: u32 = 24;
+/// Narrowest window the chart zooms to; GDELT won't search less than 15 minutes
+const MIN_WINDOW_MINUTES: i64 = 15;
+/// Window a single click zooms to, centred on where it landed
+const CLICK_WINDOW_MINUTES: i64 = 60;
+/// Drags shorter than this, in pixels, count as a click
+const CLICK_SLOP: f64 = 4.0;
+
+const CHART_HEIGHT: i32 = 72;
+const MARGIN: f64 = 6.0;
+
+/// The chart above the Global Affairs results and the window it's zoomed to
+struct VolumeChart {
+    drawing_area: gtk::DrawingArea,
+    caption: Label,
+    reset_button: gtk::Button,
+    /// Share of global coverage over the past day for `query`, oldest first
+    points: RefCell<Vec<(NaiveDateTime, f64)>>,
+    query: RefCell<String>,
+    /// Window the results are narrowed to, or None for the latest coverage
+    zoom: RefCell<Option<(NaiveDateTime, NaiveDateTime)>>,
+    /// Horizontal extent being dragged across, for drawing the selection
+    selection: RefCell<Option<(f64, f64)>>,
+}
+
+thread_local! {
+    static CHART: RefCell<Option<Rc<VolumeChart>>> = const { RefCell::new(None) };
+}
+
+fn with_chart<T>(f: impl FnOnce(&Rc<VolumeChart>) -> T) -> Option<T> {
+    CHART.with(|chart| chart.borrow().as_ref().map(f))
+}
+
+impl VolumeChart {
+    /// The part of the day being shown: the zoomed window, or all of it
+    fn visible_range(&self) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        if let Some(zoom) = *self.zoom.borrow() {
+            return Some(zoom);
+        }
+        let points = self.points.borrow();
+        Some((points.first()?.0, points.last()?.0))
+    }
+
+    fn time_at(&self, x: f64) -> Option<NaiveDateTime> {
+        let (start, end) = self.visible_range()?;
+        let width = self.drawing_area.width() as f64 - 2.0 * MARGIN;
+        if width <= 0.0 {
+            return None;
+        }
+        let fraction = ((x - MARGIN) / width).clamp(0.0, 1.0);
+        let seconds = (end - start).num_seconds() as f64 * fraction;
+        Some(start + chrono::Duration::seconds(seconds as i64))
+    }
+
+    /// Narrow the results to the window between two points on the chart
+    fn zoom_to(&self, from_x: f64, to_x: f64) {
+        let (Some(mut start), Some(mut end)) = (self.time_at(from_x.min(to_x)), self.time_at(from_x.max(to_x))) else {
+            return;
+        };
+        if (to_x - from_x).abs() < CLICK_SLOP {
+            let half = chrono::Duration::minutes(CLICK_WINDOW_MINUTES / 2);
+            (start, end) = (start - half, start + half);
+        }
+        if (end - start).num_minutes() < MIN_WINDOW_MINUTES {
+            end = start + chrono::Duration::minutes(MIN_WINDOW_MINUTES);
+        }
+        // Nothing is published in the future
+        let now = chrono::Utc::now().naive_utc();
+        if end > now {
+            start -= end - now;
+            end = now;
+        }
+
+        *self.zoom.borrow_mut() = Some((start, end));
+        self.zoom_changed();
+    }
+
+    fn zoom_changed(&self) {
+        let zoom = *self.zoom.borrow();
+        self.reset_button.set_visible(zoom.is_some());
+        self.caption.set_label(&match zoom {
+            Some((start, end)) => format!(
+                "Coverage {} – {} UTC · drag to zoom further",
+                start.format("%b %-d %H:%M"),
+                end.format("%H:%M")
+            ),
+            None => format!("Coverage over the past {} hours · drag or click to zoom", CHART_HOURS),
+        });
+        self.drawing_area.queue_draw();
+
+        // The results are fetched again for the window
+        if let Some(app) = gtk::gio::Application::default() {
+            app.activate_action("refresh", None);
+        }
+    }
+
+    fn draw(&self, cr: &gtk::cairo::Context, width: f64, height: f64) {
+        let Some((start, end)) = self.visible_range() else {
+            return;
+        };
+        let points = self.points.borrow();
+        let visible: Vec<&(NaiveDateTime, f64)> =
+            points.iter().filter(|(at, _)| *at >= start && *at <= end).collect();
+        let span = (end - start).num_seconds().max(1) as f64;
+        let max = visible.iter().map(|(_, value)| *value).fold(0.0, f64::max).max(f64::EPSILON);
+
+        let x_for = |at: NaiveDateTime| MARGIN + (at - start).num_seconds() as f64 / span * (width - 2.0 * MARGIN);
+        let y_for = |value: f64| height - MARGIN - value / max * (height - 2.0 * MARGIN);
+
+        if let Some((from, to)) = *self.selection.borrow() {
+            cr.set_source_rgba(0.21, 0.52, 0.89, 0.15);
+            cr.rectangle(from.min(to), 0.0, (to - from).abs(), height);
+            let _ = cr.fill();
+        }
+
+        let Some((first, rest)) = visible.split_first() else {
+            return;
+        };
+        cr.move_to(x_for(first.0), y_for(first.1));
+        for (at, value) in rest {
+            cr.line_to(x_for(*at), y_for(*value));
+        }
+        cr.set_source_rgba(0.21, 0.52, 0.89, 1.0);
+        cr.set_line_width(1.5);
+        let _ = cr.stroke_preserve();
+
+        // Fill down to the baseline under the line
+        let last = rest.last().unwrap_or(first);
+        cr.line_to(x_for(last.0), height - MARGIN);
+        cr.line_to(x_for(first.0), height - MARGIN);
+        cr.close_path();
+        cr.set_source_rgba(0.21, 0.52, 0.89, 0.15);
+        let _ = cr.fill();
+    }
+}
+
+/// Line chart of the current search's coverage volume. Dragging across it, or
+/// clicking, narrows the results list to that stretch of time.
+pub fn create_volume_chart() -> gtk::Box {
+    let container = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .margin_start(8)
+        .margin_end(8)
+        .visible(false)
+        .build();
+    container.add_css_class("volume-chart");
+
+    let header = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    let caption = Label::builder()
+        .xalign(0.0)
+        .hexpand(true)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build();
+    caption.add_css_class("caption");
+    caption.add_css_class("dim-label");
+    header.append(&caption);
+
+    let reset_button = gtk::Button::builder()
+        .label("Latest")
+        .tooltip_text("Show the latest coverage again")
+        .visible(false)
+        .build();
+    reset_button.add_css_class("flat");
+    reset_button.add_css_class("caption");
+    header.append(&reset_button);
+    container.append(&header);
+
+    let drawing_area = gtk::DrawingArea::builder()
+        .content_height(CHART_HEIGHT)
+        .hexpand(true)
+        .build();
+    drawing_area.set_cursor_from_name(Some("crosshair"));
+    container.append(&drawing_area);
+
+    let chart = Rc::new(VolumeChart {
+        drawing_area: drawing_area.clone(),
+        caption,
+        reset_button: reset_button.clone(),
+        points: RefCell::new(Vec::new()),
+        query: RefCell::new(String::new()),
+        zoom: RefCell::new(None),
+        selection: RefCell::new(None),
+    });
+
+    let chart_for_draw = Rc::downgrade(&chart);
+    drawing_area.set_draw_func(move |_, cr, width, height| {
+        if let Some(chart) = chart_for_draw.upgrade() {
+            chart.draw(cr, width as f64, height as f64);
+        }
+    });
+
+    let drag = gtk::GestureDrag::new();
+    let chart_for_drag = Rc::downgrade(&chart);
+    drag.connect_drag_begin(move |_, x, _| {
+        if let Some(chart) = chart_for_drag.upgrade() {
+            *chart.selection.borrow_mut() = Some((x, x));
+            chart.drawing_area.queue_draw();
+        }
+    });
+    let chart_for_drag = Rc::downgrade(&chart);
+    drag.connect_drag_update(move |gesture, offset_x, _| {
+        let (Some(chart), Some((start_x, _))) = (chart_for_drag.upgrade(), gesture.start_point()) else {
+            return;
+        };
+        *chart.selection.borrow_mut() = Some((start_x, start_x + offset_x));
+        chart.drawing_area.queue_draw();
+    });
+    let chart_for_drag = Rc::downgrade(&chart);
+    drag.connect_drag_end(move |gesture, offset_x, _| {
+        let (Some(chart), Some((start_x, _))) = (chart_for_drag.upgrade(), gesture.start_point()) else {
+            return;
+        };
+        *chart.selection.borrow_mut() = None;
+        chart.zoom_to(start_x, start_x + offset_x);
+    });
+    drawing_area.add_controller(drag);
+
+    let chart_for_reset = Rc::downgrade(&chart);
+    reset_button.connect_clicked(move |_| {
+        if let Some(chart) = chart_for_reset.upgrade() {
+            *chart.zoom.borrow_mut() = None;
+            chart.zoom_changed();
+        }
+    });
+
+    CHART.with(|current| *current.borrow_mut() = Some(chart));
+    container
+}
+
+/// Window the results are narrowed to for a search, if the chart is zoomed in on it.
+/// A different search starts from the latest coverage again.
+pub fn zoom_window(query: &str) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    with_chart(|chart| {
+        if *chart.query.borrow() != query {
+            *chart.zoom.borrow_mut() = None;
+            chart.reset_button.set_visible(false);
+        }
+        *chart.zoom.borrow()
+    })
+    .flatten()
+}
+
+async fn fetch_volume(query: &str, multilingual: bool) -> Result<Vec<(NaiveDateTime, f64)>, FetchError> {
+    let mut gdelt_query = GdeltQuery::new(query)
+        .or_default_terms("world")
+        .timespan(Timespan::hours(CHART_HOURS));
+    if !multilingual {
+        gdelt_query = gdelt_query.language("english");
+    }
+    gdelt::fetch_timeline(&gdelt_query).await
+}
+
+/// Fetch the volume for a search and redraw the chart; it's hidden if GDELT has none
+pub async fn load(query: &str, multilingual: bool) {
+    let Some(chart) = with_chart(Rc::clone) else {
+        return;
+    };
+    *chart.query.borrow_mut() = query.to_string();
+
+    let points = match fetch_volume(query, multilingual).await {
+        Ok(points) => points,
+        Err(e) => {
+            // The list is the main thing; without a chart it's still complete
+            tracing::warn!("No coverage volume for '{}': {}", query, e);
+            Vec::new()
+        }
+    };
+    // A newer search may have started meanwhile
+    if *chart.query.borrow() != query {
+        return;
+    }
+
+    let visible = points.len() >= 2;
+    *chart.points.borrow_mut() = points;
+    if let Some(container) = chart.drawing_area.parent() {
+        container.set_visible(visible);
+    }
+    chart.caption.set_label(&format!("Coverage over the past {} hours · drag or click to zoom", CHART_HOURS));
+    chart.drawing_area.queue_draw();
+}
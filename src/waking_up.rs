@@ -0,0 +1,274 @@
+use chrono::{Datelike, TimeZone, Timelike};
+use gtk::prelude::*;
+use gtk::{glib, Label, Orientation};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::coordinates::{get_country_currency, get_country_timezone};
+use crate::data::GdeltArticle;
+use crate::global_affairs;
+
+/// Local hours counted as the business day, Monday to Friday
+const BUSINESS_DAY_START: u32 = 9;
+const BUSINESS_DAY_END: u32 = 17;
+
+/// 24-hour change of a currency, None once a fetch found nothing
+type CurrencyMoves = Rc<RefCell<HashMap<String, Option<f64>>>>;
+
+/// Where a country is in its business day right now
+struct LocalDay {
+    country: String,
+    now: chrono::DateTime<chrono_tz::Tz>,
+    /// Until the next business day starts; zero while one is under way
+    until_open: chrono::Duration,
+    open: bool,
+}
+
+impl LocalDay {
+    fn for_country(country: &str) -> Option<Self> {
+        let tz: chrono_tz::Tz = get_country_timezone(country)?.parse().ok()?;
+        let now = chrono::Utc::now().with_timezone(&tz);
+        let open = is_business_day(now.weekday())
+            && (BUSINESS_DAY_START..BUSINESS_DAY_END).contains(&now.hour());
+        let until_open = if open { chrono::Duration::zero() } else { until_next_open(now)? };
+        Some(Self { country: country.to_string(), now, until_open, open })
+    }
+
+    /// e.g. "Opens in 2 h 15 min" or "Business hours until 17:00"
+    fn describe(&self) -> String {
+        if self.open {
+            return format!("Business hours until {}:00", BUSINESS_DAY_END);
+        }
+        let minutes = self.until_open.num_minutes();
+        if minutes < 60 {
+            format!("Opens in {} min", minutes)
+        } else if minutes < 24 * 60 {
+            format!("Opens in {} h {} min", minutes / 60, minutes % 60)
+        } else {
+            let opens = self.now + self.until_open;
+            format!("Opens {} {}:00", opens.format("%A"), BUSINESS_DAY_START)
+        }
+    }
+}
+
+fn is_business_day(weekday: chrono::Weekday) -> bool {
+    !matches!(weekday, chrono::Weekday::Sat | chrono::Weekday::Sun)
+}
+
+/// Time from `now` until the start of the next business day in its timezone
+fn until_next_open(now: chrono::DateTime<chrono_tz::Tz>) -> Option<chrono::Duration> {
+    (0..8)
+        .filter_map(|days| now.date_naive().checked_add_days(chrono::Days::new(days)))
+        .filter(|date| is_business_day(date.weekday()))
+        .filter_map(|date| {
+            let start = date.and_hms_opt(BUSINESS_DAY_START, 0, 0)?;
+            now.timezone().from_local_datetime(&start).earliest()
+        })
+        .find(|start| *start > now)
+        .map(|start| start - now)
+}
+
+/// Pinned countries, those in business hours first, then whoever opens next
+fn ordered_days() -> Vec<LocalDay> {
+    let mut days: Vec<LocalDay> = global_affairs::pinned_countries()
+        .iter()
+        .filter_map(|country| LocalDay::for_country(country))
+        .collect();
+    days.sort_by_key(|day| day.until_open);
+    days
+}
+
+fn create_day_row(day: &LocalDay, articles: &[GdeltArticle], use_12_hour: bool, moves: &CurrencyMoves) -> gtk::Box {
+    let row = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(12)
+        .build();
+    row.add_css_class("card");
+    row.add_css_class("waking-up-row");
+    if day.open {
+        row.add_css_class("waking-up-open");
+    }
+
+    let clock_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .width_request(180)
+        .build();
+    let country_label = Label::builder()
+        .label(&day.country)
+        .xalign(0.0)
+        .build();
+    country_label.add_css_class("heading");
+    clock_box.append(&country_label);
+
+    let format = if use_12_hour { "%a %I:%M %p" } else { "%a %H:%M" };
+    let time_label = Label::builder()
+        .label(day.now.format(format).to_string())
+        .xalign(0.0)
+        .build();
+    time_label.add_css_class("title-3");
+    time_label.add_css_class("monospace");
+    clock_box.append(&time_label);
+
+    let status_label = Label::builder()
+        .label(day.describe())
+        .xalign(0.0)
+        .build();
+    status_label.add_css_class("caption");
+    status_label.add_css_class(if day.open { "success" } else { "dim-label" });
+    clock_box.append(&status_label);
+    row.append(&clock_box);
+
+    // Latest headline from the country's outlets in the current results
+    let headline_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .hexpand(true)
+        .valign(gtk::Align::Center)
+        .build();
+    let latest = articles
+        .iter()
+        .filter(|article| article.sourcecountry == day.country)
+        .max_by(|a, b| a.seendate.cmp(&b.seendate));
+    match latest {
+        Some(article) => headline_box.append(&global_affairs::create_popover_article_row(article)),
+        None => {
+            let empty_label = Label::builder()
+                .label("No articles in the current results")
+                .xalign(0.0)
+                .build();
+            empty_label.add_css_class("dim-label");
+            empty_label.add_css_class("caption");
+            headline_box.append(&empty_label);
+        }
+    }
+    row.append(&headline_box);
+
+    if let Some(currency_code) = get_country_currency(&day.country) {
+        row.append(&create_currency_move(currency_code, moves));
+    }
+
+    row
+}
+
+fn show_move(label: &Label, change: Option<f64>, currency_code: &str) {
+    let Some(change) = change else {
+        return;
+    };
+    label.set_label(&format!("{} {}{:.2}%", currency_code, if change > 0.0 { "+" } else { "" }, change));
+    label.remove_css_class("badge-neutral");
+    label.add_css_class(if change > 0.0 {
+        "badge-positive"
+    } else if change < 0.0 {
+        "badge-negative"
+    } else {
+        "badge-neutral"
+    });
+}
+
+/// Badge with the currency's 24-hour move, fetched once per window
+fn create_currency_move(currency_code: &str, moves: &CurrencyMoves) -> Label {
+    let label = Label::builder()
+        .label(currency_code)
+        .valign(gtk::Align::Center)
+        .tooltip_text("Change against USD over the last day")
+        .build();
+    label.add_css_class("badge");
+    label.add_css_class("badge-neutral");
+
+    let cached = moves.borrow().get(currency_code).copied();
+    match cached {
+        Some(change) => show_move(&label, change, currency_code),
+        None => {
+            // Marked as fetched up front so the minute refresh doesn't ask again
+            moves.borrow_mut().insert(currency_code.to_string(), None);
+            load_currency_move(label.clone(), moves.clone(), currency_code.to_string());
+        }
+    }
+
+    label
+}
+
+fn load_currency_move(label: Label, moves: CurrencyMoves, currency_code: String) {
+    glib::spawn_future_local(async move {
+        match global_affairs::fetch_currency_info(&currency_code).await {
+            Ok(info) => {
+                moves.borrow_mut().insert(currency_code.clone(), info.change_24h);
+                show_move(&label, info.change_24h, &currency_code);
+            }
+            Err(e) => {
+                tracing::warn!("No currency move for {}: {}", currency_code, e);
+                crate::toasts::show_error(&e, move || {
+                    load_currency_move(label.clone(), moves.clone(), currency_code.clone());
+                });
+            }
+        }
+    });
+}
+
+fn fill_list(list: &gtk::Box, use_12_hour: bool, moves: &CurrencyMoves) {
+    while let Some(child) = list.first_child() {
+        list.remove(&child);
+    }
+
+    let days = ordered_days();
+    if days.is_empty() {
+        let empty = libadwaita::StatusPage::builder()
+            .icon_name("view-pin-symbolic")
+            .title("No Pinned Countries")
+            .description("Pin countries from their map markers to see who starts the business day next.")
+            .vexpand(true)
+            .build();
+        list.append(&empty);
+        return;
+    }
+
+    let articles = global_affairs::current_articles();
+    for day in &days {
+        list.append(&create_day_row(day, &articles, use_12_hour, moves));
+    }
+}
+
+/// Window listing the pinned countries by when their business day starts,
+/// with each one's top headline and currency move; reorders every minute
+pub fn present_waking_up(parent: Option<&gtk::Window>, use_12_hour: Rc<RefCell<bool>>) {
+    let list = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(8)
+        .margin_top(12)
+        .margin_bottom(12)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+
+    let moves: CurrencyMoves = Rc::new(RefCell::new(HashMap::new()));
+    fill_list(&list, *use_12_hour.borrow(), &moves);
+
+    let list_weak = list.downgrade();
+    glib::timeout_add_seconds_local(60, move || match list_weak.upgrade() {
+        Some(list) => {
+            fill_list(&list, *use_12_hour.borrow(), &moves);
+            glib::ControlFlow::Continue
+        }
+        None => glib::ControlFlow::Break,
+    });
+
+    let scrolled = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .vexpand(true)
+        .child(&list)
+        .build();
+
+    let toolbar_view = libadwaita::ToolbarView::new();
+    toolbar_view.add_top_bar(&libadwaita::HeaderBar::new());
+    toolbar_view.set_content(Some(&scrolled));
+
+    let window = libadwaita::Window::builder()
+        .title("World Waking Up")
+        .default_width(720)
+        .default_height(560)
+        .content(&toolbar_view)
+        .build();
+    window.set_transient_for(parent);
+    window.present();
+}
@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::data::{WikidataClaim, WikidataClaims, WikipediaSummary};
+use crate::error::{get_json, FetchError};
+
+const SERVICE: &str = "Wikipedia";
+const WIKIDATA_SERVICE: &str = "Wikidata";
+
+/// Briefings older than this are fetched again; a stale one is still shown if that fails
+const MAX_AGE_DAYS: i64 = 30;
+
+/// Wikidata properties read for a briefing
+const POPULATION: &str = "P1082";
+const FLAG_IMAGE: &str = "P41";
+/// Qualifier giving the date a statement was true on
+const POINT_IN_TIME: &str = "P585";
+
+/// Width the flag is rendered at by Wikimedia Commons
+const FLAG_WIDTH: u32 = 96;
+
+/// What the country popovers show about a country, from Wikipedia and Wikidata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CountryBriefing {
+    pub title: String,
+    /// Short description, e.g. "Country in Western Europe"
+    #[serde(default)]
+    pub description: String,
+    /// First paragraph of the article
+    #[serde(default)]
+    pub extract: String,
+    #[serde(default)]
+    pub population: Option<u64>,
+    /// Year the population was counted or estimated
+    #[serde(default)]
+    pub population_year: Option<i32>,
+    /// PNG rendering of the flag on Wikimedia Commons
+    #[serde(default)]
+    pub flag_url: Option<String>,
+    #[serde(default)]
+    pub page_url: String,
+    /// Unix time the briefing was fetched
+    pub fetched_at: i64,
+}
+
+impl CountryBriefing {
+    fn is_fresh(&self) -> bool {
+        chrono::Utc::now().timestamp() - self.fetched_at < MAX_AGE_DAYS * 24 * 60 * 60
+    }
+}
+
+thread_local! {
+    /// Briefings by lowercased country name; loaded from disk on first use
+    static BRIEFINGS: RefCell<Option<HashMap<String, CountryBriefing>>> = const { RefCell::new(None) };
+}
+
+fn cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("grapevine").join("country_briefings.json"))
+}
+
+fn load() -> HashMap<String, CountryBriefing> {
+    let Some(path) = cache_path() else {
+        return HashMap::new();
+    };
+
+    match std::fs::read_to_string(&path) {
+        Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+            tracing::warn!("Failed to parse country briefings at {}: {}", path.display(), e);
+            HashMap::new()
+        }),
+        // Nothing cached yet
+        Err(_) => HashMap::new(),
+    }
+}
+
+fn save(briefings: &HashMap<String, CountryBriefing>) {
+    let Some(path) = cache_path() else {
+        return;
+    };
+
+    if let Some(parent) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            tracing::warn!("Failed to create cache directory {}: {}", parent.display(), e);
+            return;
+        }
+    }
+
+    match serde_json::to_string(briefings) {
+        Ok(text) => {
+            if let Err(e) = std::fs::write(&path, text) {
+                tracing::warn!("Failed to write country briefings to {}: {}", path.display(), e);
+            }
+        }
+        Err(e) => tracing::warn!("Failed to serialize country briefings: {}", e),
+    }
+}
+
+fn with_briefings<T>(f: impl FnOnce(&mut HashMap<String, CountryBriefing>) -> T) -> T {
+    BRIEFINGS.with(|briefings| f(briefings.borrow_mut().get_or_insert_with(load)))
+}
+
+/// A country's briefing, from the local cache while it's fresh and from
+/// Wikipedia otherwise
+pub async fn country_briefing(country: &str) -> Result<CountryBriefing, FetchError> {
+    let key = country.to_lowercase();
+    let cached = with_briefings(|briefings| briefings.get(&key).cloned());
+    if let Some(briefing) = cached.as_ref().filter(|briefing| briefing.is_fresh()) {
+        return Ok(briefing.clone());
+    }
+
+    match fetch_briefing(country).await {
+        Ok(briefing) => {
+            with_briefings(|briefings| {
+                briefings.insert(key, briefing.clone());
+                save(briefings);
+            });
+            Ok(briefing)
+        }
+        Err(e) => match cached {
+            Some(briefing) => {
+                tracing::warn!("Showing an old briefing for {}: {}", country, e);
+                Ok(briefing)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+async fn fetch_briefing(country: &str) -> Result<CountryBriefing, FetchError> {
+    let url = format!(
+        "https://en.wikipedia.org/api/rest_v1/page/summary/{}",
+        urlencoding::encode(&country.replace(' ', "_"))
+    );
+    let summary: WikipediaSummary = get_json(SERVICE, crate::http::client().get(&url)).await?;
+    // A name like "Congo" can lead to a list of pages rather than the country
+    if summary.page_type == "disambiguation" || summary.extract.is_empty() {
+        return Err(FetchError::Empty { service: SERVICE });
+    }
+
+    // Population and flag are extras; the summary alone is still worth showing
+    let (mut population, mut population_year, mut flag_url) = (None, None, None);
+    if !summary.wikibase_item.is_empty() {
+        match fetch_claims(&summary.wikibase_item, POPULATION).await {
+            Ok(claims) => {
+                if let Some(claim) = best_claim(&claims) {
+                    population = claim_value(claim)
+                        .and_then(|value| value.get("amount")?.as_str()?.trim_start_matches('+').parse().ok());
+                    population_year = claim_year(claim);
+                }
+            }
+            Err(e) => tracing::warn!("No population for {}: {}", country, e),
+        }
+        match fetch_claims(&summary.wikibase_item, FLAG_IMAGE).await {
+            Ok(claims) => {
+                flag_url = best_claim(&claims)
+                    .and_then(claim_value)
+                    .and_then(|value| value.as_str())
+                    .map(|file| {
+                        format!(
+                            "https://commons.wikimedia.org/wiki/Special:FilePath/{}?width={}",
+                            urlencoding::encode(&file.replace(' ', "_")),
+                            FLAG_WIDTH
+                        )
+                    });
+            }
+            Err(e) => tracing::warn!("No flag for {}: {}", country, e),
+        }
+    }
+
+    Ok(CountryBriefing {
+        title: summary.title,
+        description: summary.description,
+        extract: summary.extract,
+        population,
+        population_year,
+        flag_url,
+        page_url: summary.content_urls.map(|urls| urls.desktop.page).unwrap_or_default(),
+        fetched_at: chrono::Utc::now().timestamp(),
+    })
+}
+
+async fn fetch_claims(item: &str, property: &str) -> Result<Vec<WikidataClaim>, FetchError> {
+    let url = format!(
+        "https://www.wikidata.org/w/api.php?action=wbgetclaims&format=json&entity={}&property={}",
+        urlencoding::encode(item),
+        property
+    );
+    let mut data: WikidataClaims = get_json(WIKIDATA_SERVICE, crate::http::client().get(&url)).await?;
+    Ok(data.claims.remove(property).unwrap_or_default())
+}
+
+fn claim_value(claim: &WikidataClaim) -> Option<&serde_json::Value> {
+    claim.mainsnak.datavalue.as_ref().map(|datavalue| &datavalue.value)
+}
+
+/// Year of a claim's point-in-time qualifier, from a time like "+2024-01-01T00:00:00Z"
+fn claim_year(claim: &WikidataClaim) -> Option<i32> {
+    let time = claim
+        .qualifiers
+        .get(POINT_IN_TIME)?
+        .first()?
+        .datavalue
+        .as_ref()?
+        .value
+        .get("time")?
+        .as_str()?;
+    time.trim_start_matches('+').split('-').next()?.parse().ok()
+}
+
+/// The preferred claim, or else the most recent one; deprecated claims never count
+fn best_claim(claims: &[WikidataClaim]) -> Option<&WikidataClaim> {
+    let current = claims.iter().filter(|claim| claim.rank != "deprecated");
+    claims
+        .iter()
+        .find(|claim| claim.rank == "preferred")
+        .or_else(|| current.max_by_key(|claim| claim_year(claim)))
+}
+
+/// Population in words for large numbers, e.g. "68.4 million"
+pub fn format_population(population: u64) -> String {
+    match population {
+        1_000_000_000.. => format!("{:.2} billion", population as f64 / 1e9),
+        1_000_000.. => format!("{:.1} million", population as f64 / 1e6),
+        _ => {
+            let digits = population.to_string();
+            let mut formatted = String::new();
+            for (i, digit) in digits.chars().enumerate() {
+                if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                    formatted.push(',');
+                }
+                formatted.push(digit);
+            }
+            formatted
+        }
+    }
+}
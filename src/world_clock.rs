@@ -0,0 +1,306 @@
+use gtk::prelude::*;
+use gtk::{glib, Label, Orientation, Popover};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::settings::Settings;
+
+/// Seconds each pinned timezone is shown for when they take turns
+const ROTATE_SECONDS: u32 = 5;
+/// Most search matches listed at once; typing more narrows them down
+const MAX_MATCHES: usize = 50;
+
+/// Pinned timezones shown beside the statusline clock
+struct WorldClock {
+    strip: gtk::Box,
+    pinned_list: gtk::ListBox,
+    settings: Rc<RefCell<Settings>>,
+    use_12_hour: Rc<RefCell<bool>>,
+    /// Pinned timezone shown while rotating
+    turn: Cell<usize>,
+    ticks: Cell<u32>,
+}
+
+thread_local! {
+    static WORLD_CLOCK: RefCell<Option<Rc<WorldClock>>> = const { RefCell::new(None) };
+}
+
+/// "America/Argentina/Buenos_Aires" → "Buenos Aires"
+fn city_name(tz: chrono_tz::Tz) -> String {
+    tz.name().rsplit('/').next().unwrap_or(tz.name()).replace('_', " ")
+}
+
+fn pinned_zones(settings: &Settings) -> Vec<chrono_tz::Tz> {
+    settings.world_clocks.iter().filter_map(|name| name.parse().ok()).collect()
+}
+
+impl WorldClock {
+    fn format_time(&self, tz: chrono_tz::Tz) -> String {
+        let now = chrono::Utc::now().with_timezone(&tz);
+        let format = if *self.use_12_hour.borrow() { "%-I:%M %p" } else { "%H:%M" };
+        format!("{} {}", city_name(tz), now.format(format))
+    }
+
+    /// Rebuild the strip after a timezone is pinned or unpinned, or the layout changes
+    fn rebuild(&self) {
+        while let Some(child) = self.strip.first_child() {
+            self.strip.remove(&child);
+        }
+        let settings = self.settings.borrow();
+        let zones = pinned_zones(&settings);
+        let labels = if settings.rotate_world_clocks { zones.len().min(1) } else { zones.len() };
+        for _ in 0..labels {
+            let label = Label::new(None);
+            label.add_css_class("monospace");
+            label.add_css_class("world-clock");
+            self.strip.append(&label);
+        }
+        self.strip.set_visible(!zones.is_empty());
+        drop(settings);
+
+        self.turn.set(0);
+        self.ticks.set(0);
+        self.update();
+        self.rebuild_pinned_list();
+    }
+
+    /// Set the strip's labels to the current times; called every second
+    fn update(&self) {
+        let settings = self.settings.borrow();
+        let zones = pinned_zones(&settings);
+        if zones.is_empty() {
+            return;
+        }
+
+        if settings.rotate_world_clocks {
+            self.ticks.set(self.ticks.get() + 1);
+            if self.ticks.get() >= ROTATE_SECONDS {
+                self.ticks.set(0);
+                self.turn.set((self.turn.get() + 1) % zones.len());
+            }
+            let tz = zones[self.turn.get() % zones.len()];
+            if let Some(label) = self.strip.first_child().and_downcast::<Label>() {
+                label.set_label(&self.format_time(tz));
+                label.set_tooltip_text(Some(tz.name()));
+            }
+            return;
+        }
+
+        let mut child = self.strip.first_child();
+        for tz in zones {
+            let Some(label) = child.and_downcast::<Label>() else {
+                break;
+            };
+            label.set_label(&self.format_time(tz));
+            label.set_tooltip_text(Some(tz.name()));
+            child = label.next_sibling();
+        }
+    }
+
+    fn rebuild_pinned_list(&self) {
+        while let Some(child) = self.pinned_list.first_child() {
+            self.pinned_list.remove(&child);
+        }
+        let zones = pinned_zones(&self.settings.borrow());
+        self.pinned_list.set_visible(!zones.is_empty());
+        for tz in zones {
+            let row = gtk::Box::builder()
+                .orientation(Orientation::Horizontal)
+                .spacing(6)
+                .margin_top(2)
+                .margin_bottom(2)
+                .margin_start(10)
+                .margin_end(4)
+                .build();
+            let name_label = Label::builder()
+                .label(tz.name())
+                .xalign(0.0)
+                .hexpand(true)
+                .build();
+            row.append(&name_label);
+
+            let remove_button = gtk::Button::builder()
+                .icon_name("list-remove-symbolic")
+                .tooltip_text("Unpin")
+                .build();
+            remove_button.add_css_class("flat");
+            remove_button.set_action_target_value(Some(&tz.name().to_variant()));
+            remove_button.set_action_name(Some("world-clock.unpin"));
+            row.append(&remove_button);
+
+            self.pinned_list.append(&row);
+        }
+    }
+
+    fn set_pinned(&self, name: &str, pinned: bool) {
+        let mut settings = self.settings.borrow_mut();
+        settings.world_clocks.retain(|zone| zone != name);
+        if pinned {
+            settings.world_clocks.push(name.to_string());
+        }
+        settings.save();
+        drop(settings);
+        self.rebuild();
+    }
+}
+
+/// List the timezones whose name contains the search, or nothing for an empty search
+fn fill_matches(matches_list: &gtk::ListBox, search: &str) {
+    while let Some(child) = matches_list.first_child() {
+        matches_list.remove(&child);
+    }
+    let search = search.trim().to_lowercase().replace(' ', "_");
+    if search.is_empty() {
+        return;
+    }
+
+    for tz in chrono_tz::TZ_VARIANTS
+        .iter()
+        .filter(|tz| tz.name().to_lowercase().contains(&search))
+        .take(MAX_MATCHES)
+    {
+        let label = Label::builder()
+            .label(tz.name())
+            .xalign(0.0)
+            .margin_top(4)
+            .margin_bottom(4)
+            .margin_start(6)
+            .build();
+        let row = gtk::ListBoxRow::builder()
+            .child(&label)
+            .build();
+        row.set_widget_name(tz.name());
+        matches_list.append(&row);
+    }
+}
+
+/// Strip of pinned timezones for the statusline, with a button to pin more.
+/// Times follow the same 12/24-hour setting as the main clock.
+pub fn create_world_clock(settings: Rc<RefCell<Settings>>, use_12_hour: Rc<RefCell<bool>>) -> gtk::Box {
+    let container = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .build();
+
+    let strip = gtk::Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    container.append(&strip);
+
+    let popover_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(6)
+        .margin_end(6)
+        .width_request(280)
+        .build();
+
+    let header = Label::builder()
+        .label("World Clock")
+        .xalign(0.0)
+        .build();
+    header.add_css_class("heading");
+    popover_box.append(&header);
+
+    let pinned_list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .build();
+    pinned_list.add_css_class("boxed-list");
+    popover_box.append(&pinned_list);
+
+    let search_entry = gtk::SearchEntry::builder()
+        .placeholder_text("Pin a timezone, e.g. Tokyo")
+        .build();
+    popover_box.append(&search_entry);
+
+    let matches_list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .activate_on_single_click(true)
+        .build();
+    let matches_scroll = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .propagate_natural_height(true)
+        .max_content_height(240)
+        .child(&matches_list)
+        .build();
+    popover_box.append(&matches_scroll);
+
+    let rotate_check = gtk::CheckButton::builder()
+        .label("Show one at a time, in turn")
+        .active(settings.borrow().rotate_world_clocks)
+        .build();
+    popover_box.append(&rotate_check);
+
+    let popover = Popover::builder()
+        .child(&popover_box)
+        .build();
+    let pin_button = gtk::MenuButton::builder()
+        .icon_name("globe-symbolic")
+        .tooltip_text("World clock")
+        .popover(&popover)
+        .build();
+    pin_button.add_css_class("flat");
+    container.append(&pin_button);
+
+    let clock = Rc::new(WorldClock {
+        strip,
+        pinned_list,
+        settings,
+        use_12_hour,
+        turn: Cell::new(0),
+        ticks: Cell::new(0),
+    });
+    clock.rebuild();
+
+    let matches_for_search = matches_list.clone();
+    search_entry.connect_search_changed(move |entry| {
+        fill_matches(&matches_for_search, &entry.text());
+    });
+
+    let clock_for_pin = Rc::downgrade(&clock);
+    let entry_for_pin = search_entry.clone();
+    matches_list.connect_row_activated(move |_, row| {
+        if let Some(clock) = clock_for_pin.upgrade() {
+            clock.set_pinned(row.widget_name().as_str(), true);
+        }
+        entry_for_pin.set_text("");
+    });
+
+    let actions = gtk::gio::SimpleActionGroup::new();
+    let unpin_action = gtk::gio::SimpleAction::new("unpin", Some(glib::VariantTy::STRING));
+    let clock_for_unpin = Rc::downgrade(&clock);
+    unpin_action.connect_activate(move |_, parameter| {
+        let (Some(clock), Some(name)) = (clock_for_unpin.upgrade(), parameter.and_then(|p| p.get::<String>())) else {
+            return;
+        };
+        clock.set_pinned(&name, false);
+    });
+    actions.add_action(&unpin_action);
+    container.insert_action_group("world-clock", Some(&actions));
+
+    let clock_for_rotate = Rc::downgrade(&clock);
+    rotate_check.connect_toggled(move |check| {
+        if let Some(clock) = clock_for_rotate.upgrade() {
+            let mut settings = clock.settings.borrow_mut();
+            settings.rotate_world_clocks = check.is_active();
+            settings.save();
+            drop(settings);
+            clock.rebuild();
+        }
+    });
+
+    let clock_for_timer = Rc::downgrade(&clock);
+    glib::timeout_add_seconds_local(1, move || match clock_for_timer.upgrade() {
+        Some(clock) => {
+            clock.update();
+            glib::ControlFlow::Continue
+        }
+        None => glib::ControlFlow::Break,
+    });
+
+    WORLD_CLOCK.with(|current| *current.borrow_mut() = Some(clock));
+    container
+}